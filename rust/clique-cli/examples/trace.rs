@@ -0,0 +1,34 @@
+//! Run a `clique` command with the core's spans logged to stderr.
+//!
+//! ```bash
+//! cargo run -p clique-cli --example trace --features tracing -- parse sprint-status.yaml
+//! ```
+//!
+//! Each parse, update and diff is logged when it closes, with its timing,
+//! the document size and the item counts.
+
+use clique_cli::{Cli, run};
+use std::process::ExitCode;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+fn main() -> ExitCode {
+    tracing_subscriber::fmt()
+        .with_max_level(LevelFilter::DEBUG)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .init();
+
+    let cli = Cli::try_parse_args_from(std::env::args_os()).unwrap_or_else(|error| error.exit());
+    let cwd = std::env::current_dir().unwrap_or_default();
+    match run(&cli, &cwd) {
+        Ok(output) => {
+            println!("{}", output.trim_end_matches('\n'));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
@@ -0,0 +1,486 @@
+//! Interactive status board (`clique board`, `tui` feature).
+//!
+//! A sprint file is shown as Kanban columns, one per status, and a story
+//! can be moved to the next or previous column; every move goes through
+//! [`write_update`], so it takes the file's lock and is written atomically
+//! like `clique update`. A workflow file is shown read-only, one column per
+//! phase.
+
+use crate::cli::Kind;
+use crate::commands::{CliError, Workspace, read_validated, write_update};
+use clique_core::report::to_kanban;
+use clique_core::{StatusClass, parse_sprint_status, parse_workflow_status};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, List, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::path::{Path, PathBuf};
+
+const SPRINT_HELP: &str = "←/→ column  ↑/↓ story  H/L or </> move story  r reload  q quit";
+const WORKFLOW_HELP: &str = "←/→ phase  ↑/↓ item  r reload  q quit";
+
+/// One column of the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Column {
+    /// Status a story moved here takes; `None` for read-only columns
+    status: Option<String>,
+    title: String,
+    cards: Vec<Card>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Card {
+    id: String,
+    label: String,
+}
+
+/// Board state: the file's columns and the selected card
+#[derive(Debug)]
+pub struct Board {
+    path: String,
+    file: PathBuf,
+    kind: Kind,
+    columns: Vec<Column>,
+    column: usize,
+    row: usize,
+    /// Stories moved so far
+    moves: usize,
+    message: Option<String>,
+}
+
+/// Columns for `content`, read as `kind`
+fn columns(content: &str, kind: Kind, file: &Path) -> Result<Vec<Column>, CliError> {
+    let path = || file.display().to_string();
+    match kind {
+        Kind::Sprint => {
+            let data = parse_sprint_status(content).map_err(|source| CliError::Sprint {
+                path: path(),
+                source,
+            })?;
+            Ok(to_kanban(&data)
+                .columns
+                .into_iter()
+                .map(|column| Column {
+                    title: format!("{} ({})", column.title, column.count),
+                    status: Some(column.status),
+                    cards: column
+                        .cards
+                        .into_iter()
+                        .map(|card| Card {
+                            label: if card.blocked {
+                                format!("! {}", card.id)
+                            } else {
+                                card.id.clone()
+                            },
+                            id: card.id,
+                        })
+                        .collect(),
+                })
+                .collect())
+        }
+        Kind::Workflow => {
+            let data = parse_workflow_status(content).map_err(|source| CliError::Workflow {
+                path: path(),
+                source,
+            })?;
+            let mut columns: Vec<(clique_core::Phase, Column)> = Vec::new();
+            for item in &data.items {
+                let index = match columns.iter().position(|(phase, _)| *phase == item.phase) {
+                    Some(index) => index,
+                    None => {
+                        let column = Column {
+                            status: None,
+                            title: item.phase.title(),
+                            cards: Vec::new(),
+                        };
+                        columns.push((item.phase, column));
+                        columns.len() - 1
+                    }
+                };
+                let mark = match item.status_class() {
+                    StatusClass::Complete => "x",
+                    StatusClass::Pending => " ",
+                    StatusClass::Skipped => "-",
+                };
+                columns[index].1.cards.push(Card {
+                    id: item.id.clone(),
+                    label: format!("[{}] {}", mark, item.id),
+                });
+            }
+            Ok(columns.into_iter().map(|(_, column)| column).collect())
+        }
+    }
+}
+
+impl Board {
+    /// Read `file` and lay it out, selecting the first card.
+    pub fn open(workspace: &Workspace, file: &Path) -> Result<Self, CliError> {
+        let path = workspace.validate(file)?;
+        let content = read_validated(&path, file)?;
+        let kind = Kind::detect(&content);
+        let mut board = Board {
+            columns: columns(&content, kind, file)?,
+            path,
+            file: file.to_path_buf(),
+            kind,
+            column: 0,
+            row: 0,
+            moves: 0,
+            message: None,
+        };
+        board.column = board
+            .columns
+            .iter()
+            .position(|column| !column.cards.is_empty())
+            .unwrap_or(0);
+        Ok(board)
+    }
+
+    /// Re-read the file, keeping the selection on `id` when it is still there
+    fn reload(&mut self, id: Option<String>) -> Result<(), CliError> {
+        let content = read_validated(&self.path, &self.file)?;
+        self.columns = columns(&content, self.kind, &self.file)?;
+        let found = id.and_then(|id| {
+            self.columns.iter().enumerate().find_map(|(c, column)| {
+                let row = column.cards.iter().position(|card| card.id == id)?;
+                Some((c, row))
+            })
+        });
+        if let Some((column, row)) = found {
+            (self.column, self.row) = (column, row);
+        }
+        self.column = self.column.min(self.columns.len().saturating_sub(1));
+        self.clamp_row();
+        Ok(())
+    }
+
+    fn clamp_row(&mut self) {
+        let len = self.columns.get(self.column).map_or(0, |c| c.cards.len());
+        self.row = self.row.min(len.saturating_sub(1));
+    }
+
+    fn select_column(&mut self, delta: isize) {
+        let last = self.columns.len().saturating_sub(1);
+        self.column = self.column.saturating_add_signed(delta).min(last);
+        self.clamp_row();
+    }
+
+    fn select_row(&mut self, delta: isize) {
+        self.row = self.row.saturating_add_signed(delta);
+        self.clamp_row();
+    }
+
+    /// ID of the selected card
+    pub fn selected(&self) -> Option<&str> {
+        let column = self.columns.get(self.column)?;
+        column.cards.get(self.row).map(|card| card.id.as_str())
+    }
+
+    /// Move the selected story `delta` columns, writing the new status.
+    fn move_selected(&mut self, delta: isize) -> Result<(), CliError> {
+        if self.kind == Kind::Workflow {
+            self.message = Some("Workflow items are read-only on the board".to_string());
+            return Ok(());
+        }
+        let Some(id) = self.selected().map(str::to_string) else {
+            return Ok(());
+        };
+        let target = self
+            .column
+            .saturating_add_signed(delta)
+            .min(self.columns.len() - 1);
+        let Some(status) = self.columns[target].status.clone() else {
+            return Ok(());
+        };
+        if target == self.column {
+            return Ok(());
+        }
+        if write_update(&self.path, &self.file, &id, &status, Some(Kind::Sprint))? {
+            self.moves += 1;
+        }
+        self.message = Some(format!("{} -> {}", id, status));
+        self.reload(Some(id))
+    }
+
+    /// Act on a key press; `true` to quit. Failed writes are reported on the
+    /// status line rather than closing the board.
+    pub fn handle(&mut self, key: KeyEvent) -> bool {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let result = match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return true,
+            KeyCode::Left if shift => self.move_selected(-1),
+            KeyCode::Right if shift => self.move_selected(1),
+            KeyCode::Char('H' | '<') => self.move_selected(-1),
+            KeyCode::Char('L' | '>') => self.move_selected(1),
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.select_column(-1);
+                Ok(())
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.select_column(1);
+                Ok(())
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_row(-1);
+                Ok(())
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_row(1);
+                Ok(())
+            }
+            KeyCode::Char('r') => {
+                self.message = Some("Reloaded".to_string());
+                let id = self.selected().map(str::to_string);
+                self.reload(id)
+            }
+            _ => Ok(()),
+        };
+        if let Err(error) = result {
+            self.message = Some(error.to_string());
+        }
+        false
+    }
+
+    /// Draw the columns and the status line.
+    pub fn draw(&self, frame: &mut Frame) {
+        let [body, footer] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let count = self.columns.len().max(1) as u32;
+        let areas = Layout::horizontal(self.columns.iter().map(|_| Constraint::Ratio(1, count)))
+            .split(body);
+
+        for (index, (column, area)) in self.columns.iter().zip(areas.iter()).enumerate() {
+            let selected = index == self.column;
+            let border = if selected {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            let list = List::new(column.cards.iter().map(|card| card.label.as_str()))
+                .block(
+                    Block::bordered()
+                        .title(column.title.as_str())
+                        .border_style(border),
+                )
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            let mut state = ListState::default();
+            if selected && !column.cards.is_empty() {
+                state.select(Some(self.row));
+            }
+            frame.render_stateful_widget(list, *area, &mut state);
+        }
+
+        let help = match self.kind {
+            Kind::Sprint => SPRINT_HELP,
+            Kind::Workflow => WORKFLOW_HELP,
+        };
+        let line = self.message.as_deref().unwrap_or(help);
+        frame.render_widget(Paragraph::new(line), footer);
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && self.handle(key)
+            {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Show `file` as a board until the user quits, returning a summary.
+pub fn run(workspace: &Workspace, file: &Path) -> Result<String, CliError> {
+    let mut board = Board::open(workspace, file)?;
+    let mut terminal = ratatui::init();
+    let result = board.event_loop(&mut terminal);
+    ratatui::restore();
+    result.map_err(CliError::Terminal)?;
+    Ok(format!(
+        "{} stor{} moved in {}\n",
+        board.moves,
+        if board.moves == 1 { "y" } else { "ies" },
+        file.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use std::fs;
+
+    const SPRINT: &str = "development_status:\n  epic-1: in-progress\n  1-a: backlog\n  1-b: backlog\n  \
+         1-c: review\n";
+    const WORKFLOW: &str = "workflow_status:\n  brainstorm: skipped\n  prd: docs/prd.md\n  \
+                            architecture: required\n";
+
+    fn open(name: &str, content: &str) -> (TempDir, Board) {
+        let dir = TempDir::new(&format!("board-{}", name));
+        fs::write(dir.join("status.yaml"), content).unwrap();
+        let board = Board::open(&Workspace::new(&dir, None), Path::new("status.yaml")).unwrap();
+        (dir, board)
+    }
+
+    fn press(board: &mut Board, code: KeyCode) -> bool {
+        board.handle(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    /// Everything drawn, row by row
+    fn screen(board: &Board) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 8)).unwrap();
+        terminal.draw(|frame| board.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        buffer
+            .content()
+            .chunks(width)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // =========================================================================
+    // Layout Tests
+    // =========================================================================
+
+    #[test]
+    fn test_sprint_columns() {
+        let (_dir, board) = open("sprint", SPRINT);
+        let titles: Vec<&str> = board.columns.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Backlog (2)",
+                "Drafted (0)",
+                "Ready for Dev (0)",
+                "In Progress (0)",
+                "Review (1)",
+                "Done (0)"
+            ]
+        );
+        assert_eq!(board.selected(), Some("1-a"));
+    }
+
+    #[test]
+    fn test_workflow_columns() {
+        let (_dir, board) = open("workflow", WORKFLOW);
+        let column = &board.columns[0];
+        assert_eq!(column.status, None);
+        let labels: Vec<&str> = board
+            .columns
+            .iter()
+            .flat_map(|c| &c.cards)
+            .map(|card| card.label.as_str())
+            .collect();
+        assert!(labels.contains(&"[-] brainstorm"));
+        assert!(labels.contains(&"[x] prd"));
+        assert!(labels.contains(&"[ ] architecture"));
+    }
+
+    #[test]
+    fn test_draw() {
+        let (_dir, board) = open("draw", SPRINT);
+        let text = screen(&board);
+        assert!(text.contains("Backlog (2)"));
+        assert!(text.contains("1-a"));
+        assert!(text.contains("1-c"));
+        assert!(text.contains("q quit"));
+    }
+
+    // =========================================================================
+    // Key Tests
+    // =========================================================================
+
+    #[test]
+    fn test_navigation() {
+        let (_dir, mut board) = open("navigate", SPRINT);
+        press(&mut board, KeyCode::Down);
+        assert_eq!(board.selected(), Some("1-b"));
+        press(&mut board, KeyCode::Down);
+        assert_eq!(board.selected(), Some("1-b"));
+        press(&mut board, KeyCode::Left);
+        assert_eq!(board.column, 0);
+        for _ in 0..4 {
+            press(&mut board, KeyCode::Char('l'));
+        }
+        assert_eq!(board.selected(), Some("1-c"));
+        press(&mut board, KeyCode::Right);
+        press(&mut board, KeyCode::Right);
+        assert_eq!(board.column, 5);
+        assert_eq!(board.selected(), None);
+
+        assert!(!press(&mut board, KeyCode::Char('x')));
+        assert!(press(&mut board, KeyCode::Char('q')));
+        assert!(board.handle(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn test_move_writes_status() {
+        let (dir, mut board) = open("move", SPRINT);
+        press(&mut board, KeyCode::Down);
+        assert!(!press(&mut board, KeyCode::Char('L')));
+        assert_eq!(board.selected(), Some("1-b"));
+        assert_eq!(board.column, 1);
+        assert_eq!(board.moves, 1);
+        assert_eq!(board.message.as_deref(), Some("1-b -> drafted"));
+        let content = fs::read_to_string(dir.join("status.yaml")).unwrap();
+        assert!(content.contains("1-b: drafted"));
+
+        board.handle(KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT));
+        assert_eq!(board.column, 0);
+        assert!(
+            fs::read_to_string(dir.join("status.yaml"))
+                .unwrap()
+                .contains("1-b: backlog")
+        );
+
+        // Already in the first column
+        press(&mut board, KeyCode::Char('<'));
+        assert_eq!(board.moves, 2);
+    }
+
+    #[test]
+    fn test_reload_and_errors() {
+        let (dir, mut board) = open("reload", SPRINT);
+        fs::write(
+            dir.join("status.yaml"),
+            "development_status:\n  epic-1: backlog\n  1-a: done\n",
+        )
+        .unwrap();
+        press(&mut board, KeyCode::Char('r'));
+        assert_eq!(board.selected(), Some("1-a"));
+        assert_eq!(board.column, 5);
+
+        fs::write(dir.join("status.yaml"), "[oops").unwrap();
+        press(&mut board, KeyCode::Char('r'));
+        assert!(
+            board
+                .message
+                .as_deref()
+                .unwrap()
+                .starts_with("status.yaml: ")
+        );
+    }
+
+    #[test]
+    fn test_workflow_is_read_only() {
+        let (dir, mut board) = open("read-only", WORKFLOW);
+        press(&mut board, KeyCode::Char('L'));
+        assert_eq!(board.moves, 0);
+        assert_eq!(
+            board.message.as_deref(),
+            Some("Workflow items are read-only on the board")
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("status.yaml")).unwrap(),
+            WORKFLOW
+        );
+    }
+}
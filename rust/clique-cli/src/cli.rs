@@ -0,0 +1,382 @@
+//! Command-line arguments.
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clique_core::WorkflowFormat;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Read and update workflow and sprint status files
+#[derive(Parser, Debug, Clone, PartialEq, Eq)]
+#[command(name = "clique", version)]
+pub struct Cli {
+    /// Workspace root; files outside it are refused [default: current directory]
+    #[arg(long, global = true, value_name = "DIR")]
+    pub root: Option<PathBuf>,
+
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Print a JSON description of every command and option, then exit
+    #[arg(long, exclusive = true)]
+    pub schema: bool,
+
+    /// Required unless `--schema` is given
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// Parse `args` (program name first), as [`Parser::try_parse_from`]
+    /// but requiring a command unless `--schema` is given.
+    pub fn try_parse_args_from<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let cli = Self::try_parse_from(args)?;
+        if cli.command.is_none() && !cli.schema {
+            let mut command = Self::command();
+            let help = command.render_help();
+            return Err(command.error(ErrorKind::MissingSubcommand, help));
+        }
+        Ok(cli)
+    }
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Print the items or stories in a status file
+    Parse {
+        file: PathBuf,
+        /// File kind, when it cannot be told from the content
+        #[arg(long, value_enum)]
+        kind: Option<Kind>,
+    },
+    /// Set the status of a workflow item or story
+    Update {
+        file: PathBuf,
+        /// Workflow item or story ID
+        id: String,
+        status: String,
+        /// Print the change as a diff without writing it
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long, value_enum)]
+        kind: Option<Kind>,
+    },
+    /// Report problems in status files; fails when any is an error
+    Validate {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Check status files against the lint rules configured in
+    /// .clique.toml; fails when any finding is an error
+    Lint {
+        /// Status files [default: bmm-workflow-status.yaml and
+        /// sprint-status.yaml in the root or its docs folder]
+        files: Vec<PathBuf>,
+    },
+    /// Count items and stories by status
+    Stats {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Rewrite a workflow file in another layout; prints the result unless
+    /// --in-place or --dry-run is given
+    Migrate {
+        file: PathBuf,
+        /// Target layout
+        #[arg(long, value_enum)]
+        to: Format,
+        /// Write the result back to the file
+        #[arg(long, conflicts_with = "dry_run")]
+        in_place: bool,
+        /// Print the change as a diff, and any fields that would be lost
+        #[arg(long)]
+        dry_run: bool,
+        /// Migrate even when the target layout cannot hold every field
+        #[arg(long)]
+        force: bool,
+    },
+    /// Gate a pipeline: fail unless required workflow steps are complete and
+    /// work in progress is within its limit
+    Check {
+        /// Status files [default: bmm-workflow-status.yaml and
+        /// sprint-status.yaml in the root or its docs folder]
+        files: Vec<PathBuf>,
+        /// Workflow item IDs that must be complete, comma-separated
+        #[arg(long, value_delimiter = ',', value_name = "IDS")]
+        require_complete: Vec<String>,
+        /// Most stories allowed in progress at once
+        #[arg(long, value_name = "N")]
+        max_in_progress: Option<usize>,
+    },
+    /// Browse a status file as a board and move stories between statuses
+    #[cfg(feature = "tui")]
+    Board {
+        /// Status file [default: sprint-status.yaml, else
+        /// bmm-workflow-status.yaml, in the root or its docs folder]
+        file: Option<PathBuf>,
+    },
+    /// Re-validate and print changes whenever a status file changes
+    #[cfg(feature = "watch")]
+    Watch {
+        /// Status files [default: bmm-workflow-status.yaml and
+        /// sprint-status.yaml in the root or its docs folder]
+        files: Vec<PathBuf>,
+        /// Shell command to run after each change; the changed file's path
+        /// is in CLIQUE_CHANGED_FILE
+        #[arg(long, value_name = "CMD")]
+        exec: Option<String>,
+    },
+    /// List the items or stories that changed between two versions of a file
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        #[arg(long, value_enum)]
+        kind: Option<Kind>,
+    },
+    /// Print a shell completion script, e.g.
+    /// `clique completions bash > /etc/bash_completion.d/clique`
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+/// Which status file format to read
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// bmm-workflow-status.yaml
+    Workflow,
+    /// sprint-status.yaml
+    Sprint,
+}
+
+/// Workflow file layout
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `workflows:` mapping of items with nested fields
+    #[value(alias = "new-format")]
+    New,
+    /// `workflow_status:` mapping of `id: status` pairs
+    #[value(alias = "flat-format")]
+    Flat,
+    /// `workflow_status:` sequence of items
+    #[value(alias = "old-format")]
+    Old,
+}
+
+/// Shell to generate completions for
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    #[value(alias = "pwsh")]
+    Powershell,
+}
+
+impl From<Shell> for clap_complete::Shell {
+    fn from(shell: Shell) -> Self {
+        match shell {
+            Shell::Bash => clap_complete::Shell::Bash,
+            Shell::Zsh => clap_complete::Shell::Zsh,
+            Shell::Fish => clap_complete::Shell::Fish,
+            Shell::Powershell => clap_complete::Shell::PowerShell,
+        }
+    }
+}
+
+impl From<Format> for WorkflowFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::New => WorkflowFormat::New,
+            Format::Flat => WorkflowFormat::Flat,
+            Format::Old => WorkflowFormat::Old,
+        }
+    }
+}
+
+impl Kind {
+    /// Tell the kind from a file's content: sprint files have a top-level
+    /// `development_status` key, anything else is read as a workflow.
+    pub fn detect(content: &str) -> Self {
+        if content
+            .lines()
+            .any(|line| line.starts_with("development_status:"))
+        {
+            Kind::Sprint
+        } else {
+            Kind::Workflow
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_args_from(std::iter::once("clique").chain(args.iter().copied())).unwrap()
+    }
+
+    // =========================================================================
+    // Argument Tests
+    // =========================================================================
+
+    #[test]
+    fn test_global_options() {
+        let cli = parse(&["stats", "a.yaml", "--json", "--root", "/ws"]);
+        assert!(cli.json);
+        assert_eq!(cli.root, Some(PathBuf::from("/ws")));
+        assert_eq!(
+            cli.command,
+            Some(Command::Stats {
+                files: vec![PathBuf::from("a.yaml")]
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_arguments() {
+        let cli = parse(&[
+            "update",
+            "s.yaml",
+            "1-a",
+            "done",
+            "--dry-run",
+            "--kind",
+            "sprint",
+        ]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Update {
+                file: PathBuf::from("s.yaml"),
+                id: "1-a".to_string(),
+                status: "done".to_string(),
+                dry_run: true,
+                kind: Some(Kind::Sprint),
+            })
+        );
+        assert!(!cli.json);
+        assert_eq!(cli.root, None);
+    }
+
+    #[test]
+    fn test_check_arguments() {
+        let cli = parse(&[
+            "check",
+            "--require-complete",
+            "prd,architecture",
+            "--max-in-progress",
+            "3",
+        ]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Check {
+                files: vec![],
+                require_complete: vec!["prd".to_string(), "architecture".to_string()],
+                max_in_progress: Some(3),
+            })
+        );
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn test_watch_arguments() {
+        let cli = parse(&["watch", "s.yaml", "--exec", "make report"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Watch {
+                files: vec![PathBuf::from("s.yaml")],
+                exec: Some("make report".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_migrate_arguments() {
+        let cli = parse(&["migrate", "w.yaml", "--to", "new-format", "--in-place"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Migrate {
+                file: PathBuf::from("w.yaml"),
+                to: Format::New,
+                in_place: true,
+                dry_run: false,
+                force: false,
+            })
+        );
+        let cli = parse(&["migrate", "w.yaml", "--to", "flat", "--dry-run"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Migrate {
+                to: Format::Flat,
+                ..
+            })
+        ));
+        assert_eq!(WorkflowFormat::from(Format::Old), WorkflowFormat::Old);
+    }
+
+    #[test]
+    fn test_schema_and_completions_arguments() {
+        let cli = parse(&["--schema"]);
+        assert!(cli.schema);
+        assert_eq!(cli.command, None);
+        let cli = parse(&["completions", "pwsh"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Completions {
+                shell: Shell::Powershell
+            })
+        );
+        assert!(Cli::try_parse_args_from(["clique", "--schema", "stats"]).is_err());
+        assert!(Cli::try_parse_args_from(["clique", "completions", "tcsh"]).is_err());
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        for args in [
+            vec!["clique"],
+            vec!["clique", "validate"],
+            vec!["clique", "update", "s.yaml", "1-a"],
+            vec!["clique", "parse", "s.yaml", "--kind", "epics"],
+            vec!["clique", "check", "--max-in-progress", "-1"],
+            vec!["clique", "migrate", "w.yaml"],
+            vec![
+                "clique",
+                "migrate",
+                "w.yaml",
+                "--to",
+                "new",
+                "--in-place",
+                "--dry-run",
+            ],
+        ] {
+            assert!(Cli::try_parse_args_from(args).is_err());
+        }
+    }
+
+    // =========================================================================
+    // Kind Tests
+    // =========================================================================
+
+    #[test]
+    fn test_detect_kind() {
+        assert_eq!(
+            Kind::detect("# sprint\ndevelopment_status:\n  epic-1: backlog\n"),
+            Kind::Sprint
+        );
+        assert_eq!(
+            Kind::detect("workflow_status:\n  prd: required\n"),
+            Kind::Workflow
+        );
+        assert_eq!(
+            Kind::detect("  development_status: nested\n"),
+            Kind::Workflow
+        );
+    }
+}
@@ -0,0 +1,1239 @@
+//! Running a parsed command line.
+//!
+//! Every file is resolved against the working directory and checked with
+//! the workspace's [`PathPolicy`](clique_core::PathPolicy) before it is read
+//! or written, so the CLI is held to the same workspace boundary as the
+//! extension. The policy, status aliases, phases, agents, lint levels and
+//! default file locations come from `.clique.toml` in the root.
+
+use crate::cli::{Cli, Command, Kind, Shell};
+use crate::render;
+use clap::CommandFactory;
+use clique_core::diagnostics::{Diagnostic, Severity};
+use clique_core::edit::unified_diff;
+use clique_core::io::DEFAULT_LOCK_TIMEOUT;
+use clique_core::lint::LintReport;
+use clique_core::workflow::LossyField;
+use clique_core::{
+    Config, ConfigError, FormatRegistry, GatePolicy, IoError, Migration, PathError, ProjectSet,
+    SprintError, WorkflowError, WorkflowFormat, config, diff_sprint, diff_workflow, gate,
+    migrate_workflow, parse_sprint_status, parse_sprint_status_with_options, parse_workflow_status,
+    sprint_diagnostics, update_story_status, with_lock, workflow_diagnostics, write_atomic,
+};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error(transparent)]
+    Path(#[from] PathError),
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("{path}: {source}")]
+    Workflow { path: String, source: WorkflowError },
+    #[error("{path}: {source}")]
+    Sprint { path: String, source: SprintError },
+    #[error("{path} is not a {expected} file")]
+    WrongKind {
+        path: String,
+        expected: &'static str,
+    },
+    #[error("Migrating {path} would lose {}; pass --force to migrate anyway", .fields.join(", "))]
+    Lossy { path: String, fields: Vec<String> },
+    #[error("Cannot compare a workflow file with a sprint file: {old} and {new}")]
+    KindMismatch { old: String, new: String },
+    #[error("More than one {kind} file: {first} and {second}")]
+    DuplicateKind {
+        kind: &'static str,
+        first: String,
+        second: String,
+    },
+    #[error("No {0} file found; pass one to check against")]
+    MissingFile(&'static str),
+    #[cfg(feature = "watch")]
+    #[error(transparent)]
+    Watch(clique_core::WatchError),
+    #[cfg(feature = "tui")]
+    #[error("Terminal error: {0}")]
+    Terminal(std::io::Error),
+    #[error("Failed to serialize output: {0}")]
+    Serialize(#[from] serde_json::Error),
+    /// The command ran; its output is the report and the check did not pass
+    #[error("{0}")]
+    CheckFailed(String),
+}
+
+impl CliError {
+    /// Process exit code: 1 when a check failed, 2 when the command could not run
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::CheckFailed(_) => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// Outcome of `update`, for `--json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReport {
+    pub path: String,
+    pub id: String,
+    pub status: String,
+    /// Whether the file content differs after the update
+    pub changed: bool,
+    /// The change as a unified diff, for `--dry-run`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// Outcome of `migrate`, for `--json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrateReport {
+    pub path: String,
+    pub from: WorkflowFormat,
+    pub to: WorkflowFormat,
+    /// Whether the file was rewritten
+    pub written: bool,
+    pub lossy_fields: Vec<LossyField>,
+    /// The change as a unified diff, for `--dry-run`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// The migrated document, when neither `--in-place` nor `--dry-run` is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// Diagnostics for one file, for `--json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiagnostics {
+    pub path: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// File names `check` looks for when given none
+const DEFAULT_FILE_NAMES: [&str; 2] = ["bmm-workflow-status.yaml", "sprint-status.yaml"];
+
+/// Where relative paths start, which directory files must stay inside and
+/// the project's `.clique.toml`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    cwd: PathBuf,
+    root: String,
+    config: Config,
+}
+
+impl Workspace {
+    /// `root` defaults to `cwd`; a relative root is taken from `cwd`. The
+    /// configuration is the default until [`Workspace::load_config`].
+    pub fn new(cwd: &Path, root: Option<&Path>) -> Self {
+        let root = root.map_or_else(|| cwd.to_path_buf(), |root| cwd.join(root));
+        Self {
+            cwd: cwd.to_path_buf(),
+            root: root.to_string_lossy().into_owned(),
+            config: Config::default(),
+        }
+    }
+
+    /// Read `.clique.toml` from the root, if there is one.
+    pub fn load_config(mut self) -> Result<Self, CliError> {
+        self.config = config::load(&self.root)?;
+        Ok(self)
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Absolute path of `file`, refused when it lies outside the root or the
+    /// configured path policy does not accept it
+    pub fn validate(&self, file: &Path) -> Result<String, CliError> {
+        let path = self.cwd.join(file);
+        let policy = self.config.path_policy(&self.root);
+        Ok(policy.validate(&path.to_string_lossy())?.into_string())
+    }
+
+    /// Status files at their configured locations, else with their usual
+    /// names in the root or its `docs` folder
+    pub fn default_status_files(&self) -> Vec<PathBuf> {
+        let root = Path::new(&self.root);
+        let files = &self.config.files;
+        [&files.workflow, &files.sprint]
+            .into_iter()
+            .zip(DEFAULT_FILE_NAMES)
+            .flat_map(|(configured, name)| match configured {
+                Some(path) => vec![root.join(path)],
+                None => ["", "docs"]
+                    .iter()
+                    .map(|dir| root.join(dir).join(name))
+                    .collect(),
+            })
+            .filter(|path| path.is_file())
+            .collect()
+    }
+
+    /// Validate `file` and read it
+    pub fn read(&self, file: &Path) -> Result<String, CliError> {
+        read_validated(&self.validate(file)?, file)
+    }
+}
+
+pub(crate) fn read_validated(path: &str, shown: &Path) -> Result<String, CliError> {
+    fs::read_to_string(path).map_err(|source| CliError::Read {
+        path: shown.display().to_string(),
+        source,
+    })
+}
+
+/// Run `cli` from `cwd`, returning what to print.
+pub fn run(cli: &Cli, cwd: &Path) -> Result<String, CliError> {
+    let json = cli.json;
+    let Some(command) = &cli.command else {
+        return Ok(serde_json::to_string_pretty(&crate::schema::schema())?);
+    };
+    let workspace = Workspace::new(cwd, cli.root.as_deref()).load_config()?;
+    match command {
+        Command::Parse { file, kind } => parse(&workspace, file, *kind, json),
+        Command::Update {
+            file,
+            id,
+            status,
+            dry_run,
+            kind,
+        } => update(&workspace, file, id, status, *kind, *dry_run, json),
+        Command::Migrate {
+            file,
+            to,
+            in_place,
+            dry_run,
+            force,
+        } => {
+            let mode = match (*in_place, *dry_run) {
+                (_, true) => MigrateMode::DryRun,
+                (true, false) => MigrateMode::InPlace,
+                (false, false) => MigrateMode::Print,
+            };
+            migrate(&workspace, file, (*to).into(), mode, *force, json)
+        }
+        Command::Validate { files } => validate(&workspace, files, json),
+        Command::Lint { files } => lint(&workspace, files, json),
+        Command::Stats { files } => stats(&workspace, files, json),
+        Command::Check {
+            files,
+            require_complete,
+            max_in_progress,
+        } => {
+            let policy = GatePolicy {
+                require_complete: require_complete.clone(),
+                max_in_progress: *max_in_progress,
+            };
+            check(&workspace, files, &policy, json)
+        }
+        Command::Diff { old, new, kind } => diff(&workspace, old, new, *kind, json),
+        Command::Completions { shell } => Ok(completions(*shell)),
+        #[cfg(feature = "watch")]
+        Command::Watch { files, exec } => {
+            let files = if files.is_empty() {
+                workspace.default_status_files()
+            } else {
+                files.clone()
+            };
+            if files.is_empty() {
+                return Err(CliError::MissingFile("status"));
+            }
+            crate::watch::Watch::new(&workspace, &files, exec.clone(), json)?.run()
+        }
+        #[cfg(feature = "tui")]
+        Command::Board { file } => {
+            let file = match file {
+                Some(file) => file.clone(),
+                None => workspace
+                    .default_status_files()
+                    .into_iter()
+                    .min_by_key(|path| !path.ends_with("sprint-status.yaml"))
+                    .ok_or(CliError::MissingFile("status"))?,
+            };
+            crate::board::run(&workspace, &file)
+        }
+    }
+}
+
+/// A status file read and parsed as its kind
+enum Document {
+    Workflow(clique_core::WorkflowData),
+    Sprint(clique_core::SprintData),
+}
+
+/// Read and parse `file` with the configured aliases, phases and agents;
+/// workflows may be in any dialect of [`FormatRegistry::new`]
+fn load(workspace: &Workspace, file: &Path, kind: Option<Kind>) -> Result<Document, CliError> {
+    let content = workspace.read(file)?;
+    let path = file.display().to_string();
+    let settings = workspace.config.settings();
+    match kind.unwrap_or_else(|| Kind::detect(&content)) {
+        Kind::Workflow => FormatRegistry::new()
+            .parse_with_options(&content, &settings.parse_options())
+            .map(|mut data| {
+                settings.apply(&mut data);
+                Document::Workflow(data)
+            })
+            .map_err(|source| CliError::Workflow { path, source }),
+        Kind::Sprint => parse_sprint_status_with_options(&content, &settings.parse_options())
+            .map(Document::Sprint)
+            .map_err(|source| CliError::Sprint { path, source }),
+    }
+}
+
+fn parse(
+    workspace: &Workspace,
+    file: &Path,
+    kind: Option<Kind>,
+    json: bool,
+) -> Result<String, CliError> {
+    Ok(match (load(workspace, file, kind)?, json) {
+        (Document::Workflow(data), true) => serde_json::to_string_pretty(&data)?,
+        (Document::Sprint(data), true) => serde_json::to_string_pretty(&data)?,
+        (Document::Workflow(data), false) => render::workflow(&data),
+        (Document::Sprint(data), false) => render::sprint(&data),
+    })
+}
+
+/// `content` with one status changed, per the file's kind and dialect
+fn apply_update(
+    content: &str,
+    file: &Path,
+    id: &str,
+    status: &str,
+    kind: Option<Kind>,
+) -> Result<String, CliError> {
+    let path = file.display().to_string();
+    match kind.unwrap_or_else(|| Kind::detect(content)) {
+        Kind::Workflow => FormatRegistry::new()
+            .update(content, id, status)
+            .map_err(|source| CliError::Workflow { path, source }),
+        Kind::Sprint => update_story_status(content, id, status)
+            .map_err(|source| CliError::Sprint { path, source }),
+    }
+}
+
+/// Set one status in the validated file at `path`, holding the file's lock
+/// from read to write; `true` when the content changed.
+pub(crate) fn write_update(
+    path: &str,
+    file: &Path,
+    id: &str,
+    status: &str,
+    kind: Option<Kind>,
+) -> Result<bool, CliError> {
+    with_lock(path, DEFAULT_LOCK_TIMEOUT, || {
+        let content = read_validated(path, file)?;
+        let updated = apply_update(&content, file, id, status, kind)?;
+        if updated != content {
+            write_atomic(path, &updated)?;
+        }
+        Ok(updated != content)
+    })?
+}
+
+fn update(
+    workspace: &Workspace,
+    file: &Path,
+    id: &str,
+    status: &str,
+    kind: Option<Kind>,
+    dry_run: bool,
+    json: bool,
+) -> Result<String, CliError> {
+    let path = workspace.validate(file)?;
+    let mut report = UpdateReport {
+        path: file.display().to_string(),
+        id: id.to_string(),
+        status: status.to_string(),
+        changed: false,
+        diff: None,
+    };
+    if dry_run {
+        let content = read_validated(&path, file)?;
+        let updated = apply_update(&content, file, id, status, kind)?;
+        report.changed = updated != content;
+        report.diff = Some(unified_diff(&content, &updated));
+    } else {
+        report.changed = write_update(&path, file, id, status, kind)?;
+    }
+
+    if json {
+        return Ok(serde_json::to_string_pretty(&report)?);
+    }
+    Ok(match (&report.diff, report.changed) {
+        (Some(diff), true) => diff.clone(),
+        (_, false) => format!("{} is already {}\n", id, status),
+        (None, true) => format!("{}: {} -> {}\n", report.path, id, status),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MigrateMode {
+    Print,
+    DryRun,
+    InPlace,
+}
+
+/// `content` migrated to `to`, refused when it is not a workflow file or,
+/// unless `force`, when fields would be lost
+fn migrate_content(
+    content: &str,
+    file: &Path,
+    to: WorkflowFormat,
+    force: bool,
+) -> Result<Migration, CliError> {
+    let path = file.display().to_string();
+    if Kind::detect(content) != Kind::Workflow {
+        return Err(CliError::WrongKind {
+            path,
+            expected: "workflow",
+        });
+    }
+    let migration = migrate_workflow(content, to).map_err(|source| CliError::Workflow {
+        path: path.clone(),
+        source,
+    })?;
+    if !force && !migration.lossy_fields.is_empty() {
+        return Err(CliError::Lossy {
+            path,
+            fields: migration
+                .lossy_fields
+                .iter()
+                .map(|field| field.path.clone())
+                .collect(),
+        });
+    }
+    Ok(migration)
+}
+
+fn migrate(
+    workspace: &Workspace,
+    file: &Path,
+    to: WorkflowFormat,
+    mode: MigrateMode,
+    force: bool,
+    json: bool,
+) -> Result<String, CliError> {
+    let path = workspace.validate(file)?;
+    let mut diff = None;
+    let migration = match mode {
+        MigrateMode::InPlace => with_lock(&path, DEFAULT_LOCK_TIMEOUT, || {
+            let migration = migrate_content(&read_validated(&path, file)?, file, to, force)?;
+            if migration.changed() {
+                write_atomic(&path, &migration.content)?;
+            }
+            Ok::<_, CliError>(migration)
+        })??,
+        // A dry run reports lost fields instead of refusing
+        MigrateMode::DryRun => {
+            let content = read_validated(&path, file)?;
+            let migration = migrate_content(&content, file, to, true)?;
+            diff = Some(unified_diff(&content, &migration.content));
+            migration
+        }
+        MigrateMode::Print => migrate_content(&read_validated(&path, file)?, file, to, force)?,
+    };
+
+    let shown = file.display().to_string();
+    let report = MigrateReport {
+        path: shown.clone(),
+        from: migration.from,
+        to: migration.to,
+        written: mode == MigrateMode::InPlace && migration.changed(),
+        lossy_fields: migration.lossy_fields,
+        diff,
+        content: (mode == MigrateMode::Print).then_some(migration.content),
+    };
+    if json {
+        return Ok(serde_json::to_string_pretty(&report)?);
+    }
+
+    if report.from == report.to && mode != MigrateMode::Print {
+        return Ok(format!(
+            "{} is already in the {} layout\n",
+            shown, report.to
+        ));
+    }
+    Ok(match mode {
+        MigrateMode::Print => report.content.unwrap_or_default(),
+        MigrateMode::InPlace => format!("{}: {} -> {}\n", shown, report.from, report.to),
+        MigrateMode::DryRun => {
+            let mut out = report.diff.unwrap_or_default();
+            for field in &report.lossy_fields {
+                out.push_str(&format!("warning: {} would be lost\n", field.path));
+            }
+            out
+        }
+    })
+}
+
+fn validate(workspace: &Workspace, files: &[PathBuf], json: bool) -> Result<String, CliError> {
+    let mut reports = Vec::new();
+    for file in files {
+        let content = workspace.read(file)?;
+        let diagnostics = match Kind::detect(&content) {
+            Kind::Workflow => workflow_diagnostics(&content).diagnostics,
+            Kind::Sprint => sprint_diagnostics(&content).diagnostics,
+        };
+        reports.push(FileDiagnostics {
+            path: file.display().to_string(),
+            diagnostics,
+        });
+    }
+
+    let count = |severity: Severity| {
+        reports
+            .iter()
+            .flat_map(|report| &report.diagnostics)
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+    let output = if json {
+        serde_json::to_string_pretty(&reports)?
+    } else {
+        let mut out: String = reports
+            .iter()
+            .map(|report| render::diagnostics(&report.path, &report.diagnostics))
+            .collect();
+        out.push_str(&format!(
+            "{} error(s), {} warning(s) in {} file(s)\n",
+            errors,
+            warnings,
+            reports.len()
+        ));
+        out
+    };
+    if errors > 0 {
+        Err(CliError::CheckFailed(output))
+    } else {
+        Ok(output)
+    }
+}
+
+/// Lint findings for one file, for `--json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileLint {
+    pub path: String,
+    #[serde(flatten)]
+    pub report: LintReport,
+}
+
+fn lint(workspace: &Workspace, files: &[PathBuf], json: bool) -> Result<String, CliError> {
+    let files = if files.is_empty() {
+        workspace.default_status_files()
+    } else {
+        files.to_vec()
+    };
+    if files.is_empty() {
+        return Err(CliError::MissingFile("status"));
+    }
+    let linter = workspace.config.linter();
+    let mut reports = Vec::new();
+    for file in &files {
+        let content = workspace.read(file)?;
+        let path = file.display().to_string();
+        let report = match Kind::detect(&content) {
+            Kind::Workflow => {
+                linter
+                    .lint_workflow(&content)
+                    .map_err(|source| CliError::Workflow {
+                        path: path.clone(),
+                        source,
+                    })?
+            }
+            Kind::Sprint => linter
+                .lint_sprint(&content)
+                .map_err(|source| CliError::Sprint {
+                    path: path.clone(),
+                    source,
+                })?,
+        };
+        reports.push(FileLint { path, report });
+    }
+
+    let output = if json {
+        serde_json::to_string_pretty(&reports)?
+    } else {
+        reports
+            .iter()
+            .map(|file| render::lint(&file.path, &file.report))
+            .collect()
+    };
+    if reports.iter().any(|file| file.report.has_errors()) {
+        Err(CliError::CheckFailed(output))
+    } else {
+        Ok(output)
+    }
+}
+
+fn stats(workspace: &Workspace, files: &[PathBuf], json: bool) -> Result<String, CliError> {
+    let mut projects = ProjectSet::new();
+    for file in files {
+        let key = file.display().to_string();
+        match load(workspace, file, None)? {
+            Document::Workflow(data) => projects.insert_workflow(&key, data),
+            Document::Sprint(data) => projects.insert_sprint(&key, data),
+        }
+    }
+    let stats = projects.stats();
+    if json {
+        Ok(serde_json::to_string_pretty(&stats)?)
+    } else {
+        Ok(render::stats(&stats))
+    }
+}
+
+fn check(
+    workspace: &Workspace,
+    files: &[PathBuf],
+    policy: &GatePolicy,
+    json: bool,
+) -> Result<String, CliError> {
+    let files = if files.is_empty() {
+        workspace.default_status_files()
+    } else {
+        files.to_vec()
+    };
+    let mut workflow = None;
+    let mut sprint = None;
+    for file in &files {
+        let shown = file.display().to_string();
+        let (kind, slot) = match load(workspace, file, None)? {
+            Document::Workflow(data) => (
+                "workflow",
+                workflow.replace((shown.clone(), data)).map(|(p, _)| p),
+            ),
+            Document::Sprint(data) => (
+                "sprint",
+                sprint.replace((shown.clone(), data)).map(|(p, _)| p),
+            ),
+        };
+        if let Some(first) = slot {
+            return Err(CliError::DuplicateKind {
+                kind,
+                first,
+                second: shown,
+            });
+        }
+    }
+
+    // A file the policy does not look at may be absent; stand in an empty one
+    if workflow.is_none() && !policy.require_complete.is_empty() {
+        return Err(CliError::MissingFile("workflow"));
+    }
+    if sprint.is_none() && policy.max_in_progress.is_some() {
+        return Err(CliError::MissingFile("sprint"));
+    }
+    let workflow = match workflow {
+        Some((_, data)) => data,
+        None => empty_workflow()?,
+    };
+    let sprint = match sprint {
+        Some((_, data)) => data,
+        None => empty_sprint()?,
+    };
+
+    let report = gate::evaluate(&workflow, &sprint, policy);
+    let output = if json {
+        serde_json::to_string_pretty(&report)?
+    } else {
+        render::gate(&report)
+    };
+    if report.passed {
+        Ok(output)
+    } else {
+        Err(CliError::CheckFailed(output))
+    }
+}
+
+fn empty_workflow() -> Result<clique_core::WorkflowData, CliError> {
+    parse_workflow_status("").map_err(|source| CliError::Workflow {
+        path: String::new(),
+        source,
+    })
+}
+
+fn empty_sprint() -> Result<clique_core::SprintData, CliError> {
+    parse_sprint_status("").map_err(|source| CliError::Sprint {
+        path: String::new(),
+        source,
+    })
+}
+
+fn diff(
+    workspace: &Workspace,
+    old: &Path,
+    new: &Path,
+    kind: Option<Kind>,
+    json: bool,
+) -> Result<String, CliError> {
+    let changes = match (load(workspace, old, kind)?, load(workspace, new, kind)?) {
+        (Document::Workflow(a), Document::Workflow(b)) => diff_workflow(&a, &b),
+        (Document::Sprint(a), Document::Sprint(b)) => diff_sprint(&a, &b),
+        _ => {
+            return Err(CliError::KindMismatch {
+                old: old.display().to_string(),
+                new: new.display().to_string(),
+            });
+        }
+    };
+    if json {
+        Ok(serde_json::to_string_pretty(&changes)?)
+    } else {
+        Ok(render::changes(&changes))
+    }
+}
+
+/// Completion script for `shell`, generated from the clap definitions
+fn completions(shell: Shell) -> String {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(
+        clap_complete::Shell::from(shell),
+        &mut command,
+        name,
+        &mut script,
+    );
+    String::from_utf8_lossy(&script).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    const WORKFLOW: &str = "workflow_status:\n  prd: required\n  brainstorm: skipped\n";
+    const SPRINT: &str = "development_status:\n  epic-1: backlog\n  1-a: backlog\n  1-b: done\n";
+
+    /// A fresh workspace directory holding `files`
+    fn workspace(name: &str, files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new(&format!("cli-{}", name));
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+        dir
+    }
+
+    fn run_in(dir: &Path, args: &[&str]) -> Result<String, CliError> {
+        let cli = Cli::try_parse_args_from(std::iter::once("clique").chain(args.iter().copied()))
+            .unwrap();
+        run(&cli, dir)
+    }
+
+    // =========================================================================
+    // Path Tests
+    // =========================================================================
+
+    #[test]
+    fn test_refuses_paths_outside_root() {
+        let dir = workspace("outside", &[("w.yaml", WORKFLOW)]);
+        let error = run_in(&dir, &["parse", "../w.yaml"]).unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::Path(PathError::TraversalDetected(_))
+        ));
+        assert_eq!(error.exit_code(), 2);
+
+        let error = run_in(&dir, &["parse", "/etc/hosts"]).unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::Path(PathError::OutsideWorkspace(_))
+        ));
+
+        let sub = dir.join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        let error = run_in(&dir, &["--root", "sub", "parse", "w.yaml"]).unwrap_err();
+        assert!(matches!(error, CliError::Path(_)));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let dir = workspace("missing", &[]);
+        let error = run_in(&dir, &["parse", "nope.yaml"]).unwrap_err();
+        assert!(matches!(error, CliError::Read { .. }));
+        assert!(error.to_string().starts_with("Failed to read nope.yaml"));
+    }
+
+    // =========================================================================
+    // Parse Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse() {
+        let dir = workspace(
+            "parse",
+            &[
+                ("w.yaml", WORKFLOW),
+                ("s.yaml", SPRINT),
+                ("bad.yaml", "[oops"),
+            ],
+        );
+        assert_eq!(
+            run_in(&dir, &["parse", "w.yaml"]).unwrap(),
+            "[-] brainstorm  skipped\n[ ] prd         required\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&run_in(&dir, &["--json", "parse", "s.yaml"]).unwrap()).unwrap();
+        assert_eq!(json["epics"][0]["stories"][1]["id"], "1-b");
+
+        let error = run_in(&dir, &["parse", "bad.yaml", "--kind", "sprint"]).unwrap_err();
+        assert!(matches!(error, CliError::Sprint { .. }));
+        assert!(error.to_string().starts_with("bad.yaml: "));
+    }
+
+    // =========================================================================
+    // Update Tests
+    // =========================================================================
+
+    #[test]
+    fn test_update_writes_file() {
+        let dir = workspace("update", &[("s.yaml", SPRINT)]);
+        let output = run_in(&dir, &["update", "s.yaml", "1-a", "review"]).unwrap();
+        assert_eq!(output, "s.yaml: 1-a -> review\n");
+        let content = fs::read_to_string(dir.join("s.yaml")).unwrap();
+        assert!(content.contains("1-a: review"));
+        assert!(!dir.join("s.yaml.lock").exists());
+
+        let output = run_in(&dir, &["update", "s.yaml", "1-a", "review"]).unwrap();
+        assert_eq!(output, "1-a is already review\n");
+
+        let error = run_in(&dir, &["update", "s.yaml", "9-z", "done"]).unwrap_err();
+        assert!(matches!(error, CliError::Sprint { .. }));
+    }
+
+    #[test]
+    fn test_update_dry_run() {
+        let dir = workspace("dry-run", &[("w.yaml", WORKFLOW)]);
+        let output = run_in(
+            &dir,
+            &["update", "w.yaml", "prd", "docs/prd.md", "--dry-run"],
+        )
+        .unwrap();
+        assert!(output.contains("-  prd: required"));
+        assert!(output.contains("+  prd: \"docs/prd.md\""));
+        assert_eq!(fs::read_to_string(dir.join("w.yaml")).unwrap(), WORKFLOW);
+
+        let json: serde_json::Value = serde_json::from_str(
+            &run_in(
+                &dir,
+                &["--json", "update", "w.yaml", "prd", "done", "--dry-run"],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["changed"], true);
+        assert_eq!(json["path"], "w.yaml");
+        assert!(json["diff"].is_string());
+    }
+
+    #[test]
+    fn test_markdown_tasklist() {
+        let plan = "# Demo\n\n- [x] brainstorm\n- [ ] prd\n";
+        let dir = workspace("tasklist", &[("planning.md", plan)]);
+        assert_eq!(
+            run_in(&dir, &["parse", "planning.md"]).unwrap(),
+            "Demo ()\n[x] brainstorm  complete\n[ ] prd         required\n"
+        );
+        run_in(&dir, &["update", "planning.md", "prd", "done"]).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("planning.md")).unwrap(),
+            "# Demo\n\n- [x] brainstorm\n- [x] prd\n"
+        );
+    }
+
+    // =========================================================================
+    // Migrate Tests
+    // =========================================================================
+
+    const OLD_WORKFLOW: &str = "workflow_status:\n  - id: prd\n    phase: 1\n    status: required\n    agent: pm\n    \
+         command: prd\n    note: Draft\n";
+
+    #[test]
+    fn test_migrate_in_place() {
+        let dir = workspace("migrate", &[("w.yaml", WORKFLOW)]);
+        let args = ["migrate", "w.yaml", "--to", "new-format", "--in-place"];
+        assert_eq!(run_in(&dir, &args).unwrap(), "w.yaml: flat -> new\n");
+        let content = fs::read_to_string(dir.join("w.yaml")).unwrap();
+        assert!(content.starts_with("workflows:\n"));
+        assert_eq!(
+            clique_core::sniff_format(&content),
+            Some(WorkflowFormat::New)
+        );
+        assert!(!dir.join("w.yaml.lock").exists());
+
+        assert_eq!(
+            run_in(&dir, &args).unwrap(),
+            "w.yaml is already in the new layout\n"
+        );
+    }
+
+    #[test]
+    fn test_migrate_dry_run_and_print() {
+        let dir = workspace("migrate-dry", &[("w.yaml", OLD_WORKFLOW)]);
+        let output = run_in(&dir, &["migrate", "w.yaml", "--to", "flat", "--dry-run"]).unwrap();
+        assert!(output.contains("-  - id: prd"));
+        assert!(output.contains("+  prd: required"));
+        assert!(output.ends_with("warning: items.prd.note would be lost\n"));
+        assert_eq!(
+            fs::read_to_string(dir.join("w.yaml")).unwrap(),
+            OLD_WORKFLOW
+        );
+
+        let output = run_in(&dir, &["migrate", "w.yaml", "--to", "new"]).unwrap();
+        assert!(output.starts_with("workflows:\n  prd:\n"));
+        assert_eq!(
+            fs::read_to_string(dir.join("w.yaml")).unwrap(),
+            OLD_WORKFLOW
+        );
+
+        let json: serde_json::Value = serde_json::from_str(
+            &run_in(
+                &dir,
+                &["--json", "migrate", "w.yaml", "--to", "flat", "--dry-run"],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["from"], "old");
+        assert_eq!(json["to"], "flat");
+        assert_eq!(json["written"], false);
+        assert_eq!(json["lossyFields"][0]["path"], "items.prd.note");
+    }
+
+    #[test]
+    fn test_migrate_refusals() {
+        let dir = workspace(
+            "migrate-refuse",
+            &[("w.yaml", OLD_WORKFLOW), ("s.yaml", SPRINT)],
+        );
+        let args = ["migrate", "w.yaml", "--to", "flat", "--in-place"];
+        let error = run_in(&dir, &args).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Migrating w.yaml would lose items.prd.note; pass --force to migrate anyway"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("w.yaml")).unwrap(),
+            OLD_WORKFLOW
+        );
+
+        run_in(&dir, &[&args[..], &["--force"]].concat()).unwrap();
+        assert!(
+            fs::read_to_string(dir.join("w.yaml"))
+                .unwrap()
+                .contains("prd: required")
+        );
+
+        let error = run_in(&dir, &["migrate", "s.yaml", "--to", "new"]).unwrap_err();
+        assert!(matches!(error, CliError::WrongKind { .. }));
+        assert_eq!(error.to_string(), "s.yaml is not a workflow file");
+    }
+
+    #[test]
+    fn test_migrate_refuses_dropping_unknown_keys() {
+        let workflow = "team_notes: keep me # important\nworkflows:\n  prd:\n    status: required\n    \
+                        custom_owner: ana\n";
+        let dir = workspace("migrate-unknown", &[("w.yaml", workflow)]);
+        let args = ["migrate", "w.yaml", "--to", "flat", "--in-place"];
+        let error = run_in(&dir, &args).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Migrating w.yaml would lose team_notes, items.prd.custom_owner, comment on line 1; \
+             pass --force to migrate anyway"
+        );
+        assert_eq!(fs::read_to_string(dir.join("w.yaml")).unwrap(), workflow);
+
+        run_in(&dir, &[&args[..], &["--force"]].concat()).unwrap();
+        assert!(
+            !fs::read_to_string(dir.join("w.yaml"))
+                .unwrap()
+                .contains("team_notes")
+        );
+    }
+
+    // =========================================================================
+    // Validate Tests
+    // =========================================================================
+
+    #[test]
+    fn test_validate() {
+        let dir = workspace(
+            "validate",
+            &[
+                ("w.yaml", WORKFLOW),
+                ("bad.yaml", "workflow_status:\n  prd: [oops\n"),
+            ],
+        );
+        assert_eq!(
+            run_in(&dir, &["validate", "w.yaml"]).unwrap(),
+            "0 error(s), 0 warning(s) in 1 file(s)\n"
+        );
+
+        let error = run_in(&dir, &["validate", "w.yaml", "bad.yaml"]).unwrap_err();
+        assert_eq!(error.exit_code(), 1);
+        let CliError::CheckFailed(output) = error else {
+            panic!("expected a failed check");
+        };
+        assert!(output.contains("bad.yaml:"));
+        assert!(output.contains(": error: "));
+
+        let Err(CliError::CheckFailed(output)) = run_in(&dir, &["--json", "validate", "bad.yaml"])
+        else {
+            panic!("expected a failed check");
+        };
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json[0]["path"], "bad.yaml");
+        assert_eq!(json[0]["diagnostics"][0]["severity"], "error");
+    }
+
+    // =========================================================================
+    // Config and Lint Tests
+    // =========================================================================
+
+    const CONFIG: &str = "[status-aliases]\nshipped = \"done\"\n\n[lint.rules]\n\
+                          empty-epic = \"error\"\n\n[paths]\ndeny = [\"private/**\"]\n\n\
+                          [files]\nsprint = \"plan/s.yaml\"\n";
+
+    #[test]
+    fn test_config_applies() {
+        let dir = workspace(
+            "config",
+            &[
+                (".clique.toml", CONFIG),
+                (
+                    "s.yaml",
+                    "development_status:\n  epic-1: backlog\n  1-a: shipped\n",
+                ),
+            ],
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&run_in(&dir, &["--json", "parse", "s.yaml"]).unwrap()).unwrap();
+        assert_eq!(json["epics"][0]["stories"][0]["status"], "done");
+
+        fs::create_dir_all(dir.join("private")).unwrap();
+        fs::write(dir.join("private/s.yaml"), SPRINT).unwrap();
+        let error = run_in(&dir, &["parse", "private/s.yaml"]).unwrap_err();
+        assert!(matches!(error, CliError::Path(PathError::Denied { .. })));
+
+        fs::write(dir.join(".clique.toml"), "[lint]\nlevel = 1\n").unwrap();
+        let error = run_in(&dir, &["parse", "s.yaml"]).unwrap_err();
+        assert!(matches!(error, CliError::Config(_)));
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid .clique.toml: line 2: ")
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let dir = workspace("lint", &[(".clique.toml", CONFIG), ("w.yaml", WORKFLOW)]);
+        assert_eq!(
+            run_in(&dir, &["lint", "w.yaml"]).unwrap(),
+            "w.yaml:3: warning[skipped-without-note]: `brainstorm` is skipped without a note\n\
+             w.yaml: 1 finding(s)\n"
+        );
+        assert!(matches!(
+            run_in(&dir, &["lint"]),
+            Err(CliError::MissingFile("status"))
+        ));
+
+        fs::create_dir_all(dir.join("plan")).unwrap();
+        fs::write(
+            dir.join("plan/s.yaml"),
+            "development_status:\n  epic-1: backlog\n  epic-2: backlog\n  1-1-a: done\n",
+        )
+        .unwrap();
+        let error = run_in(&dir, &["--json", "lint"]).unwrap_err();
+        assert_eq!(error.exit_code(), 1);
+        let CliError::CheckFailed(output) = error else {
+            panic!("expected a failed check");
+        };
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json[0]["findings"][0]["rule"], "empty-epic");
+        assert_eq!(json[0]["findings"][0]["severity"], "error");
+    }
+
+    // =========================================================================
+    // Check Tests
+    // =========================================================================
+
+    #[test]
+    fn test_check() {
+        let dir = workspace("check", &[("w.yaml", WORKFLOW), ("s.yaml", SPRINT)]);
+        assert_eq!(
+            run_in(
+                &dir,
+                &["check", "w.yaml", "s.yaml", "--max-in-progress", "0"]
+            )
+            .unwrap(),
+            "Gate passed (0 in progress)\n"
+        );
+
+        let args = ["check", "w.yaml", "--require-complete", "prd,brainstorm"];
+        let error = run_in(&dir, &args).unwrap_err();
+        assert_eq!(error.exit_code(), 1);
+        let CliError::CheckFailed(output) = error else {
+            panic!("expected a failed check");
+        };
+        assert_eq!(
+            output,
+            "- prd must be complete but is required\n- brainstorm must be complete but is skipped\n\
+             Gate failed: 2 problem(s)\n"
+        );
+
+        let Err(CliError::CheckFailed(output)) = run_in(&dir, &[&["--json"], &args[..]].concat())
+        else {
+            panic!("expected a failed check");
+        };
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["failures"][1]["kind"], "incomplete");
+    }
+
+    #[test]
+    fn test_check_finds_default_files() {
+        let dir = workspace("check-default", &[]);
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(
+            dir.join("docs/bmm-workflow-status.yaml"),
+            "workflow_status:\n  prd: docs/prd.md\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("sprint-status.yaml"),
+            "development_status:\n  epic-1: backlog\n  1-a: in-progress\n  1-b: in-progress\n",
+        )
+        .unwrap();
+        let args = [
+            "check",
+            "--require-complete",
+            "prd",
+            "--max-in-progress",
+            "2",
+        ];
+        assert_eq!(
+            run_in(&dir, &args).unwrap(),
+            "Gate passed (2 in progress)\n"
+        );
+        let error = run_in(&dir, &["check", "--max-in-progress", "1"]).unwrap_err();
+        assert!(matches!(error, CliError::CheckFailed(_)));
+    }
+
+    #[test]
+    fn test_check_file_errors() {
+        let dir = workspace("check-files", &[("w.yaml", WORKFLOW), ("s.yaml", SPRINT)]);
+        let error = run_in(&dir, &["check", "s.yaml", "--require-complete", "prd"]).unwrap_err();
+        assert!(matches!(error, CliError::MissingFile("workflow")));
+        assert_eq!(error.exit_code(), 2);
+
+        let error = run_in(&dir, &["check", "w.yaml", "--max-in-progress", "1"]).unwrap_err();
+        assert!(matches!(error, CliError::MissingFile("sprint")));
+
+        let error = run_in(&dir, &["check", "s.yaml", "s.yaml"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "More than one sprint file: s.yaml and s.yaml"
+        );
+    }
+
+    // =========================================================================
+    // Stats and Diff Tests
+    // =========================================================================
+
+    #[test]
+    fn test_stats() {
+        let dir = workspace("stats", &[("w.yaml", WORKFLOW), ("s.yaml", SPRINT)]);
+        assert_eq!(
+            run_in(&dir, &["stats", "w.yaml", "s.yaml"]).unwrap(),
+            "Workflow: 0/2 complete, 1 pending, 1 skipped\n\
+             Stories: 1/2 done\n  backlog: 1\n  done: 1\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&run_in(&dir, &["--json", "stats", "s.yaml"]).unwrap()).unwrap();
+        assert_eq!(json["storiesDone"], 1);
+    }
+
+    #[test]
+    fn test_diff() {
+        let dir = workspace(
+            "diff",
+            &[
+                ("old.yaml", SPRINT),
+                (
+                    "new.yaml",
+                    "development_status:\n  epic-1: backlog\n  1-a: review\n",
+                ),
+                ("w.yaml", WORKFLOW),
+            ],
+        );
+        assert_eq!(
+            run_in(&dir, &["diff", "old.yaml", "new.yaml"]).unwrap(),
+            "~ 1-a: backlog -> review\n- 1-b (done)\n"
+        );
+        assert_eq!(
+            run_in(&dir, &["diff", "w.yaml", "w.yaml"]).unwrap(),
+            "No changes\n"
+        );
+        let json: serde_json::Value = serde_json::from_str(
+            &run_in(&dir, &["--json", "diff", "old.yaml", "new.yaml"]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json[0]["kind"], "statusChanged");
+
+        let error = run_in(&dir, &["diff", "old.yaml", "w.yaml"]).unwrap_err();
+        assert!(matches!(error, CliError::KindMismatch { .. }));
+    }
+
+    // =========================================================================
+    // Schema and Completion Tests
+    // =========================================================================
+
+    #[test]
+    fn test_schema() {
+        let dir = workspace("schema", &[]);
+        let json: serde_json::Value =
+            serde_json::from_str(&run_in(&dir, &["--schema"]).unwrap()).unwrap();
+        assert_eq!(json["name"], "clique");
+        assert!(
+            json["commands"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|command| command["name"] == "completions")
+        );
+    }
+
+    #[test]
+    fn test_completions() {
+        let dir = workspace("completions", &[]);
+        for (shell, marker) in [
+            ("bash", "_clique()"),
+            ("zsh", "#compdef clique"),
+            ("fish", "complete -c clique"),
+            ("powershell", "Register-ArgumentCompleter"),
+        ] {
+            let script = run_in(&dir, &["completions", shell]).unwrap();
+            assert!(script.contains(marker), "{shell}");
+            assert!(script.contains("migrate"), "{shell}");
+        }
+    }
+}
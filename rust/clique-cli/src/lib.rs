@@ -0,0 +1,25 @@
+//! Clique Command Line
+//!
+//! `clique` reads and updates workflow and sprint status files from a shell
+//! or CI job: `parse`, `update`, `validate`, `lint`, `stats`, `diff`,
+//! `migrate` and `check`, each with human-readable output by default and
+//! JSON with `--json`. With the `tui` feature, `board` browses and edits a
+//! status file in the terminal; with `watch` (on by default), `watch`
+//! follows status files as they change. `completions` prints a shell
+//! completion script and `--schema` describes every command and option as
+//! JSON. Project conventions are read from `.clique.toml` in the workspace
+//! root.
+
+#[cfg(feature = "tui")]
+pub mod board;
+pub mod cli;
+pub mod commands;
+pub mod render;
+pub mod schema;
+#[cfg(test)]
+mod testing;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+pub use cli::{Cli, Command, Format, Kind, Shell};
+pub use commands::{CliError, Workspace, run};
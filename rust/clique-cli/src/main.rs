@@ -0,0 +1,28 @@
+//! `clique` entry point: arguments parsed, command run, result on stdout.
+
+use clique_cli::{Cli, CliError, run};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let cli = Cli::try_parse_args_from(std::env::args_os()).unwrap_or_else(|error| error.exit());
+    let result = std::env::current_dir()
+        .map_err(|source| CliError::Read {
+            path: ".".to_string(),
+            source,
+        })
+        .and_then(|cwd| run(&cli, &cwd));
+    match result {
+        Ok(output) => {
+            println!("{}", output.trim_end_matches('\n'));
+            ExitCode::SUCCESS
+        }
+        Err(CliError::CheckFailed(output)) => {
+            println!("{}", output.trim_end_matches('\n'));
+            ExitCode::from(1)
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Human-readable output.
+//!
+//! Plain text meant for a terminal or a CI log; `--json` bypasses all of it.
+
+use clique_core::diagnostics::{Diagnostic, Severity};
+use clique_core::lint::LintReport;
+use clique_core::{GateReport, ItemChange, ProjectStats, SprintData, StatusClass, WorkflowData};
+use std::fmt::Write;
+
+/// Workflow items with a checkbox for their status class
+pub fn workflow(data: &WorkflowData) -> String {
+    let mut out = String::new();
+    if !data.project.is_empty() {
+        let _ = writeln!(out, "{} ({})", data.project, data.selected_track);
+    }
+    let width = data
+        .items
+        .iter()
+        .map(|item| item.id.len())
+        .max()
+        .unwrap_or(0);
+    for item in &data.items {
+        let mark = match item.status_class() {
+            StatusClass::Complete => "x",
+            StatusClass::Pending => " ",
+            StatusClass::Skipped => "-",
+        };
+        let _ = writeln!(out, "[{}] {:width$}  {}", mark, item.id, item.status);
+    }
+    out
+}
+
+/// Epics with their stories indented beneath
+pub fn sprint(data: &SprintData) -> String {
+    let mut out = String::new();
+    if !data.project.is_empty() {
+        let _ = writeln!(out, "{}", data.project);
+    }
+    for epic in &data.epics {
+        let _ = writeln!(out, "{} [{}]", epic.id, epic.status);
+        let width = epic.stories.iter().map(|s| s.id.len()).max().unwrap_or(0);
+        for story in &epic.stories {
+            let _ = writeln!(out, "  {:width$}  {}", story.id, story.status);
+        }
+    }
+    out
+}
+
+/// One `path:line: severity: message` line per diagnostic
+pub fn diagnostics(path: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut out = String::new();
+    for diagnostic in diagnostics {
+        let severity = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let _ = writeln!(
+            out,
+            "{}:{}: {}: {}",
+            path, diagnostic.line, severity, diagnostic.message
+        );
+    }
+    out
+}
+
+/// One `path:line: severity[rule]: message` line per finding, then a count
+pub fn lint(path: &str, report: &LintReport) -> String {
+    let mut out = String::new();
+    for finding in &report.findings {
+        let severity = match finding.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let _ = writeln!(
+            out,
+            "{}:{}: {}[{}]: {}",
+            path, finding.line, severity, finding.rule, finding.message
+        );
+    }
+    let _ = writeln!(out, "{}: {} finding(s)", path, report.findings.len());
+    out
+}
+
+/// Workflow and story totals, then stories by status
+pub fn stats(stats: &ProjectStats) -> String {
+    let mut out = String::new();
+    if stats.workflow_items > 0 {
+        let _ = writeln!(
+            out,
+            "Workflow: {}/{} complete, {} pending, {} skipped",
+            stats.workflow_complete,
+            stats.workflow_items,
+            stats.workflow_pending,
+            stats.workflow_skipped
+        );
+    }
+    if stats.stories > 0 {
+        let _ = writeln!(
+            out,
+            "Stories: {}/{} done",
+            stats.stories_done, stats.stories
+        );
+        for (status, count) in &stats.stories_by_status {
+            let _ = writeln!(out, "  {}: {}", status, count);
+        }
+    }
+    out
+}
+
+/// `+`, `-` or `~` and the ID for each change
+pub fn changes(changes: &[ItemChange]) -> String {
+    if changes.is_empty() {
+        return "No changes\n".to_string();
+    }
+    let mut out = String::new();
+    for change in changes {
+        let _ = match change {
+            ItemChange::Added { id, status } => writeln!(out, "+ {} ({})", id, status),
+            ItemChange::Removed { id, status } => writeln!(out, "- {} ({})", id, status),
+            ItemChange::StatusChanged { id, from, to } => {
+                writeln!(out, "~ {}: {} -> {}", id, from, to)
+            }
+        };
+    }
+    out
+}
+
+/// Each failed condition, then the verdict
+pub fn gate(report: &GateReport) -> String {
+    let mut out = String::new();
+    for failure in &report.failures {
+        let _ = writeln!(out, "- {}", failure);
+    }
+    if report.passed {
+        let _ = writeln!(out, "Gate passed ({} in progress)", report.in_progress);
+    } else {
+        let _ = writeln!(out, "Gate failed: {} problem(s)", report.failures.len());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clique_core::{GateFailure, parse_sprint_status, parse_workflow_status};
+
+    // =========================================================================
+    // Document Tests
+    // =========================================================================
+
+    #[test]
+    fn test_workflow() {
+        let data = parse_workflow_status(
+            "project: Demo\nselected_track: quick-flow\nworkflow_status:\n  prd: docs/prd.md\n  \
+             brainstorm: skipped\n  architecture: required\n",
+        )
+        .unwrap();
+        assert_eq!(
+            workflow(&data),
+            "Demo (quick-flow)\n\
+             [-] brainstorm    skipped\n\
+             [x] prd           docs/prd.md\n\
+             [ ] architecture  required\n"
+        );
+    }
+
+    #[test]
+    fn test_sprint() {
+        let data = parse_sprint_status(
+            "development_status:\n  epic-1: in-progress\n  1-a: done\n  1-long: backlog\n",
+        )
+        .unwrap();
+        assert_eq!(
+            sprint(&data),
+            "Unknown\nepic-1 [in-progress]\n  1-a     done\n  1-long  backlog\n"
+        );
+    }
+
+    // =========================================================================
+    // Report Tests
+    // =========================================================================
+
+    #[test]
+    fn test_diagnostics() {
+        let found = [
+            Diagnostic {
+                line: 3,
+                severity: Severity::Error,
+                message: "bad".to_string(),
+                path: None,
+            },
+            Diagnostic {
+                line: 0,
+                severity: Severity::Warning,
+                message: "odd".to_string(),
+                path: Some("x".to_string()),
+            },
+        ];
+        assert_eq!(
+            diagnostics("s.yaml", &found),
+            "s.yaml:3: error: bad\ns.yaml:0: warning: odd\n"
+        );
+        assert_eq!(diagnostics("s.yaml", &[]), "");
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut by_status = std::collections::BTreeMap::new();
+        by_status.insert("done".to_string(), 1);
+        by_status.insert("backlog".to_string(), 2);
+        let counts = ProjectStats {
+            workflow_items: 4,
+            workflow_complete: 2,
+            workflow_pending: 1,
+            workflow_skipped: 1,
+            stories: 3,
+            stories_done: 1,
+            stories_by_status: by_status,
+        };
+        assert_eq!(
+            stats(&counts),
+            "Workflow: 2/4 complete, 1 pending, 1 skipped\n\
+             Stories: 1/3 done\n  backlog: 2\n  done: 1\n"
+        );
+        assert_eq!(stats(&ProjectStats::default()), "");
+    }
+
+    #[test]
+    fn test_changes() {
+        let list = [
+            ItemChange::Added {
+                id: "a".to_string(),
+                status: "required".to_string(),
+            },
+            ItemChange::Removed {
+                id: "b".to_string(),
+                status: "skipped".to_string(),
+            },
+            ItemChange::StatusChanged {
+                id: "c".to_string(),
+                from: "backlog".to_string(),
+                to: "done".to_string(),
+            },
+        ];
+        assert_eq!(
+            changes(&list),
+            "+ a (required)\n- b (skipped)\n~ c: backlog -> done\n"
+        );
+        assert_eq!(changes(&[]), "No changes\n");
+    }
+
+    #[test]
+    fn test_gate() {
+        let passed = GateReport {
+            passed: true,
+            failures: vec![],
+            in_progress: 2,
+        };
+        assert_eq!(gate(&passed), "Gate passed (2 in progress)\n");
+
+        let failed = GateReport {
+            passed: false,
+            failures: vec![GateFailure::Missing {
+                id: "prd".to_string(),
+            }],
+            in_progress: 0,
+        };
+        assert_eq!(
+            gate(&failed),
+            "- prd must be complete but is missing\nGate failed: 1 problem(s)\n"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Machine-readable description of the command line (`clique --schema`).
+//!
+//! Built from the same clap definitions that parse the arguments, so wrapper
+//! tools can find out which commands and options this build supports (the
+//! `tui` and `watch` commands appear only when compiled in). Fields are only
+//! ever added within a [`SCHEMA_VERSION`]; renaming or removing a command,
+//! option or field bumps it.
+
+use crate::cli::Cli;
+use clap::{Arg, ArgAction, CommandFactory};
+use serde::Serialize;
+
+/// Version of the schema layout, not of the CLI
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The whole command line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    pub schema_version: u32,
+    pub name: String,
+    /// CLI version, as printed by `--version`
+    pub version: String,
+    pub about: Option<String>,
+    /// Options accepted by every command
+    pub global_options: Vec<ArgSchema>,
+    pub commands: Vec<CommandSchema>,
+}
+
+/// One subcommand
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandSchema {
+    pub name: String,
+    pub about: Option<String>,
+    /// Positional arguments, in order
+    pub arguments: Vec<ArgSchema>,
+    pub options: Vec<ArgSchema>,
+}
+
+/// One positional argument or `--option`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArgSchema {
+    /// Field name, e.g. `dry_run`
+    pub name: String,
+    /// Long flag without dashes, e.g. `dry-run`; `None` for positionals
+    pub long: Option<String>,
+    pub help: Option<String>,
+    pub required: bool,
+    /// Whether the argument takes a value; `false` for switches
+    pub takes_value: bool,
+    /// Whether the argument may be given more than once
+    pub multiple: bool,
+    /// Separator splitting one value into several, e.g. `,`
+    pub value_delimiter: Option<char>,
+    pub value_name: Option<String>,
+    /// Accepted values, for enumerations
+    pub possible_values: Vec<String>,
+}
+
+fn text(styled: Option<&clap::builder::StyledStr>) -> Option<String> {
+    styled.map(|s| s.to_string())
+}
+
+/// Help, version and clap's generated `help` command are not part of the API
+fn is_builtin(arg: &Arg) -> bool {
+    matches!(arg.get_action(), ArgAction::Help | ArgAction::Version)
+}
+
+fn arg_schema(arg: &Arg) -> ArgSchema {
+    ArgSchema {
+        name: arg.get_id().to_string(),
+        long: arg.get_long().map(str::to_string),
+        help: text(arg.get_long_help().or(arg.get_help())),
+        required: arg.is_required_set(),
+        takes_value: arg.get_action().takes_values(),
+        multiple: matches!(arg.get_action(), ArgAction::Append),
+        value_delimiter: arg.get_value_delimiter(),
+        value_name: arg
+            .get_value_names()
+            .and_then(|names| names.first())
+            .map(|name| name.to_string()),
+        possible_values: arg
+            .get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect(),
+    }
+}
+
+fn command_schema(command: &clap::Command) -> CommandSchema {
+    let (arguments, options) = command
+        .get_arguments()
+        .filter(|arg| !is_builtin(arg) && !arg.is_global_set())
+        .partition::<Vec<_>, _>(|arg| arg.is_positional());
+    CommandSchema {
+        name: command.get_name().to_string(),
+        about: text(command.get_about()),
+        arguments: arguments.into_iter().map(arg_schema).collect(),
+        options: options.into_iter().map(arg_schema).collect(),
+    }
+}
+
+/// Describe the command line of this build.
+pub fn schema() -> Schema {
+    let mut cli = Cli::command();
+    // Propagates global options so `get_arguments` matches what is parsed
+    cli.build();
+    Schema {
+        schema_version: SCHEMA_VERSION,
+        name: cli.get_name().to_string(),
+        version: cli.get_version().unwrap_or_default().to_string(),
+        about: text(cli.get_about()),
+        global_options: cli
+            .get_arguments()
+            .filter(|arg| !is_builtin(arg))
+            .map(arg_schema)
+            .collect(),
+        commands: cli
+            .get_subcommands()
+            .filter(|command| command.get_name() != "help")
+            .map(command_schema)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command<'a>(schema: &'a Schema, name: &str) -> &'a CommandSchema {
+        schema
+            .commands
+            .iter()
+            .find(|command| command.name == name)
+            .unwrap()
+    }
+
+    fn option<'a>(command: &'a CommandSchema, name: &str) -> &'a ArgSchema {
+        command
+            .options
+            .iter()
+            .find(|option| option.name == name)
+            .unwrap()
+    }
+
+    // =========================================================================
+    // Schema Tests
+    // =========================================================================
+
+    #[test]
+    fn test_top_level() {
+        let schema = schema();
+        assert_eq!(schema.schema_version, SCHEMA_VERSION);
+        assert_eq!(schema.name, "clique");
+        assert_eq!(schema.version, env!("CARGO_PKG_VERSION"));
+        let globals: Vec<&str> = schema
+            .global_options
+            .iter()
+            .map(|option| option.name.as_str())
+            .collect();
+        assert_eq!(globals, ["root", "json", "schema"]);
+        assert!(schema.commands.iter().all(|command| command.name != "help"));
+        assert_eq!(
+            schema.commands.len(),
+            Cli::command().get_subcommands().count()
+        );
+    }
+
+    #[test]
+    fn test_command_arguments() {
+        let schema = schema();
+        let update = command(&schema, "update");
+        let positionals: Vec<&str> = update.arguments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(positionals, ["file", "id", "status"]);
+        assert!(
+            update
+                .arguments
+                .iter()
+                .all(|arg| arg.required && arg.long.is_none())
+        );
+
+        let dry_run = option(update, "dry_run");
+        assert_eq!(dry_run.long.as_deref(), Some("dry-run"));
+        assert!(!dry_run.takes_value);
+        assert!(!dry_run.required);
+        assert_eq!(
+            option(update, "kind").possible_values,
+            ["workflow", "sprint"]
+        );
+        // Global options are listed once, at the top
+        assert!(update.options.iter().all(|option| option.name != "json"));
+    }
+
+    #[test]
+    fn test_value_details() {
+        let schema = schema();
+        let check = command(&schema, "check");
+        let require = option(check, "require_complete");
+        assert!(require.multiple);
+        assert_eq!(require.value_delimiter, Some(','));
+        assert_eq!(require.value_name.as_deref(), Some("IDS"));
+        assert!(check.arguments[0].multiple);
+
+        let migrate = command(&schema, "migrate");
+        let to = option(migrate, "to");
+        assert!(to.required);
+        assert_eq!(to.possible_values, ["new", "flat", "old"]);
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_value(schema()).unwrap();
+        assert_eq!(json["schemaVersion"], SCHEMA_VERSION);
+        assert_eq!(json["commands"][0]["name"], "parse");
+        assert!(
+            json["commands"][0]["arguments"][0]["takesValue"]
+                .as_bool()
+                .unwrap()
+        );
+    }
+}
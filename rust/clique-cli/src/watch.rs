@@ -0,0 +1,300 @@
+//! Watch mode (`clique watch`, `watch` feature).
+//!
+//! Follows status files through [`watch_status_files`]. Each time statuses
+//! change the file is re-validated, the changes and any diagnostics are
+//! printed, and the `--exec` command runs, e.g. to regenerate a report.
+
+use crate::cli::Kind;
+use crate::commands::{CliError, Workspace, read_validated};
+use crate::render;
+use clique_core::diagnostics::Diagnostic;
+use clique_core::watch::StatusChange;
+use clique_core::{
+    ItemChange, StatusChangeEvent, sprint_diagnostics, watch_status_files, workflow_diagnostics,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc;
+
+/// Environment variable holding the changed file's path for `--exec`
+pub const CHANGED_FILE_VAR: &str = "CLIQUE_CHANGED_FILE";
+
+/// One change event, for `--json` (printed one per line)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchReport {
+    pub path: String,
+    pub changes: Vec<ItemChange>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Why the file could not be read or parsed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Watched files and what to do when they change
+#[derive(Debug, Clone)]
+pub struct Watch {
+    /// Display path of each file, keyed by the validated path being watched
+    shown: HashMap<String, String>,
+    exec: Option<String>,
+    json: bool,
+}
+
+fn item_change(change: StatusChange) -> ItemChange {
+    match (change.from, change.to) {
+        (Some(from), Some(to)) => ItemChange::StatusChanged {
+            id: change.id,
+            from,
+            to,
+        },
+        (None, to) => ItemChange::Added {
+            id: change.id,
+            status: to.unwrap_or_default(),
+        },
+        (Some(status), None) => ItemChange::Removed {
+            id: change.id,
+            status,
+        },
+    }
+}
+
+impl Watch {
+    /// Validate `files` against the workspace root.
+    pub fn new(
+        workspace: &Workspace,
+        files: &[PathBuf],
+        exec: Option<String>,
+        json: bool,
+    ) -> Result<Self, CliError> {
+        let mut shown = HashMap::new();
+        for file in files {
+            shown.insert(workspace.validate(file)?, file.display().to_string());
+        }
+        Ok(Watch { shown, exec, json })
+    }
+
+    /// What happened in `event`, with the file's current diagnostics
+    pub fn report(&self, event: StatusChangeEvent) -> WatchReport {
+        let shown = self.shown.get(&event.path).unwrap_or(&event.path);
+        let diagnostics = match event.error {
+            Some(_) => Vec::new(),
+            None => read_validated(&event.path, Path::new(shown))
+                .map(|content| match Kind::detect(&content) {
+                    Kind::Workflow => workflow_diagnostics(&content).diagnostics,
+                    Kind::Sprint => sprint_diagnostics(&content).diagnostics,
+                })
+                .unwrap_or_default(),
+        };
+        WatchReport {
+            path: shown.clone(),
+            changes: event.changes.into_iter().map(item_change).collect(),
+            diagnostics,
+            error: event.error,
+        }
+    }
+
+    /// `report` as printed: a JSON line, or the changes and diagnostics
+    pub fn render(&self, report: &WatchReport) -> Result<String, CliError> {
+        if self.json {
+            return Ok(format!("{}\n", serde_json::to_string(report)?));
+        }
+        let mut out = format!("{} changed\n", report.path);
+        if let Some(error) = &report.error {
+            out.push_str(&format!("{}: error: {}\n", report.path, error));
+        } else {
+            out.push_str(&render::changes(&report.changes));
+            out.push_str(&render::diagnostics(&report.path, &report.diagnostics));
+        }
+        Ok(out)
+    }
+
+    /// Run the `--exec` command through the shell, with the changed file in
+    /// [`CHANGED_FILE_VAR`]. Under `--json` its output goes to stderr so
+    /// stdout stays one JSON object per line.
+    pub fn exec(&self, path: &str) -> Option<std::io::Result<ExitStatus>> {
+        let command = self.exec.as_deref()?;
+        let mut shell = if cfg!(windows) {
+            let mut shell = Command::new("cmd");
+            shell.arg("/C");
+            shell
+        } else {
+            let mut shell = Command::new("sh");
+            shell.arg("-c");
+            shell
+        };
+        shell.arg(command).env(CHANGED_FILE_VAR, path);
+        if self.json {
+            shell.stdout(Stdio::from(std::io::stderr()));
+        }
+        Some(shell.status())
+    }
+
+    /// Watch until the process is interrupted.
+    pub fn run(&self) -> Result<String, CliError> {
+        let paths: Vec<String> = self.shown.keys().cloned().collect();
+        let (sender, receiver) = mpsc::channel();
+        let _watcher = watch_status_files(&paths, move |event| {
+            let _ = sender.send(event);
+        })
+        .map_err(CliError::Watch)?;
+        eprintln!(
+            "Watching {} file(s); press Ctrl-C to stop",
+            self.shown.len()
+        );
+
+        for event in receiver {
+            let report = self.report(event);
+            print!("{}", self.render(&report)?);
+            if report.error.is_some() {
+                continue;
+            }
+            match self.exec(&report.path) {
+                Some(Ok(status)) if !status.success() => {
+                    eprintln!("--exec command failed: {}", status)
+                }
+                Some(Err(error)) => eprintln!("--exec command could not start: {}", error),
+                _ => {}
+            }
+        }
+        Ok(String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+    use std::fs;
+
+    const SPRINT: &str = "development_status:\n  epic-1: backlog\n  1-a: review\n";
+
+    fn setup(name: &str, exec: Option<&str>, json: bool) -> (TempDir, Watch, String) {
+        let dir = TempDir::new(&format!("watch-{}", name));
+        fs::write(dir.join("s.yaml"), SPRINT).unwrap();
+        let workspace = Workspace::new(&dir, None);
+        let watch = Watch::new(
+            &workspace,
+            &[PathBuf::from("s.yaml")],
+            exec.map(str::to_string),
+            json,
+        )
+        .unwrap();
+        let path = workspace.validate(Path::new("s.yaml")).unwrap();
+        (dir, watch, path)
+    }
+
+    fn event(path: &str, changes: Vec<StatusChange>, error: Option<&str>) -> StatusChangeEvent {
+        StatusChangeEvent {
+            path: path.to_string(),
+            changes,
+            error: error.map(str::to_string),
+        }
+    }
+
+    fn change(id: &str, from: Option<&str>, to: Option<&str>) -> StatusChange {
+        StatusChange {
+            id: id.to_string(),
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+        }
+    }
+
+    // =========================================================================
+    // Report Tests
+    // =========================================================================
+
+    #[test]
+    fn test_item_change() {
+        assert_eq!(
+            item_change(change("1-a", Some("backlog"), Some("review"))),
+            ItemChange::StatusChanged {
+                id: "1-a".to_string(),
+                from: "backlog".to_string(),
+                to: "review".to_string(),
+            }
+        );
+        assert_eq!(
+            item_change(change("1-b", None, Some("drafted"))),
+            ItemChange::Added {
+                id: "1-b".to_string(),
+                status: "drafted".to_string(),
+            }
+        );
+        assert_eq!(
+            item_change(change("1-c", Some("done"), None)),
+            ItemChange::Removed {
+                id: "1-c".to_string(),
+                status: "done".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_report_and_render() {
+        let (_dir, watch, path) = setup("render", None, false);
+        let changes = vec![change("1-a", Some("backlog"), Some("review"))];
+        let report = watch.report(event(&path, changes, None));
+        assert_eq!(report.path, "s.yaml");
+        assert_eq!(
+            watch.render(&report).unwrap(),
+            "s.yaml changed\n~ 1-a: backlog -> review\n"
+        );
+
+        let report = watch.report(event(&path, vec![], Some("bad YAML")));
+        assert_eq!(
+            watch.render(&report).unwrap(),
+            "s.yaml changed\ns.yaml: error: bad YAML\n"
+        );
+    }
+
+    #[test]
+    fn test_report_revalidates() {
+        let (dir, watch, path) = setup("revalidate", None, true);
+        fs::write(dir.join("s.yaml"), "development_status:\n  1-a: [oops\n").unwrap();
+        let report = watch.report(event(&path, vec![], None));
+        assert!(!report.diagnostics.is_empty());
+
+        let line = watch.render(&report).unwrap();
+        assert!(line.ends_with("}\n"));
+        assert_eq!(line.matches('\n').count(), 1);
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["path"], "s.yaml");
+        assert_eq!(json["diagnostics"][0]["severity"], "error");
+        assert!(json.get("error").is_none());
+    }
+
+    #[test]
+    fn test_refuses_paths_outside_root() {
+        let dir = std::env::temp_dir();
+        let workspace = Workspace::new(&dir.join("clique-watch-root"), None);
+        let error = Watch::new(&workspace, &[PathBuf::from("../x.yaml")], None, false);
+        assert!(matches!(error, Err(CliError::Path(_))));
+    }
+
+    // =========================================================================
+    // Exec Tests
+    // =========================================================================
+
+    #[test]
+    fn test_exec_without_command() {
+        let (_dir, watch, path) = setup("no-exec", None, false);
+        assert!(watch.exec(&path).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exec() {
+        let (_dir, watch, _) = setup("exec", Some("exit 3"), true);
+        assert_eq!(watch.exec("s.yaml").unwrap().unwrap().code(), Some(3));
+
+        let check = format!("test \"${}\" = s.yaml", CHANGED_FILE_VAR);
+        let watch = Watch {
+            exec: Some(check),
+            ..watch
+        };
+        assert!(watch.exec("s.yaml").unwrap().unwrap().success());
+        assert!(!watch.exec("other.yaml").unwrap().unwrap().success());
+    }
+}
@@ -0,0 +1,29 @@
+//! Diff benchmarks between a fixture and a copy with scattered edits.
+
+use clique_core::bench::{generate_workflow_fixture, workflow_item_id};
+use clique_core::edit::unified_diff;
+use clique_core::update_workflow_status;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const WORKFLOW_SIZES: [usize; 3] = [10, 100, 1000];
+
+fn bench_unified_diff(c: &mut Criterion) {
+    let mut group = c.benchmark_group("unified_diff");
+    for items in WORKFLOW_SIZES {
+        let before = generate_workflow_fixture(items);
+        // Touch every tenth item
+        let after = (0..items).step_by(10).fold(before.clone(), |yaml, i| {
+            update_workflow_status(&yaml, &workflow_item_id(i), "skipped").unwrap()
+        });
+        group.bench_with_input(
+            BenchmarkId::from_parameter(items),
+            &(before, after),
+            |b, (before, after)| b.iter(|| unified_diff(black_box(before), black_box(after))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_unified_diff);
+criterion_main!(benches);
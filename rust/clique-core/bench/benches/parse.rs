@@ -0,0 +1,52 @@
+//! Parsing benchmarks over generated fixtures of increasing size.
+
+use clique_core::bench::{
+    generate_sprint_fixture, generate_workflow_fixture, story_id, workflow_item_id,
+};
+use clique_core::{
+    parse_item, parse_sprint_status, parse_story, parse_workflow_metadata, parse_workflow_status,
+};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const WORKFLOW_SIZES: [usize; 3] = [10, 100, 1000];
+const SPRINT_SIZES: [(usize, usize); 3] = [(2, 5), (10, 10), (50, 20)];
+
+fn bench_parse_workflow(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_workflow");
+    for items in WORKFLOW_SIZES {
+        let yaml = generate_workflow_fixture(items);
+        group.throughput(Throughput::Bytes(yaml.len() as u64));
+        group.bench_with_input(BenchmarkId::new("full", items), &yaml, |b, yaml| {
+            b.iter(|| parse_workflow_status(black_box(yaml)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("metadata", items), &yaml, |b, yaml| {
+            b.iter(|| parse_workflow_metadata(black_box(yaml)).unwrap())
+        });
+        let last = workflow_item_id(items - 1);
+        group.bench_with_input(BenchmarkId::new("single_item", items), &yaml, |b, yaml| {
+            b.iter(|| parse_item(black_box(yaml), &last).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_parse_sprint(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_sprint");
+    for (epics, stories) in SPRINT_SIZES {
+        let yaml = generate_sprint_fixture(epics, stories);
+        let size = format!("{}x{}", epics, stories);
+        group.throughput(Throughput::Bytes(yaml.len() as u64));
+        group.bench_with_input(BenchmarkId::new("full", &size), &yaml, |b, yaml| {
+            b.iter(|| parse_sprint_status(black_box(yaml)).unwrap())
+        });
+        let last = story_id(epics, stories);
+        group.bench_with_input(BenchmarkId::new("single_story", &size), &yaml, |b, yaml| {
+            b.iter(|| parse_story(black_box(yaml), &last).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_workflow, bench_parse_sprint);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+//! In-place update benchmarks: the edit lands on the last entry, the worst
+//! case for the line scanner.
+
+use clique_core::bench::{
+    generate_sprint_fixture, generate_workflow_fixture, story_id, workflow_item_id,
+};
+use clique_core::{
+    Operation, Transaction, WorkflowFormat, update_story_status, update_workflow_status,
+    update_workflow_status_with_format,
+};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const WORKFLOW_SIZES: [usize; 3] = [10, 100, 1000];
+const SPRINT_SIZES: [(usize, usize); 3] = [(2, 5), (10, 10), (50, 20)];
+
+fn bench_update_workflow(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_workflow");
+    for items in WORKFLOW_SIZES {
+        let yaml = generate_workflow_fixture(items);
+        let last = workflow_item_id(items - 1);
+        group.bench_with_input(BenchmarkId::new("status", items), &yaml, |b, yaml| {
+            b.iter(|| update_workflow_status(black_box(yaml), &last, "skipped").unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("known_format", items), &yaml, |b, yaml| {
+            b.iter(|| {
+                update_workflow_status_with_format(
+                    black_box(yaml),
+                    WorkflowFormat::New,
+                    &last,
+                    "skipped",
+                )
+                .unwrap()
+            })
+        });
+        let transaction = Transaction::new()
+            .with(Operation::SetWorkflowStatus {
+                item_id: workflow_item_id(0),
+                status: "skipped".to_string(),
+            })
+            .with(Operation::SetWorkflowStatus {
+                item_id: last.clone(),
+                status: "skipped".to_string(),
+            });
+        group.bench_with_input(BenchmarkId::new("transaction", items), &yaml, |b, yaml| {
+            b.iter(|| transaction.apply(black_box(yaml)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_update_sprint(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_sprint");
+    for (epics, stories) in SPRINT_SIZES {
+        let yaml = generate_sprint_fixture(epics, stories);
+        let last = story_id(epics, stories - 1);
+        let size = format!("{}x{}", epics, stories);
+        group.bench_with_input(BenchmarkId::new("status", size), &yaml, |b, yaml| {
+            b.iter(|| update_story_status(black_box(yaml), &last, "done").unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_update_workflow, bench_update_sprint);
+criterion_main!(benches);
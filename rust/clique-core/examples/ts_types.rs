@@ -0,0 +1,22 @@
+//! Write TypeScript declarations for the public data types.
+//!
+//! ```bash
+//! cargo run -p clique-core --example ts_types --features ts-types -- <out-dir>
+//! ```
+//!
+//! Each type becomes `<out-dir>/<Type>.d.ts`; the directory defaults to
+//! `bindings`.
+
+use clique_core::types::typescript_definitions;
+use std::path::PathBuf;
+
+fn main() -> std::io::Result<()> {
+    let out_dir = PathBuf::from(std::env::args().nth(1).unwrap_or_else(|| "bindings".into()));
+    std::fs::create_dir_all(&out_dir)?;
+    for (name, declaration) in typescript_definitions() {
+        let path = out_dir.join(format!("{}.d.ts", name));
+        std::fs::write(&path, declaration)?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+#ifndef CLIQUE_H
+#define CLIQUE_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parse a workflow status file; `ok` holds the WorkflowData.
+//
+// # Safety
+//
+// `yaml` must be null or a valid NUL-terminated string.
+char *clique_parse_workflow(const char *yaml);
+
+// Parse a sprint status file; `ok` holds the SprintData.
+//
+// # Safety
+//
+// `yaml` must be null or a valid NUL-terminated string.
+char *clique_parse_sprint(const char *yaml);
+
+// Set a workflow item's status; `ok` holds the updated YAML.
+//
+// # Safety
+//
+// Each argument must be null or a valid NUL-terminated string.
+char *clique_update_workflow_status(const char *content,
+                                    const char *item_id,
+                                    const char *new_status);
+
+// Set a story's status; `ok` holds the updated YAML.
+//
+// # Safety
+//
+// Each argument must be null or a valid NUL-terminated string.
+char *clique_update_story_status(const char *content, const char *story_id, const char *new_status);
+
+// Free a string returned by this library. Null is ignored.
+//
+// # Safety
+//
+// `ptr` must be null or a string returned by a `clique_*` function that
+// has not been freed yet.
+void clique_string_free(char *ptr);
+
+// The library version as a static string; do not free it.
+const char *clique_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CLIQUE_H */
@@ -0,0 +1,401 @@
+// clique-core/src/adapter.rs
+//! Pluggable workflow file dialects.
+//!
+//! A [`FormatAdapter`] knows one way of writing down workflow items: how to
+//! recognize it, read the items and change one item's status in place. The
+//! three YAML layouts of [`WorkflowFormat`] are adapters like any other, so
+//! a [`FormatRegistry`] parses them exactly as
+//! [`parse_workflow_status`](crate::parse_workflow_status) does; it also
+//! reads markdown [`Tasklist`]s. Other dialects, such as a `kanban.yaml`,
+//! are added with [`FormatRegistry::register`] without touching this crate.
+
+use crate::conflict;
+use crate::limits::{enforce_item_limit, enforce_limits};
+use crate::options::ParseOptions;
+use crate::types::{WorkflowData, WorkflowItem};
+use crate::workflow::{
+    WorkflowError, WorkflowFormat, format_of, normalize_items, tag_items,
+    update_workflow_status_with_format, workflow_data_in_format,
+};
+use serde_yaml::Value;
+
+pub use crate::tasklist::Tasklist;
+
+/// One dialect of workflow status file
+pub trait FormatAdapter {
+    /// Short lowercase name, e.g. `flat`, used to look the adapter up
+    fn name(&self) -> &'static str;
+
+    /// Whether `content` is written in this dialect
+    fn detect(&self, content: &str) -> bool;
+
+    /// The items in `content`, in the order the dialect lists them
+    fn parse_items(&self, content: &str) -> Result<Vec<WorkflowItem>, WorkflowError>;
+
+    /// `content` with the status of `item_id` set to `new_status`, changing
+    /// as little else as the dialect allows
+    fn apply_update(
+        &self,
+        content: &str,
+        item_id: &str,
+        new_status: &str,
+    ) -> Result<String, WorkflowError>;
+
+    /// The whole document; by default the items without any header fields
+    fn parse(&self, content: &str) -> Result<WorkflowData, WorkflowError> {
+        Ok(WorkflowData {
+            last_updated: String::new(),
+            status: String::new(),
+            status_note: None,
+            project: String::new(),
+            project_type: String::new(),
+            selected_track: String::new(),
+            field_type: String::new(),
+            workflow_path: String::new(),
+            items: self.parse_items(content)?,
+            phases: Vec::new(),
+        })
+    }
+}
+
+impl FormatAdapter for WorkflowFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            WorkflowFormat::New => "new",
+            WorkflowFormat::Flat => "flat",
+            WorkflowFormat::Old => "old",
+        }
+    }
+
+    /// The old layout is the parser's fallback, so it claims any document
+    /// that is neither of the others
+    fn detect(&self, content: &str) -> bool {
+        format_of(content).is_ok_and(|format| format == *self)
+    }
+
+    fn parse_items(&self, content: &str) -> Result<Vec<WorkflowItem>, WorkflowError> {
+        self.parse(content).map(|data| data.items)
+    }
+
+    fn apply_update(
+        &self,
+        content: &str,
+        item_id: &str,
+        new_status: &str,
+    ) -> Result<String, WorkflowError> {
+        update_workflow_status_with_format(content, *self, item_id, new_status)
+    }
+
+    fn parse(&self, content: &str) -> Result<WorkflowData, WorkflowError> {
+        let parsed: Value =
+            serde_yaml::from_str(content).map_err(|e| WorkflowError::ParseError(e.to_string()))?;
+        Ok(workflow_data_in_format(&parsed, *self))
+    }
+}
+
+/// Adapters tried in turn until one recognizes a document
+pub struct FormatRegistry {
+    /// In detection order: the latest registered first, built-ins last
+    adapters: Vec<Box<dyn FormatAdapter>>,
+}
+
+impl Default for FormatRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for FormatRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormatRegistry")
+            .field("adapters", &self.names())
+            .finish()
+    }
+}
+
+impl FormatRegistry {
+    /// A registry with markdown tasklists and the three YAML layouts.
+    pub fn new() -> Self {
+        Self {
+            adapters: vec![
+                Box::new(Tasklist),
+                Box::new(WorkflowFormat::New),
+                Box::new(WorkflowFormat::Flat),
+                Box::new(WorkflowFormat::Old),
+            ],
+        }
+    }
+
+    /// A registry without any adapters.
+    pub fn empty() -> Self {
+        Self {
+            adapters: Vec::new(),
+        }
+    }
+
+    /// Register an adapter, replacing any registered adapter with the same
+    /// name. It is tried before every adapter registered earlier, including
+    /// the built-in layouts.
+    pub fn register(&mut self, adapter: impl FormatAdapter + 'static) {
+        self.adapters.retain(|other| other.name() != adapter.name());
+        self.adapters.insert(0, Box::new(adapter));
+    }
+
+    /// Builder form of [`FormatRegistry::register`].
+    pub fn with_adapter(mut self, adapter: impl FormatAdapter + 'static) -> Self {
+        self.register(adapter);
+        self
+    }
+
+    /// Adapter names in detection order
+    pub fn names(&self) -> Vec<&'static str> {
+        self.adapters.iter().map(|adapter| adapter.name()).collect()
+    }
+
+    /// The adapter called `name`
+    pub fn get(&self, name: &str) -> Option<&dyn FormatAdapter> {
+        self.adapters
+            .iter()
+            .find(|adapter| adapter.name() == name)
+            .map(Box::as_ref)
+    }
+
+    /// The first adapter that recognizes `content`
+    pub fn detect(&self, content: &str) -> Option<&dyn FormatAdapter> {
+        self.adapters
+            .iter()
+            .find(|adapter| adapter.detect(content))
+            .map(Box::as_ref)
+    }
+
+    fn detected(&self, content: &str) -> Result<&dyn FormatAdapter, WorkflowError> {
+        self.detect(content)
+            .ok_or_else(|| WorkflowError::UnknownFormat(self.names().join(", ")))
+    }
+
+    /// Parse `content` with the adapter that recognizes it.
+    pub fn parse(&self, content: &str) -> Result<WorkflowData, WorkflowError> {
+        self.parse_with_options(content, &ParseOptions::default())
+    }
+
+    /// [`FormatRegistry::parse`] with preprocessing, limits, merge conflict
+    /// detection, normalization and status tagging applied as by
+    /// [`parse_workflow_status_with_options`](crate::parse_workflow_status_with_options).
+    pub fn parse_with_options(
+        &self,
+        content: &str,
+        options: &ParseOptions,
+    ) -> Result<WorkflowData, WorkflowError> {
+        enforce_limits(content, &options.limits).map_err(WorkflowError::ResourceLimit)?;
+        if let Some(preprocessing) = &options.preprocessing {
+            let content = preprocessing
+                .apply_with_limits(content, &options.limits)
+                .map_err(|e| WorkflowError::Preprocess(e.to_string()))?;
+            let options = ParseOptions {
+                preprocessing: None,
+                ..options.clone()
+            };
+            return self.parse_with_options(&content, &options);
+        }
+        if let Some(sides) = conflict::split(content)
+            && let (Ok(ours), Ok(theirs)) = (
+                self.parse_with_options(&sides.ours, options),
+                self.parse_with_options(&sides.theirs, options),
+            )
+        {
+            return Err(WorkflowError::MergeConflict {
+                ours: Box::new(ours),
+                theirs: Box::new(theirs),
+            });
+        }
+        let mut data = self.detected(content)?.parse(content)?;
+        enforce_item_limit(data.items.len(), &options.limits)
+            .map_err(WorkflowError::ResourceLimit)?;
+        if let Some(aliases) = &options.normalize {
+            normalize_items(&mut data.items, aliases);
+        }
+        if let Some(registry) = &options.registry {
+            tag_items(&mut data.items, registry);
+        }
+        Ok(data)
+    }
+
+    /// Set the status of `item_id` with the adapter that recognizes
+    /// `content`.
+    pub fn update(
+        &self,
+        content: &str,
+        item_id: &str,
+        new_status: &str,
+    ) -> Result<String, WorkflowError> {
+        self.detected(content)?
+            .apply_update(content, item_id, new_status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::text;
+    use crate::types::Phase;
+    use crate::workflow::{parse_workflow_status, update_workflow_status};
+
+    const NEW: &str = "project: Demo\nworkflows:\n  prd:\n    status: required\n";
+    const FLAT: &str = "workflow_status:\n  brainstorm: skipped\n  prd: required\n";
+    const OLD: &str = "workflow_status:\n  - id: prd\n    status: required\n";
+
+    /// `id = status` lines, as some teams keep in a plain text file
+    struct KeyValue;
+
+    impl FormatAdapter for KeyValue {
+        fn name(&self) -> &'static str {
+            "key-value"
+        }
+
+        fn detect(&self, content: &str) -> bool {
+            content.starts_with("# key-value")
+        }
+
+        fn parse_items(&self, content: &str) -> Result<Vec<WorkflowItem>, WorkflowError> {
+            Ok(content
+                .lines()
+                .filter_map(|line| line.split_once(" = "))
+                .map(|(id, status)| WorkflowItem {
+                    id: id.to_string(),
+                    phase: Phase::Number(0),
+                    status: text(status),
+                    agent: None,
+                    command: None,
+                    note: None,
+                    output_file: None,
+                    custom_status: None,
+                    depends_on: Vec::new(),
+                    tracks: Vec::new(),
+                    tags: Vec::new(),
+                    description: None,
+                    due: None,
+                })
+                .collect())
+        }
+
+        fn apply_update(
+            &self,
+            content: &str,
+            item_id: &str,
+            new_status: &str,
+        ) -> Result<String, WorkflowError> {
+            let prefix = format!("{} = ", item_id);
+            if !content.lines().any(|line| line.starts_with(&prefix)) {
+                return Err(WorkflowError::ItemNotFound(item_id.to_string()));
+            }
+            Ok(content
+                .lines()
+                .map(|line| match line.starts_with(&prefix) {
+                    true => format!("{}{}\n", prefix, new_status),
+                    false => format!("{}\n", line),
+                })
+                .collect())
+        }
+    }
+
+    // =========================================================================
+    // Built-in Adapter Tests
+    // =========================================================================
+
+    #[test]
+    fn test_builtins_match_parser() {
+        let registry = FormatRegistry::new();
+        for (content, name) in [(NEW, "new"), (FLAT, "flat"), (OLD, "old")] {
+            assert_eq!(registry.detect(content).unwrap().name(), name);
+            assert_eq!(
+                registry.parse(content).unwrap(),
+                parse_workflow_status(content).unwrap()
+            );
+            assert_eq!(
+                registry.update(content, "prd", "docs/prd.md").unwrap(),
+                update_workflow_status(content, "prd", "docs/prd.md").unwrap()
+            );
+        }
+        assert_eq!(registry.names(), ["tasklist", "new", "flat", "old"]);
+        assert_eq!(registry.parse(NEW).unwrap().project, "Demo");
+    }
+
+    #[test]
+    fn test_builtin_reads_its_own_layout() {
+        assert!(WorkflowFormat::Flat.detect(FLAT));
+        assert!(!WorkflowFormat::New.detect(FLAT));
+        assert!(WorkflowFormat::New.parse_items(FLAT).unwrap().is_empty());
+        assert_eq!(WorkflowFormat::Flat.parse_items(FLAT).unwrap().len(), 2);
+        assert!(matches!(
+            WorkflowFormat::Old.apply_update(OLD, "nope", "done"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+    }
+
+    // =========================================================================
+    // Registry Tests
+    // =========================================================================
+
+    #[test]
+    fn test_custom_adapter() {
+        let registry = FormatRegistry::new().with_adapter(KeyValue);
+        assert_eq!(
+            registry.names(),
+            ["key-value", "tasklist", "new", "flat", "old"]
+        );
+        let content = "# key-value\nprd = required\narch = done\n";
+        let data = registry.parse(content).unwrap();
+        assert_eq!(data.items.len(), 2);
+        assert_eq!(data.items[1].status, "done");
+        assert_eq!(
+            registry.update(content, "prd", "done").unwrap(),
+            "# key-value\nprd = done\narch = done\n"
+        );
+        // Other documents still reach the built-in layouts
+        assert_eq!(registry.detect(FLAT).unwrap().name(), "flat");
+        assert!(registry.get("key-value").is_some());
+    }
+
+    #[test]
+    fn test_parse_with_options() {
+        let registry = FormatRegistry::new().with_adapter(KeyValue);
+        let content = "# key-value\nprd = Completed\n";
+        let options = ParseOptions::default().with_normalize(Default::default());
+        let data = registry.parse_with_options(content, &options).unwrap();
+        assert_eq!(data.items[0].status, "complete");
+
+        let options = ParseOptions::default().with_limits(crate::ParseLimits {
+            max_items: 0,
+            ..Default::default()
+        });
+        assert!(matches!(
+            registry.parse_with_options(content, &options),
+            Err(WorkflowError::ResourceLimit(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_conflict() {
+        let content = "# key-value\n<<<<<<< ours\nprd = done\n=======\nprd = required\n\
+                       >>>>>>> theirs\n";
+        let registry = FormatRegistry::new().with_adapter(KeyValue);
+        assert!(matches!(
+            registry.parse(content),
+            Err(WorkflowError::MergeConflict { ours, .. }) if ours.items[0].status == "done"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        let registry = FormatRegistry::empty().with_adapter(KeyValue);
+        let error = registry.parse(FLAT).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No format adapter recognizes the document; tried key-value"
+        );
+        assert!(registry.update(FLAT, "prd", "done").is_err());
+
+        let registry = registry.with_adapter(KeyValue);
+        assert_eq!(registry.names(), ["key-value"]);
+    }
+}
@@ -0,0 +1,310 @@
+// clique-core/src/aggregate.rs
+//! Aggregation over several projects in one workspace (e.g. a monorepo).
+
+use crate::sprint::is_done;
+use crate::types::{SprintData, StatusClass, Story, WorkflowData, WorkflowItem};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Status files parsed for a single sub-project
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Project {
+    pub workflow: Option<WorkflowData>,
+    pub sprint: Option<SprintData>,
+}
+
+/// Item and story counts for one project or a whole set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStats {
+    pub workflow_items: usize,
+    pub workflow_complete: usize,
+    pub workflow_pending: usize,
+    pub workflow_skipped: usize,
+    pub stories: usize,
+    pub stories_done: usize,
+    /// Story counts keyed by status as written
+    pub stories_by_status: BTreeMap<String, usize>,
+}
+
+impl ProjectStats {
+    fn add(&mut self, other: &ProjectStats) {
+        self.workflow_items += other.workflow_items;
+        self.workflow_complete += other.workflow_complete;
+        self.workflow_pending += other.workflow_pending;
+        self.workflow_skipped += other.workflow_skipped;
+        self.stories += other.stories;
+        self.stories_done += other.stories_done;
+        for (status, count) in &other.stories_by_status {
+            *self.stories_by_status.entry(status.clone()).or_default() += count;
+        }
+    }
+}
+
+impl Project {
+    /// Counts for this project alone.
+    pub fn stats(&self) -> ProjectStats {
+        let mut stats = ProjectStats::default();
+        for item in self.workflow.iter().flat_map(|w| &w.items) {
+            stats.workflow_items += 1;
+            match item.status_class() {
+                StatusClass::Complete => stats.workflow_complete += 1,
+                StatusClass::Pending => stats.workflow_pending += 1,
+                StatusClass::Skipped => stats.workflow_skipped += 1,
+            }
+        }
+        for story in self.stories() {
+            stats.stories += 1;
+            if is_done(story) {
+                stats.stories_done += 1;
+            }
+            *stats
+                .stories_by_status
+                .entry(story.status.to_string())
+                .or_default() += 1;
+        }
+        stats
+    }
+
+    fn stories(&self) -> impl Iterator<Item = &Story> {
+        self.sprint
+            .iter()
+            .flat_map(|s| &s.epics)
+            .flat_map(|e| &e.stories)
+    }
+}
+
+/// Parsed status files for many projects, keyed by sub-project path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectSet {
+    projects: BTreeMap<String, Project>,
+}
+
+impl ProjectSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the workflow status of the project at `path`.
+    pub fn insert_workflow(&mut self, path: &str, data: WorkflowData) {
+        self.projects.entry(path.to_string()).or_default().workflow = Some(data);
+    }
+
+    /// Add or replace the sprint status of the project at `path`.
+    pub fn insert_sprint(&mut self, path: &str, data: SprintData) {
+        self.projects.entry(path.to_string()).or_default().sprint = Some(data);
+    }
+
+    /// Drop a project entirely, returning what was stored for it.
+    pub fn remove(&mut self, path: &str) -> Option<Project> {
+        self.projects.remove(path)
+    }
+
+    /// Drill down into a single project.
+    pub fn project(&self, path: &str) -> Option<&Project> {
+        self.projects.get(path)
+    }
+
+    /// Projects in path order.
+    pub fn projects(&self) -> impl Iterator<Item = (&str, &Project)> {
+        self.projects.iter().map(|(path, p)| (path.as_str(), p))
+    }
+
+    pub fn len(&self) -> usize {
+        self.projects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.projects.is_empty()
+    }
+
+    /// Counts summed over every project.
+    pub fn stats(&self) -> ProjectStats {
+        let mut total = ProjectStats::default();
+        for project in self.projects.values() {
+            total.add(&project.stats());
+        }
+        total
+    }
+
+    /// Counts for each project, in path order.
+    pub fn stats_by_project(&self) -> BTreeMap<&str, ProjectStats> {
+        self.projects()
+            .map(|(path, project)| (path, project.stats()))
+            .collect()
+    }
+
+    /// Workflow items from every project that satisfy `predicate`.
+    pub fn find_items(
+        &self,
+        predicate: impl Fn(&WorkflowItem) -> bool,
+    ) -> Vec<(&str, &WorkflowItem)> {
+        self.projects()
+            .flat_map(|(path, project)| {
+                project
+                    .workflow
+                    .iter()
+                    .flat_map(|w| &w.items)
+                    .map(move |item| (path, item))
+            })
+            .filter(|(_, item)| predicate(item))
+            .collect()
+    }
+
+    /// Stories from every project that satisfy `predicate`.
+    pub fn find_stories(&self, predicate: impl Fn(&Story) -> bool) -> Vec<(&str, &Story)> {
+        self.projects()
+            .flat_map(|(path, project)| project.stories().map(move |story| (path, story)))
+            .filter(|(_, story)| predicate(story))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+    use crate::workflow::parse_workflow_status;
+
+    fn sample_set() -> ProjectSet {
+        let mut set = ProjectSet::new();
+        set.insert_workflow(
+            "apps/web",
+            parse_workflow_status(
+                "workflows:\n  prd:\n    status: complete\n    output_file: docs/prd.md\n  architecture:\n    status: not_started\n",
+            )
+            .unwrap(),
+        );
+        set.insert_sprint(
+            "apps/web",
+            parse_sprint_status(
+                "development_status:\n  epic-1: in-progress\n  1-1-login: done\n  1-2-signup: review\n",
+            )
+            .unwrap(),
+        );
+        set.insert_workflow(
+            "services/api",
+            parse_workflow_status("workflow_status:\n  prd: docs/prd.md\n  ux-design: skipped\n")
+                .unwrap(),
+        );
+        set.insert_sprint(
+            "services/api",
+            parse_sprint_status("development_status:\n  epic-1: backlog\n  1-1-auth: review\n")
+                .unwrap(),
+        );
+        set
+    }
+
+    // =========================================================================
+    // Drill-down Tests
+    // =========================================================================
+
+    #[test]
+    fn test_projects_keyed_by_path() {
+        let set = sample_set();
+        assert_eq!(set.len(), 2);
+        assert!(!set.is_empty());
+        let paths: Vec<&str> = set.projects().map(|(path, _)| path).collect();
+        assert_eq!(paths, vec!["apps/web", "services/api"]);
+
+        let web = set.project("apps/web").unwrap();
+        assert_eq!(web.workflow.as_ref().unwrap().items.len(), 2);
+        assert!(web.sprint.is_some());
+        assert!(set.project("missing").is_none());
+    }
+
+    #[test]
+    fn test_insert_replaces_and_remove() {
+        let mut set = sample_set();
+        set.insert_workflow(
+            "apps/web",
+            parse_workflow_status("workflows:\n  prd:\n    status: skipped\n").unwrap(),
+        );
+        let web = set.project("apps/web").unwrap();
+        assert_eq!(web.workflow.as_ref().unwrap().items.len(), 1);
+        assert!(web.sprint.is_some(), "Sprint data should be kept");
+
+        assert!(set.remove("apps/web").is_some());
+        assert!(set.remove("apps/web").is_none());
+        assert_eq!(set.len(), 1);
+    }
+
+    // =========================================================================
+    // Stats Tests
+    // =========================================================================
+
+    #[test]
+    fn test_project_stats() {
+        let set = sample_set();
+        let web = set.project("apps/web").unwrap().stats();
+        assert_eq!(web.workflow_items, 2);
+        assert_eq!(web.workflow_complete, 1);
+        assert_eq!(web.workflow_pending, 1);
+        assert_eq!(web.stories, 2);
+        assert_eq!(web.stories_done, 1);
+
+        let by_project = set.stats_by_project();
+        assert_eq!(by_project["apps/web"], web);
+        assert_eq!(by_project["services/api"].workflow_skipped, 1);
+    }
+
+    #[test]
+    fn test_combined_stats() {
+        let stats = sample_set().stats();
+        assert_eq!(stats.workflow_items, 4);
+        assert_eq!(stats.workflow_complete, 2);
+        assert_eq!(stats.workflow_pending, 1);
+        assert_eq!(stats.workflow_skipped, 1);
+        assert_eq!(stats.stories, 3);
+        assert_eq!(stats.stories_done, 1);
+        assert_eq!(stats.stories_by_status["review"], 2);
+        assert_eq!(stats.stories_by_status["done"], 1);
+    }
+
+    #[test]
+    fn test_empty_set_stats() {
+        let set = ProjectSet::new();
+        assert!(set.is_empty());
+        assert_eq!(set.stats(), ProjectStats::default());
+    }
+
+    #[test]
+    fn test_stats_serialization() {
+        let json = serde_json::to_string(&sample_set().stats()).unwrap();
+        assert!(json.contains("\"workflowItems\":4"));
+        assert!(json.contains("\"storiesByStatus\":{"));
+    }
+
+    // =========================================================================
+    // Cross-project Query Tests
+    // =========================================================================
+
+    #[test]
+    fn test_find_items_across_projects() {
+        let set = sample_set();
+        let prds: Vec<&str> = set
+            .find_items(|item| item.id == "prd")
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(prds, vec!["apps/web", "services/api"]);
+
+        let pending = set.find_items(|item| item.status_class() == StatusClass::Pending);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].1.id, "architecture");
+    }
+
+    #[test]
+    fn test_find_stories_across_projects() {
+        let set = sample_set();
+        let in_review: Vec<(&str, &str)> = set
+            .find_stories(|story| story.status == "review")
+            .into_iter()
+            .map(|(path, story)| (path, story.id.as_str()))
+            .collect();
+        assert_eq!(
+            in_review,
+            vec![("apps/web", "1-2-signup"), ("services/api", "1-1-auth")]
+        );
+    }
+}
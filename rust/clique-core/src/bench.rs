@@ -0,0 +1,125 @@
+// clique-core/src/bench.rs
+//! Representative fixtures for benchmarks.
+//!
+//! The benches in `clique-core/bench` measure parsing, updates and diffs
+//! against these generated documents, so numbers stay comparable between
+//! runs and between the current editor and any rewrite of it. The documents
+//! are deterministic and mix the shapes real files use: completed items
+//! with output files, notes and dependencies, and stories written both as
+//! bare statuses and as mappings with extended fields.
+
+use std::fmt::Write;
+
+/// Statuses cycled through by workflow fixture items
+const WORKFLOW_STATUSES: [&str; 4] = ["complete", "not_started", "skipped", "not_started"];
+
+/// Statuses cycled through by sprint fixture stories
+const STORY_STATUSES: [&str; 5] = ["done", "review", "in-progress", "ready-for-dev", "backlog"];
+
+/// ID of the `index`th item of [`generate_workflow_fixture`]
+pub fn workflow_item_id(index: usize) -> String {
+    format!("workflow-{}", index)
+}
+
+/// ID of a story of [`generate_sprint_fixture`]; epics and stories count
+/// from 1
+pub fn story_id(epic: usize, story: usize) -> String {
+    format!("{}-{}-story", epic, story)
+}
+
+/// A new-format workflow document with `items` items.
+pub fn generate_workflow_fixture(items: usize) -> String {
+    let mut yaml = String::from(
+        "last_updated: 2025-12-01\nstatus: active\nproject: Bench Project\n\
+         project_type: software\nselected_track: method\nfield_type: greenfield\n\
+         workflow_path: .bmad/workflows/method.yaml\nworkflows:\n",
+    );
+    for i in 0..items {
+        let id = workflow_item_id(i);
+        let status = WORKFLOW_STATUSES[i % WORKFLOW_STATUSES.len()];
+        let _ = writeln!(yaml, "  {}:\n    status: {}", id, status);
+        if status == "complete" {
+            let _ = writeln!(yaml, "    output_file: docs/{}.md", id);
+        }
+        if i % 3 == 0 {
+            let _ = writeln!(yaml, "    notes: \"Reviewed: item {}\"", i);
+        }
+        if i % 5 == 4 {
+            let _ = writeln!(yaml, "    depends_on: [{}]", workflow_item_id(i - 1));
+        }
+    }
+    yaml
+}
+
+/// A sprint document with `epics` epics of `stories` stories each, plus a
+/// retrospective per epic.
+pub fn generate_sprint_fixture(epics: usize, stories: usize) -> String {
+    let mut yaml = String::from(
+        "project: Bench Sprint\nproject_key: BEN\nsprint_goal: Measure everything\n\
+         development_status:\n",
+    );
+    for epic in 1..=epics {
+        let status = if epic == 1 { "in-progress" } else { "backlog" };
+        let _ = writeln!(yaml, "  epic-{}: {}", epic, status);
+        for story in 1..=stories {
+            let id = story_id(epic, story);
+            let status = STORY_STATUSES[(epic + story) % STORY_STATUSES.len()];
+            if story % 4 == 0 {
+                let _ = writeln!(
+                    yaml,
+                    "  {}:\n    status: {}\n    assignee: dev-{}\n    tags: [bench, epic-{}]",
+                    id,
+                    status,
+                    story % 3,
+                    epic
+                );
+            } else {
+                let _ = writeln!(yaml, "  {}: {}", id, status);
+            }
+        }
+        let _ = writeln!(yaml, "  epic-{}-retrospective: optional", epic);
+    }
+    yaml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+    use crate::workflow::parse_workflow_status;
+
+    // =========================================================================
+    // Fixture Tests
+    // =========================================================================
+
+    #[test]
+    fn test_workflow_fixture_parses() {
+        let data = parse_workflow_status(&generate_workflow_fixture(100)).unwrap();
+        assert_eq!(data.project, "Bench Project");
+        assert_eq!(data.items.len(), 100);
+        let item = |i: usize| {
+            let id = workflow_item_id(i);
+            data.items.iter().find(|item| item.id == id).unwrap()
+        };
+        assert_eq!(item(0).status, "docs/workflow-0.md");
+        assert_eq!(item(4).depends_on, vec![workflow_item_id(3)]);
+        assert!(parse_workflow_status(&generate_workflow_fixture(0)).is_ok());
+    }
+
+    #[test]
+    fn test_sprint_fixture_parses() {
+        let data = parse_sprint_status(&generate_sprint_fixture(3, 8)).unwrap();
+        assert_eq!(data.epics.len(), 3);
+        assert!(data.epics.iter().all(|epic| epic.stories.len() == 8));
+        let story = &data.epics[1].stories[3];
+        assert_eq!(story.id, story_id(2, 4));
+        assert_eq!(story.assignee.as_deref(), Some("dev-1"));
+        assert_eq!(story.tags, vec!["bench", "epic-2"]);
+    }
+
+    #[test]
+    fn test_fixtures_are_deterministic() {
+        assert_eq!(generate_workflow_fixture(20), generate_workflow_fixture(20));
+        assert_eq!(generate_sprint_fixture(2, 5), generate_sprint_fixture(2, 5));
+    }
+}
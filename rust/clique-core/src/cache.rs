@@ -0,0 +1,300 @@
+// clique-core/src/cache.rs
+//! Memoized parsing of unchanged documents.
+//!
+//! Tree refreshes re-read the same status files over and over. A
+//! [`ParseCache`] keys parse results by a hash of the content, so parsing an
+//! unchanged document again is a lookup that hands back a shared
+//! [`Arc`]. With the `fs` feature, files can also be looked up by path and
+//! modification time without being read at all.
+
+use crate::sprint::{SprintError, parse_sprint_status};
+use crate::types::{SprintData, WorkflowData};
+use crate::workflow::{WorkflowError, parse_workflow_status};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+#[cfg(feature = "fs")]
+use std::{path::PathBuf, time::SystemTime};
+
+/// Parsed documents kept per kind unless a capacity is given
+pub const DEFAULT_CAPACITY: usize = 32;
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Results of one parser by content hash, evicting the oldest entry first
+#[derive(Debug)]
+struct Memo<T> {
+    entries: HashMap<u64, Arc<T>>,
+    order: VecDeque<u64>,
+}
+
+impl<T> Default for Memo<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> Memo<T> {
+    fn get_or_parse<E>(
+        &mut self,
+        hash: u64,
+        capacity: usize,
+        parse: impl FnOnce() -> Result<T, E>,
+    ) -> Result<Arc<T>, E> {
+        if let Some(hit) = self.entries.get(&hash) {
+            return Ok(Arc::clone(hit));
+        }
+        let parsed = Arc::new(parse()?);
+        if capacity > 0 {
+            while self.order.len() >= capacity {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.entries.remove(&oldest);
+                }
+            }
+            self.entries.insert(hash, Arc::clone(&parsed));
+            self.order.push_back(hash);
+        }
+        Ok(parsed)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+/// Cache of parsed workflow and sprint documents.
+///
+/// Only successful parses are cached; documents that fail are parsed (and
+/// fail) again on every call.
+#[derive(Debug)]
+pub struct ParseCache {
+    capacity: usize,
+    workflows: Memo<WorkflowData>,
+    sprints: Memo<SprintData>,
+    /// Modification time and content hash of files read by path
+    #[cfg(feature = "fs")]
+    files: HashMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl Default for ParseCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl ParseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache keeping at most `capacity` documents of each kind.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            workflows: Memo::default(),
+            sprints: Memo::default(),
+            #[cfg(feature = "fs")]
+            files: HashMap::new(),
+        }
+    }
+
+    /// [`parse_workflow_status`], reusing the result for identical content.
+    pub fn parse_workflow(&mut self, content: &str) -> Result<Arc<WorkflowData>, WorkflowError> {
+        self.workflows
+            .get_or_parse(content_hash(content), self.capacity, || {
+                parse_workflow_status(content)
+            })
+    }
+
+    /// [`parse_sprint_status`], reusing the result for identical content.
+    pub fn parse_sprint(&mut self, content: &str) -> Result<Arc<SprintData>, SprintError> {
+        self.sprints
+            .get_or_parse(content_hash(content), self.capacity, || {
+                parse_sprint_status(content)
+            })
+    }
+
+    /// Number of cached documents of both kinds
+    pub fn len(&self) -> usize {
+        self.workflows.entries.len() + self.sprints.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every cached result.
+    pub fn clear(&mut self) {
+        self.workflows.clear();
+        self.sprints.clear();
+        #[cfg(feature = "fs")]
+        self.files.clear();
+    }
+
+    /// Content hash of `path` when it is known and its modification time
+    /// has not changed since it was last read
+    #[cfg(feature = "fs")]
+    fn unchanged_file(&self, path: &str) -> Option<u64> {
+        let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+        let (seen, hash) = self.files.get(&PathBuf::from(path))?;
+        (*seen == modified).then_some(*hash)
+    }
+
+    /// Read `path`, remembering its modification time and content hash.
+    #[cfg(feature = "fs")]
+    fn read_file(&mut self, path: &str) -> std::io::Result<(String, u64)> {
+        let modified = std::fs::metadata(path)?.modified()?;
+        let content = std::fs::read_to_string(path)?;
+        let hash = content_hash(&content);
+        self.files.insert(PathBuf::from(path), (modified, hash));
+        Ok((content, hash))
+    }
+
+    /// Parse a workflow status file, skipping the read entirely when its
+    /// modification time is unchanged and its result is still cached.
+    #[cfg(feature = "fs")]
+    pub fn parse_workflow_file(&mut self, path: &str) -> Result<Arc<WorkflowData>, WorkflowError> {
+        if let Some(hit) = self
+            .unchanged_file(path)
+            .and_then(|hash| self.workflows.entries.get(&hash))
+        {
+            return Ok(Arc::clone(hit));
+        }
+        let (content, hash) = self
+            .read_file(path)
+            .map_err(|e| WorkflowError::Io(format!("{}: {}", path, e)))?;
+        self.workflows
+            .get_or_parse(hash, self.capacity, || parse_workflow_status(&content))
+    }
+
+    /// Parse a sprint status file; see [`ParseCache::parse_workflow_file`].
+    #[cfg(feature = "fs")]
+    pub fn parse_sprint_file(&mut self, path: &str) -> Result<Arc<SprintData>, SprintError> {
+        if let Some(hit) = self
+            .unchanged_file(path)
+            .and_then(|hash| self.sprints.entries.get(&hash))
+        {
+            return Ok(Arc::clone(hit));
+        }
+        let (content, hash) = self
+            .read_file(path)
+            .map_err(|e| SprintError::Io(format!("{}: {}", path, e)))?;
+        self.sprints
+            .get_or_parse(hash, self.capacity, || parse_sprint_status(&content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKFLOW_YAML: &str = "workflows:\n  prd:\n    status: complete\n";
+    const SPRINT_YAML: &str = "development_status:\n  epic-1: in-progress\n  1-login: review\n";
+
+    // =========================================================================
+    // Content Cache Tests
+    // =========================================================================
+
+    #[test]
+    fn test_cache_reuses_unchanged_content() {
+        let mut cache = ParseCache::new();
+        let first = cache.parse_workflow(WORKFLOW_YAML).unwrap();
+        let second = cache.parse_workflow(WORKFLOW_YAML).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let changed = cache
+            .parse_workflow("workflows:\n  prd:\n    status: not_started\n")
+            .unwrap();
+        assert!(!Arc::ptr_eq(&first, &changed));
+        assert_ne!(changed.items[0].status, first.items[0].status);
+
+        let sprint = cache.parse_sprint(SPRINT_YAML).unwrap();
+        assert!(Arc::ptr_eq(
+            &sprint,
+            &cache.parse_sprint(SPRINT_YAML).unwrap()
+        ));
+        assert_eq!(cache.len(), 3);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(!Arc::ptr_eq(
+            &first,
+            &cache.parse_workflow(WORKFLOW_YAML).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_cache_does_not_store_errors() {
+        let mut cache = ParseCache::new();
+        assert!(cache.parse_workflow("workflows: [unclosed").is_err());
+        assert!(cache.parse_sprint("development_status: [unclosed").is_err());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_capacity() {
+        let mut cache = ParseCache::with_capacity(2);
+        let docs: Vec<String> = (0..3)
+            .map(|i| format!("workflows:\n  item-{}:\n    status: complete\n", i))
+            .collect();
+        let first = cache.parse_workflow(&docs[0]).unwrap();
+        cache.parse_workflow(&docs[1]).unwrap();
+        cache.parse_workflow(&docs[2]).unwrap();
+        assert_eq!(cache.len(), 2);
+        // The oldest entry was evicted and is parsed afresh
+        assert!(!Arc::ptr_eq(
+            &first,
+            &cache.parse_workflow(&docs[0]).unwrap()
+        ));
+
+        let mut disabled = ParseCache::with_capacity(0);
+        disabled.parse_sprint(SPRINT_YAML).unwrap();
+        assert!(disabled.is_empty());
+    }
+
+    // =========================================================================
+    // File Cache Tests
+    // =========================================================================
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_cache_files_by_mtime() {
+        let dir = crate::testing::TempDir::new("cache");
+        let path = dir.join("sprint-status.yaml");
+        let path_str = path.to_string_lossy().to_string();
+        std::fs::write(&path, SPRINT_YAML).unwrap();
+
+        let mut cache = ParseCache::new();
+        let first = cache.parse_sprint_file(&path_str).unwrap();
+        assert!(Arc::ptr_eq(
+            &first,
+            &cache.parse_sprint_file(&path_str).unwrap()
+        ));
+        // The same content parsed directly shares the entry
+        assert!(Arc::ptr_eq(
+            &first,
+            &cache.parse_sprint(SPRINT_YAML).unwrap()
+        ));
+
+        std::fs::write(&path, "development_status:\n  epic-1: done\n").unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+        let changed = cache.parse_sprint_file(&path_str).unwrap();
+        assert_eq!(changed.epics[0].status, "done");
+
+        assert!(matches!(
+            cache.parse_workflow_file(&dir.join("missing.yaml").to_string_lossy()),
+            Err(WorkflowError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+// clique-core/src/config.rs
+//! Project configuration from `.clique.toml`.
+//!
+//! Teams whose conventions differ from the BMad defaults describe them once
+//! in the workspace root instead of on every call:
+//!
+//! ```toml
+//! [status-aliases]
+//! shipped = "done"
+//!
+//! [phases]
+//! security-review = 3
+//!
+//! [agents]
+//! security-review = "sec"
+//!
+//! [lint.rules]
+//! empty-epic = "off"
+//!
+//! [paths]
+//! deny = ["secrets/**"]
+//!
+//! [files]
+//! sprint = "planning/sprint-status.yaml"
+//! ```
+//!
+//! [`parse`] reads a document and touches no filesystem, so it also serves
+//! the WASM bindings, which receive the file's content from the extension;
+//! [`load`] reads it from a workspace root. The result feeds the parsers
+//! ([`Config::settings`]), the [`Linter`] ([`Config::linter`]) and path
+//! checks ([`Config::path_policy`]).
+
+use crate::diagnostics::line_number;
+use crate::lint::{LintConfig, Linter};
+use crate::policy::PathPolicy;
+use crate::settings::Settings;
+use crate::types::StatusAliases;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Name of the configuration file in the workspace root
+pub const CONFIG_FILE: &str = ".clique.toml";
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read {CONFIG_FILE}: {0}")]
+    ReadError(String),
+    #[error("Invalid {CONFIG_FILE}: {0}")]
+    ParseError(String),
+}
+
+/// Extra deny and allow patterns for edits, on top of
+/// [`crate::DEFAULT_DENY_PATTERNS`]; see [`PathPolicy`] for the syntax
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct PathConfig {
+    pub deny: Vec<String>,
+    pub allow: Vec<String>,
+    /// Keep the built-in deny patterns
+    pub default_deny: bool,
+}
+
+impl Default for PathConfig {
+    fn default() -> Self {
+        Self {
+            deny: Vec::new(),
+            allow: Vec::new(),
+            default_deny: true,
+        }
+    }
+}
+
+/// Status file locations relative to the workspace root, for tools that
+/// look for them by name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct FileConfig {
+    pub workflow: Option<String>,
+    pub sprint: Option<String>,
+}
+
+/// Everything `.clique.toml` can set; missing tables keep the defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct Config {
+    /// Team-specific status words mapped to canonical statuses, on top of
+    /// the built-in aliases. Any entry turns on status normalization.
+    pub status_aliases: BTreeMap<String, String>,
+    /// Phase number by workflow ID, overriding the built-in BMad map
+    pub phases: BTreeMap<String, i32>,
+    /// Agent by workflow ID, overriding the built-in BMad map
+    pub agents: BTreeMap<String, String>,
+    pub lint: LintConfig,
+    pub paths: PathConfig,
+    pub files: FileConfig,
+}
+
+impl Config {
+    /// Parser settings carrying the aliases, phases and agents, with default
+    /// limits and sort order
+    pub fn settings(&self) -> Settings {
+        let aliases = (!self.status_aliases.is_empty()).then(|| {
+            self.status_aliases
+                .iter()
+                .fold(StatusAliases::default(), |table, (alias, canonical)| {
+                    table.with_alias(alias, canonical)
+                })
+        });
+        Settings {
+            aliases,
+            phase_map: self.phases.clone().into_iter().collect(),
+            agent_map: self.agents.clone().into_iter().collect(),
+            ..Settings::default()
+        }
+    }
+
+    /// The built-in lint rules at the configured levels
+    pub fn linter(&self) -> Linter {
+        Linter::new().with_config(self.lint.clone())
+    }
+
+    /// A policy for `workspace_root` with the configured patterns
+    pub fn path_policy(&self, workspace_root: &str) -> PathPolicy {
+        let policy = PathPolicy::new(workspace_root);
+        let policy = if self.paths.default_deny {
+            policy
+        } else {
+            policy.without_denies()
+        };
+        let policy = self
+            .paths
+            .deny
+            .iter()
+            .fold(policy, |policy, pattern| policy.deny(pattern));
+        self.paths
+            .allow
+            .iter()
+            .fold(policy, |policy, pattern| policy.allow(pattern))
+    }
+}
+
+/// Parse the content of a `.clique.toml` file.
+///
+/// Unknown keys are refused, so a misspelled table does not go unnoticed.
+pub fn parse(content: &str) -> Result<Config, ConfigError> {
+    toml::from_str(content).map_err(|e| {
+        let message = e.message().trim_end().to_string();
+        ConfigError::ParseError(match e.span() {
+            Some(span) => format!("line {}: {}", line_number(content, span.start), message),
+            None => message,
+        })
+    })
+}
+
+/// Read `.clique.toml` from `workspace_root`; the defaults when there is
+/// none.
+#[cfg(feature = "fs")]
+pub fn load(workspace_root: impl AsRef<std::path::Path>) -> Result<Config, ConfigError> {
+    match std::fs::read_to_string(workspace_root.as_ref().join(CONFIG_FILE)) {
+        Ok(content) => parse(&content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(ConfigError::ReadError(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::RuleLevel;
+    use crate::types::Phase;
+
+    const CONFIG: &str = r#"
+[status-aliases]
+shipped = "done"
+
+[phases]
+security-review = 3
+
+[agents]
+security-review = "sec"
+
+[lint]
+known-statuses = ["parked"]
+
+[lint.rules]
+empty-epic = "off"
+
+[paths]
+deny = ["secrets/**"]
+
+[files]
+sprint = "planning/sprint-status.yaml"
+"#;
+
+    // =========================================================================
+    // Parse Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse() {
+        let config = parse(CONFIG).unwrap();
+        assert_eq!(config.status_aliases["shipped"], "done");
+        assert_eq!(config.phases["security-review"], 3);
+        assert_eq!(config.lint.rules["empty-epic"], RuleLevel::Off);
+        assert_eq!(config.lint.known_statuses, ["parked"]);
+        assert!(config.paths.default_deny);
+        assert_eq!(
+            config.files.sprint.as_deref(),
+            Some("planning/sprint-status.yaml")
+        );
+        assert_eq!(config.files.workflow, None);
+
+        assert_eq!(parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("[status-alias]\nshipped = \"done\"\n").unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid .clique.toml: line 1: ")
+        );
+        assert!(matches!(
+            parse("[phases]\nprd = \"two\"\n"),
+            Err(ConfigError::ParseError(message)) if message.starts_with("line 2: ")
+        ));
+        assert!(parse("[paths\n").is_err());
+    }
+
+    // =========================================================================
+    // Consumer Tests
+    // =========================================================================
+
+    #[test]
+    fn test_settings() {
+        let settings = parse(CONFIG).unwrap().settings();
+        let yaml = "workflow_status:\n  security-review: shipped\n  prd: required\n";
+        let mut data =
+            crate::parse_workflow_status_with_options(yaml, &settings.parse_options()).unwrap();
+        settings.apply(&mut data);
+        let item = &data.items[1];
+        assert_eq!(item.id, "security-review");
+        assert_eq!(item.status, "complete");
+        assert_eq!(item.phase, Phase::Number(3));
+        assert_eq!(item.agent.as_deref(), Some("sec"));
+
+        assert_eq!(Config::default().settings(), Settings::default());
+    }
+
+    #[test]
+    fn test_linter_and_path_policy() {
+        let config = parse(CONFIG).unwrap();
+        let report = config
+            .linter()
+            .lint_sprint(
+                "development_status:\n  epic-1: backlog\n  epic-2: backlog\n  1-1-a: parkd\n",
+            )
+            .unwrap();
+        assert!(report.by_rule("empty-epic").is_empty());
+        assert_eq!(report.by_rule("status-typo").len(), 1);
+
+        let policy = config.path_policy("/ws");
+        assert!(policy.validate("/ws/secrets/key.yaml").is_err());
+        assert!(policy.validate("/ws/.git/config").is_err());
+        assert!(policy.validate("/ws/sprint-status.yaml").is_ok());
+
+        let open = parse("[paths]\ndefault-deny = false\nallow = [\"**/*.yaml\"]\n").unwrap();
+        let policy = open.path_policy("/ws");
+        assert!(policy.validate("/ws/.git/a.yaml").is_ok());
+        assert!(policy.validate("/ws/notes.md").is_err());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_load() {
+        let dir = crate::testing::TempDir::new("config");
+        assert_eq!(load(&dir).unwrap(), Config::default());
+        std::fs::write(dir.join(CONFIG_FILE), CONFIG).unwrap();
+        assert_eq!(load(&dir).unwrap(), parse(CONFIG).unwrap());
+        std::fs::write(dir.join(CONFIG_FILE), "oops").unwrap();
+        assert!(matches!(load(&dir), Err(ConfigError::ParseError(_))));
+    }
+}
@@ -0,0 +1,291 @@
+// clique-core/src/conflict.rs
+//! Status files left with git merge conflict markers.
+//!
+//! A failed merge leaves `<<<<<<<` / `=======` / `>>>>>>>` blocks in the
+//! file, which no YAML parser accepts. [`split`] recovers both sides (and
+//! the common ancestor, with `merge.conflictStyle=diff3`), the parse
+//! functions report them as a `MergeConflict` error, and [`resolve_with`]
+//! writes a resolved file.
+
+use crate::merge::{SprintMerge, merge_sprint};
+use crate::sprint::SprintError;
+
+/// Both versions of a conflicted file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictSides {
+    /// The file with our side of every conflict
+    pub ours: String,
+    /// The file as in the common ancestor; only with diff3-style markers
+    pub base: Option<String>,
+    /// The file with their side of every conflict
+    pub theirs: String,
+}
+
+/// How [`resolve_with`] settles the conflicts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Keep our side of every conflict
+    Ours,
+    /// Keep their side of every conflict
+    Theirs,
+    /// Merge sprint files entry by entry with [`merge_sprint`]
+    Merge,
+}
+
+/// Which part of a conflict block a line belongs to
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Outside,
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// Whether `line` is the conflict marker `marker`, optionally followed by a
+/// label such as `HEAD` or a branch name
+fn is_marker(line: &str, marker: &str) -> bool {
+    let line = line.trim_end_matches(['\n', '\r']);
+    line.strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+/// Whether `content` contains the start of a conflict block
+pub fn has_conflict_markers(content: &str) -> bool {
+    content.lines().any(|line| is_marker(line, "<<<<<<<"))
+}
+
+/// Both sides of a conflicted file; `None` when it has no conflict blocks
+/// or a block is left unfinished.
+pub fn split(content: &str) -> Option<ConflictSides> {
+    let mut ours = String::new();
+    let mut base = String::new();
+    let mut theirs = String::new();
+    let mut section = Section::Outside;
+    let mut blocks = 0;
+    let mut blocks_with_base = 0;
+    for line in content.split_inclusive('\n') {
+        let marker = match section {
+            Section::Outside if is_marker(line, "<<<<<<<") => {
+                blocks += 1;
+                Some(Section::Ours)
+            }
+            Section::Ours if is_marker(line, "|||||||") => {
+                blocks_with_base += 1;
+                Some(Section::Base)
+            }
+            Section::Ours | Section::Base if is_marker(line, "=======") => Some(Section::Theirs),
+            Section::Theirs if is_marker(line, ">>>>>>>") => Some(Section::Outside),
+            _ => None,
+        };
+        match (marker, section) {
+            (Some(next), _) => section = next,
+            (None, Section::Outside) => {
+                ours.push_str(line);
+                base.push_str(line);
+                theirs.push_str(line);
+            }
+            (None, Section::Ours) => ours.push_str(line),
+            (None, Section::Base) => base.push_str(line),
+            (None, Section::Theirs) => theirs.push_str(line),
+        }
+    }
+    (blocks > 0 && section == Section::Outside).then(|| ConflictSides {
+        ours,
+        base: (blocks_with_base == blocks).then_some(base),
+        theirs,
+    })
+}
+
+/// Resolve the conflicts in `content`.
+///
+/// [`Strategy::Ours`] and [`Strategy::Theirs`] work on any file.
+/// [`Strategy::Merge`] needs a sprint file: entries only one side changed
+/// are taken from that side, and entries both changed differently keep
+/// ours and are listed as conflicts. Without a diff3 base, an entry present
+/// on both sides with different statuses counts as changed by both.
+/// Content without conflict markers is returned unchanged.
+pub fn resolve_with(content: &str, strategy: Strategy) -> Result<SprintMerge, SprintError> {
+    let Some(sides) = split(content) else {
+        return Ok(SprintMerge {
+            content: content.to_string(),
+            conflicts: Vec::new(),
+        });
+    };
+    let resolved = |content: String| SprintMerge {
+        content,
+        conflicts: Vec::new(),
+    };
+    match strategy {
+        Strategy::Ours => Ok(resolved(sides.ours)),
+        Strategy::Theirs => Ok(resolved(sides.theirs)),
+        Strategy::Merge => {
+            let is_sprint = |side: &str| {
+                side.lines()
+                    .any(|line| line.starts_with("development_status:"))
+            };
+            if !is_sprint(&sides.ours) || !is_sprint(&sides.theirs) {
+                return Err(SprintError::UpdateError(
+                    "Only sprint files can be merged entry by entry".to_string(),
+                ));
+            }
+            merge_sprint(
+                sides.base.as_deref().unwrap_or_default(),
+                &sides.ours,
+                &sides.theirs,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+    use crate::workflow::{WorkflowError, parse_workflow_status};
+
+    const SPRINT: &str = "development_status:\n  epic-1: in-progress\n\
+                          <<<<<<< HEAD\n  1-a: done\n  1-b: backlog\n\
+                          =======\n  1-a: review\n  1-c: backlog\n\
+                          >>>>>>> feature/stories\n";
+
+    const DIFF3: &str = "development_status:\n  epic-1: in-progress\n\
+                         <<<<<<< HEAD\n  1-a: done\n  1-b: backlog\n\
+                         ||||||| base\n  1-a: backlog\n  1-b: backlog\n\
+                         =======\n  1-a: backlog\n  1-b: done\n\
+                         >>>>>>> other\n";
+
+    // =========================================================================
+    // Split Tests
+    // =========================================================================
+
+    #[test]
+    fn test_split() {
+        let sides = split(SPRINT).unwrap();
+        assert_eq!(
+            sides.ours,
+            "development_status:\n  epic-1: in-progress\n  1-a: done\n  1-b: backlog\n"
+        );
+        assert_eq!(
+            sides.theirs,
+            "development_status:\n  epic-1: in-progress\n  1-a: review\n  1-c: backlog\n"
+        );
+        assert_eq!(sides.base, None);
+
+        let sides = split(DIFF3).unwrap();
+        assert_eq!(
+            sides.base.as_deref(),
+            Some("development_status:\n  epic-1: in-progress\n  1-a: backlog\n  1-b: backlog\n")
+        );
+    }
+
+    #[test]
+    fn test_split_rejects_non_conflicts() {
+        assert_eq!(split("development_status:\n  1-a: done\n"), None);
+        // Unfinished block
+        assert_eq!(split("<<<<<<< HEAD\na: 1\n=======\na: 2\n"), None);
+        // Marker-like text that is not a marker
+        assert!(!has_conflict_markers("note: <<<<<<< inline\n<<<<<<<<\n"));
+        assert!(has_conflict_markers("a: 1\r\n<<<<<<<\r\n"));
+    }
+
+    #[test]
+    fn test_split_crlf() {
+        let sides =
+            split("a: 1\r\n<<<<<<< HEAD\r\nb: 2\r\n=======\r\nb: 3\r\n>>>>>>> x\r\n").unwrap();
+        assert_eq!(sides.ours, "a: 1\r\nb: 2\r\n");
+        assert_eq!(sides.theirs, "a: 1\r\nb: 3\r\n");
+    }
+
+    // =========================================================================
+    // Parse Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_reports_merge_conflict() {
+        match parse_sprint_status(SPRINT) {
+            Err(SprintError::MergeConflict { ours, theirs }) => {
+                assert_eq!(ours.epics[0].stories[0].status.to_string(), "done");
+                assert_eq!(theirs.epics[0].stories[1].id, "1-c");
+            }
+            other => panic!("expected a merge conflict, got {:?}", other),
+        }
+
+        let workflow = "workflow_status:\n<<<<<<< HEAD\n  prd: docs/prd.md\n=======\n  \
+                        prd: required\n>>>>>>> other\n";
+        match parse_workflow_status(workflow) {
+            Err(WorkflowError::MergeConflict { ours, theirs }) => {
+                assert_eq!(ours.items[0].status.to_string(), "docs/prd.md");
+                assert_eq!(theirs.items[0].status.to_string(), "required");
+            }
+            other => panic!("expected a merge conflict, got {:?}", other),
+        }
+        assert_eq!(
+            parse_workflow_status(workflow).unwrap_err().to_string(),
+            "File has unresolved merge conflicts"
+        );
+    }
+
+    #[test]
+    fn test_parse_unparsable_side_is_parse_error() {
+        let content = "development_status:\n<<<<<<< HEAD\n  1-a: [done\n=======\n  \
+                       1-a: review\n>>>>>>> other\n";
+        assert!(matches!(
+            parse_sprint_status(content),
+            Err(SprintError::ParseError(_))
+        ));
+    }
+
+    // =========================================================================
+    // Resolve Tests
+    // =========================================================================
+
+    #[test]
+    fn test_resolve_ours_and_theirs() {
+        let ours = resolve_with(SPRINT, Strategy::Ours).unwrap();
+        assert!(ours.is_clean());
+        assert_eq!(ours.content, split(SPRINT).unwrap().ours);
+        let theirs = resolve_with(SPRINT, Strategy::Theirs).unwrap();
+        assert_eq!(theirs.content, split(SPRINT).unwrap().theirs);
+
+        let clean = "development_status:\n  1-a: done\n";
+        assert_eq!(resolve_with(clean, Strategy::Merge).unwrap().content, clean);
+    }
+
+    #[test]
+    fn test_resolve_merge() {
+        // Without a base: one-sided entries are kept, differing ones conflict
+        let merged = resolve_with(SPRINT, Strategy::Merge).unwrap();
+        let data = parse_sprint_status(&merged.content).unwrap();
+        let ids: Vec<&str> = data.epics[0]
+            .stories
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect();
+        assert_eq!(ids, ["1-a", "1-b", "1-c"]);
+        assert_eq!(merged.conflicts.len(), 1);
+        assert_eq!(merged.conflicts[0].id, "1-a");
+        assert_eq!(merged.conflicts[0].base, None);
+
+        // With a diff3 base, each side's own changes merge cleanly
+        let merged = resolve_with(DIFF3, Strategy::Merge).unwrap();
+        assert!(merged.is_clean());
+        assert_eq!(
+            merged.content,
+            "development_status:\n  epic-1: in-progress\n  1-a: done\n  1-b: done\n"
+        );
+    }
+
+    #[test]
+    fn test_resolve_merge_needs_sprint_file() {
+        let workflow = "workflow_status:\n<<<<<<< HEAD\n  prd: docs/prd.md\n=======\n  \
+                        prd: required\n>>>>>>> other\n";
+        assert!(matches!(
+            resolve_with(workflow, Strategy::Merge),
+            Err(SprintError::UpdateError(_))
+        ));
+        assert_eq!(
+            resolve_with(workflow, Strategy::Theirs).unwrap().content,
+            "workflow_status:\n  prd: required\n"
+        );
+    }
+}
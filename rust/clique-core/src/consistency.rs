@@ -0,0 +1,250 @@
+// clique-core/src/consistency.rs
+//! Cross-checks between sprint-status.yaml and the per-story markdown files.
+//!
+//! Both record a story's status, and they drift apart whenever an agent
+//! updates one and forgets the other.
+
+use crate::pattern::story_title_numbers;
+use crate::story_doc::StoryDoc;
+use crate::types::{SprintData, StatusAliases, Story, StoryStatus};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// The kind of drift found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InconsistencyKind {
+    /// The sprint file and the story file disagree on status
+    StatusMismatch,
+    /// A story past backlog has no story file
+    MissingStoryFile,
+    /// A story file names a story the sprint file does not list
+    MissingFromSprint,
+}
+
+/// One disagreement between the sprint file and the story files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Inconsistency {
+    /// Sprint story ID, or the story file's own ID when it has no match
+    pub story_id: String,
+    pub kind: InconsistencyKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sprint_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_status: Option<String>,
+}
+
+/// `epic-story` number key from a "Story N.M" title
+fn title_key(doc: &StoryDoc) -> Option<String> {
+    let (epic, story) = story_title_numbers(doc.title.as_deref()?)?;
+    Some(format!("{}-{}", epic, story))
+}
+
+/// Whether `story` is the one a story file describes
+fn describes(doc: &StoryDoc, key: Option<&str>, story: &Story) -> bool {
+    match (&doc.id, key) {
+        (Some(id), _) => *id == story.id,
+        (None, Some(key)) => {
+            story.id == key
+                || story
+                    .id
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        }
+        (None, None) => false,
+    }
+}
+
+/// Compare the sprint file against parsed story files.
+///
+/// Story files are matched by their front-matter `id`, falling back to the
+/// "Story N.M" title against the story ID's number prefix. Files with
+/// neither cannot be matched and are ignored. Statuses are compared after
+/// alias normalization, so `completed` in one and `done` in the other is
+/// not a mismatch. Backlog and optional stories are not expected to have a
+/// story file yet.
+pub fn check(sprint: &SprintData, docs: &[StoryDoc]) -> Vec<Inconsistency> {
+    let aliases = StatusAliases::default();
+    let stories: Vec<&Story> = sprint.epics.iter().flat_map(|e| &e.stories).collect();
+    let mut documented = HashSet::new();
+    let mut found = Vec::new();
+
+    for doc in docs {
+        let key = title_key(doc);
+        if doc.id.is_none() && key.is_none() {
+            continue;
+        }
+        let Some(story) = stories
+            .iter()
+            .find(|story| describes(doc, key.as_deref(), story))
+        else {
+            found.push(Inconsistency {
+                story_id: doc.id.clone().or(key).unwrap_or_default(),
+                kind: InconsistencyKind::MissingFromSprint,
+                sprint_status: None,
+                doc_status: doc.status.clone(),
+            });
+            continue;
+        };
+        documented.insert(story.id.as_str());
+
+        if let Some(doc_status) = &doc.status
+            && aliases.normalize(doc_status) != aliases.normalize(&story.status)
+        {
+            found.push(Inconsistency {
+                story_id: story.id.clone(),
+                kind: InconsistencyKind::StatusMismatch,
+                sprint_status: Some(story.status.to_string()),
+                doc_status: Some(doc_status.clone()),
+            });
+        }
+    }
+
+    for story in stories {
+        let expects_file = !matches!(
+            aliases.normalize(&story.status).parse::<StoryStatus>(),
+            Ok(StoryStatus::Backlog | StoryStatus::Optional)
+        );
+        if expects_file && !documented.contains(story.id.as_str()) {
+            found.push(Inconsistency {
+                story_id: story.id.clone(),
+                kind: InconsistencyKind::MissingStoryFile,
+                sprint_status: Some(story.status.to_string()),
+                doc_status: None,
+            });
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+    use crate::story_doc::parse_story_markdown;
+
+    const SPRINT_YAML: &str = r#"
+development_status:
+  epic-1: in-progress
+  1-1-project-setup: done
+  1-2-user-login: in-progress
+  1-3-profile: review
+  1-4-settings: backlog
+  1-5-themes: optional
+"#;
+
+    fn doc(content: &str) -> StoryDoc {
+        parse_story_markdown(content).unwrap()
+    }
+
+    fn kinds(found: &[Inconsistency]) -> Vec<(&str, InconsistencyKind)> {
+        found
+            .iter()
+            .map(|i| (i.story_id.as_str(), i.kind))
+            .collect()
+    }
+
+    // =========================================================================
+    // Matching Tests
+    // =========================================================================
+
+    #[test]
+    fn test_consistent_sources() {
+        let sprint = parse_sprint_status(SPRINT_YAML).unwrap();
+        let docs = vec![
+            doc("---\nid: 1-1-project-setup\nstatus: completed\n---\n"),
+            doc("# Story 1.2: User Login\n\nStatus: in-progress\n"),
+            doc("---\nid: 1-3-profile\n---\n# Story 1.3: Profile\n"),
+        ];
+        assert!(check(&sprint, &docs).is_empty());
+    }
+
+    #[test]
+    fn test_title_key_matching() {
+        let login = doc("# Story 1.2: User Login\n");
+        let key = title_key(&login);
+        assert_eq!(key.as_deref(), Some("1-2"));
+
+        let story = |id: &str| Story {
+            id: id.to_string(),
+            ..Story::default()
+        };
+        assert!(describes(&login, key.as_deref(), &story("1-2-user-login")));
+        assert!(describes(&login, key.as_deref(), &story("1-2")));
+        assert!(!describes(&login, key.as_deref(), &story("1-20-reports")));
+        // An explicit ID takes precedence over the title
+        let with_id = doc("---\nid: 1-3-profile\n---\n# Story 1.2: User Login\n");
+        assert!(!describes(
+            &with_id,
+            key.as_deref(),
+            &story("1-2-user-login")
+        ));
+    }
+
+    // =========================================================================
+    // Drift Tests
+    // =========================================================================
+
+    #[test]
+    fn test_status_mismatch() {
+        let sprint = parse_sprint_status(SPRINT_YAML).unwrap();
+        let docs = vec![
+            doc("---\nid: 1-1-project-setup\nstatus: done\n---\n"),
+            doc("---\nid: 1-2-user-login\nstatus: review\n---\n"),
+            doc("---\nid: 1-3-profile\nstatus: review\n---\n"),
+        ];
+        let found = check(&sprint, &docs);
+        assert_eq!(
+            kinds(&found),
+            vec![("1-2-user-login", InconsistencyKind::StatusMismatch)]
+        );
+        assert_eq!(found[0].sprint_status.as_deref(), Some("in-progress"));
+        assert_eq!(found[0].doc_status.as_deref(), Some("review"));
+    }
+
+    #[test]
+    fn test_missing_story_files() {
+        let sprint = parse_sprint_status(SPRINT_YAML).unwrap();
+        let found = check(&sprint, &[]);
+        assert_eq!(
+            kinds(&found),
+            vec![
+                ("1-1-project-setup", InconsistencyKind::MissingStoryFile),
+                ("1-2-user-login", InconsistencyKind::MissingStoryFile),
+                ("1-3-profile", InconsistencyKind::MissingStoryFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_from_sprint() {
+        let sprint = parse_sprint_status("development_status:\n  epic-2: backlog\n").unwrap();
+        let docs = vec![
+            doc("---\nid: 2-1-export\nstatus: drafted\n---\n"),
+            doc("# Story 2.2: Import\n"),
+            doc("# Notes without an ID\n"),
+        ];
+        let found = check(&sprint, &docs);
+        assert_eq!(
+            kinds(&found),
+            vec![
+                ("2-1-export", InconsistencyKind::MissingFromSprint),
+                ("2-2", InconsistencyKind::MissingFromSprint),
+            ]
+        );
+        assert_eq!(found[0].doc_status.as_deref(), Some("drafted"));
+    }
+
+    #[test]
+    fn test_inconsistency_serialization() {
+        let sprint = parse_sprint_status(SPRINT_YAML).unwrap();
+        let found = check(&sprint, &[]);
+        let json = serde_json::to_string(&found[0]).unwrap();
+        assert_eq!(
+            json,
+            r#"{"storyId":"1-1-project-setup","kind":"missing-story-file","sprintStatus":"done"}"#
+        );
+    }
+}
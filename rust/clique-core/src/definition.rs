@@ -0,0 +1,363 @@
+// clique-core/src/definition.rs
+//! External workflow definitions referenced by `workflow_path`.
+//!
+//! A definition (e.g. `paths/method-greenfield.yaml`) lists the phases of a
+//! track in order, each with its workflows, agents and commands. The status
+//! file only records progress, so merging the definition in supplies phase
+//! names, descriptions and the intended order of items.
+
+use crate::text::text;
+use crate::types::{Phase, PhaseInfo, WorkflowData};
+use crate::workflow::WorkflowError;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::HashMap;
+
+/// A workflow listed in a definition
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefinedWorkflow {
+    pub id: String,
+    pub phase: Phase,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A parsed workflow definition file
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowDefinition {
+    /// Display name of the method, e.g. "BMad Method"
+    pub name: String,
+    pub track: String,
+    pub field_type: String,
+    pub phases: Vec<PhaseInfo>,
+    /// Workflows in definition order
+    pub workflows: Vec<DefinedWorkflow>,
+}
+
+fn get_string(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Phase of a definition entry: `prerequisite: true` or `phase: N`
+fn definition_phase(value: &Value) -> Option<Phase> {
+    if value.get("prerequisite").and_then(|v| v.as_bool()) == Some(true) {
+        return Some(Phase::Prerequisite);
+    }
+    value
+        .get("phase")
+        .and_then(|v| v.as_i64())
+        .and_then(|n| i32::try_from(n).ok())
+        .map(Phase::Number)
+}
+
+/// Parse a workflow definition from YAML content.
+///
+/// Phases without a `phase` number or `prerequisite: true` marker, and
+/// workflows without an `id`, are skipped.
+pub fn parse_workflow_definition(content: &str) -> Result<WorkflowDefinition, WorkflowError> {
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| WorkflowError::ParseError(e.to_string()))?;
+
+    let mut definition = WorkflowDefinition {
+        name: get_string(&parsed, "method_name").unwrap_or_default(),
+        track: get_string(&parsed, "track").unwrap_or_default(),
+        field_type: get_string(&parsed, "field_type").unwrap_or_default(),
+        ..WorkflowDefinition::default()
+    };
+
+    let phases = parsed.get("phases").and_then(|v| v.as_sequence());
+    for entry in phases.into_iter().flatten() {
+        let Some(phase) = definition_phase(entry) else {
+            continue;
+        };
+        definition.phases.push(PhaseInfo {
+            phase,
+            name: get_string(entry, "name").unwrap_or_default(),
+            description: get_string(entry, "description").or_else(|| get_string(entry, "note")),
+        });
+
+        let workflows = entry.get("workflows").and_then(|v| v.as_sequence());
+        for workflow in workflows.into_iter().flatten() {
+            let Some(id) = get_string(workflow, "id") else {
+                continue;
+            };
+            definition.workflows.push(DefinedWorkflow {
+                id,
+                phase,
+                agent: get_string(workflow, "agent"),
+                command: get_string(workflow, "command"),
+                description: get_string(workflow, "description")
+                    .or_else(|| get_string(workflow, "note")),
+            });
+        }
+    }
+
+    Ok(definition)
+}
+
+/// Merge a definition into parsed workflow data.
+///
+/// Phase names and descriptions are copied over. Items named in the
+/// definition take its phase, and its agent, command and description where
+/// given, and are reordered to match it. Items the definition does not
+/// mention keep their relative order after the defined ones.
+pub fn merge_definition(data: &mut WorkflowData, definition: &WorkflowDefinition) {
+    data.phases = definition.phases.clone();
+
+    let positions: HashMap<&str, usize> = definition
+        .workflows
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, workflow)| (workflow.id.as_str(), index))
+        .collect();
+
+    for item in &mut data.items {
+        let Some(&index) = positions.get(item.id.as_str()) else {
+            continue;
+        };
+        let defined = &definition.workflows[index];
+        item.phase = defined.phase;
+        if defined.agent.is_some() {
+            item.agent = defined.agent.as_deref().map(text);
+        }
+        if defined.command.is_some() {
+            item.command = defined.command.clone();
+        }
+        if defined.description.is_some() {
+            item.description = defined.description.clone();
+        }
+    }
+
+    data.items.sort_by_key(|item| {
+        positions
+            .get(item.id.as_str())
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
+}
+
+/// Read the definition named by `data.workflow_path` and merge it in.
+///
+/// Does nothing when the status file has no `workflow_path`. The path must
+/// stay inside `workspace_root`.
+#[cfg(feature = "fs")]
+pub fn load_definition(data: &mut WorkflowData, workspace_root: &str) -> Result<(), WorkflowError> {
+    if data.workflow_path.is_empty() {
+        return Ok(());
+    }
+    let path = crate::validation::resolve_workspace_path(&data.workflow_path, workspace_root)
+        .ok_or_else(|| WorkflowError::PathOutsideWorkspace(data.workflow_path.clone()))?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| WorkflowError::Io(format!("{}: {}", path, e)))?;
+    let definition = parse_workflow_definition(&content)?;
+    merge_definition(data, &definition);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "fs")]
+    use crate::testing::TempDir;
+    use crate::workflow::parse_workflow_status;
+
+    const DEFINITION_YAML: &str = r#"
+method_name: "BMad Method"
+track: "bmad-method"
+field_type: "greenfield"
+phases:
+  - prerequisite: true
+    name: "Documentation"
+    workflows:
+      - id: "document-project"
+        agent: "analyst"
+        command: "document-project"
+  - phase: 0
+    name: "Discovery (Optional)"
+    note: "User-selected discovery workflows"
+    workflows:
+      - id: "brainstorm-project"
+        agent: "analyst"
+        command: "brainstorm-project"
+      - id: "research"
+  - phase: 1
+    name: "Planning"
+    description: "Define what to build"
+    workflows:
+      - id: "prd"
+        agent: "pm"
+        command: "prd"
+        note: "Product Requirements Document"
+      - agent: "nobody"
+  - name: "Missing number"
+    workflows:
+      - id: "ignored"
+"#;
+
+    const STATUS_YAML: &str = r#"
+workflow_path: paths/method-greenfield.yaml
+workflows:
+  prd:
+    status: not_started
+  research:
+    status: skipped
+  brainstorm-project:
+    status: complete
+    output_file: docs/brainstorm.md
+  custom-step:
+    status: not_started
+"#;
+
+    // =========================================================================
+    // Parsing Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_definition() {
+        let definition = parse_workflow_definition(DEFINITION_YAML).unwrap();
+        assert_eq!(definition.name, "BMad Method");
+        assert_eq!(definition.track, "bmad-method");
+        assert_eq!(definition.field_type, "greenfield");
+
+        assert_eq!(definition.phases.len(), 3);
+        assert_eq!(definition.phases[0].phase, Phase::Prerequisite);
+        assert_eq!(definition.phases[0].name, "Documentation");
+        assert_eq!(
+            definition.phases[1].description.as_deref(),
+            Some("User-selected discovery workflows")
+        );
+        assert_eq!(
+            definition.phases[2].description.as_deref(),
+            Some("Define what to build")
+        );
+
+        let ids: Vec<&str> = definition.workflows.iter().map(|w| w.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["document-project", "brainstorm-project", "research", "prd"]
+        );
+        assert_eq!(definition.workflows[2].agent, None);
+        assert_eq!(definition.workflows[3].phase, Phase::Number(1));
+    }
+
+    #[test]
+    fn test_parse_definition_errors() {
+        assert!(matches!(
+            parse_workflow_definition("phases: [unclosed"),
+            Err(WorkflowError::ParseError(_))
+        ));
+        let empty = parse_workflow_definition("").unwrap();
+        assert!(empty.phases.is_empty());
+        assert!(empty.workflows.is_empty());
+    }
+
+    // =========================================================================
+    // Merge Tests
+    // =========================================================================
+
+    #[test]
+    fn test_merge_definition() {
+        let definition = parse_workflow_definition(DEFINITION_YAML).unwrap();
+        let mut data = parse_workflow_status(STATUS_YAML).unwrap();
+        merge_definition(&mut data, &definition);
+
+        assert_eq!(data.phases, definition.phases);
+        let ids: Vec<&str> = data.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["brainstorm-project", "research", "prd", "custom-step"]
+        );
+
+        let prd = &data.items[2];
+        assert_eq!(prd.agent.as_deref(), Some("pm"));
+        assert_eq!(
+            prd.description.as_deref(),
+            Some("Product Requirements Document")
+        );
+        // Inferred values stay when the definition leaves them out
+        let research = &data.items[1];
+        assert_eq!(research.agent.as_deref(), Some("analyst"));
+        assert!(research.description.is_none());
+    }
+
+    #[test]
+    fn test_merge_definition_overrides_phase() {
+        let definition = WorkflowDefinition {
+            workflows: vec![DefinedWorkflow {
+                id: "prd".to_string(),
+                phase: Phase::Number(2),
+                agent: None,
+                command: None,
+                description: None,
+            }],
+            ..WorkflowDefinition::default()
+        };
+        let mut data = parse_workflow_status(STATUS_YAML).unwrap();
+        merge_definition(&mut data, &definition);
+        assert_eq!(data.items[0].id, "prd");
+        assert_eq!(data.items[0].phase, Phase::Number(2));
+    }
+
+    // =========================================================================
+    // Loading Tests
+    // =========================================================================
+
+    /// A fresh workspace with an empty `paths` folder
+    #[cfg(feature = "fs")]
+    fn workspace(name: &str) -> TempDir {
+        let dir = TempDir::new(&format!("definition-{}", name));
+        std::fs::create_dir_all(dir.join("paths")).unwrap();
+        dir
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_load_definition() {
+        let root = workspace("load");
+        std::fs::write(root.join("paths/method-greenfield.yaml"), DEFINITION_YAML).unwrap();
+        let root_str = root.to_string_lossy().to_string();
+
+        let mut data = parse_workflow_status(STATUS_YAML).unwrap();
+        load_definition(&mut data, &root_str).unwrap();
+        assert_eq!(data.phases.len(), 3);
+
+        let mut placeholder = parse_workflow_status(STATUS_YAML).unwrap();
+        placeholder.workflow_path = "{project-root}/paths/method-greenfield.yaml".to_string();
+        load_definition(&mut placeholder, &root_str).unwrap();
+        assert_eq!(placeholder.phases.len(), 3);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_load_definition_errors() {
+        let root = workspace("errors");
+        let root_str = root.to_string_lossy().to_string();
+        let mut data = parse_workflow_status(STATUS_YAML).unwrap();
+
+        data.workflow_path = "../outside.yaml".to_string();
+        assert!(matches!(
+            load_definition(&mut data, &root_str),
+            Err(WorkflowError::PathOutsideWorkspace(_))
+        ));
+
+        data.workflow_path = "paths/missing.yaml".to_string();
+        assert!(matches!(
+            load_definition(&mut data, &root_str),
+            Err(WorkflowError::Io(_))
+        ));
+
+        data.workflow_path = String::new();
+        assert!(load_definition(&mut data, &root_str).is_ok());
+        assert!(data.phases.is_empty());
+    }
+}
@@ -0,0 +1,244 @@
+// clique-core/src/diagnostics.rs
+//! Editor diagnostics for status files.
+//!
+//! Collects everything worth underlining in an open document into one list:
+//! YAML errors skipped over by the lenient parser, and the structural
+//! problems found by schema validation, each pinned to a line. The parsed
+//! data comes back alongside, so a single call serves both the tree view and
+//! the editor.
+
+use crate::schema::{SchemaKind, SchemaViolation, ViolationKind, validate_against_schema};
+use crate::sprint::parse_sprint_status;
+use crate::types::{SprintData, WorkflowData};
+use crate::workflow::{error_line, parse_workflow_status_lenient};
+use crate::yaml_line::find_key;
+use serde::Serialize;
+use serde_yaml::Value;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// The document, or part of it, could not be read
+    Error,
+    /// The document was read, but something in it is probably a mistake
+    Warning,
+}
+
+/// One problem in a status file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    /// 1-based line number, or 0 when the problem has no position
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+    /// Dotted path to the offending node, for schema problems
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// Parsed data together with the diagnostics found while reading it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnosed<T> {
+    pub data: T,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// 1-based line containing byte `offset`
+pub(crate) fn line_number(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+/// Line of the key a schema path points at, or of its closest enclosing key
+/// that can be found; 0 when none can.
+///
+/// Sequence items (`workflow_status[2].status`) are attributed to the
+/// sequence's own key, and IDs containing dots to their section.
+fn line_of(content: &str, path: &str) -> usize {
+    let mut keys = Vec::new();
+    for segment in path.split('.') {
+        match segment.split_once('[') {
+            Some((key, _)) => {
+                keys.push(key);
+                break;
+            }
+            None => keys.push(segment),
+        }
+    }
+    while !keys.is_empty() {
+        if let Some(span) = find_key(content, &keys) {
+            return line_number(content, span.start);
+        }
+        keys.pop();
+    }
+    0
+}
+
+fn from_violation(content: &str, violation: SchemaViolation) -> Diagnostic {
+    let severity = match violation.kind {
+        ViolationKind::InvalidYaml => Severity::Error,
+        _ => Severity::Warning,
+    };
+    Diagnostic {
+        line: line_of(content, &violation.path),
+        severity,
+        message: violation.message,
+        path: Some(violation.path).filter(|path| !path.is_empty()),
+    }
+}
+
+/// Parse a workflow status file and collect diagnostics for it.
+///
+/// Parsing is lenient, so the data holds every entry that could be read and
+/// each dropped block is reported as an error. Schema warnings are only
+/// reported for documents that parsed cleanly; until then they would point
+/// at the blanked-out recovery rather than at the file.
+pub fn workflow_diagnostics(content: &str) -> Diagnosed<WorkflowData> {
+    let (data, issues) = parse_workflow_status_lenient(content);
+    let diagnostics = if issues.is_empty() {
+        validate_against_schema(content, SchemaKind::Workflow)
+            .into_iter()
+            .map(|violation| from_violation(content, violation))
+            .collect()
+    } else {
+        issues
+            .into_iter()
+            .map(|issue| Diagnostic {
+                line: issue.line,
+                severity: Severity::Error,
+                message: issue.message,
+                path: None,
+            })
+            .collect()
+    };
+    Diagnosed { data, diagnostics }
+}
+
+/// Parse a sprint status file and collect diagnostics for it.
+///
+/// The sprint parser has no recovery, so a document that fails to parse
+/// yields no data and a single error at the line the YAML parser gave up on.
+pub fn sprint_diagnostics(content: &str) -> Diagnosed<Option<SprintData>> {
+    match parse_sprint_status(content) {
+        Ok(data) => Diagnosed {
+            data: Some(data),
+            diagnostics: validate_against_schema(content, SchemaKind::Sprint)
+                .into_iter()
+                .map(|violation| from_violation(content, violation))
+                .collect(),
+        },
+        Err(e) => {
+            let line = serde_yaml::from_str::<Value>(content)
+                .err()
+                .and_then(|error| error_line(&error))
+                .unwrap_or_default();
+            Diagnosed {
+                data: None,
+                diagnostics: vec![Diagnostic {
+                    line,
+                    severity: Severity::Error,
+                    message: e.to_string(),
+                    path: None,
+                }],
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // Workflow Diagnostics Tests
+    // =========================================================================
+
+    #[test]
+    fn test_clean_workflow_has_no_diagnostics() {
+        let yaml = "project: Demo\nworkflows:\n  prd:\n    status: required\n";
+        let result = workflow_diagnostics(yaml);
+        assert_eq!(result.data.items.len(), 1);
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_workflow_schema_problems_are_warnings_on_their_line() {
+        let yaml = "project: Demo\nworkflows:\n  prd:\n    status: required\n    colour: red\n  \
+                    ux-design:\n    status: [a, b]\n";
+        let result = workflow_diagnostics(yaml);
+        assert_eq!(result.data.items.len(), 2);
+
+        let unknown = &result.diagnostics[0];
+        assert_eq!(unknown.severity, Severity::Warning);
+        assert_eq!(unknown.line, 5);
+        assert_eq!(unknown.path.as_deref(), Some("workflows.prd.colour"));
+
+        let wrong = &result.diagnostics[1];
+        assert_eq!(wrong.line, 7);
+        assert_eq!(wrong.path.as_deref(), Some("workflows.ux-design.status"));
+    }
+
+    #[test]
+    fn test_workflow_parse_errors_are_errors() {
+        let yaml = "workflows:\n  prd:\n    status: required\n  broken: [unclosed\n";
+        let result = workflow_diagnostics(yaml);
+        assert_eq!(result.data.items.len(), 1);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].severity, Severity::Error);
+        assert_eq!(result.diagnostics[0].line, 4);
+        assert_eq!(result.diagnostics[0].path, None);
+    }
+
+    #[test]
+    fn test_old_format_items_point_at_their_sequence() {
+        let yaml = "project: Demo\nworkflow_status:\n  - id: prd\n    status: required\n    \
+                    colour: red\n";
+        let result = workflow_diagnostics(yaml);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].line, 2);
+        assert_eq!(
+            result.diagnostics[0].path.as_deref(),
+            Some("workflow_status[0].colour")
+        );
+    }
+
+    // =========================================================================
+    // Sprint Diagnostics Tests
+    // =========================================================================
+
+    #[test]
+    fn test_sprint_diagnostics() {
+        let yaml = "project: Demo\ndevelopment_status:\n  epic-1: in-progress\n  1-a: done\n  \
+                    story one: done\n";
+        let result = sprint_diagnostics(yaml);
+        assert!(result.data.is_some());
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].severity, Severity::Warning);
+        assert_eq!(result.diagnostics[0].line, 5);
+
+        let clean = "development_status:\n  epic-1: backlog\n";
+        assert!(sprint_diagnostics(clean).diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_unparsable_sprint_reports_error_line() {
+        let yaml = "project: Demo\ndevelopment_status:\n  epic-1: [oops\n";
+        let result = sprint_diagnostics(yaml);
+        assert_eq!(result.data, None);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].severity, Severity::Error);
+        assert_eq!(result.diagnostics[0].line, 3);
+    }
+
+    #[test]
+    fn test_diagnostics_serialize_camel_case() {
+        let result = sprint_diagnostics("development_status:\n  epic-1: [oops\n");
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["data"], serde_json::Value::Null);
+        assert_eq!(json["diagnostics"][0]["severity"], "error");
+        assert_eq!(json["diagnostics"][0]["line"], 2);
+        assert!(json["diagnostics"][0].get("path").is_none());
+    }
+}
@@ -0,0 +1,274 @@
+// clique-core/src/diff.rs
+//! Item-level differences between two versions of a status file.
+//!
+//! [`crate::edit::unified_diff`] shows what changed in the text; this shows
+//! what changed in the plan, independent of layout, quoting or comments.
+
+use crate::trace::record;
+use crate::types::{SprintData, WorkflowData};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// One change to a workflow item or sprint entry between two versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ItemChange {
+    /// The item only exists in the new version
+    Added { id: String, status: String },
+    /// The item only exists in the old version
+    Removed { id: String, status: String },
+    /// The item's status differs between the versions
+    StatusChanged {
+        id: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl ItemChange {
+    /// ID of the changed item
+    pub fn id(&self) -> &str {
+        match self {
+            ItemChange::Added { id, .. }
+            | ItemChange::Removed { id, .. }
+            | ItemChange::StatusChanged { id, .. } => id,
+        }
+    }
+}
+
+/// Compare two parsed workflow files item by item.
+///
+/// Items are matched by ID, so reordering alone is not a change. Removed
+/// and changed items are listed in their old order, followed by added
+/// items in their new order.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(old = old.items.len(), new = new.items.len(), changes = tracing::field::Empty)
+    )
+)]
+pub fn diff_workflow(old: &WorkflowData, new: &WorkflowData) -> Vec<ItemChange> {
+    let statuses = |data: &'_ WorkflowData| -> Vec<(String, String)> {
+        data.items
+            .iter()
+            .map(|item| (item.id.clone(), item.status.to_string()))
+            .collect()
+    };
+    let changes = diff_entries(&statuses(old), &statuses(new));
+    record!("changes" = changes.len());
+    changes
+}
+
+/// Compare two parsed sprint files entry by entry.
+///
+/// Epics and stories are compared alike, each epic followed by its stories,
+/// in the same order as [`diff_workflow`].
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(old = tracing::field::Empty, new = tracing::field::Empty, changes = tracing::field::Empty)
+    )
+)]
+pub fn diff_sprint(old: &SprintData, new: &SprintData) -> Vec<ItemChange> {
+    let statuses = |data: &SprintData| -> Vec<(String, String)> {
+        data.epics
+            .iter()
+            .flat_map(|epic| {
+                std::iter::once((epic.id.clone(), epic.status.clone())).chain(
+                    epic.stories
+                        .iter()
+                        .map(|story| (story.id.clone(), story.status.to_string())),
+                )
+            })
+            .collect()
+    };
+    let (old, new) = (statuses(old), statuses(new));
+    let changes = diff_entries(&old, &new);
+    record!(
+        "old" = old.len(),
+        "new" = new.len(),
+        "changes" = changes.len(),
+    );
+    changes
+}
+
+/// The changes between two versions of a sprint file, with the project
+/// they belong to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintDiff {
+    pub project: String,
+    pub changes: Vec<ItemChange>,
+}
+
+impl SprintDiff {
+    /// Compare two parsed sprint files with [`diff_sprint`]; the project
+    /// name is taken from `new`.
+    pub fn between(old: &SprintData, new: &SprintData) -> Self {
+        Self {
+            project: new.project.clone(),
+            changes: diff_sprint(old, new),
+        }
+    }
+
+    /// Whether no entry changed
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Changes between two `(id, status)` lists
+fn diff_entries(old: &[(String, String)], new: &[(String, String)]) -> Vec<ItemChange> {
+    let new_statuses: HashMap<&str, &str> = new
+        .iter()
+        .map(|(id, status)| (id.as_str(), status.as_str()))
+        .collect();
+    let old_ids: HashSet<&str> = old.iter().map(|(id, _)| id.as_str()).collect();
+
+    let mut changes = Vec::new();
+    for (id, status) in old {
+        match new_statuses.get(id.as_str()) {
+            None => changes.push(ItemChange::Removed {
+                id: id.clone(),
+                status: status.clone(),
+            }),
+            Some(&to) if to != status => changes.push(ItemChange::StatusChanged {
+                id: id.clone(),
+                from: status.clone(),
+                to: to.to_string(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (id, status) in new {
+        if !old_ids.contains(id.as_str()) {
+            changes.push(ItemChange::Added {
+                id: id.clone(),
+                status: status.clone(),
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+    use crate::workflow::parse_workflow_status;
+
+    // =========================================================================
+    // Workflow Diff Tests
+    // =========================================================================
+
+    #[test]
+    fn test_diff_workflow() {
+        let old = parse_workflow_status(
+            "workflow_status:\n  brainstorm: required\n  prd: required\n  ux: optional\n",
+        )
+        .unwrap();
+        let new = parse_workflow_status(
+            "workflow_status:\n  prd: docs/prd.md\n  brainstorm: required\n  \
+             architecture: required\n",
+        )
+        .unwrap();
+
+        let changes = diff_workflow(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                ItemChange::StatusChanged {
+                    id: "prd".to_string(),
+                    from: "required".to_string(),
+                    to: "docs/prd.md".to_string(),
+                },
+                ItemChange::Removed {
+                    id: "ux".to_string(),
+                    status: "optional".to_string(),
+                },
+                ItemChange::Added {
+                    id: "architecture".to_string(),
+                    status: "required".to_string(),
+                },
+            ]
+        );
+        assert_eq!(changes[2].id(), "architecture");
+    }
+
+    #[test]
+    fn test_diff_ignores_layout() {
+        let flat = parse_workflow_status("workflow_status:\n  prd: required\n").unwrap();
+        let nested =
+            parse_workflow_status("workflows:\n  prd:\n    status: \"required\"\n").unwrap();
+        assert!(diff_workflow(&flat, &nested).is_empty());
+        assert!(diff_workflow(&flat, &flat).is_empty());
+    }
+
+    // =========================================================================
+    // Sprint Diff Tests
+    // =========================================================================
+
+    #[test]
+    fn test_diff_sprint() {
+        let old = parse_sprint_status(
+            "development_status:\n  epic-1: backlog\n  1-a: backlog\n  1-b: review\n",
+        )
+        .unwrap();
+        let new = parse_sprint_status(
+            "development_status:\n  epic-1: in-progress\n  1-a: backlog\n  1-c: drafted\n",
+        )
+        .unwrap();
+        assert_eq!(
+            diff_sprint(&old, &new),
+            vec![
+                ItemChange::StatusChanged {
+                    id: "epic-1".to_string(),
+                    from: "backlog".to_string(),
+                    to: "in-progress".to_string(),
+                },
+                ItemChange::Removed {
+                    id: "1-b".to_string(),
+                    status: "review".to_string(),
+                },
+                ItemChange::Added {
+                    id: "1-c".to_string(),
+                    status: "drafted".to_string(),
+                },
+            ]
+        );
+        assert!(diff_sprint(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_sprint_diff_between() {
+        let old = parse_sprint_status(
+            "project: Old\ndevelopment_status:\n  epic-1: backlog\n  1-a: backlog\n",
+        )
+        .unwrap();
+        let new = parse_sprint_status(
+            "project: Demo\ndevelopment_status:\n  epic-1: backlog\n  1-a: done\n",
+        )
+        .unwrap();
+        let diff = SprintDiff::between(&old, &new);
+        assert_eq!(diff.project, "Demo");
+        assert_eq!(diff.changes, diff_sprint(&old, &new));
+        assert!(!diff.is_empty());
+        assert!(SprintDiff::between(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_item_change_serialization() {
+        let change = ItemChange::StatusChanged {
+            id: "prd".to_string(),
+            from: "required".to_string(),
+            to: "complete".to_string(),
+        };
+        let json = serde_json::to_value(&change).unwrap();
+        assert_eq!(json["kind"], "statusChanged");
+        assert_eq!(json["from"], "required");
+    }
+}
@@ -0,0 +1,208 @@
+// clique-core/src/document.rs
+//! Open status documents kept in memory between edits.
+//!
+//! The free functions take and return the whole text, so an editor that
+//! changes one status after another re-parses the full document each time.
+//! A document keeps its content and parsed model together: an update
+//! rewrites the text in place and re-reads only the entry it touched.
+
+use crate::sprint::{SprintError, parse_sprint_status, parse_story, update_story_status};
+use crate::types::{SprintData, WorkflowData};
+use crate::workflow::{
+    WorkflowError, WorkflowFormat, format_of, parse_item, parse_workflow_status,
+    update_workflow_status_with_format,
+};
+
+/// A workflow status file with its parsed model
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowDocument {
+    content: String,
+    format: WorkflowFormat,
+    data: WorkflowData,
+}
+
+impl WorkflowDocument {
+    /// Parse `content` once, keeping the text for later edits.
+    pub fn parse(content: &str) -> Result<Self, WorkflowError> {
+        Ok(Self {
+            data: parse_workflow_status(content)?,
+            format: format_of(content)?,
+            content: content.to_string(),
+        })
+    }
+
+    /// The document's current text
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Layout detected when the document was parsed
+    pub fn format(&self) -> WorkflowFormat {
+        self.format
+    }
+
+    /// The parsed model, kept in step with [`content`](Self::content)
+    pub fn data(&self) -> &WorkflowData {
+        &self.data
+    }
+
+    /// Set an item's status, as [`crate::update_workflow_status`] would.
+    ///
+    /// Only the changed item is re-read. On error the document is left as
+    /// it was.
+    pub fn update_status(&mut self, item_id: &str, new_status: &str) -> Result<(), WorkflowError> {
+        let content =
+            update_workflow_status_with_format(&self.content, self.format, item_id, new_status)?;
+        let item = parse_item(&content, item_id)?;
+        if let Some(slot) = self.data.items.iter_mut().find(|i| i.id == item_id) {
+            *slot = item;
+        }
+        self.content = content;
+        Ok(())
+    }
+
+    /// The document's text, consuming it
+    pub fn into_content(self) -> String {
+        self.content
+    }
+}
+
+/// A sprint status file with its parsed model
+#[derive(Debug, Clone, PartialEq)]
+pub struct SprintDocument {
+    content: String,
+    data: SprintData,
+}
+
+impl SprintDocument {
+    /// Parse `content` once, keeping the text for later edits.
+    pub fn parse(content: &str) -> Result<Self, SprintError> {
+        Ok(Self {
+            data: parse_sprint_status(content)?,
+            content: content.to_string(),
+        })
+    }
+
+    /// The document's current text
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// The parsed model, kept in step with [`content`](Self::content)
+    pub fn data(&self) -> &SprintData {
+        &self.data
+    }
+
+    /// Set a story's (or epic's) status, as [`crate::update_story_status`]
+    /// would.
+    ///
+    /// Only the changed story is re-read. On error the document is left as
+    /// it was.
+    pub fn update_status(&mut self, story_id: &str, new_status: &str) -> Result<(), SprintError> {
+        let content = update_story_status(&self.content, story_id, new_status)?;
+        if let Some(epic) = self.data.epics.iter_mut().find(|e| e.id == story_id) {
+            epic.status = new_status.to_string();
+        } else if let Some(slot) = self
+            .data
+            .epics
+            .iter_mut()
+            .flat_map(|e| &mut e.stories)
+            .find(|s| s.id == story_id)
+        {
+            *slot = parse_story(&content, story_id)?;
+        }
+        self.content = content;
+        Ok(())
+    }
+
+    /// The document's text, consuming it
+    pub fn into_content(self) -> String {
+        self.content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // Workflow Document Tests
+    // =========================================================================
+
+    #[test]
+    fn test_workflow_document_updates_match_full_parse() {
+        let yaml = "workflows:\n  prd:\n    status: required\n    output_file: docs/prd.md\n  \
+                    architecture:\n    status: not_started\n";
+        let mut doc = WorkflowDocument::parse(yaml).unwrap();
+        assert_eq!(doc.format(), WorkflowFormat::New);
+
+        doc.update_status("prd", "complete").unwrap();
+        doc.update_status("architecture", "skipped").unwrap();
+        assert_eq!(doc.data(), &parse_workflow_status(doc.content()).unwrap());
+
+        let item = |id: &str| doc.data().items.iter().find(|i| i.id == id).unwrap();
+        assert_eq!(item("prd").status, "docs/prd.md");
+        assert_eq!(item("architecture").status, "skipped");
+    }
+
+    #[test]
+    fn test_workflow_document_flat_format() {
+        let mut doc = WorkflowDocument::parse("workflow_status:\n  prd: required\n").unwrap();
+        doc.update_status("prd", "docs/prd.md").unwrap();
+        assert_eq!(doc.data(), &parse_workflow_status(doc.content()).unwrap());
+        assert_eq!(
+            doc.data().items[0].output_file.as_deref(),
+            Some("docs/prd.md")
+        );
+        assert_eq!(
+            doc.into_content(),
+            "workflow_status:\n  prd: \"docs/prd.md\"\n"
+        );
+    }
+
+    #[test]
+    fn test_workflow_document_failed_update_leaves_it_alone() {
+        let yaml = "workflow_status:\n  prd: required\n";
+        let mut doc = WorkflowDocument::parse(yaml).unwrap();
+        let before = doc.clone();
+        assert!(matches!(
+            doc.update_status("missing", "done"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+        assert!(doc.update_status("prd", "a\nb").is_err());
+        assert_eq!(doc, before);
+        assert!(WorkflowDocument::parse("[oops").is_err());
+    }
+
+    // =========================================================================
+    // Sprint Document Tests
+    // =========================================================================
+
+    #[test]
+    fn test_sprint_document_updates_match_full_parse() {
+        let yaml = "development_status:\n  epic-1: backlog\n  1-a: backlog\n  1-b:\n    \
+                    status: review\n    assignee: sam\n  epic-1-retrospective: optional\n";
+        let mut doc = SprintDocument::parse(yaml).unwrap();
+        doc.update_status("1-a", "in-progress").unwrap();
+        doc.update_status("1-b", "done").unwrap();
+        doc.update_status("epic-1", "in-progress").unwrap();
+        doc.update_status("epic-1-retrospective", "completed")
+            .unwrap();
+        assert_eq!(doc.data(), &parse_sprint_status(doc.content()).unwrap());
+        assert_eq!(
+            doc.data().epics[0].stories[1].assignee.as_deref(),
+            Some("sam")
+        );
+    }
+
+    #[test]
+    fn test_sprint_document_failed_update_leaves_it_alone() {
+        let mut doc = SprintDocument::parse("development_status:\n  epic-1: backlog\n").unwrap();
+        let before = doc.clone();
+        assert!(matches!(
+            doc.update_status("9-missing", "done"),
+            Err(SprintError::StoryNotFound(_))
+        ));
+        assert_eq!(doc, before);
+    }
+}
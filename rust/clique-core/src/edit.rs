@@ -0,0 +1,1080 @@
+// clique-core/src/edit.rs
+//! Composite edits applied to one document as a unit.
+//!
+//! Each editor in `workflow` and `sprint` returns new content rather than
+//! touching a file, so a [`Transaction`] can run them one after another on
+//! an in-memory copy and hand back either the fully edited document or the
+//! first error, never something in between.
+//!
+//! Every applied transaction also yields an [`EditRecord`] holding the
+//! operations that take it back, so callers can offer undo (and, by
+//! recording the undo in turn, redo) without relying on text-level history.
+//! Each editor has a `*_with_record` counterpart here, such as
+//! [`update_story_status_with_record`], for single edits.
+//!
+//! It also lists the [`EditEvent`]s the operations produced, such as a
+//! story moving to `review`, and [`Hooks`] passes them to callbacks, so
+//! hosts can notify, log or sync without diffing the text themselves.
+
+use crate::diff::{ItemChange, diff_sprint, diff_workflow};
+use crate::sprint::{
+    SprintError, add_story, parse_sprint_status, raw_story_status, remove_story, rename_story,
+    restore_story, story_block, update_story_status,
+};
+use crate::workflow::{
+    WorkflowError, WorkflowField, complete_item, is_new_format, parse_workflow_status, raw_field,
+    raw_status, remove_workflow_field, reopen_item, update_workflow_field, update_workflow_status,
+};
+use serde::Serialize;
+use serde_yaml::Value;
+use std::fmt;
+use thiserror::Error;
+
+/// Largest changed region (lines before × lines after) diffed line by line;
+/// anything bigger is reported as one replaced block
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Error, Debug)]
+pub enum EditError {
+    #[error("Operation {index} failed: {source}")]
+    Workflow { index: usize, source: WorkflowError },
+    #[error("Operation {index} failed: {source}")]
+    Sprint { index: usize, source: SprintError },
+    #[error("Edited document is not valid YAML: {0}")]
+    InvalidResult(String),
+}
+
+/// A single edit to a workflow or sprint status document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    SetWorkflowStatus {
+        item_id: String,
+        status: String,
+    },
+    CompleteItem {
+        item_id: String,
+        output_file: Option<String>,
+    },
+    ReopenItem {
+        item_id: String,
+    },
+    SetWorkflowField {
+        item_id: String,
+        field: WorkflowField,
+        value: String,
+    },
+    RemoveWorkflowField {
+        item_id: String,
+        field: WorkflowField,
+    },
+    SetStoryStatus {
+        story_id: String,
+        status: String,
+    },
+    RenameStory {
+        old_id: String,
+        new_id: String,
+    },
+    AddStory {
+        story_id: String,
+        status: String,
+    },
+    RemoveStory {
+        story_id: String,
+    },
+    /// Put back a removed story: `block` is its text as written, nested
+    /// fields included, inserted after the story `after` (first when `None`)
+    RestoreStory {
+        story_id: String,
+        block: String,
+        after: Option<String>,
+    },
+}
+
+impl Operation {
+    /// Apply this operation on its own; `index` labels any error.
+    fn apply(&self, content: &str, index: usize) -> Result<String, EditError> {
+        let workflow = |source| EditError::Workflow { index, source };
+        let sprint = |source| EditError::Sprint { index, source };
+        match self {
+            Operation::SetWorkflowStatus { item_id, status } => {
+                update_workflow_status(content, item_id, status).map_err(workflow)
+            }
+            Operation::CompleteItem {
+                item_id,
+                output_file,
+            } => complete_item(content, item_id, output_file.as_deref()).map_err(workflow),
+            Operation::ReopenItem { item_id } => reopen_item(content, item_id).map_err(workflow),
+            Operation::SetWorkflowField {
+                item_id,
+                field,
+                value,
+            } => update_workflow_field(content, item_id, field.clone(), value).map_err(workflow),
+            Operation::RemoveWorkflowField { item_id, field } => {
+                remove_workflow_field(content, item_id, field.clone()).map_err(workflow)
+            }
+            Operation::SetStoryStatus { story_id, status } => {
+                update_story_status(content, story_id, status).map_err(sprint)
+            }
+            Operation::RenameStory { old_id, new_id } => {
+                rename_story(content, old_id, new_id).map_err(sprint)
+            }
+            Operation::AddStory { story_id, status } => {
+                add_story(content, story_id, status).map_err(sprint)
+            }
+            Operation::RemoveStory { story_id } => remove_story(content, story_id).map_err(sprint),
+            Operation::RestoreStory {
+                story_id,
+                block,
+                after,
+            } => restore_story(content, story_id, block, after.as_deref()).map_err(sprint),
+        }
+    }
+
+    /// Operations that undo this one, given the content it applies to.
+    ///
+    /// Only called once the operation itself has succeeded on `content`.
+    fn inverse(&self, content: &str, index: usize) -> Result<Vec<Operation>, EditError> {
+        let workflow = |source| EditError::Workflow { index, source };
+        let sprint = |source| EditError::Sprint { index, source };
+        let parsed: Value =
+            serde_yaml::from_str(content).map_err(|e| EditError::InvalidResult(e.to_string()))?;
+
+        let status_of = |item_id: &String| -> Result<Operation, EditError> {
+            let status = raw_status(&parsed, item_id)
+                .ok_or_else(|| workflow(WorkflowError::ItemNotFound(item_id.clone())))?;
+            Ok(Operation::SetWorkflowStatus {
+                item_id: item_id.clone(),
+                status,
+            })
+        };
+        // Put a field back as it was: rewrite its old value, or remove it
+        let field_of = |item_id: &String, field: &WorkflowField| -> Result<Operation, EditError> {
+            Ok(
+                match raw_field(content, item_id, field).map_err(workflow)? {
+                    Some(value) => Operation::SetWorkflowField {
+                        item_id: item_id.clone(),
+                        field: field.clone(),
+                        value,
+                    },
+                    None => Operation::RemoveWorkflowField {
+                        item_id: item_id.clone(),
+                        field: field.clone(),
+                    },
+                },
+            )
+        };
+        let story_status_of = |story_id: &String| {
+            raw_story_status(&parsed, story_id)
+                .ok_or_else(|| sprint(SprintError::StoryNotFound(story_id.clone())))
+        };
+
+        Ok(match self {
+            Operation::SetWorkflowStatus { item_id, .. } => vec![status_of(item_id)?],
+            Operation::CompleteItem {
+                item_id,
+                output_file,
+            } => {
+                let mut ops = vec![status_of(item_id)?];
+                if output_file.is_some() && is_new_format(content).map_err(workflow)? {
+                    ops.push(field_of(item_id, &WorkflowField::OutputFile)?);
+                }
+                ops
+            }
+            Operation::ReopenItem { item_id } => {
+                let mut ops = vec![status_of(item_id)?];
+                if is_new_format(content).map_err(workflow)? {
+                    ops.push(field_of(item_id, &WorkflowField::OutputFile)?);
+                }
+                ops
+            }
+            Operation::SetWorkflowField { item_id, field, .. }
+            | Operation::RemoveWorkflowField { item_id, field } => {
+                vec![field_of(item_id, field)?]
+            }
+            Operation::SetStoryStatus { story_id, .. } => vec![Operation::SetStoryStatus {
+                story_id: story_id.clone(),
+                status: story_status_of(story_id)?,
+            }],
+            Operation::RenameStory { old_id, new_id } => vec![Operation::RenameStory {
+                old_id: new_id.clone(),
+                new_id: old_id.clone(),
+            }],
+            Operation::AddStory { story_id, .. } | Operation::RestoreStory { story_id, .. } => {
+                vec![Operation::RemoveStory {
+                    story_id: story_id.clone(),
+                }]
+            }
+            // The story comes back as written, in its old place
+            Operation::RemoveStory { story_id } => {
+                let (block, after) = story_block(content, story_id)
+                    .ok_or_else(|| sprint(SprintError::StoryNotFound(story_id.clone())))?;
+                vec![Operation::RestoreStory {
+                    story_id: story_id.clone(),
+                    block,
+                    after,
+                }]
+            }
+        })
+    }
+}
+
+impl Operation {
+    /// Whether this operation edits a sprint file rather than a workflow
+    fn is_sprint(&self) -> bool {
+        matches!(
+            self,
+            Operation::SetStoryStatus { .. }
+                | Operation::RenameStory { .. }
+                | Operation::AddStory { .. }
+                | Operation::RemoveStory { .. }
+                | Operation::RestoreStory { .. }
+        )
+    }
+
+    /// What this operation changed, given the content before and after it
+    fn events(&self, before: &str, after: &str) -> Vec<EditEvent> {
+        if let Operation::RenameStory { old_id, new_id } = self {
+            return vec![EditEvent::ItemRenamed {
+                from: old_id.clone(),
+                to: new_id.clone(),
+            }];
+        }
+        let changes = if self.is_sprint() {
+            match (parse_sprint_status(before), parse_sprint_status(after)) {
+                (Ok(before), Ok(after)) => diff_sprint(&before, &after),
+                _ => Vec::new(),
+            }
+        } else {
+            match (parse_workflow_status(before), parse_workflow_status(after)) {
+                (Ok(before), Ok(after)) => diff_workflow(&before, &after),
+                _ => Vec::new(),
+            }
+        };
+        changes.into_iter().map(EditEvent::from).collect()
+    }
+}
+
+/// One change an applied operation made to the items of a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum EditEvent {
+    ItemStatusChanged {
+        id: String,
+        from: String,
+        to: String,
+    },
+    ItemAdded {
+        id: String,
+        status: String,
+    },
+    ItemRemoved {
+        id: String,
+        status: String,
+    },
+    /// A story renamed; its status is unchanged
+    ItemRenamed {
+        from: String,
+        to: String,
+    },
+}
+
+impl From<ItemChange> for EditEvent {
+    fn from(change: ItemChange) -> Self {
+        match change {
+            ItemChange::Added { id, status } => EditEvent::ItemAdded { id, status },
+            ItemChange::Removed { id, status } => EditEvent::ItemRemoved { id, status },
+            ItemChange::StatusChanged { id, from, to } => {
+                EditEvent::ItemStatusChanged { id, from, to }
+            }
+        }
+    }
+}
+
+/// A callback registered with [`Hooks`]
+type Callback = Box<dyn Fn(&EditEvent)>;
+
+/// Callbacks told about every event of an applied transaction
+#[derive(Default)]
+pub struct Hooks {
+    callbacks: Vec<Callback>,
+}
+
+impl Hooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a callback.
+    pub fn on(mut self, callback: impl Fn(&EditEvent) + 'static) -> Self {
+        self.register(callback);
+        self
+    }
+
+    /// Add a callback in place.
+    pub fn register(&mut self, callback: impl Fn(&EditEvent) + 'static) {
+        self.callbacks.push(Box::new(callback));
+    }
+
+    /// Call every callback, in registration order, with each event in turn.
+    pub fn emit(&self, events: &[EditEvent]) {
+        for event in events {
+            for callback in &self.callbacks {
+                callback(event);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("callbacks", &self.callbacks.len())
+            .finish()
+    }
+}
+
+/// What a transaction did, and the operations that take it back
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditRecord {
+    /// The operations as applied
+    pub operations: Vec<Operation>,
+    /// Operations restoring the previous values, in the order to apply them
+    pub inverse: Vec<Operation>,
+}
+
+/// The outcome of a successful transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Applied {
+    /// The document with every operation applied
+    pub content: String,
+    /// Unified diff (without context lines) from the original document
+    pub diff: String,
+    /// How to undo the transaction; see [`apply_inverse`]
+    pub record: EditRecord,
+    /// What each operation changed, in order
+    pub events: Vec<EditEvent>,
+}
+
+/// An ordered batch of operations against one document
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transaction {
+    operations: Vec<Operation>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an operation.
+    pub fn with(mut self, operation: Operation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Append an operation in place.
+    pub fn push(&mut self, operation: Operation) {
+        self.operations.push(operation);
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Apply every operation in order.
+    ///
+    /// Later operations see the effect of earlier ones. If any operation
+    /// fails, or the result no longer parses as YAML, the error is returned
+    /// and nothing is produced, so the caller never writes a half-edited file.
+    pub fn apply(&self, content: &str) -> Result<Applied, EditError> {
+        let mut edited = content.to_string();
+        let mut inverse = Vec::new();
+        let mut events = Vec::new();
+        for (index, operation) in self.operations.iter().enumerate() {
+            let next = operation.apply(&edited, index)?;
+            let mut undo = operation.inverse(&edited, index)?;
+            // Undo runs last-to-first
+            undo.append(&mut inverse);
+            inverse = undo;
+            events.extend(operation.events(&edited, &next));
+            edited = next;
+        }
+        serde_yaml::from_str::<Value>(&edited)
+            .map_err(|e| EditError::InvalidResult(e.to_string()))?;
+
+        let diff = unified_diff(content, &edited);
+        Ok(Applied {
+            content: edited,
+            diff,
+            record: EditRecord {
+                operations: self.operations.clone(),
+                inverse,
+            },
+            events,
+        })
+    }
+
+    /// [`Transaction::apply`], then pass the events to `hooks`. Nothing is
+    /// emitted when the transaction fails.
+    pub fn apply_with_hooks(&self, content: &str, hooks: &Hooks) -> Result<Applied, EditError> {
+        let applied = self.apply(content)?;
+        hooks.emit(&applied.events);
+        Ok(applied)
+    }
+}
+
+/// Undo a recorded transaction.
+///
+/// The inverse operations write back the previous values regardless of
+/// what the document holds now, so this is meant for the most recent edit.
+/// The returned record undoes the undo, i.e. it is the redo.
+pub fn apply_inverse(content: &str, record: &EditRecord) -> Result<Applied, EditError> {
+    Transaction {
+        operations: record.inverse.clone(),
+    }
+    .apply(content)
+}
+
+/// Apply one operation, returning the edited content and its undo record
+fn recorded(content: &str, operation: Operation) -> Result<(String, EditRecord), EditError> {
+    let applied = Transaction::new().with(operation).apply(content)?;
+    Ok((applied.content, applied.record))
+}
+
+/// [`update_workflow_status`] with an [`EditRecord`] for [`apply_inverse`]
+pub fn update_workflow_status_with_record(
+    content: &str,
+    item_id: &str,
+    status: &str,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::SetWorkflowStatus {
+            item_id: item_id.to_string(),
+            status: status.to_string(),
+        },
+    )
+}
+
+/// [`complete_item`] with an [`EditRecord`] for [`apply_inverse`]
+pub fn complete_item_with_record(
+    content: &str,
+    item_id: &str,
+    output_file: Option<&str>,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::CompleteItem {
+            item_id: item_id.to_string(),
+            output_file: output_file.map(str::to_string),
+        },
+    )
+}
+
+/// [`reopen_item`] with an [`EditRecord`] for [`apply_inverse`]
+pub fn reopen_item_with_record(
+    content: &str,
+    item_id: &str,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::ReopenItem {
+            item_id: item_id.to_string(),
+        },
+    )
+}
+
+/// [`update_workflow_field`] with an [`EditRecord`] for [`apply_inverse`]
+pub fn update_workflow_field_with_record(
+    content: &str,
+    item_id: &str,
+    field: WorkflowField,
+    value: &str,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::SetWorkflowField {
+            item_id: item_id.to_string(),
+            field,
+            value: value.to_string(),
+        },
+    )
+}
+
+/// [`remove_workflow_field`] with an [`EditRecord`] for [`apply_inverse`]
+pub fn remove_workflow_field_with_record(
+    content: &str,
+    item_id: &str,
+    field: WorkflowField,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::RemoveWorkflowField {
+            item_id: item_id.to_string(),
+            field,
+        },
+    )
+}
+
+/// [`update_story_status`] with an [`EditRecord`] for [`apply_inverse`]
+pub fn update_story_status_with_record(
+    content: &str,
+    story_id: &str,
+    status: &str,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::SetStoryStatus {
+            story_id: story_id.to_string(),
+            status: status.to_string(),
+        },
+    )
+}
+
+/// [`rename_story`] with an [`EditRecord`] for [`apply_inverse`]
+pub fn rename_story_with_record(
+    content: &str,
+    old_id: &str,
+    new_id: &str,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::RenameStory {
+            old_id: old_id.to_string(),
+            new_id: new_id.to_string(),
+        },
+    )
+}
+
+/// [`add_story`] with an [`EditRecord`] for [`apply_inverse`]
+pub fn add_story_with_record(
+    content: &str,
+    story_id: &str,
+    status: &str,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::AddStory {
+            story_id: story_id.to_string(),
+            status: status.to_string(),
+        },
+    )
+}
+
+/// [`remove_story`] with an [`EditRecord`] for [`apply_inverse`]; undoing
+/// it puts the story back as written, in its old place
+pub fn remove_story_with_record(
+    content: &str,
+    story_id: &str,
+) -> Result<(String, EditRecord), EditError> {
+    recorded(
+        content,
+        Operation::RemoveStory {
+            story_id: story_id.to_string(),
+        },
+    )
+}
+
+/// Hunk header range: `start,count`, with `count` left out when it is 1
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        // An empty range names the line before it
+        0 => format!("{},0", start.saturating_sub(1)),
+        1 => start.to_string(),
+        _ => format!("{},{}", start, count),
+    }
+}
+
+/// Line-level unified diff between `before` and `after`, without context
+/// lines. Empty when the two are identical.
+pub fn unified_diff(before: &str, after: &str) -> String {
+    let a: Vec<&str> = before.lines().collect();
+    let b: Vec<&str> = after.lines().collect();
+
+    // Trim the common prefix and suffix, then align what is left
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    if a_mid.is_empty() && b_mid.is_empty() {
+        return String::new();
+    }
+
+    // Matched line pairs within the middle, by longest common subsequence
+    let mut matches = Vec::new();
+    if a_mid.len() * b_mid.len() <= MAX_DIFF_CELLS {
+        let (n, m) = (a_mid.len(), b_mid.len());
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if a_mid[i] == b_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if a_mid[i] == b_mid[j] {
+                matches.push((i, j));
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+    matches.push((a_mid.len(), b_mid.len()));
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    for (mi, mj) in matches {
+        if mi > i || mj > j {
+            out.push_str(&format!(
+                "@@ -{} +{} @@\n",
+                hunk_range(prefix + i + 1, mi - i),
+                hunk_range(prefix + j + 1, mj - j)
+            ));
+            for line in &a_mid[i..mi] {
+                out.push_str(&format!("-{}\n", line));
+            }
+            for line in &b_mid[j..mj] {
+                out.push_str(&format!("+{}\n", line));
+            }
+        }
+        (i, j) = (mi + 1, mj + 1);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+    use crate::workflow::parse_workflow_status;
+
+    const SPRINT_YAML: &str =
+        "development_status:\n  epic-1: in-progress\n  1-login: in-progress\n  1-signup: backlog\n";
+
+    const WORKFLOW_YAML: &str =
+        "workflows:\n  prd:\n    status: not_started\n  architecture:\n    status: not_started\n";
+
+    // =========================================================================
+    // Transaction Tests
+    // =========================================================================
+
+    #[test]
+    fn test_sprint_transaction() {
+        let transaction = Transaction::new()
+            .with(Operation::SetStoryStatus {
+                story_id: "1-login".to_string(),
+                status: "review".into(),
+            })
+            .with(Operation::RenameStory {
+                old_id: "1-signup".to_string(),
+                new_id: "1-register".to_string(),
+            })
+            .with(Operation::AddStory {
+                story_id: "1-logout".to_string(),
+                status: "backlog".into(),
+            })
+            .with(Operation::SetStoryStatus {
+                story_id: "1-register".to_string(),
+                status: "drafted".into(),
+            });
+        assert_eq!(transaction.len(), 4);
+
+        let applied = transaction.apply(SPRINT_YAML).unwrap();
+        assert_eq!(
+            applied.content,
+            "development_status:\n  epic-1: in-progress\n  1-login: review\n  1-register: drafted\n  1-logout: backlog\n"
+        );
+        assert_eq!(
+            applied.diff,
+            "@@ -3,2 +3,3 @@\n-  1-login: in-progress\n-  1-signup: backlog\n+  1-login: review\n+  1-register: drafted\n+  1-logout: backlog\n"
+        );
+        assert_eq!(
+            parse_sprint_status(&applied.content).unwrap().epics[0]
+                .stories
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_workflow_transaction() {
+        let mut transaction = Transaction::new();
+        transaction.push(Operation::CompleteItem {
+            item_id: "prd".to_string(),
+            output_file: Some("docs/prd.md".to_string()),
+        });
+        transaction.push(Operation::SetWorkflowField {
+            item_id: "architecture".to_string(),
+            field: WorkflowField::Notes,
+            value: "waiting on prd".to_string(),
+        });
+
+        let applied = transaction.apply(WORKFLOW_YAML).unwrap();
+        let data = parse_workflow_status(&applied.content).unwrap();
+        let item = |id: &str| data.items.iter().find(|i| i.id == id).unwrap();
+        assert_eq!(item("prd").output_file.as_deref(), Some("docs/prd.md"));
+        assert_eq!(item("architecture").note.as_deref(), Some("waiting on prd"));
+        assert!(applied.diff.contains("+    output_file: docs/prd.md\n"));
+        assert!(applied.diff.contains("+    notes: waiting on prd\n"));
+    }
+
+    #[test]
+    fn test_transaction_is_all_or_nothing() {
+        let transaction = Transaction::new()
+            .with(Operation::SetStoryStatus {
+                story_id: "1-login".to_string(),
+                status: "done".into(),
+            })
+            .with(Operation::RemoveStory {
+                story_id: "9-missing".to_string(),
+            });
+        let err = transaction.apply(SPRINT_YAML).unwrap_err();
+        assert!(matches!(
+            err,
+            EditError::Sprint {
+                index: 1,
+                source: SprintError::StoryNotFound(_)
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Operation 1 failed: Story not found: 9-missing"
+        );
+
+        let wrong_kind = Transaction::new().with(Operation::ReopenItem {
+            item_id: "1-login".to_string(),
+        });
+        assert!(matches!(
+            wrong_kind.apply(SPRINT_YAML),
+            Err(EditError::Workflow { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_empty_transaction() {
+        let transaction = Transaction::new();
+        assert!(transaction.is_empty());
+        let applied = transaction.apply(SPRINT_YAML).unwrap();
+        assert_eq!(applied.content, SPRINT_YAML);
+        assert_eq!(applied.diff, "");
+    }
+
+    // =========================================================================
+    // Undo Tests
+    // =========================================================================
+
+    #[test]
+    fn test_undo_sprint_transaction() {
+        let transaction = Transaction::new()
+            .with(Operation::RenameStory {
+                old_id: "1-signup".to_string(),
+                new_id: "1-register".to_string(),
+            })
+            .with(Operation::SetStoryStatus {
+                story_id: "1-register".to_string(),
+                status: "drafted".into(),
+            })
+            .with(Operation::AddStory {
+                story_id: "1-logout".to_string(),
+                status: "backlog".into(),
+            });
+        let applied = transaction.apply(SPRINT_YAML).unwrap();
+        assert_eq!(
+            applied.record.inverse,
+            vec![
+                Operation::RemoveStory {
+                    story_id: "1-logout".to_string()
+                },
+                Operation::SetStoryStatus {
+                    story_id: "1-register".to_string(),
+                    status: "backlog".into()
+                },
+                Operation::RenameStory {
+                    old_id: "1-register".to_string(),
+                    new_id: "1-signup".to_string()
+                },
+            ]
+        );
+
+        let undone = apply_inverse(&applied.content, &applied.record).unwrap();
+        assert_eq!(undone.content, SPRINT_YAML);
+        // The undo's own record is the redo
+        let redone = apply_inverse(&undone.content, &undone.record).unwrap();
+        assert_eq!(redone.content, applied.content);
+    }
+
+    #[test]
+    fn test_undo_workflow_edits() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n    notes: draft\n  architecture:\n    status: complete\n    output_file: docs/arch.md\n";
+        let transaction = Transaction::new()
+            .with(Operation::CompleteItem {
+                item_id: "prd".to_string(),
+                output_file: Some("docs/prd.md".to_string()),
+            })
+            .with(Operation::RemoveWorkflowField {
+                item_id: "prd".to_string(),
+                field: WorkflowField::Notes,
+            })
+            .with(Operation::ReopenItem {
+                item_id: "architecture".to_string(),
+            })
+            .with(Operation::SetWorkflowField {
+                item_id: "architecture".to_string(),
+                field: WorkflowField::Custom("owner".to_string()),
+                value: "sam".to_string(),
+            });
+        let applied = transaction.apply(yaml).unwrap();
+        assert!(!applied.content.contains("notes"));
+        assert!(!applied.content.contains("docs/arch.md"));
+
+        let undone = apply_inverse(&applied.content, &applied.record).unwrap();
+        assert_eq!(undone.content, yaml);
+        assert_eq!(undone.record.operations, applied.record.inverse);
+    }
+
+    #[test]
+    fn test_undo_removed_story() {
+        let applied = Transaction::new()
+            .with(Operation::RemoveStory {
+                story_id: "1-login".to_string(),
+            })
+            .apply(SPRINT_YAML)
+            .unwrap();
+        let undone = apply_inverse(&applied.content, &applied.record).unwrap();
+        assert_eq!(undone.content, SPRINT_YAML);
+        let redone = apply_inverse(&undone.content, &undone.record).unwrap();
+        assert_eq!(redone.content, applied.content);
+
+        assert!(
+            apply_inverse(SPRINT_YAML, &EditRecord::default())
+                .unwrap()
+                .diff
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_undo_removed_extended_story() {
+        let yaml = "development_status:\n  epic-1: in-progress\n  1-1-login:\n    status: review\n    \
+                    title: Login # shown on the board\n    assignee: ana\n    tags: [auth]\n  1-2-signup: backlog\n";
+        let (content, record) = remove_story_with_record(yaml, "1-1-login").unwrap();
+        assert_eq!(
+            content,
+            "development_status:\n  epic-1: in-progress\n  1-2-signup: backlog\n"
+        );
+        assert_eq!(apply_inverse(&content, &record).unwrap().content, yaml);
+
+        // A first story goes back first, even when the rest changed since
+        let first = "development_status:\n  1-1-a: done\n  1-2-b: backlog\n";
+        let (content, record) = remove_story_with_record(first, "1-1-a").unwrap();
+        let (content, _) = update_story_status_with_record(&content, "1-2-b", "review").unwrap();
+        assert_eq!(
+            apply_inverse(&content, &record).unwrap().content,
+            "development_status:\n  1-1-a: done\n  1-2-b: review\n"
+        );
+    }
+
+    #[test]
+    fn test_update_functions_with_record() {
+        let (content, record) =
+            update_workflow_status_with_record(WORKFLOW_YAML, "prd", "docs/prd.md").unwrap();
+        assert_eq!(
+            content,
+            update_workflow_status(WORKFLOW_YAML, "prd", "docs/prd.md").unwrap()
+        );
+        assert_eq!(
+            record.inverse,
+            vec![Operation::SetWorkflowStatus {
+                item_id: "prd".to_string(),
+                status: "not_started".to_string(),
+            }]
+        );
+        assert_eq!(
+            apply_inverse(&content, &record).unwrap().content,
+            WORKFLOW_YAML
+        );
+
+        let (content, record) = complete_item_with_record(WORKFLOW_YAML, "prd", None).unwrap();
+        let (content, _) = update_workflow_field_with_record(
+            &content,
+            "architecture",
+            WorkflowField::Notes,
+            "later",
+        )
+        .unwrap();
+        let (content, _) =
+            remove_workflow_field_with_record(&content, "architecture", WorkflowField::Notes)
+                .unwrap();
+        assert_eq!(
+            apply_inverse(&content, &record).unwrap().content,
+            WORKFLOW_YAML
+        );
+        let (reopened, record) = reopen_item_with_record(&content, "prd").unwrap();
+        assert_eq!(apply_inverse(&reopened, &record).unwrap().content, content);
+
+        let (content, record) =
+            rename_story_with_record(SPRINT_YAML, "1-login", "1-sign-in").unwrap();
+        let (content, _) = add_story_with_record(&content, "1-logout", "backlog").unwrap();
+        assert!(content.contains("1-sign-in"));
+        assert!(matches!(
+            update_story_status_with_record(&content, "9-missing", "done"),
+            Err(EditError::Sprint { index: 0, .. })
+        ));
+        assert_eq!(
+            record.inverse,
+            vec![Operation::RenameStory {
+                old_id: "1-sign-in".to_string(),
+                new_id: "1-login".to_string(),
+            }]
+        );
+    }
+
+    // =========================================================================
+    // Diff Tests
+    // =========================================================================
+
+    #[test]
+    fn test_unified_diff_hunks() {
+        let before = "a\nb\nc\nd\ne\n";
+        let after = "a\nB\nc\nd\ne\nf\n";
+        assert_eq!(
+            unified_diff(before, after),
+            "@@ -2 +2 @@\n-b\n+B\n@@ -5,0 +6 @@\n+f\n"
+        );
+
+        assert_eq!(unified_diff("a\nb\nc\n", "a\nc\n"), "@@ -2 +1,0 @@\n-b\n");
+        assert_eq!(unified_diff("", "x\n"), "@@ -0,0 +1 @@\n+x\n");
+        assert_eq!(unified_diff("same\n", "same\n"), "");
+    }
+
+    // =========================================================================
+    // Event Tests
+    // =========================================================================
+
+    #[test]
+    fn test_sprint_events() {
+        let applied = Transaction::new()
+            .with(Operation::SetStoryStatus {
+                story_id: "1-login".to_string(),
+                status: "review".into(),
+            })
+            .with(Operation::RenameStory {
+                old_id: "1-signup".to_string(),
+                new_id: "1-register".to_string(),
+            })
+            .with(Operation::AddStory {
+                story_id: "1-logout".to_string(),
+                status: "backlog".into(),
+            })
+            .with(Operation::SetStoryStatus {
+                story_id: "1-logout".to_string(),
+                status: "backlog".into(),
+            })
+            .with(Operation::RemoveStory {
+                story_id: "1-logout".to_string(),
+            })
+            .apply(SPRINT_YAML)
+            .unwrap();
+        assert_eq!(
+            applied.events,
+            vec![
+                EditEvent::ItemStatusChanged {
+                    id: "1-login".to_string(),
+                    from: "in-progress".to_string(),
+                    to: "review".to_string(),
+                },
+                EditEvent::ItemRenamed {
+                    from: "1-signup".to_string(),
+                    to: "1-register".to_string(),
+                },
+                EditEvent::ItemAdded {
+                    id: "1-logout".to_string(),
+                    status: "backlog".to_string(),
+                },
+                EditEvent::ItemRemoved {
+                    id: "1-logout".to_string(),
+                    status: "backlog".to_string(),
+                },
+            ]
+        );
+        let json = serde_json::to_value(&applied.events[0]).unwrap();
+        assert_eq!(json["kind"], "itemStatusChanged");
+        assert_eq!(json["to"], "review");
+    }
+
+    #[test]
+    fn test_workflow_events() {
+        let applied = Transaction::new()
+            .with(Operation::CompleteItem {
+                item_id: "prd".to_string(),
+                output_file: Some("docs/prd.md".to_string()),
+            })
+            .with(Operation::SetWorkflowField {
+                item_id: "architecture".to_string(),
+                field: WorkflowField::Notes,
+                value: "draft".to_string(),
+            })
+            .apply(WORKFLOW_YAML)
+            .unwrap();
+        // Statuses read as the parser reports them, like `diff_workflow`
+        assert_eq!(
+            applied.events,
+            vec![EditEvent::ItemStatusChanged {
+                id: "prd".to_string(),
+                from: "required".to_string(),
+                to: "docs/prd.md".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_hooks() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&seen);
+        let count = Rc::new(RefCell::new(0));
+        let counter = Rc::clone(&count);
+        let hooks = Hooks::new()
+            .on(move |event| log.borrow_mut().push(event.clone()))
+            .on(move |_| *counter.borrow_mut() += 1);
+        assert_eq!(format!("{:?}", hooks), "Hooks { callbacks: 2 }");
+
+        let transaction = Transaction::new().with(Operation::SetStoryStatus {
+            story_id: "1-signup".to_string(),
+            status: "ready-for-dev".into(),
+        });
+        let applied = transaction.apply_with_hooks(SPRINT_YAML, &hooks).unwrap();
+        assert_eq!(*seen.borrow(), applied.events);
+        assert_eq!(*count.borrow(), 1);
+
+        // A failed transaction notifies nobody
+        let failing = transaction.with(Operation::RemoveStory {
+            story_id: "9-missing".to_string(),
+        });
+        assert!(failing.apply_with_hooks(SPRINT_YAML, &hooks).is_err());
+        assert_eq!(*count.borrow(), 1);
+    }
+}
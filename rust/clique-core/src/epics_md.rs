@@ -0,0 +1,219 @@
+// clique-core/src/epics_md.rs
+//! Cross-referencing sprint data with the epics.md breakdown.
+//!
+//! The BMad epics-stories workflow writes headings like
+//! `## Epic 1: Foundation` and `### Story 1.2: User Login`, each story
+//! followed by an acceptance criteria section. Sprint story IDs carry the
+//! same numbers as a prefix (`1-2-user-login`), which is what links the two.
+
+use crate::pattern::{criteria_label, epic_heading, list_item, story_heading};
+use crate::types::SprintData;
+use std::collections::HashMap;
+
+/// What epics.md says about one story
+#[derive(Debug, Default)]
+struct StoryEntry {
+    title: String,
+    list_items: usize,
+    scenarios: usize,
+}
+
+impl StoryEntry {
+    /// Numbered or bulleted criteria, falling back to Given/When/Then scenarios
+    fn criteria(&self) -> usize {
+        if self.list_items > 0 {
+            self.list_items
+        } else {
+            self.scenarios
+        }
+    }
+}
+
+/// Extract epic names and story entries keyed by their numbers
+fn parse_epics_md(markdown: &str) -> (HashMap<String, String>, HashMap<String, StoryEntry>) {
+    let mut epics = HashMap::new();
+    let mut stories: HashMap<String, StoryEntry> = HashMap::new();
+    let mut current: Option<String> = None;
+    let mut in_criteria = false;
+
+    for line in markdown.lines() {
+        let line = line.trim();
+
+        if let Some((number, name)) = epic_heading(line) {
+            epics.insert(number.to_string(), name.to_string());
+            current = None;
+            in_criteria = false;
+            continue;
+        }
+        if let Some((epic, story, title)) = story_heading(line) {
+            let key = format!("{}-{}", epic, story);
+            stories.insert(
+                key.clone(),
+                StoryEntry {
+                    title: title.to_string(),
+                    ..StoryEntry::default()
+                },
+            );
+            current = Some(key);
+            in_criteria = false;
+            continue;
+        }
+
+        let Some(entry) = current.as_ref().and_then(|key| stories.get_mut(key)) else {
+            continue;
+        };
+        if criteria_label(line) {
+            in_criteria = true;
+        } else if line.starts_with('#') || (line.starts_with("**") && line.contains(":**")) {
+            // Any other heading or bold label such as "**Prerequisites:**" ends the section
+            in_criteria = false;
+        } else if in_criteria {
+            if list_item(line) {
+                entry.list_items += 1;
+            } else if line.starts_with("**Given**") || line.starts_with("Given ") {
+                entry.scenarios += 1;
+            }
+        }
+    }
+
+    (epics, stories)
+}
+
+/// Fill in epic names, story titles and acceptance criteria counts from
+/// the epics.md breakdown.
+///
+/// Stories are matched by their `epic-story` number prefix, so
+/// `### Story 1.2: User Login` applies to `1-2-user-login`. Anything not
+/// mentioned in the markdown is left untouched.
+pub fn enrich_from_epics_md(data: &mut SprintData, epics_markdown: &str) {
+    let (epics, stories) = parse_epics_md(epics_markdown);
+
+    for epic in &mut data.epics {
+        let number = epic.id.trim_start_matches("epic-");
+        if let Some(name) = epics.get(number) {
+            epic.name = name.clone();
+        }
+
+        for story in &mut epic.stories {
+            let mut parts = story.id.splitn(3, '-');
+            let (Some(epic_num), Some(story_num)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if let Some(entry) = stories.get(&format!("{}-{}", epic_num, story_num)) {
+                story.title = Some(entry.title.clone());
+                story.acceptance_criteria = Some(entry.criteria());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+
+    const EPICS_MD: &str = r#"# Demo - Epic Breakdown
+
+## Epic 1: Foundation & Core Infrastructure
+
+Goal: set up the project.
+
+### Story 1.1: Project Setup
+
+As a developer, I want a repository, so that work can start.
+
+**Acceptance Criteria:**
+
+1. Repository initialized
+2. CI pipeline runs on push
+3. README describes setup
+
+**Prerequisites:** None
+- Not a criterion
+
+### Story 1.2: User Login
+
+**Acceptance Criteria:**
+
+**Given** a registered user
+**When** they submit valid credentials
+**Then** they are signed in
+
+**Given** an unknown user
+**When** they submit credentials
+**Then** an error is shown
+
+## Epic 2 - Reporting
+
+### Story 2.1: Export CSV
+
+#### Acceptance Criteria
+
+- [ ] Export button on the dashboard
+- [x] File uses UTF-8
+
+#### Technical Notes
+
+- Use the streaming writer
+"#;
+
+    const SPRINT_YAML: &str = r#"
+development_status:
+  epic-1: in-progress
+  1-1-project-setup: done
+  1-2-user-login: in-progress
+  1-3-profile: backlog
+  epic-2: backlog
+  2-1-export-csv: backlog
+  epic-3: backlog
+"#;
+
+    fn enriched() -> SprintData {
+        let mut data = parse_sprint_status(SPRINT_YAML).unwrap();
+        enrich_from_epics_md(&mut data, EPICS_MD);
+        data
+    }
+
+    #[test]
+    fn test_epic_names() {
+        let data = enriched();
+        let names: Vec<&str> = data.epics.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Foundation & Core Infrastructure", "Reporting", "Epic 3"]
+        );
+    }
+
+    #[test]
+    fn test_story_titles() {
+        let data = enriched();
+        let story = |id: &str| data.find_story(id).unwrap();
+        assert_eq!(
+            story("1-1-project-setup").title.as_deref(),
+            Some("Project Setup")
+        );
+        assert_eq!(story("2-1-export-csv").title.as_deref(), Some("Export CSV"));
+        assert!(story("1-3-profile").title.is_none());
+    }
+
+    #[test]
+    fn test_acceptance_criteria_counts() {
+        let data = enriched();
+        let count = |id: &str| data.find_story(id).unwrap().acceptance_criteria;
+        // Numbered list, stopping at the next bold label
+        assert_eq!(count("1-1-project-setup"), Some(3));
+        // Given/When/Then scenarios
+        assert_eq!(count("1-2-user-login"), Some(2));
+        // Checklist under a heading, stopping at the next heading
+        assert_eq!(count("2-1-export-csv"), Some(2));
+        assert_eq!(count("1-3-profile"), None);
+    }
+
+    #[test]
+    fn test_empty_markdown_changes_nothing() {
+        let original = parse_sprint_status(SPRINT_YAML).unwrap();
+        let mut data = original.clone();
+        enrich_from_epics_md(&mut data, "");
+        assert_eq!(data, original);
+    }
+}
@@ -0,0 +1,272 @@
+// clique-core/src/ffi.rs
+//! C ABI for embedding the parsers outside Node (Neovim via LuaJIT FFI,
+//! JetBrains via JNI, anything that can call C).
+//!
+//! Every call that can fail returns a JSON envelope as a heap-allocated C
+//! string: `{"ok": <result>}` on success or `{"error": "<message>"}` on
+//! failure. The caller owns the string and must hand it back to
+//! [`clique_string_free`]. The declarations are in `include/clique.h`,
+//! generated with cbindgen (see the crate README).
+//!
+//! A panic never unwinds into the caller: it is caught and returned as
+//! `{"error": "internal panic: <message>"}`. That needs a build that
+//! unwinds; under the workspace's `release` profile (`panic = "abort"`) a
+//! panic still aborts the process.
+
+use crate::sprint::{parse_sprint_status, update_story_status};
+use crate::workflow::{parse_workflow_status, update_workflow_status};
+use serde::Serialize;
+use serde_json::json;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// Library version, NUL-terminated
+static VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+/// Hand a JSON envelope to the caller
+fn envelope(value: serde_json::Value) -> *mut c_char {
+    // JSON escapes control characters, so the text holds no interior NUL
+    CString::new(value.to_string())
+        .map(CString::into_raw)
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Run the body of an exported function, answering with its result or
+/// with the panic that stopped it
+fn respond<T: Serialize, E: std::fmt::Display>(body: impl FnOnce() -> Result<T, E>) -> *mut c_char {
+    envelope(match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => json!({ "ok": value }),
+        Ok(Err(e)) => json!({ "error": e.to_string() }),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown cause".to_string());
+            json!({ "error": format!("internal panic: {}", message) })
+        }
+    })
+}
+
+/// Borrow a C string argument as UTF-8.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string that outlives the
+/// returned slice.
+unsafe fn arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, String> {
+    if ptr.is_null() {
+        return Err(format!("{} is null", name));
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| format!("{} is not valid UTF-8", name))
+}
+
+/// Parse a workflow status file; `ok` holds the WorkflowData.
+///
+/// # Safety
+///
+/// `yaml` must be null or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn clique_parse_workflow(yaml: *const c_char) -> *mut c_char {
+    respond(|| {
+        // SAFETY: forwarded from this function's contract
+        unsafe { arg(yaml, "yaml") }
+            .and_then(|yaml| parse_workflow_status(yaml).map_err(|e| e.to_string()))
+    })
+}
+
+/// Parse a sprint status file; `ok` holds the SprintData.
+///
+/// # Safety
+///
+/// `yaml` must be null or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn clique_parse_sprint(yaml: *const c_char) -> *mut c_char {
+    respond(|| {
+        // SAFETY: forwarded from this function's contract
+        unsafe { arg(yaml, "yaml") }
+            .and_then(|yaml| parse_sprint_status(yaml).map_err(|e| e.to_string()))
+    })
+}
+
+/// Set a workflow item's status; `ok` holds the updated YAML.
+///
+/// # Safety
+///
+/// Each argument must be null or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn clique_update_workflow_status(
+    content: *const c_char,
+    item_id: *const c_char,
+    new_status: *const c_char,
+) -> *mut c_char {
+    respond(|| {
+        // SAFETY: forwarded from this function's contract
+        unsafe {
+            arg(content, "content").and_then(|content| {
+                Ok((
+                    content,
+                    arg(item_id, "item_id")?,
+                    arg(new_status, "new_status")?,
+                ))
+            })
+        }
+        .and_then(|(content, id, status)| {
+            update_workflow_status(content, id, status).map_err(|e| e.to_string())
+        })
+    })
+}
+
+/// Set a story's status; `ok` holds the updated YAML.
+///
+/// # Safety
+///
+/// Each argument must be null or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn clique_update_story_status(
+    content: *const c_char,
+    story_id: *const c_char,
+    new_status: *const c_char,
+) -> *mut c_char {
+    respond(|| {
+        // SAFETY: forwarded from this function's contract
+        unsafe {
+            arg(content, "content").and_then(|content| {
+                Ok((
+                    content,
+                    arg(story_id, "story_id")?,
+                    arg(new_status, "new_status")?,
+                ))
+            })
+        }
+        .and_then(|(content, id, status)| {
+            update_story_status(content, id, status).map_err(|e| e.to_string())
+        })
+    })
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `ptr` must be null or a string returned by a `clique_*` function that
+/// has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn clique_string_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        // SAFETY: allocated by CString::into_raw in `envelope`
+        let _ = catch_unwind(|| drop(unsafe { CString::from_raw(ptr) }));
+    }
+}
+
+/// The library version as a static string; do not free it.
+#[unsafe(no_mangle)]
+pub extern "C" fn clique_version() -> *const c_char {
+    VERSION.as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Take ownership of a returned envelope and decode it
+    fn take(ptr: *mut c_char) -> Value {
+        assert!(!ptr.is_null());
+        let text = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+        unsafe { clique_string_free(ptr) };
+        serde_json::from_str(&text).unwrap()
+    }
+
+    fn c(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    // =========================================================================
+    // Parse Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_functions() {
+        let yaml = c("workflow_status:\n  prd: required\n");
+        let result = take(unsafe { clique_parse_workflow(yaml.as_ptr()) });
+        assert_eq!(result["ok"]["items"][0]["id"], "prd");
+
+        let yaml = c("development_status:\n  epic-1: backlog\n  1-a: done\n");
+        let result = take(unsafe { clique_parse_sprint(yaml.as_ptr()) });
+        assert_eq!(result["ok"]["epics"][0]["stories"][0]["status"], "done");
+
+        let result = take(unsafe { clique_parse_sprint(c("[oops").as_ptr()) });
+        assert!(result["error"].is_string());
+        assert!(result.get("ok").is_none());
+    }
+
+    #[test]
+    fn test_bad_arguments() {
+        let result = take(unsafe { clique_parse_workflow(std::ptr::null()) });
+        assert_eq!(result["error"], "yaml is null");
+
+        let invalid = [0xffu8, 0];
+        let result = take(unsafe { clique_parse_workflow(invalid.as_ptr().cast()) });
+        assert_eq!(result["error"], "yaml is not valid UTF-8");
+    }
+
+    // =========================================================================
+    // Update Tests
+    // =========================================================================
+
+    #[test]
+    fn test_update_functions() {
+        let content = c("workflow_status:\n  prd: required\n");
+        let result = take(unsafe {
+            clique_update_workflow_status(content.as_ptr(), c("prd").as_ptr(), c("done").as_ptr())
+        });
+        assert_eq!(result["ok"], "workflow_status:\n  prd: done\n");
+
+        let content = c("development_status:\n  epic-1: backlog\n  1-a: backlog\n");
+        let result = take(unsafe {
+            clique_update_story_status(content.as_ptr(), c("9-x").as_ptr(), c("done").as_ptr())
+        });
+        assert!(result["error"].as_str().unwrap().contains("9-x"));
+
+        let result = take(unsafe {
+            clique_update_story_status(content.as_ptr(), c("1-a").as_ptr(), std::ptr::null())
+        });
+        assert_eq!(result["error"], "new_status is null");
+    }
+
+    #[test]
+    fn test_panics_become_errors() {
+        let result = take(respond(|| -> Result<(), String> { panic!("boom") }));
+        assert_eq!(result["error"], "internal panic: boom");
+        let result = take(respond(|| -> Result<(), String> {
+            panic!("{} failed", "update")
+        }));
+        assert_eq!(result["error"], "internal panic: update failed");
+    }
+
+    #[test]
+    fn test_version_and_free() {
+        let version = unsafe { CStr::from_ptr(clique_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+        unsafe { clique_string_free(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn test_header_declares_every_function() {
+        let header = include_str!("../include/clique.h");
+        for name in [
+            "clique_parse_workflow",
+            "clique_parse_sprint",
+            "clique_update_workflow_status",
+            "clique_update_story_status",
+            "clique_string_free",
+            "clique_version",
+        ] {
+            assert!(header.contains(&format!("{}(", name)), "{name}");
+        }
+    }
+}
@@ -0,0 +1,275 @@
+// clique-core/src/gate.rs
+//! Merge gates for CI pipelines.
+//!
+//! A [`GatePolicy`] names the workflow steps that must be complete and caps
+//! how many stories may be in progress at once. [`evaluate`] checks a
+//! project against it and lists every condition that does not hold, not just
+//! the first, so a failing pipeline shows everything left to fix.
+
+use crate::types::{SprintData, StatusClass, StoryStatus, WorkflowData, normalize_status};
+use serde::{Deserialize, Serialize};
+
+/// Conditions a project must meet to pass the gate
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GatePolicy {
+    /// Workflow item IDs whose status must be complete; skipped does not count
+    pub require_complete: Vec<String>,
+    /// Most stories allowed in progress at once; `None` for no limit
+    pub max_in_progress: Option<usize>,
+}
+
+impl GatePolicy {
+    pub fn require_complete(mut self, id: &str) -> Self {
+        self.require_complete.push(id.to_string());
+        self
+    }
+
+    pub fn with_max_in_progress(mut self, max: usize) -> Self {
+        self.max_in_progress = Some(max);
+        self
+    }
+}
+
+/// One condition of the policy that the project does not meet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum GateFailure {
+    /// A required workflow item is not in the workflow file at all
+    Missing { id: String },
+    /// A required workflow item is still pending or was skipped
+    Incomplete { id: String, status: String },
+    /// More stories are in progress than the policy allows
+    TooManyInProgress {
+        count: usize,
+        max: usize,
+        stories: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for GateFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GateFailure::Missing { id } => write!(f, "{} must be complete but is missing", id),
+            GateFailure::Incomplete { id, status } => {
+                write!(f, "{} must be complete but is {}", id, status)
+            }
+            GateFailure::TooManyInProgress {
+                count,
+                max,
+                stories,
+            } => write!(
+                f,
+                "{} stories in progress, at most {} allowed: {}",
+                count,
+                max,
+                stories.join(", ")
+            ),
+        }
+    }
+}
+
+/// Outcome of [`evaluate`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GateReport {
+    pub passed: bool,
+    pub failures: Vec<GateFailure>,
+    /// Stories in progress, counted whether or not a limit is set
+    pub in_progress: usize,
+}
+
+/// Check `workflow` and `sprint` against `policy`.
+pub fn evaluate(workflow: &WorkflowData, sprint: &SprintData, policy: &GatePolicy) -> GateReport {
+    let mut failures = Vec::new();
+    for id in &policy.require_complete {
+        match workflow.items.iter().find(|item| &item.id == id) {
+            None => failures.push(GateFailure::Missing { id: id.clone() }),
+            Some(item) if item.status_class() != StatusClass::Complete => {
+                failures.push(GateFailure::Incomplete {
+                    id: id.clone(),
+                    status: item.status.to_string(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    let in_progress: Vec<String> = sprint
+        .epics
+        .iter()
+        .flat_map(|epic| &epic.stories)
+        .filter(|story| normalize_status(&story.status) == StoryStatus::InProgress)
+        .map(|story| story.id.clone())
+        .collect();
+    if let Some(max) = policy.max_in_progress
+        && in_progress.len() > max
+    {
+        failures.push(GateFailure::TooManyInProgress {
+            count: in_progress.len(),
+            max,
+            stories: in_progress.clone(),
+        });
+    }
+
+    GateReport {
+        passed: failures.is_empty(),
+        failures,
+        in_progress: in_progress.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+    use crate::workflow::parse_workflow_status;
+
+    fn workflow() -> WorkflowData {
+        parse_workflow_status(
+            "workflow_status:\n  prd: docs/prd.md\n  architecture: required\n  \
+             brainstorm: skipped\n",
+        )
+        .unwrap()
+    }
+
+    fn sprint() -> SprintData {
+        parse_sprint_status(
+            "development_status:\n  epic-1: in-progress\n  1-a: in-progress\n  \
+             1-b: in-progress\n  1-c: review\n  1-d: done\n",
+        )
+        .unwrap()
+    }
+
+    // =========================================================================
+    // Required Item Tests
+    // =========================================================================
+
+    #[test]
+    fn test_empty_policy_passes() {
+        let report = evaluate(&workflow(), &sprint(), &GatePolicy::default());
+        assert!(report.passed);
+        assert!(report.failures.is_empty());
+        assert_eq!(report.in_progress, 2);
+    }
+
+    #[test]
+    fn test_require_complete() {
+        let policy = GatePolicy::default()
+            .require_complete("prd")
+            .require_complete("architecture")
+            .require_complete("brainstorm")
+            .require_complete("ux-design");
+        let report = evaluate(&workflow(), &sprint(), &policy);
+        assert!(!report.passed);
+        assert_eq!(
+            report.failures,
+            vec![
+                GateFailure::Incomplete {
+                    id: "architecture".to_string(),
+                    status: "required".to_string(),
+                },
+                GateFailure::Incomplete {
+                    id: "brainstorm".to_string(),
+                    status: "skipped".to_string(),
+                },
+                GateFailure::Missing {
+                    id: "ux-design".to_string(),
+                },
+            ]
+        );
+    }
+
+    // =========================================================================
+    // In-Progress Limit Tests
+    // =========================================================================
+
+    #[test]
+    fn test_max_in_progress() {
+        let report = evaluate(
+            &workflow(),
+            &sprint(),
+            &GatePolicy::default().with_max_in_progress(2),
+        );
+        assert!(report.passed);
+
+        let report = evaluate(
+            &workflow(),
+            &sprint(),
+            &GatePolicy::default().with_max_in_progress(1),
+        );
+        assert_eq!(
+            report.failures,
+            vec![GateFailure::TooManyInProgress {
+                count: 2,
+                max: 1,
+                stories: vec!["1-a".to_string(), "1-b".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_in_progress_aliases_count() {
+        let sprint =
+            parse_sprint_status("development_status:\n  epic-1: backlog\n  1-a: In Progress\n")
+                .unwrap();
+        let report = evaluate(
+            &workflow(),
+            &sprint,
+            &GatePolicy::default().with_max_in_progress(0),
+        );
+        assert!(!report.passed);
+        assert_eq!(report.in_progress, 1);
+    }
+
+    // =========================================================================
+    // Output Tests
+    // =========================================================================
+
+    #[test]
+    fn test_failure_messages() {
+        assert_eq!(
+            GateFailure::Missing {
+                id: "prd".to_string()
+            }
+            .to_string(),
+            "prd must be complete but is missing"
+        );
+        assert_eq!(
+            GateFailure::TooManyInProgress {
+                count: 2,
+                max: 1,
+                stories: vec!["1-a".to_string(), "1-b".to_string()],
+            }
+            .to_string(),
+            "2 stories in progress, at most 1 allowed: 1-a, 1-b"
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let policy: GatePolicy =
+            serde_json::from_str(r#"{"requireComplete":["prd"],"maxInProgress":3}"#).unwrap();
+        assert_eq!(
+            policy,
+            GatePolicy::default()
+                .require_complete("prd")
+                .with_max_in_progress(3)
+        );
+        assert_eq!(
+            serde_json::from_str::<GatePolicy>("{}").unwrap(),
+            GatePolicy::default()
+        );
+
+        let report = evaluate(
+            &workflow(),
+            &sprint(),
+            &GatePolicy::default().require_complete("architecture"),
+        );
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["inProgress"], 2);
+        assert_eq!(json["failures"][0]["kind"], "incomplete");
+        assert_eq!(json["failures"][0]["id"], "architecture");
+    }
+}
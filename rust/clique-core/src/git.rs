@@ -0,0 +1,589 @@
+// clique-core/src/git.rs
+//! Status files as they were at earlier commits.
+//!
+//! [`read_at`] reads a status file at any revision git understands, and
+//! [`status_history`] / [`sprint_history`] walk a commit range and parse
+//! every version of the file, so progress over time can be charted from the
+//! repository alone, even where no [`crate::history`] log was kept.
+//!
+//! [`blame_statuses`] goes one step further and attributes every status
+//! change to the commit, author and date that made it.
+//!
+//! The repository is found from the file's directory, like `git` itself
+//! does, and paths may be given relative to the current directory.
+
+use crate::diff::{ItemChange, diff_sprint, diff_workflow};
+use crate::sprint::parse_sprint_status;
+use crate::types::{SprintData, WorkflowData, format_utc_timestamp};
+use crate::workflow::parse_workflow_status;
+use git2::{ErrorCode, Oid, Repository, Sort};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum GitError {
+    #[error("Not in a git repository: {0}")]
+    NoRepository(String),
+    #[error("Unknown revision: {0}")]
+    UnknownRevision(String),
+    #[error("{path} does not exist at {rev}")]
+    NotFound { rev: String, path: String },
+    #[error("{path} at {rev} is not valid UTF-8")]
+    NotUtf8 { rev: String, path: String },
+    #[error("Git error: {0}")]
+    GitError(String),
+}
+
+impl From<git2::Error> for GitError {
+    fn from(error: git2::Error) -> Self {
+        GitError::GitError(error.message().to_string())
+    }
+}
+
+/// The commit a version of a status file was read from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitInfo {
+    /// Full hexadecimal commit ID
+    pub id: String,
+    pub author: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Author date as an RFC 3339 UTC timestamp
+    pub timestamp: String,
+    /// First line of the commit message
+    pub summary: String,
+}
+
+impl CommitInfo {
+    fn new(commit: &git2::Commit) -> Self {
+        let author = commit.author();
+        Self {
+            id: commit.id().to_string(),
+            author: author.name().unwrap_or_default().to_string(),
+            email: author.email().map(str::to_string),
+            timestamp: format_utc_timestamp(author.when().seconds().max(0) as u64),
+            summary: commit.summary().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// One status change and the commit that made it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusChange {
+    /// Workflow item, epic or story ID
+    pub id: String,
+    /// Status before the commit; `None` when the commit added the entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<String>,
+    /// Status after the commit; `None` when the commit removed the entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<String>,
+    /// Full hexadecimal commit ID
+    pub commit: String,
+    pub author: String,
+    /// Author date as an RFC 3339 UTC timestamp
+    pub date: String,
+}
+
+/// A parsed version of a status file of either kind
+enum Version {
+    Workflow(WorkflowData),
+    Sprint(SprintData),
+}
+
+impl Version {
+    /// Sprint files are recognized by their `development_status` key;
+    /// anything else is read as a workflow.
+    fn parse(content: &str) -> Option<Self> {
+        if content
+            .lines()
+            .any(|line| line.starts_with("development_status:"))
+        {
+            parse_sprint_status(content).ok().map(Version::Sprint)
+        } else {
+            parse_workflow_status(content).ok().map(Version::Workflow)
+        }
+    }
+
+    /// Changes from `previous`, every entry being added when there is no
+    /// previous version of the same kind
+    fn changes_since(&self, previous: Option<&Version>) -> Vec<ItemChange> {
+        match (previous, self) {
+            (Some(Version::Workflow(old)), Version::Workflow(new)) => diff_workflow(old, new),
+            (Some(Version::Sprint(old)), Version::Sprint(new)) => diff_sprint(old, new),
+            (_, Version::Workflow(new)) => {
+                let mut empty = new.clone();
+                empty.items.clear();
+                diff_workflow(&empty, new)
+            }
+            (_, Version::Sprint(new)) => {
+                let mut empty = new.clone();
+                empty.epics.clear();
+                diff_sprint(&empty, new)
+            }
+        }
+    }
+}
+
+/// The repository holding `path` and the path within its working tree
+fn locate(path: &str) -> Result<(Repository, PathBuf), GitError> {
+    let no_repository = || GitError::NoRepository(path.to_string());
+    let absolute = std::env::current_dir()
+        .map_err(|_| no_repository())?
+        .join(path);
+    let (dir, name) = match (absolute.parent(), absolute.file_name()) {
+        (Some(dir), Some(name)) => (dir, name),
+        _ => return Err(no_repository()),
+    };
+    let repo = Repository::discover(dir).map_err(|_| no_repository())?;
+    let workdir = repo.workdir().ok_or_else(no_repository)?;
+    // Temporary and home directories are often reached through symlinks
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let relative = canonical(dir)
+        .strip_prefix(canonical(workdir))
+        .map_err(|_| no_repository())?
+        .join(name);
+    Ok((repo, relative))
+}
+
+/// Blob ID of `relative` in `commit`, `None` when the file is absent there
+fn blob_at(commit: &git2::Commit, relative: &Path) -> Result<Option<Oid>, GitError> {
+    match commit.tree()?.get_path(relative) {
+        Ok(entry) => Ok(Some(entry.id())),
+        Err(error) if error.code() == ErrorCode::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Content of the status file at `path` as of `rev`, e.g. `HEAD~3`, a tag
+/// or a commit ID.
+pub fn read_at(rev: &str, path: &str) -> Result<String, GitError> {
+    let (repo, relative) = locate(path)?;
+    let commit = repo
+        .revparse_single(rev)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|_| GitError::UnknownRevision(rev.to_string()))?;
+    let blob = blob_at(&commit, &relative)?.ok_or_else(|| GitError::NotFound {
+        rev: rev.to_string(),
+        path: path.to_string(),
+    })?;
+    let blob = repo.find_blob(blob)?;
+    String::from_utf8(blob.content().to_vec()).map_err(|_| GitError::NotUtf8 {
+        rev: rev.to_string(),
+        path: path.to_string(),
+    })
+}
+
+/// Every distinct version of the file in `range`, oldest first.
+///
+/// `range` is either `from..to`, or a single revision meaning all of its
+/// ancestors. Only first parents are followed, as `git log --first-parent`
+/// does, and each commit is compared with its first parent, so a merge
+/// counts as one change and commits that leave the file unchanged are
+/// skipped.
+fn versions(path: &str, range: &str) -> Result<Vec<(CommitInfo, String)>, GitError> {
+    let (repo, relative) = locate(path)?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME | Sort::REVERSE)?;
+    walk.simplify_first_parent()?;
+    let pushed = if range.contains("..") {
+        walk.push_range(range)
+    } else {
+        repo.revparse_single(range)
+            .and_then(|object| object.peel_to_commit())
+            .and_then(|commit| walk.push(commit.id()))
+    };
+    pushed.map_err(|_| GitError::UnknownRevision(range.to_string()))?;
+
+    let mut versions = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let blob = blob_at(&commit, &relative)?;
+        let parent = match commit.parent(0) {
+            Ok(parent) => blob_at(&parent, &relative)?,
+            Err(_) => None,
+        };
+        if blob == parent {
+            continue;
+        }
+        let Some(blob) = blob else {
+            continue;
+        };
+        if let Ok(content) = String::from_utf8(repo.find_blob(blob)?.content().to_vec()) {
+            versions.push((CommitInfo::new(&commit), content));
+        }
+    }
+    Ok(versions)
+}
+
+/// The workflow file at `path` as of each commit in `range` that changed
+/// it, oldest first.
+///
+/// `range` is `from..to` (as in `git log`), or a single revision such as
+/// `HEAD` for its whole history. Versions that do not parse, such as a
+/// commit with a broken file, are left out rather than failing the walk.
+pub fn status_history(
+    path: &str,
+    range: &str,
+) -> Result<Vec<(CommitInfo, WorkflowData)>, GitError> {
+    Ok(versions(path, range)?
+        .into_iter()
+        .filter_map(|(commit, content)| Some((commit, parse_workflow_status(&content).ok()?)))
+        .collect())
+}
+
+/// The sprint file at `path` as of each commit in `range` that changed it,
+/// oldest first, read as by [`status_history`].
+pub fn sprint_history(path: &str, range: &str) -> Result<Vec<(CommitInfo, SprintData)>, GitError> {
+    Ok(versions(path, range)?
+        .into_iter()
+        .filter_map(|(commit, content)| Some((commit, parse_sprint_status(&content).ok()?)))
+        .collect())
+}
+
+/// Every status change in the history of the file at `path` up to `HEAD`,
+/// oldest first, with the commit that made it.
+///
+/// Successive versions are compared with [`diff_workflow`] or
+/// [`diff_sprint`], so reordering and reformatting are not changes. The
+/// first version adds every entry; versions that do not parse are skipped
+/// and the next one is compared with the last that did.
+pub fn blame_statuses(path: &str) -> Result<Vec<StatusChange>, GitError> {
+    let mut changes = Vec::new();
+    let mut previous: Option<Version> = None;
+    for (commit, content) in versions(path, "HEAD")? {
+        let Some(version) = Version::parse(&content) else {
+            continue;
+        };
+        for change in version.changes_since(previous.as_ref()) {
+            let (old, new) = match &change {
+                ItemChange::Added { status, .. } => (None, Some(status.clone())),
+                ItemChange::Removed { status, .. } => (Some(status.clone()), None),
+                ItemChange::StatusChanged { from, to, .. } => {
+                    (Some(from.clone()), Some(to.clone()))
+                }
+            };
+            changes.push(StatusChange {
+                id: change.id().to_string(),
+                old,
+                new,
+                commit: commit.id.clone(),
+                author: commit.author.clone(),
+                date: commit.timestamp.clone(),
+            });
+        }
+        previous = Some(version);
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+    use git2::{Signature, Time};
+
+    /// A fresh repository directory
+    fn repository(name: &str) -> (Repository, TempDir) {
+        let dir = TempDir::new(&format!("git-{}", name));
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        (Repository::init(&dir).unwrap(), dir)
+    }
+
+    /// Commit `docs/status.yaml` with `content` (removed when `None`)
+    fn commit(repo: &Repository, content: Option<&str>, message: &str, secs: i64) -> Oid {
+        let parents: Vec<Oid> = repo
+            .head()
+            .ok()
+            .map(|head| head.peel_to_commit().unwrap().id())
+            .into_iter()
+            .collect();
+        commit_onto(repo, Some("HEAD"), &parents, content, message, secs)
+    }
+
+    /// [`commit`] with the given parents, updating `update_ref` if any
+    fn commit_onto(
+        repo: &Repository,
+        update_ref: Option<&str>,
+        parents: &[Oid],
+        content: Option<&str>,
+        message: &str,
+        secs: i64,
+    ) -> Oid {
+        let workdir = repo.workdir().unwrap();
+        let file = Path::new("docs/status.yaml");
+        let mut index = repo.index().unwrap();
+        match content {
+            Some(content) => {
+                fs::write(workdir.join(file), content).unwrap();
+                index.add_path(file).unwrap();
+            }
+            None => {
+                fs::remove_file(workdir.join(file)).unwrap();
+                index.remove_path(file).unwrap();
+            }
+        }
+        fs::write(workdir.join("other.txt"), message).unwrap();
+        index.add_path(Path::new("other.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::new("Sam", "sam@example.com", &Time::new(secs, 0)).unwrap();
+        let parents: Vec<git2::Commit> = parents
+            .iter()
+            .map(|&parent| repo.find_commit(parent).unwrap())
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(update_ref, &signature, &signature, message, &tree, &parents)
+            .unwrap()
+    }
+
+    fn path(dir: &Path) -> String {
+        dir.join("docs/status.yaml").to_string_lossy().into_owned()
+    }
+
+    // =========================================================================
+    // Read Tests
+    // =========================================================================
+
+    #[test]
+    fn test_read_at() {
+        let (repo, dir) = repository("read");
+        let first = commit(&repo, Some("workflow_status:\n  prd: required\n"), "a", 0);
+        commit(
+            &repo,
+            Some("workflow_status:\n  prd: docs/prd.md\n"),
+            "b",
+            60,
+        );
+        let path = path(&dir);
+
+        assert!(read_at("HEAD", &path).unwrap().contains("docs/prd.md"));
+        assert!(read_at("HEAD~1", &path).unwrap().contains("required"));
+        assert_eq!(
+            read_at(&first.to_string(), &path).unwrap(),
+            read_at("HEAD^", &path).unwrap()
+        );
+
+        assert!(matches!(
+            read_at("nope", &path),
+            Err(GitError::UnknownRevision(_))
+        ));
+        let missing = dir.join("docs/sprint-status.yaml");
+        assert!(matches!(
+            read_at("HEAD", &missing.to_string_lossy()),
+            Err(GitError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_outside_repository() {
+        let dir = TempDir::new("git-none");
+        // The temporary directory itself may sit inside a repository
+        if Repository::discover(&dir).is_err() {
+            let path = dir.join("status.yaml");
+            assert!(matches!(
+                read_at("HEAD", &path.to_string_lossy()),
+                Err(GitError::NoRepository(_))
+            ));
+        }
+    }
+
+    // =========================================================================
+    // History Tests
+    // =========================================================================
+
+    #[test]
+    fn test_sprint_history() {
+        let (repo, dir) = repository("sprint");
+        commit(
+            &repo,
+            Some("development_status:\n  epic-1: backlog\n  1-a: backlog\n"),
+            "plan",
+            0,
+        );
+        commit(
+            &repo,
+            Some("development_status:\n  epic-1: backlog\n  1-a: backlog\n"),
+            "noop",
+            60,
+        );
+        let started = commit(
+            &repo,
+            Some("development_status:\n  epic-1: backlog\n  1-a: in-progress\n"),
+            "start",
+            86_400,
+        );
+        commit(&repo, Some("development_status: [oops\n"), "broken", 90_000);
+        commit(
+            &repo,
+            Some("development_status:\n  epic-1: backlog\n  1-a: done\n"),
+            "finish",
+            172_800,
+        );
+        let path = path(&dir);
+
+        let history = sprint_history(&path, "HEAD").unwrap();
+        let summaries: Vec<&str> = history.iter().map(|(c, _)| c.summary.as_str()).collect();
+        assert_eq!(summaries, ["plan", "start", "finish"]);
+        assert_eq!(history[1].0.id, started.to_string());
+        assert_eq!(history[1].0.author, "Sam");
+        assert_eq!(history[1].0.email.as_deref(), Some("sam@example.com"));
+        assert_eq!(history[1].0.timestamp, "1970-01-02T00:00:00Z");
+        assert_eq!(history[2].1.epics[0].stories[0].status.to_string(), "done");
+
+        let since = sprint_history(&path, &format!("{}..HEAD", started)).unwrap();
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].0.summary, "finish");
+
+        assert!(matches!(
+            sprint_history(&path, "nope..HEAD"),
+            Err(GitError::UnknownRevision(_))
+        ));
+    }
+
+    #[test]
+    fn test_status_history_across_removal() {
+        let (repo, dir) = repository("workflow");
+        let workflow = "workflow_status:\n  prd: required\n";
+        commit(&repo, Some(workflow), "add", 0);
+        commit(&repo, None, "remove", 60);
+        commit(&repo, Some(workflow), "restore", 120);
+        let history = status_history(&path(&dir), "HEAD").unwrap();
+        let summaries: Vec<&str> = history.iter().map(|(c, _)| c.summary.as_str()).collect();
+        assert_eq!(summaries, ["add", "restore"]);
+        assert_eq!(history[0].1.items[0].id, "prd");
+    }
+
+    // =========================================================================
+    // Blame Tests
+    // =========================================================================
+
+    #[test]
+    fn test_blame_statuses() {
+        let (repo, dir) = repository("blame");
+        let planned = commit(
+            &repo,
+            Some("development_status:\n  epic-1: backlog\n  1-a: backlog\n  1-b: backlog\n"),
+            "plan",
+            0,
+        );
+        commit(&repo, Some("development_status: [oops\n"), "broken", 30);
+        let finished = commit(
+            &repo,
+            Some("development_status:\n  epic-1: in-progress\n  1-a: done\n"),
+            "finish 1-a, drop 1-b",
+            86_400,
+        );
+        let changes = blame_statuses(&path(&dir)).unwrap();
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = changes
+            .iter()
+            .map(|c| (c.id.as_str(), c.old.as_deref(), c.new.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("epic-1", None, Some("backlog")),
+                ("1-a", None, Some("backlog")),
+                ("1-b", None, Some("backlog")),
+                ("epic-1", Some("backlog"), Some("in-progress")),
+                ("1-a", Some("backlog"), Some("done")),
+                ("1-b", Some("backlog"), None),
+            ]
+        );
+        assert_eq!(changes[0].commit, planned.to_string());
+        let done = &changes[4];
+        assert_eq!(done.commit, finished.to_string());
+        assert_eq!(done.author, "Sam");
+        assert_eq!(done.date, "1970-01-02T00:00:00Z");
+
+        let json = serde_json::to_value(&changes[0]).unwrap();
+        assert!(json.get("old").is_none());
+        assert_eq!(json["new"], "backlog");
+    }
+
+    #[test]
+    fn test_blame_workflow_reformatting() {
+        let (repo, dir) = repository("blame-workflow");
+        commit(&repo, Some("workflow_status:\n  prd: required\n"), "add", 0);
+        commit(
+            &repo,
+            Some("# plan\nworkflow_status:\n  prd: \"required\"\n"),
+            "reformat",
+            60,
+        );
+        commit(
+            &repo,
+            Some("workflow_status:\n  prd: docs/prd.md\n"),
+            "write prd",
+            120,
+        );
+        let changes = blame_statuses(&path(&dir)).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].new.as_deref(), Some("docs/prd.md"));
+        assert_eq!(changes[1].date, "1970-01-01T00:02:00Z");
+    }
+
+    #[test]
+    fn test_blame_follows_first_parents() {
+        let (repo, dir) = repository("blame-merge");
+        let base = commit(
+            &repo,
+            Some("workflow_status:\n  prd: required\n"),
+            "base",
+            0,
+        );
+        let main = commit(
+            &repo,
+            Some("workflow_status:\n  prd: required\n  brainstorm: optional\n"),
+            "main",
+            50,
+        );
+        // A side branch, committed after main's change
+        let side = commit_onto(
+            &repo,
+            None,
+            &[base],
+            Some("workflow_status:\n  prd: docs/prd.md\n"),
+            "side",
+            100,
+        );
+        let merge = commit_onto(
+            &repo,
+            Some("HEAD"),
+            &[main, side],
+            Some("workflow_status:\n  prd: docs/prd.md\n  brainstorm: optional\n"),
+            "merge",
+            150,
+        );
+
+        let history = status_history(&path(&dir), "HEAD").unwrap();
+        let summaries: Vec<&str> = history.iter().map(|(c, _)| c.summary.as_str()).collect();
+        assert_eq!(summaries, ["base", "main", "merge"]);
+
+        // Nothing is removed and re-added by interleaving the branches
+        let changes = blame_statuses(&path(&dir)).unwrap();
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = changes
+            .iter()
+            .map(|c| (c.id.as_str(), c.old.as_deref(), c.new.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("prd", None, Some("required")),
+                ("brainstorm", None, Some("optional")),
+                ("prd", Some("required"), Some("docs/prd.md")),
+            ]
+        );
+        assert_eq!(changes[2].commit, merge.to_string());
+
+        // A range starts from the first parent of its first commit
+        let range = format!("{}..HEAD", main);
+        let history = status_history(&path(&dir), &range).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].0.summary, "merge");
+    }
+}
@@ -0,0 +1,231 @@
+// clique-core/src/history.rs
+//! Append-only audit log of status changes.
+//!
+//! Every edit can be recorded in a companion `clique-history.yaml` next to
+//! the status files. The log is a top-level YAML sequence, so recording an
+//! entry is a plain append and never rewrites earlier history:
+//!
+//! ```yaml
+//! - timestamp: 2025-03-01T10:00:00Z
+//!   id: 1-2-user-login
+//!   from: in-progress
+//!   to: review
+//!   actor: sam
+//! ```
+
+use crate::yaml_line::{format_scalar, needs_quotes};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use thiserror::Error;
+
+/// File name of the history log, kept beside the status files
+pub const HISTORY_FILE: &str = "clique-history.yaml";
+
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("Failed to parse history: {0}")]
+    ParseError(String),
+    #[error("Invalid history entry: {0}")]
+    InvalidEntry(String),
+}
+
+/// One recorded status change
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    /// When the change happened, as written (e.g. RFC 3339)
+    pub timestamp: String,
+    /// Workflow item or story ID
+    pub id: String,
+    /// Status before the change; absent for newly created entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub from: Option<String>,
+    /// Status after the change
+    pub to: String,
+    /// Who made the change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub actor: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn new(timestamp: &str, id: &str, to: &str) -> Self {
+        Self {
+            timestamp: timestamp.to_string(),
+            id: id.to_string(),
+            to: to.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Record the status the change started from.
+    pub fn with_from(mut self, from: &str) -> Self {
+        self.from = Some(from.to_string());
+        self
+    }
+
+    /// Record who made the change.
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = Some(actor.to_string());
+        self
+    }
+}
+
+/// Format an entry as a YAML sequence item, ready to append to the log.
+///
+/// Fails when a field is empty where one is required, or contains a control
+/// character that would break the line-based layout.
+pub fn record(entry: &HistoryEntry) -> Result<String, HistoryError> {
+    let fields = [
+        ("timestamp", Some(&entry.timestamp)),
+        ("id", Some(&entry.id)),
+        ("from", entry.from.as_ref()),
+        ("to", Some(&entry.to)),
+        ("actor", entry.actor.as_ref()),
+    ];
+
+    let mut out = String::new();
+    for (key, value) in fields {
+        let Some(value) = value else {
+            continue;
+        };
+        if value.chars().any(char::is_control) {
+            return Err(HistoryError::InvalidEntry(format!(
+                "{}: {}",
+                key,
+                value.escape_debug()
+            )));
+        }
+        if value.trim().is_empty() && matches!(key, "timestamp" | "id" | "to") {
+            return Err(HistoryError::InvalidEntry(format!("{} is empty", key)));
+        }
+        let prefix = if out.is_empty() { "- " } else { "  " };
+        let value = format_scalar("", value, needs_quotes(value));
+        out.push_str(&format!("{}{}: {}\n", prefix, key, value));
+    }
+    Ok(out)
+}
+
+/// Append an entry to existing log content, adding a line break first if
+/// the content does not end with one.
+pub fn append(content: &str, entry: &HistoryEntry) -> Result<String, HistoryError> {
+    let record = record(entry)?;
+    let mut out = content.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&record);
+    Ok(out)
+}
+
+/// Read a history log back, oldest entry first.
+///
+/// An empty or comment-only file is an empty history.
+pub fn parse(content: &str) -> Result<Vec<HistoryEntry>, HistoryError> {
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| HistoryError::ParseError(e.to_string()))?;
+    if parsed.is_null() {
+        return Ok(Vec::new());
+    }
+    serde_yaml::from_value(parsed).map_err(|e| HistoryError::ParseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> HistoryEntry {
+        HistoryEntry::new("2025-03-01T10:00:00Z", "1-2-user-login", "review")
+            .with_from("in-progress")
+            .with_actor("sam")
+    }
+
+    // =========================================================================
+    // Record Tests
+    // =========================================================================
+
+    #[test]
+    fn test_record_entry() {
+        assert_eq!(
+            record(&entry()).unwrap(),
+            "- timestamp: 2025-03-01T10:00:00Z\n  id: 1-2-user-login\n  from: in-progress\n  to: review\n  actor: sam\n"
+        );
+    }
+
+    #[test]
+    fn test_record_skips_absent_fields() {
+        let created = HistoryEntry::new("2025-03-01", "prd", "required");
+        assert_eq!(
+            record(&created).unwrap(),
+            "- timestamp: 2025-03-01\n  id: prd\n  to: required\n"
+        );
+    }
+
+    #[test]
+    fn test_record_quotes_structural_values() {
+        let entry = entry().with_actor("ops: bot #1");
+        let text = record(&entry).unwrap();
+        assert!(text.contains("  actor: \"ops: bot #1\"\n"));
+        assert_eq!(parse(&text).unwrap(), vec![entry]);
+    }
+
+    #[test]
+    fn test_record_rejects_invalid_entries() {
+        let bad = entry().with_actor("sam\n- id: forged");
+        assert!(matches!(record(&bad), Err(HistoryError::InvalidEntry(_))));
+
+        let empty = HistoryEntry::new("2025-03-01", " ", "done");
+        assert!(matches!(record(&empty), Err(HistoryError::InvalidEntry(_))));
+    }
+
+    // =========================================================================
+    // Append / Parse Tests
+    // =========================================================================
+
+    #[test]
+    fn test_append_and_parse_roundtrip() {
+        let first = entry();
+        let second =
+            HistoryEntry::new("2025-03-02T08:30:00Z", "1-2-user-login", "done").with_from("review");
+
+        let log = append("# Clique history\n", &first).unwrap();
+        let log = append(log.trim_end(), &second).unwrap();
+        assert!(log.starts_with("# Clique history\n- timestamp"));
+        assert_eq!(parse(&log).unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn test_parse_empty_history() {
+        assert!(parse("").unwrap().is_empty());
+        assert!(parse("# nothing yet\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            parse("- id: [unclosed"),
+            Err(HistoryError::ParseError(_))
+        ));
+        // Entries must carry timestamp, id and to
+        assert!(matches!(
+            parse("- id: prd\n"),
+            Err(HistoryError::ParseError(_))
+        ));
+        assert!(matches!(
+            parse("entries: []\n"),
+            Err(HistoryError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_history_entry_serialization() {
+        let json = serde_json::to_string(&entry()).unwrap();
+        assert!(json.contains("\"from\":\"in-progress\""));
+        let created = HistoryEntry::new("2025-03-01", "prd", "required");
+        let json = serde_json::to_string(&created).unwrap();
+        assert!(!json.contains("from"));
+    }
+}
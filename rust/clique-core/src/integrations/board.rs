@@ -0,0 +1,439 @@
+// clique-core/src/integrations/board.rs
+//! Stories from lightweight kanban boards: Trello and Notion.
+//!
+//! Each card becomes a story whose status comes from its list (Trello) or
+//! its status property (Notion), through [`BoardOptions::status`]. Boards
+//! have no epics of their own, so cards are grouped by an epic label or
+//! column; epics are numbered in the order they first appear, and cards
+//! without one go to a last epic, `No epic`.
+
+use super::csv::{Table, cell};
+use super::{IntegrationError, assemble, map_status, slug};
+use crate::text::text;
+use crate::types::{SprintData, Story};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::Read;
+
+/// How board cards map onto stories
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BoardOptions {
+    /// Story status per list or status name, compared ignoring case; these
+    /// take precedence over the built-in mapping
+    pub status_map: BTreeMap<String, String>,
+    /// Project name; defaults to the Trello board's name
+    pub project: Option<String>,
+    /// Prefix of the Trello label naming a card's epic: `epic:` puts a card
+    /// labelled `epic: Payments` in epic `Payments`
+    pub epic_label_prefix: String,
+    /// Notion column naming a row's epic
+    pub epic_column: String,
+}
+
+impl Default for BoardOptions {
+    fn default() -> Self {
+        Self {
+            status_map: BTreeMap::new(),
+            project: None,
+            epic_label_prefix: "epic:".to_string(),
+            epic_column: "Epic".to_string(),
+        }
+    }
+}
+
+impl BoardOptions {
+    /// The story status for list or status `name`.
+    ///
+    /// Without an entry in `status_map`, common board names map onto the
+    /// built-in statuses (`To Do` to `backlog`, `Doing` to `in-progress`,
+    /// `Review` to `review`, `Done` to `done`, ...) and anything else is
+    /// slugged: `Waiting for QA` gives `waiting-for-qa`.
+    pub fn status(&self, name: &str) -> String {
+        map_status(&self.status_map, name)
+    }
+}
+
+/// The fields of one card used by the import, from either tool
+#[derive(Default)]
+struct Card {
+    title: String,
+    /// List or status name, before mapping
+    status: String,
+    epic: Option<String>,
+    number: Option<u64>,
+    tags: Vec<String>,
+    assignee: Option<String>,
+    due: Option<String>,
+    /// Titles of the cards blocking this one
+    blocked_by: Vec<String>,
+}
+
+/// Some trimmed, non-empty `value`
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Number the epics and turn the cards into stories, keyed
+/// `N-M-<title slug>`
+fn build(project: &str, cards: Vec<Card>, options: &BoardOptions) -> SprintData {
+    let mut epics: BTreeMap<&str, u64> = BTreeMap::new();
+    let mut names: BTreeMap<u64, String> = BTreeMap::new();
+    for epic in cards.iter().filter_map(|card| card.epic.as_deref()) {
+        if !epics.contains_key(epic) {
+            let number = epics.len() as u64 + 1;
+            epics.insert(epic, number);
+            names.insert(number, epic.to_string());
+        }
+    }
+    let loose_epic = names.len() as u64 + 1;
+
+    let mut sequence: BTreeMap<u64, u64> = BTreeMap::new();
+    let mut ids: BTreeMap<&str, String> = BTreeMap::new();
+    let mut stories = Vec::new();
+    for card in &cards {
+        let epic = card
+            .epic
+            .as_deref()
+            .and_then(|epic| epics.get(epic).copied())
+            .unwrap_or(loose_epic);
+        let seq = sequence.entry(epic).or_insert(0);
+        *seq += 1;
+        let id = format!("{}-{}-{}", epic, seq, slug(&card.title));
+        ids.entry(&card.title).or_insert_with(|| id.clone());
+        stories.push(Story {
+            id,
+            status: text(options.status(&card.status)),
+            number: card.number,
+            title: non_empty(&card.title),
+            due: card.due.clone(),
+            tags: card.tags.clone(),
+            assignee: card.assignee.clone(),
+            ..Story::default()
+        });
+    }
+    if sequence.contains_key(&loose_epic) {
+        names.insert(loose_epic, "No epic".to_string());
+    }
+
+    // Blockers outside the import are dropped
+    for (story, card) in stories.iter_mut().zip(&cards) {
+        story.blocked_by = card
+            .blocked_by
+            .iter()
+            .filter_map(|title| ids.get(title.as_str()).cloned())
+            .collect();
+    }
+
+    let project = options.project.as_deref().unwrap_or(project);
+    assemble(project, "", stories, &names)
+}
+
+/// Stories from a Trello board export (board menu > "Print, export and
+/// share" > "Export as JSON").
+///
+/// Open cards on open lists are imported, in list order. A card's status is
+/// its list's name; its epic is the label starting with
+/// `options.epic_label_prefix`, and its other labels become tags. The
+/// story number is the card's short ID, the assignee its first member and
+/// the due date the date part of `due`.
+pub fn from_trello_json(
+    json: &str,
+    options: &BoardOptions,
+) -> Result<SprintData, IntegrationError> {
+    let board: Value = serde_json::from_str(json)
+        .map_err(|e| IntegrationError::InvalidInput(format!("Trello board: {}", e)))?;
+    let array = |key: &str| {
+        board
+            .get(key)
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    };
+    let string =
+        |value: &Value, key: &str| value.get(key).and_then(Value::as_str).and_then(non_empty);
+    let open = |value: &&Value| {
+        !value
+            .get("closed")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    };
+    if board.get("lists").and_then(Value::as_array).is_none() {
+        return Err(IntegrationError::InvalidInput(
+            "Trello board: expected a lists array".to_string(),
+        ));
+    }
+
+    let members: BTreeMap<String, String> = array("members")
+        .iter()
+        .filter_map(|member| {
+            let name = string(member, "fullName").or_else(|| string(member, "username"))?;
+            Some((string(member, "id")?, name))
+        })
+        .collect();
+    let prefix = options.epic_label_prefix.to_ascii_lowercase();
+
+    let mut cards = Vec::new();
+    for list in array("lists").iter().filter(open) {
+        let list_id = string(list, "id").unwrap_or_default();
+        let status = string(list, "name").unwrap_or_default();
+        for card in array("cards")
+            .iter()
+            .filter(open)
+            .filter(|card| string(card, "idList").as_deref() == Some(list_id.as_str()))
+        {
+            let mut epic = None;
+            let mut tags = Vec::new();
+            for label in card
+                .get("labels")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|label| string(label, "name"))
+            {
+                let is_epic = !prefix.is_empty() && label.to_ascii_lowercase().starts_with(&prefix);
+                match label.get(prefix.len()..) {
+                    Some(name) if is_epic && epic.is_none() => epic = non_empty(name),
+                    _ => tags.push(label),
+                }
+            }
+            cards.push(Card {
+                title: string(card, "name").unwrap_or_default(),
+                status: status.clone(),
+                epic,
+                number: card.get("idShort").and_then(Value::as_u64),
+                tags,
+                assignee: card
+                    .get("idMembers")
+                    .and_then(Value::as_array)
+                    .and_then(|ids| ids.first())
+                    .and_then(Value::as_str)
+                    .and_then(|id| members.get(id).cloned()),
+                due: string(card, "due").map(|due| due.chars().take(10).collect()),
+                blocked_by: Vec::new(),
+            });
+        }
+    }
+    let project = string(&board, "name").unwrap_or_else(|| "Unknown".to_string());
+    Ok(build(&project, cards, options))
+}
+
+/// A Notion page mention without its link: `Login (https://www.notion.so/…)`
+/// gives `Login`
+fn strip_link(value: &str) -> &str {
+    match value.rfind(" (http") {
+        Some(start) if value.ends_with(')') => value[..start].trim(),
+        _ => value.trim(),
+    }
+}
+
+/// Each comma-separated entry of a multi-value Notion cell
+fn entries(value: &str) -> Vec<String> {
+    // Links hold no ", ", so splitting on it keeps mentions whole
+    value
+        .split(", ")
+        .filter_map(|entry| non_empty(strip_link(entry)))
+        .collect()
+}
+
+/// An ISO date from Notion's `March 1, 2025` (or the start of a range,
+/// `March 1, 2025 → March 5, 2025`); other values are kept as written
+fn notion_date(value: &str) -> String {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    let start = value.split('→').next().unwrap_or_default().trim();
+    let mut words = start.split_whitespace();
+    let parsed = (|| {
+        let month = words.next()?.to_ascii_lowercase();
+        let month = MONTHS.iter().position(|name| *name == month)? + 1;
+        let day: u32 = words.next()?.strip_suffix(',')?.parse().ok()?;
+        let year: u32 = words.next()?.parse().ok()?;
+        Some(format!("{:04}-{:02}-{:02}", year, month, day))
+    })();
+    parsed.unwrap_or_else(|| start.to_string())
+}
+
+/// Stories from a Notion database exported as CSV ("Export" > "Markdown &
+/// CSV").
+///
+/// Needs a `Name` (or `Title`) and a `Status` column; `Assignee`, `Tags`,
+/// `Due` and `Blocked by` are used when present, along with the epic
+/// column `options.epic_column`. Multi-value cells are comma-separated and
+/// page mentions lose their links, so `Blocked by` names other rows by
+/// title.
+pub fn from_notion_csv(
+    mut reader: impl Read,
+    options: &BoardOptions,
+) -> Result<SprintData, IntegrationError> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|e| IntegrationError::ReadError(e.to_string()))?;
+    let table = Table::parse(&content)?;
+
+    let title = table.require(&["Name", "Title", "Task name"])?;
+    let status = table.require(&["Status"])?;
+    let epic = table.column(&[options.epic_column.as_str()]);
+    let assignee = table.column(&["Assignee", "Assign", "Owner"]);
+    let tags = table.column(&["Tags", "Labels"]);
+    let due = table.column(&["Due", "Due date", "Date"]);
+    let blocked_by = table.column(&["Blocked by"]);
+
+    let cards = table
+        .rows
+        .iter()
+        .map(|row| Card {
+            title: strip_link(cell(row, Some(title))).to_string(),
+            status: cell(row, Some(status)).to_string(),
+            epic: entries(cell(row, epic)).into_iter().next(),
+            number: None,
+            tags: entries(cell(row, tags)),
+            assignee: entries(cell(row, assignee)).into_iter().next(),
+            due: non_empty(cell(row, due)).map(|due| notion_date(&due)),
+            blocked_by: entries(cell(row, blocked_by)),
+        })
+        .collect();
+    Ok(build("Unknown", cards, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses(data: &SprintData) -> Vec<(&str, &str)> {
+        data.epics
+            .iter()
+            .flat_map(|epic| &epic.stories)
+            .map(|story| (story.id.as_str(), story.status.as_str()))
+            .collect()
+    }
+
+    // =========================================================================
+    // Trello Tests
+    // =========================================================================
+
+    const TRELLO: &str = r#"{
+        "name": "Demo board",
+        "lists": [
+            {"id": "l1", "name": "To Do", "closed": false},
+            {"id": "l2", "name": "Doing", "closed": false},
+            {"id": "l3", "name": "Old", "closed": true},
+            {"id": "l4", "name": "Shipped", "closed": false}
+        ],
+        "members": [{"id": "m1", "fullName": "Sam Doe", "username": "sam"}],
+        "cards": [
+            {"name": "Reset password", "idList": "l1", "idShort": 7, "closed": false,
+             "labels": [{"name": "Epic: Accounts"}, {"name": "security"}],
+             "due": "2025-03-01T12:00:00.000Z"},
+            {"name": "User login", "idList": "l2", "idShort": 3,
+             "labels": [{"name": "epic: Accounts"}], "idMembers": ["m1"]},
+            {"name": "Archived card", "idList": "l1", "closed": true},
+            {"name": "Hidden", "idList": "l3"},
+            {"name": "Landing page", "idList": "l4", "labels": []}
+        ]
+    }"#;
+
+    #[test]
+    fn test_from_trello_json() {
+        let data = from_trello_json(TRELLO, &BoardOptions::default()).unwrap();
+        assert_eq!(data.project, "Demo board");
+        assert_eq!(
+            statuses(&data),
+            [
+                ("1-1-reset-password", "backlog"),
+                ("1-2-user-login", "in-progress"),
+                ("2-1-landing-page", "shipped"),
+            ]
+        );
+        assert_eq!(data.epics[0].name, "Accounts");
+        assert_eq!(data.epics[1].name, "No epic");
+
+        let reset = &data.epics[0].stories[0];
+        assert_eq!(reset.tags, ["security"]);
+        assert_eq!(reset.number, Some(7));
+        assert_eq!(reset.due.as_deref(), Some("2025-03-01"));
+        assert_eq!(
+            data.epics[0].stories[1].assignee.as_deref(),
+            Some("Sam Doe")
+        );
+    }
+
+    #[test]
+    fn test_from_trello_json_options() {
+        let options = BoardOptions {
+            status_map: BTreeMap::from([("shipped".to_string(), "done".to_string())]),
+            project: Some("Demo".to_string()),
+            epic_label_prefix: String::new(),
+            ..BoardOptions::default()
+        };
+        let data = from_trello_json(TRELLO, &options).unwrap();
+        assert_eq!(data.project, "Demo");
+        // Without a prefix no label names an epic
+        assert_eq!(data.epics.len(), 1);
+        assert_eq!(data.epics[0].stories[2].status, "done");
+        assert_eq!(
+            data.epics[0].stories[0].tags,
+            ["Epic: Accounts", "security"]
+        );
+
+        assert!(from_trello_json("{}", &options).is_err());
+        assert!(from_trello_json("not json", &options).is_err());
+    }
+
+    // =========================================================================
+    // Notion Tests
+    // =========================================================================
+
+    #[test]
+    fn test_from_notion_csv() {
+        let csv = "\u{feff}Name,Status,Assignee,Tags,Due,Epic,Blocked by\n\
+                   User login,Done,Sam Doe,\"auth, web\",\"March 1, 2025\",\
+                   Accounts (https://www.notion.so/Accounts-1a2b),\n\
+                   Reset password,In progress,,auth,\"March 3, 2025 → March 5, 2025\",\
+                   Accounts (https://www.notion.so/Accounts-1a2b),\
+                   \"User login (https://www.notion.so/User-login-3c4d)\"\n\
+                   Landing page,Not started,,,next week,,\n";
+        let data = from_notion_csv(csv.as_bytes(), &BoardOptions::default()).unwrap();
+        assert_eq!(data.project, "Unknown");
+        assert_eq!(
+            statuses(&data),
+            [
+                ("1-1-user-login", "done"),
+                ("1-2-reset-password", "in-progress"),
+                ("2-1-landing-page", "backlog"),
+            ]
+        );
+        assert_eq!(data.epics[0].name, "Accounts");
+        let login = &data.epics[0].stories[0];
+        assert_eq!(login.tags, ["auth", "web"]);
+        assert_eq!(login.due.as_deref(), Some("2025-03-01"));
+        assert_eq!(login.assignee.as_deref(), Some("Sam Doe"));
+        let reset = &data.epics[0].stories[1];
+        assert_eq!(reset.blocked_by, ["1-1-user-login"]);
+        assert_eq!(reset.due.as_deref(), Some("2025-03-03"));
+        assert_eq!(data.epics[1].stories[0].due.as_deref(), Some("next week"));
+    }
+
+    #[test]
+    fn test_from_notion_csv_errors() {
+        let missing = from_notion_csv("Name,Owner\nA,Sam\n".as_bytes(), &BoardOptions::default());
+        assert_eq!(
+            missing.unwrap_err().to_string(),
+            "Invalid input: CSV has no Status column"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+// clique-core/src/integrations/chat.rs
+//! Webhook payloads announcing status changes in chat.
+//!
+//! Bots post the returned JSON as is to a Slack incoming webhook or a
+//! Discord webhook, so every team sees the same "story moved to review"
+//! wording. Long change lists are cut to fit each service's message limits.
+
+use crate::diff::{ItemChange, SprintDiff};
+use serde_json::{Value, json};
+
+/// Longest Slack section text, in characters
+const SLACK_TEXT_LIMIT: usize = 3000;
+
+/// Longest Discord embed description, in characters
+const DISCORD_TEXT_LIMIT: usize = 4096;
+
+/// Discord embed accent colour (`#4A90D9`)
+const DISCORD_COLOR: u32 = 0x4A90D9;
+
+/// Emphasis and escaping for one chat service's markup
+struct Markup {
+    bold: fn(&str) -> String,
+    code: fn(&str) -> String,
+    bullet: &'static str,
+}
+
+/// Slack `mrkdwn`: only `&`, `<` and `>` need escaping
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Discord Markdown: backslash-escape the formatting characters
+fn escape_discord(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '~' | '`' | '|' | '>' | '#' | '[' | ']'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+const SLACK: Markup = Markup {
+    bold: |text| format!("*{}*", escape_slack(text)),
+    code: |text| format!("`{}`", escape_slack(&text.replace('`', "'"))),
+    bullet: "•",
+};
+
+const DISCORD: Markup = Markup {
+    bold: |text| format!("**{}**", escape_discord(text)),
+    code: |text| format!("`{}`", text.replace('`', "'")),
+    bullet: "-",
+};
+
+/// One line describing `change`
+fn change_line(change: &ItemChange, markup: &Markup) -> String {
+    let line = match change {
+        ItemChange::StatusChanged { id, from, to } => format!(
+            "{} moved from {} to {}",
+            (markup.code)(id),
+            (markup.code)(from),
+            (markup.bold)(to)
+        ),
+        ItemChange::Added { id, status } => {
+            format!("{} added as {}", (markup.code)(id), (markup.bold)(status))
+        }
+        ItemChange::Removed { id, status } => {
+            format!(
+                "{} removed (was {})",
+                (markup.code)(id),
+                (markup.code)(status)
+            )
+        }
+    };
+    format!("{} {}", markup.bullet, line)
+}
+
+/// The change lines joined, cut off with a count of the rest once they
+/// would exceed `limit` characters
+fn change_list(diff: &SprintDiff, markup: &Markup, limit: usize) -> String {
+    if diff.is_empty() {
+        return "No status changes".to_string();
+    }
+    let lines: Vec<String> = diff
+        .changes
+        .iter()
+        .map(|change| change_line(change, markup))
+        .collect();
+    let mut out = String::new();
+    let mut length = 0;
+    for (shown, line) in lines.iter().enumerate() {
+        let more = format!("…and {} more", lines.len() - shown);
+        let needed = line.chars().count() + 1;
+        // Leave room for the "…and N more" line unless this is the last one
+        let reserve = if shown + 1 < lines.len() {
+            more.chars().count() + 1
+        } else {
+            0
+        };
+        if length + needed + reserve > limit {
+            out.push_str(&more);
+            return out;
+        }
+        out.push_str(line);
+        out.push('\n');
+        length += needed;
+    }
+    out.pop();
+    out
+}
+
+/// Heading naming the project, e.g. `Sprint update: Demo`
+fn title(diff: &SprintDiff) -> String {
+    if diff.project.is_empty() || diff.project == "Unknown" {
+        "Sprint update".to_string()
+    } else {
+        format!("Sprint update: {}", diff.project)
+    }
+}
+
+/// Plain-text summary, e.g. `Sprint update: Demo (3 changes)`
+fn summary(diff: &SprintDiff) -> String {
+    match diff.changes.len() {
+        1 => format!("{} (1 change)", title(diff)),
+        count => format!("{} ({} changes)", title(diff), count),
+    }
+}
+
+/// A Slack message with a header and a section listing every change.
+///
+/// `text` carries a plain summary for notifications and clients that do
+/// not render blocks.
+pub fn slack_payload(diff: &SprintDiff) -> Value {
+    json!({
+        "text": summary(diff),
+        "blocks": [
+            {
+                "type": "header",
+                "text": { "type": "plain_text", "text": title(diff) }
+            },
+            {
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": change_list(diff, &SLACK, SLACK_TEXT_LIMIT)
+                }
+            }
+        ]
+    })
+}
+
+/// A Discord message with one embed listing every change.
+///
+/// Mentions are disabled, so a project name or status such as `@everyone`
+/// never pings anyone.
+pub fn discord_payload(diff: &SprintDiff) -> Value {
+    json!({
+        "allowed_mentions": { "parse": [] },
+        "embeds": [
+            {
+                "title": title(diff),
+                "description": change_list(diff, &DISCORD, DISCORD_TEXT_LIMIT),
+                "color": DISCORD_COLOR
+            }
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(changes: Vec<ItemChange>) -> SprintDiff {
+        SprintDiff {
+            project: "Demo".to_string(),
+            changes,
+        }
+    }
+
+    fn moved(id: &str, from: &str, to: &str) -> ItemChange {
+        ItemChange::StatusChanged {
+            id: id.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    // =========================================================================
+    // Slack Tests
+    // =========================================================================
+
+    #[test]
+    fn test_slack_payload() {
+        let payload = slack_payload(&diff(vec![
+            moved("1-a", "in-progress", "review"),
+            ItemChange::Added {
+                id: "1-c".to_string(),
+                status: "backlog".to_string(),
+            },
+            ItemChange::Removed {
+                id: "1-b".to_string(),
+                status: "done".to_string(),
+            },
+        ]));
+        assert_eq!(payload["text"], "Sprint update: Demo (3 changes)");
+        assert_eq!(payload["blocks"][0]["text"]["text"], "Sprint update: Demo");
+        assert_eq!(payload["blocks"][1]["text"]["type"], "mrkdwn");
+        assert_eq!(
+            payload["blocks"][1]["text"]["text"],
+            "• `1-a` moved from `in-progress` to *review*\n\
+             • `1-c` added as *backlog*\n\
+             • `1-b` removed (was `done`)"
+        );
+    }
+
+    #[test]
+    fn test_slack_escaping_and_empty() {
+        let payload = slack_payload(&diff(vec![moved("1-a", "backlog", "<b>&")]));
+        assert_eq!(payload["text"], "Sprint update: Demo (1 change)");
+        assert!(
+            payload["blocks"][1]["text"]["text"]
+                .as_str()
+                .unwrap()
+                .ends_with("*&lt;b&gt;&amp;*")
+        );
+
+        let mut empty = diff(Vec::new());
+        empty.project = "Unknown".to_string();
+        let payload = slack_payload(&empty);
+        assert_eq!(payload["blocks"][0]["text"]["text"], "Sprint update");
+        assert_eq!(payload["blocks"][1]["text"]["text"], "No status changes");
+    }
+
+    // =========================================================================
+    // Discord Tests
+    // =========================================================================
+
+    #[test]
+    fn test_discord_payload() {
+        let payload = discord_payload(&diff(vec![moved("1-a", "review", "in_review*")]));
+        let embed = &payload["embeds"][0];
+        assert_eq!(embed["title"], "Sprint update: Demo");
+        assert_eq!(embed["color"], DISCORD_COLOR);
+        assert_eq!(
+            embed["description"],
+            "- `1-a` moved from `review` to **in\\_review\\***"
+        );
+    }
+
+    #[test]
+    fn test_discord_payload_disables_mentions() {
+        let mut everyone = diff(vec![moved("1-a", "review", "@everyone")]);
+        everyone.project = "<@&123> @here".to_string();
+        let payload = discord_payload(&everyone);
+        assert_eq!(payload["allowed_mentions"], json!({ "parse": [] }));
+    }
+
+    #[test]
+    fn test_long_lists_are_cut() {
+        let changes: Vec<ItemChange> = (0..500)
+            .map(|n| moved(&format!("1-story-{}", n), "backlog", "done"))
+            .collect();
+        let text = change_list(&diff(changes), &SLACK, SLACK_TEXT_LIMIT);
+        assert!(text.chars().count() <= SLACK_TEXT_LIMIT);
+        let last = text.lines().last().unwrap();
+        assert!(last.starts_with("…and ") && last.ends_with(" more"));
+        let shown = text.lines().count() - 1;
+        assert_eq!(last, format!("…and {} more", 500 - shown));
+    }
+}
@@ -0,0 +1,140 @@
+// clique-core/src/integrations/csv.rs
+//! Reading CSV exports (RFC 4180).
+
+use super::IntegrationError;
+
+/// Rows of `text`, each a list of fields. Quoted fields may hold commas,
+/// doubled quotes and line breaks; a leading byte-order mark and blank
+/// lines are skipped.
+pub(super) fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, IntegrationError> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|field| !field.is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(IntegrationError::InvalidInput(
+            "CSV ends inside a quoted field".to_string(),
+        ));
+    }
+    row.push(field);
+    if row.iter().any(|field| !field.is_empty()) {
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// A CSV file with a header row, looked up by column name
+pub(super) struct Table {
+    header: Vec<String>,
+    pub(super) rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub(super) fn parse(text: &str) -> Result<Self, IntegrationError> {
+        let mut rows = parse_csv(text)?.into_iter();
+        let header = rows
+            .next()
+            .ok_or_else(|| IntegrationError::InvalidInput("CSV has no header row".to_string()))?
+            .into_iter()
+            .map(|name| name.trim().to_string())
+            .collect();
+        Ok(Self {
+            header,
+            rows: rows.collect(),
+        })
+    }
+
+    /// Indexes of every column named `name`, ignoring case; exports repeat a
+    /// column for multi-valued fields such as labels
+    pub(super) fn columns(&self, name: &str) -> Vec<usize> {
+        self.header
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| column.eq_ignore_ascii_case(name))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Index of the first column with one of `names`
+    pub(super) fn column(&self, names: &[&str]) -> Option<usize> {
+        names
+            .iter()
+            .find_map(|name| self.columns(name).first().copied())
+    }
+
+    /// Like [`Table::column`], failing when there is none
+    pub(super) fn require(&self, names: &[&str]) -> Result<usize, IntegrationError> {
+        self.column(names).ok_or_else(|| {
+            IntegrationError::InvalidInput(format!("CSV has no {} column", names[0]))
+        })
+    }
+}
+
+/// The trimmed field at `column` of `row`; empty when missing
+pub(super) fn cell(row: &[String], column: Option<usize>) -> &str {
+    column
+        .and_then(|index| row.get(index))
+        .map_or("", |field| field.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // CSV Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_csv() {
+        let rows =
+            parse_csv("\u{feff}a,b,c\r\n\"x, y\",\"say \"\"hi\"\"\",\n\n\"multi\nline\",2,3")
+                .unwrap();
+        assert_eq!(
+            rows,
+            [
+                vec!["a", "b", "c"],
+                vec!["x, y", "say \"hi\"", ""],
+                vec!["multi\nline", "2", "3"],
+            ]
+        );
+        assert!(parse_csv("a,\"open").is_err());
+        assert!(parse_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_table() {
+        let table = Table::parse("Summary,Labels,labels\nLogin,auth,web\n").unwrap();
+        assert_eq!(table.columns("LABELS"), [1, 2]);
+        assert_eq!(table.column(&["Title", "summary"]), Some(0));
+        assert!(table.require(&["Status"]).is_err());
+        assert_eq!(cell(&table.rows[0], Some(2)), "web");
+        assert_eq!(cell(&table.rows[0], Some(9)), "");
+        assert!(Table::parse("").is_err());
+    }
+}
@@ -0,0 +1,417 @@
+// clique-core/src/integrations/github.rs
+//! GitHub issues from sprint stories, and back.
+//!
+//! [`to_github_issues`] describes one issue per story, ready for `gh issue
+//! create` or the REST API; the body ends in a hidden marker naming the
+//! story, so [`from_github_issues`] can read an issue export (`gh issue list
+//! --json number,title,body,labels,milestone,state,assignees`) back into
+//! the same story keys.
+
+use super::{IntegrationError, assemble, slug, story_epic};
+use crate::text::text;
+use crate::types::{SprintData, Story};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Prefix of the hidden comment naming an issue's story
+const STORY_MARKER: &str = "<!-- clique-story: ";
+
+/// How stories map onto issues
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GithubMapping {
+    /// Prefix of the label carrying a story's status: `status:` labels a
+    /// review story `status:review`
+    pub status_label_prefix: String,
+    /// Labels added to every issue, e.g. `clique`
+    pub labels: Vec<String>,
+    /// Milestone per epic ID; other epics use the epic's name
+    pub milestones: BTreeMap<String, String>,
+    /// Statuses whose issues are closed
+    pub closed_statuses: Vec<String>,
+}
+
+impl Default for GithubMapping {
+    fn default() -> Self {
+        Self {
+            status_label_prefix: "status:".to_string(),
+            labels: Vec::new(),
+            milestones: BTreeMap::new(),
+            closed_statuses: vec!["done".to_string()],
+        }
+    }
+}
+
+/// Whether an issue is open or closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueState {
+    Open,
+    Closed,
+}
+
+/// One issue to create or update
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueSpec {
+    /// The story the issue tracks
+    pub story_id: String,
+    pub title: String,
+    /// Markdown body, ending in the story marker
+    pub body: String,
+    pub labels: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    pub assignees: Vec<String>,
+    pub state: IssueState,
+}
+
+/// A readable title from a story key: `1-2-user-login` gives `User login`
+fn title_from_id(id: &str) -> String {
+    let words: Vec<&str> = id
+        .split('-')
+        .skip_while(|part| part.bytes().all(|b| b.is_ascii_digit()))
+        .collect();
+    let mut title = words.join(" ");
+    if let Some(first) = title.get(..1) {
+        title.replace_range(..1, &first.to_ascii_uppercase());
+    }
+    if title.is_empty() {
+        id.to_string()
+    } else {
+        title
+    }
+}
+
+fn issue_body(story: &Story, epic_name: &str) -> String {
+    let mut body = format!("Story `{}` of {}.\n\n", story.id, epic_name);
+    let _ = writeln!(body, "**Status:** {}", story.status);
+    if !story.blocked_by.is_empty() {
+        let blockers: Vec<String> = story
+            .blocked_by
+            .iter()
+            .map(|id| format!("`{}`", id))
+            .collect();
+        let _ = writeln!(body, "**Blocked by:** {}", blockers.join(", "));
+    }
+    if let Some(due) = &story.due {
+        let _ = writeln!(body, "**Due:** {}", due);
+    }
+    let _ = write!(body, "\n{}{} -->", STORY_MARKER, story.id);
+    body
+}
+
+/// One issue per story, in document order.
+///
+/// The title is the story's title from epics.md, or one made from its key.
+/// Labels are the status label, the story's tags and the mapping's common
+/// labels; the milestone is the epic's.
+pub fn to_github_issues(data: &SprintData, mapping: &GithubMapping) -> Vec<IssueSpec> {
+    data.epics
+        .iter()
+        .flat_map(|epic| {
+            let milestone = mapping
+                .milestones
+                .get(&epic.id)
+                .cloned()
+                .or_else(|| (!epic.name.is_empty()).then(|| epic.name.clone()));
+            epic.stories.iter().map(move |story| {
+                let mut labels = vec![format!("{}{}", mapping.status_label_prefix, story.status)];
+                for label in story.tags.iter().chain(&mapping.labels) {
+                    if !labels.contains(label) {
+                        labels.push(label.clone());
+                    }
+                }
+                let closed = mapping
+                    .closed_statuses
+                    .iter()
+                    .any(|status| status == story.status.as_str());
+                IssueSpec {
+                    story_id: story.id.clone(),
+                    title: story
+                        .title
+                        .clone()
+                        .unwrap_or_else(|| title_from_id(&story.id)),
+                    body: issue_body(story, &epic.name),
+                    labels,
+                    milestone: milestone.clone(),
+                    assignees: story.assignee.iter().cloned().collect(),
+                    state: if closed {
+                        IssueState::Closed
+                    } else {
+                        IssueState::Open
+                    },
+                }
+            })
+        })
+        .collect()
+}
+
+/// A string field, or the `key` of an object field (`{"name": ...}`)
+fn name_of<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value
+        .as_str()
+        .or_else(|| value.get(key).and_then(Value::as_str))
+}
+
+/// Story key from the marker in an issue body
+fn marked_story(body: &str) -> Option<&str> {
+    let start = body.find(STORY_MARKER)? + STORY_MARKER.len();
+    let id = body[start..].split(" -->").next()?.trim();
+    story_epic(id).map(|_| id)
+}
+
+/// Stories from a GitHub issues export, as a JSON array in the shape of
+/// `gh issue list --json` or the REST API.
+///
+/// Issues created by [`to_github_issues`] get their story key back from
+/// the body marker. Others are keyed `N-<issue number>-<title slug>`, where
+/// the epic `N` is the one holding marked issues of the same milestone, the
+/// epic `mapping.milestones` names for it, or a new epic per milestone.
+/// The status comes from the status label, else `done` for closed issues
+/// and `backlog` for open ones.
+pub fn from_github_issues(
+    json: &str,
+    mapping: &GithubMapping,
+) -> Result<SprintData, IntegrationError> {
+    let issues: Vec<Value> = serde_json::from_str(json)
+        .map_err(|e| IntegrationError::InvalidInput(format!("GitHub issues: {}", e)))?;
+
+    let milestone_of = |issue: &Value| {
+        issue
+            .get("milestone")
+            .and_then(|milestone| name_of(milestone, "title"))
+            .map(str::to_string)
+    };
+
+    // Epic per milestone: from marked issues first, then the mapping
+    let mut epics: BTreeMap<String, u64> = BTreeMap::new();
+    for issue in &issues {
+        let body = issue
+            .get("body")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if let (Some(milestone), Some(epic)) =
+            (milestone_of(issue), marked_story(body).and_then(story_epic))
+        {
+            epics.entry(milestone).or_insert(epic);
+        }
+    }
+    for (epic_id, milestone) in &mapping.milestones {
+        if let Some(number) = epic_id.strip_prefix("epic-").and_then(|n| n.parse().ok()) {
+            epics.entry(milestone.clone()).or_insert(number);
+        }
+    }
+    let mut next_epic = epics.values().max().copied().unwrap_or(0) + 1;
+    let mut names: BTreeMap<u64, String> = epics
+        .iter()
+        .map(|(milestone, number)| (*number, milestone.clone()))
+        .collect();
+
+    let mut stories = Vec::new();
+    for issue in &issues {
+        let field = |key: &str| issue.get(key).and_then(Value::as_str).unwrap_or_default();
+        let number = issue.get("number").and_then(Value::as_u64);
+        let labels: Vec<&str> = issue
+            .get("labels")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|label| name_of(label, "name"))
+            .collect();
+        let status = labels
+            .iter()
+            .find_map(|label| label.strip_prefix(mapping.status_label_prefix.as_str()))
+            .filter(|status| !mapping.status_label_prefix.is_empty() && !status.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                if field("state").eq_ignore_ascii_case("closed") {
+                    "done".to_string()
+                } else {
+                    "backlog".to_string()
+                }
+            });
+
+        let id = match marked_story(field("body")) {
+            Some(id) => id.to_string(),
+            None => {
+                let milestone = milestone_of(issue).unwrap_or_default();
+                let epic = *epics.entry(milestone.clone()).or_insert_with(|| {
+                    let epic = next_epic;
+                    next_epic += 1;
+                    epic
+                });
+                if !milestone.is_empty() {
+                    names.entry(epic).or_insert(milestone);
+                }
+                match number {
+                    Some(number) => format!("{}-{}-{}", epic, number, slug(field("title"))),
+                    None => format!("{}-{}", epic, slug(field("title"))),
+                }
+            }
+        };
+        let title = field("title");
+        stories.push(Story {
+            id,
+            status: text(status),
+            number,
+            title: (!title.is_empty()).then(|| title.to_string()),
+            tags: labels
+                .iter()
+                .filter(|label| {
+                    !label.starts_with(mapping.status_label_prefix.as_str())
+                        && !mapping.labels.iter().any(|common| common == *label)
+                })
+                .map(|label| label.to_string())
+                .collect(),
+            assignee: issue
+                .get("assignees")
+                .and_then(Value::as_array)
+                .and_then(|assignees| assignees.first())
+                .and_then(|assignee| name_of(assignee, "login"))
+                .map(str::to_string),
+            ..Story::default()
+        });
+    }
+    Ok(assemble("Unknown", "", stories, &names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+
+    const SPRINT: &str = "project: Demo\ndevelopment_status:\n  epic-1: in-progress\n  \
+                          1-1-user-login: done\n  \
+                          1-2-password-reset:\n    status: review\n    \
+                          blocked_by: [1-1-user-login]\n    tags: [security]\n    \
+                          assignee: sam\n    due: 2025-03-01\n  \
+                          epic-2: backlog\n  2-1-checkout: backlog\n";
+
+    // =========================================================================
+    // Export Tests
+    // =========================================================================
+
+    #[test]
+    fn test_to_github_issues() {
+        let data = parse_sprint_status(SPRINT).unwrap();
+        let mapping = GithubMapping {
+            labels: vec!["clique".to_string()],
+            milestones: BTreeMap::from([("epic-2".to_string(), "v2".to_string())]),
+            ..GithubMapping::default()
+        };
+        let issues = to_github_issues(&data, &mapping);
+        assert_eq!(issues.len(), 3);
+
+        let login = &issues[0];
+        assert_eq!(login.title, "User login");
+        assert_eq!(login.state, IssueState::Closed);
+        assert_eq!(login.milestone.as_deref(), Some("Epic 1"));
+        assert_eq!(login.labels, ["status:done", "clique"]);
+
+        let reset = &issues[1];
+        assert_eq!(reset.state, IssueState::Open);
+        assert_eq!(reset.labels, ["status:review", "security", "clique"]);
+        assert_eq!(reset.assignees, ["sam"]);
+        assert_eq!(
+            reset.body,
+            "Story `1-2-password-reset` of Epic 1.\n\n**Status:** review\n\
+             **Blocked by:** `1-1-user-login`\n**Due:** 2025-03-01\n\n\
+             <!-- clique-story: 1-2-password-reset -->"
+        );
+        assert_eq!(issues[2].milestone.as_deref(), Some("v2"));
+
+        let json = serde_json::to_value(&issues[0]).unwrap();
+        assert_eq!(json["state"], "closed");
+        assert_eq!(json["storyId"], "1-1-user-login");
+    }
+
+    #[test]
+    fn test_title_from_id() {
+        assert_eq!(title_from_id("1-2-user-login"), "User login");
+        assert_eq!(title_from_id("12-3"), "12-3");
+    }
+
+    // =========================================================================
+    // Import Tests
+    // =========================================================================
+
+    #[test]
+    fn test_round_trip() {
+        let data = parse_sprint_status(SPRINT).unwrap();
+        let mapping = GithubMapping::default();
+        let issues: Vec<Value> = to_github_issues(&data, &mapping)
+            .into_iter()
+            .enumerate()
+            .map(|(n, issue)| {
+                serde_json::json!({
+                    "number": n + 10,
+                    "title": issue.title,
+                    "body": issue.body,
+                    "labels": issue.labels.iter().map(|l| serde_json::json!({"name": l})).collect::<Vec<_>>(),
+                    "milestone": {"title": issue.milestone},
+                    "state": if issue.state == IssueState::Closed { "CLOSED" } else { "OPEN" },
+                    "assignees": issue.assignees.iter().map(|a| serde_json::json!({"login": a})).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        let imported =
+            from_github_issues(&serde_json::to_string(&issues).unwrap(), &mapping).unwrap();
+        let statuses: Vec<(String, String)> = imported
+            .epics
+            .iter()
+            .flat_map(|e| &e.stories)
+            .map(|s| (s.id.clone(), s.status.to_string()))
+            .collect();
+        let original: Vec<(String, String)> = data
+            .epics
+            .iter()
+            .flat_map(|e| &e.stories)
+            .map(|s| (s.id.clone(), s.status.to_string()))
+            .collect();
+        assert_eq!(statuses, original);
+        let reset = imported.find_story("1-2-password-reset").unwrap();
+        assert_eq!(reset.tags, ["security"]);
+        assert_eq!(reset.assignee.as_deref(), Some("sam"));
+        assert_eq!(reset.number, Some(11));
+        assert_eq!(imported.epics[0].status, "in-progress");
+    }
+
+    #[test]
+    fn test_import_unmarked_issues() {
+        let json = r#"[
+            {"number": 7, "title": "Add OAuth", "body": "", "labels": ["backend"],
+             "milestone": {"title": "Auth"}, "state": "open", "assignees": []},
+            {"number": 3, "title": "Sign in", "body": "<!-- clique-story: 4-1-sign-in -->",
+             "labels": [{"name": "status:in-progress"}], "milestone": {"title": "Auth"},
+             "state": "OPEN"},
+            {"number": 9, "title": "Docs", "body": null, "labels": [],
+             "milestone": null, "state": "closed"}
+        ]"#;
+        let data = from_github_issues(json, &GithubMapping::default()).unwrap();
+        let ids: Vec<(&str, &str)> = data
+            .epics
+            .iter()
+            .flat_map(|e| &e.stories)
+            .map(|s| (s.id.as_str(), s.status.as_str()))
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                ("4-7-add-oauth", "backlog"),
+                ("4-1-sign-in", "in-progress"),
+                ("5-9-docs", "done"),
+            ]
+        );
+        assert_eq!(data.epics[0].name, "Auth");
+        assert_eq!(data.epics[1].name, "Epic 5");
+        assert_eq!(data.epics[0].stories[0].tags, ["backend"]);
+
+        assert!(matches!(
+            from_github_issues("{}", &GithubMapping::default()),
+            Err(IntegrationError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,437 @@
+// clique-core/src/integrations/jira.rs
+//! Stories from a Jira board export.
+//!
+//! [`from_jira_csv`] reads the CSV Jira writes from an issue search
+//! ("Export > CSV"), [`from_jira_json`] the response of the REST search API.
+//! Epics become `epic-N` entries in the order they appear; every other issue
+//! becomes a story of the epic it links to, keyed `N-M-<summary slug>`.
+//! Story numbers and the project key are taken from the issue keys, so
+//! [`Story::external_key`] gives back `DMO-12` for issue `DMO-12`.
+
+use super::csv::{Table, cell};
+use super::{IntegrationError, assemble, map_status, slug};
+use crate::text::text;
+use crate::types::{SprintData, Story};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::Read;
+
+/// How Jira issues map onto stories
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct JiraOptions {
+    /// Story status per Jira status name, compared ignoring case; these
+    /// take precedence over the built-in mapping
+    pub status_map: BTreeMap<String, String>,
+    /// Project name; defaults to the export's project name, then its key
+    pub project: Option<String>,
+}
+
+impl JiraOptions {
+    /// The story status for Jira status `name`.
+    ///
+    /// Without an entry in `status_map`, the usual Jira workflow names map
+    /// onto the built-in statuses (`To Do` to `backlog`, `In Progress` to
+    /// `in-progress`, `Code Review` to `review`, `Done` to `done`, ...) and
+    /// anything else is slugged: `Waiting for QA` gives `waiting-for-qa`.
+    pub fn status(&self, name: &str) -> String {
+        map_status(&self.status_map, name)
+    }
+}
+
+/// The fields of one issue used by the import, from either format
+#[derive(Default)]
+struct Issue {
+    key: String,
+    /// Numeric issue ID; newer CSV exports link parents by it
+    id: String,
+    summary: String,
+    issue_type: String,
+    status: String,
+    labels: Vec<String>,
+    assignee: Option<String>,
+    due: Option<String>,
+    /// Key or ID of the epic or parent issue
+    parent: Option<String>,
+    /// Keys of the issues blocking this one
+    blocked_by: Vec<String>,
+    epic_name: Option<String>,
+    project: Option<String>,
+}
+
+impl Issue {
+    fn is_epic(&self) -> bool {
+        self.issue_type.eq_ignore_ascii_case("epic")
+    }
+
+    fn is_subtask(&self) -> bool {
+        let issue_type = self.issue_type.to_ascii_lowercase();
+        issue_type == "sub-task" || issue_type == "subtask"
+    }
+}
+
+/// Project key and number of an issue key: `DMO-12` gives `("DMO", 12)`
+fn split_key(key: &str) -> Option<(&str, u64)> {
+    let (project, number) = key.rsplit_once('-')?;
+    Some((project, number.parse().ok()?))
+}
+
+/// Some non-empty `value`
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Number the epics, turn the other issues into stories and group them.
+/// Stories without a known epic go to one more epic, `No epic`.
+fn build(issues: Vec<Issue>, options: &JiraOptions) -> SprintData {
+    let mut epics: BTreeMap<&str, u64> = BTreeMap::new();
+    let mut names: BTreeMap<u64, String> = BTreeMap::new();
+    for issue in issues.iter().filter(|issue| issue.is_epic()) {
+        let number = epics.len() as u64 + 1;
+        for handle in [&issue.key, &issue.id] {
+            if !handle.is_empty() {
+                epics.insert(handle, number);
+            }
+        }
+        let name = issue.epic_name.as_deref().unwrap_or(&issue.summary);
+        names.insert(number, non_empty(name).unwrap_or_else(|| issue.key.clone()));
+    }
+    let loose_epic = names.len() as u64 + 1;
+
+    let mut sequence: BTreeMap<u64, u64> = BTreeMap::new();
+    let mut ids: BTreeMap<&str, String> = BTreeMap::new();
+    let mut stories = Vec::new();
+    let mut blockers = Vec::new();
+    for issue in issues
+        .iter()
+        .filter(|issue| !issue.is_epic() && !issue.is_subtask())
+    {
+        let epic = issue
+            .parent
+            .as_deref()
+            .and_then(|parent| epics.get(parent).copied())
+            .unwrap_or(loose_epic);
+        let seq = sequence.entry(epic).or_insert(0);
+        *seq += 1;
+        let id = format!("{}-{}-{}", epic, seq, slug(&issue.summary));
+        ids.insert(&issue.key, id.clone());
+        blockers.push(&issue.blocked_by);
+        stories.push(Story {
+            id,
+            status: text(options.status(&issue.status)),
+            number: split_key(&issue.key).map(|(_, number)| number),
+            title: non_empty(&issue.summary),
+            due: issue.due.clone(),
+            tags: issue.labels.clone(),
+            assignee: issue.assignee.clone(),
+            ..Story::default()
+        });
+    }
+    if sequence.contains_key(&loose_epic) {
+        names.insert(loose_epic, "No epic".to_string());
+    }
+
+    // Blockers outside the import are dropped
+    for (story, keys) in stories.iter_mut().zip(blockers) {
+        story.blocked_by = keys
+            .iter()
+            .filter_map(|key| ids.get(key.as_str()).cloned())
+            .collect();
+    }
+
+    let project_key = issues
+        .iter()
+        .find_map(|issue| split_key(&issue.key))
+        .map_or("", |(key, _)| key);
+    let project = options
+        .project
+        .clone()
+        .or_else(|| issues.iter().find_map(|issue| issue.project.clone()))
+        .or_else(|| non_empty(project_key))
+        .unwrap_or_else(|| "Unknown".to_string());
+    assemble(&project, project_key, stories, &names)
+}
+
+/// Stories from a Jira CSV export.
+///
+/// Needs the `Summary`, `Issue key` and `Status` columns; `Issue Type`,
+/// `Issue id`, `Labels` (repeated per label), `Assignee`, `Due Date`,
+/// `Project name` and `Inward issue link (Blocks)` are used when present.
+/// A story's epic is read from `Parent`, `Parent key` or
+/// `Custom field (Epic Link)`, by issue key or ID; an epic's name from
+/// `Custom field (Epic Name)`, else its summary. Sub-tasks are skipped.
+pub fn from_jira_csv(
+    mut reader: impl Read,
+    options: &JiraOptions,
+) -> Result<SprintData, IntegrationError> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|e| IntegrationError::ReadError(e.to_string()))?;
+    let table = Table::parse(&content)?;
+
+    let summary = table.require(&["Summary"])?;
+    let key = table.require(&["Issue key", "Key"])?;
+    let status = table.require(&["Status"])?;
+    let issue_type = table.column(&["Issue Type"]);
+    let id = table.column(&["Issue id"]);
+    let assignee = table.column(&["Assignee"]);
+    let due = table.column(&["Due Date", "Due date"]);
+    let project = table.column(&["Project name"]);
+    let epic_name = table.column(&["Custom field (Epic Name)", "Epic Name"]);
+    let parents = [
+        table.column(&["Parent key"]),
+        table.column(&["Parent", "Parent id"]),
+        table.column(&["Custom field (Epic Link)", "Epic Link"]),
+    ];
+    let labels = table.columns("Labels");
+    let blockers = table.columns("Inward issue link (Blocks)");
+
+    let issues = table
+        .rows
+        .iter()
+        .map(|row| {
+            let all = |columns: &[usize]| -> Vec<String> {
+                columns
+                    .iter()
+                    .filter_map(|&column| non_empty(cell(row, Some(column))))
+                    .collect()
+            };
+            Issue {
+                key: cell(row, Some(key)).to_string(),
+                id: cell(row, id).to_string(),
+                summary: cell(row, Some(summary)).to_string(),
+                issue_type: cell(row, issue_type).to_string(),
+                status: cell(row, Some(status)).to_string(),
+                labels: all(&labels),
+                assignee: non_empty(cell(row, assignee)),
+                due: non_empty(cell(row, due)),
+                parent: parents
+                    .iter()
+                    .find_map(|&column| non_empty(cell(row, column))),
+                blocked_by: all(&blockers),
+                epic_name: non_empty(cell(row, epic_name)),
+                project: non_empty(cell(row, project)),
+            }
+        })
+        .collect();
+    Ok(build(issues, options))
+}
+
+/// Stories from a Jira REST search response (`/rest/api/2/search` or
+/// `/rest/api/3/search`), an object with an `issues` array, or the array
+/// itself.
+///
+/// Reads the same fields as [`from_jira_csv`]: `summary`, `status`,
+/// `issuetype`, `labels`, `assignee`, `duedate`, `project`, `parent` and
+/// `Blocks` links from `issuelinks`.
+pub fn from_jira_json(json: &str, options: &JiraOptions) -> Result<SprintData, IntegrationError> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| IntegrationError::InvalidInput(format!("Jira issues: {}", e)))?;
+    let issues = value
+        .get("issues")
+        .unwrap_or(&value)
+        .as_array()
+        .ok_or_else(|| {
+            IntegrationError::InvalidInput("Jira issues: expected an issues array".to_string())
+        })?;
+
+    let issues = issues
+        .iter()
+        .map(|issue| {
+            let fields = issue.get("fields").unwrap_or(&Value::Null);
+            let string = |value: Option<&Value>| {
+                value
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .and_then(non_empty)
+            };
+            let named = |field: &str, key: &str| string(fields.get(field).and_then(|v| v.get(key)));
+            Issue {
+                key: string(issue.get("key")).unwrap_or_default(),
+                id: string(issue.get("id")).unwrap_or_default(),
+                summary: string(fields.get("summary")).unwrap_or_default(),
+                issue_type: named("issuetype", "name").unwrap_or_default(),
+                status: named("status", "name").unwrap_or_default(),
+                labels: fields
+                    .get("labels")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|label| string(Some(label)))
+                    .collect(),
+                assignee: named("assignee", "displayName"),
+                due: string(fields.get("duedate")),
+                parent: named("parent", "key"),
+                blocked_by: fields
+                    .get("issuelinks")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter(|link| {
+                        link.get("type")
+                            .and_then(|t| t.get("name"))
+                            .and_then(Value::as_str)
+                            .is_some_and(|name| name.eq_ignore_ascii_case("blocks"))
+                    })
+                    .filter_map(|link| string(link.get("inwardIssue").and_then(|i| i.get("key"))))
+                    .collect(),
+                epic_name: None,
+                project: named("project", "name"),
+            }
+        })
+        .collect();
+    Ok(build(issues, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "Summary,Issue key,Issue id,Issue Type,Status,Project name,\
+                       Assignee,Due Date,Labels,Labels,Parent,Inward issue link (Blocks)\n\
+                       Accounts,DMO-1,10001,Epic,In Progress,Demo,,,,,,\n\
+                       User login,DMO-2,10002,Story,Done,Demo,Sam,,auth,web,10001,\n\
+                       \"Reset password, by email\",DMO-5,10005,Story,Code Review,Demo,,\
+                       2025-03-01,auth,,10001,DMO-2\n\
+                       Write tests,DMO-6,10006,Sub-task,To Do,Demo,,,,,10005,\n\
+                       Fix typo,DMO-7,10007,Bug,Waiting for QA,Demo,,,,,,\n";
+
+    fn statuses(data: &SprintData) -> Vec<(&str, &str)> {
+        data.epics
+            .iter()
+            .flat_map(|epic| &epic.stories)
+            .map(|story| (story.id.as_str(), story.status.as_str()))
+            .collect()
+    }
+
+    // =========================================================================
+    // Status Mapping Tests
+    // =========================================================================
+
+    #[test]
+    fn test_status_mapping() {
+        let options = JiraOptions::default();
+        assert_eq!(options.status("To Do"), "backlog");
+        assert_eq!(options.status("IN PROGRESS"), "in-progress");
+        assert_eq!(options.status("Code Review"), "review");
+        assert_eq!(options.status("Resolved"), "done");
+        assert_eq!(options.status("Waiting for QA"), "waiting-for-qa");
+
+        let options = JiraOptions {
+            status_map: BTreeMap::from([
+                ("waiting for qa".to_string(), "review".to_string()),
+                ("Done".to_string(), "released".to_string()),
+            ]),
+            ..JiraOptions::default()
+        };
+        assert_eq!(options.status("Waiting for QA"), "review");
+        assert_eq!(options.status("done"), "released");
+        assert_eq!(options.status("To Do"), "backlog");
+    }
+
+    // =========================================================================
+    // CSV Import Tests
+    // =========================================================================
+
+    #[test]
+    fn test_from_jira_csv() {
+        let data = from_jira_csv(CSV.as_bytes(), &JiraOptions::default()).unwrap();
+        assert_eq!(data.project, "Demo");
+        assert_eq!(data.project_key, "DMO");
+        assert_eq!(
+            statuses(&data),
+            [
+                ("1-1-user-login", "done"),
+                ("1-2-reset-password-by-email", "review"),
+                ("2-1-fix-typo", "waiting-for-qa"),
+            ]
+        );
+        assert_eq!(data.epics[0].name, "Accounts");
+        assert_eq!(data.epics[0].status, "in-progress");
+        assert_eq!(data.epics[1].name, "No epic");
+
+        let login = &data.epics[0].stories[0];
+        assert_eq!(login.external_key(&data.project_key).unwrap(), "DMO-2");
+        assert_eq!(login.tags, ["auth", "web"]);
+        assert_eq!(login.assignee.as_deref(), Some("Sam"));
+        assert_eq!(login.title.as_deref(), Some("User login"));
+        let reset = &data.epics[0].stories[1];
+        assert_eq!(reset.blocked_by, ["1-1-user-login"]);
+        assert_eq!(reset.due.as_deref(), Some("2025-03-01"));
+    }
+
+    #[test]
+    fn test_from_jira_csv_epic_link_and_options() {
+        let csv = "Issue key,Summary,Status,Issue Type,Custom field (Epic Link),\
+                   Custom field (Epic Name)\n\
+                   PAY-3,Story one,Done,Story,PAY-1,\n\
+                   PAY-1,Payments epic,To Do,Epic,,Payments\n";
+        let options = JiraOptions {
+            project: Some("Shop".to_string()),
+            ..JiraOptions::default()
+        };
+        let data = from_jira_csv(csv.as_bytes(), &options).unwrap();
+        assert_eq!(data.project, "Shop");
+        assert_eq!(data.epics[0].name, "Payments");
+        assert_eq!(statuses(&data), [("1-1-story-one", "done")]);
+    }
+
+    #[test]
+    fn test_from_jira_csv_errors() {
+        let missing = from_jira_csv(
+            "Summary,Status\nA,Done\n".as_bytes(),
+            &JiraOptions::default(),
+        );
+        assert_eq!(
+            missing.unwrap_err().to_string(),
+            "Invalid input: CSV has no Issue key column"
+        );
+        assert!(from_jira_csv(&[0xff, 0xfe][..], &JiraOptions::default()).is_err());
+    }
+
+    // =========================================================================
+    // JSON Import Tests
+    // =========================================================================
+
+    #[test]
+    fn test_from_jira_json() {
+        let json = r#"{"issues": [
+            {"key": "DMO-1", "id": "10001", "fields": {"summary": "Accounts",
+                "issuetype": {"name": "Epic"}, "status": {"name": "In Progress"},
+                "project": {"key": "DMO", "name": "Demo"}}},
+            {"key": "DMO-2", "fields": {"summary": "User login",
+                "issuetype": {"name": "Story"}, "status": {"name": "Done"},
+                "labels": ["auth"], "assignee": {"displayName": "Sam"},
+                "parent": {"key": "DMO-1"}}},
+            {"key": "DMO-4", "fields": {"summary": "Logout",
+                "issuetype": {"name": "Story"}, "status": {"name": "To Do"},
+                "duedate": "2025-04-01", "parent": {"key": "DMO-1"},
+                "issuelinks": [
+                    {"type": {"name": "Blocks"}, "inwardIssue": {"key": "DMO-2"}},
+                    {"type": {"name": "Relates"}, "outwardIssue": {"key": "DMO-9"}}
+                ]}}
+        ]}"#;
+        let data = from_jira_json(json, &JiraOptions::default()).unwrap();
+        assert_eq!(data.project, "Demo");
+        assert_eq!(
+            statuses(&data),
+            [("1-1-user-login", "done"), ("1-2-logout", "backlog")]
+        );
+        let logout = &data.epics[0].stories[1];
+        assert_eq!(logout.number, Some(4));
+        assert_eq!(logout.blocked_by, ["1-1-user-login"]);
+        assert_eq!(logout.due.as_deref(), Some("2025-04-01"));
+        assert_eq!(data.epics[0].stories[0].tags, ["auth"]);
+
+        assert!(from_jira_json("{}", &JiraOptions::default()).is_err());
+        assert!(from_jira_json("[", &JiraOptions::default()).is_err());
+        assert!(
+            from_jira_json("[]", &JiraOptions::default())
+                .unwrap()
+                .epics
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,197 @@
+// clique-core/src/integrations/mod.rs
+//! Converters between status files and the tools teams use around them.
+//!
+//! Each integration lives in its own submodule; the entry points are
+//! re-exported here, so callers write `integrations::slack_payload`.
+//! Importers build a [`SprintData`] whose epics are `epic-N` entries and
+//! whose stories are `N-M-slug` keys, as in a hand-written sprint file.
+
+mod board;
+mod chat;
+mod csv;
+mod github;
+mod jira;
+
+pub use board::{BoardOptions, from_notion_csv, from_trello_json};
+pub use chat::{discord_payload, slack_payload};
+pub use github::{GithubMapping, IssueSpec, IssueState, from_github_issues, to_github_issues};
+pub use jira::{JiraOptions, from_jira_csv, from_jira_json};
+
+use crate::types::{Epic, SprintData, Story};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IntegrationError {
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Failed to read input: {0}")]
+    ReadError(String),
+}
+
+/// Epic number prefix of a story key: `3-2-login` gives `3`
+fn story_epic(id: &str) -> Option<u64> {
+    id.split_once('-')?.0.parse().ok()
+}
+
+/// Lowercase words joined by `-`, for story keys: `User Login!` gives
+/// `user-login`
+fn slug(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    let out = out.trim_end_matches('-');
+    if out.is_empty() {
+        "story".to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+/// Story status for a status, list or column named `name` on another tool.
+///
+/// An entry of `status_map`, compared ignoring case, wins; otherwise common
+/// board names map onto the built-in statuses (`To Do` to `backlog`,
+/// `Doing` to `in-progress`, `Code Review` to `review`, `Done` to `done`,
+/// ...) and anything else is slugged: `Waiting for QA` gives
+/// `waiting-for-qa`.
+fn map_status(status_map: &BTreeMap<String, String>, name: &str) -> String {
+    let name = name.trim();
+    if let Some((_, status)) = status_map
+        .iter()
+        .find(|(other, _)| other.trim().eq_ignore_ascii_case(name))
+    {
+        return status.clone();
+    }
+    let status = match name.to_ascii_lowercase().as_str() {
+        "" | "to do" | "todo" | "open" | "new" | "backlog" | "not started" | "ideas" => "backlog",
+        "selected for development"
+        | "ready"
+        | "ready for dev"
+        | "ready for development"
+        | "up next" => "ready-for-dev",
+        "in progress" | "doing" | "in development" | "reopened" => "in-progress",
+        "in review" | "code review" | "review" | "in qa" | "testing" => "review",
+        "done" | "closed" | "resolved" | "complete" | "completed" => "done",
+        _ => return slug(name),
+    };
+    status.to_string()
+}
+
+/// Status of an epic from its stories: `done` once all are done, `backlog`
+/// while none has started, `in-progress` otherwise
+fn epic_status(stories: &[Story]) -> String {
+    if !stories.is_empty() && stories.iter().all(|story| story.status == "done") {
+        "done"
+    } else if stories.iter().all(|story| story.status == "backlog") {
+        "backlog"
+    } else {
+        "in-progress"
+    }
+    .to_string()
+}
+
+/// Group imported stories into their epics, ordered by epic number, each
+/// named from `epic_names` or `Epic N`. Stories without an epic number in
+/// their key are dropped, as the sprint parser would.
+fn assemble(
+    project: &str,
+    project_key: &str,
+    stories: Vec<Story>,
+    epic_names: &BTreeMap<u64, String>,
+) -> SprintData {
+    let mut by_epic: BTreeMap<u64, Vec<Story>> = BTreeMap::new();
+    for mut story in stories {
+        if let Some(number) = story_epic(&story.id) {
+            story.epic_id = format!("epic-{}", number);
+            by_epic.entry(number).or_default().push(story);
+        }
+    }
+    SprintData {
+        project: project.to_string(),
+        project_key: project_key.to_string(),
+        epics: by_epic
+            .into_iter()
+            .map(|(number, stories)| Epic {
+                id: format!("epic-{}", number),
+                name: epic_names
+                    .get(&number)
+                    .cloned()
+                    .unwrap_or_else(|| format!("Epic {}", number)),
+                status: epic_status(&stories),
+                stories,
+            })
+            .collect(),
+        sprint_goal: None,
+        sprint_start: None,
+        sprint_end: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::text;
+
+    fn story(id: &str, status: &str) -> Story {
+        Story {
+            id: id.to_string(),
+            status: text(status),
+            ..Story::default()
+        }
+    }
+
+    // =========================================================================
+    // Helper Tests
+    // =========================================================================
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("User Login!"), "user-login");
+        assert_eq!(slug("  --Add  OAuth 2.0 "), "add-oauth-2-0");
+        assert_eq!(slug("日本"), "story");
+    }
+
+    #[test]
+    fn test_assemble() {
+        let names = BTreeMap::from([(2, "Payments".to_string())]);
+        let data = assemble(
+            "Demo",
+            "DMO",
+            vec![
+                story("2-1-card", "done"),
+                story("1-1-login", "backlog"),
+                story("2-2-refund", "done"),
+                story("1-2-logout", "review"),
+                story("loose", "done"),
+            ],
+            &names,
+        );
+        let epics: Vec<(&str, &str, &str, usize)> = data
+            .epics
+            .iter()
+            .map(|e| {
+                (
+                    e.id.as_str(),
+                    e.name.as_str(),
+                    e.status.as_str(),
+                    e.stories.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            epics,
+            [
+                ("epic-1", "Epic 1", "in-progress", 2),
+                ("epic-2", "Payments", "done", 2),
+            ]
+        );
+        assert_eq!(data.epics[1].stories[0].epic_id, "epic-2");
+        assert_eq!(epic_status(&[story("1-a", "backlog")]), "backlog");
+    }
+}
@@ -0,0 +1,383 @@
+// clique-core/src/io.rs
+//! Crash-safe reading and writing of status files.
+//!
+//! A status file written in place is truncated first, so a crash or kill
+//! mid-write leaves it empty or half-written. [`write_atomic`] writes a
+//! temporary file beside the target, flushes it to disk and renames it over
+//! the original, so readers only ever see the old or the new content.
+//!
+//! [`with_lock`] serializes writers across processes, such as two editor
+//! windows or the extension and a CLI, through a `.lock` sidecar file.
+
+use crate::validation::get_validated_path;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IoError {
+    #[error("Path is outside the workspace: {0}")]
+    PathOutsideWorkspace(String),
+    #[error("Failed to read file: {0}")]
+    ReadError(String),
+    #[error("Failed to write file: {0}")]
+    WriteError(String),
+    #[error("Failed to lock file: {0}")]
+    LockError(String),
+    #[error("Timed out waiting for lock: {0}")]
+    LockTimeout(String),
+}
+
+/// A reasonable wait for [`with_lock`]; status updates hold the lock briefly
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Age after which a lock is presumed left behind by a crashed process.
+///
+/// This is a lease, not a heartbeat: the lock file is not refreshed while
+/// held, so a holder that runs longer than this may have its lock taken
+/// over. Locked sections are single file writes and should stay far below it.
+pub const STALE_LOCK_AGE: Duration = Duration::from_secs(60);
+
+/// Pause between attempts to take a held lock
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Removes the lock file when the holder finishes, panics included, unless
+/// it was taken over and now holds someone else's token
+struct LockGuard {
+    lock: PathBuf,
+    token: String,
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        if fs::read_to_string(&self.lock).is_ok_and(|content| content == self.token) {
+            let _ = fs::remove_file(&self.lock);
+        }
+    }
+}
+
+/// A name suffix unique to this call: process ID, a per-process counter and
+/// the clock, since WASI builds all share process ID 0
+fn unique_suffix() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.subsec_nanos());
+    format!(
+        "{}-{}-{}",
+        process_id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed),
+        nanos
+    )
+}
+
+/// Path of the sidecar lock file for `path`, e.g. `sprint-status.yaml.lock`
+pub fn lock_path(path: &str) -> String {
+    format!("{}.lock", path)
+}
+
+/// Whether a lock file was last written longer ago than [`STALE_LOCK_AGE`]
+fn is_stale(lock: &Path) -> bool {
+    fs::metadata(lock)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > STALE_LOCK_AGE)
+}
+
+/// Remove a stale lock without racing other waiters.
+///
+/// The lock is renamed aside to a unique name, which only one waiter can
+/// do, and the moved file is then checked to be the stale one that was
+/// read. If a fresh lock was moved instead, it is linked back into place.
+fn take_over_stale(lock: &Path) -> bool {
+    let Ok(seen) = fs::read_to_string(lock) else {
+        return false;
+    };
+    if !is_stale(lock) {
+        return false;
+    }
+    let mut aside = lock.as_os_str().to_owned();
+    aside.push(format!(".stale-{}", unique_suffix()));
+    let aside = PathBuf::from(aside);
+    if fs::rename(lock, &aside).is_err() {
+        return false;
+    }
+    let same = is_stale(&aside) && fs::read_to_string(&aside).is_ok_and(|c| c == seen);
+    if !same {
+        // Fails harmlessly if a new lock has been created meanwhile
+        let _ = fs::hard_link(&aside, lock);
+    }
+    let _ = fs::remove_file(&aside);
+    same
+}
+
+/// Run `f` while holding the advisory lock for `path`.
+///
+/// The lock is the sidecar file from [`lock_path`], created exclusively and
+/// holding a token that starts with the owner's process ID; it is removed
+/// when `f` returns or panics, provided it still holds that token. While
+/// another holder has it, this retries until `timeout` has passed and then
+/// fails with [`IoError::LockTimeout`]. A lock file older than
+/// [`STALE_LOCK_AGE`] is assumed to belong to a crashed process and is
+/// taken over.
+///
+/// The lock is advisory: it only excludes other callers of `with_lock` (or
+/// tools honouring the same `.lock` file), not arbitrary writers.
+pub fn with_lock<T>(path: &str, timeout: Duration, f: impl FnOnce() -> T) -> Result<T, IoError> {
+    let lock = PathBuf::from(lock_path(path));
+    let token = format!("{}\n", unique_suffix());
+    let deadline = Instant::now() + timeout;
+    loop {
+        match OpenOptions::new().write(true).create_new(true).open(&lock) {
+            Ok(mut file) => {
+                let _ = file.write_all(token.as_bytes());
+                break;
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                if take_over_stale(&lock) {
+                    continue;
+                }
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(IoError::LockTimeout(lock.to_string_lossy().to_string()));
+                }
+                std::thread::sleep(LOCK_RETRY_INTERVAL.min(deadline - now));
+            }
+            Err(e) => return Err(IoError::LockError(format!("{}: {}", lock.display(), e))),
+        }
+    }
+
+    let _guard = LockGuard { lock, token };
+    Ok(f())
+}
+
+/// ID of this process for lock files and temporary names; WASI has no
+/// process IDs, so sandboxed builds all use 0
+fn process_id() -> u32 {
+    #[cfg(target_os = "wasi")]
+    return 0;
+    #[cfg(not(target_os = "wasi"))]
+    std::process::id()
+}
+
+/// Replace the file at `path` with `content` atomically.
+///
+/// The content goes to a freshly created temporary file in the same
+/// directory, under a name unique to this call, is synced, and is then
+/// renamed over `path`. An existing file keeps its permissions.
+/// On failure the temporary file is removed and `path` is left untouched.
+pub fn write_atomic(path: &str, content: &str) -> Result<(), IoError> {
+    let target = Path::new(path);
+    let error = |e: std::io::Error| IoError::WriteError(format!("{}: {}", path, e));
+    let file_name = target
+        .file_name()
+        .ok_or_else(|| IoError::WriteError(format!("{}: not a file path", path)))?;
+    let dir = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let (temp, mut file) = loop {
+        let temp = dir.join(format!(
+            ".{}.tmp-{}",
+            file_name.to_string_lossy(),
+            unique_suffix()
+        ));
+        match OpenOptions::new().write(true).create_new(true).open(&temp) {
+            Ok(file) => break (temp, file),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(error(e)),
+        }
+    };
+
+    let written = (|| {
+        file.write_all(content.as_bytes())?;
+        // WASI has no permission bits to carry over
+        #[cfg(not(target_os = "wasi"))]
+        if let Ok(meta) = fs::metadata(target) {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.sync_all()?;
+        fs::rename(&temp, target)
+    })();
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(error(e));
+    }
+
+    // Persist the rename itself; not every platform can open a directory
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Read a status file, refusing paths outside `workspace_root`.
+pub fn read_status_file(path: &str, workspace_root: &str) -> Result<String, IoError> {
+    let path = get_validated_path(path, workspace_root)
+        .ok_or_else(|| IoError::PathOutsideWorkspace(path.to_string()))?;
+    fs::read_to_string(&path).map_err(|e| IoError::ReadError(format!("{}: {}", path, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    // =========================================================================
+    // write_atomic Tests
+    // =========================================================================
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let root = TempDir::new("io-write");
+        let path = root.join("sprint-status.yaml");
+        let path_str = path.to_string_lossy().to_string();
+
+        write_atomic(&path_str, "development_status:\n  1-a: backlog\n").unwrap();
+        write_atomic(&path_str, "development_status:\n  1-a: done\n").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "development_status:\n  1-a: done\n"
+        );
+        // No temporary files are left behind
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_failure_leaves_no_file() {
+        let root = TempDir::new("io-missing-dir");
+        let path = root.join("no-such-dir/status.yaml");
+        let result = write_atomic(&path.to_string_lossy(), "x: 1\n");
+        assert!(matches!(result, Err(IoError::WriteError(_))));
+        assert!(!path.exists());
+        assert!(matches!(
+            write_atomic("/", "x"),
+            Err(IoError::WriteError(_))
+        ));
+    }
+
+    // =========================================================================
+    // with_lock Tests
+    // =========================================================================
+
+    #[test]
+    fn test_with_lock_runs_and_releases() {
+        let root = TempDir::new("io-lock");
+        let path = root
+            .join("sprint-status.yaml")
+            .to_string_lossy()
+            .to_string();
+        let lock = lock_path(&path);
+
+        let held = with_lock(&path, DEFAULT_LOCK_TIMEOUT, || Path::new(&lock).exists()).unwrap();
+        assert!(held);
+        assert!(!Path::new(&lock).exists());
+
+        // A panicking holder still releases the lock
+        let result = std::panic::catch_unwind(|| {
+            with_lock(&path, DEFAULT_LOCK_TIMEOUT, || panic!("boom")).unwrap();
+        });
+        assert!(result.is_err());
+        assert!(!Path::new(&lock).exists());
+    }
+
+    #[test]
+    fn test_with_lock_times_out_when_held() {
+        let root = TempDir::new("io-contended");
+        let path = root
+            .join("sprint-status.yaml")
+            .to_string_lossy()
+            .to_string();
+        fs::write(lock_path(&path), "12345\n").unwrap();
+
+        let started = Instant::now();
+        let result = with_lock(&path, Duration::from_millis(60), || ());
+        assert!(matches!(result, Err(IoError::LockTimeout(_))));
+        assert!(started.elapsed() >= Duration::from_millis(60));
+        // Someone else's lock is left alone
+        assert!(Path::new(&lock_path(&path)).exists());
+    }
+
+    #[test]
+    fn test_with_lock_takes_over_stale_lock() {
+        let root = TempDir::new("io-stale");
+        let path = root
+            .join("sprint-status.yaml")
+            .to_string_lossy()
+            .to_string();
+        let lock = File::create(lock_path(&path)).unwrap();
+        lock.set_modified(SystemTime::now() - STALE_LOCK_AGE * 2)
+            .unwrap();
+
+        assert_eq!(with_lock(&path, Duration::ZERO, || 7).unwrap(), 7);
+        assert!(!Path::new(&lock_path(&path)).exists());
+        // Only the lock itself was moved aside and removed
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+
+        let missing = root.join("no-such-dir/status.yaml");
+        assert!(matches!(
+            with_lock(&missing.to_string_lossy(), Duration::ZERO, || ()),
+            Err(IoError::LockError(_))
+        ));
+    }
+
+    #[test]
+    fn test_with_lock_keeps_a_lock_it_lost() {
+        let root = TempDir::new("io-lost");
+        let path = root
+            .join("sprint-status.yaml")
+            .to_string_lossy()
+            .to_string();
+        let lock = lock_path(&path);
+
+        // Another process took the lock over while this holder overran
+        with_lock(&path, DEFAULT_LOCK_TIMEOUT, || {
+            fs::write(&lock, "4242-0-0\n").unwrap();
+        })
+        .unwrap();
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "4242-0-0\n");
+
+        // A fresh lock is never taken over
+        assert!(!take_over_stale(Path::new(&lock)));
+        assert!(Path::new(&lock).exists());
+    }
+
+    #[test]
+    fn test_unique_suffix_differs_per_call() {
+        assert_ne!(unique_suffix(), unique_suffix());
+    }
+
+    // =========================================================================
+    // read_status_file Tests
+    // =========================================================================
+
+    #[test]
+    fn test_read_status_file() {
+        let root = TempDir::new("io-read");
+        let root_str = root.to_string_lossy().to_string();
+        let path = root.join("bmm-workflow-status.yaml");
+        fs::write(&path, "workflows: {}\n").unwrap();
+
+        assert_eq!(
+            read_status_file(&path.to_string_lossy(), &root_str).unwrap(),
+            "workflows: {}\n"
+        );
+        assert!(matches!(
+            read_status_file(&root.join("absent.yaml").to_string_lossy(), &root_str),
+            Err(IoError::ReadError(_))
+        ));
+        assert!(matches!(
+            read_status_file("/etc/passwd", &root_str),
+            Err(IoError::PathOutsideWorkspace(_))
+        ));
+        let escape = format!("{}/../secret.yaml", root_str);
+        assert!(matches!(
+            read_status_file(&escape, &root_str),
+            Err(IoError::PathOutsideWorkspace(_))
+        ));
+    }
+}
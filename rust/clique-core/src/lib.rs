@@ -3,19 +3,120 @@
 //! Pure Rust implementation of workflow and sprint parsing logic
 //! for the Clique VS Code extension.
 
+pub mod adapter;
+pub mod aggregate;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod cache;
+pub mod config;
+pub mod conflict;
+pub mod consistency;
+mod definition;
+pub mod diagnostics;
+pub mod diff;
+pub mod document;
+pub mod edit;
+mod epics_md;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod gate;
+#[cfg(all(feature = "git", not(target_os = "wasi")))]
+pub mod git;
+pub mod history;
+pub mod integrations;
+#[cfg(feature = "fs")]
+pub mod io;
+pub mod limits;
+pub mod lint;
+pub mod merge;
+pub mod metrics;
+pub mod options;
+#[cfg(feature = "fs")]
+mod outputs;
+mod pattern;
+pub mod policy;
+pub mod preprocess;
+pub mod query;
+pub mod report;
+mod schema;
+pub mod settings;
 pub mod sprint;
+pub mod story_doc;
+mod tasklist;
+pub mod text;
+mod trace;
 pub mod types;
 pub mod validation;
+#[cfg(all(feature = "watch", not(target_os = "wasi")))]
+pub mod watch;
 pub mod workflow;
+mod yaml_line;
 
 #[cfg(test)]
 mod fuzz_tests;
+#[cfg(all(test, feature = "fs"))]
+mod testing;
 
 // Re-export main types and functions for convenience
-pub use sprint::{SprintError, parse_sprint_status, update_story_status};
-pub use types::{Epic, Phase, SprintData, Story, WorkflowData, WorkflowItem};
-pub use validation::{get_validated_path, is_inside_workspace};
-pub use workflow::{WorkflowError, parse_workflow_status, update_workflow_status};
+pub use adapter::{FormatAdapter, FormatRegistry, Tasklist};
+pub use aggregate::{Project, ProjectSet, ProjectStats};
+pub use cache::ParseCache;
+pub use config::{CONFIG_FILE, Config, ConfigError};
+pub use conflict::{ConflictSides, Strategy, resolve_with};
+pub use consistency::{Inconsistency, InconsistencyKind};
+pub use diagnostics::{Diagnosed, Diagnostic, Severity, sprint_diagnostics, workflow_diagnostics};
+pub use diff::{ItemChange, SprintDiff, diff_sprint, diff_workflow};
+pub use document::{SprintDocument, WorkflowDocument};
+pub use edit::{Applied, EditError, EditEvent, EditRecord, Hooks, Operation, Transaction};
+pub use gate::{GateFailure, GatePolicy, GateReport};
+#[cfg(all(feature = "git", not(target_os = "wasi")))]
+pub use git::{CommitInfo, GitError};
+pub use history::{HISTORY_FILE, HistoryEntry, HistoryError};
+#[cfg(feature = "fs")]
+pub use io::{IoError, read_status_file, with_lock, write_atomic};
+pub use limits::ParseLimits;
+pub use merge::{MergeConflict, SprintMerge, merge_sprint};
+pub use metrics::{CycleTime, Summary, TransitionTime};
+pub use options::{ParseOptions, UpdateOptions};
+pub use policy::{DEFAULT_DENY_PATTERNS, PathPolicy, STATUS_FILE_PATTERNS};
+pub use preprocess::{PreprocessError, Preprocessing};
+pub use query::WorkflowQuery;
+pub use sprint::{
+    BlockedStory, SprintError, add_story, enrich_from_epics_md, parse_sprint_status,
+    parse_sprint_status_with_limits, parse_sprint_status_with_options, parse_story, remove_story,
+    rename_story, update_story_status, update_story_status_if_unchanged,
+    update_story_status_with_options, update_story_statuses,
+};
+pub use story_doc::{StoryDoc, StoryDocError, parse_story_markdown, update_story_doc_status};
+pub use text::{Atom, Text};
+pub use types::{
+    Date, Epic, ItemState, ParseIssue, Phase, PhaseInfo, SprintData, StatusAliases, StatusClass,
+    StatusInfo, StatusRegistry, Story, StoryStatus, WorkflowData, WorkflowItem, WorkflowMeta,
+    normalize_status, normalize_workflow_status, parse_date,
+};
+#[cfg(feature = "fs")]
+pub use validation::is_inside_workspace_canonical;
+pub use validation::{
+    CaseSensitivity, PathError, SchemaKind, SchemaViolation, ValidatedPath, ViolationKind,
+    WorkspaceSet, get_validated_path, is_inside_any_workspace, is_inside_workspace,
+    validate_against_schema, validate_path, validate_uri,
+};
+#[cfg(all(feature = "watch", not(target_os = "wasi")))]
+pub use watch::{StatusChange, StatusChangeEvent, StatusWatcher, WatchError, watch_status_files};
+pub use workflow::{
+    DefinedWorkflow, LossyField, Migration, RoundtripReport, WorkflowDefinition, WorkflowError,
+    WorkflowField, WorkflowFormat, complete_item, merge_definition, migrate_workflow, parse_item,
+    parse_workflow_definition, parse_workflow_metadata, parse_workflow_status,
+    parse_workflow_status_lenient, parse_workflow_status_with_limits,
+    parse_workflow_status_with_options, remove_workflow_field, reopen_item, roundtrip_check,
+    sniff_format, update_workflow_field, update_workflow_status,
+    update_workflow_status_if_unchanged, update_workflow_status_with_format,
+    update_workflow_statuses,
+};
+#[cfg(feature = "fs")]
+pub use workflow::{
+    OutputIssue, OutputIssueKind, load_definition, verify_outputs, verify_outputs_since,
+};
 
 #[cfg(test)]
 mod tests {
@@ -30,10 +131,88 @@ mod tests {
         // Verify that all public exports are accessible
         let _: fn(&str) -> Result<WorkflowData, WorkflowError> = parse_workflow_status;
         let _: fn(&str) -> Result<SprintData, SprintError> = parse_sprint_status;
+        let _: fn(&str, &str) -> Result<WorkflowItem, WorkflowError> = parse_item;
+        let _: fn(&str, &str) -> Result<Story, SprintError> = parse_story;
+        let _: fn(&str) -> Result<WorkflowMeta, WorkflowError> = parse_workflow_metadata;
+        let _: fn(&str) -> Option<WorkflowFormat> = sniff_format;
+        let _: fn(&str, WorkflowFormat, &str, &str) -> Result<String, WorkflowError> =
+            update_workflow_status_with_format;
         let _: fn(&str, &str, &str) -> Result<String, WorkflowError> = update_workflow_status;
         let _: fn(&str, &str, &str) -> Result<String, SprintError> = update_story_status;
+        let _: Result<String, WorkflowError> = update_workflow_statuses("", &[]);
+        let _: Result<String, SprintError> = update_story_statuses("", &[]);
+        let _: fn(&str) -> Diagnosed<WorkflowData> = workflow_diagnostics;
+        let _: fn(&str) -> Diagnosed<Option<SprintData>> = sprint_diagnostics;
+        let _: fn(&WorkflowData, &WorkflowData) -> Vec<ItemChange> = diff_workflow;
+        let _: fn(&SprintData, &SprintData) -> Vec<ItemChange> = diff_sprint;
+        let _: fn(&WorkflowData, &SprintData, &GatePolicy) -> GateReport = gate::evaluate;
+        let _: fn(&str, &str, &str) -> Result<SprintMerge, SprintError> = merge_sprint;
+        let _: fn(&str) -> Result<WorkflowDocument, WorkflowError> = WorkflowDocument::parse;
+        let _: fn(&str) -> Result<SprintDocument, SprintError> = SprintDocument::parse;
+        let _: fn(&str, &str, &str) -> Result<String, SprintError> = rename_story;
+        let _: fn(&str, &str, &str) -> Result<String, SprintError> = add_story;
+        let _: fn(&str, &str) -> Result<String, SprintError> = remove_story;
+        let _: Result<Applied, EditError> = Transaction::new().apply("");
+        let _: fn(&str, &str) -> String = edit::unified_diff;
+        let _: ParseCache = ParseCache::with_capacity(cache::DEFAULT_CAPACITY);
+        let _: fn(&str, &EditRecord) -> Result<Applied, EditError> = edit::apply_inverse;
+        let _: fn(&str, &str, WorkflowField) -> Result<String, WorkflowError> =
+            remove_workflow_field;
+        let _: fn(&str) -> (WorkflowData, Vec<ParseIssue>) = parse_workflow_status_lenient;
+        let _: fn(&str, &str, Option<&str>) -> Result<String, WorkflowError> = complete_item;
+        let _: fn(&str, &str) -> Result<String, WorkflowError> = reopen_item;
+        let _: fn(&str, &str, WorkflowField, &str) -> Result<String, WorkflowError> =
+            update_workflow_field;
+        let _: fn(&str) -> Result<(), RoundtripReport> = roundtrip_check;
+        let _: fn(&str) -> StoryStatus = normalize_status;
+        let _: fn(&str) -> StatusClass = normalize_workflow_status;
+        let _: fn(&str, &ParseOptions) -> Result<WorkflowData, WorkflowError> =
+            parse_workflow_status_with_options;
+        let _: fn(&str, &ParseOptions) -> Result<SprintData, SprintError> =
+            parse_sprint_status_with_options;
+        let _: StatusAliases = StatusAliases::default();
+        let _: ItemState = ItemState::Actionable;
+        let _: fn(&str) -> Result<StoryDoc, StoryDocError> = parse_story_markdown;
+        let _: fn(&str) -> Option<Date> = parse_date;
+        let _: fn(&HistoryEntry) -> Result<String, HistoryError> = history::record;
+        let _: fn(&str) -> Result<Vec<HistoryEntry>, HistoryError> = history::parse;
+        assert_eq!(HISTORY_FILE, "clique-history.yaml");
+        let _: fn(&str, &str, &str, &str) -> Result<String, WorkflowError> =
+            update_workflow_status_if_unchanged;
+        let _: fn(&str, &str, &str, &str) -> Result<String, SprintError> =
+            update_story_status_if_unchanged;
+        let _: fn(&[HistoryEntry]) -> Vec<CycleTime> = metrics::cycle_times;
+        let _: fn(&[HistoryEntry]) -> Vec<TransitionTime> = metrics::transition_times;
+        let _: Option<Summary> = metrics::summarize([1]);
+        let _: fn(&WorkflowData, &SprintData) -> String = metrics::to_prometheus;
+        let _: fn(&str) -> Result<Config, ConfigError> = config::parse;
+        let _: Result<WorkflowData, WorkflowError> = FormatRegistry::new().parse("");
+        assert_eq!(CONFIG_FILE, ".clique.toml");
+        let _: fn(&str, &str, &str, &UpdateOptions) -> Result<String, SprintError> =
+            update_story_status_with_options;
+        let _: fn(&SprintData, &[StoryDoc]) -> Vec<Inconsistency> = consistency::check;
+        let _: InconsistencyKind = InconsistencyKind::StatusMismatch;
+        let _: fn(&str, &str) -> Result<String, StoryDocError> = update_story_doc_status;
+        let _: fn(&mut SprintData, &str) = enrich_from_epics_md;
+        let _: ProjectStats = ProjectSet::new().stats();
+        let _: Project = Project::default();
+        let _: fn(&str) -> Result<WorkflowDefinition, WorkflowError> = parse_workflow_definition;
+        let _: fn(&mut WorkflowData, &WorkflowDefinition) = merge_definition;
+        let _: StatusRegistry = StatusRegistry::new().with_status(StatusInfo::new("x", "X"));
+        let _ = LossyField {
+            path: String::new(),
+            original: None,
+            roundtripped: None,
+        };
+        let _: fn(&str, &ParseLimits) -> Result<WorkflowData, WorkflowError> =
+            parse_workflow_status_with_limits;
+        let _: fn(&str, &ParseLimits) -> Result<SprintData, SprintError> =
+            parse_sprint_status_with_limits;
         let _: fn(&str, &str) -> bool = is_inside_workspace;
         let _: fn(&str, &str) -> Option<String> = get_validated_path;
+        let _: fn(&str, &str) -> Result<ValidatedPath, PathError> = validate_path;
+        let _: fn(&str, &str) -> Result<ValidatedPath, PathError> = validate_uri;
+        let _: fn(&str, &[&str]) -> Option<usize> = is_inside_any_workspace;
     }
 
     #[test]
@@ -42,16 +221,22 @@ mod tests {
         let _item = WorkflowItem {
             id: "test".to_string(),
             phase: Phase::Number(1),
-            status: "required".to_string(),
+            status: "required".into(),
             agent: None,
             command: None,
             note: None,
             output_file: None,
+            custom_status: None,
+            depends_on: Vec::new(),
+            tracks: Vec::new(),
+            description: None,
+            due: None,
+            tags: Vec::new(),
         };
 
         let _workflow_data = WorkflowData {
             last_updated: "2025-01-01".to_string(),
-            status: "active".to_string(),
+            status: "active".into(),
             status_note: None,
             project: "Test".to_string(),
             project_type: "greenfield".to_string(),
@@ -59,18 +244,20 @@ mod tests {
             field_type: "default".to_string(),
             workflow_path: "".to_string(),
             items: vec![],
+            phases: Vec::new(),
         };
 
         let _story = Story {
             id: "1-test".to_string(),
-            status: "backlog".to_string(),
+            status: "backlog".into(),
             epic_id: "epic-1".to_string(),
+            ..Default::default()
         };
 
         let _epic = Epic {
             id: "epic-1".to_string(),
             name: "Test Epic".to_string(),
-            status: "in-progress".to_string(),
+            status: "in-progress".into(),
             stories: vec![],
         };
 
@@ -78,6 +265,9 @@ mod tests {
             project: "Test".to_string(),
             project_key: "TST".to_string(),
             epics: vec![],
+            sprint_goal: None,
+            sprint_start: None,
+            sprint_end: None,
         };
     }
 
@@ -289,7 +479,7 @@ workflows:
             Some("Initial brainstorm session complete".to_string())
         );
         assert_eq!(item.phase, Phase::Number(0));
-        assert_eq!(item.agent, Some("analyst".to_string()));
+        assert_eq!(item.agent.as_deref(), Some("analyst"));
         assert_eq!(item.command, Some("brainstorm".to_string()));
     }
 
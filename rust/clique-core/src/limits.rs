@@ -0,0 +1,439 @@
+// clique-core/src/limits.rs
+//! Resource limits enforced before a document is parsed.
+//!
+//! Size limits (bytes, line length) are checked first since they are cheap.
+//! YAML aliases let a few hundred bytes expand into millions of nodes
+//! ("billion laughs"). The limits here are checked by walking the event
+//! stream without building a tree, so an oversized document is rejected
+//! before it can allocate anything substantial.
+
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::cell::RefCell;
+use std::fmt;
+
+/// Limits applied while parsing status files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum size of the input in bytes
+    pub max_bytes: usize,
+    /// Maximum length of a single line in bytes
+    pub max_line_length: usize,
+    /// Maximum number of workflow items or sprint entries
+    pub max_items: usize,
+    /// Maximum number of alias references (`*name`) in the document
+    pub max_aliases: usize,
+    /// Maximum number of nodes after alias expansion
+    pub max_nodes: usize,
+    /// Maximum nesting depth of sequences and mappings
+    pub max_depth: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: 2 * 1024 * 1024,
+            max_line_length: 16 * 1024,
+            max_items: 10_000,
+            max_aliases: 100,
+            max_nodes: 100_000,
+            max_depth: 64,
+        }
+    }
+}
+
+impl ParseLimits {
+    /// Limits that never trigger; useful for trusted input.
+    pub fn unlimited() -> Self {
+        Self {
+            max_bytes: usize::MAX,
+            max_line_length: usize::MAX,
+            max_items: usize::MAX,
+            max_aliases: usize::MAX,
+            max_nodes: usize::MAX,
+            max_depth: usize::MAX,
+        }
+    }
+}
+
+/// Count alias references outside quoted strings and comments.
+fn count_aliases(content: &str) -> usize {
+    let mut count = 0;
+    let mut quote: Option<char> = None;
+    let mut prev = '\n';
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' if prev.is_whitespace() || "[{,:".contains(prev) => quote = Some(c),
+                '#' if prev.is_whitespace() => {
+                    // Skip the rest of the comment line
+                    for next in chars.by_ref() {
+                        if next == '\n' {
+                            break;
+                        }
+                    }
+                    prev = '\n';
+                    continue;
+                }
+                '*' if (prev.is_whitespace() || "[{,".contains(prev))
+                    && chars.peek().is_some_and(|n| !n.is_whitespace()) =>
+                {
+                    count += 1;
+                }
+                _ => {}
+            },
+        }
+        prev = c;
+    }
+    count
+}
+
+/// Running totals shared by every node visitor
+struct Budget<'a> {
+    limits: &'a ParseLimits,
+    nodes: usize,
+    exceeded: Option<String>,
+}
+
+/// Visits a node and its children, counting without storing anything
+struct NodeCounter<'b, 'a> {
+    budget: &'b RefCell<Budget<'a>>,
+    depth: usize,
+}
+
+impl NodeCounter<'_, '_> {
+    fn child(&self) -> Self {
+        NodeCounter {
+            budget: self.budget,
+            depth: self.depth + 1,
+        }
+    }
+
+    fn count<E: de::Error>(&self) -> Result<(), E> {
+        let mut budget = self.budget.borrow_mut();
+        budget.nodes += 1;
+
+        let message = if budget.nodes > budget.limits.max_nodes {
+            format!(
+                "document expands to more than {} nodes",
+                budget.limits.max_nodes
+            )
+        } else if self.depth > budget.limits.max_depth {
+            format!(
+                "document nests deeper than {} levels",
+                budget.limits.max_depth
+            )
+        } else {
+            return Ok(());
+        };
+        budget.exceeded = Some(message.clone());
+        Err(E::custom(message))
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for NodeCounter<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NodeCounter<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any YAML node")
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        self.count()?;
+        while seq.next_element_seed(self.child())?.is_some() {}
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        self.count()?;
+        while map.next_key_seed(self.child())?.is_some() {
+            map.next_value_seed(self.child())?;
+        }
+        Ok(())
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<(), A::Error> {
+        // Tagged values (`!tag value`) count as a single node plus their content
+        self.count()?;
+        let ((), variant) = data.variant_seed(self.child())?;
+        de::VariantAccess::newtype_variant_seed(variant, self.child())
+    }
+}
+
+/// Check a document against the limits without building a tree.
+///
+/// Returns a description of the first limit exceeded. Syntax errors are not
+/// reported here; the real parse that follows produces a better message.
+pub(crate) fn enforce_limits(content: &str, limits: &ParseLimits) -> Result<(), String> {
+    if content.len() > limits.max_bytes {
+        return Err(format!(
+            "input is {} bytes (limit {})",
+            content.len(),
+            limits.max_bytes
+        ));
+    }
+
+    if let Some((index, line)) = content
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.len() > limits.max_line_length)
+    {
+        return Err(format!(
+            "line {} is {} bytes long (limit {})",
+            index + 1,
+            line.len(),
+            limits.max_line_length
+        ));
+    }
+
+    let aliases = count_aliases(content);
+    if aliases > limits.max_aliases {
+        return Err(format!(
+            "document uses {} aliases (limit {})",
+            aliases, limits.max_aliases
+        ));
+    }
+
+    let budget = RefCell::new(Budget {
+        limits,
+        nodes: 0,
+        exceeded: None,
+    });
+    let counter = NodeCounter {
+        budget: &budget,
+        depth: 0,
+    };
+    let result = counter.deserialize(serde_yaml::Deserializer::from_str(content));
+
+    if let Some(message) = budget.into_inner().exceeded {
+        return Err(message);
+    }
+    // serde_yaml has fixed guards of its own that may trip before ours do
+    match result {
+        Err(e) if e.to_string().starts_with("repetition limit exceeded") => {
+            Err("document expands aliases beyond the parser's repetition limit".to_string())
+        }
+        Err(e) if e.to_string().starts_with("recursion limit exceeded") => {
+            Err("document nests deeper than the parser's recursion limit".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check the number of parsed entries against `max_items`.
+pub(crate) fn enforce_item_limit(count: usize, limits: &ParseLimits) -> Result<(), String> {
+    if count > limits.max_items {
+        return Err(format!(
+            "document has {} entries (limit {})",
+            count, limits.max_items
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAUGHS: &str = r#"
+a: &a ["lol","lol","lol","lol","lol","lol","lol","lol","lol"]
+b: &b [*a,*a,*a,*a,*a,*a,*a,*a,*a]
+c: &c [*b,*b,*b,*b,*b,*b,*b,*b,*b]
+d: &d [*c,*c,*c,*c,*c,*c,*c,*c,*c]
+e: &e [*d,*d,*d,*d,*d,*d,*d,*d,*d]
+f: &f [*e,*e,*e,*e,*e,*e,*e,*e,*e]
+g: &g [*f,*f,*f,*f,*f,*f,*f,*f,*f]
+"#;
+
+    // =========================================================================
+    // Alias Counting Tests
+    // =========================================================================
+
+    #[test]
+    fn test_count_aliases() {
+        assert_eq!(count_aliases("a: &x 1\nb: *x\nc: [*x, *x]\n"), 3);
+        assert_eq!(count_aliases(LAUGHS), 54);
+    }
+
+    #[test]
+    fn test_count_aliases_ignores_quotes_and_comments() {
+        assert_eq!(count_aliases("a: \"*not an alias\"\n"), 0);
+        assert_eq!(count_aliases("a: '*nor this'\n"), 0);
+        assert_eq!(count_aliases("a: 1 # *comment\nb: *x\n"), 1);
+        assert_eq!(count_aliases("a: b*c\n"), 0);
+        assert_eq!(count_aliases("a: * \n"), 0);
+    }
+
+    // =========================================================================
+    // Enforcement Tests
+    // =========================================================================
+
+    #[test]
+    fn test_byte_limit() {
+        let limits = ParseLimits {
+            max_bytes: 10,
+            ..ParseLimits::default()
+        };
+        assert!(enforce_limits("a: 1234567", &limits).is_ok());
+        let error = enforce_limits("a: 12345678", &limits).unwrap_err();
+        assert_eq!(error, "input is 11 bytes (limit 10)");
+    }
+
+    #[test]
+    fn test_default_byte_limit_rejects_multi_megabyte_input() {
+        let yaml = format!("a: [{}]", "1,".repeat(2 * 1024 * 1024));
+        let error = enforce_limits(&yaml, &ParseLimits::default()).unwrap_err();
+        assert!(error.starts_with("input is"), "{}", error);
+    }
+
+    #[test]
+    fn test_line_length_limit() {
+        let limits = ParseLimits {
+            max_line_length: 8,
+            ..ParseLimits::default()
+        };
+        assert!(enforce_limits("a: 1\nb: 2\n", &limits).is_ok());
+        let error = enforce_limits("a: 1\nnote: too long\n", &limits).unwrap_err();
+        assert_eq!(error, "line 2 is 14 bytes long (limit 8)");
+    }
+
+    #[test]
+    fn test_item_limit() {
+        let limits = ParseLimits {
+            max_items: 2,
+            ..ParseLimits::default()
+        };
+        assert!(enforce_item_limit(2, &limits).is_ok());
+        assert_eq!(
+            enforce_item_limit(3, &limits).unwrap_err(),
+            "document has 3 entries (limit 2)"
+        );
+    }
+
+    #[test]
+    fn test_default_limits_accept_normal_documents() {
+        let yaml =
+            "project: Demo\nworkflow_status:\n  prd: required\n  architecture: docs/arch.md\n";
+        assert!(enforce_limits(yaml, &ParseLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn test_billion_laughs_rejected() {
+        let error = enforce_limits(LAUGHS, &ParseLimits::default()).unwrap_err();
+        assert!(error.contains("repetition limit"), "{}", error);
+
+        // A tighter budget trips before the parser's own guard
+        let limits = ParseLimits {
+            max_nodes: 1_000,
+            ..ParseLimits::default()
+        };
+        let error = enforce_limits(LAUGHS, &limits).unwrap_err();
+        assert_eq!(error, "document expands to more than 1000 nodes");
+    }
+
+    #[test]
+    fn test_alias_limit() {
+        let limits = ParseLimits {
+            max_aliases: 2,
+            ..ParseLimits::default()
+        };
+        let error = enforce_limits("a: &x 1\nb: [*x, *x, *x]\n", &limits).unwrap_err();
+        assert_eq!(error, "document uses 3 aliases (limit 2)");
+    }
+
+    #[test]
+    fn test_node_limit_counts_expanded_aliases() {
+        let yaml = "a: &x [1, 2, 3]\nb: *x\n";
+        let limits = ParseLimits {
+            max_nodes: 10,
+            ..ParseLimits::default()
+        };
+        // root + a + [1,2,3] (4) + b + expanded [1,2,3] (4) = 11
+        assert!(enforce_limits(yaml, &limits).is_err());
+
+        let limits = ParseLimits {
+            max_nodes: 11,
+            ..ParseLimits::default()
+        };
+        assert!(enforce_limits(yaml, &limits).is_ok());
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let yaml = "a: [[[[1]]]]\n";
+        let limits = ParseLimits {
+            max_depth: 3,
+            ..ParseLimits::default()
+        };
+        let error = enforce_limits(yaml, &limits).unwrap_err();
+        assert_eq!(error, "document nests deeper than 3 levels");
+
+        let limits = ParseLimits {
+            max_depth: 5,
+            ..ParseLimits::default()
+        };
+        assert!(enforce_limits(yaml, &limits).is_ok());
+    }
+
+    #[test]
+    fn test_parser_recursion_limit_reported() {
+        let yaml = format!("a: {}{}", "[".repeat(200), "]".repeat(200));
+        let error = enforce_limits(&yaml, &ParseLimits::unlimited()).unwrap_err();
+        assert!(error.contains("recursion limit"), "{}", error);
+    }
+
+    #[test]
+    fn test_syntax_errors_are_left_to_the_parser() {
+        assert!(enforce_limits("a: [unclosed", &ParseLimits::default()).is_ok());
+        assert!(enforce_limits("", &ParseLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let limits = ParseLimits::unlimited();
+        assert!(enforce_limits("a: &x [[[1]]]\nb: [*x, *x, *x]\n", &limits).is_ok());
+    }
+}
@@ -0,0 +1,688 @@
+// clique-core/src/lint.rs
+//! Style and hygiene checks for status files.
+//!
+//! A [`Linter`] runs a registry of [`Rule`]s over a workflow or sprint file
+//! and collects what they find into a [`LintReport`]. Each rule has an ID
+//! such as `skipped-without-note` and a default level, which the `[lint]`
+//! table of `.clique.toml` can override (see [`crate::config`]):
+//!
+//! ```toml
+//! [lint]
+//! known-statuses = ["blocked"]
+//!
+//! [lint.rules]
+//! id-naming = "error"
+//! empty-epic = "off"
+//! ```
+//!
+//! Projects add their own checks by implementing [`Rule`] and registering
+//! it with [`Linter::with_rule`].
+
+use crate::diagnostics::{Severity, line_number};
+use crate::sprint::{SprintError, parse_sprint_status};
+use crate::types::{SprintData, StatusAliases, WorkflowData};
+use crate::workflow::{WorkflowError, parse_workflow_status};
+use crate::yaml_line::find_key;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+
+/// How a rule's findings are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    /// The rule does not run
+    Off,
+    Warning,
+    Error,
+}
+
+/// Settings for the linter, from the `[lint]` table of `.clique.toml`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// Level per rule ID, overriding the rule's default
+    pub rules: BTreeMap<String, RuleLevel>,
+    /// Statuses the project uses on purpose, never reported as typos and
+    /// suggested for near misses
+    pub known_statuses: Vec<String>,
+}
+
+/// The document a rule looks at
+pub struct LintContext<'a> {
+    /// The workflow, when linting a workflow file
+    pub workflow: Option<&'a WorkflowData>,
+    /// The sprint, when linting a sprint file
+    pub sprint: Option<&'a SprintData>,
+    /// Every `development_status` key with its status as written, in
+    /// document order, including entries the sprint parser leaves out
+    pub entries: &'a [(String, String)],
+    pub config: &'a LintConfig,
+}
+
+/// Something a rule found, before the linter attaches the rule and level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// The item, epic or story concerned, used to find its line
+    pub id: Option<String>,
+    pub message: String,
+}
+
+impl Lint {
+    pub fn new(id: &str, message: impl Into<String>) -> Self {
+        Self {
+            id: Some(id.to_string()),
+            message: message.into(),
+        }
+    }
+}
+
+/// A check run by the [`Linter`]
+pub trait Rule {
+    /// Kebab-case ID used in reports and configuration, e.g. `empty-epic`
+    fn id(&self) -> &'static str;
+    /// One line on what the rule checks
+    fn description(&self) -> &'static str;
+    /// Level used when the configuration does not name the rule
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Warning
+    }
+    fn check(&self, context: &LintContext<'_>) -> Vec<Lint>;
+}
+
+/// One finding in a [`LintReport`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFinding {
+    pub rule: String,
+    pub severity: Severity,
+    /// 1-based line of the entry concerned, or 0 when it has none
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub message: String,
+}
+
+/// Everything the linter found in one file, in rule order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Whether any finding is an error
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error)
+    }
+
+    /// Findings of one rule
+    pub fn by_rule(&self, rule: &str) -> Vec<&LintFinding> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.rule == rule)
+            .collect()
+    }
+}
+
+/// Runs registered rules over status files
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    config: LintConfig,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for Linter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Linter")
+            .field(
+                "rules",
+                &self.rules.iter().map(|r| r.id()).collect::<Vec<_>>(),
+            )
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl Linter {
+    /// A linter with the built-in rules at their default levels.
+    pub fn new() -> Self {
+        Self::empty()
+            .with_rule(IdNaming)
+            .with_rule(SkippedWithoutNote)
+            .with_rule(StoryWithoutEpic)
+            .with_rule(EmptyEpic)
+            .with_rule(StatusTypo)
+    }
+
+    /// A linter without any rules.
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            config: LintConfig::default(),
+        }
+    }
+
+    /// Register a rule, replacing any registered rule with the same ID.
+    pub fn register(&mut self, rule: impl Rule + 'static) {
+        self.rules.retain(|other| other.id() != rule.id());
+        self.rules.push(Box::new(rule));
+    }
+
+    /// Builder form of [`Linter::register`].
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.register(rule);
+        self
+    }
+
+    pub fn with_config(mut self, config: LintConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// ID, description and effective level of each registered rule
+    pub fn rules(&self) -> Vec<(&'static str, &'static str, RuleLevel)> {
+        self.rules
+            .iter()
+            .map(|rule| (rule.id(), rule.description(), self.level(rule.as_ref())))
+            .collect()
+    }
+
+    fn level(&self, rule: &dyn Rule) -> RuleLevel {
+        self.config
+            .rules
+            .get(rule.id())
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+
+    fn run(&self, content: &str, context: &LintContext<'_>, sections: &[&str]) -> LintReport {
+        let line_of = |id: &str| {
+            sections
+                .iter()
+                .find_map(|section| find_key(content, &[section, id]))
+                .map_or(0, |span| line_number(content, span.start))
+        };
+        let mut findings = Vec::new();
+        for rule in &self.rules {
+            let severity = match self.level(rule.as_ref()) {
+                RuleLevel::Off => continue,
+                RuleLevel::Warning => Severity::Warning,
+                RuleLevel::Error => Severity::Error,
+            };
+            for lint in rule.check(context) {
+                findings.push(LintFinding {
+                    rule: rule.id().to_string(),
+                    severity,
+                    line: lint.id.as_deref().map_or(0, line_of),
+                    id: lint.id,
+                    message: lint.message,
+                });
+            }
+        }
+        LintReport { findings }
+    }
+
+    /// Lint a workflow status file.
+    pub fn lint_workflow(&self, content: &str) -> Result<LintReport, WorkflowError> {
+        let data = parse_workflow_status(content)?;
+        let context = LintContext {
+            workflow: Some(&data),
+            sprint: None,
+            entries: &[],
+            config: &self.config,
+        };
+        Ok(self.run(content, &context, &["workflows", "workflow_status"]))
+    }
+
+    /// Lint a sprint status file.
+    pub fn lint_sprint(&self, content: &str) -> Result<LintReport, SprintError> {
+        let data = parse_sprint_status(content)?;
+        let parsed: Value =
+            serde_yaml::from_str(content).map_err(|e| SprintError::ParseError(e.to_string()))?;
+        let entries: Vec<(String, String)> = parsed
+            .get("development_status")
+            .and_then(Value::as_mapping)
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, value)| {
+                let status = match value {
+                    Value::Mapping(_) => value.get("status")?.as_str()?,
+                    _ => value.as_str()?,
+                };
+                Some((key.as_str()?.to_string(), status.to_string()))
+            })
+            .collect();
+        let context = LintContext {
+            workflow: None,
+            sprint: Some(&data),
+            entries: &entries,
+            config: &self.config,
+        };
+        Ok(self.run(content, &context, &["development_status"]))
+    }
+}
+
+// =============================================================================
+// Built-in rules
+// =============================================================================
+
+/// Epic number of an `epic-N` key
+fn epic_key_number(id: &str) -> Option<&str> {
+    id.strip_prefix("epic-")
+        .filter(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Epic number prefix of an `N-...` story key
+fn story_key_epic(id: &str) -> Option<&str> {
+    let (number, _) = id.split_once('-')?;
+    (!number.is_empty() && number.bytes().all(|b| b.is_ascii_digit())).then_some(number)
+}
+
+/// Lowercase ASCII words joined by single `separators`
+fn is_word_list(id: &str, separators: &[char]) -> bool {
+    !id.is_empty()
+        && id.split(separators).all(|word| {
+            !word.is_empty()
+                && word
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+}
+
+/// Story and epic keys should read `epic-N` and `N-M-slug`, workflow item
+/// IDs lowercase words joined by `-` or `_`
+struct IdNaming;
+
+impl Rule for IdNaming {
+    fn id(&self) -> &'static str {
+        "id-naming"
+    }
+
+    fn description(&self) -> &'static str {
+        "IDs follow the naming conventions (epic-N, N-M-slug, lowercase item IDs)"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        for item in context.workflow.iter().flat_map(|w| &w.items) {
+            if !is_word_list(&item.id, &['-', '_']) {
+                lints.push(Lint::new(
+                    &item.id,
+                    format!(
+                        "Item ID `{}` should be lowercase words joined by - or _",
+                        item.id
+                    ),
+                ));
+            }
+        }
+        for (id, _) in context.entries {
+            if epic_key_number(id).is_some() || id.contains("retrospective") {
+                continue;
+            }
+            if story_key_epic(id).is_none() || !is_word_list(id, &['-']) {
+                lints.push(Lint::new(
+                    id,
+                    format!("Story key `{}` should read N-M-slug in lowercase", id),
+                ));
+            }
+        }
+        lints
+    }
+}
+
+/// Skipped workflow items should say why
+struct SkippedWithoutNote;
+
+impl Rule for SkippedWithoutNote {
+    fn id(&self) -> &'static str {
+        "skipped-without-note"
+    }
+
+    fn description(&self) -> &'static str {
+        "Skipped workflow items have a note saying why"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Lint> {
+        context
+            .workflow
+            .iter()
+            .flat_map(|w| &w.items)
+            .filter(|item| {
+                item.status == "skipped" && item.note.as_deref().is_none_or(|n| n.trim().is_empty())
+            })
+            .map(|item| Lint::new(&item.id, format!("`{}` is skipped without a note", item.id)))
+            .collect()
+    }
+}
+
+/// Stories whose `epic-N` entry is missing; the parser drops them
+struct StoryWithoutEpic;
+
+impl Rule for StoryWithoutEpic {
+    fn id(&self) -> &'static str {
+        "story-without-epic"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every story's epic-N entry exists"
+    }
+
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Error
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Lint> {
+        let epics: Vec<&str> = context
+            .entries
+            .iter()
+            .filter_map(|(id, _)| epic_key_number(id))
+            .collect();
+        context
+            .entries
+            .iter()
+            .filter(|(id, _)| epic_key_number(id).is_none() && !id.contains("retrospective"))
+            .filter_map(|(id, _)| Some((id, story_key_epic(id)?)))
+            .filter(|(_, epic)| !epics.contains(epic))
+            .map(|(id, epic)| {
+                Lint::new(
+                    id,
+                    format!(
+                        "`{}` belongs to epic-{}, which is not listed; it is ignored",
+                        id, epic
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Epics without any story
+struct EmptyEpic;
+
+impl Rule for EmptyEpic {
+    fn id(&self) -> &'static str {
+        "empty-epic"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every epic has at least one story"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Lint> {
+        context
+            .sprint
+            .iter()
+            .flat_map(|s| &s.epics)
+            .filter(|epic| epic.stories.is_empty())
+            .map(|epic| Lint::new(&epic.id, format!("`{}` has no stories", epic.id)))
+            .collect()
+    }
+}
+
+/// Statuses the built-in rules accept without question
+const WORKFLOW_STATUSES: &[&str] = &[
+    "required",
+    "optional",
+    "recommended",
+    "conditional",
+    "skipped",
+    "not_started",
+    "complete",
+    "completed",
+    "done",
+];
+const SPRINT_STATUSES: &[&str] = &[
+    "backlog",
+    "drafted",
+    "contexted",
+    "ready-for-dev",
+    "in-progress",
+    "review",
+    "done",
+    "optional",
+    "completed",
+];
+
+/// Levenshtein distance between `a` and `b`, by characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Unknown statuses within two edits of a known one, e.g. `in-progres`
+struct StatusTypo;
+
+impl StatusTypo {
+    /// The known status `status` probably meant to be, if any
+    fn suggestion(status: &str, known: &[&str], context: &LintContext<'_>) -> Option<String> {
+        let aliases = StatusAliases::default();
+        let custom = &context.config.known_statuses;
+        let is_known = |status: &str| {
+            known.contains(&status)
+                || known.contains(&aliases.normalize(status).as_str())
+                || custom
+                    .iter()
+                    .any(|other| other.eq_ignore_ascii_case(status))
+        };
+        // Paths and empty values are not statuses to correct
+        if status.is_empty() || status.contains(['/', '.']) || is_known(status) {
+            return None;
+        }
+        let lower = status.to_ascii_lowercase();
+        known
+            .iter()
+            .copied()
+            .chain(custom.iter().map(String::as_str))
+            .map(|candidate| {
+                (
+                    edit_distance(&lower, &candidate.to_ascii_lowercase()),
+                    candidate,
+                )
+            })
+            .filter(|(distance, _)| *distance <= 2)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, candidate)| candidate.to_string())
+    }
+}
+
+impl Rule for StatusTypo {
+    fn id(&self) -> &'static str {
+        "status-typo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Statuses are not misspellings of known ones"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Lint> {
+        let workflow = context
+            .workflow
+            .iter()
+            .flat_map(|w| &w.items)
+            .map(|item| (item.id.as_str(), item.status.as_str(), WORKFLOW_STATUSES));
+        let sprint = context
+            .entries
+            .iter()
+            .map(|(id, status)| (id.as_str(), status.as_str(), SPRINT_STATUSES));
+        workflow
+            .chain(sprint)
+            .filter_map(|(id, status, known)| {
+                let suggestion = Self::suggestion(status, known, context)?;
+                Some(Lint::new(
+                    id,
+                    format!(
+                        "Unknown status `{}` on `{}`; did you mean `{}`?",
+                        status, id, suggestion
+                    ),
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPRINT: &str = "development_status:\n  epic-1: in-progress\n  1-1-login: in-progres\n  \
+                          1-2-Signup: done\n  epic-2: backlog\n  3-1-orphan: backlog\n  \
+                          epic-1-retrospective: optional\n  1-3-blocked: blocked\n";
+
+    const WORKFLOW: &str = "workflow_status:\n  prd: docs/prd.md\n  Research: skipped\n  \
+                            ux_design: optinal\n  brainstorm: compelte\n";
+
+    fn rules(report: &LintReport) -> Vec<(&str, &str, usize)> {
+        report
+            .findings
+            .iter()
+            .map(|f| (f.rule.as_str(), f.id.as_deref().unwrap_or_default(), f.line))
+            .collect()
+    }
+
+    // =========================================================================
+    // Sprint Tests
+    // =========================================================================
+
+    #[test]
+    fn test_lint_sprint() {
+        let report = Linter::new().lint_sprint(SPRINT).unwrap();
+        assert_eq!(
+            rules(&report),
+            [
+                ("id-naming", "1-2-Signup", 4),
+                ("story-without-epic", "3-1-orphan", 6),
+                ("empty-epic", "epic-2", 5),
+                ("status-typo", "1-1-login", 3),
+            ]
+        );
+        assert!(report.has_errors());
+        assert_eq!(
+            report.by_rule("story-without-epic")[0].severity,
+            Severity::Error
+        );
+        assert_eq!(
+            report.by_rule("status-typo")[0].message,
+            "Unknown status `in-progres` on `1-1-login`; did you mean `in-progress`?"
+        );
+    }
+
+    #[test]
+    fn test_lint_workflow() {
+        let report = Linter::new().lint_workflow(WORKFLOW).unwrap();
+        assert_eq!(
+            rules(&report),
+            [
+                ("id-naming", "Research", 3),
+                ("skipped-without-note", "Research", 3),
+                ("status-typo", "brainstorm", 5),
+                ("status-typo", "ux_design", 4),
+            ]
+        );
+        assert!(!report.has_errors());
+        assert_eq!(
+            report.by_rule("status-typo")[0].message,
+            "Unknown status `compelte` on `brainstorm`; did you mean `complete`?"
+        );
+    }
+
+    // =========================================================================
+    // Configuration Tests
+    // =========================================================================
+
+    #[test]
+    fn test_config_levels_and_known_statuses() {
+        let config = crate::config::parse(
+            "[lint]\nknown-statuses = [\"Blocked\"]\n\n[lint.rules]\nid-naming = \"error\"\n\
+             empty-epic = \"off\"\n",
+        )
+        .unwrap()
+        .lint;
+        assert_eq!(config.rules["id-naming"], RuleLevel::Error);
+        let linter = Linter::new().with_config(config);
+        let report = linter.lint_sprint(SPRINT).unwrap();
+        assert!(report.by_rule("empty-epic").is_empty());
+        assert_eq!(report.by_rule("id-naming")[0].severity, Severity::Error);
+        assert!(linter.rules().contains(&(
+            "empty-epic",
+            "Every epic has at least one story",
+            RuleLevel::Off
+        )));
+
+        assert!(crate::config::parse("[lint.rules]\nid-naming = \"loud\"\n").is_err());
+        assert!(crate::config::parse("[lint]\nrule = {}\n").is_err());
+    }
+
+    // =========================================================================
+    // Custom Rule Tests
+    // =========================================================================
+
+    struct NoReview;
+
+    impl Rule for NoReview {
+        fn id(&self) -> &'static str {
+            "no-review"
+        }
+
+        fn description(&self) -> &'static str {
+            "Nothing waits in review"
+        }
+
+        fn default_level(&self) -> RuleLevel {
+            RuleLevel::Error
+        }
+
+        fn check(&self, context: &LintContext<'_>) -> Vec<Lint> {
+            context
+                .entries
+                .iter()
+                .filter(|(_, status)| status == "review")
+                .map(|(id, _)| Lint::new(id, "In review"))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let linter = Linter::empty().with_rule(NoReview);
+        let report = linter
+            .lint_sprint("development_status:\n  epic-1: in-progress\n  1-1-a: review\n")
+            .unwrap();
+        assert_eq!(rules(&report), [("no-review", "1-1-a", 3)]);
+        assert_eq!(linter.rules().len(), 1);
+        assert!(
+            Linter::empty()
+                .lint_sprint("development_status: [")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("in-progres", "in-progress"), 1);
+        assert_eq!(edit_distance("revew", "review"), 1);
+        assert_eq!(edit_distance("", "done"), 4);
+        assert_eq!(edit_distance("done", "done"), 0);
+    }
+}
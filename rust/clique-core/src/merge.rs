@@ -0,0 +1,227 @@
+// clique-core/src/merge.rs
+//! Three-way merge of sprint status files.
+//!
+//! Two branches that each move different stories merge cleanly in git only
+//! when the edited lines are far enough apart. This merge works per entry of
+//! `development_status` instead: `ours` is kept byte for byte, and each
+//! entry that only `theirs` changed is carried over with the same in-place
+//! edits the update functions use.
+
+use crate::sprint::{SprintError, entry_status, update_story_status};
+use crate::yaml_line::{find_block, line_ending, remove_field, splice};
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::HashMap;
+
+/// An entry both sides changed in different ways; `ours` was kept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeConflict {
+    pub id: String,
+    /// Status in the common ancestor; `None` where the entry did not exist
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+/// Outcome of [`merge_sprint`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintMerge {
+    /// `ours` with every non-conflicting change from `theirs` applied
+    pub content: String,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl SprintMerge {
+    /// Whether the merge needs no manual resolution
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Statuses of the `development_status` entries, in document order
+fn statuses(content: &str) -> Result<Vec<(String, String)>, SprintError> {
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| SprintError::ParseError(e.to_string()))?;
+    Ok(parsed
+        .get("development_status")
+        .and_then(|v| v.as_mapping())
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| Some((key.as_str()?.to_string(), entry_status(value))))
+        .collect())
+}
+
+/// Copy the entry `id` (with any extended fields) from `theirs` to the end
+/// of `ours`' `development_status` block.
+fn append_entry(ours: &str, theirs: &str, id: &str) -> Option<String> {
+    let block = &theirs[find_block(theirs, &["development_status", id])?];
+    let end = find_block(ours, &["development_status"])?.end;
+    let eol = line_ending(ours);
+    let mut inserted = String::new();
+    for line in block.lines() {
+        inserted.push_str(eol);
+        inserted.push_str(line);
+    }
+    Some(splice(ours, end..end, &inserted))
+}
+
+/// Apply `theirs`' version of entry `id` to `content`
+fn take_theirs(
+    content: &str,
+    theirs_content: &str,
+    id: &str,
+    ours: Option<&str>,
+    theirs: Option<&str>,
+) -> Option<String> {
+    match theirs {
+        None => remove_field(content, &["development_status", id]),
+        Some(status) if ours.is_some() => update_story_status(content, id, status).ok(),
+        Some(_) => append_entry(content, theirs_content, id),
+    }
+}
+
+/// Merge the `development_status` entries of two sprint files that share
+/// the common ancestor `base`.
+///
+/// An entry is carried over from `theirs` when only `theirs` changed it:
+/// status updates, new entries (copied with their extended fields) and
+/// removals. Entries both sides changed differently are reported as
+/// conflicts and keep `ours`. Everything outside `development_status`, and
+/// fields other than the status, follow `ours`.
+pub fn merge_sprint(base: &str, ours: &str, theirs: &str) -> Result<SprintMerge, SprintError> {
+    let base_statuses: HashMap<String, String> = statuses(base)?.into_iter().collect();
+    let our_entries = statuses(ours)?;
+    let their_entries = statuses(theirs)?;
+    let our_statuses: HashMap<&str, &str> = our_entries
+        .iter()
+        .map(|(id, status)| (id.as_str(), status.as_str()))
+        .collect();
+    let their_statuses: HashMap<&str, &str> = their_entries
+        .iter()
+        .map(|(id, status)| (id.as_str(), status.as_str()))
+        .collect();
+
+    // Ours first, then entries only theirs has, each in document order
+    let ids = our_entries.iter().chain(
+        their_entries
+            .iter()
+            .filter(|(id, _)| !our_statuses.contains_key(id.as_str())),
+    );
+
+    let mut content = ours.to_string();
+    let mut conflicts = Vec::new();
+    for (id, _) in ids {
+        let base_status = base_statuses.get(id).map(String::as_str);
+        let our_status = our_statuses.get(id.as_str()).copied();
+        let their_status = their_statuses.get(id.as_str()).copied();
+        if our_status == their_status || their_status == base_status {
+            continue;
+        }
+        let merged = if our_status == base_status {
+            take_theirs(&content, theirs, id, our_status, their_status)
+        } else {
+            None
+        };
+        match merged {
+            Some(merged) => content = merged,
+            None => conflicts.push(MergeConflict {
+                id: id.clone(),
+                base: base_status.map(str::to_string),
+                ours: our_status.map(str::to_string),
+                theirs: their_status.map(str::to_string),
+            }),
+        }
+    }
+
+    Ok(SprintMerge { content, conflicts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+
+    const BASE: &str = "project: Demo\ndevelopment_status:\n  epic-1: in-progress\n  \
+                        1-a: backlog\n  1-b: backlog\n  1-c: backlog\n";
+
+    // =========================================================================
+    // Merge Tests
+    // =========================================================================
+
+    #[test]
+    fn test_merge_disjoint_status_changes() {
+        let ours = BASE.replace("1-a: backlog", "1-a: done");
+        let theirs = BASE.replace("1-b: backlog", "1-b: review");
+        let merged = merge_sprint(BASE, &ours, &theirs).unwrap();
+        assert!(merged.is_clean());
+        assert_eq!(
+            merged.content,
+            BASE.replace("1-a: backlog", "1-a: done")
+                .replace("1-b: backlog", "1-b: review")
+        );
+    }
+
+    #[test]
+    fn test_merge_same_change_is_not_a_conflict() {
+        let both = BASE.replace("1-c: backlog", "1-c: done");
+        let merged = merge_sprint(BASE, &both, &both).unwrap();
+        assert!(merged.is_clean());
+        assert_eq!(merged.content, both);
+    }
+
+    #[test]
+    fn test_merge_conflict_keeps_ours() {
+        let ours = BASE.replace("1-a: backlog", "1-a: done");
+        let theirs = BASE.replace("1-a: backlog", "1-a: review");
+        let merged = merge_sprint(BASE, &ours, &theirs).unwrap();
+        assert_eq!(merged.content, ours);
+        assert_eq!(
+            merged.conflicts,
+            vec![MergeConflict {
+                id: "1-a".to_string(),
+                base: Some("backlog".to_string()),
+                ours: Some("done".to_string()),
+                theirs: Some("review".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_merge_additions_and_removals() {
+        let ours = BASE.replace("1-a: backlog", "1-a: done");
+        let theirs = format!(
+            "{}  epic-2: backlog\n  2-x:\n    status: ready-for-dev\n    assignee: sam\n",
+            BASE.replace("  1-c: backlog\n", "")
+        );
+        let merged = merge_sprint(BASE, &ours, &theirs).unwrap();
+        assert!(merged.is_clean());
+        assert_eq!(
+            merged.content,
+            "project: Demo\ndevelopment_status:\n  epic-1: in-progress\n  1-a: done\n  \
+             1-b: backlog\n  epic-2: backlog\n  2-x:\n    status: ready-for-dev\n    \
+             assignee: sam\n"
+        );
+        let data = parse_sprint_status(&merged.content).unwrap();
+        assert_eq!(data.epics[1].stories[0].assignee.as_deref(), Some("sam"));
+    }
+
+    #[test]
+    fn test_merge_removed_on_one_side_and_changed_on_other() {
+        let ours = BASE.replace("  1-c: backlog\n", "");
+        let theirs = BASE.replace("1-c: backlog", "1-c: done");
+        let merged = merge_sprint(BASE, &ours, &theirs).unwrap();
+        assert_eq!(merged.content, ours);
+        assert_eq!(merged.conflicts[0].ours, None);
+        assert_eq!(merged.conflicts[0].theirs.as_deref(), Some("done"));
+    }
+
+    #[test]
+    fn test_merge_invalid_yaml() {
+        assert!(matches!(
+            merge_sprint(BASE, "development_status: [oops", BASE),
+            Err(SprintError::ParseError(_))
+        ));
+    }
+}
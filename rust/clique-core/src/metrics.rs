@@ -0,0 +1,499 @@
+// clique-core/src/metrics.rs
+//! Cycle-time and lead-time metrics computed from the history log.
+//!
+//! Lead time runs from a story's first recorded entry to when it first
+//! reached `done`; cycle time from when it first went `in-progress` to the
+//! same point. Statuses are compared after alias normalization, and entries
+//! whose timestamp cannot be read are ignored.
+//!
+//! [`to_prometheus`] renders the current state of the status files as
+//! gauges in the Prometheus text exposition format.
+
+use crate::history::HistoryEntry;
+use crate::sprint::is_done;
+use crate::types::{ItemState, SprintData, StatusAliases, WorkflowData, parse_timestamp};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// How long one finished story took
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycleTime {
+    pub id: String,
+    /// Epic derived from the story's number prefix, e.g. `epic-1`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epic_id: Option<String>,
+    /// Seconds from the first entry to done
+    pub lead_secs: i64,
+    /// Seconds from first going in-progress to done; `None` if it never did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle_secs: Option<i64>,
+}
+
+/// Distribution of a set of durations, in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub count: usize,
+    pub median_secs: i64,
+    pub p90_secs: i64,
+}
+
+/// Time spent in `from` before moving to `to`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitionTime {
+    pub from: String,
+    pub to: String,
+    pub summary: Summary,
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[i64], p: f64) -> i64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Median and 90th percentile (nearest-rank) of `durations`; `None` when
+/// there are none.
+pub fn summarize(durations: impl IntoIterator<Item = i64>) -> Option<Summary> {
+    let mut sorted: Vec<i64> = durations.into_iter().collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_unstable();
+    Some(Summary {
+        count: sorted.len(),
+        median_secs: percentile(&sorted, 0.5),
+        p90_secs: percentile(&sorted, 0.9),
+    })
+}
+
+/// Entries per ID as (seconds, normalized status), oldest first
+fn timelines(history: &[HistoryEntry]) -> Vec<(&str, Vec<(i64, String)>)> {
+    let aliases = StatusAliases::default();
+    let mut order = Vec::new();
+    let mut by_id: HashMap<&str, Vec<(i64, String)>> = HashMap::new();
+    for entry in history {
+        let Some(secs) = parse_timestamp(&entry.timestamp) else {
+            continue;
+        };
+        let timeline = by_id.entry(entry.id.as_str()).or_insert_with(|| {
+            order.push(entry.id.as_str());
+            Vec::new()
+        });
+        timeline.push((secs, aliases.normalize(&entry.to)));
+    }
+    order
+        .into_iter()
+        .map(|id| {
+            let mut timeline = by_id.remove(id).unwrap_or_default();
+            timeline.sort_by_key(|(secs, _)| *secs);
+            (id, timeline)
+        })
+        .collect()
+}
+
+/// `epic-N` for a story ID starting with `N-`
+fn epic_of(id: &str) -> Option<String> {
+    let (number, _) = id.split_once('-')?;
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        .then(|| format!("epic-{}", number))
+}
+
+/// Lead and cycle time of every ID that reached `done`, ordered by when it
+/// got there.
+pub fn cycle_times(history: &[HistoryEntry]) -> Vec<CycleTime> {
+    let mut finished: Vec<(i64, CycleTime)> = timelines(history)
+        .into_iter()
+        .filter_map(|(id, timeline)| {
+            let (first, _) = timeline.first()?;
+            let (done, _) = timeline.iter().find(|(_, status)| status == "done")?;
+            let started = timeline
+                .iter()
+                .find(|(secs, status)| status == "in-progress" && secs <= done);
+            Some((
+                *done,
+                CycleTime {
+                    id: id.to_string(),
+                    epic_id: epic_of(id),
+                    lead_secs: done - first,
+                    cycle_secs: started.map(|(secs, _)| done - secs),
+                },
+            ))
+        })
+        .collect();
+    finished.sort_by_key(|(done, _)| *done);
+    finished.into_iter().map(|(_, time)| time).collect()
+}
+
+/// Cycle-time distribution per epic. Stories without an epic prefix or
+/// without a cycle time are left out.
+pub fn cycle_time_by_epic(times: &[CycleTime]) -> BTreeMap<String, Summary> {
+    let mut by_epic: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+    for time in times {
+        if let (Some(epic), Some(secs)) = (&time.epic_id, time.cycle_secs) {
+            by_epic.entry(epic.clone()).or_default().push(secs);
+        }
+    }
+    by_epic
+        .into_iter()
+        .filter_map(|(epic, durations)| Some((epic, summarize(durations)?)))
+        .collect()
+}
+
+/// How long IDs stay in a status before each kind of transition out of
+/// it, ordered by `from` then `to`. Answers questions such as how long
+/// stories sit in review before being accepted.
+pub fn transition_times(history: &[HistoryEntry]) -> Vec<TransitionTime> {
+    let mut durations: BTreeMap<(String, String), Vec<i64>> = BTreeMap::new();
+    for (_, timeline) in timelines(history) {
+        for pair in timeline.windows(2) {
+            let ((start, from), (end, to)) = (&pair[0], &pair[1]);
+            if from != to {
+                durations
+                    .entry((from.clone(), to.clone()))
+                    .or_default()
+                    .push(end - start);
+            }
+        }
+    }
+    durations
+        .into_iter()
+        .filter_map(|((from, to), secs)| {
+            Some(TransitionTime {
+                from,
+                to,
+                summary: summarize(secs)?,
+            })
+        })
+        .collect()
+}
+
+/// Workflow item states in exposition order, with their label values
+const ITEM_STATES: [(ItemState, &str); 6] = [
+    (ItemState::Done, "done"),
+    (ItemState::Actionable, "actionable"),
+    (ItemState::Blocked, "blocked"),
+    (ItemState::Skipped, "skipped"),
+    (ItemState::Optional, "optional"),
+    (ItemState::NotApplicable, "not-applicable"),
+];
+
+/// A label value with `\`, `"` and line breaks escaped
+fn label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// The `# HELP` and `# TYPE` lines of a gauge
+fn gauge_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+}
+
+/// Fraction of `total` that is `done`; 0 for an empty set
+fn ratio(done: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        done as f64 / total as f64
+    }
+}
+
+/// Progress gauges in the Prometheus text exposition format, ready for a
+/// Pushgateway or a node-exporter textfile.
+///
+/// Workflow items are counted per [`ItemState`], every state included so
+/// series don't disappear when they reach zero; stories per status as
+/// written, with done and blocked counts and a progress ratio for the
+/// sprint and for each epic. Project names are left to the Pushgateway's
+/// grouping key or the scrape's labels.
+pub fn to_prometheus(workflow: &WorkflowData, sprint: &SprintData) -> String {
+    let mut out = String::new();
+
+    let mut states: HashMap<ItemState, usize> = HashMap::new();
+    for (_, state) in workflow.item_states() {
+        *states.entry(state).or_default() += 1;
+    }
+    gauge_header(
+        &mut out,
+        "clique_workflow_items_total",
+        "Workflow items by state.",
+    );
+    for (state, label) in ITEM_STATES {
+        let count = states.get(&state).copied().unwrap_or(0);
+        let _ = writeln!(
+            out,
+            "clique_workflow_items_total{{state=\"{}\"}} {}",
+            label, count
+        );
+    }
+    let applicable = workflow.items.len() - states.get(&ItemState::NotApplicable).unwrap_or(&0);
+    let done = states.get(&ItemState::Done).copied().unwrap_or(0);
+    gauge_header(
+        &mut out,
+        "clique_workflow_progress_ratio",
+        "Fraction of applicable workflow items that are done.",
+    );
+    let _ = writeln!(
+        out,
+        "clique_workflow_progress_ratio {}",
+        ratio(done, applicable)
+    );
+
+    let stories: Vec<_> = sprint.epics.iter().flat_map(|epic| &epic.stories).collect();
+    let mut by_status: BTreeMap<&str, usize> = BTreeMap::new();
+    for story in &stories {
+        *by_status.entry(story.status.as_str()).or_default() += 1;
+    }
+    gauge_header(&mut out, "clique_stories_total", "Stories by status.");
+    for (status, count) in by_status {
+        let _ = writeln!(
+            out,
+            "clique_stories_total{{status=\"{}\"}} {}",
+            label_value(status),
+            count
+        );
+    }
+    let done = stories.iter().filter(|story| is_done(story)).count();
+    gauge_header(&mut out, "clique_stories_done", "Stories that are done.");
+    let _ = writeln!(out, "clique_stories_done {}", done);
+    gauge_header(
+        &mut out,
+        "clique_stories_blocked",
+        "Stories waiting on an unfinished blocker.",
+    );
+    let _ = writeln!(
+        out,
+        "clique_stories_blocked {}",
+        sprint.blocked_stories().len()
+    );
+    gauge_header(
+        &mut out,
+        "clique_sprint_progress_ratio",
+        "Fraction of stories that are done.",
+    );
+    let _ = writeln!(
+        out,
+        "clique_sprint_progress_ratio {}",
+        ratio(done, stories.len())
+    );
+
+    gauge_header(
+        &mut out,
+        "clique_epic_progress_ratio",
+        "Fraction of an epic's stories that are done.",
+    );
+    for epic in &sprint.epics {
+        let done = epic.stories.iter().filter(|story| is_done(story)).count();
+        let _ = writeln!(
+            out,
+            "clique_epic_progress_ratio{{epic=\"{}\"}} {}",
+            label_value(&epic.id),
+            ratio(done, epic.stories.len())
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history;
+
+    const HOUR: i64 = 3600;
+
+    const HISTORY: &str = r#"
+- timestamp: 2025-03-01T09:00:00Z
+  id: 1-1-login
+  to: ready-for-dev
+- timestamp: 2025-03-01T10:00:00Z
+  id: 1-1-login
+  from: ready-for-dev
+  to: in-progress
+- timestamp: 2025-03-01T09:00:00Z
+  id: 1-2-signup
+  to: In Progress
+- timestamp: 2025-03-01T14:00:00Z
+  id: 1-1-login
+  from: in-progress
+  to: review
+- timestamp: 2025-03-01T12:00:00Z
+  id: 1-2-signup
+  from: in-progress
+  to: review
+- timestamp: 2025-03-01T16:00:00Z
+  id: 1-1-login
+  from: review
+  to: done
+- timestamp: 2025-03-02T12:00:00Z
+  id: 1-2-signup
+  from: review
+  to: completed
+- timestamp: 2025-03-01T09:00:00Z
+  id: 2-1-export
+  to: in-progress
+- timestamp: 2025-03-01T11:00:00+01:00
+  id: 2-1-export
+  from: in-progress
+  to: review
+- timestamp: someday
+  id: 2-1-export
+  to: done
+- timestamp: 2025-03-01T08:00:00Z
+  id: prd
+  to: complete
+"#;
+
+    fn history() -> Vec<HistoryEntry> {
+        history::parse(HISTORY).unwrap()
+    }
+
+    // =========================================================================
+    // Cycle Time Tests
+    // =========================================================================
+
+    #[test]
+    fn test_cycle_times() {
+        let times = cycle_times(&history());
+        let ids: Vec<&str> = times.iter().map(|t| t.id.as_str()).collect();
+        // Ordered by completion; 2-1-export never validly reached done
+        assert_eq!(ids, vec!["prd", "1-1-login", "1-2-signup"]);
+
+        let login = &times[1];
+        assert_eq!(login.epic_id.as_deref(), Some("epic-1"));
+        assert_eq!(login.lead_secs, 7 * HOUR);
+        assert_eq!(login.cycle_secs, Some(6 * HOUR));
+
+        // Normalized statuses: "In Progress" and "completed"
+        let signup = &times[2];
+        assert_eq!(signup.lead_secs, 27 * HOUR);
+        assert_eq!(signup.cycle_secs, Some(27 * HOUR));
+
+        let prd = &times[0];
+        assert_eq!(prd.epic_id, None);
+        assert_eq!(prd.lead_secs, 0);
+        assert_eq!(prd.cycle_secs, None);
+    }
+
+    #[test]
+    fn test_cycle_time_by_epic() {
+        let by_epic = cycle_time_by_epic(&cycle_times(&history()));
+        assert_eq!(by_epic.keys().collect::<Vec<_>>(), vec!["epic-1"]);
+        let summary = by_epic["epic-1"];
+        assert_eq!(summary.count, 2);
+        assert_eq!(summary.median_secs, 6 * HOUR);
+        assert_eq!(summary.p90_secs, 27 * HOUR);
+    }
+
+    // =========================================================================
+    // Transition Tests
+    // =========================================================================
+
+    #[test]
+    fn test_transition_times() {
+        let transitions = transition_times(&history());
+        let keys: Vec<(&str, &str)> = transitions
+            .iter()
+            .map(|t| (t.from.as_str(), t.to.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("in-progress", "review"),
+                ("ready-for-dev", "in-progress"),
+                ("review", "done"),
+            ]
+        );
+
+        let review = &transitions[2].summary;
+        assert_eq!(review.count, 2);
+        assert_eq!(review.median_secs, 2 * HOUR);
+        assert_eq!(review.p90_secs, 24 * HOUR);
+
+        // 2-1-export moved to review one hour later, with the offset applied
+        let in_progress = &transitions[0].summary;
+        assert_eq!(in_progress.count, 3);
+        assert_eq!(in_progress.median_secs, 3 * HOUR);
+    }
+
+    // =========================================================================
+    // Prometheus Tests
+    // =========================================================================
+
+    #[test]
+    fn test_to_prometheus() {
+        let workflow = crate::workflow::parse_workflow_status(
+            "workflow_status:\n  prd: docs/prd.md\n  architecture: required\n  \
+             ux-design: optional\n  research: skipped\n",
+        )
+        .unwrap();
+        let sprint = crate::sprint::parse_sprint_status(
+            "development_status:\n  epic-1: in-progress\n  1-1-login: done\n  \
+             1-2-signup:\n    status: review\n    blocked_by: [1-3-oauth]\n  \
+             1-3-oauth: in-progress\n  epic-2: backlog\n  2-1-export: backlog\n",
+        )
+        .unwrap();
+        let text = to_prometheus(&workflow, &sprint);
+        for line in [
+            "# TYPE clique_workflow_items_total gauge",
+            "clique_workflow_items_total{state=\"done\"} 1",
+            "clique_workflow_items_total{state=\"actionable\"} 1",
+            "clique_workflow_items_total{state=\"skipped\"} 1",
+            "clique_workflow_items_total{state=\"not-applicable\"} 0",
+            "clique_workflow_progress_ratio 0.25",
+            "clique_stories_total{status=\"backlog\"} 1",
+            "clique_stories_total{status=\"done\"} 1",
+            "clique_stories_done 1",
+            "clique_stories_blocked 1",
+            "clique_sprint_progress_ratio 0.25",
+            "clique_epic_progress_ratio{epic=\"epic-1\"} 0.3333333333333333",
+            "clique_epic_progress_ratio{epic=\"epic-2\"} 0",
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "missing {:?} in\n{}",
+                line,
+                text
+            );
+        }
+        assert!(text.ends_with('\n'));
+        assert_eq!(label_value("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    // =========================================================================
+    // Summary Tests
+    // =========================================================================
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(summarize(Vec::new()), None);
+        let one = summarize([5]).unwrap();
+        assert_eq!((one.count, one.median_secs, one.p90_secs), (1, 5, 5));
+
+        let summary = summarize((1..=10).rev()).unwrap();
+        assert_eq!(summary.count, 10);
+        assert_eq!(summary.median_secs, 5);
+        assert_eq!(summary.p90_secs, 9);
+    }
+
+    #[test]
+    fn test_metrics_serialization() {
+        let json = serde_json::to_string(&cycle_times(&history())[0]).unwrap();
+        assert_eq!(json, r#"{"id":"prd","leadSecs":0}"#);
+        let json = serde_json::to_string(&summarize([60]).unwrap()).unwrap();
+        assert_eq!(json, r#"{"count":1,"medianSecs":60,"p90Secs":60}"#);
+    }
+
+    #[test]
+    fn test_epic_of() {
+        assert_eq!(epic_of("12-3-story").as_deref(), Some("epic-12"));
+        assert_eq!(epic_of("prd"), None);
+        assert_eq!(epic_of("tech-spec"), None);
+        assert_eq!(epic_of("-1-x"), None);
+    }
+}
@@ -0,0 +1,129 @@
+// clique-core/src/options.rs
+//! Options controlling how status files are parsed and edited.
+
+use crate::limits::ParseLimits;
+use crate::preprocess::Preprocessing;
+use crate::types::{StatusAliases, StatusRegistry, format_utc_timestamp};
+
+/// Options accepted by the `*_with_options` parsers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Resource limits checked before parsing
+    pub limits: ParseLimits,
+    /// Map status synonyms to canonical values; `None` keeps statuses as written
+    pub normalize: Option<StatusAliases>,
+    /// Custom statuses to tag items and stories with
+    pub registry: Option<StatusRegistry>,
+    /// Interpolate variables and merge includes before parsing; `None`
+    /// parses the content as written
+    pub preprocessing: Option<Preprocessing>,
+}
+
+impl ParseOptions {
+    /// Use the given resource limits.
+    pub fn with_limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Normalize statuses through the given alias table.
+    pub fn with_normalize(mut self, aliases: StatusAliases) -> Self {
+        self.normalize = Some(aliases);
+        self
+    }
+
+    /// Tag unrecognized statuses against the given registry.
+    pub fn with_registry(mut self, registry: StatusRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Run the content through the given preprocessing first.
+    pub fn with_preprocessing(mut self, preprocessing: Preprocessing) -> Self {
+        self.preprocessing = Some(preprocessing);
+        self
+    }
+}
+
+/// Options accepted by the `*_with_options` editors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateOptions {
+    /// Refresh the `updated:` timestamp of edited entries written in the
+    /// extended block form
+    pub touch_timestamps: bool,
+    /// Timestamp to write; the current UTC time when `None`. Must be set on
+    /// wasm32, which has no system clock.
+    pub timestamp: Option<String>,
+}
+
+impl UpdateOptions {
+    /// Refresh `updated:` timestamps on edit.
+    pub fn with_touch_timestamps(mut self) -> Self {
+        self.touch_timestamps = true;
+        self
+    }
+
+    /// Write the given timestamp instead of reading the clock.
+    pub fn with_timestamp(mut self, timestamp: &str) -> Self {
+        self.timestamp = Some(timestamp.to_string());
+        self
+    }
+
+    /// The timestamp to write, reading the clock if none was given.
+    pub(crate) fn timestamp(&self) -> String {
+        self.timestamp.clone().unwrap_or_else(|| {
+            let secs = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            format_utc_timestamp(secs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_options() {
+        let options = ParseOptions::default();
+        assert_eq!(options.limits, ParseLimits::default());
+        assert!(options.normalize.is_none());
+        assert!(options.registry.is_none());
+        assert!(options.preprocessing.is_none());
+    }
+
+    #[test]
+    fn test_builder() {
+        let options = ParseOptions::default()
+            .with_limits(ParseLimits::unlimited())
+            .with_normalize(StatusAliases::default())
+            .with_registry(StatusRegistry::new())
+            .with_preprocessing(Preprocessing::new());
+        assert_eq!(options.limits, ParseLimits::unlimited());
+        assert_eq!(options.normalize, Some(StatusAliases::default()));
+        assert_eq!(options.registry, Some(StatusRegistry::new()));
+        assert_eq!(options.preprocessing, Some(Preprocessing::new()));
+    }
+
+    #[test]
+    fn test_update_options_builder() {
+        let options = UpdateOptions::default();
+        assert!(!options.touch_timestamps);
+        assert!(options.timestamp.is_none());
+
+        let options = UpdateOptions::default()
+            .with_touch_timestamps()
+            .with_timestamp("2025-03-01T10:00:00Z");
+        assert!(options.touch_timestamps);
+        assert_eq!(options.timestamp(), "2025-03-01T10:00:00Z");
+    }
+
+    #[test]
+    fn test_update_options_reads_clock() {
+        let stamp = UpdateOptions::default().timestamp();
+        assert_eq!(stamp.len(), "2025-03-01T10:00:00Z".len());
+        assert!(stamp.ends_with('Z'));
+        assert!(crate::types::parse_date(&stamp).is_some());
+    }
+}
@@ -0,0 +1,160 @@
+// clique-core/src/outputs.rs
+//! Checks that completed workflow items actually produced their output files.
+
+use crate::types::{StatusClass, WorkflowData, WorkflowItem};
+use crate::validation::resolve_workspace_path;
+use crate::workflow::is_file_path;
+use serde::Serialize;
+use std::time::SystemTime;
+
+/// What is wrong with a completed item's output file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputIssueKind {
+    /// The file does not exist
+    Missing,
+    /// The file was last modified before the cutoff
+    Stale,
+    /// The path points outside the workspace and was not checked
+    OutsideWorkspace,
+}
+
+/// A completed item whose output file failed verification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputIssue {
+    pub item_id: String,
+    /// Output path as written in the status file
+    pub path: String,
+    pub kind: OutputIssueKind,
+}
+
+/// Output path of a completed item: `output_file`, or a path-like status
+fn output_path(item: &WorkflowItem) -> Option<&str> {
+    if item.status_class() != StatusClass::Complete {
+        return None;
+    }
+    item.output_file
+        .as_deref()
+        .or_else(|| is_file_path(&item.status).then_some(item.status.as_str()))
+}
+
+/// Report completed items whose output file is missing or outside the workspace.
+pub fn verify_outputs(data: &WorkflowData, workspace_root: &str) -> Vec<OutputIssue> {
+    verify_outputs_since(data, workspace_root, None)
+}
+
+/// Like [`verify_outputs`], also reporting output files last modified before
+/// `since`. Files whose modification time cannot be read are not stale.
+pub fn verify_outputs_since(
+    data: &WorkflowData,
+    workspace_root: &str,
+    since: Option<SystemTime>,
+) -> Vec<OutputIssue> {
+    data.items
+        .iter()
+        .filter_map(|item| {
+            let path = output_path(item)?;
+            let kind = match resolve_workspace_path(path, workspace_root) {
+                None => OutputIssueKind::OutsideWorkspace,
+                Some(resolved) => match std::fs::metadata(&resolved) {
+                    Err(_) => OutputIssueKind::Missing,
+                    Ok(meta) => {
+                        let modified = meta.modified().ok();
+                        match (since, modified) {
+                            (Some(cutoff), Some(modified)) if modified < cutoff => {
+                                OutputIssueKind::Stale
+                            }
+                            _ => return None,
+                        }
+                    }
+                },
+            };
+            Some(OutputIssue {
+                item_id: item.id.clone(),
+                path: path.to_string(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+    use crate::workflow::parse_workflow_status;
+    use std::time::Duration;
+
+    const STATUS_YAML: &str = r#"
+workflows:
+  brainstorm:
+    status: complete
+    output_file: docs/brainstorm.md
+  prd:
+    status: complete
+    output_file: docs/missing.md
+  architecture:
+    status: complete
+    output_file: ../outside.md
+  tech-spec:
+    status: complete
+  sprint-planning:
+    status: not_started
+    output_file: docs/missing.md
+"#;
+
+    /// A fresh workspace holding `docs/brainstorm.md`
+    fn workspace(name: &str) -> TempDir {
+        let dir = TempDir::new(&format!("outputs-{}", name));
+        std::fs::create_dir_all(dir.join("docs")).unwrap();
+        std::fs::write(dir.join("docs/brainstorm.md"), "# Brainstorm").unwrap();
+        dir
+    }
+
+    fn issues(found: &[OutputIssue]) -> Vec<(&str, OutputIssueKind)> {
+        found.iter().map(|i| (i.item_id.as_str(), i.kind)).collect()
+    }
+
+    #[test]
+    fn test_verify_outputs() {
+        let root = workspace("verify");
+        let data = parse_workflow_status(STATUS_YAML).unwrap();
+
+        let found = verify_outputs(&data, &root.to_string_lossy());
+        assert_eq!(
+            issues(&found),
+            vec![
+                ("prd", OutputIssueKind::Missing),
+                ("architecture", OutputIssueKind::OutsideWorkspace),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_outputs_since() {
+        let root = workspace("since");
+        let root_str = root.to_string_lossy().to_string();
+        let data = parse_workflow_status(
+            "workflows:\n  brainstorm:\n    status: complete\n    output_file: docs/brainstorm.md\n",
+        )
+        .unwrap();
+
+        let past = SystemTime::now() - Duration::from_secs(3600);
+        assert!(verify_outputs_since(&data, &root_str, Some(past)).is_empty());
+
+        let future = SystemTime::now() + Duration::from_secs(3600);
+        let found = verify_outputs_since(&data, &root_str, Some(future));
+        assert_eq!(issues(&found), vec![("brainstorm", OutputIssueKind::Stale)]);
+        assert_eq!(found[0].path, "docs/brainstorm.md");
+    }
+
+    #[test]
+    fn test_verify_outputs_flat_format_paths() {
+        let root = workspace("flat");
+        let yaml = "workflow_status:\n  brainstorm: docs/brainstorm.md\n  prd: docs/prd.md\n  ux: optional\n";
+        let data = parse_workflow_status(yaml).unwrap();
+        let found = verify_outputs(&data, &root.to_string_lossy());
+        assert_eq!(issues(&found), vec![("prd", OutputIssueKind::Missing)]);
+    }
+}
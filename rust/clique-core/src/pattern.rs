@@ -0,0 +1,324 @@
+// clique-core/src/pattern.rs
+//! Hand-written matchers for the ID and markdown patterns.
+//!
+//! The sprint, workflow, story document and epics.md parsers use these in
+//! place of a regex engine, which would be most of the size of slim builds
+//! such as the size-optimized WASM module. Numbers are runs of ASCII digits.
+
+use std::ops::Range;
+
+/// Number of an epic key: `epic-3` gives `3`
+pub(crate) fn epic_number(id: &str) -> Option<&str> {
+    id.strip_prefix("epic-").filter(|n| is_number(n))
+}
+
+/// Epic number prefix of a story key: `3-2-login` gives `3`
+pub(crate) fn story_epic(id: &str) -> Option<&str> {
+    let (number, _) = id.split_once('-')?;
+    Some(number).filter(|n| is_number(n))
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Split a leading run of ASCII digits off `s`
+fn leading_number(s: &str) -> Option<(&str, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    (end > 0).then(|| s.split_at(end))
+}
+
+/// Whether `id` is a well-formed workflow ID: a letter or digit, then
+/// letters, digits, `.`, `_` or `-`
+pub(crate) fn workflow_id(id: &str) -> bool {
+    id.starts_with(|c: char| c.is_ascii_alphanumeric()) && id.bytes().all(is_slug_byte)
+}
+
+/// Whether `key` is a well-formed sprint key: `epic-N`,
+/// `epic-N-retrospective` or `N-slug`
+pub(crate) fn sprint_key(key: &str) -> bool {
+    if let Some(rest) = key.strip_prefix("epic-") {
+        let number = rest.strip_suffix("-retrospective").unwrap_or(rest);
+        if is_number(number) {
+            return true;
+        }
+    }
+    story_epic(key).is_some_and(|number| {
+        let slug = &key[number.len() + 1..];
+        !slug.is_empty() && slug.bytes().all(is_slug_byte)
+    })
+}
+
+fn is_slug_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-')
+}
+
+/// Epic and story numbers of a story document title (`Story 1.2: Login`)
+pub(crate) fn story_title_numbers(title: &str) -> Option<(&str, &str)> {
+    let (epic, rest) = leading_number(after_word(title, "Story")?)?;
+    let (story, rest) = leading_number(rest.strip_prefix('.')?)?;
+    (!rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')).then_some((epic, story))
+}
+
+/// Hours, minutes, optional seconds and optional UTC offset of the time
+/// part of an ISO date-time (`T09:30:00.5+02:00`)
+pub(crate) fn time_of_day(time: &str) -> Option<(&str, &str, Option<&str>, Option<&str>)> {
+    let rest = time.strip_prefix(['T', ' '])?;
+    let (hours, rest) = two_digits(rest)?;
+    let (minutes, mut rest) = two_digits(rest.strip_prefix(':')?)?;
+    let mut seconds = None;
+    if let Some((secs, after)) = rest.strip_prefix(':').and_then(two_digits) {
+        seconds = Some(secs);
+        rest = after;
+        if let Some((_, after)) = rest.strip_prefix('.').and_then(leading_number) {
+            rest = after;
+        }
+    }
+    let offset = rest.trim_start();
+    let valid = match offset.strip_prefix(['+', '-']) {
+        Some(digits) => two_digits(digits).is_some_and(|(_, minutes)| {
+            let minutes = minutes.strip_prefix(':').unwrap_or(minutes);
+            minutes.len() == 2 && is_number(minutes)
+        }),
+        None => offset.is_empty() || offset == "Z",
+    };
+    valid.then_some((
+        hours,
+        minutes,
+        seconds,
+        Some(offset).filter(|o| !o.is_empty()),
+    ))
+}
+
+fn two_digits(s: &str) -> Option<(&str, &str)> {
+    s.get(..2)
+        .filter(|digits| is_number(digits))
+        .map(|digits| (digits, &s[2..]))
+}
+
+/// Lines of the "while parsing a flow ... at line N" notes in a YAML error
+pub(crate) fn flow_context_lines(message: &str) -> impl Iterator<Item = usize> + '_ {
+    let marker = "while parsing a flow ";
+    message.match_indices(marker).filter_map(move |(i, _)| {
+        let rest = &message[i + marker.len()..];
+        let word = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let rest = rest[word..]
+            .strip_prefix(" at line ")
+            .filter(|_| word > 0)?;
+        leading_number(rest)?.0.parse().ok()
+    })
+}
+
+/// Level and text of a markdown heading: `## Dev Notes ##` gives
+/// `(2, "Dev Notes")`
+pub(crate) fn heading(line: &str) -> Option<(usize, &str)> {
+    let text = heading_marker(line)?;
+    let level = line.len() - line.trim_start_matches('#').len();
+    let first = text.chars().next()?;
+    let trimmed = text.trim_end().trim_end_matches('#').trim_end();
+    // The heading text is never empty: `# ##` reads as `#`
+    Some((
+        level,
+        if trimmed.is_empty() {
+            &text[..first.len_utf8()]
+        } else {
+            trimmed
+        },
+    ))
+}
+
+/// What follows `word` and the whitespace after it
+fn after_word<'a>(text: &'a str, word: &str) -> Option<&'a str> {
+    let rest = text.strip_prefix(word)?;
+    rest.starts_with(char::is_whitespace)
+        .then(|| rest.trim_start())
+}
+
+/// A number, then a title separator (`:`, `.`, `-`, `–` or `—`): the number
+/// and the title after the separator
+fn numbered_title(text: &str) -> Option<(&str, &str)> {
+    let (number, rest) = leading_number(text)?;
+    let rest = rest.trim_start();
+    let separator = rest.chars().next().filter(|c| ":.-–—".contains(*c))?;
+    let title = rest[separator.len_utf8()..].trim_start();
+    (!title.is_empty()).then_some((number, title))
+}
+
+/// Number and title of an epics.md epic heading (`## Epic 1: Foundation`)
+pub(crate) fn epic_heading(line: &str) -> Option<(&str, &str)> {
+    let (_, text) = heading(line)?;
+    numbered_title(after_word(text, "Epic")?)
+}
+
+/// Epic number, story number and title of an epics.md story heading
+/// (`### Story 1.2: User Login`)
+pub(crate) fn story_heading(line: &str) -> Option<(&str, &str, &str)> {
+    let (_, text) = heading(line)?;
+    let (epic, rest) = leading_number(after_word(text, "Story")?)?;
+    let (story, title) = numbered_title(rest.strip_prefix('.')?)?;
+    Some((epic, story, title))
+}
+
+/// Whether a line is a numbered or bulleted list item with some text
+pub(crate) fn list_item(line: &str) -> bool {
+    let rest = match leading_number(line) {
+        Some((_, rest)) => rest.strip_prefix(['.', ')']),
+        None => line.strip_prefix(['-', '*', '+']),
+    };
+    rest.is_some_and(|rest| rest.starts_with(char::is_whitespace) && !rest.trim_start().is_empty())
+}
+
+/// Whether a line opens an acceptance criteria section, as a heading or a
+/// bold label
+pub(crate) fn criteria_label(line: &str) -> bool {
+    let text = match heading_marker(line) {
+        Some(rest) => rest,
+        None => line.strip_prefix("**").unwrap_or(line),
+    };
+    let label = "acceptance criteria";
+    text.get(..label.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(label))
+        && !text[label.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+}
+
+/// What follows a heading's `#` marks and the whitespace after them
+fn heading_marker(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    let level = line.len() - text.len();
+    ((1..=6).contains(&level) && text.starts_with(char::is_whitespace)).then(|| text.trim_start())
+}
+
+/// Byte range of the value on the first `Status: value` line of `text`,
+/// also accepting the bold forms `**Status:**` and `**Status**:`
+pub(crate) fn status_line(text: &str) -> Option<Range<usize>> {
+    let mut start = 0;
+    for line in text.split('\n') {
+        let offset = start;
+        start += line.len() + 1;
+        let body = line.strip_suffix('\r').unwrap_or(line);
+        let Some(rest) = body
+            .strip_prefix("**")
+            .unwrap_or(body)
+            .strip_prefix("Status")
+        else {
+            continue;
+        };
+        for separator in [":**", "**:", ":"] {
+            let Some(value) = rest.strip_prefix(separator) else {
+                continue;
+            };
+            let value = value.trim_start_matches([' ', '\t']);
+            let trimmed = value.trim_end_matches([' ', '\t']);
+            if trimmed.starts_with(|c: char| !c.is_whitespace()) && !trimmed.contains('\r') {
+                let value_start = offset + (body.len() - value.len());
+                return Some(value_start..value_start + trimmed.len());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // ID Pattern Tests
+    // =========================================================================
+
+    #[test]
+    fn test_epic_number() {
+        assert_eq!(epic_number("epic-1"), Some("1"));
+        assert_eq!(epic_number("epic-12345"), Some("12345"));
+        assert_eq!(epic_number("epic-"), None);
+        assert_eq!(epic_number("epic-1-retrospective"), None);
+        assert_eq!(epic_number("EPIC-1"), None);
+        assert_eq!(epic_number("prefix-epic-1"), None);
+    }
+
+    #[test]
+    fn test_story_epic() {
+        assert_eq!(story_epic("1-x"), Some("1"));
+        assert_eq!(story_epic("123-long-story-name"), Some("123"));
+        assert_eq!(story_epic("-1-negative"), None);
+        assert_eq!(story_epic("abc-story"), None);
+        assert_eq!(story_epic("12"), None);
+    }
+
+    #[test]
+    fn test_key_patterns() {
+        assert!(workflow_id("create-architecture"));
+        assert!(!workflow_id("-leading"));
+        assert!(sprint_key("epic-2-retrospective"));
+        assert!(sprint_key("1-2-user-login"));
+        assert!(!sprint_key("1-"));
+        assert!(!sprint_key("epic-x"));
+    }
+
+    #[test]
+    fn test_time_and_error_patterns() {
+        assert_eq!(
+            time_of_day("T09:30:15.5 +02:00"),
+            Some(("09", "30", Some("15"), Some("+02:00")))
+        );
+        assert_eq!(time_of_day("T09:30"), Some(("09", "30", None, None)));
+        assert_eq!(time_of_day("T09:30+2"), None);
+        assert_eq!(story_title_numbers("Story 1.2: Login"), Some(("1", "2")));
+        assert_eq!(story_title_numbers("Story 1.2a"), None);
+        let message = "while parsing a flow sequence at line 3 column 9";
+        assert_eq!(flow_context_lines(message).collect::<Vec<_>>(), [3]);
+    }
+
+    // =========================================================================
+    // Markdown Pattern Tests
+    // =========================================================================
+
+    #[test]
+    fn test_heading() {
+        assert_eq!(heading("# Story 1.2: Login"), Some((1, "Story 1.2: Login")));
+        assert_eq!(heading("###   Tasks  ##  "), Some((3, "Tasks")));
+        assert_eq!(heading("# ##"), Some((1, "#")));
+        assert_eq!(heading("#NoSpace"), None);
+        assert_eq!(heading("####### Seven"), None);
+        assert_eq!(heading("# "), None);
+    }
+
+    #[test]
+    fn test_epics_md_headings() {
+        assert_eq!(
+            epic_heading("## Epic 10: Payments"),
+            Some(("10", "Payments"))
+        );
+        assert_eq!(
+            epic_heading("## Epic 2 — Reporting"),
+            Some(("2", "Reporting"))
+        );
+        assert_eq!(epic_heading("## Epics overview"), None);
+        assert_eq!(
+            story_heading("#### Story 3.14: Pi ##"),
+            Some(("3", "14", "Pi"))
+        );
+        assert_eq!(story_heading("### Story 3: Missing number"), None);
+        assert!(criteria_label("### Acceptance Criteria"));
+        assert!(criteria_label("**acceptance criteria:**"));
+        assert!(!criteria_label("Acceptance criteriaX"));
+        assert!(!criteria_label("The acceptance criteria"));
+        assert!(list_item("1. First"));
+        assert!(list_item("12) Twelfth"));
+        assert!(list_item("- bullet"));
+        assert!(!list_item("-nospace"));
+        assert!(!list_item("1.5 kg"));
+    }
+
+    #[test]
+    fn test_status_line() {
+        let text = "# Title\n\n**Status:** in-progress  \r\nStatus: later\n";
+        let range = status_line(text).unwrap();
+        assert_eq!(&text[range], "in-progress");
+        assert_eq!(status_line("Status:\nStatus:   \n"), None);
+        let range = status_line("Status:**").unwrap();
+        assert_eq!(&"Status:**"[range], "**");
+    }
+}
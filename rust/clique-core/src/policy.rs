@@ -0,0 +1,402 @@
+// clique-core/src/policy.rs
+//! Which files inside a workspace may be edited.
+//!
+//! Containment alone lets through everything below the workspace root,
+//! including `.git` internals and `.env` secrets. A [`PathPolicy`] layers
+//! deny patterns on top of [`validate_path`], so a crafted output path or
+//! status entry cannot steer an edit into those files. Allow patterns go
+//! further and name the only files that may be touched, such as
+//! [`STATUS_FILE_PATTERNS`].
+//!
+//! Patterns are matched against the path relative to the workspace root,
+//! one `/`-separated segment at a time: `*` and `?` match within a segment
+//! and `**` matches any number of segments. A pattern starting with `/` is
+//! anchored at the root; any other pattern matches at any depth, so
+//! `node_modules/**` also covers `packages/app/node_modules/x`.
+//!
+//! macOS stores names decomposed (`cafe\u{301}`) while editors often send
+//! them composed (`caf\u{e9}`), and its default volumes ignore case. The
+//! [`PathPolicy::normalize_unicode`] and [`PathPolicy::case_sensitivity`]
+//! settings make comparisons, patterns included, agree with such volumes.
+
+use crate::query::glob_match;
+use crate::validation::{
+    CaseSensitivity, PathError, ValidatedPath, is_windows_path, validate_path_with,
+    workspace_relative_path,
+};
+use std::borrow::Cow;
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick};
+
+/// Deny patterns of [`PathPolicy::new`]
+pub const DEFAULT_DENY_PATTERNS: &[&str] = &[".git/**", "node_modules/**", ".env*"];
+
+/// Allow patterns of [`PathPolicy::only_status_files`]
+pub const STATUS_FILE_PATTERNS: &[&str] = &["**/bmm-workflow-status.yaml", "**/sprint-status.yaml"];
+
+/// Containment plus deny and allow patterns for one workspace root.
+///
+/// A path is accepted when it is inside the root, matches no deny pattern
+/// and, if any allow patterns are set, matches one of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPolicy {
+    workspace_root: String,
+    deny: Vec<String>,
+    allow: Vec<String>,
+    normalize_unicode: bool,
+    case: CaseSensitivity,
+}
+
+impl PathPolicy {
+    /// A policy for `workspace_root` denying [`DEFAULT_DENY_PATTERNS`].
+    pub fn new(workspace_root: &str) -> Self {
+        Self {
+            workspace_root: workspace_root.to_string(),
+            deny: DEFAULT_DENY_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            allow: Vec::new(),
+            normalize_unicode: false,
+            case: CaseSensitivity::Auto,
+        }
+    }
+
+    /// Also deny paths matching `pattern`.
+    pub fn deny(mut self, pattern: &str) -> Self {
+        self.deny.push(pattern.to_string());
+        self
+    }
+
+    /// Drop every deny pattern, including the defaults, leaving only
+    /// containment.
+    pub fn without_denies(mut self) -> Self {
+        self.deny.clear();
+        self
+    }
+
+    /// Only accept paths matching `pattern` or another allow pattern.
+    pub fn allow(mut self, pattern: &str) -> Self {
+        self.allow.push(pattern.to_string());
+        self
+    }
+
+    /// Only accept the workflow and sprint status files.
+    pub fn only_status_files(self) -> Self {
+        STATUS_FILE_PATTERNS
+            .iter()
+            .fold(self, |policy, pattern| policy.allow(pattern))
+    }
+
+    /// Compare paths and patterns in Unicode NFC, so composed and
+    /// decomposed spellings of a name are the same file.
+    pub fn normalize_unicode(mut self) -> Self {
+        self.normalize_unicode = true;
+        self
+    }
+
+    /// How containment and patterns compare letter case; by default only
+    /// Windows paths ignore it.
+    pub fn case_sensitivity(mut self, case: CaseSensitivity) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Compare paths and patterns ignoring case, as on default macOS
+    /// volumes: [`CaseSensitivity::Insensitive`].
+    pub fn case_insensitive(self) -> Self {
+        self.case_sensitivity(CaseSensitivity::Insensitive)
+    }
+
+    pub fn workspace_root(&self) -> &str {
+        &self.workspace_root
+    }
+
+    pub fn deny_patterns(&self) -> &[String] {
+        &self.deny
+    }
+
+    pub fn allow_patterns(&self) -> &[String] {
+        &self.allow
+    }
+
+    /// [`validate_path`](crate::validate_path), then refuse paths matching a deny pattern with
+    /// [`PathError::Denied`] naming the first pattern that matched, and
+    /// paths matching no allow pattern with [`PathError::NotAllowed`].
+    ///
+    /// With [`PathPolicy::normalize_unicode`] set, containment errors
+    /// describe the normalized path; accepted paths are returned as given.
+    pub fn validate(&self, file_path: &str) -> Result<ValidatedPath, PathError> {
+        let file = self.comparable(file_path);
+        let root = self.comparable(&self.workspace_root);
+        validate_path_with(&file, &root, self.case)?;
+        let relative = workspace_relative_path(&file, &root, self.case).unwrap_or_default();
+        let ignore_case = self
+            .case
+            .folds(is_windows_path(&file) || is_windows_path(&root));
+        let matches =
+            |pattern: &String| path_matches(&self.comparable(pattern), &relative, ignore_case);
+        if let Some(pattern) = self.deny.iter().find(|pattern| matches(pattern)) {
+            return Err(PathError::Denied {
+                path: file_path.to_string(),
+                pattern: pattern.clone(),
+            });
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(matches) {
+            return Err(PathError::NotAllowed(file_path.to_string()));
+        }
+        Ok(ValidatedPath::new(file_path))
+    }
+
+    /// A path or pattern in the form this policy compares
+    fn comparable<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        if self.normalize_unicode && is_nfc_quick(s.chars()) != IsNormalized::Yes {
+            Cow::Owned(s.nfc().collect())
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+
+    /// Whether [`PathPolicy::validate`] accepts the path
+    pub fn is_allowed(&self, file_path: &str) -> bool {
+        self.validate(file_path).is_ok()
+    }
+}
+
+/// Match a workspace-relative path against a pattern; see the
+/// [module docs](self).
+fn path_matches(pattern: &str, relative: &str, ignore_case: bool) -> bool {
+    let pattern = if ignore_case {
+        pattern.to_lowercase()
+    } else {
+        pattern.to_string()
+    };
+    let path: Vec<&str> = relative.split('/').filter(|s| !s.is_empty()).collect();
+    let (anchored, pattern) = match pattern.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, pattern.as_str()),
+    };
+    let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    if anchored {
+        segments_match(&segments, &path)
+    } else {
+        (0..=path.len()).any(|start| segments_match(&segments, &path[start..]))
+    }
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((segment, rest)) => path
+            .split_first()
+            .is_some_and(|(first, tail)| glob_match(segment, first) && segments_match(rest, tail)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn denied_by(policy: &PathPolicy, path: &str) -> Option<String> {
+        match policy.validate(path) {
+            Err(PathError::Denied { pattern, .. }) => Some(pattern),
+            _ => None,
+        }
+    }
+
+    // =========================================================================
+    // Pattern Tests
+    // =========================================================================
+
+    #[test]
+    fn test_path_matches() {
+        assert!(path_matches(".git/**", ".git", false));
+        assert!(path_matches(".git/**", ".git/objects/ab/cd", false));
+        assert!(path_matches(".git/**", "vendor/lib/.git/config", false));
+        assert!(!path_matches(".git/**", ".github/workflows/ci.yml", false));
+        assert!(path_matches(".env*", ".env.local", false));
+        assert!(path_matches(".env*", "apps/api/.env", false));
+        assert!(!path_matches(".env*", "docs/env.md", false));
+        assert!(path_matches("docs/*.md", "docs/prd.md", false));
+        assert!(!path_matches("docs/*.md", "docs/sub/prd.md", false));
+        assert!(path_matches("docs/**/*.md", "docs/sub/prd.md", false));
+        assert!(path_matches("/secrets/*", "secrets/key", false));
+        assert!(!path_matches("/secrets/*", "app/secrets/key", false));
+        assert!(!path_matches("README.md", "readme.md", false));
+        assert!(path_matches("README.md", "readme.md", true));
+    }
+
+    // =========================================================================
+    // Policy Tests
+    // =========================================================================
+
+    #[test]
+    fn test_default_policy_denies_sensitive_paths() {
+        let policy = PathPolicy::new("/ws");
+        assert_eq!(policy.deny_patterns(), DEFAULT_DENY_PATTERNS);
+        assert_eq!(
+            denied_by(&policy, "/ws/.git/config").as_deref(),
+            Some(".git/**")
+        );
+        assert_eq!(
+            denied_by(&policy, "/ws/web/node_modules/x/index.js").as_deref(),
+            Some("node_modules/**")
+        );
+        assert_eq!(
+            denied_by(&policy, "/ws/.env.production").as_deref(),
+            Some(".env*")
+        );
+        // Traversal back into a denied directory is still denied
+        assert_eq!(
+            denied_by(&policy, "/ws/docs/../.git/HEAD").as_deref(),
+            Some(".git/**")
+        );
+
+        let ok = policy.validate("/ws/docs/sprint-status.yaml").unwrap();
+        assert_eq!(ok.as_str(), "/ws/docs/sprint-status.yaml");
+        assert!(policy.is_allowed("/ws"));
+        assert!(policy.is_allowed("/ws/.github/workflows/ci.yml"));
+    }
+
+    #[test]
+    fn test_policy_keeps_containment_errors() {
+        let policy = PathPolicy::new("/ws");
+        assert_eq!(
+            policy.validate("/other/.git/config"),
+            Err(PathError::OutsideWorkspace(
+                "/other/.git/config".to_string()
+            ))
+        );
+        assert_eq!(
+            policy.validate("/ws/../.env"),
+            Err(PathError::TraversalDetected("/ws/../.env".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_policy_windows_paths_ignore_case() {
+        let policy = PathPolicy::new(r"C:\Ws");
+        assert_eq!(
+            denied_by(&policy, r"c:\ws\.GIT\config").as_deref(),
+            Some(".git/**")
+        );
+        let policy = policy.deny("Secrets/**");
+        assert_eq!(policy.workspace_root(), r"C:\Ws");
+        assert!(!policy.is_allowed(r"C:\WS\secrets\key.txt"));
+        assert!(policy.is_allowed(r"C:\WS\docs\prd.md"));
+    }
+
+    #[test]
+    fn test_policy_custom_patterns() {
+        let policy = PathPolicy::new("/ws").without_denies().deny("/build/**");
+        assert_eq!(policy.deny_patterns(), ["/build/**"]);
+        assert!(policy.is_allowed("/ws/.git/config"));
+        assert!(!policy.is_allowed("/ws/build/out.js"));
+        assert!(policy.is_allowed("/ws/app/build/out.js"));
+        assert!(!policy.is_allowed("/elsewhere/a.md"));
+    }
+
+    #[test]
+    fn test_policy_only_status_files() {
+        let policy = PathPolicy::new("/ws").only_status_files();
+        assert_eq!(policy.allow_patterns(), STATUS_FILE_PATTERNS);
+        assert!(policy.is_allowed("/ws/docs/bmm-workflow-status.yaml"));
+        assert!(policy.is_allowed("/ws/sprint-status.yaml"));
+        assert!(policy.is_allowed("/ws/a/b/sprint-status.yaml"));
+        assert_eq!(
+            policy.validate("/ws/docs/prd.md"),
+            Err(PathError::NotAllowed("/ws/docs/prd.md".to_string()))
+        );
+        assert_eq!(
+            policy.validate("/ws"),
+            Err(PathError::NotAllowed("/ws".to_string()))
+        );
+        // Deny patterns and containment are checked first
+        assert!(matches!(
+            policy.validate("/ws/.git/sprint-status.yaml"),
+            Err(PathError::Denied { .. })
+        ));
+        assert!(matches!(
+            policy.validate("/other/sprint-status.yaml"),
+            Err(PathError::OutsideWorkspace(_))
+        ));
+        let windows = PathPolicy::new(r"C:\ws").only_status_files();
+        assert!(windows.is_allowed(r"C:\ws\docs\Sprint-Status.YAML"));
+    }
+
+    #[test]
+    fn test_policy_custom_allow_patterns() {
+        let policy = PathPolicy::new("/ws")
+            .allow("/docs/stories/*.md")
+            .allow("**/sprint-status.yaml");
+        assert!(policy.is_allowed("/ws/docs/stories/1-1-login.md"));
+        assert!(!policy.is_allowed("/ws/docs/stories/old/1-1-login.md"));
+        assert!(!policy.is_allowed("/ws/other/docs/stories/1-1-login.md"));
+        assert!(policy.is_allowed("/ws/sprint-status.yaml"));
+        assert_eq!(
+            PathError::NotAllowed("/ws/a.md".to_string()).to_string(),
+            "Path is not among the writable files: /ws/a.md"
+        );
+    }
+
+    #[test]
+    fn test_policy_normalize_unicode() {
+        let composed = "/Users/me/caf\u{e9}";
+        let decomposed = "/Users/me/cafe\u{301}/docs/sprint-status.yaml";
+        assert!(matches!(
+            PathPolicy::new(composed).validate(decomposed),
+            Err(PathError::OutsideWorkspace(_))
+        ));
+
+        let policy = PathPolicy::new(composed).normalize_unicode();
+        assert_eq!(policy.validate(decomposed).unwrap().as_str(), decomposed);
+        assert!(policy.is_allowed("/Users/me/caf\u{e9}/docs/a.md"));
+        let policy = policy.deny("r\u{e9}sum\u{e9}*");
+        assert!(!policy.is_allowed("/Users/me/cafe\u{301}/re\u{301}sume\u{301}.md"));
+    }
+
+    #[test]
+    fn test_policy_case_insensitive() {
+        let policy = PathPolicy::new("/Users/Me/Project");
+        assert!(!policy.is_allowed("/users/me/project/a.md"));
+        assert!(policy.is_allowed("/Users/Me/Project/.GIT/config"));
+
+        let policy = policy.case_insensitive().only_status_files();
+        let path = "/users/me/PROJECT/Sprint-Status.yaml";
+        assert_eq!(policy.validate(path).unwrap().as_str(), path);
+        assert!(matches!(
+            policy.validate("/Users/Me/Project/.GIT/sprint-status.yaml"),
+            Err(PathError::Denied { .. })
+        ));
+        let both = PathPolicy::new("/Users/Me/Caf\u{c9}")
+            .normalize_unicode()
+            .case_insensitive();
+        assert!(both.is_allowed("/users/me/cafe\u{301}/a.md"));
+    }
+
+    #[test]
+    fn test_policy_case_sensitivity() {
+        let sensitive = PathPolicy::new(r"C:\Ws").case_sensitivity(CaseSensitivity::Sensitive);
+        assert!(sensitive.is_allowed(r"C:\Ws\docs\a.md"));
+        assert!(matches!(
+            sensitive.validate(r"c:\ws\docs\a.md"),
+            Err(PathError::OutsideWorkspace(_))
+        ));
+        // Patterns are compared with case too
+        assert!(sensitive.is_allowed(r"C:\Ws\.GIT\config"));
+        assert!(!sensitive.is_allowed(r"C:\Ws\.git\config"));
+
+        let auto = PathPolicy::new(r"C:\Ws").case_sensitivity(CaseSensitivity::Auto);
+        assert_eq!(auto, PathPolicy::new(r"C:\Ws"));
+        assert!(auto.is_allowed(r"c:\ws\docs\a.md"));
+    }
+
+    #[test]
+    fn test_denied_error_display() {
+        let err = PathPolicy::new("/ws").validate("/ws/.env").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Path /ws/.env matches the protected pattern .env*"
+        );
+    }
+}
@@ -0,0 +1,666 @@
+// clique-core/src/preprocess.rs
+//! Opt-in rewriting of a status file before it is parsed.
+//!
+//! Multi-repo setups keep shared workflow definitions in one file and pull
+//! them into each repository's status file:
+//!
+//! ```yaml
+//! include: ../shared/workflow.yaml
+//! project: ${PROJECT_NAME:-Demo}
+//! workflow_status:
+//!   prd: required
+//! ```
+//!
+//! [`Preprocessing`] replaces `${VAR}` references (`${VAR:-default}` for a
+//! fallback, `$${VAR}` for the literal text) and merges the files named by a
+//! top-level `include:` under the document: mappings are merged key by key
+//! with the including file winning, sequences are appended to. Included
+//! files are preprocessed the same way, must lie inside the workspace root
+//! (symlinks resolved, with the `fs` feature) and may nest
+//! [`DEFAULT_MAX_INCLUDE_DEPTH`] levels deep. At most
+//! [`DEFAULT_MAX_INCLUDES`] files are included in all, and each one and
+//! their total size are held to the parser's [`ParseLimits`].
+//!
+//! Comments are copied as written: a `${VAR}` in a comment is not replaced.
+//!
+//! Parsers apply it when [`ParseOptions::with_preprocessing`] is set.
+//! Editing functions never do: they rewrite the file as written.
+//!
+//! [`ParseOptions::with_preprocessing`]: crate::ParseOptions::with_preprocessing
+
+use crate::limits::{ParseLimits, enforce_limits};
+#[cfg(feature = "fs")]
+use crate::validation::is_inside_workspace_canonical;
+use crate::validation::{is_windows_path, resolve_path_components, validate_path};
+use crate::yaml_line::{comments, lines};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use thiserror::Error;
+
+/// Include nesting allowed by [`Preprocessing::new`]
+pub const DEFAULT_MAX_INCLUDE_DEPTH: usize = 4;
+
+/// Included files allowed in one document by [`Preprocessing::new`],
+/// counting each time a file is included
+pub const DEFAULT_MAX_INCLUDES: usize = 32;
+
+#[derive(Error, Debug)]
+pub enum PreprocessError {
+    #[error("Undefined variable: {0}")]
+    UndefinedVariable(String),
+    #[error("Unterminated variable reference: {0}")]
+    UnterminatedVariable(String),
+    /// The value would splice new lines, and so new structure, into the file
+    #[error("Variable {0} contains a line break")]
+    UnsafeValue(String),
+    #[error("Includes are not enabled: {0}")]
+    IncludesDisabled(String),
+    #[error("Include refused: {0}")]
+    IncludeRefused(String),
+    #[error("Include cycle: {0}")]
+    IncludeCycle(String),
+    #[error("Includes nested too deep: {0}")]
+    IncludeDepth(String),
+    #[error("Include over limit: {0}")]
+    IncludeLimit(String),
+    #[error("Failed to read include {0}")]
+    ReadError(String),
+    #[error("Failed to parse YAML: {0}")]
+    ParseError(String),
+}
+
+/// What to do to a document before parsing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preprocessing {
+    /// Values for `${VAR}`, looked up before the environment
+    pub variables: BTreeMap<String, String>,
+    /// Fall back to process environment variables
+    pub env: bool,
+    /// Directory `include:` paths are resolved from; includes are refused
+    /// when unset
+    pub base_dir: Option<String>,
+    /// Directory included files must stay inside; the base directory when
+    /// unset
+    pub workspace_root: Option<String>,
+    /// Content of included files by resolved path, used instead of reading
+    /// them, e.g. on wasm32
+    pub files: BTreeMap<String, String>,
+    pub max_include_depth: usize,
+    pub max_includes: usize,
+}
+
+impl Default for Preprocessing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Preprocessing {
+    /// No variables and no includes; `${VAR}` references must have a
+    /// default.
+    pub fn new() -> Self {
+        Self {
+            variables: BTreeMap::new(),
+            env: false,
+            base_dir: None,
+            workspace_root: None,
+            files: BTreeMap::new(),
+            max_include_depth: DEFAULT_MAX_INCLUDE_DEPTH,
+            max_includes: DEFAULT_MAX_INCLUDES,
+        }
+    }
+
+    /// Set `${name}` to `value`.
+    pub fn with_variable(mut self, name: &str, value: &str) -> Self {
+        self.variables.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Read variables not set with [`Preprocessing::with_variable`] from the
+    /// environment.
+    pub fn with_env(mut self) -> Self {
+        self.env = true;
+        self
+    }
+
+    /// Resolve includes from `base_dir`, usually the status file's folder.
+    pub fn with_includes(mut self, base_dir: &str) -> Self {
+        self.base_dir = Some(base_dir.to_string());
+        self
+    }
+
+    /// Let includes reach anywhere inside `workspace_root`, such as a shared
+    /// folder next to the status file's.
+    pub fn with_workspace_root(mut self, workspace_root: &str) -> Self {
+        self.workspace_root = Some(workspace_root.to_string());
+        self
+    }
+
+    /// Serve the include at `path` from memory.
+    pub fn with_file(mut self, path: &str, content: &str) -> Self {
+        self.files.insert(path.to_string(), content.to_string());
+        self
+    }
+
+    pub fn with_max_include_depth(mut self, depth: usize) -> Self {
+        self.max_include_depth = depth;
+        self
+    }
+
+    pub fn with_max_includes(mut self, count: usize) -> Self {
+        self.max_includes = count;
+        self
+    }
+
+    /// `content` with variables replaced and includes merged in, under the
+    /// default [`ParseLimits`].
+    ///
+    /// A document without an `include:` line is only interpolated, so its
+    /// line numbers stay as written.
+    pub fn apply(&self, content: &str) -> Result<String, PreprocessError> {
+        self.apply_with_limits(content, &ParseLimits::default())
+    }
+
+    /// [`Preprocessing::apply`], holding every included file and their
+    /// total size to `limits`.
+    pub fn apply_with_limits(
+        &self,
+        content: &str,
+        limits: &ParseLimits,
+    ) -> Result<String, PreprocessError> {
+        let mut expansion = Expansion {
+            limits,
+            stack: Vec::new(),
+            includes: 0,
+            bytes: content.len(),
+        };
+        self.expand(content, self.base_dir.as_deref(), &mut expansion)
+    }
+
+    fn variable(&self, name: &str) -> Option<String> {
+        self.variables
+            .get(name)
+            .cloned()
+            .or_else(|| self.env.then(|| std::env::var(name).ok()).flatten())
+    }
+
+    /// Replace `${VAR}` and `${VAR:-default}` references outside comments
+    fn interpolate(&self, content: &str) -> Result<String, PreprocessError> {
+        let mut comments = comments(content).into_iter().peekable();
+        let mut out = String::with_capacity(content.len());
+        let mut copied = 0;
+        for (number, line) in lines(content).enumerate() {
+            let Some((_, comment)) = comments.next_if(|&(at, _)| at == number + 1) else {
+                continue;
+            };
+            let start = line.start + line.text.len() - comment.len();
+            self.interpolate_text(&content[copied..start], &mut out)?;
+            out.push_str(comment);
+            copied = start + comment.len();
+        }
+        self.interpolate_text(&content[copied..], &mut out)?;
+        Ok(out)
+    }
+
+    /// Append `text` to `out` with its references replaced
+    fn interpolate_text(&self, text: &str, out: &mut String) -> Result<(), PreprocessError> {
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            let (before, reference) = rest.split_at(start);
+            if let Some(literal) = before.strip_suffix('$') {
+                out.push_str(literal);
+                out.push_str("${");
+                rest = &reference[2..];
+                continue;
+            }
+            out.push_str(before);
+            let end = reference
+                .find('}')
+                .filter(|&end| !reference[..end].contains('\n'))
+                .ok_or_else(|| {
+                    let line = reference.lines().next().unwrap_or_default();
+                    PreprocessError::UnterminatedVariable(line.to_string())
+                })?;
+            let expression = &reference[2..end];
+            let (name, default) = match expression.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (expression, None),
+            };
+            let value = self
+                .variable(name)
+                .or_else(|| default.map(str::to_string))
+                .ok_or_else(|| PreprocessError::UndefinedVariable(name.to_string()))?;
+            if value.contains(['\n', '\r']) {
+                return Err(PreprocessError::UnsafeValue(name.to_string()));
+            }
+            out.push_str(&value);
+            rest = &reference[end + 1..];
+        }
+        out.push_str(rest);
+        Ok(())
+    }
+
+    /// Interpolate `content`, then merge the files it includes beneath it
+    fn expand(
+        &self,
+        content: &str,
+        dir: Option<&str>,
+        expansion: &mut Expansion,
+    ) -> Result<String, PreprocessError> {
+        let content = self.interpolate(content)?;
+        if !content.lines().any(|line| line.starts_with("include:")) {
+            return Ok(content);
+        }
+        let mut own: Value = serde_yaml::from_str(&content)
+            .map_err(|e| PreprocessError::ParseError(e.to_string()))?;
+        let includes = match own.as_mapping_mut().and_then(|m| m.remove("include")) {
+            Some(Value::String(path)) => vec![path],
+            Some(Value::Sequence(paths)) => paths
+                .iter()
+                .map(|path| path.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| {
+                    PreprocessError::ParseError("include paths must be strings".to_string())
+                })?,
+            Some(Value::Null) | None => Vec::new(),
+            Some(_) => {
+                return Err(PreprocessError::ParseError(
+                    "include must be a path or a list of paths".to_string(),
+                ));
+            }
+        };
+
+        let mut merged = Value::Mapping(Mapping::new());
+        for include in includes {
+            let Some(dir) = dir else {
+                return Err(PreprocessError::IncludesDisabled(include));
+            };
+            if expansion.stack.len() >= self.max_include_depth {
+                return Err(PreprocessError::IncludeDepth(include));
+            }
+            if expansion.includes >= self.max_includes {
+                return Err(PreprocessError::IncludeLimit(format!(
+                    "{}: more than {} includes",
+                    include, self.max_includes
+                )));
+            }
+            expansion.includes += 1;
+            let path = self.resolve(dir, &include)?;
+            if expansion.stack.contains(&path) {
+                expansion.stack.push(path);
+                return Err(PreprocessError::IncludeCycle(expansion.stack.join(" -> ")));
+            }
+            let text = self.read(&path)?;
+            expansion.bytes += text.len();
+            if expansion.bytes > expansion.limits.max_bytes {
+                return Err(PreprocessError::IncludeLimit(format!(
+                    "{}: includes total more than {} bytes",
+                    include, expansion.limits.max_bytes
+                )));
+            }
+            enforce_limits(&text, expansion.limits)
+                .map_err(|e| PreprocessError::IncludeLimit(format!("{}: {}", include, e)))?;
+            let parent = Path::new(&path)
+                .parent()
+                .map(|parent| parent.to_string_lossy().into_owned());
+            expansion.stack.push(path);
+            let expanded = self.expand(&text, parent.as_deref(), expansion)?;
+            expansion.stack.pop();
+            let value: Value = serde_yaml::from_str(&expanded)
+                .map_err(|e| PreprocessError::ParseError(format!("{}: {}", include, e)))?;
+            merge(&mut merged, value);
+        }
+        merge(&mut merged, own);
+        serde_yaml::to_string(&merged).map_err(|e| PreprocessError::ParseError(e.to_string()))
+    }
+
+    /// Absolute path of `include`, refused outside the workspace root, and
+    /// with the `fs` feature also when a symlink leads out of it
+    fn resolve(&self, dir: &str, include: &str) -> Result<String, PreprocessError> {
+        let root = self
+            .workspace_root
+            .as_deref()
+            .or(self.base_dir.as_deref())
+            .unwrap_or(dir);
+        let joined = Path::new(dir).join(include).to_string_lossy().into_owned();
+        let path = validate_path(&joined, root)
+            .map_err(|e| PreprocessError::IncludeRefused(format!("{}: {}", include, e)))?;
+        // Resolved so that cycles through `..` are caught
+        let path = path.into_string();
+        let path = resolve_path_components(&path, is_windows_path(&path));
+        #[cfg(feature = "fs")]
+        if !self.files.contains_key(&path)
+            && Path::new(&path).exists()
+            && !is_inside_workspace_canonical(&path, root)
+        {
+            return Err(PreprocessError::IncludeRefused(format!(
+                "{}: resolves outside the workspace",
+                include
+            )));
+        }
+        Ok(path)
+    }
+
+    fn read(&self, path: &str) -> Result<String, PreprocessError> {
+        if let Some(content) = self.files.get(path) {
+            return Ok(content.clone());
+        }
+        #[cfg(feature = "fs")]
+        {
+            std::fs::read_to_string(path)
+                .map_err(|e| PreprocessError::ReadError(format!("{}: {}", path, e)))
+        }
+        #[cfg(not(feature = "fs"))]
+        Err(PreprocessError::ReadError(format!(
+            "{}: not provided with Preprocessing::with_file",
+            path
+        )))
+    }
+}
+
+/// Bookkeeping for one [`Preprocessing::apply_with_limits`] call
+struct Expansion<'a> {
+    limits: &'a ParseLimits,
+    /// Resolved paths of the includes being expanded, outermost first
+    stack: Vec<String>,
+    /// Files included so far
+    includes: usize,
+    /// Size of the document and the files included so far
+    bytes: usize,
+}
+
+/// Merge `overlay` into `base`: mappings key by key, sequences appended,
+/// anything else replaced
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Sequence(base), Value::Sequence(overlay)) => base.extend(overlay),
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "fs", unix))]
+    use std::fs;
+
+    const SHARED: &str = "workflow_status:\n  brainstorm: optional\n  prd: required\n";
+
+    // =========================================================================
+    // Interpolation Tests
+    // =========================================================================
+
+    #[test]
+    fn test_interpolate() {
+        let preprocessing = Preprocessing::new().with_variable("NAME", "Demo");
+        assert_eq!(
+            preprocessing
+                .apply("project: ${NAME}\ntrack: ${TRACK:-method}\nraw: $${NAME}\n")
+                .unwrap(),
+            "project: Demo\ntrack: method\nraw: ${NAME}\n"
+        );
+        assert!(matches!(
+            preprocessing.apply("project: ${MISSING}\n"),
+            Err(PreprocessError::UndefinedVariable(name)) if name == "MISSING"
+        ));
+        assert!(matches!(
+            preprocessing.apply("project: ${NAME\nx: }\n"),
+            Err(PreprocessError::UnterminatedVariable(_))
+        ));
+        let injected = Preprocessing::new().with_variable("X", "a\nworkflows: {}");
+        assert!(matches!(
+            injected.apply("project: ${X}\n"),
+            Err(PreprocessError::UnsafeValue(_))
+        ));
+    }
+
+    #[test]
+    fn test_interpolate_skips_comments() {
+        let preprocessing = Preprocessing::new().with_variable("NAME", "Demo");
+        let content =
+            "# set ${UNSET} in CI\nproject: ${NAME} # was ${OLD}\nnotes: |\n  # ${NAME}\n";
+        assert_eq!(
+            preprocessing.apply(content).unwrap(),
+            "# set ${UNSET} in CI\nproject: Demo # was ${OLD}\nnotes: |\n  # Demo\n"
+        );
+    }
+
+    #[test]
+    fn test_environment() {
+        let name = format!("CLIQUE_PREPROCESS_TEST_{}", std::process::id());
+        let content = format!("project: ${{{}:-fallback}}\n", name);
+        assert_eq!(
+            Preprocessing::new().with_env().apply(&content).unwrap(),
+            "project: fallback\n"
+        );
+        let path = std::env::var("PATH").unwrap_or_default();
+        let preprocessing = Preprocessing::new().with_env();
+        if !path.contains('\n') {
+            assert_eq!(
+                preprocessing.apply("p: ${PATH}").unwrap(),
+                format!("p: {}", path)
+            );
+        }
+        assert!(Preprocessing::new().apply("p: ${PATH}").is_err());
+    }
+
+    // =========================================================================
+    // Include Tests
+    // =========================================================================
+
+    #[test]
+    fn test_include_merges() {
+        let preprocessing = Preprocessing::new()
+            .with_includes("/ws/app")
+            .with_workspace_root("/ws")
+            .with_file("/ws/shared/workflow.yaml", SHARED)
+            .with_variable("NAME", "Demo");
+        let content = "include: ../shared/workflow.yaml\nproject: ${NAME}\nworkflow_status:\n  \
+                       prd: docs/prd.md\n  architecture: required\n";
+        let merged: Value = serde_yaml::from_str(&preprocessing.apply(content).unwrap()).unwrap();
+        let expected: Value = serde_yaml::from_str(
+            "workflow_status:\n  brainstorm: optional\n  prd: docs/prd.md\n  \
+             architecture: required\nproject: Demo\n",
+        )
+        .unwrap();
+        assert_eq!(merged, expected);
+        assert!(merged.get("include").is_none());
+
+        let list = "include: [../shared/workflow.yaml]\n";
+        assert!(preprocessing.apply(list).unwrap().contains("brainstorm"));
+    }
+
+    #[test]
+    fn test_include_refusals() {
+        let content = "include: shared.yaml\nworkflow_status: {}\n";
+        assert!(matches!(
+            Preprocessing::new().apply(content),
+            Err(PreprocessError::IncludesDisabled(_))
+        ));
+        assert!(matches!(
+            Preprocessing::new()
+                .with_includes("/ws")
+                .apply("include: ../etc/passwd\n"),
+            Err(PreprocessError::IncludeRefused(_))
+        ));
+        assert!(matches!(
+            Preprocessing::new().with_includes("/ws").apply(content),
+            Err(PreprocessError::ReadError(_))
+        ));
+        assert!(matches!(
+            Preprocessing::new().apply("include: 3\n"),
+            Err(PreprocessError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_include_cycles_and_depth() {
+        let cyclic = Preprocessing::new()
+            .with_includes("/ws")
+            .with_file("/ws/a.yaml", "include: b.yaml\n")
+            .with_file("/ws/b.yaml", "include: a.yaml\n");
+        let error = cyclic.apply("include: a.yaml\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Include cycle: /ws/a.yaml -> /ws/b.yaml -> /ws/a.yaml"
+        );
+
+        let chain = Preprocessing::new()
+            .with_includes("/ws")
+            .with_file("/ws/1.yaml", "include: 2.yaml\none: 1\n")
+            .with_file("/ws/2.yaml", "two: 2\n");
+        assert!(chain.apply("include: 1.yaml\n").unwrap().contains("two: 2"));
+        assert!(matches!(
+            chain.with_max_include_depth(1).apply("include: 1.yaml\n"),
+            Err(PreprocessError::IncludeDepth(_))
+        ));
+    }
+
+    #[test]
+    fn test_include_limits() {
+        // Each level includes the next one four times over
+        let mut fan_out = Preprocessing::new().with_includes("/ws");
+        for level in 0..3 {
+            let next = format!("{}.yaml", level + 1);
+            let text = format!("include: [{0}, {0}, {0}, {0}]\n", next);
+            fan_out = fan_out.with_file(&format!("/ws/{}.yaml", level), &text);
+        }
+        fan_out = fan_out.with_file("/ws/3.yaml", SHARED);
+        let error = fan_out.apply("include: 0.yaml\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Include over limit: 3.yaml: more than 32 includes"
+        );
+        assert!(
+            fan_out
+                .with_max_includes(usize::MAX)
+                .apply("include: 0.yaml\n")
+                .is_ok()
+        );
+
+        let large = Preprocessing::new()
+            .with_includes("/ws")
+            .with_file("/ws/large.yaml", &format!("notes: {}\n", "x".repeat(600)));
+        let limits = ParseLimits {
+            max_bytes: 1024,
+            max_line_length: 512,
+            ..Default::default()
+        };
+        assert!(matches!(
+            large.apply_with_limits("include: large.yaml\n", &limits),
+            Err(PreprocessError::IncludeLimit(e)) if e.contains("line 1")
+        ));
+        let twice = "include: [large.yaml, large.yaml]\n";
+        assert!(large.apply(twice).is_ok());
+        assert!(matches!(
+            large.apply_with_limits(
+                twice,
+                &ParseLimits {
+                    max_bytes: 1024,
+                    ..Default::default()
+                }
+            ),
+            Err(PreprocessError::IncludeLimit(e)) if e.contains("1024 bytes")
+        ));
+    }
+
+    #[cfg(all(feature = "fs", unix))]
+    #[test]
+    fn test_include_refuses_symlink_escape() {
+        let dir = crate::testing::TempDir::new("preprocess");
+        let root = dir.join("ws");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(dir.join("secret.yaml"), "token: hunter2\n").unwrap();
+        fs::write(root.join("shared.yaml"), SHARED).unwrap();
+        std::os::unix::fs::symlink(dir.join("secret.yaml"), root.join("link.yaml")).unwrap();
+
+        let preprocessing = Preprocessing::new().with_includes(&root.to_string_lossy());
+        assert!(
+            preprocessing
+                .apply("include: shared.yaml\n")
+                .unwrap()
+                .contains("brainstorm")
+        );
+        assert!(matches!(
+            preprocessing.apply("include: link.yaml\n"),
+            Err(PreprocessError::IncludeRefused(_))
+        ));
+    }
+
+    // =========================================================================
+    // Parser Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_with_preprocessing() {
+        use crate::{
+            FormatRegistry, ParseLimits, ParseOptions, SprintError, WorkflowError,
+            parse_sprint_status_with_options, parse_workflow_status_with_options,
+        };
+
+        let preprocessing = Preprocessing::new()
+            .with_includes("/ws")
+            .with_file("/ws/shared.yaml", SHARED)
+            .with_variable("NAME", "Demo");
+        let options = ParseOptions::default().with_preprocessing(preprocessing.clone());
+        let content = "include: shared.yaml
+project: ${NAME}
+workflow_status:
+  prd: complete
+";
+        let data = parse_workflow_status_with_options(content, &options).unwrap();
+        assert_eq!(data.project, "Demo");
+        let items: Vec<(&str, &str)> = data
+            .items
+            .iter()
+            .map(|item| (item.id.as_str(), &*item.status))
+            .collect();
+        assert_eq!(items, [("brainstorm", "optional"), ("prd", "complete")]);
+        assert_eq!(
+            FormatRegistry::new()
+                .parse_with_options(content, &options)
+                .unwrap(),
+            data
+        );
+
+        let sprint = "project: ${NAME}
+development_status:
+  epic-1: backlog
+";
+        let data = parse_sprint_status_with_options(sprint, &options).unwrap();
+        assert_eq!(data.project, "Demo");
+        assert!(matches!(
+            parse_sprint_status_with_options(
+                "project: ${OTHER}
+",
+                &options
+            ),
+            Err(SprintError::Preprocess(_))
+        ));
+
+        // Without preprocessing the directive is just an unknown key
+        let raw = parse_workflow_status_with_options(content, &ParseOptions::default()).unwrap();
+        assert_eq!(raw.project, "${NAME}");
+        assert_eq!(raw.items.len(), 1);
+
+        let limited = options.with_limits(ParseLimits {
+            max_items: 1,
+            ..Default::default()
+        });
+        assert!(matches!(
+            parse_workflow_status_with_options(content, &limited),
+            Err(WorkflowError::ResourceLimit(_))
+        ));
+    }
+}
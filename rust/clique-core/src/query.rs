@@ -0,0 +1,285 @@
+// clique-core/src/query.rs
+//! Query and filter API over parsed workflow data.
+
+use crate::types::{Phase, StatusClass, WorkflowData, WorkflowItem};
+use std::collections::BTreeMap;
+
+/// A composable filter over the items of a `WorkflowData`.
+///
+/// All configured filters must match for an item to be returned.
+#[derive(Debug, Clone)]
+pub struct WorkflowQuery<'a> {
+    data: &'a WorkflowData,
+    phase: Option<Phase>,
+    agent: Option<String>,
+    status_class: Option<StatusClass>,
+    id_pattern: Option<String>,
+    tag: Option<String>,
+}
+
+impl WorkflowData {
+    /// Start a query over this workflow's items.
+    pub fn query(&self) -> WorkflowQuery<'_> {
+        WorkflowQuery {
+            data: self,
+            phase: None,
+            agent: None,
+            status_class: None,
+            id_pattern: None,
+            tag: None,
+        }
+    }
+}
+
+impl<'a> WorkflowQuery<'a> {
+    /// Only match items in the given phase.
+    pub fn phase(mut self, phase: Phase) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
+    /// Only match items assigned to the given agent.
+    pub fn agent(mut self, agent: &str) -> Self {
+        self.agent = Some(agent.to_string());
+        self
+    }
+
+    /// Only match items whose status falls into the given class.
+    pub fn status_class(mut self, class: StatusClass) -> Self {
+        self.status_class = Some(class);
+        self
+    }
+
+    /// Only match items whose ID matches a glob pattern (`*` and `?` wildcards).
+    pub fn id_pattern(mut self, pattern: &str) -> Self {
+        self.id_pattern = Some(pattern.to_string());
+        self
+    }
+
+    /// Only match items carrying the given tag (case-insensitively).
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_string());
+        self
+    }
+
+    /// Check whether a single item satisfies every configured filter.
+    pub fn matches(&self, item: &WorkflowItem) -> bool {
+        if let Some(phase) = self.phase
+            && item.phase != phase
+        {
+            return false;
+        }
+        if let Some(agent) = &self.agent
+            && item.agent.as_deref() != Some(agent.as_str())
+        {
+            return false;
+        }
+        if let Some(class) = self.status_class
+            && item.status_class() != class
+        {
+            return false;
+        }
+        if let Some(pattern) = &self.id_pattern
+            && !glob_match(pattern, &item.id)
+        {
+            return false;
+        }
+        if let Some(tag) = &self.tag
+            && !item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+        {
+            return false;
+        }
+        true
+    }
+
+    /// Collect the matching items in document order.
+    pub fn items(&self) -> Vec<&'a WorkflowItem> {
+        self.data
+            .items
+            .iter()
+            .filter(|item| self.matches(item))
+            .collect()
+    }
+
+    /// Count the matching items.
+    pub fn count(&self) -> usize {
+        self.data
+            .items
+            .iter()
+            .filter(|item| self.matches(item))
+            .count()
+    }
+
+    /// Count the matching items per tag, as written. An item with several
+    /// tags counts once under each.
+    pub fn tag_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for item in self.items() {
+            for tag in &item.tags {
+                *counts.entry(tag.clone()).or_default() += 1;
+            }
+        }
+        counts
+    }
+}
+
+/// Match `text` against a glob pattern supporting `*` (any run) and `?` (any char).
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<usize> = None;
+    let mut star_t = 0;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some(p);
+            star_t = t;
+            p += 1;
+        } else if let Some(s) = star {
+            p = s + 1;
+            star_t += 1;
+            t = star_t;
+        } else {
+            return false;
+        }
+    }
+
+    while p < pattern.len() && pattern[p] == '*' {
+        p += 1;
+    }
+    p == pattern.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::parse_workflow_status;
+
+    const YAML: &str = r#"
+project: Query Test
+workflow_status:
+  brainstorm: docs/brainstorm.md
+  research: skipped
+  prd: required
+  validate-prd: optional
+  create-ux-design: conditional
+  architecture: docs/architecture.md
+  sprint-planning: required
+"#;
+
+    fn ids(items: Vec<&WorkflowItem>) -> Vec<&str> {
+        items.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_query_no_filters_returns_all() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        assert_eq!(data.query().count(), data.items.len());
+    }
+
+    #[test]
+    fn test_query_by_phase() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let items = data.query().phase(Phase::Number(1)).items();
+        assert_eq!(ids(items), vec!["create-ux-design", "prd", "validate-prd"]);
+    }
+
+    #[test]
+    fn test_query_by_agent() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let items = data.query().agent("analyst").items();
+        assert_eq!(ids(items), vec!["brainstorm", "research"]);
+    }
+
+    #[test]
+    fn test_query_by_status_class() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+
+        let complete = data.query().status_class(StatusClass::Complete).items();
+        assert_eq!(ids(complete), vec!["brainstorm", "architecture"]);
+
+        let skipped = data.query().status_class(StatusClass::Skipped).items();
+        assert_eq!(ids(skipped), vec!["research"]);
+
+        assert_eq!(data.query().status_class(StatusClass::Pending).count(), 4);
+    }
+
+    #[test]
+    fn test_query_by_id_pattern() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let items = data.query().id_pattern("*prd").items();
+        assert_eq!(ids(items), vec!["prd", "validate-prd"]);
+    }
+
+    #[test]
+    fn test_query_combined_filters() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let items = data
+            .query()
+            .phase(Phase::Number(1))
+            .status_class(StatusClass::Pending)
+            .id_pattern("validate-*")
+            .items();
+        assert_eq!(ids(items), vec!["validate-prd"]);
+    }
+
+    const TAGGED_YAML: &str = r#"
+workflows:
+  prd:
+    status: complete
+    output_file: docs/prd.md
+    tags: [backend, security]
+  architecture:
+    status: not_started
+    tags: [Backend]
+  ux-design:
+    status: not_started
+    tags: frontend
+  research:
+    status: skipped
+"#;
+
+    #[test]
+    fn test_query_by_tag() {
+        let data = parse_workflow_status(TAGGED_YAML).expect("Should parse");
+        let items = data.query().tag("backend").items();
+        assert_eq!(ids(items), vec!["prd", "architecture"]);
+        assert_eq!(ids(data.query().tag("frontend").items()), vec!["ux-design"]);
+        assert_eq!(data.query().tag("missing").count(), 0);
+    }
+
+    #[test]
+    fn test_tag_counts() {
+        let data = parse_workflow_status(TAGGED_YAML).expect("Should parse");
+        let counts = data.query().tag_counts();
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts["backend"], 1);
+        assert_eq!(counts["Backend"], 1);
+        assert_eq!(counts["security"], 1);
+
+        let pending = data.query().status_class(StatusClass::Pending).tag_counts();
+        assert_eq!(
+            pending.keys().collect::<Vec<_>>(),
+            vec!["Backend", "frontend"]
+        );
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("prd", "prd"));
+        assert!(!glob_match("prd", "prd2"));
+        assert!(glob_match("create-*", "create-architecture"));
+        assert!(glob_match("*-design", "test-design"));
+        assert!(glob_match("p?d", "prd"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+        assert!(!glob_match("?", ""));
+    }
+}
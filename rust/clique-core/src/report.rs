@@ -0,0 +1,1661 @@
+// clique-core/src/report.rs
+//! Human-readable reports generated from parsed workflow and sprint data.
+
+use crate::history::HistoryEntry;
+use crate::sprint::is_done;
+use crate::types::{
+    Date, ItemState, Phase, SprintData, StatusAliases, StatusClass, Story, WorkflowData,
+    WorkflowItem, fnv1a, format_utc_timestamp, parse_date, parse_timestamp,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+/// Group items by phase, keeping document order within each phase.
+fn group_by_phase(data: &WorkflowData) -> BTreeMap<Phase, Vec<&WorkflowItem>> {
+    let mut phases: BTreeMap<Phase, Vec<&WorkflowItem>> = BTreeMap::new();
+    for item in &data.items {
+        phases.entry(item.phase).or_default().push(item);
+    }
+    phases
+}
+
+/// Escape characters that Markdown would otherwise interpret as formatting.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Format a Markdown link target, wrapping paths with spaces in angle brackets.
+fn markdown_link_target(path: &str) -> String {
+    if path.contains(char::is_whitespace) || path.contains(['(', ')']) {
+        format!("<{}>", path)
+    } else {
+        path.to_string()
+    }
+}
+
+/// Render a workflow as a phase-by-phase Markdown checklist.
+///
+/// Completed items link to their output file, pending items are bolded with
+/// their status so they stand out in a PR comment, and skipped items are
+/// struck through.
+pub fn workflow_markdown(data: &WorkflowData) -> String {
+    let mut out = String::new();
+
+    let title = if data.project.is_empty() {
+        "Workflow Status".to_string()
+    } else {
+        format!("Workflow Status: {}", escape_markdown(&data.project))
+    };
+    let _ = writeln!(out, "# {}\n", title);
+
+    let mut meta = Vec::new();
+    if !data.last_updated.is_empty() {
+        meta.push(format!(
+            "Last updated: {}",
+            escape_markdown(&data.last_updated)
+        ));
+    }
+    if !data.status.is_empty() {
+        meta.push(format!("Status: {}", escape_markdown(&data.status)));
+    }
+    if !meta.is_empty() {
+        let _ = writeln!(out, "_{}_\n", meta.join(" · "));
+    }
+    if let Some(note) = &data.status_note {
+        let _ = writeln!(out, "> {}\n", escape_markdown(note));
+    }
+
+    let total = data.items.len();
+    let complete = data.query().status_class(StatusClass::Complete).count();
+    let skipped = data.query().status_class(StatusClass::Skipped).count();
+    let _ = write!(out, "**Progress:** {}/{} complete", complete, total);
+    if skipped > 0 {
+        let _ = write!(out, " ({} skipped)", skipped);
+    }
+    out.push_str("\n\n");
+
+    for (phase, items) in group_by_phase(data) {
+        let _ = writeln!(out, "## {}\n", phase.title());
+        for item in items {
+            out.push_str(&markdown_checklist_line(item));
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    // Exactly one trailing newline
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+    out
+}
+
+fn markdown_checklist_line(item: &WorkflowItem) -> String {
+    let id = escape_markdown(&item.id);
+    let mut line = match item.status_class() {
+        StatusClass::Complete => match &item.output_file {
+            Some(path) => format!(
+                "- [x] {} — [{}]({})",
+                id,
+                escape_markdown(path),
+                markdown_link_target(path)
+            ),
+            None => format!("- [x] {}", id),
+        },
+        StatusClass::Skipped => format!("- [ ] ~~{}~~ (skipped)", id),
+        StatusClass::Pending => format!("- [ ] **{}** — {}", id, escape_markdown(&item.status)),
+    };
+    if let Some(agent) = &item.agent {
+        let _ = write!(line, " _({})_", escape_markdown(agent));
+    }
+    line
+}
+
+/// Escape text for use inside a double-quoted Mermaid label.
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+/// Mermaid class name for a sprint status
+fn story_status_class(status: &str) -> &'static str {
+    match status {
+        "done" | "completed" => "done",
+        "in-progress" => "active",
+        "review" => "review",
+        "ready-for-dev" | "drafted" => "ready",
+        _ => "todo",
+    }
+}
+
+/// Render a workflow as a Mermaid flowchart with one subgraph per phase.
+///
+/// Phases are chained left to right; items are styled as complete, pending
+/// or skipped. A flowchart is used rather than a gantt chart because status
+/// files carry no dates.
+pub fn to_mermaid(data: &WorkflowData) -> String {
+    let mut out = String::from("flowchart LR\n");
+    let phases = group_by_phase(data);
+
+    let mut node = 0usize;
+    for (index, (phase, items)) in phases.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "  subgraph p{}[\"{}\"]",
+            index,
+            escape_mermaid(&phase.title())
+        );
+        out.push_str("    direction TB\n");
+        for item in items {
+            let class = match item.status_class() {
+                StatusClass::Complete => "complete",
+                StatusClass::Pending => "pending",
+                StatusClass::Skipped => "skipped",
+            };
+            let detail = item.output_file.as_deref().unwrap_or(&item.status);
+            let _ = writeln!(
+                out,
+                "    w{}[\"{}<br/>{}\"]:::{}",
+                node,
+                escape_mermaid(&item.id),
+                escape_mermaid(detail),
+                class
+            );
+            node += 1;
+        }
+        out.push_str("  end\n");
+    }
+
+    for index in 1..phases.len() {
+        let _ = writeln!(out, "  p{} --> p{}", index - 1, index);
+    }
+
+    out.push_str("  classDef complete fill:#d4edda,stroke:#28a745\n");
+    out.push_str("  classDef pending fill:#fff3cd,stroke:#ffc107\n");
+    out.push_str("  classDef skipped fill:#e2e3e5,stroke:#6c757d,stroke-dasharray: 4 2\n");
+    out
+}
+
+/// Render a sprint as a Mermaid flowchart with one subgraph per epic.
+///
+/// Stories are styled by status and `blocked_by` dependencies are drawn as
+/// edges from the blocker to the blocked story.
+pub fn sprint_to_mermaid(data: &SprintData) -> String {
+    let mut out = String::from("flowchart TB\n");
+    let mut node_ids: BTreeMap<&str, String> = BTreeMap::new();
+
+    let mut node = 0usize;
+    for (index, epic) in data.epics.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "  subgraph e{}[\"{} ({})\"]",
+            index,
+            escape_mermaid(&epic.name),
+            escape_mermaid(&epic.status)
+        );
+        out.push_str("    direction TB\n");
+        for story in &epic.stories {
+            let id = format!("s{}", node);
+            let _ = writeln!(
+                out,
+                "    {}[\"{}<br/>{}\"]:::{}",
+                id,
+                escape_mermaid(&story.id),
+                escape_mermaid(&story.status),
+                story_status_class(&story.status)
+            );
+            node_ids.insert(story.id.as_str(), id);
+            node += 1;
+        }
+        out.push_str("  end\n");
+    }
+
+    for story in data.epics.iter().flat_map(|e| e.stories.iter()) {
+        for blocker in &story.blocked_by {
+            if let (Some(from), Some(to)) = (
+                node_ids.get(blocker.as_str()),
+                node_ids.get(story.id.as_str()),
+            ) {
+                let _ = writeln!(out, "  {} --> {}", from, to);
+            }
+        }
+    }
+
+    out.push_str("  classDef done fill:#d4edda,stroke:#28a745\n");
+    out.push_str("  classDef review fill:#d1ecf1,stroke:#17a2b8\n");
+    out.push_str("  classDef active fill:#cce5ff,stroke:#007bff\n");
+    out.push_str("  classDef ready fill:#fff3cd,stroke:#ffc107\n");
+    out.push_str("  classDef todo fill:#f8f9fa,stroke:#6c757d\n");
+    out
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a delimiter, quote or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn push_csv_row(out: &mut String, fields: &[&str]) {
+    let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+    out.push_str(&row.join(","));
+    out.push('\n');
+}
+
+/// Column order for [`workflow_to_csv`]; kept stable for downstream spreadsheets
+pub const WORKFLOW_CSV_COLUMNS: &[&str] = &[
+    "id",
+    "phase",
+    "status",
+    "status_class",
+    "agent",
+    "command",
+    "output_file",
+    "note",
+];
+
+/// Column order for [`sprint_to_csv`]; kept stable for downstream spreadsheets
+pub const SPRINT_CSV_COLUMNS: &[&str] = &[
+    "epic_id",
+    "epic_name",
+    "epic_status",
+    "story_id",
+    "story_status",
+    "blocked_by",
+];
+
+/// Export workflow items as CSV with a header row.
+pub fn workflow_to_csv(data: &WorkflowData) -> String {
+    let mut out = String::new();
+    push_csv_row(&mut out, WORKFLOW_CSV_COLUMNS);
+
+    for item in &data.items {
+        let phase = match item.phase {
+            Phase::Number(n) => n.to_string(),
+            Phase::Prerequisite => "prerequisite".to_string(),
+        };
+        let class = match item.status_class() {
+            StatusClass::Complete => "complete",
+            StatusClass::Pending => "pending",
+            StatusClass::Skipped => "skipped",
+        };
+        push_csv_row(
+            &mut out,
+            &[
+                &item.id,
+                &phase,
+                &item.status,
+                class,
+                item.agent.as_deref().unwrap_or_default(),
+                item.command.as_deref().unwrap_or_default(),
+                item.output_file.as_deref().unwrap_or_default(),
+                item.note.as_deref().unwrap_or_default(),
+            ],
+        );
+    }
+    out
+}
+
+/// Export sprint stories as CSV with a header row, one row per story.
+///
+/// Multiple blockers are joined with `;`.
+pub fn sprint_to_csv(data: &SprintData) -> String {
+    let mut out = String::new();
+    push_csv_row(&mut out, SPRINT_CSV_COLUMNS);
+
+    for epic in &data.epics {
+        for story in &epic.stories {
+            push_csv_row(
+                &mut out,
+                &[
+                    &epic.id,
+                    &epic.name,
+                    &epic.status,
+                    &story.id,
+                    &story.status,
+                    &story.blocked_by.join(";"),
+                ],
+            );
+        }
+    }
+    out
+}
+
+/// A story card on a Kanban board
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct KanbanCard {
+    pub id: String,
+    pub epic_id: String,
+    /// True when the story has blockers that are not done
+    pub blocked: bool,
+}
+
+/// A status column on a Kanban board
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct KanbanColumn {
+    pub status: String,
+    pub title: String,
+    /// Number of cards in the column (work-in-progress count)
+    pub count: usize,
+    pub cards: Vec<KanbanCard>,
+}
+
+/// Sprint stories grouped into status columns
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct KanbanBoard {
+    pub project: String,
+    pub project_key: String,
+    pub columns: Vec<KanbanColumn>,
+}
+
+/// Standard sprint columns, always present on a board in this order
+const KANBAN_COLUMNS: &[(&str, &str)] = &[
+    ("backlog", "Backlog"),
+    ("drafted", "Drafted"),
+    ("ready-for-dev", "Ready for Dev"),
+    ("in-progress", "In Progress"),
+    ("review", "Review"),
+    ("done", "Done"),
+];
+
+/// Group sprint stories into Kanban columns by status.
+///
+/// The standard columns are always emitted (possibly empty); any other
+/// status gets its own column after them, in order of first appearance.
+pub fn to_kanban(data: &SprintData) -> KanbanBoard {
+    let mut columns: Vec<KanbanColumn> = KANBAN_COLUMNS
+        .iter()
+        .map(|(status, title)| KanbanColumn {
+            status: status.to_string(),
+            title: title.to_string(),
+            count: 0,
+            cards: Vec::new(),
+        })
+        .collect();
+
+    let blocked: HashSet<&str> = data
+        .blocked_stories()
+        .iter()
+        .map(|b| b.story.id.as_str())
+        .collect();
+
+    for story in data.epics.iter().flat_map(|e| e.stories.iter()) {
+        let index = match columns.iter().position(|c| c.status == story.status) {
+            Some(index) => index,
+            None => {
+                columns.push(KanbanColumn {
+                    status: story.status.to_string(),
+                    title: story.status.to_string(),
+                    count: 0,
+                    cards: Vec::new(),
+                });
+                columns.len() - 1
+            }
+        };
+        let column = &mut columns[index];
+        column.cards.push(KanbanCard {
+            id: story.id.clone(),
+            epic_id: story.epic_id.clone(),
+            blocked: blocked.contains(story.id.as_str()),
+        });
+        column.count += 1;
+    }
+
+    KanbanBoard {
+        project: data.project.clone(),
+        project_key: data.project_key.clone(),
+        columns,
+    }
+}
+
+/// Approximate width in pixels of `text` in 11px Verdana, the badge font.
+/// A fixed table rather than font metrics, so the same text always gives
+/// the same SVG.
+fn badge_text_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '!' | '|' | '\'' => 3,
+            'f' | 'r' | 't' | 'I' | ' ' | '/' | '(' | ')' | '-' => 5,
+            'm' | 'w' | 'M' | 'W' | '%' => 10,
+            _ => 7,
+        })
+        .sum()
+}
+
+/// Escape text for an XML, SVG or HTML text node or attribute.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Shields.io colour for a share of work done: bright green when all is
+/// done, through green, yellow and orange to red, grey when there is none
+fn badge_color(done: usize, total: usize) -> &'static str {
+    if total == 0 {
+        return "#9f9f9f";
+    }
+    match done * 4 / total {
+        4.. => "#4c1",
+        3 => "#97ca00",
+        2 => "#dfb317",
+        1 => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+/// A flat shields-style badge reading `label | done/total`.
+fn progress_svg(label: &str, done: usize, total: usize) -> String {
+    let message = format!("{}/{}", done, total);
+    let label_width = badge_text_width(label) + 10;
+    let message_width = badge_text_width(&message) + 10;
+    let width = label_width + message_width;
+    let title = escape_xml(&format!("{}: {}", label, message));
+    let label = escape_xml(label);
+    // Text is positioned at 10x scale, as shields.io does, for crisp output
+    let label_x = label_width * 5;
+    let message_x = label_width * 10 + message_width * 5;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"20\" role=\"img\" aria-label=\"{}\">",
+        width, title
+    );
+    let _ = writeln!(out, "  <title>{}</title>", title);
+    out.push_str("  <linearGradient id=\"s\" x2=\"0\" y2=\"100%\">\n");
+    out.push_str("    <stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/>\n");
+    out.push_str("    <stop offset=\"1\" stop-opacity=\".1\"/>\n");
+    out.push_str("  </linearGradient>\n");
+    let _ = writeln!(
+        out,
+        "  <clipPath id=\"r\"><rect width=\"{}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>",
+        width
+    );
+    out.push_str("  <g clip-path=\"url(#r)\">\n");
+    let _ = writeln!(
+        out,
+        "    <rect width=\"{}\" height=\"20\" fill=\"#555\"/>",
+        label_width
+    );
+    let _ = writeln!(
+        out,
+        "    <rect x=\"{}\" width=\"{}\" height=\"20\" fill=\"{}\"/>",
+        label_width,
+        message_width,
+        badge_color(done, total)
+    );
+    let _ = writeln!(
+        out,
+        "    <rect width=\"{}\" height=\"20\" fill=\"url(#s)\"/>",
+        width
+    );
+    out.push_str("  </g>\n");
+    out.push_str(
+        "  <g fill=\"#fff\" text-anchor=\"middle\" \
+         font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"110\">\n",
+    );
+    for (x, text, text_width) in [
+        (label_x, &label, label_width - 10),
+        (message_x, &message, message_width - 10),
+    ] {
+        let _ = writeln!(
+            out,
+            "    <text x=\"{}\" y=\"150\" fill=\"#010101\" fill-opacity=\".3\" \
+             transform=\"scale(.1)\" textLength=\"{}\">{}</text>",
+            x,
+            text_width * 10,
+            text
+        );
+        let _ = writeln!(
+            out,
+            "    <text x=\"{}\" y=\"140\" transform=\"scale(.1)\" textLength=\"{}\">{}</text>",
+            x,
+            text_width * 10,
+            text
+        );
+    }
+    out.push_str("  </g>\n");
+    out.push_str("</svg>\n");
+    out
+}
+
+/// An SVG badge reading `workflow | 7/12`, for embedding in a README.
+///
+/// Counts complete items out of all items that are not skipped. The output
+/// depends only on those two numbers, so a regenerated badge only shows up
+/// in a diff when progress changed.
+pub fn progress_badge(data: &WorkflowData) -> String {
+    let complete = data.query().status_class(StatusClass::Complete).count();
+    let skipped = data.query().status_class(StatusClass::Skipped).count();
+    progress_svg("workflow", complete, data.items.len() - skipped)
+}
+
+/// An SVG badge reading `sprint | 4/9`: done stories out of all stories.
+/// Like [`progress_badge`], the same counts always give the same SVG.
+pub fn sprint_progress_badge(data: &SprintData) -> String {
+    let stories: Vec<_> = data.epics.iter().flat_map(|e| &e.stories).collect();
+    let done = stories.iter().filter(|story| is_done(story)).count();
+    progress_svg("sprint", done, stories.len())
+}
+
+/// One project's status files, as shown on [`html_dashboard`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectSnapshot {
+    pub name: String,
+    pub workflow: Option<WorkflowData>,
+    pub sprint: Option<SprintData>,
+    /// Stories left to do, oldest first, e.g. from [`burndown`]; drawn as
+    /// a sparkline when there are at least two points
+    pub burndown: Vec<usize>,
+}
+
+/// Stories of `sprint` not yet done at the end of each day on which the
+/// history log records a change to one of them, oldest first.
+///
+/// A story counts as done from its first entry to `done` or `completed`
+/// until an entry moves it elsewhere; stories never logged count as not
+/// done. Entries whose timestamp cannot be read are ignored.
+pub fn burndown(sprint: &SprintData, history: &[HistoryEntry]) -> Vec<usize> {
+    let aliases = StatusAliases::default();
+    let stories: HashSet<&str> = sprint
+        .epics
+        .iter()
+        .flat_map(|e| &e.stories)
+        .map(|story| story.id.as_str())
+        .collect();
+    let mut entries: Vec<(i64, &HistoryEntry)> = history
+        .iter()
+        .filter(|entry| stories.contains(entry.id.as_str()))
+        .filter_map(|entry| Some((parse_timestamp(&entry.timestamp)?, entry)))
+        .collect();
+    entries.sort_by_key(|(secs, _)| *secs);
+
+    let mut done: HashSet<&str> = HashSet::new();
+    let mut points = Vec::new();
+    let mut day = None;
+    for (secs, entry) in entries {
+        let today = secs.div_euclid(86_400);
+        if day.is_some_and(|day| day != today) {
+            points.push(stories.len() - done.len());
+        }
+        day = Some(today);
+        if aliases.normalize(&entry.to) == "done" {
+            done.insert(entry.id.as_str());
+        } else {
+            done.remove(entry.id.as_str());
+        }
+    }
+    if day.is_some() {
+        points.push(stories.len() - done.len());
+    }
+    points
+}
+
+/// Styles for [`html_dashboard`]; inline so the page stands alone
+const DASHBOARD_CSS: &str = "\
+body{font-family:system-ui,-apple-system,Segoe UI,sans-serif;margin:2rem auto;max-width:60rem;\
+padding:0 1rem;color:#24292f}
+h1{font-size:1.6rem}
+section{border-top:1px solid #d0d7de;padding-top:1rem;margin-top:2rem}
+.phase{display:grid;grid-template-columns:14rem 1fr 4rem;gap:.75rem;align-items:center;\
+margin:.3rem 0}
+.bar{background:#eaeef2;border-radius:4px;height:.6rem;overflow:hidden}
+.bar div{background:#2da44e;height:100%}
+.count{text-align:right;font-variant-numeric:tabular-nums}
+table{border-collapse:collapse;width:100%;margin:.5rem 0 1.5rem}
+caption{text-align:left;font-weight:600;padding:.3rem 0}
+th,td{text-align:left;padding:.3rem .5rem;border-bottom:1px solid #eaeef2}
+.status{font-family:ui-monospace,monospace;font-size:.85rem}
+.done{color:#1a7f37}
+.sparkline{vertical-align:middle}
+.muted{color:#57606a}
+";
+
+/// Percentage of `total` that is `done`, for a progress bar; 0 when empty
+fn percent(done: usize, total: usize) -> usize {
+    (done * 100).checked_div(total).unwrap_or(0)
+}
+
+/// `points` as an inline SVG polyline, highest value at the top
+fn sparkline(points: &[usize]) -> String {
+    const WIDTH: f64 = 160.0;
+    const HEIGHT: f64 = 32.0;
+    let max = points.iter().copied().max().unwrap_or(0).max(1) as f64;
+    let step = WIDTH / (points.len() - 1) as f64;
+    let coordinates: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| {
+            let y = 1.0 + (HEIGHT - 2.0) * (1.0 - value as f64 / max);
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect();
+    format!(
+        "<svg class=\"sparkline\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" \
+         role=\"img\" aria-label=\"Burndown: {}\"><polyline fill=\"none\" \
+         stroke=\"#0969da\" stroke-width=\"1.5\" points=\"{}\"/></svg>",
+        WIDTH,
+        HEIGHT,
+        WIDTH,
+        HEIGHT,
+        points
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+        coordinates.join(" ")
+    )
+}
+
+fn dashboard_workflow(out: &mut String, data: &WorkflowData) {
+    out.push_str("<h3>Workflow</h3>\n");
+    if data.items.is_empty() {
+        out.push_str("<p class=\"muted\">No workflow items.</p>\n");
+        return;
+    }
+    for (phase, items) in group_by_phase(data) {
+        let classes: Vec<StatusClass> = items.iter().map(|item| item.status_class()).collect();
+        let complete = classes
+            .iter()
+            .filter(|c| **c == StatusClass::Complete)
+            .count();
+        let total = classes
+            .iter()
+            .filter(|c| **c != StatusClass::Skipped)
+            .count();
+        let _ = writeln!(
+            out,
+            "<div class=\"phase\"><span>{}</span><div class=\"bar\"><div style=\"width:{}%\"></div></div>\
+             <span class=\"count\">{}/{}</span></div>",
+            escape_xml(&phase.title()),
+            percent(complete, total),
+            complete,
+            total
+        );
+    }
+}
+
+fn dashboard_sprint(out: &mut String, data: &SprintData, burndown: &[usize]) {
+    let stories: Vec<&Story> = data.epics.iter().flat_map(|e| &e.stories).collect();
+    let done = stories.iter().filter(|story| is_done(story)).count();
+    let _ = writeln!(
+        out,
+        "<h3>Sprint</h3>\n<p>{} of {} stories done",
+        done,
+        stories.len()
+    );
+    if let Some(goal) = &data.sprint_goal {
+        let _ = write!(out, " · {}", escape_xml(goal));
+    }
+    if burndown.len() >= 2 {
+        let _ = write!(out, " {}", sparkline(burndown));
+    }
+    out.push_str("</p>\n");
+
+    for epic in &data.epics {
+        let done = epic.stories.iter().filter(|story| is_done(story)).count();
+        let _ = writeln!(
+            out,
+            "<table>\n<caption>{} <span class=\"muted\">{} · {}/{}</span></caption>",
+            escape_xml(&epic.name),
+            escape_xml(&epic.status),
+            done,
+            epic.stories.len()
+        );
+        out.push_str("<thead><tr><th>Story</th><th>Title</th><th>Status</th><th>Assignee</th></tr></thead>\n<tbody>\n");
+        for story in &epic.stories {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td class=\"status{}\">{}</td><td>{}</td></tr>",
+                escape_xml(&story.id),
+                escape_xml(story.title.as_deref().unwrap_or_default()),
+                if is_done(story) { " done" } else { "" },
+                escape_xml(&story.status),
+                escape_xml(story.assignee.as_deref().unwrap_or_default())
+            );
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+}
+
+/// A self-contained HTML page summarizing `projects`, for publishing as a
+/// CI artifact.
+///
+/// Each project gets a progress bar per workflow phase (complete out of
+/// items not skipped), its sprint's done count with a burndown sparkline,
+/// and a table of stories per epic. Styles are inline and there is no
+/// script, so the page renders offline; nothing in it depends on the time
+/// of generation, so unchanged projects give an unchanged page.
+pub fn html_dashboard(projects: &[ProjectSnapshot]) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str("<title>Project status</title>\n");
+    let _ = writeln!(out, "<style>\n{}</style>", DASHBOARD_CSS);
+    out.push_str("</head>\n<body>\n<h1>Project status</h1>\n");
+    if projects.is_empty() {
+        out.push_str("<p class=\"muted\">No projects.</p>\n");
+    }
+    for project in projects {
+        let _ = writeln!(out, "<section>\n<h2>{}</h2>", escape_xml(&project.name));
+        if let Some(workflow) = &project.workflow {
+            dashboard_workflow(&mut out, workflow);
+        }
+        if let Some(sprint) = &project.sprint {
+            dashboard_sprint(&mut out, sprint, &project.burndown);
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// One test case of [`to_junit_xml_on`]: `None` passed, otherwise the
+/// element recording why not
+fn junit_outcome(
+    data: &WorkflowData,
+    item: &WorkflowItem,
+    state: ItemState,
+    today: &Date,
+) -> Option<String> {
+    match state {
+        ItemState::Skipped => Some("<skipped message=\"Skipped\"/>".to_string()),
+        ItemState::Blocked => {
+            let open: Vec<&str> = item
+                .depends_on
+                .iter()
+                .filter(|id| {
+                    data.items
+                        .iter()
+                        .find(|other| &other.id == *id)
+                        .is_none_or(|other| other.status_class() == StatusClass::Pending)
+                })
+                .map(String::as_str)
+                .collect();
+            let message = if open.is_empty() {
+                "Blocked by an earlier phase".to_string()
+            } else {
+                format!("Blocked by {}", open.join(", "))
+            };
+            Some(format!(
+                "<failure message=\"{}\" type=\"blocked\"/>",
+                escape_xml(&message)
+            ))
+        }
+        ItemState::Actionable => match item.due_date() {
+            Some(due) if due < *today => Some(format!(
+                "<failure message=\"{}\" type=\"overdue\"/>",
+                escape_xml(&format!(
+                    "Overdue since {}",
+                    item.due.as_deref().unwrap_or_default()
+                ))
+            )),
+            _ => Some(format!(
+                "<skipped message=\"{}\"/>",
+                escape_xml(&format!("Pending: {}", item.status))
+            )),
+        },
+        _ => None,
+    }
+}
+
+/// Workflow readiness as a JUnit XML report, for CI systems that only
+/// display test results. See [`to_junit_xml_on`]; overdue items are judged
+/// against today's UTC date.
+pub fn to_junit_xml(data: &WorkflowData) -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let today = parse_date(&format_utc_timestamp(secs)).expect("formatted timestamps parse");
+    to_junit_xml_on(data, &today)
+}
+
+/// Workflow readiness as a JUnit XML report, with `today` as the date
+/// overdue items are judged against.
+///
+/// Each phase is a test suite and each required item a test case: complete
+/// items pass, blocked items and items due before `today` fail, skipped
+/// items are skipped, and other pending items are skipped with a `Pending`
+/// message. Optional items and items outside the selected track are left
+/// out.
+pub fn to_junit_xml_on(data: &WorkflowData, today: &Date) -> String {
+    let states: HashMap<&str, ItemState> = data
+        .item_states()
+        .into_iter()
+        .map(|(item, state)| (item.id.as_str(), state))
+        .collect();
+    let mut suites = Vec::new();
+    let (mut tests, mut failures, mut skipped) = (0, 0, 0);
+    for (phase, items) in group_by_phase(data) {
+        let cases: Vec<(&WorkflowItem, Option<String>)> = items
+            .into_iter()
+            .filter_map(|item| {
+                let state = states[item.id.as_str()];
+                matches!(
+                    state,
+                    ItemState::Done
+                        | ItemState::Actionable
+                        | ItemState::Blocked
+                        | ItemState::Skipped
+                )
+                .then(|| (item, junit_outcome(data, item, state, today)))
+            })
+            .collect();
+        if cases.is_empty() {
+            continue;
+        }
+        let count = |kind: &str| {
+            cases
+                .iter()
+                .filter(|(_, outcome)| outcome.as_deref().is_some_and(|o| o.starts_with(kind)))
+                .count()
+        };
+        let (suite_failures, suite_skipped) = (count("<failure"), count("<skipped"));
+        tests += cases.len();
+        failures += suite_failures;
+        skipped += suite_skipped;
+
+        let classname = match phase {
+            Phase::Number(n) => format!("workflow.phase-{}", n),
+            Phase::Prerequisite => "workflow.prerequisites".to_string(),
+        };
+        let mut suite = format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n",
+            escape_xml(&phase.title()),
+            cases.len(),
+            suite_failures,
+            suite_skipped
+        );
+        for (item, outcome) in cases {
+            let _ = write!(
+                suite,
+                "    <testcase name=\"{}\" classname=\"{}\"",
+                escape_xml(&item.id),
+                classname
+            );
+            match outcome {
+                Some(outcome) => {
+                    let _ = writeln!(suite, ">\n      {}\n    </testcase>", outcome);
+                }
+                None => suite.push_str("/>\n"),
+            }
+        }
+        suite.push_str("  </testsuite>\n");
+        suites.push(suite);
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">",
+        escape_xml(&data.project),
+        tests,
+        failures,
+        skipped
+    );
+    for suite in suites {
+        out.push_str(&suite);
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Sprint stories as a board for the Obsidian Kanban plugin.
+///
+/// The columns are those of [`to_kanban`], each a `##` lane with one
+/// `- [ ]` card per story; the done lane is marked complete and its cards
+/// checked. A card reads `story-id: Title`, followed by the story's tags as
+/// `#tags`, its assignee as `@assignee`, its due date as `@{date}`, and
+/// `(blocked)` when it waits on an unfinished blocker. Empty lanes are kept
+/// so the board layout doesn't shift as stories move.
+pub fn to_obsidian_kanban(data: &SprintData) -> String {
+    let mut out = String::from("---\n\nkanban-plugin: basic\n\n---\n\n");
+    for column in to_kanban(data).columns {
+        let done = column.status == "done";
+        let _ = writeln!(out, "## {}\n", column.title);
+        if done {
+            out.push_str("**Complete**\n");
+        }
+        for card in &column.cards {
+            let _ = write!(out, "- [{}] {}", if done { 'x' } else { ' ' }, card.id);
+            if let Some(story) = data.find_story(&card.id) {
+                if let Some(title) = &story.title {
+                    let _ = write!(out, ": {}", title);
+                }
+                for tag in &story.tags {
+                    let _ = write!(out, " #{}", tag.replace(char::is_whitespace, "-"));
+                }
+                if let Some(assignee) = &story.assignee {
+                    let _ = write!(out, " @{}", assignee.replace(char::is_whitespace, "-"));
+                }
+                if let Some(due) = &story.due {
+                    let _ = write!(out, " @{{{}}}", due);
+                }
+            }
+            if card.blocked {
+                out.push_str(" (blocked)");
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out.push_str("\n%% kanban:settings\n```\n{\"kanban-plugin\":\"basic\"}\n```\n%%\n");
+    out
+}
+
+/// `text` with whitespace runs replaced by `-`, for todo.txt and
+/// Taskwarrior words that cannot contain spaces
+fn single_word(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+/// Priority letter for a story status: work under way first
+fn story_priority(status: &str) -> Option<char> {
+    match status {
+        "in-progress" => Some('A'),
+        "review" => Some('B'),
+        "ready-for-dev" => Some('C'),
+        _ => None,
+    }
+}
+
+/// Sprint stories as todo.txt lines, one per story in sprint order.
+///
+/// Done stories start with `x`; in-progress, review and ready-for-dev
+/// stories get priorities `(A)`, `(B)` and `(C)`. Each line reads
+/// `story-id: Title`, then the project and epic as `+projects`, tags as
+/// `@contexts`, and `due:`, `owner:` and `status:` key-value pairs. Use
+/// [`SprintData::assigned_to`] first to export one person's stories.
+pub fn to_todotxt(data: &SprintData) -> String {
+    let mut out = String::new();
+    for epic in &data.epics {
+        for story in &epic.stories {
+            if is_done(story) {
+                out.push_str("x ");
+            } else if let Some(priority) = story_priority(&story.status) {
+                let _ = write!(out, "({}) ", priority);
+            }
+            out.push_str(&story.id);
+            if let Some(title) = &story.title {
+                let _ = write!(out, ": {}", title);
+            }
+            if !data.project.is_empty() {
+                let _ = write!(out, " +{}", single_word(&data.project));
+            }
+            let _ = write!(out, " +{}", epic.id);
+            for tag in &story.tags {
+                let _ = write!(out, " @{}", single_word(tag));
+            }
+            if let Some(due) = &story.due {
+                let _ = write!(out, " due:{}", due);
+            }
+            if let Some(assignee) = &story.assignee {
+                let _ = write!(out, " owner:{}", single_word(assignee));
+            }
+            let _ = writeln!(out, " status:{}", story.status);
+        }
+    }
+    out
+}
+
+/// A UUID derived from the project and story ID, so re-importing an export
+/// updates Taskwarrior's task instead of adding a duplicate
+fn story_uuid(project: &str, id: &str) -> String {
+    let high = fnv1a(format!("clique:{}:{}", project, id).as_bytes());
+    let low = fnv1a(format!("{}:{}:clique", id, project).as_bytes());
+    // Version 4 and RFC 4122 variant bits, so tools accept it as a UUID
+    let high = (high & 0xffff_ffff_ffff_0fff) | 0x4000;
+    let low = (low & 0x3fff_ffff_ffff_ffff) | 0x8000_0000_0000_0000;
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
+
+/// Sprint stories as a JSON array for `task import`, one task per story in
+/// sprint order.
+///
+/// Each task has a stable `uuid`, so importing a newer export updates the
+/// same tasks. The description is the title, or the story ID without one;
+/// the project is `<project>.<epic-id>`; done stories are `completed` and
+/// the rest `pending`, prioritized `H`, `M` and `L` for in-progress, review
+/// and ready-for-dev. Tags, due date and assignee carry over, and the story
+/// ID and status are kept in `clique_id` and `clique_status`. Use
+/// [`SprintData::assigned_to`] first to export one person's stories.
+pub fn to_taskwarrior_json(data: &SprintData) -> String {
+    let tasks: Vec<serde_json::Value> = data
+        .epics
+        .iter()
+        .flat_map(|epic| epic.stories.iter().map(move |story| (epic, story)))
+        .map(|(epic, story)| {
+            let mut task = serde_json::Map::new();
+            let mut set = |key: &str, value: serde_json::Value| {
+                task.insert(key.to_string(), value);
+            };
+            set("uuid", story_uuid(&data.project, &story.id).into());
+            set(
+                "description",
+                story
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| story.id.clone())
+                    .into(),
+            );
+            let project = single_word(&data.project);
+            set(
+                "project",
+                if project.is_empty() {
+                    epic.id.clone()
+                } else {
+                    format!("{}.{}", project, epic.id)
+                }
+                .into(),
+            );
+            let status = if is_done(story) {
+                "completed"
+            } else {
+                "pending"
+            };
+            set("status", status.into());
+            if let Some(priority) = story_priority(&story.status) {
+                let priority = match priority {
+                    'A' => "H",
+                    'B' => "M",
+                    _ => "L",
+                };
+                set("priority", priority.into());
+            }
+            if !story.tags.is_empty() {
+                let tags: Vec<String> = story.tags.iter().map(|tag| single_word(tag)).collect();
+                set("tags", tags.into());
+            }
+            // Taskwarrior's own date format: ISO 8601 basic, in UTC
+            if let Some(due) = story.due_date() {
+                set(
+                    "due",
+                    format!("{}T000000Z", due.to_string().replace('-', "")).into(),
+                );
+            }
+            if let Some(assignee) = &story.assignee {
+                set("assignee", assignee.clone().into());
+            }
+            set("clique_id", story.id.clone().into());
+            set("clique_status", story.status.to_string().into());
+            serde_json::Value::Object(task)
+        })
+        .collect();
+    serde_json::to_string_pretty(&tasks).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::parse_sprint_status;
+    use crate::workflow::parse_workflow_status;
+
+    const YAML: &str = r#"
+last_updated: 2025-12-01
+status: active
+status_note: On track
+project: Demo Project
+workflows:
+  brainstorm:
+    status: complete
+    output_file: docs/brainstorm.md
+  research:
+    status: skipped
+  prd:
+    status: not_started
+  architecture:
+    status: complete
+    output_file: docs/my architecture.md
+"#;
+
+    #[test]
+    fn test_workflow_markdown_structure() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let md = workflow_markdown(&data);
+
+        assert!(md.starts_with("# Workflow Status: Demo Project\n"));
+        assert!(md.contains("_Last updated: 2025-12-01 · Status: active_"));
+        assert!(md.contains("> On track"));
+        assert!(md.contains("**Progress:** 2/4 complete (1 skipped)"));
+        assert!(md.ends_with('\n'));
+        assert!(!md.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn test_workflow_markdown_phases_in_order() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let md = workflow_markdown(&data);
+
+        let discovery = md.find("## Phase 0: Discovery").expect("Phase 0 heading");
+        let planning = md.find("## Phase 1: Planning").expect("Phase 1 heading");
+        let solutioning = md.find("## Phase 2: Solutioning").expect("Phase 2 heading");
+        assert!(discovery < planning && planning < solutioning);
+        assert!(!md.contains("Phase 3"));
+    }
+
+    #[test]
+    fn test_workflow_markdown_item_lines() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let md = workflow_markdown(&data);
+
+        assert!(
+            md.contains("- [x] brainstorm — [docs/brainstorm.md](docs/brainstorm.md) _(analyst)_")
+        );
+        assert!(md.contains("- [ ] ~~research~~ (skipped)"));
+        assert!(md.contains("- [ ] **prd** — required _(pm)_"));
+        // Paths with spaces use angle-bracket link targets
+        assert!(md.contains("(<docs/my architecture.md>)"));
+    }
+
+    #[test]
+    fn test_workflow_markdown_escapes_text() {
+        let yaml = r#"
+project: "Project *bold*"
+workflow_status:
+  my_item: required
+"#;
+        let data = parse_workflow_status(yaml).expect("Should parse");
+        let md = workflow_markdown(&data);
+        assert!(md.contains("# Workflow Status: Project \\*bold\\*"));
+        assert!(md.contains("**my\\_item**"));
+    }
+
+    #[test]
+    fn test_workflow_markdown_empty() {
+        let data = parse_workflow_status("project: Empty").expect("Should parse");
+        let md = workflow_markdown(&data);
+        assert!(md.contains("**Progress:** 0/0 complete"));
+        assert!(!md.contains("## "));
+    }
+
+    // =========================================================================
+    // Mermaid Tests
+    // =========================================================================
+
+    const SPRINT_YAML: &str = r#"
+project: Mermaid Sprint
+project_key: MER
+development_status:
+  epic-1: in-progress
+  1-login: done
+  1-profile:
+    status: in-progress
+    blocked_by: [1-login]
+  epic-2: backlog
+  2-search: backlog
+"#;
+
+    #[test]
+    fn test_to_mermaid_workflow() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let chart = to_mermaid(&data);
+
+        assert!(chart.starts_with("flowchart LR\n"));
+        assert!(chart.contains("  subgraph p0[\"Phase 0: Discovery\"]"));
+        assert!(chart.contains("  subgraph p2[\"Phase 2: Solutioning\"]"));
+        assert!(chart.contains("[\"brainstorm<br/>docs/brainstorm.md\"]:::complete"));
+        assert!(chart.contains("[\"research<br/>skipped\"]:::skipped"));
+        assert!(chart.contains("[\"prd<br/>required\"]:::pending"));
+        assert!(chart.contains("  p0 --> p1\n  p1 --> p2\n"));
+        assert_eq!(chart.matches("  end\n").count(), 3);
+    }
+
+    #[test]
+    fn test_to_mermaid_escapes_labels() {
+        let yaml = r#"
+project: Escape
+workflow_status:
+  'say "hi"': required
+"#;
+        let data = parse_workflow_status(yaml).expect("Should parse");
+        let chart = to_mermaid(&data);
+        assert!(chart.contains("say #quot;hi#quot;"));
+    }
+
+    #[test]
+    fn test_sprint_to_mermaid() {
+        let data = parse_sprint_status(SPRINT_YAML).expect("Should parse");
+        let chart = sprint_to_mermaid(&data);
+
+        assert!(chart.starts_with("flowchart TB\n"));
+        assert!(chart.contains("  subgraph e0[\"Epic 1 (in-progress)\"]"));
+        assert!(chart.contains("  subgraph e1[\"Epic 2 (backlog)\"]"));
+        assert!(chart.contains("    s0[\"1-login<br/>done\"]:::done"));
+        assert!(chart.contains("    s1[\"1-profile<br/>in-progress\"]:::active"));
+        assert!(chart.contains("    s2[\"2-search<br/>backlog\"]:::todo"));
+        // Dependency edge from blocker to blocked story
+        assert!(chart.contains("  s0 --> s1\n"));
+    }
+
+    #[test]
+    fn test_mermaid_is_deterministic() {
+        let data = parse_sprint_status(SPRINT_YAML).expect("Should parse");
+        assert_eq!(sprint_to_mermaid(&data), sprint_to_mermaid(&data));
+    }
+
+    // =========================================================================
+    // CSV Tests
+    // =========================================================================
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn test_workflow_to_csv() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let csv = workflow_to_csv(&data);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "id,phase,status,status_class,agent,command,output_file,note"
+        );
+        assert_eq!(lines.len(), data.items.len() + 1);
+        assert!(lines.contains(
+            &"brainstorm,0,docs/brainstorm.md,complete,analyst,brainstorm,docs/brainstorm.md,"
+        ));
+        assert!(lines.contains(&"prd,1,required,pending,pm,prd,,"));
+    }
+
+    #[test]
+    fn test_workflow_to_csv_quotes_notes() {
+        let yaml = r#"
+project: Notes
+workflows:
+  prd:
+    status: not_started
+    notes: "Blocked on legal, see \"ticket\""
+"#;
+        let data = parse_workflow_status(yaml).expect("Should parse");
+        let csv = workflow_to_csv(&data);
+        assert!(csv.contains(",\"Blocked on legal, see \"\"ticket\"\"\"\n"));
+    }
+
+    #[test]
+    fn test_sprint_to_csv() {
+        let data = parse_sprint_status(SPRINT_YAML).expect("Should parse");
+        let csv = sprint_to_csv(&data);
+
+        assert_eq!(
+            csv,
+            "epic_id,epic_name,epic_status,story_id,story_status,blocked_by\n\
+             epic-1,Epic 1,in-progress,1-login,done,\n\
+             epic-1,Epic 1,in-progress,1-profile,in-progress,1-login\n\
+             epic-2,Epic 2,backlog,2-search,backlog,\n"
+        );
+    }
+
+    // =========================================================================
+    // Kanban Tests
+    // =========================================================================
+
+    #[test]
+    fn test_to_kanban_columns() {
+        let data = parse_sprint_status(SPRINT_YAML).expect("Should parse");
+        let board = to_kanban(&data);
+
+        let statuses: Vec<&str> = board.columns.iter().map(|c| c.status.as_str()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                "backlog",
+                "drafted",
+                "ready-for-dev",
+                "in-progress",
+                "review",
+                "done"
+            ]
+        );
+        assert_eq!(board.project_key, "MER");
+
+        let counts: Vec<usize> = board.columns.iter().map(|c| c.count).collect();
+        assert_eq!(counts, vec![1, 0, 0, 1, 0, 1]);
+
+        let in_progress = &board.columns[3];
+        assert_eq!(in_progress.cards[0].id, "1-profile");
+        assert_eq!(in_progress.cards[0].epic_id, "epic-1");
+        // Its blocker is done, so it is not flagged
+        assert!(!in_progress.cards[0].blocked);
+    }
+
+    #[test]
+    fn test_to_kanban_custom_status_and_blocked() {
+        let yaml = r#"
+project: Custom
+project_key: CUS
+development_status:
+  epic-1: in-progress
+  1-a: on-hold
+  1-b:
+    status: backlog
+    blocked_by: [1-a]
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        let board = to_kanban(&data);
+
+        let last = board.columns.last().unwrap();
+        assert_eq!(last.status, "on-hold");
+        assert_eq!(last.count, 1);
+
+        let backlog = &board.columns[0];
+        assert!(backlog.cards[0].blocked);
+    }
+
+    #[test]
+    fn test_to_kanban_serialization() {
+        let data = parse_sprint_status(SPRINT_YAML).expect("Should parse");
+        let json = serde_json::to_string(&to_kanban(&data)).expect("Should serialize");
+        assert!(json.contains("\"projectKey\":\"MER\""));
+        assert!(json.contains("\"epicId\":\"epic-1\""));
+        assert!(json.contains("\"title\":\"In Progress\""));
+    }
+
+    // =========================================================================
+    // Badge Tests
+    // =========================================================================
+
+    #[test]
+    fn test_progress_badge() {
+        let data = parse_workflow_status(YAML).expect("Should parse");
+        let svg = progress_badge(&data);
+        // Two of the three items that are not skipped
+        assert!(svg.contains("aria-label=\"workflow: 2/3\""));
+        assert!(svg.contains(">2/3</text>"));
+        assert!(svg.contains("fill=\"#dfb317\""));
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        // Deterministic: same data, same bytes
+        assert_eq!(svg, progress_badge(&data));
+    }
+
+    #[test]
+    fn test_sprint_progress_badge() {
+        let data = parse_sprint_status(SPRINT_YAML).expect("Should parse");
+        let svg = sprint_progress_badge(&data);
+        assert!(svg.contains("<title>sprint: 1/3</title>"));
+        assert!(svg.contains("fill=\"#fe7d37\""));
+
+        let empty = parse_sprint_status("development_status: {}").expect("Should parse");
+        assert!(sprint_progress_badge(&empty).contains("fill=\"#9f9f9f\""));
+    }
+
+    #[test]
+    fn test_badge_helpers() {
+        assert_eq!(badge_color(4, 4), "#4c1");
+        assert_eq!(badge_color(3, 4), "#97ca00");
+        assert_eq!(badge_color(0, 4), "#e05d44");
+        assert_eq!(escape_xml("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+        assert!(badge_text_width("workflow") > badge_text_width("sprint"));
+    }
+
+    // =========================================================================
+    // Dashboard Tests
+    // =========================================================================
+
+    #[test]
+    fn test_burndown() {
+        let data = parse_sprint_status(SPRINT_YAML).expect("Should parse");
+        let history = crate::history::parse(
+            r#"
+- timestamp: 2025-03-01T09:00:00Z
+  id: 1-profile
+  to: in-progress
+- timestamp: 2025-03-02T09:00:00Z
+  id: 1-login
+  to: done
+- timestamp: 2025-03-02T17:00:00Z
+  id: 1-profile
+  to: completed
+- timestamp: 2025-03-03T09:00:00Z
+  id: 1-profile
+  to: review
+- timestamp: 2025-03-04T09:00:00Z
+  id: not-in-sprint
+  to: done
+- timestamp: whenever
+  id: 1-login
+  to: backlog
+"#,
+        )
+        .expect("Should parse");
+        assert_eq!(burndown(&data, &history), vec![3, 1, 2]);
+        assert!(burndown(&data, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_html_dashboard() {
+        let mut sprint = parse_sprint_status(SPRINT_YAML).expect("Should parse");
+        sprint.epics[0].name = "Auth <core>".to_string();
+        let projects = [ProjectSnapshot {
+            name: "Demo & Co".to_string(),
+            workflow: Some(parse_workflow_status(YAML).expect("Should parse")),
+            sprint: Some(sprint),
+            burndown: vec![3, 2, 2, 1],
+        }];
+        let html = html_dashboard(&projects);
+
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.ends_with("</html>\n"));
+        assert!(html.contains("<h2>Demo &amp; Co</h2>"));
+        assert!(html.contains("<caption>Auth &lt;core&gt;"));
+        assert!(html.contains("1 of 3 stories done"));
+        assert!(html.contains("aria-label=\"Burndown: 3, 2, 2, 1\""));
+        assert!(html.contains("points=\"0.0,1.0 53.3,11.0 106.7,11.0 160.0,21.0\""));
+        // No scripts or external resources
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<link"));
+        assert_eq!(html, html_dashboard(&projects));
+    }
+
+    #[test]
+    fn test_html_dashboard_phases() {
+        let projects = [ProjectSnapshot {
+            name: "Demo".to_string(),
+            workflow: Some(parse_workflow_status(YAML).expect("Should parse")),
+            ..ProjectSnapshot::default()
+        }];
+        let html = html_dashboard(&projects);
+        let phases: Vec<&str> = html
+            .lines()
+            .filter(|line| line.starts_with("<div class=\"phase\">"))
+            .collect();
+        assert!(!phases.is_empty());
+        assert!(!html.contains("<h3>Sprint</h3>"));
+        assert!(html_dashboard(&[]).contains("No projects."));
+    }
+
+    // =========================================================================
+    // JUnit Tests
+    // =========================================================================
+
+    #[test]
+    fn test_to_junit_xml() {
+        let yaml = r#"
+project: "R&D"
+workflows:
+  brainstorm:
+    status: complete
+    phase: 0
+  research:
+    status: skipped
+    phase: 0
+  ideas:
+    status: optional
+    phase: 0
+  prd:
+    status: required
+    phase: 1
+    due: 2025-03-01
+  ux:
+    status: required
+    phase: 1
+    due: 2025-06-01
+  architecture:
+    status: required
+    phase: 2
+    depends_on: [prd]
+"#;
+        let data = parse_workflow_status(yaml).expect("Should parse");
+        let today = parse_date("2025-04-01").unwrap();
+        let xml = to_junit_xml_on(&data, &today);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(xml.contains(
+            "<testsuites name=\"R&amp;D\" tests=\"5\" failures=\"2\" errors=\"0\" skipped=\"2\">"
+        ));
+        assert!(xml.contains("<testcase name=\"brainstorm\" classname=\"workflow.phase-0\"/>"));
+        assert!(!xml.contains("\"ideas\""));
+        assert!(xml.contains("<skipped message=\"Skipped\"/>"));
+        assert!(xml.contains("<failure message=\"Overdue since 2025-03-01\" type=\"overdue\"/>"));
+        assert!(xml.contains("<skipped message=\"Pending: required\"/>"));
+        assert!(xml.contains("<failure message=\"Blocked by prd\" type=\"blocked\"/>"));
+        assert!(xml.ends_with("</testsuites>\n"));
+        // Every opened element is closed
+        assert_eq!(
+            xml.matches("<testsuite ").count(),
+            xml.matches("</testsuite>").count()
+        );
+    }
+
+    #[test]
+    fn test_to_junit_xml_empty() {
+        let data = parse_workflow_status("project: Empty").expect("Should parse");
+        let xml = to_junit_xml(&data);
+        assert!(xml.contains("tests=\"0\" failures=\"0\""));
+        assert!(!xml.contains("<testsuite "));
+    }
+
+    // =========================================================================
+    // Obsidian Tests
+    // =========================================================================
+
+    #[test]
+    fn test_to_obsidian_kanban() {
+        let yaml = r#"
+project: Board
+development_status:
+  epic-1: in-progress
+  1-a:
+    status: done
+  1-b:
+    status: in-progress
+    tags: [ui, quick win]
+    assignee: sam
+    due: 2025-03-01
+    blocked_by: [1-c]
+  1-c: review
+"#;
+        let mut data = parse_sprint_status(yaml).expect("Should parse");
+        data.epics[0].stories[1].title = Some("Profile page".to_string());
+        let board = to_obsidian_kanban(&data);
+
+        assert!(board.starts_with("---\n\nkanban-plugin: basic\n\n---\n\n## Backlog\n\n"));
+        assert!(board.contains(
+            "## In Progress\n\n- [ ] 1-b: Profile page #ui #quick-win @sam @{2025-03-01} (blocked)\n"
+        ));
+        assert!(board.contains("## Review\n\n- [ ] 1-c\n"));
+        assert!(board.contains("## Done\n\n**Complete**\n- [x] 1-a\n"));
+        assert!(
+            board.ends_with("%% kanban:settings\n```\n{\"kanban-plugin\":\"basic\"}\n```\n%%\n")
+        );
+    }
+
+    // =========================================================================
+    // Task Manager Tests
+    // =========================================================================
+
+    const TASKS_YAML: &str = r#"
+project: Demo App
+development_status:
+  epic-1: in-progress
+  1-login:
+    status: in-progress
+    assignee: Sam
+    tags: [auth, quick win]
+    due: 2025-03-01
+  1-signup: { status: done, assignee: Sam }
+  1-profile: { status: backlog, assignee: Ari }
+"#;
+
+    #[test]
+    fn test_to_todotxt() {
+        let mut data = parse_sprint_status(TASKS_YAML).expect("Should parse");
+        data.epics[0].stories[0].title = Some("Log in".to_string());
+        assert_eq!(
+            to_todotxt(&data),
+            "(A) 1-login: Log in +Demo-App +epic-1 @auth @quick-win due:2025-03-01 \
+             owner:Sam status:in-progress\n\
+             x 1-signup +Demo-App +epic-1 owner:Sam status:done\n\
+             1-profile +Demo-App +epic-1 owner:Ari status:backlog\n"
+        );
+        let sams = to_todotxt(&data.assigned_to("sam"));
+        assert_eq!(sams.lines().count(), 2);
+        assert!(!sams.contains("owner:Ari"));
+    }
+
+    #[test]
+    fn test_to_taskwarrior_json() {
+        let data = parse_sprint_status(TASKS_YAML).expect("Should parse");
+        let json = to_taskwarrior_json(&data);
+        let tasks: Vec<serde_json::Value> = serde_json::from_str(&json).expect("Valid JSON");
+        assert_eq!(tasks.len(), 3);
+
+        let login = &tasks[0];
+        assert_eq!(login["description"], "1-login");
+        assert_eq!(login["project"], "Demo-App.epic-1");
+        assert_eq!(login["status"], "pending");
+        assert_eq!(login["priority"], "H");
+        assert_eq!(login["tags"], serde_json::json!(["auth", "quick-win"]));
+        assert_eq!(login["due"], "20250301T000000Z");
+        assert_eq!(login["assignee"], "Sam");
+        assert_eq!(login["clique_id"], "1-login");
+        assert_eq!(tasks[1]["status"], "completed");
+        assert!(tasks[2].get("priority").is_none());
+
+        // Stable, well-formed and distinct UUIDs
+        let uuid = login["uuid"].as_str().unwrap();
+        assert_eq!(uuid, story_uuid("Demo App", "1-login"));
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+        assert_ne!(tasks[1]["uuid"], login["uuid"]);
+        assert_eq!(to_taskwarrior_json(&parse_sprint_status("").unwrap()), "[]");
+    }
+}
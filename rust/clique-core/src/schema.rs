@@ -0,0 +1,477 @@
+// clique-core/src/schema.rs
+//! Structural validation of status files against a bundled schema.
+//!
+//! The parsers are deliberately permissive: a mistyped key or a list where a
+//! string was expected simply yields an empty field. This module reports those
+//! mistakes explicitly, with a dotted path to the offending node.
+
+use crate::pattern::{sprint_key, workflow_id};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+/// Which kind of status file to validate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// bmm-workflow-status.yaml
+    Workflow,
+    /// sprint-status.yaml
+    Sprint,
+}
+
+/// The kind of structural problem found
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ViolationKind {
+    /// The document is not valid YAML at all
+    InvalidYaml,
+    /// A node has the wrong YAML type
+    WrongType { expected: String, found: String },
+    /// A key that the schema does not know about
+    UnknownKey,
+    /// A workflow or story ID that does not follow naming conventions
+    MalformedId,
+}
+
+/// A single schema violation with the path of the offending node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaViolation {
+    /// Dotted path to the node, e.g. `workflows.prd.status`
+    pub path: String,
+    #[serde(flatten)]
+    pub kind: ViolationKind,
+    pub message: String,
+}
+
+/// Expected shape of a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    /// String, or a scalar that reads naturally as one (number, bool)
+    Scalar,
+    Number,
+    Mapping,
+    /// A sequence of scalars, or a single scalar
+    List,
+    /// Mapping or sequence (workflow_status supports both layouts)
+    MappingOrSequence,
+}
+
+const WORKFLOW_TOP_LEVEL: &[(&str, Expect)] = &[
+    ("generated", Expect::Scalar),
+    ("last_updated", Expect::Scalar),
+    ("status", Expect::Scalar),
+    ("status_note", Expect::Scalar),
+    ("project", Expect::Scalar),
+    ("project_name", Expect::Scalar),
+    ("project_type", Expect::Scalar),
+    ("selected_track", Expect::Scalar),
+    ("field_type", Expect::Scalar),
+    ("workflow_path", Expect::Scalar),
+    ("workflows", Expect::Mapping),
+    ("workflow_status", Expect::MappingOrSequence),
+];
+
+const WORKFLOW_ITEM_FIELDS: &[(&str, Expect)] = &[
+    ("status", Expect::Scalar),
+    ("output_file", Expect::Scalar),
+    ("notes", Expect::Scalar),
+    ("note", Expect::Scalar),
+    ("depends_on", Expect::List),
+    ("tracks", Expect::List),
+    ("due", Expect::Scalar),
+    ("tags", Expect::List),
+];
+
+const OLD_FORMAT_ITEM_FIELDS: &[(&str, Expect)] = &[
+    ("id", Expect::Scalar),
+    ("phase", Expect::Number),
+    ("status", Expect::Scalar),
+    ("agent", Expect::Scalar),
+    ("command", Expect::Scalar),
+    ("note", Expect::Scalar),
+    ("depends_on", Expect::List),
+    ("tracks", Expect::List),
+    ("due", Expect::Scalar),
+    ("tags", Expect::List),
+];
+
+const SPRINT_TOP_LEVEL: &[(&str, Expect)] = &[
+    ("generated", Expect::Scalar),
+    ("project", Expect::Scalar),
+    ("project_key", Expect::Scalar),
+    ("sprint_goal", Expect::Scalar),
+    ("sprint_start", Expect::Scalar),
+    ("sprint_end", Expect::Scalar),
+    ("tracking_system", Expect::Scalar),
+    ("story_location", Expect::Scalar),
+    ("development_status", Expect::Mapping),
+];
+
+const STORY_FIELDS: &[(&str, Expect)] = &[
+    ("status", Expect::Scalar),
+    ("blocked_by", Expect::List),
+    ("number", Expect::Number),
+    ("due", Expect::Scalar),
+    ("tags", Expect::List),
+    ("assignee", Expect::Scalar),
+    ("updated", Expect::Scalar),
+];
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Sequence(_) => "sequence",
+        Value::Mapping(_) => "mapping",
+        Value::Tagged(_) => "tagged",
+    }
+}
+
+fn expected_name(expect: Expect) -> &'static str {
+    match expect {
+        Expect::Scalar => "string",
+        Expect::Number => "number",
+        Expect::Mapping => "mapping",
+        Expect::List => "sequence",
+        Expect::MappingOrSequence => "mapping or sequence",
+    }
+}
+
+fn is_scalar(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_)
+    )
+}
+
+fn matches_expect(value: &Value, expect: Expect) -> bool {
+    match expect {
+        Expect::Scalar => is_scalar(value),
+        Expect::Number => value.is_number() || value.is_null(),
+        Expect::Mapping => value.is_mapping() || value.is_null(),
+        Expect::List => match value {
+            Value::Sequence(seq) => seq.iter().all(is_scalar),
+            other => is_scalar(other),
+        },
+        Expect::MappingOrSequence => value.is_mapping() || value.is_sequence() || value.is_null(),
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+fn key_label(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Check the keys of a mapping against a field table.
+fn check_fields(
+    map: &Mapping,
+    path: &str,
+    fields: &[(&str, Expect)],
+    violations: &mut Vec<SchemaViolation>,
+) {
+    for (key, value) in map {
+        let label = key_label(key);
+        let child = join_path(path, &label);
+        match fields.iter().find(|(name, _)| key.as_str() == Some(*name)) {
+            Some((_, expect)) => {
+                if !matches_expect(value, *expect) {
+                    violations.push(wrong_type(child, expected_name(*expect), value));
+                }
+            }
+            None => violations.push(SchemaViolation {
+                message: format!("Unknown key '{}'", child),
+                path: child,
+                kind: ViolationKind::UnknownKey,
+            }),
+        }
+    }
+}
+
+fn malformed_id(path: String, id: &str) -> SchemaViolation {
+    SchemaViolation {
+        message: format!("Malformed ID '{}' at '{}'", id, path),
+        path,
+        kind: ViolationKind::MalformedId,
+    }
+}
+
+fn wrong_type(path: String, expected: &str, value: &Value) -> SchemaViolation {
+    SchemaViolation {
+        message: format!(
+            "Expected {} at '{}', found {}",
+            expected,
+            path,
+            type_name(value)
+        ),
+        path,
+        kind: ViolationKind::WrongType {
+            expected: expected.to_string(),
+            found: type_name(value).to_string(),
+        },
+    }
+}
+
+fn validate_workflow(root: &Mapping, violations: &mut Vec<SchemaViolation>) {
+    check_fields(root, "", WORKFLOW_TOP_LEVEL, violations);
+
+    for section in ["workflows", "workflow_status"] {
+        let Some(entries) = root.get(section).and_then(|v| v.as_mapping()) else {
+            continue;
+        };
+        for (key, value) in entries {
+            let id = key_label(key);
+            let path = join_path(section, &id);
+            if !key.is_string() || !workflow_id(&id) {
+                violations.push(malformed_id(path.clone(), &id));
+            }
+            match (section, value) {
+                ("workflows", Value::Mapping(fields)) => {
+                    check_fields(fields, &path, WORKFLOW_ITEM_FIELDS, violations)
+                }
+                ("workflows", Value::Null) => {}
+                ("workflows", value) => violations.push(wrong_type(path, "mapping", value)),
+                (_, value) if is_scalar(value) => {}
+                (_, value) => violations.push(wrong_type(path, "string", value)),
+            }
+        }
+    }
+
+    if let Some(items) = root.get("workflow_status").and_then(|v| v.as_sequence()) {
+        for (index, item) in items.iter().enumerate() {
+            let path = format!("workflow_status[{}]", index);
+            match item.as_mapping() {
+                Some(fields) => {
+                    check_fields(fields, &path, OLD_FORMAT_ITEM_FIELDS, violations);
+                    if let Some(id) = fields.get("id").and_then(|v| v.as_str())
+                        && !workflow_id(id)
+                    {
+                        violations.push(malformed_id(format!("{}.id", path), id));
+                    }
+                }
+                None => violations.push(wrong_type(path, "mapping", item)),
+            }
+        }
+    }
+}
+
+fn validate_sprint(root: &Mapping, violations: &mut Vec<SchemaViolation>) {
+    check_fields(root, "", SPRINT_TOP_LEVEL, violations);
+
+    let Some(entries) = root.get("development_status").and_then(|v| v.as_mapping()) else {
+        return;
+    };
+    for (key, value) in entries {
+        let id = key_label(key);
+        let path = join_path("development_status", &id);
+        if !key.is_string() || !sprint_key(&id) {
+            violations.push(malformed_id(path.clone(), &id));
+        }
+        match value {
+            Value::Mapping(fields) => check_fields(fields, &path, STORY_FIELDS, violations),
+            value if is_scalar(value) => {}
+            value => violations.push(wrong_type(path, "string or mapping", value)),
+        }
+    }
+}
+
+/// Validate a status file against the bundled schema for its kind.
+///
+/// Returns every violation found; an empty list means the document is
+/// structurally sound. Invalid YAML yields a single `InvalidYaml` violation.
+pub fn validate_against_schema(content: &str, kind: SchemaKind) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+
+    let parsed: Value = match serde_yaml::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            violations.push(SchemaViolation {
+                path: String::new(),
+                kind: ViolationKind::InvalidYaml,
+                message: e.to_string(),
+            });
+            return violations;
+        }
+    };
+
+    let root = match &parsed {
+        Value::Mapping(map) => map,
+        Value::Null => return violations,
+        other => {
+            violations.push(wrong_type(String::new(), "mapping", other));
+            return violations;
+        }
+    };
+
+    match kind {
+        SchemaKind::Workflow => validate_workflow(root, &mut violations),
+        SchemaKind::Sprint => validate_sprint(root, &mut violations),
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(violations: &[SchemaViolation]) -> Vec<&str> {
+        violations.iter().map(|v| v.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_valid_workflow_has_no_violations() {
+        let yaml = r#"
+last_updated: 2025-12-01
+status: active
+project: Demo
+workflows:
+  brainstorm:
+    status: complete
+    output_file: docs/brainstorm.md
+  prd:
+    status: not_started
+    notes: Needs review
+"#;
+        assert!(validate_against_schema(yaml, SchemaKind::Workflow).is_empty());
+    }
+
+    #[test]
+    fn test_valid_flat_and_old_formats() {
+        let flat = "project: Demo\nworkflow_status:\n  prd: docs/prd.md\n";
+        assert!(validate_against_schema(flat, SchemaKind::Workflow).is_empty());
+
+        let old =
+            "project: Demo\nworkflow_status:\n  - id: prd\n    phase: 1\n    status: required\n";
+        assert!(validate_against_schema(old, SchemaKind::Workflow).is_empty());
+    }
+
+    #[test]
+    fn test_workflow_unknown_top_level_key() {
+        let yaml = "project: Demo\nprojct_type: typo\n";
+        let violations = validate_against_schema(yaml, SchemaKind::Workflow);
+        assert_eq!(paths(&violations), vec!["projct_type"]);
+        assert_eq!(violations[0].kind, ViolationKind::UnknownKey);
+    }
+
+    #[test]
+    fn test_workflow_wrong_types() {
+        let yaml = r#"
+project: [not, a, string]
+workflows:
+  prd:
+    status: {nested: true}
+    output_fle: docs/prd.md
+"#;
+        let violations = validate_against_schema(yaml, SchemaKind::Workflow);
+        assert_eq!(
+            paths(&violations),
+            vec![
+                "project",
+                "workflows.prd.status",
+                "workflows.prd.output_fle"
+            ]
+        );
+        assert_eq!(
+            violations[0].kind,
+            ViolationKind::WrongType {
+                expected: "string".to_string(),
+                found: "sequence".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_workflow_malformed_id() {
+        let yaml = "workflow_status:\n  'bad id': required\n  123: required\n";
+        let violations = validate_against_schema(yaml, SchemaKind::Workflow);
+        assert_eq!(
+            paths(&violations),
+            vec!["workflow_status.bad id", "workflow_status.123"]
+        );
+        assert!(
+            violations
+                .iter()
+                .all(|v| v.kind == ViolationKind::MalformedId)
+        );
+    }
+
+    #[test]
+    fn test_valid_sprint_has_no_violations() {
+        let yaml = r#"
+project: Demo
+project_key: DMO
+tracking_system: file-system
+development_status:
+  epic-1: in-progress
+  1-login: done
+  1-profile:
+    status: backlog
+    blocked_by: [1-login]
+    number: 12
+  epic-1-retrospective: optional
+"#;
+        assert!(validate_against_schema(yaml, SchemaKind::Sprint).is_empty());
+    }
+
+    #[test]
+    fn test_sprint_violations() {
+        let yaml = r#"
+project: Demo
+project_kee: DMO
+development_status:
+  epic-1: in-progress
+  login-page: backlog
+  1-profile:
+    status: backlog
+    blocked_by: {1-login: true}
+  1-list: [a, b]
+"#;
+        let violations = validate_against_schema(yaml, SchemaKind::Sprint);
+        assert_eq!(
+            paths(&violations),
+            vec![
+                "project_kee",
+                "development_status.login-page",
+                "development_status.1-profile.blocked_by",
+                "development_status.1-list",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_yaml_and_non_mapping_root() {
+        let violations = validate_against_schema("[unclosed", SchemaKind::Sprint);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, ViolationKind::InvalidYaml);
+
+        let violations = validate_against_schema("- a\n- b\n", SchemaKind::Workflow);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            violations[0].kind,
+            ViolationKind::WrongType { .. }
+        ));
+
+        assert!(validate_against_schema("", SchemaKind::Workflow).is_empty());
+    }
+
+    #[test]
+    fn test_violation_serialization() {
+        let violations = validate_against_schema("bogus: 1", SchemaKind::Sprint);
+        let json = serde_json::to_string(&violations[0]).expect("Should serialize");
+        assert!(json.contains("\"kind\":\"unknownKey\""));
+        assert!(json.contains("\"path\":\"bogus\""));
+    }
+}
@@ -0,0 +1,360 @@
+// clique-core/src/settings.rs
+//! Parser settings that persist between calls from JavaScript.
+//!
+//! The bindings (`clique-wasm`, `clique-napi`) are configured once
+//! (`init_wasm`) instead of passing options to every call. Settings are per
+//! thread, which on wasm32 means per module instance.
+//! `set_parser_config_wasm` later swaps in the extension's own settings
+//! (status aliases, phase map, sort order) without touching the limits.
+
+use crate::config::Config;
+use crate::limits::ParseLimits;
+use crate::options::ParseOptions;
+use crate::sprint::{SprintError, parse_sprint_status_with_options};
+use crate::text::text;
+use crate::types::{Phase, SprintData, StatusAliases, WorkflowData, WorkflowItem};
+use crate::workflow::{WorkflowError, parse_workflow_status_with_options};
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Overrides for individual parse limits; unset fields keep their default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LimitOverrides {
+    pub max_bytes: Option<usize>,
+    pub max_line_length: Option<usize>,
+    pub max_items: Option<usize>,
+    pub max_aliases: Option<usize>,
+    pub max_nodes: Option<usize>,
+    pub max_depth: Option<usize>,
+}
+
+impl LimitOverrides {
+    /// The default limits with these overrides applied
+    pub fn to_limits(&self) -> ParseLimits {
+        let defaults = ParseLimits::default();
+        ParseLimits {
+            max_bytes: self.max_bytes.unwrap_or(defaults.max_bytes),
+            max_line_length: self.max_line_length.unwrap_or(defaults.max_line_length),
+            max_items: self.max_items.unwrap_or(defaults.max_items),
+            max_aliases: self.max_aliases.unwrap_or(defaults.max_aliases),
+            max_nodes: self.max_nodes.unwrap_or(defaults.max_nodes),
+            max_depth: self.max_depth.unwrap_or(defaults.max_depth),
+        }
+    }
+}
+
+/// The `options` object accepted by `init_wasm`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InitOptions {
+    pub limits: LimitOverrides,
+    /// Phase number by workflow ID, overriding the built-in BMad map
+    pub phase_map: HashMap<String, i32>,
+    /// Agent by workflow ID, overriding the built-in BMad map
+    pub agent_map: HashMap<String, String>,
+}
+
+/// Order of workflow items in parse results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    /// By phase, then ID (the core parser's order)
+    #[default]
+    Phase,
+    /// By ID alone
+    Id,
+    /// By status, then phase and ID, grouping items that are at the same stage
+    Status,
+}
+
+impl SortOrder {
+    fn sort(self, items: &mut [WorkflowItem]) {
+        match self {
+            SortOrder::Phase => {
+                items.sort_by(|a, b| a.phase.cmp(&b.phase).then_with(|| a.id.cmp(&b.id)))
+            }
+            SortOrder::Id => items.sort_by(|a, b| a.id.cmp(&b.id)),
+            SortOrder::Status => items.sort_by(|a, b| {
+                (*a.status)
+                    .cmp(&*b.status)
+                    .then_with(|| a.phase.cmp(&b.phase))
+                    .then_with(|| a.id.cmp(&b.id))
+            }),
+        }
+    }
+}
+
+/// The `config` object accepted by `set_parser_config_wasm`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParserConfig {
+    /// Team-specific status words mapped to canonical statuses, on top of
+    /// the built-in aliases. Any entry turns on status normalization.
+    pub status_aliases: HashMap<String, String>,
+    /// Phase number by workflow ID, overriding the built-in BMad map
+    pub phase_map: HashMap<String, i32>,
+    pub sort_order: SortOrder,
+}
+
+/// Settings applied to every parse
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Settings {
+    pub limits: ParseLimits,
+    /// Status normalization; `None` keeps statuses as written
+    pub aliases: Option<StatusAliases>,
+    pub phase_map: HashMap<String, i32>,
+    pub agent_map: HashMap<String, String>,
+    pub sort_order: SortOrder,
+}
+
+impl Settings {
+    /// Settings from `init_wasm` options; anything not given is the default.
+    pub fn from_init(options: InitOptions) -> Self {
+        Self {
+            limits: options.limits.to_limits(),
+            phase_map: options.phase_map,
+            agent_map: options.agent_map,
+            ..Self::default()
+        }
+    }
+
+    /// These settings with the aliases, phase map and sort order replaced by
+    /// `config`; limits and the agent map are kept.
+    pub fn with_config(self, config: ParserConfig) -> Self {
+        let aliases = (!config.status_aliases.is_empty()).then(|| {
+            config
+                .status_aliases
+                .iter()
+                .fold(StatusAliases::default(), |table, (alias, canonical)| {
+                    table.with_alias(alias, canonical)
+                })
+        });
+        Self {
+            aliases,
+            phase_map: config.phase_map,
+            sort_order: config.sort_order,
+            ..self
+        }
+    }
+
+    /// Options for the core `*_with_options` parsers
+    pub fn parse_options(&self) -> ParseOptions {
+        let options = ParseOptions::default().with_limits(self.limits.clone());
+        match &self.aliases {
+            Some(aliases) => options.with_normalize(aliases.clone()),
+            None => options,
+        }
+    }
+
+    /// Apply the custom phase and agent maps and the sort order to parsed
+    /// items.
+    pub fn apply(&self, data: &mut WorkflowData) {
+        for item in &mut data.items {
+            if let Some(&phase) = self.phase_map.get(&item.id) {
+                item.phase = Phase::Number(phase);
+            }
+            if let Some(agent) = self.agent_map.get(&item.id) {
+                item.agent = Some(text(agent.as_str()));
+            }
+        }
+        if !self.phase_map.is_empty() || self.sort_order != SortOrder::Phase {
+            self.sort_order.sort(&mut data.items);
+        }
+    }
+}
+
+thread_local! {
+    static SETTINGS: RefCell<Settings> = RefCell::new(Settings::default());
+}
+
+/// Replace the settings used by subsequent calls.
+pub fn set(settings: Settings) {
+    SETTINGS.with_borrow_mut(|current| *current = settings);
+}
+
+/// Replace the aliases, phase map and sort order used by subsequent calls,
+/// keeping the limits and agent map.
+pub fn configure(config: ParserConfig) {
+    SETTINGS.with_borrow_mut(|current| *current = std::mem::take(current).with_config(config));
+}
+
+/// Replace the aliases, phase and agent maps used by subsequent calls with
+/// those of a `.clique.toml` configuration, keeping the limits; the sort
+/// order returns to phase order.
+pub fn configure_from(config: &Config) {
+    SETTINGS.with_borrow_mut(|current| {
+        *current = Settings {
+            limits: current.limits.clone(),
+            ..config.settings()
+        }
+    });
+}
+
+/// A copy of the current settings
+pub fn current() -> Settings {
+    SETTINGS.with_borrow(Settings::clone)
+}
+
+/// Parse workflow status with the current settings.
+pub fn parse_workflow(content: &str) -> Result<WorkflowData, WorkflowError> {
+    SETTINGS.with_borrow(|settings| {
+        let mut data = parse_workflow_status_with_options(content, &settings.parse_options())?;
+        settings.apply(&mut data);
+        Ok(data)
+    })
+}
+
+/// Parse sprint status with the current settings.
+pub fn parse_sprint(content: &str) -> Result<SprintData, SprintError> {
+    SETTINGS.with_borrow(|settings| {
+        parse_sprint_status_with_options(content, &settings.parse_options())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // Settings Tests
+    // =========================================================================
+
+    #[test]
+    fn test_init_options_deserialize() {
+        let json =
+            r#"{"limits": {"maxItems": 5}, "phaseMap": {"prd": 2}, "agentMap": {"prd": "po"}}"#;
+        let options: InitOptions = serde_json::from_str(json).unwrap();
+        let settings = Settings::from_init(options);
+        assert_eq!(settings.limits.max_items, 5);
+        assert_eq!(settings.limits.max_bytes, ParseLimits::default().max_bytes);
+        assert_eq!(settings.phase_map["prd"], 2);
+
+        let empty: InitOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(Settings::from_init(empty), Settings::default());
+    }
+
+    #[test]
+    fn test_settings_persist_between_parses() {
+        let yaml = "workflow_status:\n  brainstorm: required\n  prd: required\n";
+        assert_eq!(parse_workflow(yaml).unwrap().items[0].id, "brainstorm");
+
+        set(Settings {
+            phase_map: HashMap::from([("brainstorm".to_string(), 4)]),
+            agent_map: HashMap::from([("prd".to_string(), "po".to_string())]),
+            ..Settings::default()
+        });
+        let data = parse_workflow(yaml).unwrap();
+        assert_eq!(data.items[0].id, "prd");
+        assert_eq!(data.items[0].agent.as_deref(), Some("po"));
+        assert_eq!(data.items[1].phase, Phase::Number(4));
+        assert_eq!(current().phase_map.len(), 1);
+
+        set(Settings {
+            limits: LimitOverrides {
+                max_items: Some(1),
+                ..LimitOverrides::default()
+            }
+            .to_limits(),
+            ..Settings::default()
+        });
+        assert!(parse_workflow(yaml).is_err());
+        assert!(parse_sprint("development_status:\n  epic-1: a\n  1-a: b\n").is_err());
+
+        set(Settings::default());
+        assert!(parse_workflow(yaml).is_ok());
+    }
+
+    #[test]
+    fn test_parser_config_deserialize() {
+        let json = r#"{"statusAliases": {"Shipped": "done"}, "sortOrder": "status"}"#;
+        let config: ParserConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.status_aliases["Shipped"], "done");
+        assert_eq!(config.sort_order, SortOrder::Status);
+        assert!(config.phase_map.is_empty());
+
+        assert_eq!(
+            serde_json::from_str::<ParserConfig>("{}").unwrap(),
+            ParserConfig::default()
+        );
+        assert!(serde_json::from_str::<ParserConfig>(r#"{"sortOrder": "random"}"#).is_err());
+    }
+
+    #[test]
+    fn test_configure_keeps_limits_and_agents() {
+        set(Settings {
+            limits: LimitOverrides {
+                max_items: Some(3),
+                ..LimitOverrides::default()
+            }
+            .to_limits(),
+            agent_map: HashMap::from([("prd".to_string(), "po".to_string())]),
+            ..Settings::default()
+        });
+        configure(ParserConfig {
+            status_aliases: HashMap::from([("shipped".to_string(), "done".to_string())]),
+            ..ParserConfig::default()
+        });
+        let settings = current();
+        assert_eq!(settings.limits.max_items, 3);
+        assert_eq!(settings.agent_map["prd"], "po");
+        assert!(settings.aliases.is_some());
+
+        configure(ParserConfig::default());
+        assert_eq!(current().aliases, None);
+        set(Settings::default());
+    }
+
+    #[test]
+    fn test_configure_from_config_file() {
+        set(Settings {
+            limits: ParseLimits::unlimited(),
+            ..Settings::default()
+        });
+        let config = crate::config::parse("[agents]\nprd = \"po\"\n").unwrap();
+        configure_from(&config);
+        let data = parse_workflow("workflow_status:\n  prd: required\n").unwrap();
+        assert_eq!(data.items[0].agent.as_deref(), Some("po"));
+        assert_eq!(current().limits, ParseLimits::unlimited());
+        set(Settings::default());
+    }
+
+    #[test]
+    fn test_status_aliases_apply_to_both_parsers() {
+        set(Settings::default().with_config(ParserConfig {
+            status_aliases: HashMap::from([("Shipped".to_string(), "done".to_string())]),
+            ..ParserConfig::default()
+        }));
+        let sprint = parse_sprint("development_status:\n  epic-1: backlog\n  1-a: shipped\n");
+        assert_eq!(sprint.unwrap().epics[0].stories[0].status, "done");
+        // Built-in aliases still apply alongside the custom ones
+        let sprint = parse_sprint("development_status:\n  epic-1: backlog\n  1-a: completed\n");
+        assert_eq!(sprint.unwrap().epics[0].stories[0].status, "done");
+
+        set(Settings::default());
+        let sprint = parse_sprint("development_status:\n  epic-1: backlog\n  1-a: shipped\n");
+        assert_eq!(sprint.unwrap().epics[0].stories[0].status, "shipped");
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        let yaml = "workflow_status:\n  prd: required\n  brainstorm: docs/b.md\n  \
+                    architecture: optional\n";
+        let ids = |order: SortOrder| {
+            set(Settings::default().with_config(ParserConfig {
+                sort_order: order,
+                ..ParserConfig::default()
+            }));
+            let data = parse_workflow(yaml).unwrap();
+            data.items.into_iter().map(|i| i.id).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(SortOrder::Phase), ["brainstorm", "prd", "architecture"]);
+        assert_eq!(ids(SortOrder::Id), ["architecture", "brainstorm", "prd"]);
+        assert_eq!(
+            ids(SortOrder::Status),
+            ["brainstorm", "architecture", "prd"]
+        );
+        set(Settings::default());
+    }
+}
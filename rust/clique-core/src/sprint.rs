@@ -1,21 +1,24 @@
 // clique-core/src/sprint.rs
 //! Sprint parsing and story status update logic.
 
-use crate::types::{Epic, SprintData, Story};
-use once_cell::sync::Lazy;
-use regex::Regex;
+use crate::conflict;
+pub use crate::epics_md::enrich_from_epics_md;
+use crate::limits::{ParseLimits, enforce_item_limit, enforce_limits};
+use crate::options::{ParseOptions, UpdateOptions};
+use crate::pattern::{epic_number, story_epic};
+use crate::text::text;
+use crate::trace::record;
+use crate::types::{
+    Date, Epic, SprintData, Story, StoryStatus, day_number, normalize_status, parse_date,
+};
+use crate::yaml_line::{
+    find_block, find_key, find_value, format_scalar, line_ending, needs_quotes, remove_field,
+    set_field, splice,
+};
 use serde_yaml::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use thiserror::Error;
 
-/// Static regex for matching epic IDs (e.g., "epic-1", "epic-2")
-static EPIC_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^epic-(\d+)$").expect("Invalid epic regex pattern"));
-
-/// Static regex for matching story prefixes (e.g., "1-", "2-")
-static STORY_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^(\d+)-").expect("Invalid story regex pattern"));
-
 #[derive(Error, Debug)]
 pub enum SprintError {
     #[error("Failed to parse YAML: {0}")]
@@ -24,10 +27,146 @@ pub enum SprintError {
     StoryNotFound(String),
     #[error("Update failed: {0}")]
     UpdateError(String),
+    #[error("Invalid story ID: {0}")]
+    InvalidStoryId(String),
+    #[error("Story already exists: {0}")]
+    DuplicateStory(String),
+    #[error("Resource limit exceeded: {0}")]
+    ResourceLimit(String),
+    #[error("Invalid status: {0}")]
+    InvalidStatus(String),
+    #[error("Jira key collision: {0}")]
+    KeyCollision(String),
+    #[error("Failed to read file: {0}")]
+    Io(String),
+    #[error("Preprocessing failed: {0}")]
+    Preprocess(String),
+    #[error("Status changed since it was read; it is now {actual:?}")]
+    ConflictError { actual: String },
+    /// The file holds git conflict markers; both sides parsed
+    #[error("File has unresolved merge conflicts")]
+    MergeConflict {
+        ours: Box<SprintData>,
+        theirs: Box<SprintData>,
+    },
+}
+
+/// Read the status of a `development_status` entry.
+///
+/// Entries are either a plain scalar (`1-story: backlog`) or the extended
+/// mapping form (`1-story: { status: backlog, blocked_by: [...] }`).
+pub(crate) fn entry_status(value: &Value) -> String {
+    match value.as_mapping() {
+        Some(map) => map.get("status").and_then(|v| v.as_str()),
+        None => value.as_str(),
+    }
+    .unwrap_or_default()
+    .to_string()
+}
+
+/// Read a list-valued field from an extended entry, accepting a single string too.
+fn entry_list(value: &Value, field: &str) -> Vec<String> {
+    match value.as_mapping().and_then(|m| m.get(field)) {
+        Some(Value::Sequence(seq)) => seq
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_string())
+            .collect(),
+        Some(Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// A string field of an extended-form entry
+fn entry_string(value: &Value, field: &str) -> Option<String> {
+    value
+        .as_mapping()
+        .and_then(|m| m.get(field))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Build a story from its `development_status` entry, with the status as
+/// written
+fn story_from_entry(id: &str, epic_num: &str, value: &Value) -> Story {
+    Story {
+        id: id.to_string(),
+        status: text(entry_status(value)),
+        epic_id: format!("epic-{}", epic_num),
+        blocked_by: entry_list(value, "blocked_by"),
+        tags: entry_list(value, "tags"),
+        assignee: entry_string(value, "assignee"),
+        updated: entry_string(value, "updated"),
+        number: value
+            .as_mapping()
+            .and_then(|m| m.get("number"))
+            .and_then(|v| v.as_u64()),
+        due: entry_string(value, "due"),
+        ..Story::default()
+    }
 }
 
 /// Parse sprint status from YAML content
 pub fn parse_sprint_status(yaml_content: &str) -> Result<SprintData, SprintError> {
+    parse_sprint_status_with_limits(yaml_content, &ParseLimits::default())
+}
+
+/// Parse sprint status from YAML content, enforcing custom resource limits
+pub fn parse_sprint_status_with_limits(
+    yaml_content: &str,
+    limits: &ParseLimits,
+) -> Result<SprintData, SprintError> {
+    parse_sprint_status_with_options(
+        yaml_content,
+        &ParseOptions::default().with_limits(limits.clone()),
+    )
+}
+
+/// Parse sprint status from YAML content with explicit parse options.
+///
+/// With `normalize` set, epic and story statuses are mapped through the
+/// alias table, so `complete` and `completed` both read as `done`. With a
+/// `registry`, stories whose status is not a built-in one carry the
+/// registered metadata in `custom_status`. With `preprocessing`, the content
+/// is interpolated and its includes merged in first; limits apply to the
+/// result.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(bytes = yaml_content.len(), epics = tracing::field::Empty, stories = tracing::field::Empty)
+    )
+)]
+pub fn parse_sprint_status_with_options(
+    yaml_content: &str,
+    options: &ParseOptions,
+) -> Result<SprintData, SprintError> {
+    let limits = &options.limits;
+    enforce_limits(yaml_content, limits).map_err(SprintError::ResourceLimit)?;
+    if let Some(preprocessing) = &options.preprocessing {
+        let content = preprocessing
+            .apply_with_limits(yaml_content, &options.limits)
+            .map_err(|e| SprintError::Preprocess(e.to_string()))?;
+        let options = ParseOptions {
+            preprocessing: None,
+            ..options.clone()
+        };
+        return parse_sprint_status_with_options(&content, &options);
+    }
+
+    if let Some(sides) = conflict::split(yaml_content)
+        && let (Ok(ours), Ok(theirs)) = (
+            parse_sprint_status_with_options(&sides.ours, options),
+            parse_sprint_status_with_options(&sides.theirs, options),
+        )
+    {
+        return Err(SprintError::MergeConflict {
+            ours: Box::new(ours),
+            theirs: Box::new(theirs),
+        });
+    }
+
     let parsed: Value =
         serde_yaml::from_str(yaml_content).map_err(|e| SprintError::ParseError(e.to_string()))?;
 
@@ -43,20 +182,35 @@ pub fn parse_sprint_status(yaml_content: &str) -> Result<SprintData, SprintError
         .unwrap_or_default()
         .to_string();
 
+    let sprint_field = |key: &str| {
+        parsed
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+
     let dev_status = parsed
         .get("development_status")
         .and_then(|v| v.as_mapping())
         .cloned()
         .unwrap_or_default();
+    enforce_item_limit(dev_status.len(), limits).map_err(SprintError::ResourceLimit)?;
+
+    let status_of = |value: &Value| {
+        let status = entry_status(value);
+        match &options.normalize {
+            Some(aliases) => aliases.normalize(&status),
+            None => status,
+        }
+    };
 
     let mut epics_map: HashMap<String, Epic> = HashMap::new();
 
     // First pass: identify epics by "epic-N" pattern
     for (key, value) in &dev_status {
         let key_str = key.as_str().unwrap_or_default();
-        if let Some(caps) = EPIC_REGEX.captures(key_str) {
-            let epic_num = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
-            let status = value.as_str().unwrap_or_default().to_string();
+        if let Some(epic_num) = epic_number(key_str) {
+            let status = status_of(value);
 
             epics_map.insert(
                 epic_num.to_string(),
@@ -75,22 +229,24 @@ pub fn parse_sprint_status(yaml_content: &str) -> Result<SprintData, SprintError
         let key_str = key.as_str().unwrap_or_default();
 
         // Skip epic entries and retrospectives
-        if EPIC_REGEX.is_match(key_str) || key_str.contains("retrospective") {
+        if epic_number(key_str).is_some() || key_str.contains("retrospective") {
             continue;
         }
 
         // Extract epic number from story id (e.g., "4-7-create-admin-staff-domain" -> "4")
-        if let Some(caps) = STORY_REGEX.captures(key_str) {
-            let epic_num = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
-
-            if let Some(epic) = epics_map.get_mut(epic_num) {
-                let status = value.as_str().unwrap_or_default().to_string();
-                epic.stories.push(Story {
-                    id: key_str.to_string(),
-                    status,
-                    epic_id: format!("epic-{}", epic_num),
-                });
-            }
+        if let Some(epic_num) = story_epic(key_str)
+            && let Some(epic) = epics_map.get_mut(epic_num)
+        {
+            let status = status_of(value);
+            let custom_status = options
+                .registry
+                .as_ref()
+                .and_then(|r| r.tag_story_status(&status));
+            epic.stories.push(Story {
+                status: text(status),
+                custom_status,
+                ..story_from_entry(key_str, epic_num, value)
+            });
         }
     }
 
@@ -102,49 +258,549 @@ pub fn parse_sprint_status(yaml_content: &str) -> Result<SprintData, SprintError
         num_a.cmp(&num_b)
     });
 
+    record!(
+        "epics" = epics.len(),
+        "stories" = epics.iter().map(|epic| epic.stories.len()).sum::<usize>(),
+    );
     Ok(SprintData {
         project,
         project_key,
         epics,
+        sprint_goal: sprint_field("sprint_goal"),
+        sprint_start: sprint_field("sprint_start"),
+        sprint_end: sprint_field("sprint_end"),
     })
 }
 
-fn escape_regex(s: &str) -> String {
-    let special_chars = [
-        '.', '*', '+', '?', '^', '$', '{', '}', '(', ')', '|', '[', ']', '\\', '-',
-    ];
-    let mut result = String::with_capacity(s.len() * 2);
-    for c in s.chars() {
-        if special_chars.contains(&c) {
-            result.push('\\');
+/// Parse a single story without building the whole sprint.
+///
+/// Only the story's own entry is parsed, after locating it (and checking
+/// its epic exists) by scanning the block structure. Documents the scan
+/// cannot follow fall back to a full parse. Either way the story is the one
+/// [`parse_sprint_status`] would return; epic keys, retrospectives and
+/// stories whose epic is missing are not stories and are not found.
+pub fn parse_story(content: &str, story_id: &str) -> Result<Story, SprintError> {
+    let not_found = || SprintError::StoryNotFound(story_id.to_string());
+    if epic_number(story_id).is_some() || story_id.contains("retrospective") {
+        return Err(not_found());
+    }
+    let epic_num = story_epic(story_id).ok_or_else(not_found)?;
+
+    let epic_key = format!("epic-{}", epic_num);
+    if find_key(content, &["development_status", &epic_key]).is_some()
+        && let Some(block) = find_block(content, &["development_status", story_id])
+        && let Ok(parsed) = serde_yaml::from_str::<Value>(&content[block])
+        && let Some(value) = parsed.as_mapping().and_then(|m| m.values().next())
+    {
+        return Ok(story_from_entry(story_id, epic_num, value));
+    }
+
+    parse_sprint_status(content)?
+        .epics
+        .into_iter()
+        .flat_map(|epic| epic.stories)
+        .find(|story| story.id == story_id)
+        .ok_or_else(not_found)
+}
+
+/// A story that cannot proceed because some of its blockers are not done yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockedStory<'a> {
+    pub story: &'a Story,
+    /// Blockers that are not done, or that do not exist in the sprint
+    pub open_blockers: Vec<&'a str>,
+}
+
+/// Whether a story releases its dependents
+pub(crate) fn is_done(story: &Story) -> bool {
+    matches!(story.status.as_str(), "done" | "completed")
+        || story.custom_status.as_ref().is_some_and(|c| c.is_terminal)
+}
+
+impl SprintData {
+    /// Find a story by ID across all epics.
+    pub fn find_story(&self, story_id: &str) -> Option<&Story> {
+        self.epics
+            .iter()
+            .flat_map(|e| e.stories.iter())
+            .find(|s| s.id == story_id)
+    }
+
+    /// Map every story ID to its external tracker key (e.g. `DMO-12`).
+    ///
+    /// Each key is the story's own [`Story::external_key`], so adding or
+    /// removing a story never changes another story's key. Two stories with
+    /// the same key are a [`SprintError::KeyCollision`]; give one of them an
+    /// explicit `number`. Empty when the sprint has no `project_key`.
+    pub fn jira_keys(&self) -> Result<BTreeMap<String, String>, SprintError> {
+        if self.project_key.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+        let mut claims: BTreeMap<u64, Vec<&str>> = BTreeMap::new();
+        for story in self.epics.iter().flat_map(|e| e.stories.iter()) {
+            claims
+                .entry(story.key_number())
+                .or_default()
+                .push(&story.id);
+        }
+
+        let collisions: Vec<String> = claims
+            .iter()
+            .filter(|(_, ids)| ids.len() > 1)
+            .map(|(number, ids)| format!("{}-{} ({})", self.project_key, number, ids.join(", ")))
+            .collect();
+        if !collisions.is_empty() {
+            return Err(SprintError::KeyCollision(collisions.join("; ")));
         }
-        result.push(c);
+        Ok(claims
+            .into_iter()
+            .map(|(number, ids)| {
+                (
+                    ids[0].to_string(),
+                    format!("{}-{}", self.project_key, number),
+                )
+            })
+            .collect())
+    }
+
+    /// Reverse lookup: find the story whose [`Story::external_key`] equals
+    /// `key`. `None` when no story, or more than one, has that key.
+    pub fn story_for_key(&self, key: &str) -> Option<&Story> {
+        let mut matches = self
+            .epics
+            .iter()
+            .flat_map(|e| e.stories.iter())
+            .filter(|s| s.external_key(&self.project_key).as_deref() == Some(key));
+        let story = matches.next()?;
+        matches.next().is_none().then_some(story)
+    }
+
+    /// The `sprint_start` date, if present and a valid ISO date.
+    pub fn sprint_start_date(&self) -> Option<Date> {
+        self.sprint_start.as_deref().and_then(parse_date)
+    }
+
+    /// The `sprint_end` date, if present and a valid ISO date.
+    pub fn sprint_end_date(&self) -> Option<Date> {
+        self.sprint_end.as_deref().and_then(parse_date)
+    }
+
+    /// Whole days from `today` to the sprint's last day: 0 on the last day,
+    /// negative once it has passed. `None` without a valid `sprint_end` or
+    /// when `today` is not a valid date.
+    pub fn days_remaining(&self, today: &Date) -> Option<i64> {
+        let end = self.sprint_end_date()?;
+        Some(day_number(&end)? - day_number(today)?)
+    }
+
+    /// Whether the sprint's last day has passed with stories still not done.
+    pub fn is_overrunning(&self, today: &Date) -> bool {
+        self.days_remaining(today).is_some_and(|days| days < 0)
+            && self
+                .epics
+                .iter()
+                .flat_map(|e| e.stories.iter())
+                .any(|s| !is_done(s))
+    }
+
+    /// In-progress stories whose `updated` date is more than
+    /// `threshold_days` before `today`, in sprint order. Stories without a
+    /// valid `updated` timestamp are not reported, and nothing is when
+    /// `today` is not a valid date.
+    pub fn stale_in_progress(&self, today: &Date, threshold_days: i64) -> Vec<&Story> {
+        let Some(today) = day_number(today) else {
+            return Vec::new();
+        };
+        self.epics
+            .iter()
+            .flat_map(|e| e.stories.iter())
+            .filter(|s| {
+                normalize_status(&s.status) == StoryStatus::InProgress
+                    && s.updated
+                        .as_deref()
+                        .and_then(parse_date)
+                        .and_then(|updated| day_number(&updated))
+                        .is_some_and(|updated| today - updated > threshold_days)
+            })
+            .collect()
+    }
+
+    /// Stories that are not done and were due before `today`, in sprint order.
+    pub fn overdue(&self, today: &Date) -> Vec<&Story> {
+        self.epics
+            .iter()
+            .flat_map(|e| e.stories.iter())
+            .filter(|s| !is_done(s) && s.due_date().is_some_and(|due| due < *today))
+            .collect()
+    }
+
+    /// Stories carrying the given tag (case-insensitively), in sprint order.
+    pub fn stories_tagged(&self, tag: &str) -> Vec<&Story> {
+        self.epics
+            .iter()
+            .flat_map(|e| e.stories.iter())
+            .filter(|s| s.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            .collect()
+    }
+
+    /// Count stories per tag, as written. A story with several tags counts
+    /// once under each.
+    pub fn tag_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for story in self.epics.iter().flat_map(|e| e.stories.iter()) {
+            for tag in &story.tags {
+                *counts.entry(tag.clone()).or_default() += 1;
+            }
+        }
+        counts
+    }
+
+    /// Stories grouped by assignee, each list in sprint order.
+    ///
+    /// Stories without an assignee, or with a blank one, are left out; see
+    /// [`SprintData::unassigned`].
+    pub fn by_assignee(&self) -> BTreeMap<String, Vec<&Story>> {
+        let mut groups: BTreeMap<String, Vec<&Story>> = BTreeMap::new();
+        for story in self.epics.iter().flat_map(|e| e.stories.iter()) {
+            if let Some(assignee) = &story.assignee
+                && !assignee.trim().is_empty()
+            {
+                groups.entry(assignee.clone()).or_default().push(story);
+            }
+        }
+        groups
+    }
+
+    /// The sprint narrowed to the stories assigned to `person` (ignoring
+    /// case and surrounding spaces); epics left without stories are dropped.
+    /// Handy before an export for one developer's task manager.
+    pub fn assigned_to(&self, person: &str) -> SprintData {
+        let person = person.trim();
+        let epics = self
+            .epics
+            .iter()
+            .filter_map(|epic| {
+                let stories: Vec<Story> = epic
+                    .stories
+                    .iter()
+                    .filter(|story| {
+                        story
+                            .assignee
+                            .as_deref()
+                            .is_some_and(|a| a.trim().eq_ignore_ascii_case(person))
+                    })
+                    .cloned()
+                    .collect();
+                (!stories.is_empty()).then(|| Epic {
+                    stories,
+                    ..epic.clone()
+                })
+            })
+            .collect();
+        SprintData {
+            epics,
+            ..self.clone()
+        }
+    }
+
+    /// Stories that are not done and have no assignee, in sprint order.
+    pub fn unassigned(&self) -> Vec<&Story> {
+        self.epics
+            .iter()
+            .flat_map(|e| e.stories.iter())
+            .filter(|s| !is_done(s) && s.assignee.as_deref().is_none_or(|a| a.trim().is_empty()))
+            .collect()
+    }
+
+    /// List stories whose `blocked_by` entries are not all done.
+    ///
+    /// A blocker that does not exist in the sprint counts as open.
+    pub fn blocked_stories(&self) -> Vec<BlockedStory<'_>> {
+        self.epics
+            .iter()
+            .flat_map(|e| e.stories.iter())
+            .filter_map(|story| {
+                let open_blockers: Vec<&str> = story
+                    .blocked_by
+                    .iter()
+                    .filter(|id| self.find_story(id).map(|b| !is_done(b)).unwrap_or(true))
+                    .map(|id| id.as_str())
+                    .collect();
+                if open_blockers.is_empty() {
+                    None
+                } else {
+                    Some(BlockedStory {
+                        story,
+                        open_blockers,
+                    })
+                }
+            })
+            .collect()
     }
-    result
 }
 
 /// Update story status in YAML content
+///
+/// Only keys directly under `development_status` are considered, matched on
+/// the whole key token, so same-named keys elsewhere are never modified.
 pub fn update_story_status(
     content: &str,
     story_id: &str,
     new_status: &str,
 ) -> Result<String, SprintError> {
-    // Match pattern: "storyId: oldStatus" and replace with "storyId: newStatus"
-    let pattern = format!(r"(?m)(^\s*{}:\s*)\S+", escape_regex(story_id));
-    let re = Regex::new(&pattern).map_err(|e| SprintError::UpdateError(e.to_string()))?;
+    update_story_status_with_options(content, story_id, new_status, &UpdateOptions::default())
+}
+
+/// Update story status in YAML content with explicit edit options.
+///
+/// With `touch_timestamps` set, a story written in the extended block form
+/// also gets its `updated:` field set (added if missing). Plain and inline
+/// entries have nowhere to put one and are left as they are.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(bytes = content.len(), story = story_id)
+    )
+)]
+pub fn update_story_status_with_options(
+    content: &str,
+    story_id: &str,
+    new_status: &str,
+    options: &UpdateOptions,
+) -> Result<String, SprintError> {
+    // A line break would splice new structure into the document
+    if new_status.chars().any(char::is_control) {
+        return Err(SprintError::InvalidStatus(
+            new_status.escape_debug().to_string(),
+        ));
+    }
+
+    // Plain form "storyId: oldStatus", or the extended form where "storyId:"
+    // opens a block with an indented "status: oldStatus" line
+    let span = find_value(content, &["development_status", story_id])
+        .filter(|span| !span.is_empty())
+        .or_else(|| find_value(content, &["development_status", story_id, "status"]))
+        .filter(|span| !span.is_empty())
+        .ok_or_else(|| SprintError::StoryNotFound(story_id.to_string()))?;
+
+    let replacement = format_scalar(&content[span.clone()], new_status, needs_quotes(new_status));
+    let updated = splice(content, span, &replacement);
+
+    if options.touch_timestamps {
+        let timestamp = options.timestamp();
+        let value = format_scalar("", &timestamp, needs_quotes(&timestamp));
+        if let Some(touched) = set_field(
+            &updated,
+            &["development_status", story_id],
+            "updated",
+            &value,
+        ) {
+            return Ok(touched);
+        }
+    }
+    Ok(updated)
+}
+
+/// Apply several `(story_id, new_status)` updates in one pass.
+///
+/// Each update is applied to the result of the previous one. The batch is
+/// all-or-nothing: the first failing update is returned as the error and no
+/// content is produced.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(bytes = content.len(), updates = updates.len())
+    )
+)]
+pub fn update_story_statuses(
+    content: &str,
+    updates: &[(&str, &str)],
+) -> Result<String, SprintError> {
+    updates
+        .iter()
+        .try_fold(content.to_string(), |current, (story_id, new_status)| {
+            update_story_status(&current, story_id, new_status)
+        })
+}
+
+/// Status of a story exactly as written in the file
+pub(crate) fn raw_story_status(parsed: &Value, story_id: &str) -> Option<String> {
+    parsed
+        .get("development_status")?
+        .get(story_id)
+        .map(entry_status)
+}
+
+/// Update a story's status only if it still reads `expected_current`.
+///
+/// Guards a read-modify-write cycle against the file changing on disk in
+/// between: when the status as written differs from `expected_current`, the
+/// update fails with [`SprintError::ConflictError`] carrying the actual
+/// value and `content` is left alone.
+pub fn update_story_status_if_unchanged(
+    content: &str,
+    story_id: &str,
+    new_status: &str,
+    expected_current: &str,
+) -> Result<String, SprintError> {
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| SprintError::ParseError(e.to_string()))?;
+    let actual = raw_story_status(&parsed, story_id)
+        .ok_or_else(|| SprintError::StoryNotFound(story_id.to_string()))?;
+    if actual != expected_current {
+        return Err(SprintError::ConflictError { actual });
+    }
+    update_story_status(content, story_id, new_status)
+}
+
+/// Add a story to `development_status`, after its last entry.
+///
+/// The ID must have a numeric epic prefix (e.g. "1-") and must not already
+/// exist.
+pub fn add_story(content: &str, story_id: &str, status: &str) -> Result<String, SprintError> {
+    if story_epic(story_id).is_none()
+        || story_id.contains(|c: char| c.is_whitespace() || c == ':' || c == '#')
+    {
+        return Err(SprintError::InvalidStoryId(story_id.to_string()));
+    }
+    if status.trim().is_empty() || status.chars().any(char::is_control) {
+        return Err(SprintError::InvalidStatus(
+            status.escape_debug().to_string(),
+        ));
+    }
+
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| SprintError::ParseError(e.to_string()))?;
+    let dev_status = parsed
+        .get("development_status")
+        .and_then(|v| v.as_mapping())
+        .ok_or_else(|| SprintError::UpdateError("No development_status mapping".to_string()))?;
+    if dev_status.keys().any(|k| k.as_str() == Some(story_id)) {
+        return Err(SprintError::DuplicateStory(story_id.to_string()));
+    }
+
+    let value = format_scalar("", status, needs_quotes(status));
+    set_field(content, &["development_status"], story_id, &value).ok_or_else(|| {
+        SprintError::UpdateError("development_status is not a block mapping".to_string())
+    })
+}
+
+/// Remove a story, including any extended-form fields nested under it.
+pub fn remove_story(content: &str, story_id: &str) -> Result<String, SprintError> {
+    if epic_number(story_id).is_some() {
+        return Err(SprintError::InvalidStoryId(story_id.to_string()));
+    }
+    remove_field(content, &["development_status", story_id])
+        .ok_or_else(|| SprintError::StoryNotFound(story_id.to_string()))
+}
+
+/// A story's text as written, nested fields included, and the key of the
+/// `development_status` entry before it (`None` when it comes first)
+pub(crate) fn story_block(content: &str, story_id: &str) -> Option<(String, Option<String>)> {
+    let range = find_block(content, &["development_status", story_id])?;
+    let parsed: Value = serde_yaml::from_str(content).ok()?;
+    let previous = parsed
+        .get("development_status")?
+        .as_mapping()?
+        .keys()
+        .filter_map(Value::as_str)
+        .take_while(|key| *key != story_id)
+        .last()
+        .map(str::to_string);
+    Some((content[range].to_string(), previous))
+}
+
+/// Put back a story removed by [`remove_story`]: `block` is its text as
+/// given by [`story_block`], inserted after the entry `after`, or first in
+/// `development_status` when that is `None` or no longer there.
+pub(crate) fn restore_story(
+    content: &str,
+    story_id: &str,
+    block: &str,
+    after: Option<&str>,
+) -> Result<String, SprintError> {
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| SprintError::ParseError(e.to_string()))?;
+    let dev_status = parsed
+        .get("development_status")
+        .and_then(|v| v.as_mapping())
+        .ok_or_else(|| SprintError::UpdateError("No development_status mapping".to_string()))?;
+    if dev_status.keys().any(|k| k.as_str() == Some(story_id)) {
+        return Err(SprintError::DuplicateStory(story_id.to_string()));
+    }
 
-    if !re.is_match(content) {
-        return Err(SprintError::StoryNotFound(story_id.to_string()));
+    let at = match after.and_then(|key| find_block(content, &["development_status", key])) {
+        Some(previous) => previous.end,
+        None => {
+            let key = find_key(content, &["development_status"]).ok_or_else(|| {
+                SprintError::UpdateError("development_status is not a block mapping".to_string())
+            })?;
+            content[key.end..]
+                .find(['\r', '\n'])
+                .map_or(content.len(), |i| key.end + i)
+        }
+    };
+    let inserted = format!("{}{}", line_ending(content), block);
+    Ok(splice(content, at..at, &inserted))
+}
+
+/// Rename a story key in YAML content, leaving its status untouched.
+///
+/// The old ID must be a story key, not an epic or retrospective. The new ID
+/// must keep the same numeric epic prefix (e.g. "1-"), so a rename never
+/// moves a story to another epic, and must not collide with an existing key
+/// in `development_status`.
+pub fn rename_story(content: &str, old_id: &str, new_id: &str) -> Result<String, SprintError> {
+    let epic = match story_epic(old_id) {
+        Some(epic) if epic_number(old_id).is_none() && !old_id.contains("retrospective") => epic,
+        _ => return Err(SprintError::InvalidStoryId(old_id.to_string())),
+    };
+    if story_epic(new_id).is_none()
+        || new_id.contains(|c: char| c.is_whitespace() || c == ':' || c == '#')
+    {
+        return Err(SprintError::InvalidStoryId(new_id.to_string()));
+    }
+    if story_epic(new_id) != Some(epic) {
+        return Err(SprintError::InvalidStoryId(format!(
+            "{} (must stay in epic {})",
+            new_id, epic
+        )));
+    }
+
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| SprintError::ParseError(e.to_string()))?;
+    let dev_status = parsed
+        .get("development_status")
+        .and_then(|v| v.as_mapping());
+
+    let has_key = |id: &str| {
+        dev_status
+            .map(|m| m.keys().any(|k| k.as_str() == Some(id)))
+            .unwrap_or(false)
+    };
+
+    if !has_key(old_id) {
+        return Err(SprintError::StoryNotFound(old_id.to_string()));
+    }
+    if old_id != new_id && has_key(new_id) {
+        return Err(SprintError::DuplicateStory(new_id.to_string()));
     }
 
-    Ok(re
-        .replace(content, format!("${{1}}{}", new_status))
-        .to_string())
+    // Swap only the key token, keeping its quoting style
+    let span = find_key(content, &["development_status", old_id])
+        .ok_or_else(|| SprintError::StoryNotFound(old_id.to_string()))?;
+    let replacement = format_scalar(&content[span.clone()], new_id, false);
+    Ok(splice(content, span, &replacement))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{StatusAliases, StatusInfo, StatusRegistry};
 
     const SPRINT_YAML: &str = r#"
 project: Demo Project
@@ -268,6 +924,412 @@ development_status:
         assert_eq!(epic.stories[0].epic_id, "epic-3");
     }
 
+    // =========================================================================
+    // Extended Form / Dependency Tests
+    // =========================================================================
+
+    const BLOCKED_YAML: &str = r#"
+project: Blocked Test
+project_key: BLK
+development_status:
+  epic-1: in-progress
+  1-create-api:
+    status: backlog
+    blocked_by: [2-create-db, 1-design]
+  1-design: done
+  1-write-docs:
+    status: ready-for-dev
+    blocked_by: 1-design
+  epic-2: backlog
+  2-create-db: in-progress
+"#;
+
+    #[test]
+    fn test_parse_extended_story_form() {
+        let data = parse_sprint_status(BLOCKED_YAML).expect("Should parse extended form");
+        let story = data.find_story("1-create-api").expect("Should find story");
+        assert_eq!(story.status, "backlog");
+        assert_eq!(story.blocked_by, vec!["2-create-db", "1-design"]);
+
+        let docs = data.find_story("1-write-docs").expect("Should find story");
+        assert_eq!(docs.blocked_by, vec!["1-design"]);
+
+        let design = data.find_story("1-design").expect("Should find story");
+        assert!(design.blocked_by.is_empty());
+    }
+
+    #[test]
+    fn test_blocked_stories() {
+        let data = parse_sprint_status(BLOCKED_YAML).expect("Should parse");
+        let blocked = data.blocked_stories();
+
+        // 1-write-docs is only blocked by a done story
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].story.id, "1-create-api");
+        assert_eq!(blocked[0].open_blockers, vec!["2-create-db"]);
+    }
+
+    #[test]
+    fn test_blocked_by_unknown_story_counts_as_open() {
+        let yaml = r#"
+project: Unknown Blocker
+project_key: UNK
+development_status:
+  epic-1: backlog
+  1-story:
+    status: backlog
+    blocked_by: [9-does-not-exist]
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        let blocked = data.blocked_stories();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].open_blockers, vec!["9-does-not-exist"]);
+    }
+
+    #[test]
+    fn test_update_extended_story_status() {
+        let updated = update_story_status(BLOCKED_YAML, "1-create-api", "in-progress")
+            .expect("Should update extended story");
+        assert!(
+            updated.contains("    status: in-progress\n    blocked_by: [2-create-db, 1-design]")
+        );
+
+        let data = parse_sprint_status(&updated).expect("Should re-parse");
+        let story = data.find_story("1-create-api").unwrap();
+        assert_eq!(story.status, "in-progress");
+        assert_eq!(story.blocked_by.len(), 2);
+        // Neighbouring entries are untouched
+        assert_eq!(data.find_story("1-design").unwrap().status, "done");
+    }
+
+    #[test]
+    fn test_update_extended_story_without_status_is_not_corrupted() {
+        let yaml = r#"
+project: No Status
+project_key: NST
+development_status:
+  epic-1: backlog
+  1-story:
+    blocked_by: [1-other]
+  1-other: backlog
+"#;
+        let result = update_story_status(yaml, "1-story", "done");
+        assert!(matches!(result, Err(SprintError::StoryNotFound(_))));
+    }
+
+    #[test]
+    fn test_story_tags() {
+        let yaml = r#"
+development_status:
+  epic-1: in-progress
+  1-login:
+    status: in-progress
+    tags: [backend, security]
+  1-signup: { status: review, tags: Backend }
+  1-profile: backlog
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        let login = data.find_story("1-login").unwrap();
+        assert_eq!(login.tags, vec!["backend", "security"]);
+        assert!(data.find_story("1-profile").unwrap().tags.is_empty());
+
+        let ids: Vec<&str> = data
+            .stories_tagged("BACKEND")
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1-login", "1-signup"]);
+
+        let counts = data.tag_counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["security"], 1);
+    }
+
+    #[test]
+    fn test_assignee_workload() {
+        let yaml = r#"
+development_status:
+  epic-1: in-progress
+  1-login:
+    status: in-progress
+    assignee: Sam
+  1-signup: { status: review, assignee: Ari }
+  1-logout: { status: backlog, assignee: Sam }
+  1-profile: backlog
+  1-settings: done
+  epic-2: backlog
+  2-export: { status: ready-for-dev, assignee: "" }
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        assert_eq!(
+            data.find_story("1-login").unwrap().assignee.as_deref(),
+            Some("Sam")
+        );
+
+        let workload = data.by_assignee();
+        let ids =
+            |stories: &[&Story]| -> Vec<String> { stories.iter().map(|s| s.id.clone()).collect() };
+        assert_eq!(workload.keys().collect::<Vec<_>>(), vec!["Ari", "Sam"]);
+        assert_eq!(ids(&workload["Sam"]), vec!["1-login", "1-logout"]);
+        assert_eq!(ids(&workload["Ari"]), vec!["1-signup"]);
+
+        assert_eq!(ids(&data.unassigned()), vec!["1-profile", "2-export"]);
+
+        let sam = data.assigned_to(" sam ");
+        assert_eq!(sam.epics.len(), 1);
+        let sam_ids: Vec<&str> = sam.epics[0].stories.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(sam_ids, vec!["1-login", "1-logout"]);
+        assert!(data.assigned_to("nobody").epics.is_empty());
+    }
+
+    #[test]
+    fn test_sprint_dates() {
+        let yaml = r#"
+sprint_goal: Ship login
+sprint_start: 2025-02-24
+sprint_end: "2025-03-07"
+development_status:
+  epic-1: in-progress
+  1-login: in-progress
+  1-logout: done
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        assert_eq!(data.sprint_goal.as_deref(), Some("Ship login"));
+        assert_eq!(data.sprint_start.as_deref(), Some("2025-02-24"));
+        assert_eq!(data.sprint_end_date(), parse_date("2025-03-07"));
+        assert!(data.sprint_start_date() < data.sprint_end_date());
+
+        let day = |date: &str| parse_date(date).unwrap();
+        assert_eq!(data.days_remaining(&day("2025-02-24")), Some(11));
+        assert_eq!(data.days_remaining(&day("2025-03-07")), Some(0));
+        assert_eq!(data.days_remaining(&day("2025-03-10")), Some(-3));
+        assert_eq!(data.days_remaining(&day("2024-03-07")), Some(365));
+
+        assert!(!data.is_overrunning(&day("2025-03-07")));
+        assert!(data.is_overrunning(&day("2025-03-08")));
+
+        let finished = parse_sprint_status(&yaml.replace("1-login: in-progress", "1-login: done"))
+            .expect("Should parse");
+        assert!(!finished.is_overrunning(&day("2025-03-08")));
+    }
+
+    #[test]
+    fn test_sprint_dates_absent_or_invalid() {
+        let data = parse_sprint_status("development_status:\n  epic-1: backlog\n  1-a: backlog\n")
+            .expect("Should parse");
+        assert!(data.sprint_goal.is_none());
+        let today = parse_date("2025-03-01").unwrap();
+        assert_eq!(data.days_remaining(&today), None);
+        assert!(!data.is_overrunning(&today));
+
+        let data = parse_sprint_status("sprint_end: end of March\ndevelopment_status: {}\n")
+            .expect("Should parse");
+        assert_eq!(data.sprint_end.as_deref(), Some("end of March"));
+        assert_eq!(data.days_remaining(&today), None);
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    #[test]
+    fn test_sprint_dates_malformed_today() {
+        let data = parse_sprint_status(
+            "sprint_end: 2025-03-07\ndevelopment_status:\n  epic-1: backlog\n  1-a: backlog\n",
+        )
+        .expect("Should parse");
+        for today in [
+            "today",
+            "",
+            "2025-0é-01",
+            "2025-02-30",
+            "2025-03-0x",
+            "éééééééééé",
+        ] {
+            let today = today.to_string();
+            assert_eq!(data.days_remaining(&today), None, "{:?}", today);
+            assert!(!data.is_overrunning(&today), "{:?}", today);
+        }
+        assert_eq!(data.days_remaining(&"2025-03-01".to_string()), Some(6));
+    }
+
+    #[test]
+    fn test_stale_in_progress() {
+        let yaml = r#"
+development_status:
+  epic-1: in-progress
+  1-login:
+    status: in-progress
+    updated: 2025-02-20T09:00:00Z
+  1-signup:
+    status: In Progress
+    updated: 2025-02-27
+  1-profile: { status: review, updated: 2025-01-01 }
+  1-settings: in-progress
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        assert_eq!(
+            data.find_story("1-login").unwrap().updated.as_deref(),
+            Some("2025-02-20T09:00:00Z")
+        );
+
+        let today = parse_date("2025-03-01").unwrap();
+        let ids = |stories: Vec<&Story>| -> Vec<String> {
+            stories.iter().map(|s| s.id.clone()).collect()
+        };
+        assert_eq!(ids(data.stale_in_progress(&today, 7)), vec!["1-login"]);
+        assert_eq!(
+            ids(data.stale_in_progress(&today, 1)),
+            vec!["1-login", "1-signup"]
+        );
+        assert!(data.stale_in_progress(&today, 30).is_empty());
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    #[test]
+    fn test_stale_in_progress_malformed_today() {
+        let data = parse_sprint_status(
+            "development_status:\n  epic-1: in-progress\n  1-a:\n    status: in-progress\n    updated: 2025-01-01\n",
+        )
+        .expect("Should parse");
+        assert_eq!(
+            data.stale_in_progress(&"2025-03-01".to_string(), 7).len(),
+            1
+        );
+        for today in ["today", "2025-0é-01", "2025-13-01"] {
+            assert!(
+                data.stale_in_progress(&today.to_string(), 7).is_empty(),
+                "{:?}",
+                today
+            );
+        }
+    }
+
+    // =========================================================================
+    // External Key Tests
+    // =========================================================================
+
+    #[test]
+    fn test_jira_keys_and_reverse_lookup() {
+        let yaml = r#"
+project: Jira Test
+project_key: DMO
+development_status:
+  epic-1: in-progress
+  1-login:
+    status: review
+    number: 12
+  1-logout: backlog
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        let keys = data.jira_keys().expect("Should derive keys");
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys.get("1-login"), Some(&"DMO-12".to_string()));
+
+        let hashed = keys.get("1-logout").expect("Should derive hashed key");
+        assert!(hashed.starts_with("DMO-"));
+
+        let story = data.story_for_key("DMO-12").expect("Should find by key");
+        assert_eq!(story.id, "1-login");
+        let story = data
+            .story_for_key(hashed)
+            .expect("Should find by hashed key");
+        assert_eq!(story.id, "1-logout");
+        assert!(data.story_for_key("DMO-999999").is_none());
+    }
+
+    #[test]
+    fn test_jira_keys_report_collisions() {
+        // 1-login hashes to 32880, which 1-a also claims explicitly
+        let yaml = r#"
+project_key: DMO
+development_status:
+  epic-1: in-progress
+  1-login: backlog
+  1-a:
+    status: done
+    number: 32880
+  1-b:
+    status: done
+    number: 18446744073709551615
+  1-c:
+    status: done
+    number: 18446744073709551615
+  1-d: backlog
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        let err = data.jira_keys().expect_err("Should report collisions");
+        assert!(matches!(err, SprintError::KeyCollision(_)));
+        assert_eq!(
+            err.to_string(),
+            "Jira key collision: DMO-32880 (1-login, 1-a); DMO-18446744073709551615 (1-b, 1-c)"
+        );
+
+        assert!(data.story_for_key("DMO-32880").is_none());
+        let hashed = data.find_story("1-d").unwrap().external_key("DMO").unwrap();
+        assert_eq!(data.story_for_key(&hashed).unwrap().id, "1-d");
+    }
+
+    #[test]
+    fn test_jira_keys_stable_when_story_inserted() {
+        let yaml = r#"
+project_key: DMO
+development_status:
+  epic-1: in-progress
+  1-login: backlog
+  1-logout:
+    status: done
+    number: 4
+  1-signup: review
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        let before = data.jira_keys().expect("Should derive keys");
+
+        let yaml = yaml.replace(
+            "  1-login: backlog\n",
+            "  1-a: backlog\n  1-login: backlog\n",
+        );
+        let grown = parse_sprint_status(&yaml).expect("Should parse");
+        let after = grown.jira_keys().expect("Should derive keys");
+        assert_eq!(after.len(), before.len() + 1);
+        for (id, key) in &before {
+            assert_eq!(&after[id], key, "{} changed key", id);
+        }
+    }
+
+    #[test]
+    fn test_jira_keys_without_project_key() {
+        let yaml = r#"
+project: No Key
+development_status:
+  epic-1: backlog
+  1-story: backlog
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        assert!(data.jira_keys().expect("Should derive keys").is_empty());
+    }
+
+    #[test]
+    fn test_overdue_stories() {
+        let yaml = r#"
+development_status:
+  epic-1: in-progress
+  1-login:
+    status: in-progress
+    due: 2025-03-01
+  1-logout: { status: done, due: 2025-01-01 }
+  1-signup: { status: review, due: "2025-04-01" }
+  1-profile: backlog
+"#;
+        let data = parse_sprint_status(yaml).expect("Should parse");
+        let login = data.find_story("1-login").unwrap();
+        assert_eq!(login.due.as_deref(), Some("2025-03-01"));
+        assert!(data.find_story("1-profile").unwrap().due.is_none());
+
+        let today = parse_date("2025-03-10").unwrap();
+        let ids: Vec<&str> = data.overdue(&today).iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["1-login"]);
+        let later = parse_date("2025-05-01").unwrap();
+        assert_eq!(data.overdue(&later).len(), 2);
+    }
+
     // =========================================================================
     // Update Tests
     // =========================================================================
@@ -343,80 +1405,430 @@ development_status:
         assert!(updated3.contains("1-story: done"));
     }
 
+    #[test]
+    fn test_update_preserves_crlf_line_endings() {
+        let yaml = "project: Win\r\ndevelopment_status:\r\n  epic-1: backlog\r\n  1-story: backlog\r\n  1-other:\r\n    status: backlog\r\n    blocked_by: [1-story]\r\n";
+
+        let updated = update_story_status(yaml, "1-story", "done").expect("Should update");
+        assert_eq!(
+            updated,
+            yaml.replacen("1-story: backlog", "1-story: done", 1)
+        );
+
+        let updated = update_story_status(yaml, "1-other", "review").expect("Should update");
+        assert_eq!(
+            updated,
+            yaml.replacen("    status: backlog", "    status: review", 1)
+        );
+
+        let renamed = rename_story(yaml, "1-story", "1-login").expect("Should rename");
+        assert_eq!(
+            renamed.matches("\r\n").count(),
+            yaml.matches("\r\n").count()
+        );
+    }
+
+    #[test]
+    fn test_update_preserves_missing_final_newline() {
+        let yaml = "development_status:\r\n  epic-1: backlog\r\n  1-story: backlog";
+        let updated = update_story_status(yaml, "1-story", "done").expect("Should update");
+        assert_eq!(
+            updated,
+            "development_status:\r\n  epic-1: backlog\r\n  1-story: done"
+        );
+    }
+
+    #[test]
+    fn test_update_scoped_to_development_status() {
+        let yaml = r#"
+project: Scoped
+notes:
+  1-story: keep-me
+development_status:
+  epic-1: in-progress
+  1-story-two: backlog
+  1-story: backlog
+  1-other:
+    status: backlog
+    1-story: nested-keep
+archive:
+  1-story: keep-me-too
+"#;
+        let updated = update_story_status(yaml, "1-story", "done").expect("Should update");
+        assert_eq!(
+            updated,
+            yaml.replacen("  1-story: backlog", "  1-story: done", 1)
+        );
+        assert!(updated.contains("1-story-two: backlog"));
+        assert!(updated.contains("1-story: keep-me\n"));
+        assert!(updated.contains("1-story: nested-keep"));
+        assert!(updated.contains("1-story: keep-me-too"));
+    }
+
+    #[test]
+    fn test_update_key_only_outside_development_status() {
+        let yaml = "notes:\n  1-story: backlog\ndevelopment_status:\n  epic-1: backlog\n";
+        assert!(matches!(
+            update_story_status(yaml, "1-story", "done"),
+            Err(SprintError::StoryNotFound(_))
+        ));
+        assert!(matches!(
+            rename_story(yaml, "1-story", "1-renamed"),
+            Err(SprintError::StoryNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_update_and_rename_quoted_story_key() {
+        let yaml = "development_status:\n  epic-1: backlog\n  \"1-story\": 'backlog' # todo\n";
+        let updated = update_story_status(yaml, "1-story", "done").expect("Should update");
+        assert_eq!(
+            updated,
+            "development_status:\n  epic-1: backlog\n  \"1-story\": 'done' # todo\n"
+        );
+
+        let renamed = rename_story(yaml, "1-story", "1-login").expect("Should rename");
+        assert!(renamed.contains("  \"1-login\": 'backlog' # todo"));
+    }
+
+    #[test]
+    fn test_update_rejects_control_characters() {
+        let yaml = "development_status:\n  epic-1: backlog\n  1-story: backlog\n";
+        for status in ["done\n  1-evil: done", "done\r", "a\tb"] {
+            assert!(matches!(
+                update_story_status(yaml, "1-story", status),
+                Err(SprintError::InvalidStatus(_))
+            ));
+        }
+
+        let updated = update_story_status(yaml, "1-story", "a: b").expect("Should quote");
+        assert!(updated.contains("1-story: \"a: b\""));
+        let data = parse_sprint_status(&updated).expect("Should stay well-formed");
+        assert_eq!(data.epics[0].stories.len(), 1);
+    }
+
+    #[test]
+    fn test_rename_story() {
+        let renamed =
+            rename_story(SPRINT_YAML, "1-story-one", "1-story-uno").expect("Should rename story");
+        assert!(renamed.contains("1-story-uno: ready-for-dev"));
+        assert!(!renamed.contains("1-story-one"));
+
+        let data = parse_sprint_status(&renamed).expect("Should re-parse");
+        let epic1 = data.epics.iter().find(|e| e.id == "epic-1").unwrap();
+        let story = epic1
+            .stories
+            .iter()
+            .find(|s| s.id == "1-story-uno")
+            .unwrap();
+        assert_eq!(story.status, "ready-for-dev");
+    }
+
+    #[test]
+    fn test_rename_story_preserves_other_lines() {
+        let renamed =
+            rename_story(SPRINT_YAML, "1-story-two", "1-story-2").expect("Should rename story");
+        assert!(renamed.contains("1-story-one: ready-for-dev"));
+        assert!(renamed.contains("2-story-alpha: backlog"));
+        assert_eq!(
+            renamed.lines().count(),
+            SPRINT_YAML.lines().count(),
+            "Rename should not add or remove lines"
+        );
+    }
+
+    #[test]
+    fn test_rename_story_requires_numeric_prefix() {
+        let result = rename_story(SPRINT_YAML, "1-story-one", "story-one");
+        assert!(matches!(result, Err(SprintError::InvalidStoryId(_))));
+
+        let result = rename_story(SPRINT_YAML, "1-story-one", "1-story: injected");
+        assert!(matches!(result, Err(SprintError::InvalidStoryId(_))));
+    }
+
+    #[test]
+    fn test_rename_story_rejects_epic_keys() {
+        let yaml = "development_status:\n  epic-1: in-progress\n  1-a: backlog\n  epic-1-retrospective: optional\n";
+        for old_id in ["epic-1", "epic-1-retrospective"] {
+            let result = rename_story(yaml, old_id, "1-x");
+            assert!(
+                matches!(result, Err(SprintError::InvalidStoryId(ref id)) if id == old_id),
+                "{}: {:?}",
+                old_id,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_rename_story_keeps_epic() {
+        let result = rename_story(SPRINT_YAML, "1-story-one", "2-story-one");
+        assert!(matches!(
+            result,
+            Err(SprintError::InvalidStoryId(ref id)) if id == "2-story-one (must stay in epic 1)"
+        ));
+        assert!(rename_story(SPRINT_YAML, "1-story-one", "1-story-first").is_ok());
+    }
+
+    #[test]
+    fn test_rename_story_rejects_duplicate() {
+        let result = rename_story(SPRINT_YAML, "1-story-one", "1-story-two");
+        assert!(matches!(
+            result,
+            Err(SprintError::DuplicateStory(ref id)) if id == "1-story-two"
+        ));
+    }
+
+    #[test]
+    fn test_rename_story_not_found() {
+        let result = rename_story(SPRINT_YAML, "1-missing", "1-found");
+        assert!(matches!(result, Err(SprintError::StoryNotFound(_))));
+    }
+
+    #[test]
+    fn test_rename_story_does_not_match_prefix() {
+        let yaml = r#"
+project: Prefix Test
+project_key: PFX
+development_status:
+  epic-1: backlog
+  1-story: backlog
+  1-story-extended: review
+"#;
+        let renamed = rename_story(yaml, "1-story", "1-renamed").expect("Should rename");
+        assert!(renamed.contains("1-renamed: backlog"));
+        assert!(renamed.contains("1-story-extended: review"));
+    }
+
+    #[test]
+    fn test_update_touches_timestamp() {
+        let yaml = "development_status:\n  epic-1: in-progress\n  1-login:\n    status: in-progress\n    updated: 2025-02-20T09:00:00Z\n  1-signup:\n    status: backlog\n  1-plain: backlog\n";
+        let options = UpdateOptions::default()
+            .with_touch_timestamps()
+            .with_timestamp("2025-03-01T10:00:00Z");
+
+        let updated =
+            update_story_status_with_options(yaml, "1-login", "review", &options).unwrap();
+        assert!(
+            updated.contains("  1-login:\n    status: review\n    updated: 2025-03-01T10:00:00Z\n")
+        );
+
+        // Added when the block has no timestamp yet
+        let updated =
+            update_story_status_with_options(yaml, "1-signup", "drafted", &options).unwrap();
+        assert!(
+            updated
+                .contains("  1-signup:\n    status: drafted\n    updated: 2025-03-01T10:00:00Z\n")
+        );
+
+        // Plain entries only change status
+        let updated = update_story_status_with_options(yaml, "1-plain", "done", &options).unwrap();
+        assert_eq!(updated, yaml.replace("1-plain: backlog", "1-plain: done"));
+
+        // Without the option the timestamp is left alone
+        let updated = update_story_status(yaml, "1-login", "review").unwrap();
+        assert!(updated.contains("updated: 2025-02-20T09:00:00Z"));
+    }
+
+    #[test]
+    fn test_update_if_unchanged() {
+        let yaml = "development_status:\n  epic-1: in-progress\n  1-login: in-progress\n  1-signup:\n    status: review\n";
+        let updated = update_story_status_if_unchanged(yaml, "1-login", "review", "in-progress")
+            .expect("Should update");
+        assert!(updated.contains("1-login: review"));
+
+        assert!(matches!(
+            update_story_status_if_unchanged(&updated, "1-login", "done", "in-progress"),
+            Err(SprintError::ConflictError { actual }) if actual == "review"
+        ));
+        assert!(update_story_status_if_unchanged(yaml, "1-signup", "done", "review").is_ok());
+        assert!(matches!(
+            update_story_status_if_unchanged(yaml, "9-missing", "done", "review"),
+            Err(SprintError::StoryNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_story() {
+        let yaml = "development_status:\n  epic-1: in-progress\n  1-login: done # shipped\n\nnotes: keep\n";
+        let added = add_story(yaml, "1-signup", "backlog").unwrap();
+        assert_eq!(
+            added,
+            "development_status:\n  epic-1: in-progress\n  1-login: done # shipped\n  1-signup: backlog\n\nnotes: keep\n"
+        );
+        let data = parse_sprint_status(&added).unwrap();
+        assert_eq!(data.find_story("1-signup").unwrap().status, "backlog");
+
+        assert!(matches!(
+            add_story(yaml, "1-login", "backlog"),
+            Err(SprintError::DuplicateStory(_))
+        ));
+        assert!(matches!(
+            add_story(yaml, "signup", "backlog"),
+            Err(SprintError::InvalidStoryId(_))
+        ));
+        assert!(matches!(
+            add_story(yaml, "1-signup", "back\nlog"),
+            Err(SprintError::InvalidStatus(_))
+        ));
+        assert!(matches!(
+            add_story("project: x\n", "1-signup", "backlog"),
+            Err(SprintError::UpdateError(_))
+        ));
+    }
+
+    #[test]
+    fn test_remove_story() {
+        let yaml = "development_status:\n  epic-1: in-progress\n  1-login:\n    status: done\n    tags: [auth]\n  1-signup: backlog\n";
+        let removed = remove_story(yaml, "1-login").unwrap();
+        assert_eq!(
+            removed,
+            "development_status:\n  epic-1: in-progress\n  1-signup: backlog\n"
+        );
+        assert!(matches!(
+            remove_story(yaml, "9-missing"),
+            Err(SprintError::StoryNotFound(_))
+        ));
+        assert!(matches!(
+            remove_story(yaml, "epic-1"),
+            Err(SprintError::InvalidStoryId(_))
+        ));
+    }
+
+    #[test]
+    fn test_update_story_statuses_batch() {
+        let yaml = "development_status:\n  epic-1: in-progress\n  1-a: backlog\n  1-b:\n    status: review\n";
+        let updated = update_story_statuses(yaml, &[("1-a", "done"), ("1-b", "done")]).unwrap();
+        assert_eq!(
+            updated,
+            "development_status:\n  epic-1: in-progress\n  1-a: done\n  1-b:\n    status: done\n"
+        );
+        assert_eq!(update_story_statuses(yaml, &[]).unwrap(), yaml);
+
+        let result = update_story_statuses(yaml, &[("1-a", "done"), ("9-missing", "done")]);
+        assert!(matches!(result, Err(SprintError::StoryNotFound(id)) if id == "9-missing"));
+    }
+
     // =========================================================================
-    // Regex Tests
+    // ID Pattern Tests
     // =========================================================================
 
     #[test]
-    fn test_static_regex_initialization() {
-        // Test that the static regexes are properly initialized
-        // by attempting to match against valid patterns
-        assert!(EPIC_REGEX.is_match("epic-1"));
-        assert!(EPIC_REGEX.is_match("epic-99"));
-        assert!(!EPIC_REGEX.is_match("epic-"));
-        assert!(!EPIC_REGEX.is_match("not-an-epic"));
-
-        assert!(STORY_REGEX.is_match("1-story"));
-        assert!(STORY_REGEX.is_match("99-another-story"));
-        assert!(!STORY_REGEX.is_match("story-no-prefix"));
+    fn test_id_patterns() {
+        assert_eq!(epic_number("epic-1"), Some("1"));
+        assert_eq!(epic_number("epic-99"), Some("99"));
+        assert_eq!(epic_number("epic-"), None);
+        assert_eq!(epic_number("not-an-epic"), None);
+
+        assert_eq!(story_epic("1-story"), Some("1"));
+        assert_eq!(story_epic("99-another-story"), Some("99"));
+        assert_eq!(story_epic("story-no-prefix"), None);
     }
 
     #[test]
-    fn test_epic_regex_edge_cases() {
+    fn test_epic_number_edge_cases() {
         // Valid patterns
-        assert!(EPIC_REGEX.is_match("epic-0"));
-        assert!(EPIC_REGEX.is_match("epic-999"));
-        assert!(EPIC_REGEX.is_match("epic-12345"));
+        assert_eq!(epic_number("epic-0"), Some("0"));
+        assert_eq!(epic_number("epic-999"), Some("999"));
+        assert_eq!(epic_number("epic-12345"), Some("12345"));
 
         // Invalid patterns
-        assert!(!EPIC_REGEX.is_match("EPIC-1")); // Case sensitive
-        assert!(!EPIC_REGEX.is_match("epic--1")); // Double dash
-        assert!(!EPIC_REGEX.is_match("epic-1-extra")); // Extra content
-        assert!(!EPIC_REGEX.is_match("prefix-epic-1")); // Prefix
+        assert_eq!(epic_number("EPIC-1"), None); // Case sensitive
+        assert_eq!(epic_number("epic--1"), None); // Double dash
+        assert_eq!(epic_number("epic-1-extra"), None); // Extra content
+        assert_eq!(epic_number("prefix-epic-1"), None); // Prefix
     }
 
     #[test]
-    fn test_story_regex_edge_cases() {
+    fn test_story_epic_edge_cases() {
         // Valid patterns
-        assert!(STORY_REGEX.is_match("1-x"));
-        assert!(STORY_REGEX.is_match("123-long-story-name"));
-        assert!(STORY_REGEX.is_match("0-zero-prefix"));
+        assert_eq!(story_epic("1-x"), Some("1"));
+        assert_eq!(story_epic("123-long-story-name"), Some("123"));
+        assert_eq!(story_epic("0-zero-prefix"), Some("0"));
 
         // Invalid patterns
-        assert!(!STORY_REGEX.is_match("-1-negative")); // Negative-like
-        assert!(!STORY_REGEX.is_match("abc-story")); // Non-numeric prefix
-    }
-
-    #[test]
-    fn test_escape_regex_special_chars() {
-        // Test internal escape_regex function
-        let escaped = escape_regex("1-my.story[0]");
-        assert!(escaped.contains("\\.")); // Dot escaped
-        assert!(escaped.contains("\\[")); // Bracket escaped
-        assert!(escaped.contains("\\]")); // Bracket escaped
-        assert!(escaped.contains("\\-")); // Hyphen escaped
-    }
-
-    #[test]
-    fn test_escape_regex_all_special_chars() {
-        let input = "a.b*c+d?e^f$g{h}i(j)k|l[m]n\\o-p";
-        let escaped = escape_regex(input);
-        // All special chars should be escaped
-        assert!(escaped.contains("\\."));
-        assert!(escaped.contains("\\*"));
-        assert!(escaped.contains("\\+"));
-        assert!(escaped.contains("\\?"));
-        assert!(escaped.contains("\\^"));
-        assert!(escaped.contains("\\$"));
-        assert!(escaped.contains("\\{"));
-        assert!(escaped.contains("\\}"));
-        assert!(escaped.contains("\\("));
-        assert!(escaped.contains("\\)"));
-        assert!(escaped.contains("\\|"));
-        assert!(escaped.contains("\\["));
-        assert!(escaped.contains("\\]"));
-        assert!(escaped.contains("\\\\"));
-        assert!(escaped.contains("\\-"));
+        assert_eq!(story_epic("-1-negative"), None); // Negative-like
+        assert_eq!(story_epic("abc-story"), None); // Non-numeric prefix
+    }
+
+    #[test]
+    fn test_update_story_id_special_chars() {
+        // Only the exact key changes; `.` and brackets are not wildcards
+        let yaml = "development_status:\n  epic-1: in-progress\n  1-myXstory[0]: backlog\n  1-my.story[0]: backlog\n";
+        let updated = update_story_status(yaml, "1-my.story[0]", "done").expect("Should update");
+        assert_eq!(
+            updated,
+            "development_status:\n  epic-1: in-progress\n  1-myXstory[0]: backlog\n  1-my.story[0]: done\n"
+        );
+        assert!(matches!(
+            update_story_status(yaml, "1-my.story", "done"),
+            Err(SprintError::StoryNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_update_story_id_all_special_chars() {
+        let id = "1-a.b*c+d?e^f$g{h}i(j)k|l[m]n\\o-p";
+        let yaml = format!(
+            "development_status:\n  epic-1: in-progress\n  1-abc: backlog\n  '{}': backlog\n",
+            id
+        );
+        let updated = update_story_status(&yaml, id, "review").expect("Should update");
+        assert_eq!(updated, yaml.replace("': backlog", "': review"));
+        let data = parse_sprint_status(&updated).expect("Should parse");
+        assert_eq!(data.find_story(id).unwrap().status, "review");
+        assert_eq!(data.find_story("1-abc").unwrap().status, "backlog");
+    }
+
+    // =========================================================================
+    // Single Story Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_story_matches_full_parse() {
+        let yaml = r#"development_status:
+  epic-1: in-progress
+  1-login:
+    status: review
+    assignee: ana
+    tags: [auth, ui]
+    blocked_by: [1-setup]
+  1-setup: done
+  epic-1-retrospective: optional
+  epic-2: backlog
+  2-export: backlog
+"#;
+        let full = parse_sprint_status(yaml).unwrap();
+        for story in full.epics.iter().flat_map(|epic| &epic.stories) {
+            assert_eq!(&parse_story(yaml, &story.id).unwrap(), story);
+        }
+        let login = parse_story(yaml, "1-login").unwrap();
+        assert_eq!(login.assignee.as_deref(), Some("ana"));
+        assert_eq!(login.blocked_by, vec!["1-setup"]);
+    }
+
+    #[test]
+    fn test_parse_story_not_found() {
+        let yaml = "development_status:\n  epic-1: done\n  1-a: done\n  3-orphan: backlog\n";
+        for id in [
+            "epic-1",
+            "epic-1-retrospective",
+            "3-orphan",
+            "1-missing",
+            "no-prefix",
+        ] {
+            assert!(matches!(
+                parse_story(yaml, id),
+                Err(SprintError::StoryNotFound(_))
+            ));
+        }
+        // Flow-style documents fall back to the full parse
+        let flow = "development_status: {epic-1: done, 1-a: review}\n";
+        assert_eq!(parse_story(flow, "1-a").unwrap().status, "review");
+        assert!(matches!(
+            parse_story("development_status: [unclosed", "1-a"),
+            Err(SprintError::ParseError(_))
+        ));
     }
 
     // =========================================================================
@@ -476,6 +1888,138 @@ development_status:
 
         let update_err = SprintError::UpdateError("update failed".to_string());
         assert_eq!(format!("{}", update_err), "Update failed: update failed");
+
+        let invalid_err = SprintError::InvalidStoryId("bad id".to_string());
+        assert_eq!(format!("{}", invalid_err), "Invalid story ID: bad id");
+
+        let duplicate_err = SprintError::DuplicateStory("1-story".to_string());
+        assert_eq!(
+            format!("{}", duplicate_err),
+            "Story already exists: 1-story"
+        );
+
+        let status_err = SprintError::InvalidStatus("bad".to_string());
+        assert_eq!(format!("{}", status_err), "Invalid status: bad");
+
+        let limit_err = SprintError::ResourceLimit("too deep".to_string());
+        assert_eq!(
+            format!("{}", limit_err),
+            "Resource limit exceeded: too deep"
+        );
+    }
+
+    #[test]
+    fn test_parse_with_limits() {
+        let yaml = "project: Demo\ndevelopment_status:\n  epic-1: backlog\n  1-story: backlog\n";
+        let limits = ParseLimits {
+            max_nodes: 3,
+            ..ParseLimits::default()
+        };
+        let result = parse_sprint_status_with_limits(yaml, &limits);
+        assert!(matches!(result, Err(SprintError::ResourceLimit(_))));
+        assert!(parse_sprint_status_with_limits(yaml, &ParseLimits::default()).is_ok());
+
+        let limits = ParseLimits {
+            max_items: 1,
+            ..ParseLimits::default()
+        };
+        let result = parse_sprint_status_with_limits(yaml, &limits);
+        assert!(matches!(result, Err(SprintError::ResourceLimit(m)) if m.contains("2 entries")));
+    }
+
+    #[test]
+    fn test_parse_with_normalize_option() {
+        let yaml = r#"
+development_status:
+  epic-1: Completed
+  1-1-login: complete
+  1-2-signup: { status: To Do }
+  1-3-profile: shipped
+"#;
+        let raw = parse_sprint_status(yaml).unwrap();
+        assert_eq!(raw.epics[0].status, "Completed");
+        assert_eq!(raw.epics[0].stories[0].status, "complete");
+
+        let options = ParseOptions::default().with_normalize(StatusAliases::default());
+        let data = parse_sprint_status_with_options(yaml, &options).unwrap();
+        assert_eq!(data.epics[0].status, "done");
+        let statuses: Vec<&str> = data.epics[0]
+            .stories
+            .iter()
+            .map(|s| s.status.as_str())
+            .collect();
+        assert_eq!(statuses, vec!["done", "backlog", "shipped"]);
+
+        let custom = ParseOptions::default()
+            .with_normalize(StatusAliases::default().with_alias("shipped", "done"));
+        let data = parse_sprint_status_with_options(yaml, &custom).unwrap();
+        assert_eq!(data.epics[0].stories[2].status, "done");
+    }
+
+    #[test]
+    fn test_parse_with_registry_tags_custom_statuses() {
+        let yaml = r#"
+development_status:
+  epic-1: in-progress
+  1-1-login: shipped
+  1-2-signup: { status: On Hold, blocked_by: [1-1-login] }
+  1-3-profile: { status: backlog, blocked_by: [1-2-signup] }
+  1-4-avatar: parked
+"#;
+        let registry = StatusRegistry::new()
+            .with_status(StatusInfo::new("shipped", "Shipped").terminal())
+            .with_status(StatusInfo::new("on-hold", "On hold").with_color("yellow"));
+
+        let raw = parse_sprint_status(yaml).unwrap();
+        assert!(
+            raw.epics[0]
+                .stories
+                .iter()
+                .all(|s| s.custom_status.is_none())
+        );
+
+        let options = ParseOptions::default().with_registry(registry);
+        let data = parse_sprint_status_with_options(yaml, &options).unwrap();
+        let story = |id: &str| data.find_story(id).unwrap();
+        assert_eq!(story("1-1-login").status, "shipped");
+        assert_eq!(
+            story("1-1-login")
+                .custom_status
+                .as_ref()
+                .map(|c| c.is_terminal),
+            Some(true)
+        );
+        assert_eq!(
+            story("1-2-signup")
+                .custom_status
+                .as_ref()
+                .map(|c| c.display_name.as_str()),
+            Some("On hold")
+        );
+        assert!(story("1-3-profile").custom_status.is_none());
+        assert!(story("1-4-avatar").custom_status.is_none());
+
+        // A terminal custom status releases dependents
+        let blocked: Vec<&str> = data
+            .blocked_stories()
+            .iter()
+            .map(|b| b.story.id.as_str())
+            .collect();
+        assert_eq!(blocked, vec!["1-3-profile"]);
+    }
+
+    #[test]
+    fn test_parse_with_options_enforces_limits() {
+        let yaml = "development_status:\n  epic-1: backlog\n  1-1-a: done\n";
+        let limits = ParseLimits {
+            max_items: 1,
+            ..ParseLimits::default()
+        };
+        let options = ParseOptions::default().with_limits(limits);
+        assert!(matches!(
+            parse_sprint_status_with_options(yaml, &options),
+            Err(SprintError::ResourceLimit(_))
+        ));
     }
 
     #[test]
@@ -0,0 +1,335 @@
+// clique-core/src/story_doc.rs
+//! Parsing and status updates for per-story markdown files.
+//!
+//! The dev agent writes one `.md` file per story. Status lives either in
+//! YAML front-matter (`status: in-progress` between `---` fences) or, in
+//! older templates, on a `Status: drafted` line in the body. Front-matter
+//! wins when both are present.
+
+use crate::pattern::{heading, status_line};
+use crate::yaml_line::{
+    find_value, format_scalar, line_ending, needs_quotes, replace_field, splice,
+};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::ops::Range;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StoryDocError {
+    #[error("Failed to parse front-matter: {0}")]
+    ParseError(String),
+    #[error("Story document has no status field")]
+    StatusNotFound,
+    #[error("Invalid status: {0}")]
+    InvalidStatus(String),
+}
+
+/// What a story markdown file says about itself
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct StoryDoc {
+    /// Sprint story ID from the front-matter `id` key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub id: Option<String>,
+    /// Text of the first top-level heading
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub epic: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub assignee: Option<String>,
+    /// Second-level and deeper headings, in document order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<String>,
+}
+
+/// Byte range of the front-matter body (between the fences), if any
+fn front_matter(content: &str) -> Option<Range<usize>> {
+    let start = content.strip_prefix('\u{feff}').map_or(0, |_| 3);
+    let rest = &content[start..];
+    let first_break = rest.find('\n')?;
+    if rest[..first_break].trim_end() != "---" {
+        return None;
+    }
+    let body_start = start + first_break + 1;
+
+    let mut offset = body_start;
+    for line in content[body_start..].split_inclusive('\n') {
+        let text = line.trim_end();
+        if text == "---" || text == "..." {
+            return Some(body_start..offset);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Front-matter value as a string, accepting numbers too
+fn scalar_string(value: &Value, key: &str) -> Option<String> {
+    match value.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a story markdown file.
+///
+/// Fails only when the front-matter is present but is not valid YAML; a file
+/// without any metadata parses to a mostly empty [`StoryDoc`].
+pub fn parse_story_markdown(content: &str) -> Result<StoryDoc, StoryDocError> {
+    let mut doc = StoryDoc::default();
+    if let Some(range) = front_matter(content) {
+        let meta: Value = serde_yaml::from_str(&content[range])
+            .map_err(|e| StoryDocError::ParseError(e.to_string()))?;
+        doc.id = scalar_string(&meta, "id");
+        doc.status = scalar_string(&meta, "status");
+        doc.epic = scalar_string(&meta, "epic");
+        doc.points = meta.get("points").and_then(|v| v.as_f64());
+        doc.assignee = scalar_string(&meta, "assignee");
+    }
+    let body = &content[body_start(content)..];
+
+    if doc.status.is_none() {
+        doc.status = status_line(body).map(|range| body[range].to_string());
+    }
+
+    let mut in_code = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if let Some((level, text)) = heading(line) {
+            let text = text.to_string();
+            if level == 1 && doc.title.is_none() {
+                doc.title = Some(text);
+            } else if level > 1 {
+                doc.sections.push(text);
+            }
+        }
+    }
+
+    Ok(doc)
+}
+
+/// Set the status of a story markdown file, preserving everything else.
+///
+/// Updates the front-matter `status:` when there is front-matter (adding the
+/// key if missing) and the body's `Status:` line when there is one, so a
+/// file carrying both never contradicts itself.
+pub fn update_story_doc_status(content: &str, new_status: &str) -> Result<String, StoryDocError> {
+    if new_status.trim().is_empty() || new_status.chars().any(char::is_control) {
+        return Err(StoryDocError::InvalidStatus(
+            new_status.escape_debug().to_string(),
+        ));
+    }
+
+    if let Some(range) = front_matter(content) {
+        let meta = &content[range.clone()];
+        serde_yaml::from_str::<Value>(meta)
+            .map_err(|e| StoryDocError::ParseError(e.to_string()))?;
+        let updated = match find_value(meta, &["status"]) {
+            Some(span) => {
+                let original = &meta[span];
+                let value = format_scalar(original, new_status, needs_quotes(new_status));
+                let updated = replace_field(meta, &["status"], &value).unwrap_or_default();
+                splice(content, range, &updated)
+            }
+            None => {
+                let value = format_scalar("", new_status, needs_quotes(new_status));
+                let line = format!("status: {}{}", value, line_ending(content));
+                splice(content, range.start..range.start, &line)
+            }
+        };
+        let body = body_start(&updated);
+        return Ok(match status_line(&updated[body..]) {
+            Some(span) => splice(&updated, body + span.start..body + span.end, new_status),
+            None => updated,
+        });
+    }
+
+    let span = status_line(content).ok_or(StoryDocError::StatusNotFound)?;
+    Ok(splice(content, span, new_status))
+}
+
+/// Byte offset of the body, after the closing front-matter fence if any
+fn body_start(content: &str) -> usize {
+    front_matter(content).map_or(0, |range| {
+        let fence = &content[range.end..];
+        range.end + fence.find('\n').map_or(fence.len(), |i| i + 1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRONT_MATTER_DOC: &str = "---\nid: 1-2-user-login\nstatus: in-progress # set by dev agent\nepic: 1\npoints: 5\nassignee: Alex\n---\n\n# Story 1.2: User Login\n\n## Story\n\nAs a user...\n\n## Acceptance Criteria\n\n```md\n# not a heading\n```\n\n### Tasks\n";
+
+    const STATUS_LINE_DOC: &str =
+        "# Story 2.1: Export CSV\n\nStatus: drafted\n\n## Story\n\n## Dev Notes\n";
+
+    // =========================================================================
+    // Parsing Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_front_matter() {
+        let doc = parse_story_markdown(FRONT_MATTER_DOC).unwrap();
+        assert_eq!(doc.id.as_deref(), Some("1-2-user-login"));
+        assert_eq!(doc.status.as_deref(), Some("in-progress"));
+        assert_eq!(doc.epic.as_deref(), Some("1"));
+        assert_eq!(doc.points, Some(5.0));
+        assert_eq!(doc.assignee.as_deref(), Some("Alex"));
+        assert_eq!(doc.title.as_deref(), Some("Story 1.2: User Login"));
+        assert_eq!(doc.sections, vec!["Story", "Acceptance Criteria", "Tasks"]);
+    }
+
+    #[test]
+    fn test_parse_status_line() {
+        let doc = parse_story_markdown(STATUS_LINE_DOC).unwrap();
+        assert_eq!(doc.status.as_deref(), Some("drafted"));
+        assert_eq!(doc.title.as_deref(), Some("Story 2.1: Export CSV"));
+        assert_eq!(doc.sections, vec!["Story", "Dev Notes"]);
+        assert!(doc.epic.is_none());
+        assert!(doc.points.is_none());
+
+        let bold = parse_story_markdown("**Status:** review\r\n").unwrap();
+        assert_eq!(bold.status.as_deref(), Some("review"));
+        let prose = parse_story_markdown("Status quo is fine\n").unwrap();
+        assert!(prose.status.is_none());
+    }
+
+    #[test]
+    fn test_front_matter_status_wins() {
+        let doc = parse_story_markdown("---\nstatus: done\n---\nStatus: drafted\n").unwrap();
+        assert_eq!(doc.status.as_deref(), Some("done"));
+    }
+
+    #[test]
+    fn test_parse_without_metadata() {
+        let doc = parse_story_markdown("Just some notes\n").unwrap();
+        assert_eq!(doc, StoryDoc::default());
+        // An unterminated fence is body text, not front-matter
+        let doc = parse_story_markdown("---\nstatus: done\n").unwrap();
+        assert!(doc.epic.is_none());
+    }
+
+    #[test]
+    fn test_parse_invalid_front_matter() {
+        assert!(matches!(
+            parse_story_markdown("---\nstatus: [unclosed\n---\n"),
+            Err(StoryDocError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_bom_and_crlf() {
+        let doc =
+            parse_story_markdown("\u{feff}---\r\nstatus: review\r\n---\r\n# Title\r\n").unwrap();
+        assert_eq!(doc.status.as_deref(), Some("review"));
+        assert_eq!(doc.title.as_deref(), Some("Title"));
+    }
+
+    // =========================================================================
+    // Update Tests
+    // =========================================================================
+
+    #[test]
+    fn test_update_front_matter_status() {
+        let updated = update_story_doc_status(FRONT_MATTER_DOC, "review").unwrap();
+        assert_eq!(
+            updated,
+            FRONT_MATTER_DOC.replace("status: in-progress #", "status: review #")
+        );
+        let doc = parse_story_markdown(&updated).unwrap();
+        assert_eq!(doc.status.as_deref(), Some("review"));
+    }
+
+    #[test]
+    fn test_update_replaces_block_scalar_status() {
+        let updated =
+            update_story_doc_status("---\nstatus: |\n  multi\nepic: 1\n---\n", "done").unwrap();
+        assert_eq!(updated, "---\nstatus: done\nepic: 1\n---\n");
+        let doc = parse_story_markdown(&updated).unwrap();
+        assert_eq!(doc.status.as_deref(), Some("done"));
+    }
+
+    #[test]
+    fn test_update_front_matter_and_status_line() {
+        let doc = "---\nstatus: draft\n---\n# Story 1.1\n\nStatus: draft\n";
+        let updated = update_story_doc_status(doc, "done").unwrap();
+        assert_eq!(
+            updated,
+            "---\nstatus: done\n---\n# Story 1.1\n\nStatus: done\n"
+        );
+
+        let inserted =
+            update_story_doc_status("---\nepic: 1\n---\nStatus: draft\n", "done").unwrap();
+        assert_eq!(inserted, "---\nstatus: done\nepic: 1\n---\nStatus: done\n");
+    }
+
+    #[test]
+    fn test_update_inserts_missing_front_matter_status() {
+        let updated = update_story_doc_status("---\r\nepic: 1\r\n---\r\n# T\r\n", "done").unwrap();
+        assert_eq!(updated, "---\r\nstatus: done\r\nepic: 1\r\n---\r\n# T\r\n");
+    }
+
+    #[test]
+    fn test_update_status_line() {
+        let updated = update_story_doc_status(STATUS_LINE_DOC, "ready-for-dev").unwrap();
+        assert_eq!(
+            updated,
+            STATUS_LINE_DOC.replace("Status: drafted", "Status: ready-for-dev")
+        );
+    }
+
+    #[test]
+    fn test_update_quotes_structural_values() {
+        let updated = update_story_doc_status("---\nstatus: draft\n---\n", "a: b").unwrap();
+        assert_eq!(updated, "---\nstatus: \"a: b\"\n---\n");
+        let doc = parse_story_markdown(&updated).unwrap();
+        assert_eq!(doc.status.as_deref(), Some("a: b"));
+    }
+
+    #[test]
+    fn test_update_errors() {
+        assert!(matches!(
+            update_story_doc_status("# No status here\n", "done"),
+            Err(StoryDocError::StatusNotFound)
+        ));
+        assert!(matches!(
+            update_story_doc_status(STATUS_LINE_DOC, "done\nStatus: x"),
+            Err(StoryDocError::InvalidStatus(_))
+        ));
+        assert!(matches!(
+            update_story_doc_status(STATUS_LINE_DOC, "  "),
+            Err(StoryDocError::InvalidStatus(_))
+        ));
+    }
+
+    #[test]
+    fn test_story_doc_error_display() {
+        assert_eq!(
+            StoryDocError::StatusNotFound.to_string(),
+            "Story document has no status field"
+        );
+    }
+}
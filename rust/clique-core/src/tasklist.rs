@@ -0,0 +1,307 @@
+// clique-core/src/tasklist.rs
+//! Markdown tasklists as a workflow dialect.
+//!
+//! Small projects often track their plan as a GitHub-style tasklist in a
+//! README or `planning.md` instead of a YAML file:
+//!
+//! ```markdown
+//! # Demo
+//!
+//! ## Phase 1
+//! - [x] prd
+//! - [ ] `architecture` Decide on the service layout
+//! - [-] ux-design
+//! ```
+//!
+//! [`Tasklist`] reads each task as a workflow item: `[x]` is complete,
+//! `[ ]` required and `[-]` skipped. An item's ID is the task's first code
+//! span, else the task text when it is a single word, else a slug of the
+//! text. A `Phase N` heading sets the phase of the tasks below it. Updates
+//! only rewrite the character between the brackets.
+
+use crate::adapter::FormatAdapter;
+use crate::text::text;
+use crate::types::{Phase, StatusClass, StoryStatus, WorkflowData, WorkflowItem, normalize_status};
+use crate::workflow::{WorkflowError, infer_agent, infer_command, infer_phase};
+
+/// GitHub-style markdown tasklists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tasklist;
+
+/// One task line
+struct Task<'a> {
+    /// Byte offset of the mark between the brackets
+    mark_at: usize,
+    mark: char,
+    text: &'a str,
+    phase: Option<i32>,
+}
+
+/// The mark and text of a `- [x] text` line
+fn task_line(line: &str) -> Option<(usize, char, &str)> {
+    let trimmed = line.trim_start();
+    let bullet = trimmed.strip_prefix(['-', '*', '+'])?.strip_prefix(' ')?;
+    let rest = bullet.strip_prefix('[')?;
+    let mark = rest.chars().next()?;
+    let text = rest[mark.len_utf8()..].strip_prefix("] ")?;
+    let mark_at = line.len() - rest.len();
+    Some((mark_at, mark, text.trim()))
+}
+
+/// Phase number of a heading such as `## Phase 2: Solutioning`
+fn heading_phase(line: &str) -> Option<i32> {
+    let heading = line.trim_start_matches('#').trim_start();
+    let lower = heading.to_ascii_lowercase();
+    let number = lower.strip_prefix("phase")?.trim_start();
+    let digits: String = number.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// Task lines outside fenced code blocks, with the phase heading above them
+fn tasks(content: &str) -> Vec<Task<'_>> {
+    let mut tasks = Vec::new();
+    let mut fenced = false;
+    let mut phase = None;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let line = line.trim_end_matches(['\n', '\r']);
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fenced = !fenced;
+            continue;
+        }
+        if fenced {
+            continue;
+        }
+        if trimmed.starts_with('#') {
+            phase = heading_phase(trimmed);
+        } else if let Some((mark_at, mark, text)) = task_line(line) {
+            tasks.push(Task {
+                mark_at: start + mark_at,
+                mark,
+                text,
+                phase,
+            });
+        }
+    }
+    tasks
+}
+
+/// Item ID of a task's text
+fn task_id(text: &str) -> String {
+    if let Some((_, rest)) = text.split_once('`')
+        && let Some((code, _)) = rest.split_once('`')
+        && !code.trim().is_empty()
+    {
+        return code.trim().to_string();
+    }
+    if !text.contains(char::is_whitespace) {
+        return text.to_string();
+    }
+    let slug: String = text
+        .chars()
+        .map(|c| match c.is_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '-',
+        })
+        .collect();
+    slug.split('-')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Status of a task mark
+fn mark_status(mark: char) -> &'static str {
+    match mark {
+        'x' | 'X' => "complete",
+        '-' => "skipped",
+        _ => "required",
+    }
+}
+
+/// Task mark for a status: checked when it means the work is done
+fn status_mark(status: &str) -> char {
+    match StatusClass::of(status) {
+        StatusClass::Skipped => '-',
+        StatusClass::Pending => ' ',
+        // Unrecognized statuses are output paths; sprint words are not
+        StatusClass::Complete => match normalize_status(status) {
+            StoryStatus::Done | StoryStatus::Completed | StoryStatus::Unknown => 'x',
+            _ => ' ',
+        },
+    }
+}
+
+impl FormatAdapter for Tasklist {
+    fn name(&self) -> &'static str {
+        "tasklist"
+    }
+
+    /// Documents with at least one task line and no YAML workflow keys
+    fn detect(&self, content: &str) -> bool {
+        let yaml = content
+            .lines()
+            .any(|line| line.starts_with("workflows:") || line.starts_with("workflow_status:"));
+        !yaml && !tasks(content).is_empty()
+    }
+
+    fn parse_items(&self, content: &str) -> Result<Vec<WorkflowItem>, WorkflowError> {
+        Ok(tasks(content)
+            .into_iter()
+            .map(|task| {
+                let id = task_id(task.text);
+                WorkflowItem {
+                    phase: task.phase.map_or_else(|| infer_phase(&id), Phase::Number),
+                    status: text(mark_status(task.mark)),
+                    agent: Some(text(infer_agent(&id))),
+                    command: Some(infer_command(&id)),
+                    note: None,
+                    output_file: None,
+                    custom_status: None,
+                    depends_on: Vec::new(),
+                    tracks: Vec::new(),
+                    tags: Vec::new(),
+                    description: (task.text != id).then(|| task.text.to_string()),
+                    due: None,
+                    id,
+                }
+            })
+            .collect())
+    }
+
+    fn apply_update(
+        &self,
+        content: &str,
+        item_id: &str,
+        new_status: &str,
+    ) -> Result<String, WorkflowError> {
+        let task = tasks(content)
+            .into_iter()
+            .find(|task| task_id(task.text) == item_id)
+            .ok_or_else(|| WorkflowError::ItemNotFound(item_id.to_string()))?;
+        let mut updated = content.to_string();
+        updated.replace_range(
+            task.mark_at..task.mark_at + task.mark.len_utf8(),
+            &status_mark(new_status).to_string(),
+        );
+        Ok(updated)
+    }
+
+    /// The items, with the first top-level heading as the project name
+    fn parse(&self, content: &str) -> Result<WorkflowData, WorkflowError> {
+        let project = content
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        Ok(WorkflowData {
+            last_updated: String::new(),
+            status: String::new(),
+            status_note: None,
+            project,
+            project_type: String::new(),
+            selected_track: String::new(),
+            field_type: String::new(),
+            workflow_path: String::new(),
+            items: self.parse_items(content)?,
+            phases: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::FormatRegistry;
+
+    const PLAN: &str = "# Demo\n\nSome intro with [a link](x).\n\n## Phase 0\n- [x] brainstorm\n\n\
+                        ## Phase 2: Solutioning\n- [ ] `architecture` Decide on the layout\n  \
+                        * [-] Write UX notes\n\n```markdown\n- [ ] example\n```\n- [X] prd\n";
+
+    // =========================================================================
+    // Parse Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_tasklist() {
+        let data = Tasklist.parse(PLAN).unwrap();
+        assert_eq!(data.project, "Demo");
+        let items: Vec<(&str, &str, Phase)> = data
+            .items
+            .iter()
+            .map(|item| (item.id.as_str(), &*item.status, item.phase))
+            .collect();
+        assert_eq!(
+            items,
+            [
+                ("brainstorm", "complete", Phase::Number(0)),
+                ("architecture", "required", Phase::Number(2)),
+                ("write-ux-notes", "skipped", Phase::Number(2)),
+                ("prd", "complete", Phase::Number(2)),
+            ]
+        );
+        assert_eq!(
+            data.items[1].description.as_deref(),
+            Some("`architecture` Decide on the layout")
+        );
+        assert_eq!(data.items[0].description, None);
+        assert_eq!(data.items[3].agent.as_deref(), Some("pm"));
+    }
+
+    #[test]
+    fn test_detect() {
+        assert!(Tasklist.detect(PLAN));
+        assert!(!Tasklist.detect("# Notes\n- plain bullet\n"));
+        assert!(!Tasklist.detect("workflow_status:\n  prd: required\n# - [ ] x\n"));
+        assert!(!Tasklist.detect("```\n- [ ] only in code\n```\n"));
+
+        let registry = FormatRegistry::new();
+        assert_eq!(registry.detect(PLAN).unwrap().name(), "tasklist");
+        assert_eq!(
+            registry
+                .detect("workflow_status:\n  prd: required\n")
+                .unwrap()
+                .name(),
+            "flat"
+        );
+    }
+
+    // =========================================================================
+    // Update Tests
+    // =========================================================================
+
+    #[test]
+    fn test_check_and_uncheck() {
+        let registry = FormatRegistry::new();
+        let checked = registry.update(PLAN, "architecture", "complete").unwrap();
+        assert!(checked.contains("- [x] `architecture` Decide on the layout\n"));
+        assert_eq!(checked.len(), PLAN.len());
+
+        let unchecked = registry.update(&checked, "prd", "required").unwrap();
+        assert!(unchecked.contains("- [ ] prd\n"));
+        let skipped = registry.update(PLAN, "prd", "skipped").unwrap();
+        assert!(skipped.contains("- [-] prd\n"));
+        let shipped = registry
+            .update(PLAN, "write-ux-notes", "docs/ux.md")
+            .unwrap();
+        assert!(shipped.contains("  * [x] Write UX notes\n"));
+
+        assert!(matches!(
+            Tasklist.apply_update(PLAN, "example", "done"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_status_marks() {
+        assert_eq!(status_mark("done"), 'x');
+        assert_eq!(status_mark("Completed"), 'x');
+        assert_eq!(status_mark("in-progress"), ' ');
+        assert_eq!(status_mark("optional"), ' ');
+        assert_eq!(status_mark("skipped"), '-');
+    }
+}
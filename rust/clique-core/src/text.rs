@@ -0,0 +1,257 @@
+// clique-core/src/text.rs
+//! Compact storage for heavily repeated strings.
+//!
+//! Statuses and agent names repeat across every item of a large status
+//! file, and their allocations dominate parsing of files with thousands of
+//! items. [`Atom`] is a shared, interned `Arc<str>`: every `required` status
+//! parsed on a thread points at the same allocation.
+//!
+//! [`Text`] is the type of those fields. It is a plain `String` unless the
+//! `compact` feature is enabled, in which case it is an [`Atom`]. Code that
+//! builds values with [`text`] and reads them with `as_str()` or as `&str`
+//! works with either.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Storage for statuses and agent names; see the [module docs](self).
+#[cfg(feature = "compact")]
+pub type Text = Atom;
+
+/// Storage for statuses and agent names; see the [module docs](self).
+#[cfg(not(feature = "compact"))]
+pub type Text = String;
+
+/// Store a string as [`Text`]: interned under `compact`, moved as is
+/// otherwise.
+#[cfg(feature = "compact")]
+pub fn text(s: impl AsRef<str>) -> Text {
+    Atom::new(s.as_ref())
+}
+
+/// Store a string as [`Text`]: interned under `compact`, moved as is
+/// otherwise.
+#[cfg(not(feature = "compact"))]
+pub fn text(s: impl Into<String>) -> Text {
+    s.into()
+}
+
+/// Distinct strings kept in a thread's intern pool. Statuses come from a
+/// small vocabulary, but completed workflow items use their output path as
+/// the status, so the pool is bounded; strings beyond it are still shared
+/// between clones, just not across parses.
+pub const MAX_INTERNED: usize = 1024;
+
+thread_local! {
+    static POOL: RefCell<HashSet<Arc<str>>> = RefCell::new(HashSet::new());
+}
+
+/// An immutable, interned string that is cheap to clone
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Atom(Arc<str>);
+
+impl Atom {
+    /// The interned copy of `s`, allocating only the first time it is seen
+    /// on this thread.
+    pub fn new(s: &str) -> Self {
+        POOL.with_borrow_mut(|pool| {
+            if let Some(hit) = pool.get(s) {
+                return Atom(Arc::clone(hit));
+            }
+            let shared: Arc<str> = Arc::from(s);
+            if pool.len() < MAX_INTERNED {
+                pool.insert(Arc::clone(&shared));
+            }
+            Atom(shared)
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether two atoms share one allocation
+    pub fn ptr_eq(&self, other: &Atom) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Default for Atom {
+    fn default() -> Self {
+        Atom::new("")
+    }
+}
+
+impl Deref for Atom {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Atom {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Atom {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl From<&str> for Atom {
+    fn from(s: &str) -> Self {
+        Atom::new(s)
+    }
+}
+
+impl From<String> for Atom {
+    fn from(s: String) -> Self {
+        Atom::new(&s)
+    }
+}
+
+impl From<&String> for Atom {
+    fn from(s: &String) -> Self {
+        Atom::new(s)
+    }
+}
+
+impl From<Atom> for String {
+    fn from(atom: Atom) -> Self {
+        atom.0.to_string()
+    }
+}
+
+impl PartialEq<str> for Atom {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Atom {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Atom {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<Atom> for str {
+    fn eq(&self, other: &Atom) -> bool {
+        self == &*other.0
+    }
+}
+
+impl PartialEq<Atom> for &str {
+    fn eq(&self, other: &Atom) -> bool {
+        *self == &*other.0
+    }
+}
+
+impl PartialEq<Atom> for String {
+    fn eq(&self, other: &Atom) -> bool {
+        **self == *other.0
+    }
+}
+
+impl Serialize for Atom {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Atom {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s: std::borrow::Cow<'de, str> = Deserialize::deserialize(deserializer)?;
+        Ok(Atom::new(&s))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Atom {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        String::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        String::json_schema(generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // Interning Tests
+    // =========================================================================
+
+    #[test]
+    fn test_atoms_share_allocations() {
+        let a = Atom::new("in-progress");
+        let b = Atom::from("in-progress".to_string());
+        assert!(a.ptr_eq(&b));
+        assert!(!a.ptr_eq(&Atom::new("review")));
+        assert_eq!(a, b);
+        assert_eq!(a, "in-progress");
+        assert_eq!("in-progress", a);
+        assert_eq!(a.len(), 11);
+        assert_eq!(String::from(a.clone()), "in-progress");
+        assert_eq!(format!("{} {:?}", a, a), "in-progress \"in-progress\"");
+    }
+
+    #[test]
+    fn test_atom_serde_roundtrip() {
+        let atoms = vec![Atom::new("done"), Atom::default()];
+        let json = serde_json::to_string(&atoms).unwrap();
+        assert_eq!(json, r#"["done",""]"#);
+        let back: Vec<Atom> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, atoms);
+        assert!(back[0].ptr_eq(&atoms[0]));
+
+        let yaml: Atom = serde_yaml::from_str("\"backlog\"").unwrap();
+        assert_eq!(yaml, "backlog");
+    }
+
+    #[cfg(feature = "compact")]
+    #[test]
+    fn test_parsed_statuses_share_allocations() {
+        let workflow = "workflow_status:\n  prd: required\n  ux-design: required\n";
+        let data = crate::parse_workflow_status(workflow).unwrap();
+        assert!(data.items[0].status.ptr_eq(&data.items[1].status));
+
+        let sprint = "development_status:\n  epic-1: in-progress\n  1-a: review\n  1-b: review\n";
+        let data = crate::parse_sprint_status(sprint).unwrap();
+        let stories = &data.epics[0].stories;
+        assert!(stories[0].status.ptr_eq(&stories[1].status));
+    }
+}
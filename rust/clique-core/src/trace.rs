@@ -0,0 +1,128 @@
+// clique-core/src/trace.rs
+//! `tracing` instrumentation that compiles away without the feature.
+//!
+//! Entry points open their span with
+//! `#[cfg_attr(feature = "tracing", tracing::instrument(...))]`, declaring
+//! the fields only known once the work is done as `Empty`; [`record!`]
+//! fills them in.
+
+/// Record `field = value` pairs on the current span. Without the `tracing`
+/// feature the values are not even evaluated.
+macro_rules! record {
+    ($($field:literal = $value:expr),+ $(,)?) => {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            $(span.record($field, $value);)+
+        }
+    };
+}
+
+pub(crate) use record;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+    use tracing_core::span::Current;
+
+    /// `span: field=value` for every field set on a span, in order, with a
+    /// stack of entered spans so `Span::current()` works
+    #[derive(Clone, Default)]
+    struct Recorder {
+        spans: Arc<Mutex<Vec<&'static Metadata<'static>>>>,
+        fields: Arc<Mutex<Vec<String>>>,
+        entered: Arc<Mutex<Vec<Id>>>,
+    }
+
+    struct Fields<'a>(&'a str, &'a mut Vec<String>);
+
+    impl Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.1
+                .push(format!("{}: {}={:?}", self.0, field.name(), value));
+        }
+    }
+
+    impl Recorder {
+        fn name(&self, id: &Id) -> &'static str {
+            self.spans.lock().unwrap()[id.into_u64() as usize - 1].name()
+        }
+
+        fn metadata(&self, id: &Id) -> &'static Metadata<'static> {
+            self.spans.lock().unwrap()[id.into_u64() as usize - 1]
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata());
+            let name = span.metadata().name();
+            span.record(&mut Fields(name, &mut self.fields.lock().unwrap()));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, id: &Id, values: &Record<'_>) {
+            let name = self.name(id);
+            values.record(&mut Fields(name, &mut self.fields.lock().unwrap()));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, id: &Id) {
+            self.entered.lock().unwrap().push(id.clone());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> Current {
+            match self.entered.lock().unwrap().last() {
+                Some(id) => Current::new(id.clone(), self.metadata(id)),
+                None => Current::none(),
+            }
+        }
+    }
+
+    // =========================================================================
+    // Span Tests
+    // =========================================================================
+
+    #[test]
+    fn test_spans_carry_sizes_and_counts() {
+        let recorder = Recorder::default();
+        let sprint = "development_status:\n  epic-1: in-progress\n  1-1-login: done\n";
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let old = crate::parse_sprint_status(sprint).unwrap();
+            let new = crate::parse_sprint_status(&sprint.replace("done", "review")).unwrap();
+            crate::diff::diff_sprint(&old, &new);
+            crate::parse_workflow_status("workflow_status:\n  prd: required\n").unwrap();
+        });
+        let fields = recorder.fields.lock().unwrap();
+        for field in [
+            "parse_sprint_status_with_options: bytes=60",
+            "parse_sprint_status_with_options: epics=1",
+            "parse_sprint_status_with_options: stories=1",
+            "diff_sprint: old=2",
+            "diff_sprint: changes=1",
+            "parse_workflow_status_with_options: format=flat",
+            "parse_workflow_status_with_options: items=1",
+        ] {
+            assert!(
+                fields.iter().any(|f| f == field),
+                "{} not in {:?}",
+                field,
+                fields
+            );
+        }
+    }
+}
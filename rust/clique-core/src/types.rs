@@ -1,27 +1,372 @@
 // clique-core/src/types.rs
 //! Core types for the Clique extension.
 
+use crate::pattern::time_of_day;
+use crate::text::Text;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
 /// A workflow item from bmm-workflow-status.yaml
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct WorkflowItem {
     pub id: String,
     pub phase: Phase,
-    pub status: String,
+    #[cfg_attr(feature = "ts-types", ts(as = "String"))]
+    pub status: Text,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub agent: Option<String>,
+    #[cfg_attr(feature = "ts-types", ts(optional, as = "Option<String>"))]
+    pub agent: Option<Text>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
     pub command: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
     pub note: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
     pub output_file: Option<String>,
+    /// Registry entry for a status outside the built-in vocabulary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub custom_status: Option<StatusInfo>,
+    /// Workflow IDs that must be done or skipped before this item can start
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Tracks or field types this item applies to; empty means all
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tracks: Vec<String>,
+    /// What the workflow does, from the workflow definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub description: Option<String>,
+    /// Due date as written, e.g. `2025-03-01`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub due: Option<String>,
+    /// Free-form labels such as `backend` or `security`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Coarse classification of a workflow item's status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusClass {
+    /// Completed: the status is an output file path or an explicit completion marker
+    Complete,
+    /// Still to do: required, optional, recommended, conditional or not started
+    Pending,
+    /// Explicitly skipped
+    Skipped,
+}
+
+impl StatusClass {
+    /// Class of a built-in workflow status; anything unrecognized counts as
+    /// complete, since completed items carry their output file path.
+    pub(crate) fn of(status: &str) -> Self {
+        match status {
+            "skipped" => StatusClass::Skipped,
+            "required" | "optional" | "recommended" | "conditional" | "not_started" | "" => {
+                StatusClass::Pending
+            }
+            _ => StatusClass::Complete,
+        }
+    }
+}
+
+/// What a frontend should do with a workflow item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "kebab-case")]
+pub enum ItemState {
+    /// Finished
+    Done,
+    /// Required and ready to start
+    Actionable,
+    /// Required but waiting on a dependency or an earlier phase
+    Blocked,
+    /// Explicitly skipped
+    Skipped,
+    /// Optional, recommended or conditional; never blocks anything
+    Optional,
+    /// Not part of the project's selected track
+    NotApplicable,
+}
+
+/// A calendar date: [`chrono::NaiveDate`] with the `chrono` feature,
+/// otherwise the ISO `YYYY-MM-DD` string, which orders the same way.
+#[cfg(feature = "chrono")]
+pub type Date = chrono::NaiveDate;
+/// A calendar date: [`chrono::NaiveDate`] with the `chrono` feature,
+/// otherwise the ISO `YYYY-MM-DD` string, which orders the same way.
+#[cfg(not(feature = "chrono"))]
+pub type Date = String;
+
+/// Parse an ISO date, or the date part of an ISO date-time
+/// (`2025-03-01`, `2025-03-01T09:30:00Z`). Impossible dates are rejected.
+pub fn parse_date(value: &str) -> Option<Date> {
+    let value = value.trim();
+    let date = value.get(..10)?;
+    if !value[10..].is_empty() && !value[10..].starts_with(['T', ' ']) {
+        return None;
+    }
+
+    #[cfg(feature = "chrono")]
+    {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }
+    #[cfg(not(feature = "chrono"))]
+    {
+        let bytes = date.as_bytes();
+        let digits = [0, 1, 2, 3, 5, 6, 8, 9];
+        if bytes[4] != b'-'
+            || bytes[7] != b'-'
+            || digits.iter().any(|&i| !bytes[i].is_ascii_digit())
+        {
+            return None;
+        }
+        let year: u32 = date[..4].parse().ok()?;
+        let month: u32 = date[5..7].parse().ok()?;
+        let day: u32 = date[8..].parse().ok()?;
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days_in_month).contains(&day).then(|| date.to_string())
+    }
+}
+
+/// Format seconds since the Unix epoch as an RFC 3339 UTC timestamp
+pub(crate) fn format_utc_timestamp(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+
+    // Civil date from days (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
+/// Days since a fixed epoch, for differences between dates. `None` when
+/// the date is not a valid ISO date, which only a `String` date can be.
+pub(crate) fn day_number(date: &Date) -> Option<i64> {
+    #[cfg(feature = "chrono")]
+    {
+        use chrono::Datelike;
+        Some(i64::from(date.num_days_from_ce()))
+    }
+    #[cfg(not(feature = "chrono"))]
+    {
+        // Days from civil date (Howard Hinnant's algorithm)
+        let date = parse_date(date)?;
+        let field = |range: std::ops::Range<usize>| date[range].parse::<i64>().ok();
+        let (month, day) = (field(5..7)?, field(8..10)?);
+        let year = field(0..4)? - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Some(era * 146_097 + day_of_era)
+    }
+}
+
+/// Seconds since a fixed epoch for an ISO date or date-time, honouring a
+/// UTC offset when given. A bare date means midnight UTC.
+pub(crate) fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let date = parse_date(value)?;
+    let mut secs = day_number(&date)? * 86_400;
+
+    let time = &value[10..];
+    if time.is_empty() {
+        return Some(secs);
+    }
+    let (hours, minutes, seconds, offset) = time_of_day(time)?;
+    let field = |part: Option<&str>| part.map_or(0, |p| p.parse::<i64>().unwrap_or(0));
+    let (hours, minutes, seconds) = (field(Some(hours)), field(Some(minutes)), field(seconds));
+    if hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    secs += hours * 3600 + minutes * 60 + seconds;
+
+    if let Some(offset) = offset.filter(|o| *o != "Z") {
+        let digits: String = offset[1..].chars().filter(char::is_ascii_digit).collect();
+        let offset_secs =
+            digits[..2].parse::<i64>().ok()? * 3600 + digits[2..].parse::<i64>().ok()? * 60;
+        secs -= if offset.starts_with('-') {
+            -offset_secs
+        } else {
+            offset_secs
+        };
+    }
+    Some(secs)
+}
+
+impl WorkflowItem {
+    /// Classify this item's status the same way the extension's tree view does.
+    ///
+    /// Registered custom statuses count as complete only when terminal.
+    pub fn status_class(&self) -> StatusClass {
+        if let Some(custom) = &self.custom_status {
+            return if custom.is_terminal {
+                StatusClass::Complete
+            } else {
+                StatusClass::Pending
+            };
+        }
+        StatusClass::of(&self.status)
+    }
+
+    /// Whether this item applies to a project with the given track and field
+    /// type. Items without `tracks` apply everywhere; otherwise one of the
+    /// listed tracks must name either value (case-insensitively).
+    pub fn applies_to(&self, selected_track: &str, field_type: &str) -> bool {
+        self.tracks.is_empty()
+            || self.tracks.iter().any(|track| {
+                [selected_track, field_type]
+                    .iter()
+                    .any(|value| !value.is_empty() && track.eq_ignore_ascii_case(value))
+            })
+    }
+
+    /// The `due` date, if present and a valid ISO date.
+    pub fn due_date(&self) -> Option<Date> {
+        self.due.as_deref().and_then(parse_date)
+    }
+
+    /// State of this item on its own, ignoring dependencies and phase order.
+    ///
+    /// Never returns [`ItemState::Blocked`] or [`ItemState::NotApplicable`];
+    /// use [`WorkflowData::item_state`] for the full picture.
+    pub fn state(&self) -> ItemState {
+        match self.status_class() {
+            StatusClass::Complete => ItemState::Done,
+            StatusClass::Skipped => ItemState::Skipped,
+            StatusClass::Pending => match self.status.as_str() {
+                "optional" | "recommended" | "conditional" => ItemState::Optional,
+                _ => ItemState::Actionable,
+            },
+        }
+    }
+}
+
+/// Sort key placing prerequisites before every numbered phase
+fn phase_rank(phase: Phase) -> i64 {
+    match phase {
+        Phase::Prerequisite => i64::MIN,
+        Phase::Number(n) => i64::from(n),
+    }
+}
+
+impl WorkflowData {
+    /// Whether an item applies to this project's selected track.
+    ///
+    /// Every item applies when the project names neither a track nor a
+    /// field type.
+    pub fn is_applicable(&self, item: &WorkflowItem) -> bool {
+        (self.selected_track.is_empty() && self.field_type.is_empty())
+            || item.applies_to(&self.selected_track, &self.field_type)
+    }
+
+    /// Items that apply to this project's selected track, in item order.
+    pub fn items_for_track(&self) -> Vec<&WorkflowItem> {
+        self.items
+            .iter()
+            .filter(|item| self.is_applicable(item))
+            .collect()
+    }
+
+    /// State of an item, taking track, dependencies and phase order into
+    /// account.
+    ///
+    /// Items outside the selected track are not applicable and never block
+    /// anything. A required item is blocked while any of its `depends_on`
+    /// entries is neither done nor skipped (unknown IDs count as open), or
+    /// while a required item in an earlier phase is still outstanding.
+    pub fn item_state(&self, item: &WorkflowItem) -> ItemState {
+        if !self.is_applicable(item) {
+            return ItemState::NotApplicable;
+        }
+        let state = item.state();
+        if state != ItemState::Actionable {
+            return state;
+        }
+
+        let is_open = |other: &WorkflowItem| {
+            other.state() == ItemState::Actionable && self.is_applicable(other)
+        };
+        let waiting_on_dependency = item.depends_on.iter().any(|id| {
+            self.items
+                .iter()
+                .find(|other| &other.id == id)
+                .is_none_or(is_open)
+        });
+        let waiting_on_phase = self
+            .items
+            .iter()
+            .any(|other| phase_rank(other.phase) < phase_rank(item.phase) && is_open(other));
+
+        if waiting_on_dependency || waiting_on_phase {
+            ItemState::Blocked
+        } else {
+            ItemState::Actionable
+        }
+    }
+
+    /// State of every item, in item order.
+    pub fn item_states(&self) -> Vec<(&WorkflowItem, ItemState)> {
+        self.items
+            .iter()
+            .map(|item| (item, self.item_state(item)))
+            .collect()
+    }
+
+    /// Pending items that apply to the selected track and were due before
+    /// `today`, in item order.
+    pub fn overdue(&self, today: &Date) -> Vec<&WorkflowItem> {
+        self.items
+            .iter()
+            .filter(|item| {
+                item.status_class() == StatusClass::Pending
+                    && self.is_applicable(item)
+                    && item.due_date().is_some_and(|due| due < *today)
+            })
+            .collect()
+    }
 }
 
 /// Phase can be a number (0-3) or "prerequisite"
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
 #[serde(untagged)]
 pub enum Phase {
     Number(i32),
@@ -35,13 +380,39 @@ impl Default for Phase {
     }
 }
 
+impl Phase {
+    /// BMad name of the phase, if it is one of the standard phases
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Phase::Number(0) => Some("Discovery"),
+            Phase::Number(1) => Some("Planning"),
+            Phase::Number(2) => Some("Solutioning"),
+            Phase::Number(3) => Some("Implementation"),
+            Phase::Prerequisite => Some("Prerequisites"),
+            Phase::Number(_) => None,
+        }
+    }
+
+    /// Human-readable heading, e.g. "Phase 1: Planning"
+    pub fn title(&self) -> String {
+        match (self, self.name()) {
+            (Phase::Prerequisite, _) => "Prerequisites".to_string(),
+            (Phase::Number(n), Some(name)) => format!("Phase {}: {}", n, name),
+            (Phase::Number(n), None) => format!("Phase {}", n),
+        }
+    }
+}
+
 /// Workflow data parsed from bmm-workflow-status.yaml
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct WorkflowData {
     pub last_updated: String,
     pub status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
     pub status_note: Option<String>,
     pub project: String,
     pub project_type: String,
@@ -49,10 +420,71 @@ pub struct WorkflowData {
     pub field_type: String,
     pub workflow_path: String,
     pub items: Vec<WorkflowItem>,
+    /// Phase names and descriptions from the workflow definition
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<PhaseInfo>,
+}
+
+/// Header fields of a workflow status file with item counts, for listings
+/// that do not need the items themselves
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowMeta {
+    pub last_updated: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub status_note: Option<String>,
+    pub project: String,
+    pub project_type: String,
+    pub selected_track: String,
+    pub field_type: String,
+    pub workflow_path: String,
+    pub item_count: usize,
+    /// Items whose status classifies as [`StatusClass::Complete`]
+    pub complete_count: usize,
+}
+
+impl From<&WorkflowData> for WorkflowMeta {
+    fn from(data: &WorkflowData) -> Self {
+        Self {
+            last_updated: data.last_updated.clone(),
+            status: data.status.clone(),
+            status_note: data.status_note.clone(),
+            project: data.project.clone(),
+            project_type: data.project_type.clone(),
+            selected_track: data.selected_track.clone(),
+            field_type: data.field_type.clone(),
+            workflow_path: data.workflow_path.clone(),
+            item_count: data.items.len(),
+            complete_count: data
+                .items
+                .iter()
+                .filter(|item| item.status_class() == StatusClass::Complete)
+                .count(),
+        }
+    }
+}
+
+/// A phase as described by the external workflow definition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseInfo {
+    pub phase: Phase,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub description: Option<String>,
 }
 
 /// Story status in sprint tracking
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
 #[serde(rename_all = "kebab-case")]
 pub enum StoryStatus {
     Backlog,
@@ -83,17 +515,313 @@ impl std::fmt::Display for StoryStatus {
     }
 }
 
+impl std::str::FromStr for StoryStatus {
+    type Err = std::convert::Infallible;
+
+    /// Parse a canonical status string; anything else is `Unknown`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "backlog" => StoryStatus::Backlog,
+            "drafted" => StoryStatus::Drafted,
+            "ready-for-dev" => StoryStatus::ReadyForDev,
+            "in-progress" => StoryStatus::InProgress,
+            "review" => StoryStatus::Review,
+            "done" => StoryStatus::Done,
+            "optional" => StoryStatus::Optional,
+            "completed" => StoryStatus::Completed,
+            _ => StoryStatus::Unknown,
+        })
+    }
+}
+
+/// Built-in synonyms, keyed by normalized alias
+const DEFAULT_STATUS_ALIASES: &[(&str, &str)] = &[
+    ("complete", "done"),
+    ("completed", "done"),
+    ("finished", "done"),
+    ("closed", "done"),
+    ("todo", "backlog"),
+    ("to-do", "backlog"),
+    ("not-started", "backlog"),
+    ("draft", "drafted"),
+    ("ready", "ready-for-dev"),
+    ("ready-for-development", "ready-for-dev"),
+    ("wip", "in-progress"),
+    ("doing", "in-progress"),
+    ("started", "in-progress"),
+    ("in-review", "review"),
+    ("code-review", "review"),
+];
+
+/// The built-in alias table, built once for [`normalize_status`]
+static DEFAULT_ALIASES: Lazy<StatusAliases> = Lazy::new(StatusAliases::default);
+
+/// Alias table mapping team-specific status words to canonical values.
+///
+/// Lookups ignore case and treat `_`, spaces and `-` alike, so `To Do`,
+/// `to_do` and `TO-DO` all hit the same entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusAliases {
+    aliases: std::collections::HashMap<String, String>,
+}
+
+impl Default for StatusAliases {
+    fn default() -> Self {
+        DEFAULT_STATUS_ALIASES
+            .iter()
+            .fold(Self::empty(), |table, (alias, canonical)| {
+                table.with_alias(alias, canonical)
+            })
+    }
+}
+
+impl StatusAliases {
+    /// A table with no aliases; only spelling variants are normalized.
+    pub fn empty() -> Self {
+        Self {
+            aliases: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Add or replace an alias.
+    pub fn with_alias(mut self, alias: &str, canonical: &str) -> Self {
+        self.aliases.insert(alias_key(alias), canonical.to_string());
+        self
+    }
+
+    /// Map a status to its canonical value.
+    ///
+    /// Aliases win first, then spelling variants of the standard statuses
+    /// (`In Progress` becomes `in-progress`). Unrecognized statuses are
+    /// returned unchanged.
+    pub fn normalize(&self, status: &str) -> String {
+        let key = alias_key(status);
+        if let Some(canonical) = self.aliases.get(&key) {
+            return canonical.clone();
+        }
+        match key.parse() {
+            Ok(StoryStatus::Unknown) => status.to_string(),
+            _ => key,
+        }
+    }
+}
+
+/// Lowercase a status and unify the separators teams use between words
+fn alias_key(status: &str) -> String {
+    status
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '_' | ' ' => '-',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+/// Classify a status written in any common vocabulary, using the built-in
+/// alias table (`complete` and `completed` count as `done`, `todo` as
+/// `backlog`).
+pub fn normalize_status(status: &str) -> StoryStatus {
+    DEFAULT_ALIASES
+        .normalize(status)
+        .parse()
+        .unwrap_or(StoryStatus::Unknown)
+}
+
+/// Classify a workflow item status written in any common vocabulary, the
+/// workflow counterpart of [`normalize_status`].
+///
+/// Story words count too: `done` and its synonyms are complete, `todo`,
+/// `in progress` and the other unfinished story statuses are pending.
+/// Workflow statuses may be spelled loosely (`Not Started`); anything else,
+/// such as an output file path, is classified as [`StatusClass::of`] does.
+pub fn normalize_workflow_status(status: &str) -> StatusClass {
+    match normalize_status(status) {
+        StoryStatus::Done | StoryStatus::Completed => StatusClass::Complete,
+        StoryStatus::Unknown => {
+            let key = alias_key(status).replace('-', "_");
+            match StatusClass::of(&key) {
+                StatusClass::Complete => StatusClass::of(status.trim()),
+                class => class,
+            }
+        }
+        _ => StatusClass::Pending,
+    }
+}
+
+/// Metadata for a user-defined status
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct StatusInfo {
+    /// Status as written in the file
+    pub name: String,
+    pub display_name: String,
+    /// Color hint for frontends, e.g. `#22863a` or a theme color name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub color: Option<String>,
+    /// Whether the status ends the work, like `done` does
+    #[serde(default)]
+    pub is_terminal: bool,
+}
+
+impl StatusInfo {
+    pub fn new(name: &str, display_name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            display_name: display_name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_color(mut self, color: &str) -> Self {
+        self.color = Some(color.to_string());
+        self
+    }
+
+    pub fn terminal(mut self) -> Self {
+        self.is_terminal = true;
+        self
+    }
+}
+
+/// Statuses registered on top of the built-in vocabulary.
+///
+/// Parsers given a registry attach the matching [`StatusInfo`] to items
+/// and stories whose status they do not otherwise recognize. Lookups
+/// ignore case and separator differences, like [`StatusAliases`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatusRegistry {
+    statuses: std::collections::BTreeMap<String, StatusInfo>,
+}
+
+impl StatusRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a status, replacing any earlier entry with the same name.
+    pub fn register(&mut self, info: StatusInfo) {
+        self.statuses.insert(alias_key(&info.name), info);
+    }
+
+    /// Builder form of [`register`](Self::register).
+    pub fn with_status(mut self, info: StatusInfo) -> Self {
+        self.register(info);
+        self
+    }
+
+    pub fn lookup(&self, status: &str) -> Option<&StatusInfo> {
+        self.statuses.get(&alias_key(status))
+    }
+
+    /// Registered statuses, ordered by name.
+    pub fn statuses(&self) -> impl Iterator<Item = &StatusInfo> {
+        self.statuses.values()
+    }
+
+    /// Look up a story status that the built-in vocabulary does not know.
+    pub(crate) fn tag_story_status(&self, status: &str) -> Option<StatusInfo> {
+        match StatusAliases::empty().normalize(status).parse() {
+            Ok(StoryStatus::Unknown) => self.lookup(status).cloned(),
+            _ => None,
+        }
+    }
+}
+
 /// A story within an epic
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct Story {
     pub id: String,
-    pub status: String,
+    #[cfg_attr(feature = "ts-types", ts(as = "String"))]
+    pub status: Text,
     pub epic_id: String,
+    /// Story IDs that must be done before this story can proceed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
+    /// Explicit issue number used to build external tracker keys (e.g. Jira)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional, as = "Option<f64>"))]
+    pub number: Option<u64>,
+    /// Registry entry for a status outside the built-in vocabulary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub custom_status: Option<StatusInfo>,
+    /// Human-readable title, from epics.md
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub title: Option<String>,
+    /// Number of acceptance criteria listed in epics.md
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub acceptance_criteria: Option<usize>,
+    /// Due date as written, e.g. `2025-03-01`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub due: Option<String>,
+    /// Free-form labels such as `backend` or `security`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Person responsible for the story
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub assignee: Option<String>,
+    /// When the story was last changed, as written (e.g. RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub updated: Option<String>,
+}
+
+/// Upper bound for issue numbers derived by hashing a story ID
+pub(crate) const HASHED_KEY_SPACE: u64 = 100_000;
+
+impl Story {
+    /// Derive an external tracker key such as `DMO-12` for this story.
+    ///
+    /// Uses the explicit `number` field when present, otherwise a stable
+    /// FNV-1a hash of the story ID so the mapping never changes between runs.
+    /// Returns `None` when the project key is empty.
+    ///
+    /// The key ignores the rest of the sprint, so two stories can share one;
+    /// [`SprintData::jira_keys`] reports when they do.
+    pub fn external_key(&self, project_key: &str) -> Option<String> {
+        if project_key.is_empty() {
+            return None;
+        }
+        Some(format!("{}-{}", project_key, self.key_number()))
+    }
+
+    /// Issue number of [`Story::external_key`]
+    pub(crate) fn key_number(&self) -> u64 {
+        self.number
+            .unwrap_or_else(|| fnv1a(self.id.as_bytes()) % HASHED_KEY_SPACE)
+    }
+
+    /// The `due` date, if present and a valid ISO date.
+    pub fn due_date(&self) -> Option<Date> {
+        self.due.as_deref().and_then(parse_date)
+    }
+}
+
+/// 64-bit FNV-1a hash; stable across platforms and releases.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
 }
 
 /// An epic containing stories
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct Epic {
     pub id: String,
@@ -104,11 +832,108 @@ pub struct Epic {
 
 /// Sprint data parsed from sprint-status.yaml
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct SprintData {
     pub project: String,
     pub project_key: String,
     pub epics: Vec<Epic>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub sprint_goal: Option<String>,
+    /// First day of the sprint as written, e.g. `2025-03-03`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub sprint_start: Option<String>,
+    /// Last day of the sprint as written, e.g. `2025-03-14`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-types", ts(optional))]
+    pub sprint_end: Option<String>,
+}
+
+/// A problem encountered and skipped over by a lenient parse
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-types", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ParseIssue {
+    /// 1-based line number where the problem was reported
+    pub line: usize,
+    pub message: String,
+    /// The source lines that were dropped to recover
+    pub skipped: String,
+}
+
+/// JSON Schemas for the public data types, keyed by type name.
+///
+/// The schemas follow the serde representation (camelCase field names,
+/// omitted optional fields), so they match what the WASM bindings return.
+#[cfg(feature = "schemars")]
+pub fn json_schema() -> std::collections::BTreeMap<&'static str, serde_json::Value> {
+    use schemars::schema_for;
+
+    let mut schemas = std::collections::BTreeMap::new();
+    schemas.insert("WorkflowData", schema_for!(WorkflowData).to_value());
+    schemas.insert("WorkflowItem", schema_for!(WorkflowItem).to_value());
+    schemas.insert("Phase", schema_for!(Phase).to_value());
+    schemas.insert("PhaseInfo", schema_for!(PhaseInfo).to_value());
+    schemas.insert("StatusClass", schema_for!(StatusClass).to_value());
+    schemas.insert("ItemState", schema_for!(ItemState).to_value());
+    schemas.insert("SprintData", schema_for!(SprintData).to_value());
+    schemas.insert("Epic", schema_for!(Epic).to_value());
+    schemas.insert("Story", schema_for!(Story).to_value());
+    schemas.insert("StoryStatus", schema_for!(StoryStatus).to_value());
+    schemas.insert(
+        "HistoryEntry",
+        schema_for!(crate::history::HistoryEntry).to_value(),
+    );
+    schemas.insert("ParseIssue", schema_for!(ParseIssue).to_value());
+    schemas.insert("StatusInfo", schema_for!(StatusInfo).to_value());
+    schemas.insert(
+        "StoryDoc",
+        schema_for!(crate::story_doc::StoryDoc).to_value(),
+    );
+    schemas
+}
+
+/// TypeScript declarations for the public data types, keyed by type name.
+///
+/// Each value is the content of a `<name>.d.ts` module exporting the type,
+/// importing the types it refers to from sibling modules. The declarations
+/// follow the serde representation (camelCase field names, omitted optional
+/// fields), so they match what the WASM bindings return.
+#[cfg(feature = "ts-types")]
+pub fn typescript_definitions() -> std::collections::BTreeMap<&'static str, String> {
+    use crate::history::HistoryEntry;
+    use crate::report::{KanbanBoard, KanbanCard, KanbanColumn};
+    use crate::story_doc::StoryDoc;
+    use ts_rs::TS;
+
+    fn declaration<T: TS + 'static>() -> String {
+        T::export_to_string().expect("derived types always export")
+    }
+
+    let mut definitions = std::collections::BTreeMap::new();
+    definitions.insert("WorkflowData", declaration::<WorkflowData>());
+    definitions.insert("WorkflowItem", declaration::<WorkflowItem>());
+    definitions.insert("WorkflowMeta", declaration::<WorkflowMeta>());
+    definitions.insert("Phase", declaration::<Phase>());
+    definitions.insert("PhaseInfo", declaration::<PhaseInfo>());
+    definitions.insert("StatusClass", declaration::<StatusClass>());
+    definitions.insert("ItemState", declaration::<ItemState>());
+    definitions.insert("SprintData", declaration::<SprintData>());
+    definitions.insert("Epic", declaration::<Epic>());
+    definitions.insert("Story", declaration::<Story>());
+    definitions.insert("StoryStatus", declaration::<StoryStatus>());
+    definitions.insert("HistoryEntry", declaration::<HistoryEntry>());
+    definitions.insert("ParseIssue", declaration::<ParseIssue>());
+    definitions.insert("StatusInfo", declaration::<StatusInfo>());
+    definitions.insert("StoryDoc", declaration::<StoryDoc>());
+    definitions.insert("KanbanBoard", declaration::<KanbanBoard>());
+    definitions.insert("KanbanColumn", declaration::<KanbanColumn>());
+    definitions.insert("KanbanCard", declaration::<KanbanCard>());
+    definitions
 }
 
 #[cfg(test)]
@@ -142,6 +967,15 @@ mod tests {
         assert_eq!(default_phase, Phase::Number(1));
     }
 
+    #[test]
+    fn test_phase_title() {
+        assert_eq!(Phase::Number(0).title(), "Phase 0: Discovery");
+        assert_eq!(Phase::Number(3).title(), "Phase 3: Implementation");
+        assert_eq!(Phase::Number(7).title(), "Phase 7");
+        assert_eq!(Phase::Number(7).name(), None);
+        assert_eq!(Phase::Prerequisite.title(), "Prerequisites");
+    }
+
     #[test]
     fn test_phase_serialization_number() {
         let phase = Phase::Number(2);
@@ -166,7 +1000,7 @@ mod tests {
     #[test]
     fn test_phase_clone() {
         let original = Phase::Number(5);
-        let cloned = original.clone();
+        let cloned = original;
         assert_eq!(original, cloned);
     }
 
@@ -237,20 +1071,471 @@ mod tests {
         assert_eq!(original, cloned);
     }
 
+    #[test]
+    fn test_story_status_from_str() {
+        for status in [
+            StoryStatus::Backlog,
+            StoryStatus::Drafted,
+            StoryStatus::ReadyForDev,
+            StoryStatus::InProgress,
+            StoryStatus::Review,
+            StoryStatus::Done,
+            StoryStatus::Optional,
+            StoryStatus::Completed,
+        ] {
+            assert_eq!(status.to_string().parse::<StoryStatus>(), Ok(status));
+        }
+        assert_eq!("shipped".parse::<StoryStatus>(), Ok(StoryStatus::Unknown));
+    }
+
+    // =========================================================================
+    // Status Normalization Tests
+    // =========================================================================
+
+    #[test]
+    fn test_normalize_status_synonyms() {
+        for done in [
+            "done",
+            "complete",
+            "completed",
+            "Completed",
+            "DONE",
+            "finished",
+        ] {
+            assert_eq!(normalize_status(done), StoryStatus::Done, "{}", done);
+        }
+        for backlog in ["backlog", "todo", "To Do", "to_do", "not_started"] {
+            assert_eq!(
+                normalize_status(backlog),
+                StoryStatus::Backlog,
+                "{}",
+                backlog
+            );
+        }
+        assert_eq!(normalize_status("In Progress"), StoryStatus::InProgress);
+        assert_eq!(normalize_status("ready_for_dev"), StoryStatus::ReadyForDev);
+        assert_eq!(normalize_status("  review "), StoryStatus::Review);
+        assert_eq!(normalize_status("shipped"), StoryStatus::Unknown);
+        assert_eq!(normalize_status(""), StoryStatus::Unknown);
+    }
+
+    #[test]
+    fn test_normalize_workflow_status() {
+        for complete in ["complete", "Completed", "DONE", "docs/prd.md"] {
+            assert_eq!(
+                normalize_workflow_status(complete),
+                StatusClass::Complete,
+                "{}",
+                complete
+            );
+        }
+        for pending in [
+            "required",
+            "Optional",
+            "Not Started",
+            "not-started",
+            "todo",
+            "In Progress",
+            "",
+        ] {
+            assert_eq!(
+                normalize_workflow_status(pending),
+                StatusClass::Pending,
+                "{}",
+                pending
+            );
+        }
+        assert_eq!(normalize_workflow_status("Skipped"), StatusClass::Skipped);
+        assert_eq!(normalize_workflow_status(" skipped "), StatusClass::Skipped);
+    }
+
+    #[test]
+    fn test_status_aliases_normalize() {
+        let aliases = StatusAliases::default();
+        assert_eq!(aliases.normalize("Complete"), "done");
+        assert_eq!(aliases.normalize("IN_PROGRESS"), "in-progress");
+        // Unrecognized statuses keep their original spelling
+        assert_eq!(aliases.normalize("Shipped Early"), "Shipped Early");
+        assert_eq!(aliases.normalize("docs/prd.md"), "docs/prd.md");
+    }
+
+    #[test]
+    fn test_status_aliases_custom_table() {
+        let aliases = StatusAliases::empty()
+            .with_alias("Shipped", "done")
+            .with_alias("parked", "optional");
+        assert_eq!(aliases.normalize("shipped"), "done");
+        assert_eq!(aliases.normalize("PARKED"), "optional");
+        // The built-in synonyms are not part of an empty table
+        assert_eq!(aliases.normalize("completed"), "completed");
+        assert_eq!(aliases.normalize("todo"), "todo");
+
+        let overridden = StatusAliases::default().with_alias("complete", "review");
+        assert_eq!(overridden.normalize("complete"), "review");
+        assert_eq!(overridden.normalize("completed"), "done");
+    }
+
+    // =========================================================================
+    // Status Registry Tests
+    // =========================================================================
+
+    #[test]
+    fn test_status_registry_lookup() {
+        let registry = StatusRegistry::new()
+            .with_status(StatusInfo::new("shipped", "Shipped").terminal())
+            .with_status(StatusInfo::new("On Hold", "On hold").with_color("yellow"));
+
+        let shipped = registry.lookup("Shipped").expect("Should ignore case");
+        assert_eq!(shipped.display_name, "Shipped");
+        assert!(shipped.is_terminal);
+        let held = registry
+            .lookup("on_hold")
+            .expect("Should ignore separators");
+        assert_eq!(held.color.as_deref(), Some("yellow"));
+        assert!(!held.is_terminal);
+        assert!(registry.lookup("parked").is_none());
+
+        let names: Vec<&str> = registry.statuses().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["On Hold", "shipped"]);
+    }
+
+    #[test]
+    fn test_status_registry_replaces_entries() {
+        let mut registry = StatusRegistry::new();
+        registry.register(StatusInfo::new("shipped", "Shipped"));
+        registry.register(StatusInfo::new("SHIPPED", "Released").terminal());
+        assert_eq!(registry.statuses().count(), 1);
+        assert_eq!(registry.lookup("shipped").unwrap().display_name, "Released");
+    }
+
+    #[test]
+    fn test_status_registry_tags_only_unknown_story_statuses() {
+        let registry = StatusRegistry::new()
+            .with_status(StatusInfo::new("shipped", "Shipped"))
+            .with_status(StatusInfo::new("done", "Finished"));
+        assert!(registry.tag_story_status("shipped").is_some());
+        // Built-in statuses are never overridden
+        assert!(registry.tag_story_status("done").is_none());
+        assert!(registry.tag_story_status("In Progress").is_none());
+        assert!(registry.tag_story_status("parked").is_none());
+    }
+
+    #[test]
+    fn test_status_info_serialization() {
+        let info = StatusInfo::new("shipped", "Shipped").terminal();
+        let json = serde_json::to_string(&info).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"shipped","displayName":"Shipped","isTerminal":true}"#
+        );
+        let back: StatusInfo = serde_json::from_str(r#"{"name":"x","displayName":"X"}"#).unwrap();
+        assert!(!back.is_terminal);
+        assert!(back.color.is_none());
+    }
+
+    #[test]
+    fn test_custom_status_class() {
+        let mut item = WorkflowItem {
+            id: "prd".to_string(),
+            phase: Phase::Number(1),
+            status: "parked".into(),
+            agent: None,
+            command: None,
+            note: None,
+            output_file: None,
+            custom_status: Some(StatusInfo::new("parked", "Parked")),
+            depends_on: Vec::new(),
+            tracks: Vec::new(),
+            description: None,
+            due: None,
+            tags: Vec::new(),
+        };
+        assert_eq!(item.status_class(), StatusClass::Pending);
+        item.custom_status = Some(StatusInfo::new("parked", "Parked").terminal());
+        assert_eq!(item.status_class(), StatusClass::Complete);
+    }
+
+    // =========================================================================
+    // Item State Tests
+    // =========================================================================
+
+    fn state_item(id: &str, phase: Phase, status: &str) -> WorkflowItem {
+        WorkflowItem {
+            id: id.to_string(),
+            phase,
+            status: status.into(),
+            agent: None,
+            command: None,
+            note: None,
+            output_file: None,
+            custom_status: None,
+            depends_on: Vec::new(),
+            tracks: Vec::new(),
+            description: None,
+            due: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn state_data(items: Vec<WorkflowItem>) -> WorkflowData {
+        WorkflowData {
+            last_updated: String::new(),
+            status: String::new(),
+            status_note: None,
+            project: String::new(),
+            project_type: String::new(),
+            selected_track: String::new(),
+            field_type: String::new(),
+            workflow_path: String::new(),
+            items,
+            phases: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_item_state_on_its_own() {
+        let item = |status: &str| state_item("x", Phase::Number(1), status);
+        assert_eq!(item("docs/prd.md").state(), ItemState::Done);
+        assert_eq!(item("complete").state(), ItemState::Done);
+        assert_eq!(item("skipped").state(), ItemState::Skipped);
+        assert_eq!(item("optional").state(), ItemState::Optional);
+        assert_eq!(item("recommended").state(), ItemState::Optional);
+        assert_eq!(item("conditional").state(), ItemState::Optional);
+        assert_eq!(item("required").state(), ItemState::Actionable);
+        assert_eq!(item("not_started").state(), ItemState::Actionable);
+
+        let mut custom = item("in_review");
+        custom.custom_status = Some(StatusInfo::new("in_review", "In review"));
+        assert_eq!(custom.state(), ItemState::Actionable);
+    }
+
+    #[test]
+    fn test_item_state_blocked_by_earlier_phase() {
+        let data = state_data(vec![
+            state_item("brainstorm", Phase::Number(0), "optional"),
+            state_item("prd", Phase::Number(1), "required"),
+            state_item("architecture", Phase::Number(2), "required"),
+            state_item("ux", Phase::Number(2), "recommended"),
+        ]);
+        let states: Vec<(&str, ItemState)> = data
+            .item_states()
+            .into_iter()
+            .map(|(item, state)| (item.id.as_str(), state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("brainstorm", ItemState::Optional),
+                ("prd", ItemState::Actionable),
+                ("architecture", ItemState::Blocked),
+                ("ux", ItemState::Optional),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_item_state_prerequisites_come_first() {
+        let data = state_data(vec![
+            state_item("prd", Phase::Number(1), "required"),
+            state_item("setup", Phase::Prerequisite, "required"),
+        ]);
+        assert_eq!(data.item_state(&data.items[0]), ItemState::Blocked);
+        assert_eq!(data.item_state(&data.items[1]), ItemState::Actionable);
+    }
+
+    #[test]
+    fn test_item_state_dependencies() {
+        let mut tests = state_item("test-design", Phase::Number(1), "required");
+        tests.depends_on = vec!["prd".to_string()];
+        let mut data = state_data(vec![state_item("prd", Phase::Number(1), "required"), tests]);
+        assert_eq!(data.item_state(&data.items[1]), ItemState::Blocked);
+
+        data.items[0].status = "skipped".into();
+        assert_eq!(data.item_state(&data.items[1]), ItemState::Actionable);
+
+        data.items[1].depends_on = vec!["missing".to_string()];
+        assert_eq!(data.item_state(&data.items[1]), ItemState::Blocked);
+
+        // Finished items stay done regardless of dependencies
+        data.items[1].status = "docs/test-design.md".into();
+        assert_eq!(data.item_state(&data.items[1]), ItemState::Done);
+    }
+
+    #[test]
+    fn test_item_applies_to_track() {
+        let mut item = state_item("prd", Phase::Number(1), "required");
+        assert!(item.applies_to("quick-flow", "greenfield"));
+
+        item.tracks = vec!["Method".to_string(), "brownfield".to_string()];
+        assert!(item.applies_to("method", "greenfield"));
+        assert!(item.applies_to("quick-flow", "brownfield"));
+        assert!(!item.applies_to("quick-flow", "greenfield"));
+        assert!(!item.applies_to("", ""));
+    }
+
+    #[test]
+    fn test_items_for_track() {
+        let mut enterprise = state_item("security", Phase::Number(2), "required");
+        enterprise.tracks = vec!["enterprise".to_string()];
+        let mut data = state_data(vec![
+            state_item("prd", Phase::Number(1), "docs/prd.md"),
+            enterprise,
+            state_item("architecture", Phase::Number(3), "required"),
+        ]);
+
+        // Without a selected track everything applies
+        assert_eq!(data.items_for_track().len(), 3);
+
+        data.selected_track = "method".to_string();
+        let ids: Vec<&str> = data
+            .items_for_track()
+            .iter()
+            .map(|i| i.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["prd", "architecture"]);
+        assert_eq!(data.item_state(&data.items[1]), ItemState::NotApplicable);
+        // An item outside the track does not hold back later phases
+        assert_eq!(data.item_state(&data.items[2]), ItemState::Actionable);
+
+        data.selected_track = "enterprise".to_string();
+        assert_eq!(data.item_state(&data.items[1]), ItemState::Actionable);
+        assert_eq!(data.item_state(&data.items[2]), ItemState::Blocked);
+    }
+
+    // =========================================================================
+    // Due Date Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_date() {
+        let date = parse_date("2025-03-01");
+        assert!(date.is_some());
+        assert_eq!(parse_date("2025-03-01T09:30:00Z"), date);
+        assert_eq!(parse_date(" 2025-03-01 10:00 "), date);
+        assert!(parse_date("2024-02-29").is_some());
+        assert!(parse_date("2025-03-02") > date);
+        assert!(parse_date("2025-02-29").is_none());
+        assert!(parse_date("2025-13-01").is_none());
+        assert!(parse_date("2025-3-1").is_none());
+        assert!(parse_date("2025-03-01x").is_none());
+        assert!(parse_date("next week").is_none());
+        assert!(parse_date("").is_none());
+    }
+
+    #[test]
+    fn test_format_utc_timestamp() {
+        assert_eq!(format_utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc_timestamp(1_740_823_445), "2025-03-01T10:04:05Z");
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let midnight = parse_timestamp("2025-03-01").unwrap();
+        assert_eq!(parse_timestamp("2025-03-01T00:00:00Z"), Some(midnight));
+        assert_eq!(parse_timestamp("2025-03-01T09:30"), Some(midnight + 34_200));
+        assert_eq!(
+            parse_timestamp("2025-03-01 09:30:15.250Z"),
+            Some(midnight + 34_215)
+        );
+        assert_eq!(
+            parse_timestamp("2025-03-01T10:30:00+01:00"),
+            Some(midnight + 34_200)
+        );
+        assert_eq!(parse_timestamp("2025-02-28T20:00:00-0400"), Some(midnight));
+        assert_eq!(parse_timestamp("2025-03-02"), Some(midnight + 86_400));
+        assert!(parse_timestamp("2025-03-01T25:00").is_none());
+        assert!(parse_timestamp("2025-03-01Tnoon").is_none());
+        assert!(parse_timestamp("yesterday").is_none());
+    }
+
+    #[test]
+    fn test_workflow_overdue() {
+        let due = |id: &str, status: &str, date: &str| WorkflowItem {
+            due: Some(date.to_string()),
+            ..state_item(id, Phase::Number(1), status)
+        };
+        let mut enterprise = due("security", "required", "2025-01-01");
+        enterprise.tracks = vec!["enterprise".to_string()];
+        let mut data = state_data(vec![
+            due("prd", "required", "2025-03-01"),
+            due("ux-design", "optional", "2025-02-01"),
+            due("research", "docs/research.md", "2025-01-01"),
+            due("brainstorm", "skipped", "2025-01-01"),
+            due("architecture", "required", "2025-03-10"),
+            due("epics", "required", "soon"),
+            enterprise,
+            state_item("tech-spec", Phase::Number(2), "required"),
+        ]);
+        data.selected_track = "method".to_string();
+
+        let today = parse_date("2025-03-10").unwrap();
+        let ids: Vec<&str> = data.overdue(&today).iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["prd", "ux-design"]);
+        assert_eq!(data.items[0].due_date(), parse_date("2025-03-01"));
+        assert_eq!(data.items[5].due_date(), None);
+    }
+
+    #[test]
+    fn test_item_state_serialization() {
+        let json = serde_json::to_string(&ItemState::Actionable).unwrap();
+        assert_eq!(json, "\"actionable\"");
+        let json = serde_json::to_string(&ItemState::NotApplicable).unwrap();
+        assert_eq!(json, "\"not-applicable\"");
+    }
+
     // =========================================================================
     // WorkflowItem Tests
     // =========================================================================
 
+    #[test]
+    fn test_workflow_item_status_class() {
+        let mut item = WorkflowItem {
+            id: "prd".to_string(),
+            phase: Phase::Number(1),
+            status: "required".into(),
+            agent: None,
+            command: None,
+            note: None,
+            output_file: None,
+            custom_status: None,
+            depends_on: Vec::new(),
+            tracks: Vec::new(),
+            description: None,
+            due: None,
+            tags: Vec::new(),
+        };
+        assert_eq!(item.status_class(), StatusClass::Pending);
+
+        item.status = "conditional".into();
+        assert_eq!(item.status_class(), StatusClass::Pending);
+
+        item.status = "skipped".into();
+        assert_eq!(item.status_class(), StatusClass::Skipped);
+
+        item.status = "docs/prd.md".into();
+        assert_eq!(item.status_class(), StatusClass::Complete);
+
+        item.status = "complete".into();
+        assert_eq!(item.status_class(), StatusClass::Complete);
+    }
+
     #[test]
     fn test_workflow_item_full_serialization() {
         let item = WorkflowItem {
             id: "test-item".to_string(),
             phase: Phase::Number(2),
-            status: "complete".to_string(),
-            agent: Some("architect".to_string()),
+            status: "complete".into(),
+            agent: Some("architect".into()),
             command: Some("create-architecture".to_string()),
             note: Some("Architecture design notes".to_string()),
             output_file: Some("docs/architecture.md".to_string()),
+            custom_status: None,
+            depends_on: Vec::new(),
+            tracks: Vec::new(),
+            description: None,
+            due: None,
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&item).expect("Should serialize WorkflowItem");
@@ -267,11 +1552,17 @@ mod tests {
         let item = WorkflowItem {
             id: "minimal".to_string(),
             phase: Phase::Number(0),
-            status: "required".to_string(),
+            status: "required".into(),
             agent: None,
             command: None,
             note: None,
             output_file: None,
+            custom_status: None,
+            depends_on: Vec::new(),
+            tracks: Vec::new(),
+            description: None,
+            due: None,
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&item).expect("Should serialize");
@@ -287,7 +1578,7 @@ mod tests {
         let item: WorkflowItem = serde_json::from_str(json).expect("Should deserialize");
         assert_eq!(item.id, "test");
         assert_eq!(item.phase, Phase::Number(1));
-        assert_eq!(item.agent, Some("pm".to_string()));
+        assert_eq!(item.agent.as_deref(), Some("pm"));
     }
 
     #[test]
@@ -295,11 +1586,17 @@ mod tests {
         let item1 = WorkflowItem {
             id: "test".to_string(),
             phase: Phase::Number(1),
-            status: "done".to_string(),
+            status: "done".into(),
             agent: None,
             command: None,
             note: None,
             output_file: None,
+            custom_status: None,
+            depends_on: Vec::new(),
+            tracks: Vec::new(),
+            description: None,
+            due: None,
+            tags: Vec::new(),
         };
         let item2 = item1.clone();
         assert_eq!(item1, item2);
@@ -310,11 +1607,17 @@ mod tests {
         let item = WorkflowItem {
             id: "debug-test".to_string(),
             phase: Phase::Number(0),
-            status: "required".to_string(),
+            status: "required".into(),
             agent: None,
             command: None,
             note: None,
             output_file: None,
+            custom_status: None,
+            depends_on: Vec::new(),
+            tracks: Vec::new(),
+            description: None,
+            due: None,
+            tags: Vec::new(),
         };
         let debug_str = format!("{:?}", item);
         assert!(debug_str.contains("debug-test"));
@@ -329,7 +1632,7 @@ mod tests {
     fn test_workflow_data_serialization() {
         let data = WorkflowData {
             last_updated: "2025-01-01".to_string(),
-            status: "active".to_string(),
+            status: "active".into(),
             status_note: Some("On track".to_string()),
             project: "Test Project".to_string(),
             project_type: "greenfield".to_string(),
@@ -337,6 +1640,7 @@ mod tests {
             field_type: "default".to_string(),
             workflow_path: "docs/workflow.yaml".to_string(),
             items: vec![],
+            phases: Vec::new(),
         };
 
         let json = serde_json::to_string(&data).expect("Should serialize");
@@ -349,7 +1653,7 @@ mod tests {
     fn test_workflow_data_no_status_note() {
         let data = WorkflowData {
             last_updated: "2025-01-01".to_string(),
-            status: "active".to_string(),
+            status: "active".into(),
             status_note: None,
             project: "Test".to_string(),
             project_type: "".to_string(),
@@ -357,6 +1661,7 @@ mod tests {
             field_type: "".to_string(),
             workflow_path: "".to_string(),
             items: vec![],
+            phases: Vec::new(),
         };
 
         let json = serde_json::to_string(&data).expect("Should serialize");
@@ -367,7 +1672,7 @@ mod tests {
     fn test_workflow_data_equality() {
         let data1 = WorkflowData {
             last_updated: "2025-01-01".to_string(),
-            status: "active".to_string(),
+            status: "active".into(),
             status_note: None,
             project: "Test".to_string(),
             project_type: "".to_string(),
@@ -375,6 +1680,7 @@ mod tests {
             field_type: "".to_string(),
             workflow_path: "".to_string(),
             items: vec![],
+            phases: Vec::new(),
         };
         let data2 = data1.clone();
         assert_eq!(data1, data2);
@@ -388,8 +1694,9 @@ mod tests {
     fn test_story_serialization() {
         let story = Story {
             id: "1-create-feature".to_string(),
-            status: "in-progress".to_string(),
+            status: "in-progress".into(),
             epic_id: "epic-1".to_string(),
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&story).expect("Should serialize");
@@ -397,6 +1704,48 @@ mod tests {
         assert!(json.contains("\"epicId\":\"epic-1\""));
     }
 
+    #[test]
+    fn test_story_blocked_by_serialization() {
+        let mut story = Story {
+            id: "1-api".to_string(),
+            status: "backlog".into(),
+            epic_id: "epic-1".to_string(),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&story).expect("Should serialize");
+        assert!(!json.contains("blockedBy"));
+
+        story.blocked_by = vec!["2-create-db".to_string()];
+        let json = serde_json::to_string(&story).expect("Should serialize");
+        assert!(json.contains("\"blockedBy\":[\"2-create-db\"]"));
+    }
+
+    #[test]
+    fn test_story_external_key_explicit_number() {
+        let story = Story {
+            id: "1-login".to_string(),
+            number: Some(12),
+            ..Default::default()
+        };
+        assert_eq!(story.external_key("DMO"), Some("DMO-12".to_string()));
+        assert_eq!(story.external_key(""), None);
+    }
+
+    #[test]
+    fn test_story_external_key_hashed_is_stable() {
+        let story = Story {
+            id: "1-login".to_string(),
+            ..Default::default()
+        };
+        let key = story.external_key("DMO").expect("Should derive key");
+        assert!(key.starts_with("DMO-"));
+        assert_eq!(story.external_key("DMO"), Some(key.clone()));
+
+        // Known FNV-1a value guards against accidental algorithm changes
+        assert_eq!(fnv1a(b"1-login") % HASHED_KEY_SPACE, 32_880);
+        assert_eq!(key, "DMO-32880");
+    }
+
     #[test]
     fn test_story_deserialization() {
         let json = r#"{"id":"2-test","status":"done","epicId":"epic-2"}"#;
@@ -409,8 +1758,9 @@ mod tests {
     fn test_story_equality() {
         let story1 = Story {
             id: "test".to_string(),
-            status: "backlog".to_string(),
+            status: "backlog".into(),
             epic_id: "epic-1".to_string(),
+            ..Default::default()
         };
         let story2 = story1.clone();
         assert_eq!(story1, story2);
@@ -420,8 +1770,9 @@ mod tests {
     fn test_story_debug() {
         let story = Story {
             id: "debug-story".to_string(),
-            status: "review".to_string(),
+            status: "review".into(),
             epic_id: "epic-5".to_string(),
+            ..Default::default()
         };
         let debug_str = format!("{:?}", story);
         assert!(debug_str.contains("debug-story"));
@@ -437,11 +1788,12 @@ mod tests {
         let epic = Epic {
             id: "epic-1".to_string(),
             name: "Core Features".to_string(),
-            status: "in-progress".to_string(),
+            status: "in-progress".into(),
             stories: vec![Story {
                 id: "1-story-1".to_string(),
-                status: "done".to_string(),
+                status: "done".into(),
                 epic_id: "epic-1".to_string(),
+                ..Default::default()
             }],
         };
 
@@ -456,7 +1808,7 @@ mod tests {
         let epic = Epic {
             id: "epic-empty".to_string(),
             name: "Empty Epic".to_string(),
-            status: "backlog".to_string(),
+            status: "backlog".into(),
             stories: vec![],
         };
 
@@ -469,7 +1821,7 @@ mod tests {
         let epic1 = Epic {
             id: "epic-1".to_string(),
             name: "Test".to_string(),
-            status: "backlog".to_string(),
+            status: "backlog".into(),
             stories: vec![],
         };
         let epic2 = epic1.clone();
@@ -486,6 +1838,9 @@ mod tests {
             project: "Sprint Project".to_string(),
             project_key: "SPR".to_string(),
             epics: vec![],
+            sprint_goal: None,
+            sprint_start: None,
+            sprint_end: None,
         };
 
         let json = serde_json::to_string(&data).expect("Should serialize");
@@ -501,9 +1856,12 @@ mod tests {
             epics: vec![Epic {
                 id: "epic-1".to_string(),
                 name: "Epic 1".to_string(),
-                status: "done".to_string(),
+                status: "done".into(),
                 stories: vec![],
             }],
+            sprint_goal: None,
+            sprint_start: None,
+            sprint_end: None,
         };
 
         let json = serde_json::to_string(&data).expect("Should serialize");
@@ -517,6 +1875,9 @@ mod tests {
             project: "Test".to_string(),
             project_key: "TST".to_string(),
             epics: vec![],
+            sprint_goal: None,
+            sprint_start: None,
+            sprint_end: None,
         };
         let data2 = data1.clone();
         assert_eq!(data1, data2);
@@ -528,9 +1889,103 @@ mod tests {
             project: "Debug Test".to_string(),
             project_key: "DBG".to_string(),
             epics: vec![],
+            sprint_goal: None,
+            sprint_start: None,
+            sprint_end: None,
         };
         let debug_str = format!("{:?}", data);
         assert!(debug_str.contains("Debug Test"));
         assert!(debug_str.contains("SprintData"));
     }
+
+    // =========================================================================
+    // TypeScript Definition Tests
+    // =========================================================================
+
+    #[cfg(feature = "ts-types")]
+    #[test]
+    fn test_typescript_definitions_use_serde_names() {
+        let definitions = typescript_definitions();
+        let workflow = &definitions["WorkflowData"];
+        assert!(workflow.contains("export type WorkflowData = {"));
+        assert!(workflow.contains("lastUpdated: string"));
+        assert!(workflow.contains("statusNote?: string,"));
+        assert!(workflow.contains("phases?: Array<PhaseInfo>"));
+        assert!(!workflow.contains("last_updated"));
+
+        let item = &definitions["WorkflowItem"];
+        assert!(item.contains("status: string,"));
+        assert!(item.contains("agent?: string,"));
+        assert!(item.contains("outputFile?: string,"));
+        assert!(!item.contains("null"));
+
+        let story = &definitions["Story"];
+        assert!(story.contains("epicId: string"));
+        // serde writes u64 as a plain JSON number
+        assert!(story.contains("number?: number,"));
+        assert!(definitions["StoryStatus"].contains(r#""ready-for-dev""#));
+        // Phase::Prerequisite is an untagged unit variant, written as null
+        assert!(definitions["Phase"].contains("number | null"));
+    }
+
+    #[cfg(feature = "ts-types")]
+    #[test]
+    fn test_typescript_definitions_imports_resolve() {
+        let definitions = typescript_definitions();
+        for (name, declaration) in &definitions {
+            assert!(declaration.contains(&format!("export type {} =", name)));
+            for import in declaration
+                .lines()
+                .filter_map(|l| l.split("from \"./").nth(1))
+            {
+                let target = import.trim_end_matches(['"', ';']);
+                assert!(
+                    definitions.contains_key(target),
+                    "{} imports {}",
+                    name,
+                    target
+                );
+            }
+        }
+    }
+
+    // =========================================================================
+    // JSON Schema Tests
+    // =========================================================================
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema_covers_public_types() {
+        let schemas = json_schema();
+        for name in [
+            "WorkflowData",
+            "SprintData",
+            "Story",
+            "Epic",
+            "WorkflowItem",
+        ] {
+            assert!(schemas.contains_key(name), "Missing schema for {}", name);
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema_uses_serde_names() {
+        let schemas = json_schema();
+        let workflow = &schemas["WorkflowData"];
+        let properties = workflow["properties"]
+            .as_object()
+            .expect("Should have properties");
+        assert!(properties.contains_key("lastUpdated"));
+        assert!(properties.contains_key("selectedTrack"));
+        assert!(!properties.contains_key("last_updated"));
+
+        let required = workflow["required"]
+            .as_array()
+            .expect("Should list required fields");
+        assert!(!required.iter().any(|r| r == "statusNote"));
+
+        let story = &schemas["Story"];
+        assert!(story["properties"]["epicId"].is_object());
+    }
 }
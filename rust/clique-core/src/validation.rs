@@ -1,9 +1,92 @@
 // clique-core/src/validation.rs
-//! Path validation for workspace containment.
+//! Path validation for workspace containment, and structural validation of
+//! status files against the bundled schema.
+
+pub use crate::schema::{SchemaKind, SchemaViolation, ViolationKind, validate_against_schema};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Why [`validate_path`] refused a path
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    #[error("Path or workspace root is empty")]
+    EmptyInput,
+    #[error("Path is outside the workspace: {0}")]
+    OutsideWorkspace(String),
+    #[error("Path escapes the workspace through '..': {0}")]
+    TraversalDetected(String),
+    #[error("Path {path} and workspace root {root} use different platforms' conventions")]
+    MixedPlatform { path: String, root: String },
+    #[error("Unsupported path scheme: {0}")]
+    UnsupportedScheme(String),
+    #[error("Malformed file URI: {0}")]
+    InvalidUri(String),
+    #[error("Path {path} matches the protected pattern {pattern}")]
+    Denied { path: String, pattern: String },
+    #[error("Path is not among the writable files: {0}")]
+    NotAllowed(String),
+    #[error("Path {path} uses the name '{component}', which Windows reserves or rewrites")]
+    ReservedName { path: String, component: String },
+    #[error("Path {path:?} contains the control character {character:?}")]
+    IllegalCharacter { path: String, character: char },
+}
+
+/// A file path confirmed to be inside the workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedPath {
+    path: String,
+}
+
+impl ValidatedPath {
+    pub(crate) fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+        }
+    }
+
+    /// The path as it was given
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    pub fn into_string(self) -> String {
+        self.path
+    }
+}
+
+impl std::fmt::Display for ValidatedPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
+/// How letter case is compared when checking containment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CaseSensitivity {
+    /// Case matters, even for Windows paths
+    Sensitive,
+    /// Case never matters, as on default macOS volumes
+    Insensitive,
+    /// Case matters unless either path looks like a Windows path
+    #[default]
+    Auto,
+}
+
+impl CaseSensitivity {
+    /// Whether comparisons ignore case for paths of this platform
+    pub(crate) fn folds(self, is_windows: bool) -> bool {
+        match self {
+            CaseSensitivity::Sensitive => false,
+            CaseSensitivity::Insensitive => true,
+            CaseSensitivity::Auto => is_windows,
+        }
+    }
+}
 
 /// Detect if running on Windows based on path characteristics.
 /// WASM runs in a host environment, so we detect Windows by path format.
-fn is_windows_path(path: &str) -> bool {
+pub(crate) fn is_windows_path(path: &str) -> bool {
     // Windows paths typically start with a drive letter like "C:\" or "c:/"
     // or use backslashes
     if path.len() >= 2 {
@@ -12,42 +95,150 @@ fn is_windows_path(path: &str) -> bool {
             return true;
         }
     }
-    path.contains('\\')
+    // Extended-length and device prefixes may be written with forward slashes
+    path.contains('\\') || path.starts_with("//?/") || path.starts_with("//./")
+}
+
+/// Split a backslash-separated Windows path into the root that `..` cannot
+/// leave and the rest.
+///
+/// The root is a drive (`C:`) or a share (`\\server\share`), with
+/// extended-length prefixes (`\\?\C:`, `\\?\UNC\server\share`) reduced to
+/// the same forms so both spellings compare equal. Relative paths have an
+/// empty root.
+fn split_windows_root(path: &str) -> (String, &str) {
+    // A share is the first two components after the leading backslashes
+    fn share(path: &str) -> (String, &str) {
+        let end = path
+            .match_indices('\\')
+            .nth(1)
+            .map_or(path.len(), |(i, _)| i);
+        (format!(r"\\{}", &path[..end]), &path[end..])
+    }
+
+    let verbatim = path
+        .strip_prefix(r"\\?\")
+        .or_else(|| path.strip_prefix(r"\\.\"));
+    if let Some(inner) = verbatim {
+        if inner.len() >= 4 && inner[..4].eq_ignore_ascii_case(r"UNC\") {
+            return share(&inner[4..]);
+        }
+        if has_drive_letter(inner) {
+            return (inner[..2].to_string(), &inner[2..]);
+        }
+        // Volume GUIDs and devices: the prefixed first component is the root
+        let end = path.len() - inner.len() + inner.find('\\').unwrap_or(inner.len());
+        return (path[..end].to_string(), &path[end..]);
+    }
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return share(unc);
+    }
+    if has_drive_letter(path) {
+        return (path[..2].to_string(), &path[2..]);
+    }
+    (String::new(), path)
 }
 
 /// Normalize a path for comparison.
-/// On Windows-style paths, this lowercases and normalizes separators.
+/// On Windows-style paths, this lowercases and normalizes separators and
+/// reduces extended-length prefixes to plain drives and shares.
 fn normalize_path_str(path_str: &str, is_windows: bool) -> String {
-    if is_windows {
-        // On Windows, normalize to lowercase and use consistent separators
-        path_str.to_lowercase().replace('/', "\\")
+    comparable_path(path_str, is_windows, is_windows)
+}
+
+/// [`normalize_path_str`] with lowercasing chosen separately
+fn comparable_path(path_str: &str, is_windows: bool, fold_case: bool) -> String {
+    let normalized = if is_windows {
+        // On Windows, use consistent separators
+        let separated = path_str.replace('/', "\\");
+        let (root, rest) = split_windows_root(&separated);
+        format!("{}{}", root, rest)
     } else {
         path_str.to_string()
+    };
+    if fold_case {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    }
+}
+
+/// Whether a path starts with a drive letter, e.g. `C:`
+fn has_drive_letter(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic()
+}
+
+/// Device names Windows resolves in every directory, with any extension
+const RESERVED_DEVICE_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// The first component of a Windows path that names a device (`NUL`,
+/// `com1.txt`) or ends in a dot or space, which Windows silently strips so
+/// that `docs.` and `docs` are one directory. Extended-length (`\\?\`) paths
+/// are taken literally by Windows and are not checked.
+fn reserved_component(path: &str) -> Option<String> {
+    let separated = path.replace('/', "\\");
+    if separated.starts_with(r"\\?\") {
+        return None;
     }
+    let (_, rest) = split_windows_root(&separated);
+    rest.split('\\')
+        .filter(|part| !matches!(*part, "" | "." | ".."))
+        .find(|part| {
+            let stem = part
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches(' ');
+            part.ends_with(['.', ' '])
+                || RESERVED_DEVICE_NAMES
+                    .iter()
+                    .any(|name| stem.eq_ignore_ascii_case(name))
+        })
+        .map(str::to_string)
+}
+
+/// The first control character in a path, NUL included
+fn control_character(path: &str) -> Option<char> {
+    path.chars().find(|c| c.is_control())
+}
+
+/// The URI scheme a path starts with, such as `file` or `vscode-remote`.
+/// Single letters are drive letters, not schemes.
+fn uri_scheme(path: &str) -> Option<&str> {
+    let (scheme, _) = path.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = scheme.len() > 1
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some(scheme)
 }
 
 /// Resolve . and .. components in a path string
-fn resolve_path_components(path_str: &str, is_windows: bool) -> String {
+pub(crate) fn resolve_path_components(path_str: &str, is_windows: bool) -> String {
     let sep = if is_windows { '\\' } else { '/' };
     let normalized = if is_windows {
         path_str.replace('/', "\\")
     } else {
         path_str.to_string()
     };
+    // Windows drives and shares are kept aside so ".." cannot pop them
+    let (root, rest) = if is_windows {
+        split_windows_root(&normalized)
+    } else {
+        (String::new(), normalized.as_str())
+    };
 
-    let parts: Vec<&str> = normalized.split(sep).collect();
+    let parts: Vec<&str> = rest.split(sep).collect();
     let mut resolved: Vec<&str> = Vec::new();
 
     for part in parts {
         match part {
             ".." => {
-                // Only pop if we have something to pop and it's not a drive letter
-                if let Some(last) = resolved.last() {
-                    // Don't pop drive letters like "C:"
-                    if !(last.len() == 2 && last.ends_with(':')) {
-                        resolved.pop();
-                    }
-                }
+                resolved.pop();
             }
             "." | "" => {
                 // Skip current dir markers and empty parts (except first for absolute paths)
@@ -64,15 +255,40 @@ fn resolve_path_components(path_str: &str, is_windows: bool) -> String {
         }
     }
 
-    resolved.join(&sep.to_string())
+    let resolved = resolved.join(&sep.to_string());
+    match (root.is_empty(), resolved.is_empty()) {
+        (true, _) => resolved,
+        (false, true) => root,
+        (false, false) => format!("{}{}{}", root, sep, resolved),
+    }
 }
 
 /// Validate that a file path is inside the workspace root.
 /// This is a pure function that works on path strings without file system access.
+/// Paths containing NUL or other control characters are never inside: file
+/// systems truncate or reject them, so the string compared is not the file
+/// opened.
 pub fn is_inside_workspace(file_path: &str, workspace_root: &str) -> bool {
+    workspace_relative_path(file_path, workspace_root, CaseSensitivity::Auto).is_some()
+}
+
+/// The path of a file below the workspace root, with `/` separators and `.`
+/// and `..` resolved; empty for the root itself. Paths compared ignoring
+/// case come back lowercased. None if the file is outside the root.
+pub(crate) fn workspace_relative_path(
+    file_path: &str,
+    workspace_root: &str,
+    case: CaseSensitivity,
+) -> Option<String> {
     // Handle empty inputs
     if file_path.is_empty() || workspace_root.is_empty() {
-        return false;
+        return None;
+    }
+    if control_character(file_path)
+        .or(control_character(workspace_root))
+        .is_some()
+    {
+        return None;
     }
 
     // Detect Windows based on path format
@@ -83,22 +299,26 @@ pub fn is_inside_workspace(file_path: &str, workspace_root: &str) -> bool {
     let resolved_root = resolve_path_components(workspace_root, is_windows);
 
     // Normalize for comparison
-    let normalized_file = normalize_path_str(&resolved_file, is_windows);
-    let normalized_root = normalize_path_str(&resolved_root, is_windows);
+    let fold_case = case.folds(is_windows);
+    let normalized_file = comparable_path(&resolved_file, is_windows, fold_case);
+    let normalized_root = comparable_path(&resolved_root, is_windows, fold_case);
 
     // Check if file path equals workspace root
     if normalized_file == normalized_root {
-        return true;
+        return Some(String::new());
     }
 
     // Check if file is under root (with path separator)
     let sep = if is_windows { "\\" } else { "/" };
     let root_prefix = format!("{}{}", normalized_root, sep);
 
-    normalized_file.starts_with(&root_prefix)
+    let relative = normalized_file.strip_prefix(&root_prefix)?;
+    Some(relative.replace(sep, "/"))
 }
 
 /// Get validated file path, returns None if path is outside workspace.
+///
+/// See [`validate_path`] for the reason a path was refused.
 pub fn get_validated_path(file_path: &str, workspace_root: &str) -> Option<String> {
     if is_inside_workspace(file_path, workspace_root) {
         Some(file_path.to_string())
@@ -107,10 +327,357 @@ pub fn get_validated_path(file_path: &str, workspace_root: &str) -> Option<Strin
     }
 }
 
+/// Validate that a file path is inside the workspace root, explaining any
+/// refusal.
+///
+/// Accepts exactly the paths [`is_inside_workspace`] does, except that URIs
+/// (`file:///...`, `vscode-remote://...`) are always refused; see
+/// [`validate_uri`] for `file:` URIs. Windows file paths are also refused
+/// when a component is a device name (`CON`, `nul.txt`) or ends in a dot or
+/// space, since Windows would open a different file than the one compared.
+pub fn validate_path(file_path: &str, workspace_root: &str) -> Result<ValidatedPath, PathError> {
+    validate_path_with(file_path, workspace_root, CaseSensitivity::Auto)
+}
+
+/// [`validate_path`] comparing case as `case` says
+pub(crate) fn validate_path_with(
+    file_path: &str,
+    workspace_root: &str,
+    case: CaseSensitivity,
+) -> Result<ValidatedPath, PathError> {
+    if file_path.is_empty() || workspace_root.is_empty() {
+        return Err(PathError::EmptyInput);
+    }
+    for path in [file_path, workspace_root] {
+        if let Some(character) = control_character(path) {
+            return Err(PathError::IllegalCharacter {
+                path: path.to_string(),
+                character,
+            });
+        }
+    }
+    if let Some(scheme) = uri_scheme(file_path).or_else(|| uri_scheme(workspace_root)) {
+        return Err(PathError::UnsupportedScheme(scheme.to_string()));
+    }
+    if (is_windows_path(file_path) || is_windows_path(workspace_root))
+        && let Some(component) = reserved_component(file_path)
+    {
+        return Err(PathError::ReservedName {
+            path: file_path.to_string(),
+            component,
+        });
+    }
+    if workspace_relative_path(file_path, workspace_root, case).is_some() {
+        return Ok(ValidatedPath {
+            path: file_path.to_string(),
+        });
+    }
+
+    let is_mixed = (has_drive_letter(file_path) && workspace_root.starts_with('/'))
+        || (has_drive_letter(workspace_root) && file_path.starts_with('/'));
+    if is_mixed {
+        return Err(PathError::MixedPlatform {
+            path: file_path.to_string(),
+            root: workspace_root.to_string(),
+        });
+    }
+    if file_path.split(['/', '\\']).any(|part| part == "..") {
+        return Err(PathError::TraversalDetected(file_path.to_string()));
+    }
+    Err(PathError::OutsideWorkspace(file_path.to_string()))
+}
+
+/// Validate a `file:` URI against a workspace root, which may itself be a
+/// path or a `file:` URI.
+///
+/// Accepts what VS Code supplies: percent-encoded paths
+/// (`file:///c%3A/ws/a%20b.md`), drive letters after the leading slash, and
+/// hosts, which become UNC shares. Plain paths are validated as they are.
+/// The [`ValidatedPath`] holds the decoded path.
+pub fn validate_uri(uri: &str, workspace_root: &str) -> Result<ValidatedPath, PathError> {
+    let path = file_uri_to_path(uri)?;
+    let root = file_uri_to_path(workspace_root)?;
+    validate_path(&path, &root)
+}
+
+/// The file system path of a `file:` URI; anything without a scheme is
+/// returned as is.
+fn file_uri_to_path(uri: &str) -> Result<String, PathError> {
+    let Some(scheme) = uri_scheme(uri) else {
+        return Ok(uri.to_string());
+    };
+    if !scheme.eq_ignore_ascii_case("file") {
+        return Err(PathError::UnsupportedScheme(scheme.to_string()));
+    }
+    let invalid = || PathError::InvalidUri(uri.to_string());
+    let rest = uri[scheme.len() + 1..]
+        .strip_prefix("//")
+        .ok_or_else(invalid)?;
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+    let path = percent_decode(path).ok_or_else(invalid)?;
+    if host.is_empty() || host.eq_ignore_ascii_case("localhost") {
+        // "/C:/ws" is a drive path written with the URI's leading slash
+        match path.strip_prefix('/') {
+            Some(drive) if has_drive_letter(drive) => Ok(drive.to_string()),
+            _ => Ok(path),
+        }
+    } else {
+        let host = percent_decode(host).ok_or_else(invalid)?;
+        Ok(format!("//{}{}", host, path))
+    }
+}
+
+/// Decode `%XX` escapes, failing on truncated escapes and invalid UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// A workspace root resolved and normalized the way [`is_inside_workspace`]
+/// compares it
+fn normalized_root(root: &str) -> String {
+    let is_windows = is_windows_path(root);
+    normalize_path_str(&resolve_path_components(root, is_windows), is_windows)
+}
+
+/// Index of the most specific root containing the file; the first on ties
+fn most_specific_root<'a>(file_path: &str, roots: impl Iterator<Item = &'a str>) -> Option<usize> {
+    roots
+        .enumerate()
+        .filter(|(_, root)| is_inside_workspace(file_path, root))
+        .min_by_key(|(_, root)| std::cmp::Reverse(normalized_root(root).len()))
+        .map(|(index, _)| index)
+}
+
+/// Index of the workspace root containing a file, for multi-root
+/// workspaces.
+///
+/// When roots are nested, the innermost one containing the file wins.
+pub fn is_inside_any_workspace(file_path: &str, roots: &[&str]) -> Option<usize> {
+    most_specific_root(file_path, roots.iter().copied())
+}
+
+/// The roots of a multi-root workspace.
+///
+/// Roots that normalize to the same directory (`C:\ws` and `c:/WS/`) are
+/// kept once, so indexes stay stable however a host spells them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceSet {
+    roots: Vec<String>,
+}
+
+impl WorkspaceSet {
+    /// A set of the given roots, dropping empty and duplicate ones.
+    pub fn new<I, S>(roots: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut seen = std::collections::HashSet::new();
+        let roots = roots
+            .into_iter()
+            .map(Into::into)
+            .filter(|root| !root.is_empty() && seen.insert(normalized_root(root)))
+            .collect();
+        Self { roots }
+    }
+
+    pub fn roots(&self) -> &[String] {
+        &self.roots
+    }
+
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Index into [`WorkspaceSet::roots`] of the root containing a file;
+    /// see [`is_inside_any_workspace`].
+    pub fn find(&self, file_path: &str) -> Option<usize> {
+        most_specific_root(file_path, self.roots.iter().map(String::as_str))
+    }
+
+    /// The root containing a file
+    pub fn root_of(&self, file_path: &str) -> Option<&str> {
+        self.find(file_path).map(|index| self.roots[index].as_str())
+    }
+
+    pub fn contains(&self, file_path: &str) -> bool {
+        self.find(file_path).is_some()
+    }
+
+    /// [`validate_path`] against the root containing the file. Refusals are
+    /// explained relative to the first root; an empty set refuses everything
+    /// as [`PathError::EmptyInput`].
+    pub fn validate(&self, file_path: &str) -> Result<ValidatedPath, PathError> {
+        let Some(first) = self.roots.first() else {
+            return Err(PathError::EmptyInput);
+        };
+        let root = self.root_of(file_path).unwrap_or(first);
+        validate_path(file_path, root)
+    }
+}
+
+/// Whether a file is inside the workspace root once symlinks are resolved.
+///
+/// [`is_inside_workspace`] only compares strings, so a symlinked directory
+/// inside the workspace that points elsewhere passes it. This resolves both
+/// paths with [`std::fs::canonicalize`] first. The root must exist; a file
+/// that does not exist yet is resolved through its nearest existing
+/// ancestor, and may not use `..` below it.
+#[cfg(feature = "fs")]
+pub fn is_inside_workspace_canonical(file_path: &str, workspace_root: &str) -> bool {
+    use std::path::{Component, Path};
+
+    if file_path.is_empty() || workspace_root.is_empty() {
+        return false;
+    }
+    let Ok(root) = std::fs::canonicalize(workspace_root) else {
+        return false;
+    };
+
+    let mut existing = Path::new(file_path);
+    let mut missing = Vec::new();
+    let base = loop {
+        match std::fs::canonicalize(existing) {
+            Ok(resolved) => break resolved,
+            Err(_) => {
+                let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+                    return false;
+                };
+                missing.push(name);
+                existing = parent;
+            }
+        }
+    };
+    if Path::new(file_path)
+        .strip_prefix(existing)
+        .is_ok_and(|rest| rest.components().any(|c| c == Component::ParentDir))
+    {
+        return false;
+    }
+    let resolved = missing
+        .iter()
+        .rev()
+        .fold(base, |path, name| path.join(name));
+    resolved.starts_with(&root)
+}
+
+/// Resolve a path from a status file against the workspace root.
+/// Expands the `{project-root}` placeholder BMad writes and joins relative
+/// paths onto the root. Returns None if the result is outside the workspace.
+#[cfg(feature = "fs")]
+pub(crate) fn resolve_workspace_path(path: &str, workspace_root: &str) -> Option<String> {
+    let expanded = path.replace("{project-root}", workspace_root);
+    let joined = std::path::Path::new(workspace_root).join(expanded);
+    get_validated_path(&joined.to_string_lossy(), workspace_root)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // =========================================================================
+    // resolve_workspace_path Tests
+    // =========================================================================
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_resolve_workspace_path() {
+        assert_eq!(
+            resolve_workspace_path("docs/prd.md", "/ws").as_deref(),
+            Some("/ws/docs/prd.md")
+        );
+        assert_eq!(
+            resolve_workspace_path("{project-root}/docs/prd.md", "/ws").as_deref(),
+            Some("/ws/docs/prd.md")
+        );
+        assert_eq!(
+            resolve_workspace_path("/ws/docs/prd.md", "/ws").as_deref(),
+            Some("/ws/docs/prd.md")
+        );
+        assert_eq!(resolve_workspace_path("../secret.md", "/ws"), None);
+        assert_eq!(resolve_workspace_path("/etc/passwd", "/ws"), None);
+    }
+
+    // =========================================================================
+    // is_inside_workspace_canonical Tests
+    // =========================================================================
+
+    #[cfg(feature = "fs")]
+    fn canonical_fixture(name: &str) -> (crate::testing::TempDir, String) {
+        let dir = crate::testing::TempDir::new(name);
+        std::fs::create_dir_all(dir.join("ws/docs")).unwrap();
+        std::fs::create_dir_all(dir.join("outside")).unwrap();
+        std::fs::write(dir.join("ws/docs/prd.md"), "# PRD").unwrap();
+        std::fs::write(dir.join("outside/secret.md"), "secret").unwrap();
+        let root = dir.join("ws").to_string_lossy().to_string();
+        (dir, root)
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_canonical_accepts_workspace_files() {
+        let (dir, root) = canonical_fixture("canonical-ok");
+        let inside = |rel: &str| {
+            let path = dir.join("ws").join(rel);
+            is_inside_workspace_canonical(&path.to_string_lossy(), &root)
+        };
+        assert!(inside("docs/prd.md"));
+        assert!(inside("docs/../docs/prd.md"));
+        // Files that do not exist yet resolve through their parent
+        assert!(inside("docs/new/story.md"));
+        assert!(!inside("docs/new/../../../outside/secret.md"));
+        assert!(!inside("../outside/secret.md"));
+        assert!(!is_inside_workspace_canonical(
+            &dir.join("outside/secret.md").to_string_lossy(),
+            &root
+        ));
+        assert!(!is_inside_workspace_canonical("", &root));
+        assert!(!is_inside_workspace_canonical(
+            &root,
+            &dir.join("gone").to_string_lossy()
+        ));
+    }
+
+    #[cfg(all(feature = "fs", unix))]
+    #[test]
+    fn test_canonical_rejects_symlink_escapes() {
+        let (dir, root) = canonical_fixture("canonical-link");
+        std::os::unix::fs::symlink(dir.join("outside"), dir.join("ws/link")).unwrap();
+        let linked = dir.join("ws/link/secret.md").to_string_lossy().to_string();
+        // The string check is fooled; the canonical one is not
+        assert!(is_inside_workspace(&linked, &root));
+        assert!(!is_inside_workspace_canonical(&linked, &root));
+        assert!(!is_inside_workspace_canonical(
+            &dir.join("ws/link/new.md").to_string_lossy(),
+            &root
+        ));
+
+        // A workspace reached through a symlink still contains its files
+        std::os::unix::fs::symlink(dir.join("ws"), dir.join("alias")).unwrap();
+        assert!(is_inside_workspace_canonical(
+            &dir.join("ws/docs/prd.md").to_string_lossy(),
+            &dir.join("alias").to_string_lossy()
+        ));
+    }
+
     // =========================================================================
     // is_windows_path Tests
     // =========================================================================
@@ -154,6 +721,15 @@ mod tests {
         assert!(is_windows_path(r"\\server\share\file"));
     }
 
+    #[test]
+    fn test_is_windows_path_extended_length() {
+        assert!(is_windows_path(r"\\?\C:\very\long\path"));
+        assert!(is_windows_path("//?/C:/very/long/path"));
+        assert!(is_windows_path("//./UNC/server/share"));
+        // A doubled slash alone is still a Unix path
+        assert!(!is_windows_path("//server/share"));
+    }
+
     // =========================================================================
     // normalize_path_str Tests
     // =========================================================================
@@ -176,6 +752,45 @@ mod tests {
         assert_eq!(normalized, r"c:\path\to\file");
     }
 
+    #[test]
+    fn test_normalize_path_str_prefixes() {
+        assert_eq!(normalize_path_str(r"\\?\C:\Path", true), r"c:\path");
+        assert_eq!(
+            normalize_path_str("//?/UNC/Server/Share/A", true),
+            r"\\server\share\a"
+        );
+        assert_eq!(
+            normalize_path_str(r"\\Server\Share", true),
+            r"\\server\share"
+        );
+    }
+
+    #[test]
+    fn test_split_windows_root() {
+        assert_eq!(split_windows_root(r"C:\a\b"), ("C:".to_string(), r"\a\b"));
+        assert_eq!(
+            split_windows_root(r"\\srv\share\a"),
+            (r"\\srv\share".to_string(), r"\a")
+        );
+        assert_eq!(
+            split_windows_root(r"\\srv\share"),
+            (r"\\srv\share".to_string(), "")
+        );
+        assert_eq!(split_windows_root(r"\\?\D:\a"), ("D:".to_string(), r"\a"));
+        assert_eq!(
+            split_windows_root(r"\\?\unc\srv\share\a"),
+            (r"\\srv\share".to_string(), r"\a")
+        );
+        assert_eq!(
+            split_windows_root(r"\\?\Volume{1234}\a"),
+            (r"\\?\Volume{1234}".to_string(), r"\a")
+        );
+        assert_eq!(
+            split_windows_root(r"relative\a"),
+            (String::new(), r"relative\a")
+        );
+    }
+
     #[test]
     fn test_normalize_path_str_unix() {
         let normalized = normalize_path_str("/Path/To/File", false);
@@ -262,6 +877,14 @@ mod tests {
         assert!(resolved.is_empty() || resolved == "/");
     }
 
+    #[test]
+    fn test_resolve_path_components_preserves_share() {
+        let resolved = resolve_path_components(r"\\server\share\a\..\..\..\b", true);
+        assert_eq!(resolved, r"\\server\share\b");
+        let resolved = resolve_path_components(r"\\?\C:\ws\..\..\b", true);
+        assert_eq!(resolved, r"C:\b");
+    }
+
     // =========================================================================
     // is_inside_workspace Tests - Windows
     // =========================================================================
@@ -313,6 +936,59 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_unc_paths_windows() {
+        assert!(is_inside_workspace(
+            r"\\server\share\ws\file.md",
+            r"\\server\share\ws"
+        ));
+        assert!(is_inside_workspace(
+            "//server/share/ws/file.md",
+            r"\\Server\Share\ws"
+        ));
+        assert!(is_inside_workspace(
+            r"\\server\share\ws\a.md",
+            r"\\server\share"
+        ));
+        assert!(!is_inside_workspace(
+            r"\\server\share\other\a.md",
+            r"\\server\share\ws"
+        ));
+        assert!(!is_inside_workspace(
+            r"\\server\other\ws\a.md",
+            r"\\server\share\ws"
+        ));
+        assert!(!is_inside_workspace(
+            r"\\other\share\ws\a.md",
+            r"\\server\share\ws"
+        ));
+        // ".." cannot climb from one share into another
+        assert!(!is_inside_workspace(
+            r"\\server\share\..\other\a.md",
+            r"\\server\other"
+        ));
+        // A share is not a drive folder of the same name
+        assert!(!is_inside_workspace(
+            r"\\server\share\a.md",
+            r"C:\server\share"
+        ));
+    }
+
+    #[test]
+    fn test_extended_length_paths_windows() {
+        assert!(is_inside_workspace(r"\\?\C:\ws\docs\file.md", r"C:\ws"));
+        assert!(is_inside_workspace(r"C:\ws\docs\file.md", r"\\?\c:\WS"));
+        assert!(is_inside_workspace(
+            r"\\?\UNC\server\share\ws\a.md",
+            r"\\server\share\ws"
+        ));
+        assert!(!is_inside_workspace(r"\\?\D:\ws\file.md", r"C:\ws"));
+        assert!(!is_inside_workspace(
+            r"\\?\C:\ws\..\other\file.md",
+            r"C:\ws"
+        ));
+    }
+
     // =========================================================================
     // is_inside_workspace Tests - Unix
     // =========================================================================
@@ -422,6 +1098,56 @@ mod tests {
         assert!(is_inside_workspace(r"C:\workspace\file.md", r"C:\workspace\"));
     }
 
+    #[test]
+    fn test_workspace_relative_path() {
+        let relative = |file, root| workspace_relative_path(file, root, CaseSensitivity::Auto);
+        assert_eq!(
+            relative("/ws/docs/./a.md", "/ws").as_deref(),
+            Some("docs/a.md")
+        );
+        assert_eq!(relative("/ws", "/ws/").as_deref(), Some(""));
+        assert_eq!(
+            relative(r"C:\WS\Docs\A.md", "c:/ws").as_deref(),
+            Some("docs/a.md")
+        );
+        assert_eq!(relative("/a.md", "/").as_deref(), Some("a.md"));
+        assert_eq!(relative("/ws/../a.md", "/ws"), None);
+        assert_eq!(relative("", "/ws"), None);
+    }
+
+    #[test]
+    fn test_case_sensitivity() {
+        let relative = |file, root, case| workspace_relative_path(file, root, case);
+        use CaseSensitivity::*;
+        assert_eq!(
+            relative(r"C:\WS\A.md", r"c:\ws", Auto).as_deref(),
+            Some("a.md")
+        );
+        assert_eq!(relative(r"C:\WS\A.md", r"c:\ws", Sensitive), None);
+        assert_eq!(
+            relative(r"C:\ws\A.md", r"C:\ws", Sensitive).as_deref(),
+            Some("A.md")
+        );
+        assert_eq!(relative("/Users/Me/A.md", "/users/me", Auto), None);
+        assert_eq!(
+            relative("/Users/Me/A.md", "/users/me", Insensitive).as_deref(),
+            Some("a.md")
+        );
+        // Windows paths written with forward slashes and no drive letter
+        assert_eq!(
+            relative("//Server/Share/a.md", "//server/share", Auto),
+            None
+        );
+        assert!(relative("//Server/Share/a.md", "//server/share", Insensitive).is_some());
+        assert_eq!(CaseSensitivity::default(), Auto);
+        assert_eq!(
+            serde_json::to_string(&Insensitive).unwrap(),
+            r#""insensitive""#
+        );
+        assert!(validate_path_with("/Ws/a.md", "/ws", Insensitive).is_ok());
+        assert!(validate_path_with(r"C:\Ws\a.md", r"C:\ws", Sensitive).is_err());
+    }
+
     // =========================================================================
     // get_validated_path Tests
     // =========================================================================
@@ -465,16 +1191,298 @@ mod tests {
         assert_eq!(result, Some("/workspace".to_string()));
     }
 
+    // =========================================================================
+    // validate_path Tests
+    // =========================================================================
+
+    #[test]
+    fn test_validate_path_accepts_workspace_paths() {
+        let path = validate_path("/workspace/docs/../file.md", "/workspace").unwrap();
+        assert_eq!(path.as_str(), "/workspace/docs/../file.md");
+        assert_eq!(path.to_string(), "/workspace/docs/../file.md");
+
+        let path = validate_path(r"C:\Workspace\file.md", r"c:\workspace").unwrap();
+        assert_eq!(path.into_string(), r"C:\Workspace\file.md");
+    }
+
+    #[test]
+    fn test_validate_path_reasons() {
+        assert_eq!(validate_path("", "/workspace"), Err(PathError::EmptyInput));
+        assert_eq!(validate_path("/file.md", ""), Err(PathError::EmptyInput));
+        assert_eq!(
+            validate_path("/other/file.md", "/workspace"),
+            Err(PathError::OutsideWorkspace("/other/file.md".to_string()))
+        );
+        assert_eq!(
+            validate_path("/workspace/../etc/passwd", "/workspace"),
+            Err(PathError::TraversalDetected(
+                "/workspace/../etc/passwd".to_string()
+            ))
+        );
+        assert_eq!(
+            validate_path(r"C:\workspace\..\secret", r"C:\workspace"),
+            Err(PathError::TraversalDetected(
+                r"C:\workspace\..\secret".to_string()
+            ))
+        );
+        assert_eq!(
+            validate_path(r"C:\workspace\file.md", "/workspace"),
+            Err(PathError::MixedPlatform {
+                path: r"C:\workspace\file.md".to_string(),
+                root: "/workspace".to_string(),
+            })
+        );
+        assert!(matches!(
+            validate_path("/workspace/file.md", "D:/workspace"),
+            Err(PathError::MixedPlatform { .. })
+        ));
+        assert_eq!(
+            validate_path("file:///workspace/file.md", "file:///workspace"),
+            Err(PathError::UnsupportedScheme("file".to_string()))
+        );
+        assert_eq!(
+            validate_path("/workspace/file.md", "vscode-remote://wsl/workspace"),
+            Err(PathError::UnsupportedScheme("vscode-remote".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_validate_path_agrees_with_is_inside_workspace() {
+        let cases = [
+            ("/workspace/a.md", "/workspace"),
+            ("/workspace", "/workspace"),
+            ("/workspace2/a.md", "/workspace"),
+            ("relative/a.md", "/workspace"),
+            (r"C:\workspace/docs\a.md", r"C:\workspace"),
+            (r"\\server\share\a.md", r"\\server\share"),
+        ];
+        for (path, root) in cases {
+            assert_eq!(
+                validate_path(path, root).is_ok(),
+                is_inside_workspace(path, root),
+                "{} in {}",
+                path,
+                root
+            );
+        }
+    }
+
+    #[test]
+    fn test_path_error_display() {
+        let err = PathError::MixedPlatform {
+            path: r"C:\a".to_string(),
+            root: "/ws".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            r"Path C:\a and workspace root /ws use different platforms' conventions"
+        );
+        assert_eq!(
+            PathError::UnsupportedScheme("file".to_string()).to_string(),
+            "Unsupported path scheme: file"
+        );
+    }
+
+    #[test]
+    fn test_validate_path_reserved_names() {
+        let reserved = |path: &str| match validate_path(path, r"C:\ws") {
+            Err(PathError::ReservedName { component, .. }) => Some(component),
+            _ => None,
+        };
+        assert_eq!(reserved(r"C:\ws\CON").as_deref(), Some("CON"));
+        assert_eq!(reserved(r"C:\ws\docs\nul.txt").as_deref(), Some("nul.txt"));
+        assert_eq!(reserved(r"C:\ws\Com1\a.md").as_deref(), Some("Com1"));
+        assert_eq!(reserved(r"C:\ws\lpt9 .log").as_deref(), Some("lpt9 .log"));
+        assert_eq!(reserved(r"C:\ws\docs.\a.md").as_deref(), Some("docs."));
+        assert_eq!(reserved("C:/ws/notes /a.md").as_deref(), Some("notes "));
+        // Names that merely contain a device name are fine
+        assert_eq!(reserved(r"C:\ws\console\com10.md"), None);
+        assert_eq!(reserved(r"C:\ws\.\docs\..\a.md"), None);
+        // Extended-length paths are taken literally
+        assert!(validate_path(r"\\?\C:\ws\docs.", r"C:\ws").is_ok());
+        assert_eq!(reserved(r"\\.\C:\ws\prn").as_deref(), Some("prn"));
+        // Unix paths may use these names
+        assert!(validate_path("/ws/con/notes.", "/ws").is_ok());
+        assert_eq!(
+            validate_path(r"C:\ws\aux", r"C:\ws")
+                .unwrap_err()
+                .to_string(),
+            r"Path C:\ws\aux uses the name 'aux', which Windows reserves or rewrites"
+        );
+    }
+
+    #[test]
+    fn test_control_characters_rejected() {
+        assert!(!is_inside_workspace("/ws/file\0.txt", "/ws"));
+        assert!(!is_inside_workspace("/ws/a\n.md", "/ws"));
+        assert!(!is_inside_workspace("/ws/a.md", "/ws\0"));
+        assert!(!is_inside_workspace("C:\\ws\\a\u{1b}.md", r"C:\ws"));
+        assert_eq!(
+            validate_path("/ws/file\0.txt", "/ws"),
+            Err(PathError::IllegalCharacter {
+                path: "/ws/file\0.txt".to_string(),
+                character: '\0',
+            })
+        );
+        assert_eq!(
+            validate_path("/ws/a.md", "/w\ts"),
+            Err(PathError::IllegalCharacter {
+                path: "/w\ts".to_string(),
+                character: '\t',
+            })
+        );
+        assert_eq!(
+            validate_path("/ws/file\0.txt", "/ws")
+                .unwrap_err()
+                .to_string(),
+            r#"Path "/ws/file\0.txt" contains the control character '\0'"#
+        );
+        // Percent-encoded NULs are caught once decoded
+        assert!(matches!(
+            validate_uri("file:///ws/a%00.md", "/ws"),
+            Err(PathError::IllegalCharacter {
+                character: '\0',
+                ..
+            })
+        ));
+        assert!(validate_path("/ws/caf\u{e9} \u{2713}.md", "/ws").is_ok());
+    }
+
+    // =========================================================================
+    // validate_uri Tests
+    // =========================================================================
+
+    #[test]
+    fn test_validate_uri_decodes_file_uris() {
+        let path = validate_uri("file:///C:/ws/a%20b.md", r"C:\ws").unwrap();
+        assert_eq!(path.as_str(), "C:/ws/a b.md");
+        let path = validate_uri("file:///c%3A/ws/docs/prd.md", "file:///c%3A/ws").unwrap();
+        assert_eq!(path.as_str(), "c:/ws/docs/prd.md");
+        let path = validate_uri("file:///home/me/ws/%E2%9C%93.md", "/home/me/ws").unwrap();
+        assert_eq!(path.as_str(), "/home/me/ws/\u{2713}.md");
+        let path = validate_uri("FILE://localhost/ws/a.md?x=1#L3", "/ws").unwrap();
+        assert_eq!(path.as_str(), "/ws/a.md");
+        // A host names a UNC share
+        let path = validate_uri("file://server/share/ws/a.md", r"\\server\share\ws").unwrap();
+        assert_eq!(path.as_str(), "//server/share/ws/a.md");
+        // Plain paths pass through
+        assert!(validate_uri("/ws/a.md", "/ws").is_ok());
+    }
+
+    #[test]
+    fn test_validate_uri_refusals() {
+        assert_eq!(
+            validate_uri("file:///ws/%2E%2E/etc/passwd", "/ws"),
+            Err(PathError::TraversalDetected(
+                "/ws/../etc/passwd".to_string()
+            ))
+        );
+        assert_eq!(
+            validate_uri("file:///other/a.md", "file:///ws"),
+            Err(PathError::OutsideWorkspace("/other/a.md".to_string()))
+        );
+        assert_eq!(
+            validate_uri("vscode-remote://wsl+Ubuntu/ws/a.md", "/ws"),
+            Err(PathError::UnsupportedScheme("vscode-remote".to_string()))
+        );
+        assert_eq!(validate_uri("file:///", ""), Err(PathError::EmptyInput));
+        for bad in [
+            "file:/ws/a.md",
+            "file:///ws/a%2",
+            "file:///ws/a%zz.md",
+            "file:///ws/%FF",
+        ] {
+            assert_eq!(
+                validate_uri(bad, "/ws"),
+                Err(PathError::InvalidUri(bad.to_string())),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b%2Fc").as_deref(), Some("a b/c"));
+        assert_eq!(percent_decode("100%").as_deref(), None);
+        assert_eq!(percent_decode("plain").as_deref(), Some("plain"));
+    }
+
+    // =========================================================================
+    // Multi-root Workspace Tests
+    // =========================================================================
+
+    #[test]
+    fn test_is_inside_any_workspace() {
+        let roots = ["/work/api", "/work/web", r"C:\ws"];
+        assert_eq!(
+            is_inside_any_workspace("/work/api/src/a.md", &roots),
+            Some(0)
+        );
+        assert_eq!(is_inside_any_workspace("/work/web", &roots), Some(1));
+        assert_eq!(is_inside_any_workspace(r"c:\WS\docs\a.md", &roots), Some(2));
+        assert_eq!(
+            is_inside_any_workspace("/work/api/../db/a.md", &roots),
+            None
+        );
+        assert_eq!(is_inside_any_workspace("/work/webapp/a.md", &roots), None);
+        assert_eq!(is_inside_any_workspace("/work/api/a.md", &[]), None);
+    }
+
+    #[test]
+    fn test_is_inside_any_workspace_prefers_innermost_root() {
+        let roots = ["/work", "/work/api/", "/work/api"];
+        assert_eq!(is_inside_any_workspace("/work/api/a.md", &roots), Some(1));
+        assert_eq!(is_inside_any_workspace("/work/web/a.md", &roots), Some(0));
+    }
+
+    #[test]
+    fn test_workspace_set_dedupes_roots() {
+        let set = WorkspaceSet::new([r"C:\ws", "c:/WS/", "", "/work", "/work/./"]);
+        assert_eq!(set.roots(), [r"C:\ws", "/work"]);
+        assert_eq!(set.len(), 2);
+        assert!(!set.is_empty());
+        assert!(WorkspaceSet::default().is_empty());
+    }
+
+    #[test]
+    fn test_workspace_set_lookup() {
+        let set = WorkspaceSet::new(vec!["/work".to_string(), "/work/api".to_string()]);
+        assert_eq!(set.find("/work/api/a.md"), Some(1));
+        assert_eq!(set.root_of("/work/api/a.md"), Some("/work/api"));
+        assert_eq!(set.root_of("/work/a.md"), Some("/work"));
+        assert!(set.contains("/work"));
+        assert!(!set.contains("/elsewhere/a.md"));
+
+        assert_eq!(
+            set.validate("/work/api/a.md").unwrap().as_str(),
+            "/work/api/a.md"
+        );
+        assert_eq!(
+            set.validate("/work/../etc/passwd"),
+            Err(PathError::TraversalDetected(
+                "/work/../etc/passwd".to_string()
+            ))
+        );
+        assert!(matches!(
+            set.validate("file:///work/a.md"),
+            Err(PathError::UnsupportedScheme(_))
+        ));
+        assert_eq!(
+            WorkspaceSet::default().validate("/work/a.md"),
+            Err(PathError::EmptyInput)
+        );
+    }
+
     // =========================================================================
     // Additional Security Tests
     // =========================================================================
 
     #[test]
     fn test_null_byte_in_path() {
-        // Null bytes should be treated as regular characters (no special handling)
+        // File systems truncate at a null byte, so the path is never trusted
         let result = is_inside_workspace("/workspace/file\x00.txt", "/workspace");
-        // The path technically starts inside workspace
-        assert!(result);
+        assert!(!result);
     }
 
     #[test]
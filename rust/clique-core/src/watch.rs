@@ -0,0 +1,366 @@
+// clique-core/src/watch.rs
+//! Watching status files for changes.
+//!
+//! Native hosts (a CLI, a language server) get the same change feed the
+//! extension builds itself: file events are debounced, the file is
+//! re-parsed, and the statuses are diffed against the previous snapshot so
+//! the callback only hears about items that actually moved.
+//!
+//! Parent directories are watched rather than the files themselves, so
+//! files replaced by an atomic rename (see [`crate::io::write_atomic`]) keep
+//! being followed.
+
+use crate::sprint::parse_sprint_status;
+use crate::workflow::parse_workflow_status;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Quiet period after the last file event before a file is re-read
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+
+#[derive(Error, Debug)]
+pub enum WatchError {
+    #[error("Invalid watch path: {0}")]
+    InvalidPath(String),
+    #[error("Failed to watch files: {0}")]
+    NotifyError(String),
+}
+
+/// One item or story whose status changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusChange {
+    pub id: String,
+    /// Status before the change; `None` when the entry is new
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Status after the change; `None` when the entry was removed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+/// What changed in one watched file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusChangeEvent {
+    /// The watched path, as it was passed in
+    pub path: String,
+    pub changes: Vec<StatusChange>,
+    /// Why the file could not be read or parsed. The previous snapshot is
+    /// kept, so changes are reported against it once the file is valid again.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Statuses by ID: workflow items, or epics and stories of a sprint file
+pub type Snapshot = BTreeMap<String, String>;
+
+/// Parse a status file into a snapshot. Sprint files are recognized by
+/// their `development_status` key; anything else is read as a workflow.
+pub fn snapshot(content: &str) -> Result<Snapshot, String> {
+    let parsed: Value = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+    if parsed.get("development_status").is_some() {
+        let data = parse_sprint_status(content).map_err(|e| e.to_string())?;
+        Ok(data
+            .epics
+            .iter()
+            .flat_map(|epic| {
+                std::iter::once((epic.id.clone(), epic.status.clone())).chain(
+                    epic.stories
+                        .iter()
+                        .map(|story| (story.id.clone(), story.status.to_string())),
+                )
+            })
+            .collect())
+    } else {
+        let data = parse_workflow_status(content).map_err(|e| e.to_string())?;
+        Ok(data
+            .items
+            .into_iter()
+            .map(|item| (item.id, item.status.to_string()))
+            .collect())
+    }
+}
+
+/// Status changes from `before` to `after`, ordered by ID
+pub fn diff_snapshots(before: &Snapshot, after: &Snapshot) -> Vec<StatusChange> {
+    let ids: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    ids.into_iter()
+        .filter_map(|id| {
+            let (from, to) = (before.get(id), after.get(id));
+            (from != to).then(|| StatusChange {
+                id: id.clone(),
+                from: from.cloned(),
+                to: to.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// A watched file and its last good snapshot
+struct Watched {
+    /// Path as given by the caller, reported in events
+    path: String,
+    snapshot: Snapshot,
+}
+
+impl Watched {
+    /// Re-read the file, returning an event if anything changed.
+    fn refresh(&mut self) -> Option<StatusChangeEvent> {
+        let result = std::fs::read_to_string(&self.path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| snapshot(&content));
+        match result {
+            Ok(snapshot) => {
+                let changes = diff_snapshots(&self.snapshot, &snapshot);
+                self.snapshot = snapshot;
+                (!changes.is_empty()).then(|| StatusChangeEvent {
+                    path: self.path.clone(),
+                    changes,
+                    error: None,
+                })
+            }
+            Err(error) => Some(StatusChangeEvent {
+                path: self.path.clone(),
+                changes: Vec::new(),
+                error: Some(error),
+            }),
+        }
+    }
+}
+
+/// Handle keeping a watch alive; dropping it stops watching and waits for
+/// the delivery thread to finish.
+pub struct StatusWatcher {
+    watcher: Option<RecommendedWatcher>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for StatusWatcher {
+    fn drop(&mut self) {
+        // Dropping the watcher closes the event channel, ending the thread
+        self.watcher.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Watch status files, calling `callback` whenever statuses change; see
+/// [`watch_status_files_with_debounce`].
+pub fn watch_status_files<F>(paths: &[String], callback: F) -> Result<StatusWatcher, WatchError>
+where
+    F: FnMut(StatusChangeEvent) + Send + 'static,
+{
+    watch_status_files_with_debounce(paths, DEFAULT_DEBOUNCE, callback)
+}
+
+/// Watch status files, calling `callback` from a background thread once a
+/// file has been quiet for `debounce` after changing.
+///
+/// Each file is snapshotted when watching starts (a missing or invalid file
+/// starts out empty), so the first event reports changes from that point.
+/// Events are only delivered when at least one status changed or the file
+/// could not be read.
+pub fn watch_status_files_with_debounce<F>(
+    paths: &[String],
+    debounce: Duration,
+    mut callback: F,
+) -> Result<StatusWatcher, WatchError>
+where
+    F: FnMut(StatusChangeEvent) + Send + 'static,
+{
+    let mut watched: HashMap<PathBuf, Watched> = HashMap::new();
+    let mut dirs = HashSet::new();
+    for path in paths {
+        let file = Path::new(path);
+        let invalid = || WatchError::InvalidPath(path.clone());
+        let name = file.file_name().ok_or_else(invalid)?;
+        let dir = match file.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let dir = dir.canonicalize().map_err(|_| invalid())?;
+        let snapshot = std::fs::read_to_string(file)
+            .ok()
+            .and_then(|content| snapshot(&content).ok())
+            .unwrap_or_default();
+        watched.insert(
+            dir.join(name),
+            Watched {
+                path: path.clone(),
+                snapshot,
+            },
+        );
+        dirs.insert(dir);
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher =
+        notify::recommended_watcher(sender).map_err(|e| WatchError::NotifyError(e.to_string()))?;
+    for dir in &dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| WatchError::NotifyError(format!("{}: {}", dir.display(), e)))?;
+    }
+
+    let thread = std::thread::spawn(move || {
+        let mut pending: HashSet<PathBuf> = HashSet::new();
+        loop {
+            match receiver.recv_timeout(debounce) {
+                Ok(Ok(event)) => {
+                    if matches!(event.kind, EventKind::Access(_)) {
+                        continue;
+                    }
+                    pending.extend(
+                        event
+                            .paths
+                            .into_iter()
+                            .filter(|path| watched.contains_key(path)),
+                    );
+                }
+                // Backend errors are transient; the next event re-reads anyway
+                Ok(Err(_)) => {}
+                Err(RecvTimeoutError::Timeout) => {
+                    for path in pending.drain() {
+                        if let Some(event) = watched.get_mut(&path).and_then(Watched::refresh) {
+                            callback(event);
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(StatusWatcher {
+        watcher: Some(watcher),
+        thread: Some(thread),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(pairs: &[(&str, &str)]) -> Snapshot {
+        pairs
+            .iter()
+            .map(|(id, status)| (id.to_string(), status.to_string()))
+            .collect()
+    }
+
+    // =========================================================================
+    // Snapshot Tests
+    // =========================================================================
+
+    #[test]
+    fn test_snapshot_formats() {
+        let sprint = snapshot("development_status:\n  epic-1: in-progress\n  1-login: review\n");
+        assert_eq!(
+            sprint.unwrap(),
+            snap(&[("epic-1", "in-progress"), ("1-login", "review")])
+        );
+
+        let workflow = snapshot("workflows:\n  prd:\n    status: complete\n");
+        assert_eq!(workflow.unwrap(), snap(&[("prd", "complete")]));
+
+        assert!(snapshot("workflows: [unclosed").is_err());
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let before = snap(&[("a", "backlog"), ("b", "review"), ("c", "done")]);
+        let after = snap(&[("a", "in-progress"), ("c", "done"), ("d", "backlog")]);
+        let changes = diff_snapshots(&before, &after);
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = changes
+            .iter()
+            .map(|c| (c.id.as_str(), c.from.as_deref(), c.to.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("a", Some("backlog"), Some("in-progress")),
+                ("b", Some("review"), None),
+                ("d", None, Some("backlog")),
+            ]
+        );
+        assert!(diff_snapshots(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn test_status_change_serialization() {
+        let change = StatusChange {
+            id: "1-login".to_string(),
+            from: None,
+            to: Some("backlog".to_string()),
+        };
+        let json = serde_json::to_string(&change).unwrap();
+        assert_eq!(json, r#"{"id":"1-login","to":"backlog"}"#);
+    }
+
+    // =========================================================================
+    // Watcher Tests
+    // =========================================================================
+
+    #[test]
+    fn test_watch_delivers_debounced_changes() {
+        let dir = crate::testing::TempDir::new("watch");
+        let path = dir.join("sprint-status.yaml");
+        std::fs::write(
+            &path,
+            "development_status:\n  epic-1: in-progress\n  1-login: backlog\n",
+        )
+        .unwrap();
+        let path_str = path.to_string_lossy().to_string();
+
+        let (sender, receiver) = mpsc::channel();
+        let watcher = watch_status_files_with_debounce(
+            std::slice::from_ref(&path_str),
+            Duration::from_millis(300),
+            move |event| {
+                let _ = sender.send(event);
+            },
+        )
+        .unwrap();
+
+        // Several quick writes settle into one event against the start
+        std::fs::write(
+            &path,
+            "development_status:\n  epic-1: in-progress\n  1-login: in-progress\n",
+        )
+        .unwrap();
+        crate::io::write_atomic(
+            &path_str,
+            "development_status:\n  epic-1: in-progress\n  1-login: review\n",
+        )
+        .unwrap();
+        let event = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(event.path, path_str);
+        assert_eq!(event.error, None);
+        assert_eq!(event.changes.len(), 1);
+        assert_eq!(event.changes[0].from.as_deref(), Some("backlog"));
+        assert_eq!(event.changes[0].to.as_deref(), Some("review"));
+
+        // Watching continues after the atomic replace; errors are reported
+        std::fs::write(&path, "development_status: [unclosed").unwrap();
+        let event = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(event.error.is_some());
+        assert!(event.changes.is_empty());
+
+        drop(watcher);
+    }
+
+    #[test]
+    fn test_watch_invalid_path() {
+        let result = watch_status_files(&["/no/such/dir/status.yaml".to_string()], |_| {});
+        assert!(matches!(result, Err(WatchError::InvalidPath(_))));
+    }
+}
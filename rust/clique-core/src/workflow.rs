@@ -1,10 +1,31 @@
 // clique-core/src/workflow.rs
 //! Workflow parsing and status update logic.
 
-use crate::types::{Phase, WorkflowData, WorkflowItem};
-use regex::Regex;
-use serde_yaml::Value;
-use std::collections::HashMap;
+use crate::conflict;
+#[cfg(feature = "fs")]
+pub use crate::definition::load_definition;
+pub use crate::definition::{
+    DefinedWorkflow, WorkflowDefinition, merge_definition, parse_workflow_definition,
+};
+use crate::limits::{ParseLimits, enforce_item_limit, enforce_limits};
+use crate::options::ParseOptions;
+#[cfg(feature = "fs")]
+pub use crate::outputs::{OutputIssue, OutputIssueKind, verify_outputs, verify_outputs_since};
+use crate::pattern::flow_context_lines;
+use crate::text::text;
+use crate::trace::record;
+use crate::types::{
+    ParseIssue, Phase, StatusAliases, StatusClass, StatusRegistry, StoryStatus, WorkflowData,
+    WorkflowItem, WorkflowMeta,
+};
+use crate::yaml_line::{
+    Entry, comments, find_block, find_item_block, find_item_value, find_key, find_value,
+    format_scalar, indent, is_blank, lines, needs_quotes, parse_entry, remove_field,
+    remove_item_field, set_field, set_item_field, splice,
+};
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, HashMap};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -15,6 +36,26 @@ pub enum WorkflowError {
     ItemNotFound(String),
     #[error("Update failed: {0}")]
     UpdateError(String),
+    #[error("Resource limit exceeded: {0}")]
+    ResourceLimit(String),
+    #[error("Invalid status: {0}")]
+    InvalidStatus(String),
+    #[error("Path is outside the workspace: {0}")]
+    PathOutsideWorkspace(String),
+    #[error("Failed to read file: {0}")]
+    Io(String),
+    #[error("Preprocessing failed: {0}")]
+    Preprocess(String),
+    #[error("No format adapter recognizes the document; tried {0}")]
+    UnknownFormat(String),
+    #[error("Status changed since it was read; it is now {actual:?}")]
+    ConflictError { actual: String },
+    /// The file holds git conflict markers; both sides parsed
+    #[error("File has unresolved merge conflicts")]
+    MergeConflict {
+        ours: Box<WorkflowData>,
+        theirs: Box<WorkflowData>,
+    },
 }
 
 /// Mapping of workflow IDs to phases based on BMad methodology
@@ -63,22 +104,22 @@ fn get_agent_map() -> HashMap<&'static str, &'static str> {
     map
 }
 
-fn infer_phase(workflow_id: &str) -> Phase {
+pub(crate) fn infer_phase(workflow_id: &str) -> Phase {
     let map = get_phase_map();
     Phase::Number(*map.get(workflow_id).unwrap_or(&1))
 }
 
-fn infer_agent(workflow_id: &str) -> String {
+pub(crate) fn infer_agent(workflow_id: &str) -> String {
     let map = get_agent_map();
     map.get(workflow_id).unwrap_or(&"pm").to_string()
 }
 
-fn infer_command(workflow_id: &str) -> String {
+pub(crate) fn infer_command(workflow_id: &str) -> String {
     workflow_id.to_string()
 }
 
 /// Check if a value looks like a file path
-fn is_file_path(value: &str) -> bool {
+pub(crate) fn is_file_path(value: &str) -> bool {
     value.contains('/')
         || value.ends_with(".md")
         || value.ends_with(".yaml")
@@ -87,91 +128,94 @@ fn is_file_path(value: &str) -> bool {
         || value.ends_with(".txt")
 }
 
+/// Read a list of strings, accepting a single string too
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Sequence(seq)) => seq
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_string())
+            .collect(),
+        Some(Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
 /// Parse new format: workflows object with nested status fields
 fn parse_new_format(parsed: &Value) -> Vec<WorkflowItem> {
-    let mut items = Vec::new();
-
-    for (key, data) in parsed
+    let mut items: Vec<WorkflowItem> = parsed
         .get("workflows")
         .and_then(|v| v.as_mapping())
         .into_iter()
         .flat_map(|m| m.iter())
-    {
-        let id = key.as_str().unwrap_or_default().to_string();
-        let workflow_data = data.as_mapping();
+        .map(|(key, data)| new_format_item(key.as_str().unwrap_or_default(), data))
+        .collect();
 
-        let raw_status = workflow_data
-            .and_then(|m| m.get("status"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("not_started");
+    // Sort by phase, then by ID
+    items.sort_by(|a, b| a.phase.cmp(&b.phase).then_with(|| a.id.cmp(&b.id)));
 
-        let output_file = workflow_data
-            .and_then(|m| m.get("output_file"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
+    items
+}
 
-        // Map status: 'complete' -> output_file path, 'not_started' -> 'required'
-        let status = if raw_status == "complete" {
-            output_file
-                .clone()
-                .unwrap_or_else(|| "complete".to_string())
-        } else if raw_status == "not_started" {
-            "required".to_string()
-        } else {
-            raw_status.to_string()
-        };
+/// Build one item of the new format from its `workflows:` entry
+fn new_format_item(id: &str, data: &Value) -> WorkflowItem {
+    let workflow_data = data.as_mapping();
+
+    let raw_status = workflow_data
+        .and_then(|m| m.get("status"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("not_started");
+
+    let output_file = workflow_data
+        .and_then(|m| m.get("output_file"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    // Map status: 'complete' -> output_file path, 'not_started' -> 'required'
+    let status = if raw_status == "complete" {
+        output_file
+            .clone()
+            .unwrap_or_else(|| "complete".to_string())
+    } else if raw_status == "not_started" {
+        "required".to_string()
+    } else {
+        raw_status.to_string()
+    };
 
-        let note = workflow_data
-            .and_then(|m| m.get("notes").or_else(|| m.get("note")))
+    let note = workflow_data
+        .and_then(|m| m.get("notes").or_else(|| m.get("note")))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    WorkflowItem {
+        id: id.to_string(),
+        phase: infer_phase(id),
+        status: text(status),
+        agent: Some(text(infer_agent(id))),
+        command: Some(infer_command(id)),
+        note,
+        output_file,
+        custom_status: None,
+        depends_on: string_list(data.get("depends_on")),
+        tracks: string_list(data.get("tracks")),
+        tags: string_list(data.get("tags")),
+        description: None,
+        due: data
+            .get("due")
             .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-
-        items.push(WorkflowItem {
-            id: id.clone(),
-            phase: infer_phase(&id),
-            status,
-            agent: Some(infer_agent(&id)),
-            command: Some(infer_command(&id)),
-            note,
-            output_file,
-        });
+            .map(|s| s.to_string()),
     }
-
-    // Sort by phase, then by ID
-    items.sort_by(|a, b| a.phase.cmp(&b.phase).then_with(|| a.id.cmp(&b.id)));
-
-    items
 }
 
 /// Parse flat format: workflow_status object with key-value pairs
 fn parse_flat_format(parsed: &Value) -> Vec<WorkflowItem> {
-    let mut items = Vec::new();
-
-    for (key, value) in parsed
+    let mut items: Vec<WorkflowItem> = parsed
         .get("workflow_status")
         .and_then(|v| v.as_mapping())
         .into_iter()
         .flat_map(|m| m.iter())
-    {
-        let id = key.as_str().unwrap_or_default().to_string();
-        let status = value.as_str().unwrap_or_default().to_string();
-
-        let output_file = if is_file_path(&status) {
-            Some(status.clone())
-        } else {
-            None
-        };
-
-        items.push(WorkflowItem {
-            id: id.clone(),
-            phase: infer_phase(&id),
-            status,
-            agent: Some(infer_agent(&id)),
-            command: Some(infer_command(&id)),
-            note: None,
-            output_file,
-        });
-    }
+        .map(|(key, value)| flat_format_item(key.as_str().unwrap_or_default(), value))
+        .collect();
 
     // Sort by phase, then by ID
     items.sort_by(|a, b| a.phase.cmp(&b.phase).then_with(|| a.id.cmp(&b.id)));
@@ -179,85 +223,471 @@ fn parse_flat_format(parsed: &Value) -> Vec<WorkflowItem> {
     items
 }
 
+/// Build one item of the flat format from its `id: status` pair
+fn flat_format_item(id: &str, value: &Value) -> WorkflowItem {
+    let status = value.as_str().unwrap_or_default().to_string();
+
+    let output_file = if is_file_path(&status) {
+        Some(status.clone())
+    } else {
+        None
+    };
+
+    WorkflowItem {
+        id: id.to_string(),
+        phase: infer_phase(id),
+        status: text(status),
+        agent: Some(text(infer_agent(id))),
+        command: Some(infer_command(id)),
+        note: None,
+        output_file,
+        custom_status: None,
+        depends_on: Vec::new(),
+        tracks: Vec::new(),
+        description: None,
+        due: None,
+        tags: Vec::new(),
+    }
+}
+
 /// Parse old format: workflow_status array of objects
 fn parse_old_format(parsed: &Value) -> Vec<WorkflowItem> {
-    let mut items = Vec::new();
-
-    if let Some(workflow_status) = parsed.get("workflow_status").and_then(|v| v.as_sequence()) {
-        for item in workflow_status {
-            let id = item
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string();
-
-            let phase = item
-                .get("phase")
-                .and_then(|v| v.as_i64())
-                .map(|n| Phase::Number(n as i32))
-                .unwrap_or_else(|| infer_phase(&id));
-
-            let status = item
-                .get("status")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string();
-
-            let agent = item
-                .get("agent")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-
-            let command = item
-                .get("command")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-
-            let note = item
-                .get("note")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-
-            items.push(WorkflowItem {
-                id,
-                phase,
-                status,
-                agent,
-                command,
-                note,
-                output_file: None,
-            });
-        }
-    }
+    parsed
+        .get("workflow_status")
+        .and_then(|v| v.as_sequence())
+        .into_iter()
+        .flatten()
+        .map(old_format_item)
+        .collect()
+}
 
-    items
+/// Build one item of the old format from its sequence entry
+fn old_format_item(item: &Value) -> WorkflowItem {
+    let id = item
+        .get("id")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let phase = item
+        .get("phase")
+        .and_then(|v| v.as_i64())
+        .map(|n| Phase::Number(n as i32))
+        .unwrap_or_else(|| infer_phase(&id));
+
+    let status = item
+        .get("status")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let agent = item
+        .get("agent")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let command = item
+        .get("command")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let note = item
+        .get("note")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    WorkflowItem {
+        id,
+        phase,
+        status: text(status),
+        agent: agent.map(text),
+        command,
+        note,
+        output_file: None,
+        custom_status: None,
+        depends_on: string_list(item.get("depends_on")),
+        tracks: string_list(item.get("tracks")),
+        tags: string_list(item.get("tags")),
+        description: None,
+        due: item
+            .get("due")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+    }
 }
 
 /// Parse workflow status from YAML content
 pub fn parse_workflow_status(yaml_content: &str) -> Result<WorkflowData, WorkflowError> {
+    parse_workflow_status_with_limits(yaml_content, &ParseLimits::default())
+}
+
+/// Parse workflow status from YAML content, enforcing custom resource limits
+pub fn parse_workflow_status_with_limits(
+    yaml_content: &str,
+    limits: &ParseLimits,
+) -> Result<WorkflowData, WorkflowError> {
+    parse_workflow_status_with_options(
+        yaml_content,
+        &ParseOptions::default().with_limits(limits.clone()),
+    )
+}
+
+/// Parse workflow status from YAML content with explicit parse options.
+///
+/// With `normalize` set, item statuses that normalize to `done` are treated
+/// as complete (reported as the output file when there is one) and those
+/// that normalize to `backlog` as `required`. Other statuses are kept. With a
+/// `registry`, items whose status is not a built-in one carry the registered
+/// metadata in `custom_status`. With `preprocessing`, the content is
+/// interpolated and its includes merged in first; limits apply to the result.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(bytes = yaml_content.len(), format = tracing::field::Empty, items = tracing::field::Empty)
+    )
+)]
+pub fn parse_workflow_status_with_options(
+    yaml_content: &str,
+    options: &ParseOptions,
+) -> Result<WorkflowData, WorkflowError> {
+    enforce_limits(yaml_content, &options.limits).map_err(WorkflowError::ResourceLimit)?;
+    if let Some(preprocessing) = &options.preprocessing {
+        let content = preprocessing
+            .apply_with_limits(yaml_content, &options.limits)
+            .map_err(|e| WorkflowError::Preprocess(e.to_string()))?;
+        let options = ParseOptions {
+            preprocessing: None,
+            ..options.clone()
+        };
+        return parse_workflow_status_with_options(&content, &options);
+    }
+
+    if let Some(sides) = conflict::split(yaml_content)
+        && let (Ok(ours), Ok(theirs)) = (
+            parse_workflow_status_with_options(&sides.ours, options),
+            parse_workflow_status_with_options(&sides.theirs, options),
+        )
+    {
+        return Err(WorkflowError::MergeConflict {
+            ours: Box::new(ours),
+            theirs: Box::new(theirs),
+        });
+    }
+
     let parsed: Value =
         serde_yaml::from_str(yaml_content).map_err(|e| WorkflowError::ParseError(e.to_string()))?;
 
+    let mut data = workflow_data_from_value(&parsed);
+    record!(
+        "format" = tracing::field::display(detect_format(&parsed)),
+        "items" = data.items.len(),
+    );
+    enforce_item_limit(data.items.len(), &options.limits).map_err(WorkflowError::ResourceLimit)?;
+    if let Some(aliases) = &options.normalize {
+        normalize_items(&mut data.items, aliases);
+    }
+    if let Some(registry) = &options.registry {
+        tag_items(&mut data.items, registry);
+    }
+    Ok(data)
+}
+
+/// Parse a single item without building the whole document.
+///
+/// The item's lines are located by scanning the block structure and only
+/// they are parsed, so the cost does not grow with the number of other
+/// items. Documents the scan cannot follow (flow-style collections, anchors
+/// defined elsewhere) fall back to a full parse. Either way the item is the
+/// one [`parse_workflow_status`] would return.
+pub fn parse_item(content: &str, item_id: &str) -> Result<WorkflowItem, WorkflowError> {
+    if let Some(item) = scan_item(content, item_id) {
+        return Ok(item);
+    }
+    parse_workflow_status(content)?
+        .items
+        .into_iter()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| WorkflowError::ItemNotFound(item_id.to_string()))
+}
+
+/// Fast path of [`parse_item`]; `None` when the item cannot be located and
+/// parsed on its own
+fn scan_item(content: &str, item_id: &str) -> Option<WorkflowItem> {
+    let snippet =
+        |range: std::ops::Range<usize>| serde_yaml::from_str::<Value>(&content[range]).ok();
+    // The entry's value, whatever its key's quoting
+    let entry_value = |parsed: Value| parsed.as_mapping()?.values().next().cloned();
+
+    if find_value(content, &["workflows"]).is_some() {
+        // Only a block mapping is scanned; anything else takes the full parse
+        if !find_value(content, &["workflows"])?.is_empty() {
+            return None;
+        }
+        let data = entry_value(snippet(find_block(content, &["workflows", item_id])?)?)?;
+        return Some(new_format_item(item_id, &data));
+    }
+    if let Some(range) = find_item_block(content, &["workflow_status"], "id", item_id) {
+        let parsed = snippet(range)?;
+        return parsed.as_sequence()?.first().map(old_format_item);
+    }
+    let value = entry_value(snippet(find_block(
+        content,
+        &["workflow_status", item_id],
+    )?)?)?;
+    Some(flat_format_item(item_id, &value))
+}
+
+/// Parse only the header fields and item counts of a workflow document.
+///
+/// Workspace scanners listing many projects need names and progress, not
+/// items. The item collection is skimmed line by line for its entries and
+/// their statuses, and only the remaining top-level fields are parsed as
+/// YAML, so the cost barely grows with the number of items. Documents the
+/// skim cannot follow fall back to a full parse. The counts match
+/// [`parse_workflow_status`], although on the fast path a malformed item
+/// collection goes unnoticed.
+pub fn parse_workflow_metadata(content: &str) -> Result<WorkflowMeta, WorkflowError> {
+    if let Some(meta) = scan_metadata(content) {
+        return Ok(meta);
+    }
+    Ok(WorkflowMeta::from(&parse_workflow_status(content)?))
+}
+
+/// Top-level keys that may hold the item collection
+const COLLECTION_KEYS: [&str; 2] = ["workflows", "workflow_status"];
+
+/// Fast path of [`parse_workflow_metadata`]; `None` when the document needs
+/// a full parse
+fn scan_metadata(content: &str) -> Option<WorkflowMeta> {
+    let mut blocks = Vec::new();
+    let mut collections = HashMap::new();
+    for key in COLLECTION_KEYS {
+        let Some(block) = find_block(content, &[key]) else {
+            continue;
+        };
+        // Flow collections, aliases and scalars are left to the full parse
+        if !content[find_value(content, &[key])?].is_empty() {
+            return None;
+        }
+        collections.insert(key, skim_entries(&content[block.clone()])?);
+        blocks.push(block);
+    }
+
+    // Parse everything but the collections
+    blocks.sort_by_key(|block| block.start);
+    let mut head = String::with_capacity(content.len());
+    let mut offset = 0;
+    for block in blocks {
+        head.push_str(&content[offset..block.start]);
+        offset = block.end;
+    }
+    head.push_str(&content[offset..]);
+    // A duplicate key is an error only the full parse reports
+    if COLLECTION_KEYS
+        .iter()
+        .any(|key| collections.contains_key(key) && find_key(&head, &[key]).is_some())
+    {
+        return None;
+    }
+    enforce_limits(&head, &ParseLimits::default()).ok()?;
+    let parsed: Value = serde_yaml::from_str(&head).ok()?;
+    let header = workflow_data_from_value(&parsed);
+
+    // Mirror detect_format: an empty block is null, not a mapping
+    let is_mapping = |entries: &[Skimmed]| entries.first().is_some_and(|e| !e.entry.is_item);
+    let statuses = match (
+        collections.get("workflows"),
+        collections.get("workflow_status"),
+    ) {
+        (Some(entries), _) if is_mapping(entries) => entries
+            .iter()
+            .map(Skimmed::new_format_status)
+            .collect::<Option<Vec<_>>>()?,
+        (_, Some(entries)) if is_mapping(entries) => entries
+            .iter()
+            .map(Skimmed::flat_format_status)
+            .collect::<Option<Vec<_>>>()?,
+        (_, Some(entries)) => entries
+            .iter()
+            .map(Skimmed::old_format_status)
+            .collect::<Option<Vec<_>>>()?,
+        (_, None) => Vec::new(),
+    };
+    enforce_item_limit(statuses.len(), &ParseLimits::default()).ok()?;
+
+    Some(WorkflowMeta {
+        item_count: statuses.len(),
+        complete_count: statuses
+            .iter()
+            .filter(|status| StatusClass::of(status) == StatusClass::Complete)
+            .count(),
+        ..WorkflowMeta::from(&header)
+    })
+}
+
+/// An entry of a block collection read line by line
+struct Skimmed<'a> {
+    line: &'a str,
+    entry: Entry<'a>,
+    /// Non-blank lines nested under the entry, with their entries if any
+    nested: Vec<(&'a str, Option<Entry<'a>>)>,
+}
+
+impl Skimmed<'_> {
+    /// Raw value token of the field `key` whose key sits at `column`, on a
+    /// sequence item's own line or nested under the entry
+    fn field(&self, column: usize, key: &str) -> Option<&str> {
+        let own = self.entry.is_item.then_some((self.line, &self.entry));
+        own.into_iter()
+            .chain(self.nested.iter().filter_map(|(line, entry)| {
+                entry
+                    .as_ref()
+                    .filter(|e| !e.is_item && indent(line) == column)
+                    .map(|e| (*line, e))
+            }))
+            .find(|(_, entry)| entry.indent == column && entry.key == key)
+            .map(|(line, entry)| &line[entry.value.clone()])
+    }
+
+    /// Status of a `workflows` entry as written, `not_started` when absent
+    fn new_format_status(&self) -> Option<String> {
+        if !self.entry.value.is_empty() {
+            return None;
+        }
+        let Some((first, _)) = self.nested.first() else {
+            return Some("not_started".to_string());
+        };
+        match self.field(indent(first), "status") {
+            Some(token) => field_status(token, "not_started"),
+            None => Some("not_started".to_string()),
+        }
+    }
+
+    /// Status of a flat `workflow_status` entry: its own value
+    fn flat_format_status(&self) -> Option<String> {
+        // Values continued or nested on later lines take the full parse
+        if !self.nested.is_empty() {
+            return None;
+        }
+        let value = skim_scalar(&self.line[self.entry.value.clone()])?;
+        Some(value.as_str().unwrap_or_default().to_string())
+    }
+
+    /// Status of an old-format sequence item
+    fn old_format_status(&self) -> Option<String> {
+        match self.field(self.entry.indent, "status") {
+            Some(token) => field_status(token, ""),
+            None => Some(String::new()),
+        }
+    }
+}
+
+/// A `status` field's value, `default` when it is not a string
+fn field_status(token: &str, default: &str) -> Option<String> {
+    // An empty token means the value sits on the following lines
+    if token.is_empty() {
+        return None;
+    }
+    let value = skim_scalar(token)?;
+    Some(value.as_str().unwrap_or(default).to_string())
+}
+
+/// The entries of a block collection, `block` being its key line and
+/// children; `None` when a child line is not a `key: value` entry or the
+/// children mix mapping entries and sequence items.
+fn skim_entries(block: &str) -> Option<Vec<Skimmed<'_>>> {
+    let mut entries: Vec<Skimmed> = Vec::new();
+    let mut child_indent = None;
+    for line in lines(block).skip(1).filter(|l| !is_blank(l.text)) {
+        let level = indent(line.text);
+        let child_indent = *child_indent.get_or_insert(level);
+        if level < child_indent {
+            return None;
+        }
+        if level > child_indent {
+            let entry = parse_entry(line.text);
+            entries.last_mut()?.nested.push((line.text, entry));
+            continue;
+        }
+        let entry = parse_entry(line.text)?;
+        if entries
+            .first()
+            .is_some_and(|first| first.entry.is_item != entry.is_item)
+        {
+            return None;
+        }
+        entries.push(Skimmed {
+            line: line.text,
+            entry,
+            nested: Vec::new(),
+        });
+    }
+    Some(entries)
+}
+
+/// Read a raw scalar token the way the YAML parser would; `None` for
+/// anything beyond a single-line scalar
+fn skim_scalar(token: &str) -> Option<Value> {
+    if token.starts_with(['&', '*', '!', '|', '>', '[', '{']) {
+        return None;
+    }
+    serde_yaml::from_str(token).ok()
+}
+
+/// Whether a status belongs to the workflow vocabulary the parser understands
+fn is_builtin_status(item: &WorkflowItem) -> bool {
+    matches!(
+        item.status.as_str(),
+        "required"
+            | "optional"
+            | "recommended"
+            | "conditional"
+            | "not_started"
+            | "skipped"
+            | "complete"
+            | ""
+    ) || is_file_path(&item.status)
+        || item.output_file.as_deref() == Some(item.status.as_str())
+}
+
+/// Attach registry metadata to items with statuses the parser does not know
+pub(crate) fn tag_items(items: &mut [WorkflowItem], registry: &StatusRegistry) {
+    for item in items.iter_mut().filter(|i| !is_builtin_status(i)) {
+        item.custom_status = registry.lookup(&item.status).cloned();
+    }
+}
+
+/// Map item statuses onto the workflow vocabulary through an alias table
+pub(crate) fn normalize_items(items: &mut [WorkflowItem], aliases: &StatusAliases) {
+    for item in items {
+        match aliases.normalize(&item.status).parse() {
+            Ok(StoryStatus::Done) => {
+                item.status = text(item.output_file.as_deref().unwrap_or("complete"));
+            }
+            Ok(StoryStatus::Backlog) => item.status = text("required"),
+            _ => {}
+        }
+    }
+}
+
+/// Build workflow data from an already-parsed YAML document
+fn workflow_data_from_value(parsed: &Value) -> WorkflowData {
     // Detect format:
     // - New format: 'workflows' as object with nested status fields
     // - Flat format: 'workflow_status' as object with key-value pairs (id: status)
     // - Old format: 'workflow_status' as array of objects
-    let is_new_format = parsed
-        .get("workflows")
-        .map(|v| v.is_mapping())
-        .unwrap_or(false);
-
-    let is_flat_format = parsed
-        .get("workflow_status")
-        .map(|v| v.is_mapping())
-        .unwrap_or(false);
+    workflow_data_in_format(parsed, detect_format(parsed))
+}
 
-    let items = if is_new_format {
-        parse_new_format(&parsed)
-    } else if is_flat_format {
-        parse_flat_format(&parsed)
-    } else {
-        parse_old_format(&parsed)
+/// Build workflow data from a parsed document read as `format`, whatever
+/// layout it actually has
+pub(crate) fn workflow_data_in_format(parsed: &Value, format: WorkflowFormat) -> WorkflowData {
+    let items = match format {
+        WorkflowFormat::New => parse_new_format(parsed),
+        WorkflowFormat::Flat => parse_flat_format(parsed),
+        WorkflowFormat::Old => parse_old_format(parsed),
     };
 
     let get_str = |key: &str| -> String {
@@ -268,7 +698,7 @@ pub fn parse_workflow_status(yaml_content: &str) -> Result<WorkflowData, Workflo
             .to_string()
     };
 
-    Ok(WorkflowData {
+    WorkflowData {
         last_updated: get_str("last_updated"),
         status: get_str("status"),
         status_note: parsed
@@ -286,156 +716,1058 @@ pub fn parse_workflow_status(yaml_content: &str) -> Result<WorkflowData, Workflo
         field_type: get_str("field_type"),
         workflow_path: get_str("workflow_path"),
         items,
-    })
+        phases: Vec::new(),
+    }
 }
 
-fn escape_regex(s: &str) -> String {
-    let special_chars = [
-        '.', '*', '+', '?', '^', '$', '{', '}', '(', ')', '|', '[', ']', '\\',
-    ];
-    let mut result = String::with_capacity(s.len() * 2);
-    for c in s.chars() {
-        if special_chars.contains(&c) {
-            result.push('\\');
+/// Upper bound on the number of blocks a lenient parse will drop
+const MAX_RECOVERY_ATTEMPTS: usize = 64;
+
+/// The line a YAML error should be attributed to.
+///
+/// Errors inside flow collections report where the parser gave up, but also
+/// mention where the collection started ("while parsing a flow sequence at
+/// line N"); that earlier line is the one worth dropping.
+pub(crate) fn error_line(error: &serde_yaml::Error) -> Option<usize> {
+    let message = error.to_string();
+    flow_context_lines(&message)
+        .chain(error.location().map(|l| l.line()))
+        .min()
+}
+
+fn indent_width(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+/// Find the block of lines to drop for an error reported at `line` (1-based):
+/// the nearest non-blank line at or before it, plus its more-indented children
+/// when it opens a nested mapping.
+fn bad_block(lines: &[String], line: usize) -> Option<std::ops::Range<usize>> {
+    let last = line.saturating_sub(1).min(lines.len().checked_sub(1)?);
+    let start = (0..=last).rev().find(|&i| !lines[i].trim().is_empty())?;
+
+    // Only a key that opens a nested block owns the lines below it
+    let mut end = start + 1;
+    if lines[start].trim_end().ends_with(':') {
+        let indent = indent_width(&lines[start]);
+        while end < lines.len()
+            && (lines[end].trim().is_empty() || indent_width(&lines[end]) > indent)
+        {
+            end += 1;
+        }
+    }
+    while end > start + 1 && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+    Some(start..end)
+}
+
+/// Parse workflow status, recovering from YAML errors where possible.
+///
+/// When the document fails to parse, the offending block (the reported line
+/// and anything nested under it) is blanked out and parsing is retried. Every
+/// dropped block is reported as a `ParseIssue`, so callers can still show the
+/// entries that did parse. If recovery is impossible the data is empty and
+/// the issues explain why.
+pub fn parse_workflow_status_lenient(yaml_content: &str) -> (WorkflowData, Vec<ParseIssue>) {
+    // Resource limits are not recoverable: dropping blocks would hide the attack
+    if let Err(message) = enforce_limits(yaml_content, &ParseLimits::default()) {
+        let issue = ParseIssue {
+            line: 0,
+            message: WorkflowError::ResourceLimit(message).to_string(),
+            skipped: String::new(),
+        };
+        return (workflow_data_from_value(&Value::Null), vec![issue]);
+    }
+
+    let mut lines: Vec<String> = yaml_content.lines().map(str::to_string).collect();
+    let mut issues = Vec::new();
+
+    for _ in 0..MAX_RECOVERY_ATTEMPTS {
+        let error = match serde_yaml::from_str::<Value>(&lines.join("\n")) {
+            Ok(parsed) => return (workflow_data_from_value(&parsed), issues),
+            Err(e) => e,
+        };
+
+        let line = error_line(&error);
+        let Some(block) = line.and_then(|line| bad_block(&lines, line)) else {
+            issues.push(ParseIssue {
+                line: line.unwrap_or_default(),
+                message: error.to_string(),
+                skipped: String::new(),
+            });
+            break;
+        };
+
+        issues.push(ParseIssue {
+            line: block.start + 1,
+            message: error.to_string(),
+            skipped: lines[block.clone()].join("\n"),
+        });
+        // Blank rather than remove, so later line numbers stay accurate
+        for line in &mut lines[block] {
+            line.clear();
         }
-        result.push(c);
     }
-    result
+
+    (workflow_data_from_value(&Value::Null), issues)
 }
 
 /// Update workflow item status in YAML content
+///
+/// Items are located by their YAML key token, so quoted keys and keys
+/// containing spaces or colons are found exactly as the parser reads them.
+/// Only the status value is rewritten; every other byte is preserved. The
+/// layout is sniffed from the lines ([`sniff_format`]), so the document is
+/// only parsed when that cannot tell.
 pub fn update_workflow_status(
     content: &str,
     item_id: &str,
     new_status: &str,
 ) -> Result<String, WorkflowError> {
-    let parsed: Value =
-        serde_yaml::from_str(content).map_err(|e| WorkflowError::ParseError(e.to_string()))?;
-
-    let is_new_format = parsed
-        .get("workflows")
-        .map(|v| v.is_mapping())
-        .unwrap_or(false);
+    update_workflow_status_with_format(content, format_of(content)?, item_id, new_status)
+}
 
-    let is_flat_format = parsed
-        .get("workflow_status")
-        .map(|v| v.is_mapping())
-        .unwrap_or(false);
+/// [`update_workflow_status`] for a document whose layout is already known,
+/// e.g. from [`sniff_format`] or a previous parse. The document itself is
+/// not parsed at all.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(bytes = content.len(), %format, item = item_id)
+    )
+)]
+pub fn update_workflow_status_with_format(
+    content: &str,
+    format: WorkflowFormat,
+    item_id: &str,
+    new_status: &str,
+) -> Result<String, WorkflowError> {
+    // A line break would splice new structure into the document
+    if new_status.chars().any(char::is_control) {
+        return Err(WorkflowError::InvalidStatus(
+            new_status.escape_debug().to_string(),
+        ));
+    }
 
-    if is_new_format {
+    let (span, replacement) = if format == WorkflowFormat::New {
         // New format: workflows object with nested status
         // Pattern: "  itemId:\n    status: value"
-        let pattern = format!(
-            r"(?m)(^[ \t]*{}:\s*\n[ \t]*status:\s*)\S+",
-            escape_regex(item_id)
-        );
-        let re = Regex::new(&pattern).map_err(|e| WorkflowError::UpdateError(e.to_string()))?;
-
-        if !re.is_match(content) {
-            return Err(WorkflowError::ItemNotFound(item_id.to_string()));
-        }
-
-        Ok(re
-            .replace(content, format!("${{1}}{}", new_status))
-            .to_string())
-    } else if is_flat_format {
+        let span = find_value(content, &["workflows", item_id, "status"]);
+        let original = span.clone().map(|r| &content[r]).unwrap_or_default();
+        (
+            span,
+            format_scalar(original, new_status, needs_quotes(new_status)),
+        )
+    } else if format == WorkflowFormat::Flat {
         // Flat format: workflow_status object with key-value pairs
         // Pattern: "  itemId: value" (value can be quoted or unquoted)
-        let pattern = format!(
-            r#"(?m)(^[ \t]*{}:\s*)["']?[^\n"']+["']?"#,
-            escape_regex(item_id)
-        );
-        let re = Regex::new(&pattern).map_err(|e| WorkflowError::UpdateError(e.to_string()))?;
-
-        if !re.is_match(content) {
-            return Err(WorkflowError::ItemNotFound(item_id.to_string()));
-        }
+        // Only the scalar is replaced: trailing comments and the original
+        // quoting style survive the edit.
+        let span = find_value(content, &["workflow_status", item_id]);
 
         // Quote the new status if it contains special characters
-        let quoted_status = if new_status.contains('/') || new_status.contains(':') {
-            format!("\"{}\"", new_status)
-        } else {
-            new_status.to_string()
-        };
-
-        Ok(re
-            .replace(content, format!("${{1}}{}", quoted_status))
-            .to_string())
+        let quote =
+            new_status.contains('/') || new_status.contains(':') || needs_quotes(new_status);
+        let original = span.clone().map(|r| &content[r]).unwrap_or_default();
+        (span, format_scalar(original, new_status, quote))
     } else {
         // Old format: array with id and status fields
         // Pattern: "- id: itemId" followed by "status: value"
-        let pattern = format!(
-            r#"(?m)(- id: ["']?{}["']?[\s\S]*?status:\s*)["']?[^\s"']+["']?"#,
-            escape_regex(item_id)
-        );
-        let re = Regex::new(&pattern).map_err(|e| WorkflowError::UpdateError(e.to_string()))?;
+        let span = find_item_value(content, &["workflow_status"], "id", item_id, "status");
+        (span, format_scalar("\"\"", new_status, true))
+    };
 
-        if !re.is_match(content) {
-            return Err(WorkflowError::ItemNotFound(item_id.to_string()));
-        }
+    // An empty span means the key opens a block rather than holding a value
+    let span = span
+        .filter(|span| !span.is_empty())
+        .ok_or_else(|| WorkflowError::ItemNotFound(item_id.to_string()))?;
 
-        Ok(re
-            .replace(content, format!("${{1}}\"{}\"", new_status))
-            .to_string())
-    }
+    Ok(splice(content, span, &replacement))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Apply several `(item_id, new_status)` updates in one pass.
+///
+/// The layout is sniffed once and each update is applied to the result of
+/// the previous one. The batch is all-or-nothing: the first failing update
+/// is returned as the error and no content is produced.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(bytes = content.len(), updates = updates.len())
+    )
+)]
+pub fn update_workflow_statuses(
+    content: &str,
+    updates: &[(&str, &str)],
+) -> Result<String, WorkflowError> {
+    let format = format_of(content)?;
+    updates
+        .iter()
+        .try_fold(content.to_string(), |current, (item_id, new_status)| {
+            update_workflow_status_with_format(&current, format, item_id, new_status)
+        })
+}
 
-    const NEW_FORMAT_YAML: &str = r#"
-last_updated: 2025-12-01
-status: active
-status_note: On track
-project: Demo Project
-project_type: greenfield
-selected_track: web
-field_type: default
-workflow_path: docs/workflow.yaml
-workflows:
-  brainstorm:
-    status: complete
-    output_file: docs/brainstorm.md
-  prd:
-    status: not_started
-    notes: Needs review
-  architecture:
-    status: skipped
-  sprint-planning:
-    status: complete
-    output_file: _bmad-output/sprint-planning.md
-"#;
+/// Status of an item exactly as written in the file
+pub(crate) fn raw_status(parsed: &Value, item_id: &str) -> Option<String> {
+    let status = match detect_format(parsed) {
+        WorkflowFormat::New => parsed.get("workflows")?.get(item_id)?.get("status"),
+        WorkflowFormat::Flat => parsed.get("workflow_status")?.get(item_id),
+        WorkflowFormat::Old => parsed
+            .get("workflow_status")?
+            .as_sequence()?
+            .iter()
+            .find(|item| item.get("id").and_then(|v| v.as_str()) == Some(item_id))?
+            .get("status"),
+    };
+    Some(
+        status
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
 
-    const FLAT_FORMAT_YAML: &str = r#"
-project: Demo Project
-workflow_status:
-  brainstorm: required
-  prd: docs/prd.md
-  test-design: optional
-"#;
+/// Update an item's status only if it still reads `expected_current`.
+///
+/// Guards a read-modify-write cycle against the file changing on disk in
+/// between: when the status as written differs from `expected_current`, the
+/// update fails with [`WorkflowError::ConflictError`] carrying the actual
+/// value and `content` is left alone.
+pub fn update_workflow_status_if_unchanged(
+    content: &str,
+    item_id: &str,
+    new_status: &str,
+    expected_current: &str,
+) -> Result<String, WorkflowError> {
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| WorkflowError::ParseError(e.to_string()))?;
+    let actual = raw_status(&parsed, item_id)
+        .ok_or_else(|| WorkflowError::ItemNotFound(item_id.to_string()))?;
+    if actual != expected_current {
+        return Err(WorkflowError::ConflictError { actual });
+    }
+    update_workflow_status(content, item_id, new_status)
+}
 
-    const OLD_FORMAT_YAML: &str = r#"
-project: Demo Project
-workflow_status:
-  - id: brainstorm
-    phase: 0
-    status: required
-    agent: analyst
-    command: brainstorm
-    note: Seed ideas
-  - id: prd
-    phase: 1
-    status: complete
-    agent: pm
-    command: prd
-"#;
+/// Mark an item complete, recording where its output was written.
+///
+/// In the new format this sets `status: complete` and writes (or inserts)
+/// the item's `output_file:` line. In the flat and old formats, where a
+/// completed item's status *is* its output path, the status is set to the
+/// path, or to `complete` when there is none.
+pub fn complete_item(
+    content: &str,
+    item_id: &str,
+    output_file: Option<&str>,
+) -> Result<String, WorkflowError> {
+    if let Some(path) = output_file
+        && path.chars().any(char::is_control)
+    {
+        return Err(WorkflowError::UpdateError(format!(
+            "Invalid output file: {}",
+            path.escape_debug()
+        )));
+    }
 
-    // =========================================================================
-    // Parsing Tests - New Format
-    // =========================================================================
+    if !is_new_format(content)? {
+        return update_workflow_status(content, item_id, output_file.unwrap_or("complete"));
+    }
 
-    #[test]
+    let updated = update_workflow_status(content, item_id, "complete")?;
+    let Some(path) = output_file else {
+        return Ok(updated);
+    };
+
+    let item = ["workflows", item_id];
+    let mut field = item.to_vec();
+    field.push("output_file");
+    let original = find_value(&updated, &field)
+        .map(|span| updated[span].to_string())
+        .unwrap_or_default();
+    let value = format_scalar(&original, path, needs_quotes(path));
+    set_field(&updated, &item, "output_file", &value)
+        .ok_or_else(|| WorkflowError::UpdateError(format!("Cannot set output_file of {}", item_id)))
+}
+
+/// Reopen a completed item: the counterpart of [`complete_item`].
+///
+/// In the new format the status returns to `not_started` and the
+/// `output_file:` line is removed; in the flat and old formats the status
+/// becomes `required`.
+pub fn reopen_item(content: &str, item_id: &str) -> Result<String, WorkflowError> {
+    if !is_new_format(content)? {
+        return update_workflow_status(content, item_id, "required");
+    }
+
+    let updated = update_workflow_status(content, item_id, "not_started")?;
+    Ok(remove_field(&updated, &["workflows", item_id, "output_file"]).unwrap_or(updated))
+}
+
+/// A writable field of a workflow item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkflowField {
+    /// Free-form notes (`notes:` in the new format, `note:` in the old one)
+    Notes,
+    /// The item's output document path
+    OutputFile,
+    /// Any other key under the item
+    Custom(String),
+}
+
+/// The key a field is written under for an item in the given format
+fn field_key<'a>(
+    content: &str,
+    item_id: &str,
+    field: &'a WorkflowField,
+    format: WorkflowFormat,
+) -> &'a str {
+    match (field, format) {
+        // The parser accepts either spelling; keep whichever is present
+        (WorkflowField::Notes, WorkflowFormat::New)
+            if find_value(content, &["workflows", item_id, "note"]).is_some()
+                && find_value(content, &["workflows", item_id, "notes"]).is_none() =>
+        {
+            "note"
+        }
+        (WorkflowField::Notes, WorkflowFormat::New) => "notes",
+        (WorkflowField::Notes, _) => "note",
+        (WorkflowField::OutputFile, _) => "output_file",
+        (WorkflowField::Custom(key), _) => key.as_str(),
+    }
+}
+
+/// Value of an item's field as written, or `None` when it is absent.
+/// Fails if the item itself does not exist.
+pub(crate) fn raw_field(
+    content: &str,
+    item_id: &str,
+    field: &WorkflowField,
+) -> Result<Option<String>, WorkflowError> {
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| WorkflowError::ParseError(e.to_string()))?;
+    let format = detect_format(&parsed);
+    let key = field_key(content, item_id, field, format);
+    let item = match format {
+        WorkflowFormat::New => parsed.get("workflows").and_then(|v| v.get(item_id)),
+        WorkflowFormat::Flat => parsed.get("workflow_status").and_then(|v| v.get(item_id)),
+        WorkflowFormat::Old => parsed
+            .get("workflow_status")
+            .and_then(|v| v.as_sequence())
+            .and_then(|items| {
+                items
+                    .iter()
+                    .find(|item| item.get("id").and_then(|v| v.as_str()) == Some(item_id))
+            }),
+    }
+    .ok_or_else(|| WorkflowError::ItemNotFound(item_id.to_string()))?;
+    Ok(match item.get(key) {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        Some(Value::Bool(b)) => Some(b.to_string()),
+        _ => None,
+    })
+}
+
+/// Remove a field of a workflow item: the counterpart of
+/// [`update_workflow_field`].
+///
+/// Removing a field the item does not have leaves `content` unchanged.
+pub fn remove_workflow_field(
+    content: &str,
+    item_id: &str,
+    field: WorkflowField,
+) -> Result<String, WorkflowError> {
+    let format = format_of(content)?;
+    let key = field_key(content, item_id, &field, format);
+    match format {
+        WorkflowFormat::New => {
+            find_value(content, &["workflows", item_id])
+                .ok_or_else(|| WorkflowError::ItemNotFound(item_id.to_string()))?;
+            Ok(remove_field(content, &["workflows", item_id, key])
+                .unwrap_or_else(|| content.to_string()))
+        }
+        WorkflowFormat::Old => {
+            let sequence = ["workflow_status"];
+            find_item_value(content, &sequence, "id", item_id, "id")
+                .ok_or_else(|| WorkflowError::ItemNotFound(item_id.to_string()))?;
+            if key == "id" {
+                return Err(WorkflowError::UpdateError(format!(
+                    "Cannot remove the id of {}",
+                    item_id
+                )));
+            }
+            Ok(remove_item_field(content, &sequence, "id", item_id, key)
+                .unwrap_or_else(|| content.to_string()))
+        }
+        WorkflowFormat::Flat if find_value(content, &["workflow_status", item_id]).is_some() => {
+            Err(WorkflowError::UpdateError(format!(
+                "Cannot set fields of {}",
+                item_id
+            )))
+        }
+        WorkflowFormat::Flat => Err(WorkflowError::ItemNotFound(item_id.to_string())),
+    }
+}
+
+/// Set a field of a workflow item, inserting the key if it is absent.
+///
+/// Supported for the new `workflows:` format and the old sequence format;
+/// flat-format items are bare `id: status` pairs with nowhere to put fields.
+/// A `Custom("status")` field is handed to [`update_workflow_status`], which
+/// validates the status and follows each layout's conventions.
+pub fn update_workflow_field(
+    content: &str,
+    item_id: &str,
+    field: WorkflowField,
+    value: &str,
+) -> Result<String, WorkflowError> {
+    if field == WorkflowField::Custom("status".to_string()) {
+        return update_workflow_status(content, item_id, value);
+    }
+    if value.chars().any(char::is_control) {
+        return Err(WorkflowError::UpdateError(format!(
+            "Invalid field value: {}",
+            value.escape_debug()
+        )));
+    }
+    if let WorkflowField::Custom(key) = &field
+        && (key.trim().is_empty() || key.chars().any(char::is_control))
+    {
+        return Err(WorkflowError::UpdateError(format!(
+            "Invalid field name: {}",
+            key.escape_debug()
+        )));
+    }
+
+    let format = format_of(content)?;
+    let not_found = || WorkflowError::ItemNotFound(item_id.to_string());
+    let cannot_set = || WorkflowError::UpdateError(format!("Cannot set fields of {}", item_id));
+
+    if format == WorkflowFormat::New {
+        let item = ["workflows", item_id];
+        find_value(content, &item).ok_or_else(not_found)?;
+
+        let key = field_key(content, item_id, &field, WorkflowFormat::New);
+        let original = find_value(content, &["workflows", item_id, key])
+            .map(|span| &content[span])
+            .unwrap_or_default();
+        let formatted = format_scalar(original, value, needs_quotes(value));
+        set_field(content, &item, key, &formatted).ok_or_else(cannot_set)
+    } else if format == WorkflowFormat::Old {
+        let key = field_key(content, item_id, &field, WorkflowFormat::Old);
+        let sequence = ["workflow_status"];
+        let original = find_item_value(content, &sequence, "id", item_id, key)
+            .map(|span| &content[span])
+            .unwrap_or_default();
+        let formatted = format_scalar(original, value, needs_quotes(value));
+        set_item_field(content, &sequence, "id", item_id, key, &formatted).ok_or_else(not_found)
+    } else if find_value(content, &["workflow_status", item_id]).is_some() {
+        Err(cannot_set())
+    } else {
+        Err(not_found())
+    }
+}
+
+/// Whether the document uses the new `workflows:` mapping format
+pub(crate) fn is_new_format(content: &str) -> Result<bool, WorkflowError> {
+    Ok(format_of(content)? == WorkflowFormat::New)
+}
+
+/// A field whose value did not survive a serialize/re-parse round trip
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LossyField {
+    /// Dotted path of the field, e.g. `items.prd.note`, or `comment on
+    /// line N` for a comment
+    pub path: String,
+    /// Value in the parsed original
+    pub original: Option<String>,
+    /// Value after re-serializing and re-parsing
+    pub roundtripped: Option<String>,
+}
+
+/// Why a document failed [`roundtrip_check`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundtripReport {
+    /// Parse failure of the original or the re-serialized document
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Fields that changed during the round trip
+    pub lossy_fields: Vec<LossyField>,
+}
+
+impl std::fmt::Display for RoundtripReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(error) = &self.error {
+            return write!(f, "Round trip failed: {}", error);
+        }
+        let paths: Vec<&str> = self.lossy_fields.iter().map(|l| l.path.as_str()).collect();
+        write!(f, "Round trip lost fields: {}", paths.join(", "))
+    }
+}
+
+impl std::error::Error for RoundtripReport {}
+
+/// Workflow document layouts understood by the parser
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WorkflowFormat {
+    /// `workflows:` mapping of items with nested fields
+    New,
+    /// `workflow_status:` mapping of `id: status` pairs
+    Flat,
+    /// `workflow_status:` sequence of items with an `id` field
+    Old,
+}
+
+impl std::fmt::Display for WorkflowFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WorkflowFormat::New => "new",
+            WorkflowFormat::Flat => "flat",
+            WorkflowFormat::Old => "old",
+        })
+    }
+}
+
+/// How a top-level collection is written, as far as its lines tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Mapping,
+    Sequence,
+    /// Absent, empty or a scalar
+    Other,
+}
+
+/// Kind of the top-level value under `key`, read from the line after it;
+/// `None` for inline values (flow collections, anchors, tags).
+fn sniff_block(content: &str, key: &str) -> Option<BlockKind> {
+    let Some(block) = find_block(content, &[key]) else {
+        return Some(BlockKind::Other);
+    };
+    if !content[find_value(content, &[key])?].is_empty() {
+        return None;
+    }
+    let key_indent = indent(&content[block.start..]);
+    let child = lines(&content[block.start..])
+        .skip(1)
+        .find(|line| !is_blank(line.text));
+    let Some(line) = child else {
+        return Some(BlockKind::Other);
+    };
+    let trimmed = line.text.trim_start();
+    let level = indent(line.text);
+    if trimmed == "-" || trimmed.starts_with("- ") {
+        // YAML lets a sequence sit at its key's own indentation
+        return Some(BlockKind::Sequence);
+    }
+    if level <= key_indent {
+        return Some(BlockKind::Other);
+    }
+    // Anything but a plain entry (complex keys, multi-line scalars) is left
+    // to the parser
+    parse_entry(line.text).map(|_| BlockKind::Mapping)
+}
+
+/// Tell the document layout from its lines, without parsing it.
+///
+/// Returns `None` when block structure alone cannot tell, such as an item
+/// collection written in flow style; a full parse is needed then. When it
+/// returns a layout, it is the one the parser would pick.
+pub fn sniff_format(content: &str) -> Option<WorkflowFormat> {
+    if sniff_block(content, "workflows")? == BlockKind::Mapping {
+        return Some(WorkflowFormat::New);
+    }
+    Some(match sniff_block(content, "workflow_status")? {
+        BlockKind::Mapping => WorkflowFormat::Flat,
+        BlockKind::Sequence | BlockKind::Other => WorkflowFormat::Old,
+    })
+}
+
+/// Layout of a document, sniffed when possible and parsed otherwise
+pub(crate) fn format_of(content: &str) -> Result<WorkflowFormat, WorkflowError> {
+    if let Some(format) = sniff_format(content) {
+        return Ok(format);
+    }
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| WorkflowError::ParseError(e.to_string()))?;
+    Ok(detect_format(&parsed))
+}
+
+fn detect_format(parsed: &Value) -> WorkflowFormat {
+    if parsed.get("workflows").is_some_and(|v| v.is_mapping()) {
+        WorkflowFormat::New
+    } else if parsed
+        .get("workflow_status")
+        .is_some_and(|v| v.is_mapping())
+    {
+        WorkflowFormat::Flat
+    } else {
+        WorkflowFormat::Old
+    }
+}
+
+fn string_sequence(values: &[String]) -> Value {
+    values.iter().map(|v| Value::from(v.as_str())).collect()
+}
+
+/// Serialize workflow data back to YAML in the given layout
+fn serialize_workflow(data: &WorkflowData, format: WorkflowFormat) -> Result<String, String> {
+    let mut root = Mapping::new();
+    let header = [
+        ("last_updated", &data.last_updated),
+        ("status", &data.status),
+        ("project", &data.project),
+        ("project_type", &data.project_type),
+        ("selected_track", &data.selected_track),
+        ("field_type", &data.field_type),
+        ("workflow_path", &data.workflow_path),
+    ];
+    for (key, value) in header {
+        if !value.is_empty() {
+            root.insert(key.into(), value.as_str().into());
+        }
+    }
+    if let Some(note) = &data.status_note {
+        root.insert("status_note".into(), note.as_str().into());
+    }
+
+    match format {
+        WorkflowFormat::New => {
+            let mut workflows = Mapping::new();
+            for item in &data.items {
+                let mut fields = Mapping::new();
+                // The parser reports completed items by their output file
+                let status = if item.output_file.as_deref() == Some(item.status.as_str()) {
+                    "complete"
+                } else {
+                    item.status.as_str()
+                };
+                fields.insert("status".into(), status.into());
+                if let Some(output_file) = &item.output_file {
+                    fields.insert("output_file".into(), output_file.as_str().into());
+                }
+                if let Some(note) = &item.note {
+                    fields.insert("notes".into(), note.as_str().into());
+                }
+                if let Some(due) = &item.due {
+                    fields.insert("due".into(), due.as_str().into());
+                }
+                let lists = [
+                    ("depends_on", &item.depends_on),
+                    ("tracks", &item.tracks),
+                    ("tags", &item.tags),
+                ];
+                for (key, values) in lists {
+                    if !values.is_empty() {
+                        fields.insert(key.into(), string_sequence(values));
+                    }
+                }
+                workflows.insert(item.id.as_str().into(), fields.into());
+            }
+            root.insert("workflows".into(), workflows.into());
+        }
+        WorkflowFormat::Flat => {
+            let mut statuses = Mapping::new();
+            for item in &data.items {
+                statuses.insert(item.id.as_str().into(), item.status.as_str().into());
+            }
+            root.insert("workflow_status".into(), statuses.into());
+        }
+        WorkflowFormat::Old => {
+            let mut sequence = Vec::new();
+            for item in &data.items {
+                let mut fields = Mapping::new();
+                fields.insert("id".into(), item.id.as_str().into());
+                if let Phase::Number(n) = item.phase {
+                    fields.insert("phase".into(), n.into());
+                }
+                fields.insert("status".into(), item.status.as_str().into());
+                let optional = [
+                    ("agent", item.agent.as_deref()),
+                    ("command", item.command.as_deref()),
+                    ("note", item.note.as_deref()),
+                    ("due", item.due.as_deref()),
+                ];
+                for (key, value) in optional {
+                    if let Some(value) = value {
+                        fields.insert(key.into(), value.into());
+                    }
+                }
+                let lists = [
+                    ("depends_on", &item.depends_on),
+                    ("tracks", &item.tracks),
+                    ("tags", &item.tags),
+                ];
+                for (key, values) in lists {
+                    if !values.is_empty() {
+                        fields.insert(key.into(), string_sequence(values));
+                    }
+                }
+                sequence.push(Value::Mapping(fields));
+            }
+            root.insert("workflow_status".into(), sequence.into());
+        }
+    }
+
+    serde_yaml::to_string(&Value::Mapping(root)).map_err(|e| e.to_string())
+}
+
+/// Field-by-field differences between two parses of the same document
+fn lossy_fields(original: &WorkflowData, roundtripped: &WorkflowData) -> Vec<LossyField> {
+    let mut lossy = Vec::new();
+    let mut check = |path: String, a: Option<String>, b: Option<String>| {
+        if a != b {
+            lossy.push(LossyField {
+                path,
+                original: a,
+                roundtripped: b,
+            });
+        }
+    };
+
+    let header = [
+        (
+            "last_updated",
+            &original.last_updated,
+            &roundtripped.last_updated,
+        ),
+        ("status", &original.status, &roundtripped.status),
+        ("project", &original.project, &roundtripped.project),
+        (
+            "project_type",
+            &original.project_type,
+            &roundtripped.project_type,
+        ),
+        (
+            "selected_track",
+            &original.selected_track,
+            &roundtripped.selected_track,
+        ),
+        ("field_type", &original.field_type, &roundtripped.field_type),
+        (
+            "workflow_path",
+            &original.workflow_path,
+            &roundtripped.workflow_path,
+        ),
+    ];
+    for (key, a, b) in header {
+        check(key.to_string(), Some(a.clone()), Some(b.clone()));
+    }
+    check(
+        "status_note".to_string(),
+        original.status_note.clone(),
+        roundtripped.status_note.clone(),
+    );
+
+    if original.items.len() != roundtripped.items.len() {
+        check(
+            "items".to_string(),
+            Some(format!("{} items", original.items.len())),
+            Some(format!("{} items", roundtripped.items.len())),
+        );
+    }
+    for (a, b) in original.items.iter().zip(&roundtripped.items) {
+        let field = |name: &str| format!("items.{}.{}", a.id, name);
+        check(field("id"), Some(a.id.clone()), Some(b.id.clone()));
+        check(
+            field("phase"),
+            Some(format!("{:?}", a.phase)),
+            Some(format!("{:?}", b.phase)),
+        );
+        check(
+            field("status"),
+            Some(a.status.to_string()),
+            Some(b.status.to_string()),
+        );
+        check(
+            field("agent"),
+            a.agent.as_deref().map(str::to_string),
+            b.agent.as_deref().map(str::to_string),
+        );
+        check(field("command"), a.command.clone(), b.command.clone());
+        check(field("note"), a.note.clone(), b.note.clone());
+        check(
+            field("output_file"),
+            a.output_file.clone(),
+            b.output_file.clone(),
+        );
+        check(
+            field("depends_on"),
+            Some(a.depends_on.join(", ")),
+            Some(b.depends_on.join(", ")),
+        );
+        check(
+            field("tracks"),
+            Some(a.tracks.join(", ")),
+            Some(b.tracks.join(", ")),
+        );
+        check(field("due"), a.due.clone(), b.due.clone());
+        check(
+            field("tags"),
+            Some(a.tags.join(", ")),
+            Some(b.tags.join(", ")),
+        );
+    }
+
+    lossy
+}
+
+/// Top-level keys the parser reads
+const HEADER_KEYS: &[&str] = &[
+    "last_updated",
+    "status",
+    "status_note",
+    "project",
+    "project_name",
+    "project_type",
+    "selected_track",
+    "field_type",
+    "workflow_path",
+    "workflows",
+    "workflow_status",
+];
+
+/// Item fields the parser reads, in any layout
+const ITEM_KEYS: &[&str] = &[
+    "id",
+    "phase",
+    "status",
+    "agent",
+    "command",
+    "note",
+    "notes",
+    "output_file",
+    "depends_on",
+    "tracks",
+    "tags",
+    "due",
+];
+
+/// Raw field mappings of each item by ID; flat items have none
+fn raw_items(parsed: &Value) -> BTreeMap<String, &Mapping> {
+    match detect_format(parsed) {
+        WorkflowFormat::New => parsed
+            .get("workflows")
+            .and_then(Value::as_mapping)
+            .into_iter()
+            .flatten()
+            .filter_map(|(id, fields)| Some((yaml_key(id), fields.as_mapping()?)))
+            .collect(),
+        WorkflowFormat::Flat => BTreeMap::new(),
+        WorkflowFormat::Old => parsed
+            .get("workflow_status")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter_map(|item| {
+                let id = item.get("id")?.as_str()?.to_string();
+                Some((id, item.as_mapping()?))
+            })
+            .collect(),
+    }
+}
+
+/// A mapping key or scalar as written, other values as inline YAML
+fn yaml_key(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim_end()
+            .to_string(),
+    }
+}
+
+/// Keys of `original` the parser does not read that are missing from
+/// `migrated`, and every comment of `content`, which regenerating the
+/// document never keeps
+fn dropped_keys(content: &str, original: &Value, migrated: &Value) -> Vec<LossyField> {
+    let mut lossy = Vec::new();
+    let mut dropped = |path: String, value: &Value| {
+        lossy.push(LossyField {
+            path,
+            original: Some(yaml_key(value)),
+            roundtripped: None,
+        });
+    };
+
+    for (key, value) in original.as_mapping().into_iter().flatten() {
+        let name = yaml_key(key);
+        if !HEADER_KEYS.contains(&name.as_str()) && migrated.get(key).is_none() {
+            dropped(name, value);
+        }
+    }
+    let kept = raw_items(migrated);
+    for (id, fields) in raw_items(original) {
+        for (key, value) in fields {
+            let name = yaml_key(key);
+            let unknown = !ITEM_KEYS.contains(&name.as_str());
+            if unknown && !kept.get(&id).is_some_and(|kept| kept.contains_key(key)) {
+                dropped(format!("items.{}.{}", id, name), value);
+            }
+        }
+    }
+
+    for (line, comment) in comments(content) {
+        lossy.push(LossyField {
+            path: format!("comment on line {}", line),
+            original: Some(comment.to_string()),
+            roundtripped: None,
+        });
+    }
+    lossy
+}
+
+/// Verify that a document survives parse → serialize → re-parse unchanged.
+///
+/// The document is re-serialized in its own layout (new, flat or old), so a
+/// passing check means the parsed model holds everything needed to write the
+/// file back. On failure the report lists each field that changed.
+pub fn roundtrip_check(content: &str) -> Result<(), RoundtripReport> {
+    let failed = |error: String| RoundtripReport {
+        error: Some(error),
+        lossy_fields: Vec::new(),
+    };
+
+    let parsed: Value = serde_yaml::from_str(content).map_err(|e| failed(e.to_string()))?;
+    let original = workflow_data_from_value(&parsed);
+
+    let serialized = serialize_workflow(&original, detect_format(&parsed)).map_err(&failed)?;
+    let reparsed: Value = serde_yaml::from_str(&serialized)
+        .map_err(|e| failed(format!("re-serialized document is invalid: {}", e)))?;
+    let roundtripped = workflow_data_from_value(&reparsed);
+
+    let lossy = lossy_fields(&original, &roundtripped);
+    if lossy.is_empty() {
+        Ok(())
+    } else {
+        Err(RoundtripReport {
+            error: None,
+            lossy_fields: lossy,
+        })
+    }
+}
+
+/// A workflow document rewritten in another layout by [`migrate_workflow`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Migration {
+    pub from: WorkflowFormat,
+    pub to: WorkflowFormat,
+    /// The document in the target layout; the input itself when it is
+    /// already in that layout
+    pub content: String,
+    /// Fields the target layout has no place for, such as agents in the
+    /// flat layout
+    pub lossy_fields: Vec<LossyField>,
+}
+
+impl Migration {
+    /// Whether the document was in another layout
+    pub fn changed(&self) -> bool {
+        self.from != self.to
+    }
+}
+
+/// Rewrite a workflow document in the `to` layout.
+///
+/// The document is regenerated from its parsed model, as in
+/// [`roundtrip_check`], so comments and unknown keys are not carried over.
+/// They are listed in `lossy_fields` along with fields the target layout
+/// cannot hold, rather than refused; whether to write the result is up to
+/// the caller.
+pub fn migrate_workflow(content: &str, to: WorkflowFormat) -> Result<Migration, WorkflowError> {
+    let parsed: Value =
+        serde_yaml::from_str(content).map_err(|e| WorkflowError::ParseError(e.to_string()))?;
+    let from = detect_format(&parsed);
+    if from == to {
+        return Ok(Migration {
+            from,
+            to,
+            content: content.to_string(),
+            lossy_fields: Vec::new(),
+        });
+    }
+
+    let original = workflow_data_from_value(&parsed);
+    let migrated = serialize_workflow(&original, to).map_err(WorkflowError::UpdateError)?;
+    let reparsed: Value = serde_yaml::from_str(&migrated)
+        .map_err(|e| WorkflowError::UpdateError(format!("migrated document is invalid: {}", e)))?;
+    let mut lossy = lossy_fields(&original, &workflow_data_from_value(&reparsed));
+    lossy.extend(dropped_keys(content, &parsed, &reparsed));
+    Ok(Migration {
+        from,
+        to,
+        lossy_fields: lossy,
+        content: migrated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ItemState, StatusClass, StatusInfo};
+
+    const NEW_FORMAT_YAML: &str = r#"
+last_updated: 2025-12-01
+status: active
+status_note: On track
+project: Demo Project
+project_type: greenfield
+selected_track: web
+field_type: default
+workflow_path: docs/workflow.yaml
+workflows:
+  brainstorm:
+    status: complete
+    output_file: docs/brainstorm.md
+  prd:
+    status: not_started
+    notes: Needs review
+  architecture:
+    status: skipped
+  sprint-planning:
+    status: complete
+    output_file: _bmad-output/sprint-planning.md
+"#;
+
+    const FLAT_FORMAT_YAML: &str = r#"
+project: Demo Project
+workflow_status:
+  brainstorm: required
+  prd: docs/prd.md
+  test-design: optional
+"#;
+
+    const OLD_FORMAT_YAML: &str = r#"
+project: Demo Project
+workflow_status:
+  - id: brainstorm
+    phase: 0
+    status: required
+    agent: analyst
+    command: brainstorm
+    note: Seed ideas
+  - id: prd
+    phase: 1
+    status: complete
+    agent: pm
+    command: prd
+"#;
+
+    // =========================================================================
+    // Parsing Tests - New Format
+    // =========================================================================
+
+    #[test]
     fn test_parse_new_format() {
         let result = parse_workflow_status(NEW_FORMAT_YAML).expect("Should parse new format YAML");
 
@@ -483,201 +1815,1206 @@ workflow_status:
         let result = parse_workflow_status(NEW_FORMAT_YAML).expect("Should parse");
 
         let brainstorm = result.items.iter().find(|i| i.id == "brainstorm").unwrap();
-        assert_eq!(brainstorm.agent, Some("analyst".to_string()));
+        assert_eq!(brainstorm.agent.as_deref(), Some("analyst"));
 
         let prd = result.items.iter().find(|i| i.id == "prd").unwrap();
-        assert_eq!(prd.agent, Some("pm".to_string()));
+        assert_eq!(prd.agent.as_deref(), Some("pm"));
 
         let architecture = result
             .items
             .iter()
             .find(|i| i.id == "architecture")
             .unwrap();
-        assert_eq!(architecture.agent, Some("architect".to_string()));
+        assert_eq!(architecture.agent.as_deref(), Some("architect"));
+    }
+
+    #[test]
+    fn test_new_format_inferred_commands() {
+        let result = parse_workflow_status(NEW_FORMAT_YAML).expect("Should parse");
+
+        for item in &result.items {
+            assert_eq!(item.command, Some(item.id.clone()));
+        }
+    }
+
+    // =========================================================================
+    // Parsing Tests - Flat Format
+    // =========================================================================
+
+    #[test]
+    fn test_parse_flat_format() {
+        let result =
+            parse_workflow_status(FLAT_FORMAT_YAML).expect("Should parse flat format YAML");
+
+        assert_eq!(result.project, "Demo Project");
+        assert_eq!(result.items.len(), 3);
+
+        let prd = result
+            .items
+            .iter()
+            .find(|i| i.id == "prd")
+            .expect("Should find prd");
+        assert_eq!(prd.status, "docs/prd.md");
+        assert_eq!(prd.output_file, Some("docs/prd.md".to_string()));
+    }
+
+    #[test]
+    fn test_flat_format_file_path_detection() {
+        let yaml = r#"
+project: File Path Test
+workflow_status:
+  item1: docs/output.md
+  item2: path/to/file.yaml
+  item3: output.json
+  item4: completed
+  item5: required
+"#;
+        let result = parse_workflow_status(yaml).expect("Should parse");
+
+        let item1 = result.items.iter().find(|i| i.id == "item1").unwrap();
+        assert_eq!(item1.output_file, Some("docs/output.md".to_string()));
+
+        let item2 = result.items.iter().find(|i| i.id == "item2").unwrap();
+        assert_eq!(item2.output_file, Some("path/to/file.yaml".to_string()));
+
+        let item3 = result.items.iter().find(|i| i.id == "item3").unwrap();
+        assert_eq!(item3.output_file, Some("output.json".to_string()));
+
+        let item4 = result.items.iter().find(|i| i.id == "item4").unwrap();
+        assert_eq!(item4.output_file, None);
+
+        let item5 = result.items.iter().find(|i| i.id == "item5").unwrap();
+        assert_eq!(item5.output_file, None);
+    }
+
+    // =========================================================================
+    // Parsing Tests - Old Format
+    // =========================================================================
+
+    #[test]
+    fn test_parse_old_format() {
+        let result = parse_workflow_status(OLD_FORMAT_YAML).expect("Should parse old format YAML");
+
+        assert_eq!(result.project, "Demo Project");
+        assert_eq!(result.items.len(), 2);
+
+        let brainstorm = result
+            .items
+            .iter()
+            .find(|i| i.id == "brainstorm")
+            .expect("Should find brainstorm");
+        assert_eq!(brainstorm.phase, Phase::Number(0));
+        assert_eq!(brainstorm.agent.as_deref(), Some("analyst"));
+        assert_eq!(brainstorm.note, Some("Seed ideas".to_string()));
+    }
+
+    #[test]
+    fn test_old_format_explicit_phase() {
+        let yaml = r#"
+project: Phase Test
+workflow_status:
+  - id: custom-item
+    phase: 5
+    status: required
+"#;
+        let result = parse_workflow_status(yaml).expect("Should parse");
+        let item = &result.items[0];
+        assert_eq!(item.phase, Phase::Number(5));
+    }
+
+    #[test]
+    fn test_old_format_missing_phase_inferred() {
+        let yaml = r#"
+project: Infer Phase Test
+workflow_status:
+  - id: brainstorm
+    status: required
+"#;
+        let result = parse_workflow_status(yaml).expect("Should parse");
+        let item = &result.items[0];
+        assert_eq!(item.phase, Phase::Number(0)); // brainstorm is phase 0
+    }
+
+    // =========================================================================
+    // Update Tests
+    // =========================================================================
+
+    #[test]
+    fn test_update_new_format() {
+        let updated = update_workflow_status(NEW_FORMAT_YAML, "prd", "complete")
+            .expect("Should update new format");
+        assert!(updated.contains("status: complete"));
+    }
+
+    #[test]
+    fn test_update_flat_format() {
+        let updated = update_workflow_status(FLAT_FORMAT_YAML, "prd", "docs/new-prd.md")
+            .expect("Should update flat format");
+        assert!(updated.contains("prd: \"docs/new-prd.md\""));
+    }
+
+    #[test]
+    fn test_update_old_format() {
+        let updated = update_workflow_status(OLD_FORMAT_YAML, "brainstorm", "done")
+            .expect("Should update old format");
+        assert!(updated.contains("status: \"done\""));
+    }
+
+    #[test]
+    fn test_update_item_not_found() {
+        let result = update_workflow_status(NEW_FORMAT_YAML, "nonexistent", "done");
+        assert!(matches!(result, Err(WorkflowError::ItemNotFound(_))));
+    }
+
+    #[test]
+    fn test_update_flat_format_item_not_found() {
+        let result = update_workflow_status(FLAT_FORMAT_YAML, "missing", "done");
+        assert!(matches!(
+            result,
+            Err(WorkflowError::ItemNotFound(ref id)) if id == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_update_old_format_item_not_found() {
+        let result = update_workflow_status(OLD_FORMAT_YAML, "missing", "done");
+        assert!(matches!(
+            result,
+            Err(WorkflowError::ItemNotFound(ref id)) if id == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_update_preserves_structure() {
+        let updated =
+            update_workflow_status(NEW_FORMAT_YAML, "prd", "complete").expect("Should update");
+        // Verify other items are unchanged
+        assert!(updated.contains("brainstorm:"));
+        assert!(updated.contains("architecture:"));
+        // Verify metadata preserved
+        assert!(updated.contains("project: Demo Project"));
+        assert!(updated.contains("last_updated: 2025-12-01"));
+    }
+
+    #[test]
+    fn test_update_flat_format_quoting() {
+        let yaml = r#"
+project: Quote Test
+workflow_status:
+  item1: required
+"#;
+        // Status with / should be quoted
+        let updated = update_workflow_status(yaml, "item1", "docs/file.md").expect("Should update");
+        assert!(updated.contains("\"docs/file.md\"") || updated.contains("'docs/file.md'"));
+
+        // Status with : should be quoted
+        let updated = update_workflow_status(yaml, "item1", "status:done").expect("Should update");
+        assert!(updated.contains("\"status:done\"") || updated.contains("'status:done'"));
+    }
+
+    #[test]
+    fn test_update_preserves_crlf_line_endings() {
+        let cases = [
+            (
+                "project: Win\r\nworkflows:\r\n  prd:\r\n    status: not_started\r\n    notes: keep\r\n",
+                "not_started",
+            ),
+            (
+                "project: Win\r\nworkflow_status:\r\n  prd: required\r\n  architecture: optional\r\n",
+                "required",
+            ),
+            (
+                "project: Win\r\nworkflow_status:\r\n  - id: prd\r\n    phase: 1\r\n    status: \"required\"\r\n",
+                "\"required\"",
+            ),
+        ];
+        for (yaml, old) in cases {
+            let updated = update_workflow_status(yaml, "prd", "skipped").expect("Should update");
+            let expected = if old.starts_with('"') {
+                yaml.replacen(old, "\"skipped\"", 1)
+            } else {
+                yaml.replacen(old, "skipped", 1)
+            };
+            assert_eq!(updated, expected);
+            assert_eq!(
+                updated.matches("\r\n").count(),
+                yaml.matches("\r\n").count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_preserves_missing_final_newline() {
+        let yaml = "project: Demo\nworkflow_status:\n  prd: required";
+        let updated = update_workflow_status(yaml, "prd", "optional").expect("Should update");
+        assert_eq!(updated, "project: Demo\nworkflow_status:\n  prd: optional");
+
+        let yaml = "workflows:\r\n  prd:\r\n    status: not_started";
+        let updated = update_workflow_status(yaml, "prd", "complete").expect("Should update");
+        assert_eq!(updated, "workflows:\r\n  prd:\r\n    status: complete");
+    }
+
+    #[test]
+    fn test_update_empty_status_does_not_touch_next_line() {
+        let yaml = "workflows:\n  prd:\n    status:\n    output_file: docs/prd.md\n";
+        let result = update_workflow_status(yaml, "prd", "complete");
+        assert!(matches!(result, Err(WorkflowError::ItemNotFound(_))));
+    }
+
+    #[test]
+    fn test_update_quoted_and_spaced_keys() {
+        let yaml = r#"
+workflow_status:
+  "my item": required
+  'single quoted': optional
+  key with spaces: conditional
+  "ns: prd": required
+"#;
+        let data = parse_workflow_status(yaml).expect("Should parse");
+        for item in &data.items {
+            let updated = update_workflow_status(yaml, &item.id, "skipped").expect("Should update");
+            let reparsed = parse_workflow_status(&updated).expect("Should reparse");
+            let found = reparsed.items.iter().find(|i| i.id == item.id).unwrap();
+            assert_eq!(found.status, "skipped", "Failed for {}", item.id);
+            assert_eq!(
+                reparsed
+                    .items
+                    .iter()
+                    .filter(|i| i.status == "skipped")
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_quoted_keys_new_and_old_formats() {
+        let yaml = "workflows:\n  \"my item\":\n    status: not_started\n";
+        let updated = update_workflow_status(yaml, "my item", "complete").expect("Should update");
+        assert_eq!(
+            updated,
+            "workflows:\n  \"my item\":\n    status: complete\n"
+        );
+
+        let yaml = "workflow_status:\n  - id: \"my: item\"\n    status: required\n";
+        let updated = update_workflow_status(yaml, "my: item", "optional").expect("Should update");
+        assert!(updated.contains("status: \"optional\""));
+    }
+
+    #[test]
+    fn test_update_new_format_status_after_other_fields() {
+        let yaml = "workflows:\n  prd:\n    output_file: docs/prd.md\n    status: not_started\n  other:\n    status: not_started\n";
+        let updated = update_workflow_status(yaml, "prd", "complete").expect("Should update");
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    output_file: docs/prd.md\n    status: complete\n  other:\n    status: not_started\n"
+        );
+    }
+
+    #[test]
+    fn test_update_flat_format_preserves_comments() {
+        let yaml = "workflow_status:\n  prd: docs/prd.md # latest\n  architecture: required  # owner: alice\n";
+
+        let updated = update_workflow_status(yaml, "prd", "required").expect("Should update");
+        assert_eq!(
+            updated,
+            "workflow_status:\n  prd: required # latest\n  architecture: required  # owner: alice\n"
+        );
+
+        let updated =
+            update_workflow_status(yaml, "architecture", "docs/arch.md").expect("Should update");
+        assert!(updated.contains("  architecture: \"docs/arch.md\"  # owner: alice\n"));
+    }
+
+    #[test]
+    fn test_update_flat_format_values_with_colons() {
+        let yaml = "workflow_status:\n  prd: \"docs/a:b.md\"\n  research: http://example.com/x\n";
+
+        let updated = update_workflow_status(yaml, "prd", "required").expect("Should update");
+        assert_eq!(
+            updated,
+            "workflow_status:\n  prd: \"required\"\n  research: http://example.com/x\n"
+        );
+
+        let updated = update_workflow_status(yaml, "research", "skipped").expect("Should update");
+        assert!(updated.ends_with("  research: skipped\n"));
+        let data = parse_workflow_status(&updated).expect("Should reparse");
+        assert!(
+            data.items
+                .iter()
+                .any(|i| i.id == "prd" && i.status == "docs/a:b.md")
+        );
+    }
+
+    #[test]
+    fn test_update_flat_format_preserves_quote_style() {
+        let yaml = "workflow_status:\n  prd: 'required'\n";
+        let updated = update_workflow_status(yaml, "prd", "docs/prd.md").expect("Should update");
+        assert_eq!(updated, "workflow_status:\n  prd: 'docs/prd.md'\n");
+
+        let updated = update_workflow_status(yaml, "prd", "a: b").expect("Should update");
+        let data = parse_workflow_status(&updated).expect("Should reparse");
+        assert_eq!(data.items[0].status, "a: b");
+    }
+
+    #[test]
+    fn test_update_rejects_control_characters() {
+        for status in ["done\nevil: true", "done\r", "a\tb", "nul\0"] {
+            for yaml in [NEW_FORMAT_YAML, FLAT_FORMAT_YAML, OLD_FORMAT_YAML] {
+                let result = update_workflow_status(yaml, "prd", status);
+                assert!(
+                    matches!(result, Err(WorkflowError::InvalidStatus(_))),
+                    "Accepted {:?}",
+                    status
+                );
+            }
+        }
+        let err = update_workflow_status(NEW_FORMAT_YAML, "prd", "x\ny").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid status: x\\ny");
+    }
+
+    #[test]
+    fn test_update_quotes_structural_values() {
+        for status in ["a: b", "x # y", "[list]", "*alias", "say \"hi\""] {
+            for yaml in [NEW_FORMAT_YAML, FLAT_FORMAT_YAML, OLD_FORMAT_YAML] {
+                let updated = update_workflow_status(yaml, "prd", status).expect("Should update");
+                let data = parse_workflow_status(&updated).expect("Should stay well-formed");
+                let item = data.items.iter().find(|i| i.id == "prd").unwrap();
+                assert_eq!(item.status, status);
+                assert_eq!(
+                    data.items.len(),
+                    parse_workflow_status(yaml).unwrap().items.len()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_if_unchanged() {
+        let new = "workflows:\n  prd:\n    status: not_started\n";
+        let updated = update_workflow_status_if_unchanged(new, "prd", "complete", "not_started")
+            .expect("Should update");
+        assert!(updated.contains("status: complete"));
+        assert!(matches!(
+            update_workflow_status_if_unchanged(&updated, "prd", "skipped", "not_started"),
+            Err(WorkflowError::ConflictError { actual }) if actual == "complete"
+        ));
+
+        let flat = "workflow_status:\n  prd: \"docs/prd.md\"\n  ux: required\n";
+        assert!(
+            update_workflow_status_if_unchanged(flat, "prd", "required", "docs/prd.md").is_ok()
+        );
+        assert!(matches!(
+            update_workflow_status_if_unchanged(flat, "ux", "skipped", "optional"),
+            Err(WorkflowError::ConflictError { actual }) if actual == "required"
+        ));
+
+        let old = "workflow_status:\n  - id: prd\n    status: required\n";
+        assert!(update_workflow_status_if_unchanged(old, "prd", "skipped", "required").is_ok());
+        assert!(matches!(
+            update_workflow_status_if_unchanged(old, "missing", "skipped", "required"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_conflict_error_display() {
+        let err = WorkflowError::ConflictError {
+            actual: "complete".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Status changed since it was read; it is now \"complete\""
+        );
+    }
+
+    #[test]
+    fn test_update_workflow_statuses_batch() {
+        let yaml =
+            "workflows:\n  prd:\n    status: required\n  architecture:\n    status: required\n";
+        let updated =
+            update_workflow_statuses(yaml, &[("prd", "complete"), ("architecture", "skipped")])
+                .unwrap();
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    status: complete\n  architecture:\n    status: skipped\n"
+        );
+
+        let flat = "workflow_status:\n  prd: required\n  ux: optional\n";
+        let updated = update_workflow_statuses(flat, &[("ux", "skipped"), ("ux", "required")]);
+        assert_eq!(
+            updated.unwrap(),
+            "workflow_status:\n  prd: required\n  ux: required\n"
+        );
+
+        assert_eq!(update_workflow_statuses(yaml, &[]).unwrap(), yaml);
+    }
+
+    #[test]
+    fn test_update_workflow_statuses_is_all_or_nothing() {
+        let yaml = "workflows:\n  prd:\n    status: required\n";
+        let result = update_workflow_statuses(yaml, &[("prd", "complete"), ("missing", "done")]);
+        assert!(matches!(result, Err(WorkflowError::ItemNotFound(id)) if id == "missing"));
+        assert!(matches!(
+            update_workflow_statuses(yaml, &[("prd", "a\nb")]),
+            Err(WorkflowError::InvalidStatus(_))
+        ));
+    }
+
+    #[test]
+    fn test_update_item_id_special_chars() {
+        // Only the exact key changes; `.` and brackets are not wildcards
+        let yaml =
+            "workflow_status:\n  testXitem: required\n  test.item: required\n  item[0]: optional\n";
+        let updated = update_workflow_status(yaml, "test.item", "complete").expect("Should update");
+        assert_eq!(
+            updated,
+            "workflow_status:\n  testXitem: required\n  test.item: complete\n  item[0]: optional\n"
+        );
+        let updated = update_workflow_status(yaml, "item[0]", "skipped").expect("Should update");
+        assert!(updated.ends_with("  item[0]: skipped\n"));
+        assert!(matches!(
+            update_workflow_status(yaml, "item", "complete"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_update_item_id_all_special_chars() {
+        let id = "a.b*c+d?e^f$g{h}i(j)k|l[m]n\\o";
+        let yaml = format!(
+            "workflows:\n  abc:\n    status: not_started\n  '{}':\n    status: not_started\n",
+            id
+        );
+        let updated = update_workflow_status(&yaml, id, "complete").expect("Should update");
+        assert_eq!(
+            updated,
+            format!(
+                "workflows:\n  abc:\n    status: not_started\n  '{}':\n    status: complete\n",
+                id
+            )
+        );
+    }
+
+    // =========================================================================
+    // Complete/Reopen Tests
+    // =========================================================================
+
+    #[test]
+    fn test_complete_item_inserts_output_file() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n  architecture:\n    status: not_started\n";
+        let updated = complete_item(yaml, "prd", Some("docs/prd.md")).expect("Should complete");
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    status: complete\n    output_file: docs/prd.md\n  architecture:\n    status: not_started\n"
+        );
+
+        let data = parse_workflow_status(&updated).expect("Should parse");
+        let prd = data.items.iter().find(|i| i.id == "prd").unwrap();
+        assert_eq!(prd.status, "docs/prd.md");
+        assert_eq!(prd.output_file.as_deref(), Some("docs/prd.md"));
+    }
+
+    #[test]
+    fn test_complete_item_replaces_output_file() {
+        let yaml = "workflows:\n  prd:\n    output_file: 'old.md'\n    status: in_progress\n";
+        let updated = complete_item(yaml, "prd", Some("docs/new.md")).expect("Should complete");
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    output_file: 'docs/new.md'\n    status: complete\n"
+        );
+    }
+
+    #[test]
+    fn test_complete_item_without_output_file() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n";
+        let updated = complete_item(yaml, "prd", None).expect("Should complete");
+        assert_eq!(updated, "workflows:\n  prd:\n    status: complete\n");
+    }
+
+    #[test]
+    fn test_complete_item_flat_and_old_formats() {
+        let updated =
+            complete_item(FLAT_FORMAT_YAML, "brainstorm", Some("docs/brainstorm.md")).unwrap();
+        let data = parse_workflow_status(&updated).unwrap();
+        let item = data.items.iter().find(|i| i.id == "brainstorm").unwrap();
+        assert_eq!(item.output_file.as_deref(), Some("docs/brainstorm.md"));
+
+        let updated = complete_item(OLD_FORMAT_YAML, "prd", None).unwrap();
+        let data = parse_workflow_status(&updated).unwrap();
+        let prd = data.items.iter().find(|i| i.id == "prd").unwrap();
+        assert_eq!(prd.status, "complete");
+    }
+
+    #[test]
+    fn test_complete_item_crlf() {
+        let yaml = "workflows:\r\n  prd:\r\n    status: not_started\r\n";
+        let updated = complete_item(yaml, "prd", Some("docs/prd.md")).unwrap();
+        assert_eq!(
+            updated,
+            "workflows:\r\n  prd:\r\n    status: complete\r\n    output_file: docs/prd.md\r\n"
+        );
+    }
+
+    #[test]
+    fn test_complete_item_errors() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n";
+        assert!(matches!(
+            complete_item(yaml, "missing", Some("a.md")),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+        assert!(matches!(
+            complete_item(yaml, "prd", Some("a\nb")),
+            Err(WorkflowError::UpdateError(_))
+        ));
+    }
+
+    #[test]
+    fn test_reopen_item() {
+        let yaml = "workflows:\n  prd:\n    status: complete\n    output_file: docs/prd.md\n    notes: keep\n";
+        let updated = reopen_item(yaml, "prd").expect("Should reopen");
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    status: not_started\n    notes: keep\n"
+        );
+
+        let round_trip = complete_item(&updated, "prd", Some("docs/prd.md")).unwrap();
+        assert_eq!(reopen_item(&round_trip, "prd").unwrap(), updated);
+
+        let reopened = reopen_item(FLAT_FORMAT_YAML, "prd").unwrap();
+        let data = parse_workflow_status(&reopened).unwrap();
+        let item = data.items.iter().find(|i| i.id == "prd").unwrap();
+        assert_eq!(item.status, "required");
+        assert_eq!(item.output_file, None);
+    }
+
+    #[test]
+    fn test_remove_workflow_field() {
+        let yaml = "workflows:\n  prd:\n    status: complete\n    note: keep\n    output_file: docs/prd.md\n";
+        assert_eq!(
+            raw_field(yaml, "prd", &WorkflowField::Notes).unwrap(),
+            Some("keep".to_string())
+        );
+        let updated = remove_workflow_field(yaml, "prd", WorkflowField::Notes).unwrap();
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    status: complete\n    output_file: docs/prd.md\n"
+        );
+        assert_eq!(
+            raw_field(&updated, "prd", &WorkflowField::Notes).unwrap(),
+            None
+        );
+        // Absent fields are already removed
+        assert_eq!(
+            remove_workflow_field(&updated, "prd", WorkflowField::Notes).unwrap(),
+            updated
+        );
+
+        let old = "workflow_status:\n  - id: prd\n    status: required\n    note: hi\n";
+        let updated = remove_workflow_field(old, "prd", WorkflowField::Notes).unwrap();
+        assert_eq!(
+            updated,
+            "workflow_status:\n  - id: prd\n    status: required\n"
+        );
+        assert!(matches!(
+            remove_workflow_field(old, "prd", WorkflowField::Custom("id".to_string())),
+            Err(WorkflowError::UpdateError(_))
+        ));
+
+        assert!(matches!(
+            remove_workflow_field(yaml, "missing", WorkflowField::Notes),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+        assert!(matches!(
+            remove_workflow_field(FLAT_FORMAT_YAML, "prd", WorkflowField::Notes),
+            Err(WorkflowError::UpdateError(_))
+        ));
+        assert!(matches!(
+            raw_field(yaml, "missing", &WorkflowField::Notes),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_depends_on() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n  architecture:\n    status: not_started\n    depends_on: [prd, ux-design]\n  test-design:\n    status: not_started\n    depends_on: prd\n";
+        let data = parse_workflow_status(yaml).unwrap();
+        let item = |id: &str| data.items.iter().find(|i| i.id == id).unwrap();
+        assert_eq!(item("architecture").depends_on, vec!["prd", "ux-design"]);
+        assert_eq!(item("test-design").depends_on, vec!["prd"]);
+        assert!(item("prd").depends_on.is_empty());
+        assert_eq!(roundtrip_check(yaml), Ok(()));
+
+        let old = "workflow_status:\n  - id: prd\n    status: required\n  - id: architecture\n    status: required\n    depends_on:\n      - prd\n";
+        let data = parse_workflow_status(old).unwrap();
+        assert_eq!(data.items[1].depends_on, vec!["prd"]);
+        assert_eq!(roundtrip_check(old), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_tracks() {
+        let yaml = "selected_track: quick-flow\nfield_type: greenfield\nworkflows:\n  prd:\n    status: not_started\n    tracks: [method, enterprise]\n  tech-spec:\n    status: not_started\n    tracks: quick-flow\n  architecture:\n    status: not_started\n";
+        let data = parse_workflow_status(yaml).unwrap();
+        let ids: Vec<&str> = data
+            .items_for_track()
+            .iter()
+            .map(|i| i.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["tech-spec", "architecture"]);
+
+        let prd = data.items.iter().find(|i| i.id == "prd").unwrap();
+        assert_eq!(prd.tracks, vec!["method", "enterprise"]);
+        assert_eq!(data.item_state(prd), ItemState::NotApplicable);
+        assert_eq!(roundtrip_check(yaml), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n    tags: [backend, security]\n  architecture:\n    status: not_started\n    tags: backend\n";
+        let data = parse_workflow_status(yaml).unwrap();
+        let item = |id: &str| data.items.iter().find(|i| i.id == id).unwrap();
+        assert_eq!(item("prd").tags, vec!["backend", "security"]);
+        assert_eq!(item("architecture").tags, vec!["backend"]);
+        assert_eq!(roundtrip_check(yaml), Ok(()));
+
+        let old = "workflow_status:\n  - id: prd\n    status: required\n    tags: [api]\n";
+        assert_eq!(
+            parse_workflow_status(old).unwrap().items[0].tags,
+            vec!["api"]
+        );
+        assert_eq!(roundtrip_check(old), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_due() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n    due: 2025-03-01\n  architecture:\n    status: not_started\n";
+        let data = parse_workflow_status(yaml).unwrap();
+        let item = |id: &str| data.items.iter().find(|i| i.id == id).unwrap();
+        assert_eq!(item("prd").due.as_deref(), Some("2025-03-01"));
+        assert!(item("architecture").due.is_none());
+        assert_eq!(roundtrip_check(yaml), Ok(()));
+
+        let old = "workflow_status:\n  - id: prd\n    status: required\n    due: \"2025-03-01\"\n";
+        let data = parse_workflow_status(old).unwrap();
+        assert_eq!(data.items[0].due.as_deref(), Some("2025-03-01"));
+        assert_eq!(roundtrip_check(old), Ok(()));
+    }
+
+    // =========================================================================
+    // Single Item Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_item_matches_full_parse() {
+        let depends = "workflows:\n  prd:\n    status: not_started\n  architecture:\n    status: complete\n    depends_on: [prd]\n    due: 2025-03-01\n";
+        for yaml in [NEW_FORMAT_YAML, FLAT_FORMAT_YAML, OLD_FORMAT_YAML, depends] {
+            let data = parse_workflow_status(yaml).unwrap();
+            for item in &data.items {
+                assert!(scan_item(yaml, &item.id).is_some(), "{}", item.id);
+                assert_eq!(&parse_item(yaml, &item.id).unwrap(), item);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_item_fallback_and_missing() {
+        // Flow style cannot be scanned, but still parses
+        let flow = "workflows: {prd: {status: complete, output_file: docs/prd.md}}\n";
+        assert!(scan_item(flow, "prd").is_none());
+        assert_eq!(parse_item(flow, "prd").unwrap().status, "docs/prd.md");
+
+        // Quoted keys are found as the parser reads them
+        let quoted = "workflows:\n  \"prd\": # main\n    status: not_started\n";
+        assert_eq!(parse_item(quoted, "prd").unwrap().status, "required");
+
+        assert!(matches!(
+            parse_item(NEW_FORMAT_YAML, "missing"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+        assert!(matches!(
+            parse_item(OLD_FORMAT_YAML, "missing"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+        assert!(matches!(
+            parse_item("workflows: [unclosed", "prd"),
+            Err(WorkflowError::ParseError(_))
+        ));
+    }
+
+    // =========================================================================
+    // Metadata Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_workflow_metadata_matches_full_parse() {
+        let mixed = "project: Mixed # trailing\nworkflows:\n  prd:\n    status: \"complete\"\n  ux:\n    notes: none yet\n    status: skipped\n  architecture:\n  tests:\n    status: 5\nlast_updated: 2025-12-01\n";
+        let flat =
+            "workflow_status:\n  prd: docs/prd.md\n  ux: 'required'\n  tests: ~\nproject: Flat\n";
+        for yaml in [
+            NEW_FORMAT_YAML,
+            FLAT_FORMAT_YAML,
+            OLD_FORMAT_YAML,
+            mixed,
+            flat,
+            "project: Empty\n",
+        ] {
+            let expected = WorkflowMeta::from(&parse_workflow_status(yaml).unwrap());
+            assert_eq!(scan_metadata(yaml), Some(expected), "{}", yaml);
+        }
+
+        let meta = parse_workflow_metadata(mixed).unwrap();
+        assert_eq!(meta.project, "Mixed");
+        assert_eq!(meta.last_updated, "2025-12-01");
+        assert_eq!((meta.item_count, meta.complete_count), (4, 1));
+    }
+
+    #[test]
+    fn test_parse_workflow_metadata_fallback() {
+        let cases = [
+            // Flow collection, multi-line scalar, duplicate key, alias
+            "project: Flow\nworkflows: {prd: {status: complete}}\n",
+            "workflow_status:\n  prd: docs/\n    prd.md\n",
+            "workflows:\n  prd:\n    status: complete\nworkflows:\n  ux:\n    status: complete\n",
+            "defaults: &done complete\nworkflow_status:\n  prd: *done\n",
+        ];
+        for yaml in cases {
+            assert_eq!(scan_metadata(yaml), None, "{}", yaml);
+            let full = parse_workflow_status(yaml).map(|data| WorkflowMeta::from(&data));
+            assert_eq!(parse_workflow_metadata(yaml).ok(), full.ok());
+        }
+        assert!(matches!(
+            parse_workflow_metadata("project: [unclosed\nworkflows:\n  prd: {}\n"),
+            Err(WorkflowError::ParseError(_))
+        ));
     }
 
+    // =========================================================================
+    // Format Sniffing Tests
+    // =========================================================================
+
     #[test]
-    fn test_new_format_inferred_commands() {
-        let result = parse_workflow_status(NEW_FORMAT_YAML).expect("Should parse");
+    fn test_sniff_format_matches_parser() {
+        let cases = [
+            (NEW_FORMAT_YAML, WorkflowFormat::New),
+            (FLAT_FORMAT_YAML, WorkflowFormat::Flat),
+            (OLD_FORMAT_YAML, WorkflowFormat::Old),
+            (
+                "workflow_status:\n- id: prd\n  status: required\n",
+                WorkflowFormat::Old,
+            ),
+            (
+                "workflows:\nworkflow_status:\n  prd: required\n",
+                WorkflowFormat::Flat,
+            ),
+            (
+                "workflows:\n  - prd\nworkflow_status:\n  prd: required\n",
+                WorkflowFormat::Flat,
+            ),
+            ("project: Empty\n", WorkflowFormat::Old),
+        ];
+        for (yaml, format) in cases {
+            let parsed: Value = serde_yaml::from_str(yaml).unwrap();
+            assert_eq!(detect_format(&parsed), format, "{}", yaml);
+            assert_eq!(sniff_format(yaml), Some(format), "{}", yaml);
+        }
 
-        for item in &result.items {
-            assert_eq!(item.command, Some(item.id.clone()));
+        for yaml in [
+            "workflows: {prd: {status: complete}}\n",
+            "workflow_status: &items\n  prd: required\n",
+            "workflows:\n  ? prd\n  : {status: complete}\n",
+        ] {
+            assert_eq!(sniff_format(yaml), None, "{}", yaml);
         }
     }
 
+    #[test]
+    fn test_update_with_format() {
+        let updated = update_workflow_status_with_format(
+            FLAT_FORMAT_YAML,
+            WorkflowFormat::Flat,
+            "prd",
+            "docs/prd.md",
+        )
+        .unwrap();
+        assert_eq!(
+            updated,
+            update_workflow_status(FLAT_FORMAT_YAML, "prd", "docs/prd.md").unwrap()
+        );
+        assert!(matches!(
+            update_workflow_status_with_format(NEW_FORMAT_YAML, WorkflowFormat::New, "prd", "a\nb"),
+            Err(WorkflowError::InvalidStatus(_))
+        ));
+
+        // Flow style cannot be sniffed; the update still finds the format
+        let flow = "workflows: {prd: {status: complete}}\n";
+        assert!(matches!(
+            update_workflow_status(flow, "prd", "skipped"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+        assert!(matches!(
+            update_workflow_status("workflows: {prd: [unclosed", "prd", "skipped"),
+            Err(WorkflowError::ParseError(_))
+        ));
+    }
+
     // =========================================================================
-    // Parsing Tests - Flat Format
+    // Normalization Tests
     // =========================================================================
 
     #[test]
-    fn test_parse_flat_format() {
-        let result =
-            parse_workflow_status(FLAT_FORMAT_YAML).expect("Should parse flat format YAML");
+    fn test_parse_with_normalize_option() {
+        let yaml = "workflows:\n  brainstorm:\n    status: Done\n    output_file: docs/brainstorm.md\n  prd:\n    status: finished\n  architecture:\n    status: todo\n  sprint-planning:\n    status: skipped\n";
+
+        let raw = parse_workflow_status(yaml).unwrap();
+        let raw_status = |id: &str| {
+            raw.items
+                .iter()
+                .find(|i| i.id == id)
+                .unwrap()
+                .status
+                .clone()
+        };
+        assert_eq!(raw_status("prd"), "finished");
+        assert_eq!(raw_status("architecture"), "todo");
+
+        let options = ParseOptions::default().with_normalize(StatusAliases::default());
+        let data = parse_workflow_status_with_options(yaml, &options).unwrap();
+        let status = |id: &str| {
+            data.items
+                .iter()
+                .find(|i| i.id == id)
+                .unwrap()
+                .status
+                .clone()
+        };
+        assert_eq!(status("brainstorm"), "docs/brainstorm.md");
+        assert_eq!(status("prd"), "complete");
+        assert_eq!(status("architecture"), "required");
+        assert_eq!(status("sprint-planning"), "skipped");
+    }
 
-        assert_eq!(result.project, "Demo Project");
-        assert_eq!(result.items.len(), 3);
+    #[test]
+    fn test_normalize_keeps_output_file_statuses() {
+        let options = ParseOptions::default().with_normalize(StatusAliases::default());
+        let raw = parse_workflow_status(FLAT_FORMAT_YAML).unwrap();
+        let data = parse_workflow_status_with_options(FLAT_FORMAT_YAML, &options).unwrap();
+        assert_eq!(raw, data);
+    }
 
-        let prd = result
-            .items
-            .iter()
-            .find(|i| i.id == "prd")
-            .expect("Should find prd");
-        assert_eq!(prd.status, "docs/prd.md");
-        assert_eq!(prd.output_file, Some("docs/prd.md".to_string()));
+    #[test]
+    fn test_parse_with_registry_tags_custom_statuses() {
+        let yaml = "workflows:\n  brainstorm:\n    status: complete\n    output_file: docs/brainstorm.md\n  prd:\n    status: in_review\n  architecture:\n    status: parked\n  sprint-planning:\n    status: deferred\n";
+        let registry = StatusRegistry::new()
+            .with_status(StatusInfo::new("in_review", "In review"))
+            .with_status(StatusInfo::new("deferred", "Deferred").terminal())
+            .with_status(StatusInfo::new("docs/brainstorm.md", "Not a status"));
+
+        // Unregistered custom statuses read as complete
+        let raw = parse_workflow_status(yaml).unwrap();
+        let raw_item = |id: &str| raw.items.iter().find(|i| i.id == id).unwrap().clone();
+        assert_eq!(raw_item("prd").status_class(), StatusClass::Complete);
+
+        let options = ParseOptions::default().with_registry(registry);
+        let data = parse_workflow_status_with_options(yaml, &options).unwrap();
+        let item = |id: &str| data.items.iter().find(|i| i.id == id).unwrap().clone();
+        assert!(item("brainstorm").custom_status.is_none());
+        assert_eq!(
+            item("prd").custom_status.map(|c| c.display_name),
+            Some("In review".to_string())
+        );
+        assert_eq!(item("prd").status_class(), StatusClass::Pending);
+        assert_eq!(
+            item("sprint-planning").status_class(),
+            StatusClass::Complete
+        );
+        assert!(item("architecture").custom_status.is_none());
     }
 
+    // =========================================================================
+    // Round Trip Tests
+    // =========================================================================
+
     #[test]
-    fn test_flat_format_file_path_detection() {
-        let yaml = r#"
-project: File Path Test
-workflow_status:
-  item1: docs/output.md
-  item2: path/to/file.yaml
-  item3: output.json
-  item4: completed
-  item5: required
-"#;
-        let result = parse_workflow_status(yaml).expect("Should parse");
+    fn test_roundtrip_all_formats() {
+        for yaml in [NEW_FORMAT_YAML, FLAT_FORMAT_YAML, OLD_FORMAT_YAML] {
+            assert_eq!(roundtrip_check(yaml), Ok(()), "{}", yaml);
+        }
+    }
 
-        let item1 = result.items.iter().find(|i| i.id == "item1").unwrap();
-        assert_eq!(item1.output_file, Some("docs/output.md".to_string()));
+    #[test]
+    fn test_roundtrip_after_updates() {
+        let updated = complete_item(NEW_FORMAT_YAML, "prd", Some("docs/prd.md")).unwrap();
+        let updated =
+            update_workflow_field(&updated, "architecture", WorkflowField::Notes, "a: b").unwrap();
+        assert_eq!(roundtrip_check(&updated), Ok(()));
 
-        let item2 = result.items.iter().find(|i| i.id == "item2").unwrap();
-        assert_eq!(item2.output_file, Some("path/to/file.yaml".to_string()));
+        let updated = update_workflow_status(OLD_FORMAT_YAML, "prd", "docs/prd.md").unwrap();
+        assert_eq!(roundtrip_check(&updated), Ok(()));
+    }
 
-        let item3 = result.items.iter().find(|i| i.id == "item3").unwrap();
-        assert_eq!(item3.output_file, Some("output.json".to_string()));
+    #[test]
+    fn test_roundtrip_quoting_sensitive_values() {
+        let yaml = "project: \"123\"\nstatus_note: \"\"\nworkflows:\n  prd:\n    status: \"yes\"\n    notes: \"#1: ready\"\n";
+        assert_eq!(roundtrip_check(yaml), Ok(()));
+    }
 
-        let item4 = result.items.iter().find(|i| i.id == "item4").unwrap();
-        assert_eq!(item4.output_file, None);
+    #[test]
+    fn test_roundtrip_reports_lossy_items() {
+        // Non-string keys all parse to an empty id and collapse when written back
+        let yaml = "workflows:\n  1:\n    status: required\n  2:\n    status: skipped\n";
+        let report = roundtrip_check(yaml).unwrap_err();
+        assert!(report.error.is_none());
+        assert!(report.lossy_fields.iter().any(|l| l.path == "items"));
+        assert!(report.to_string().contains("items"));
+    }
 
-        let item5 = result.items.iter().find(|i| i.id == "item5").unwrap();
-        assert_eq!(item5.output_file, None);
+    #[test]
+    fn test_roundtrip_invalid_yaml() {
+        let report = roundtrip_check("workflows: [unclosed").unwrap_err();
+        assert!(report.error.is_some());
+        assert!(report.lossy_fields.is_empty());
     }
 
     // =========================================================================
-    // Parsing Tests - Old Format
+    // Migration Tests
     // =========================================================================
 
     #[test]
-    fn test_parse_old_format() {
-        let result = parse_workflow_status(OLD_FORMAT_YAML).expect("Should parse old format YAML");
+    fn test_migrate_to_new_format() {
+        let migration = migrate_workflow(FLAT_FORMAT_YAML, WorkflowFormat::New).unwrap();
+        assert_eq!(migration.from, WorkflowFormat::Flat);
+        assert!(migration.changed());
+        assert!(
+            migration.lossy_fields.is_empty(),
+            "{:?}",
+            migration.lossy_fields
+        );
+        assert_eq!(sniff_format(&migration.content), Some(WorkflowFormat::New));
+
+        let before = parse_workflow_status(FLAT_FORMAT_YAML).unwrap();
+        let after = parse_workflow_status(&migration.content).unwrap();
+        assert_eq!(before.project, after.project);
+        let statuses = |data: &WorkflowData| -> Vec<(String, String)> {
+            data.items
+                .iter()
+                .map(|item| (item.id.clone(), item.status.to_string()))
+                .collect()
+        };
+        assert_eq!(statuses(&before), statuses(&after));
+    }
 
-        assert_eq!(result.project, "Demo Project");
-        assert_eq!(result.items.len(), 2);
+    #[test]
+    fn test_migrate_old_format() {
+        let migration = migrate_workflow(OLD_FORMAT_YAML, WorkflowFormat::New).unwrap();
+        assert_eq!(migration.from, WorkflowFormat::Old);
+        // Agents, commands and phases of known workflows are derived again
+        assert!(
+            migration.lossy_fields.is_empty(),
+            "{:?}",
+            migration.lossy_fields
+        );
 
-        let brainstorm = result
-            .items
+        let migration = migrate_workflow(OLD_FORMAT_YAML, WorkflowFormat::Flat).unwrap();
+        assert!(
+            migration
+                .lossy_fields
+                .iter()
+                .any(|l| l.path == "items.brainstorm.note")
+        );
+        assert_eq!(sniff_format(&migration.content), Some(WorkflowFormat::Flat));
+    }
+
+    #[test]
+    fn test_migrate_reports_dropped_keys_and_comments() {
+        let yaml = "# Team workflow\nteam_notes: keep me # important\nproject: Demo\nworkflows:\n  \
+                    prd:\n    status: required\n    custom_owner: ana\n    notes: |\n      # not a comment\n";
+        let migration = migrate_workflow(yaml, WorkflowFormat::Flat).unwrap();
+        let paths: Vec<&str> = migration
+            .lossy_fields
             .iter()
-            .find(|i| i.id == "brainstorm")
-            .expect("Should find brainstorm");
-        assert_eq!(brainstorm.phase, Phase::Number(0));
-        assert_eq!(brainstorm.agent, Some("analyst".to_string()));
-        assert_eq!(brainstorm.note, Some("Seed ideas".to_string()));
+            .map(|l| l.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "items.prd.note",
+                "team_notes",
+                "items.prd.custom_owner",
+                "comment on line 1",
+                "comment on line 2",
+            ]
+        );
+        let owner = &migration.lossy_fields[2];
+        assert_eq!(owner.original.as_deref(), Some("ana"));
+        assert_eq!(owner.roundtripped, None);
     }
 
     #[test]
-    fn test_old_format_explicit_phase() {
-        let yaml = r#"
-project: Phase Test
-workflow_status:
-  - id: custom-item
-    phase: 5
-    status: required
-"#;
-        let result = parse_workflow_status(yaml).expect("Should parse");
-        let item = &result.items[0];
-        assert_eq!(item.phase, Phase::Number(5));
+    fn test_migrate_same_format_is_identity() {
+        let migration = migrate_workflow(NEW_FORMAT_YAML, WorkflowFormat::New).unwrap();
+        assert!(!migration.changed());
+        assert_eq!(migration.content, NEW_FORMAT_YAML);
+        assert!(migration.lossy_fields.is_empty());
     }
 
     #[test]
-    fn test_old_format_missing_phase_inferred() {
-        let yaml = r#"
-project: Infer Phase Test
-workflow_status:
-  - id: brainstorm
-    status: required
-"#;
-        let result = parse_workflow_status(yaml).expect("Should parse");
-        let item = &result.items[0];
-        assert_eq!(item.phase, Phase::Number(0)); // brainstorm is phase 0
+    fn test_migrate_invalid_yaml() {
+        assert!(matches!(
+            migrate_workflow("workflows: [unclosed", WorkflowFormat::New),
+            Err(WorkflowError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_display() {
+        assert_eq!(WorkflowFormat::New.to_string(), "new");
+        assert_eq!(WorkflowFormat::Flat.to_string(), "flat");
+        assert_eq!(WorkflowFormat::Old.to_string(), "old");
+    }
+
+    #[test]
+    fn test_lossy_fields_paths() {
+        let original = parse_workflow_status(NEW_FORMAT_YAML).unwrap();
+        let mut changed = original.clone();
+        changed.status_note = None;
+        changed.items[0].note = Some("extra".to_string());
+
+        let lossy = lossy_fields(&original, &changed);
+        let paths: Vec<&str> = lossy.iter().map(|l| l.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "status_note".to_string(),
+                format!("items.{}.note", original.items[0].id)
+            ]
+        );
+        assert_eq!(lossy[1].roundtripped.as_deref(), Some("extra"));
     }
 
     // =========================================================================
-    // Update Tests
+    // Field Update Tests
     // =========================================================================
 
     #[test]
-    fn test_update_new_format() {
-        let updated = update_workflow_status(NEW_FORMAT_YAML, "prd", "complete")
-            .expect("Should update new format");
-        assert!(updated.contains("status: complete"));
+    fn test_update_field_new_format() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n  architecture:\n    status: not_started\n";
+
+        let updated = update_workflow_field(yaml, "prd", WorkflowField::Notes, "Needs review")
+            .expect("Should insert notes");
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    status: not_started\n    notes: Needs review\n  architecture:\n    status: not_started\n"
+        );
+
+        let updated = update_workflow_field(&updated, "prd", WorkflowField::Notes, "Approved")
+            .expect("Should replace notes");
+        assert!(updated.contains("    notes: Approved\n"));
+        assert!(!updated.contains("Needs review"));
+
+        let data = parse_workflow_status(&updated).unwrap();
+        let prd = data.items.iter().find(|i| i.id == "prd").unwrap();
+        assert_eq!(prd.note.as_deref(), Some("Approved"));
     }
 
     #[test]
-    fn test_update_flat_format() {
-        let updated = update_workflow_status(FLAT_FORMAT_YAML, "prd", "docs/new-prd.md")
-            .expect("Should update flat format");
-        assert!(updated.contains("prd: \"docs/new-prd.md\""));
+    fn test_update_field_keeps_note_spelling() {
+        let yaml = "workflows:\n  prd:\n    status: not_started\n    note: old\n";
+        let updated = update_workflow_field(yaml, "prd", WorkflowField::Notes, "new").unwrap();
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    status: not_started\n    note: new\n"
+        );
     }
 
     #[test]
-    fn test_update_old_format() {
-        let updated = update_workflow_status(OLD_FORMAT_YAML, "brainstorm", "done")
-            .expect("Should update old format");
-        assert!(updated.contains("status: \"done\""));
+    fn test_update_field_output_file_and_custom() {
+        let yaml = "workflows:\n  prd:\n    status: complete\n";
+        let updated =
+            update_workflow_field(yaml, "prd", WorkflowField::OutputFile, "docs/prd.md").unwrap();
+        let updated = update_workflow_field(
+            &updated,
+            "prd",
+            WorkflowField::Custom("owner".to_string()),
+            "alice: pm",
+        )
+        .unwrap();
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    status: complete\n    output_file: docs/prd.md\n    owner: \"alice: pm\"\n"
+        );
+        parse_workflow_status(&updated).expect("Should stay well-formed");
     }
 
     #[test]
-    fn test_update_item_not_found() {
-        let result = update_workflow_status(NEW_FORMAT_YAML, "nonexistent", "done");
-        assert!(matches!(result, Err(WorkflowError::ItemNotFound(_))));
+    fn test_update_field_old_format() {
+        let updated =
+            update_workflow_field(OLD_FORMAT_YAML, "brainstorm", WorkflowField::Notes, "Done")
+                .unwrap();
+        let data = parse_workflow_status(&updated).unwrap();
+        let item = data.items.iter().find(|i| i.id == "brainstorm").unwrap();
+        assert_eq!(item.note.as_deref(), Some("Done"));
+
+        let updated =
+            update_workflow_field(OLD_FORMAT_YAML, "prd", WorkflowField::Notes, "Added").unwrap();
+        let data = parse_workflow_status(&updated).unwrap();
+        let item = data.items.iter().find(|i| i.id == "prd").unwrap();
+        assert_eq!(item.note.as_deref(), Some("Added"));
+        assert_eq!(data.items.len(), 2);
     }
 
     #[test]
-    fn test_update_flat_format_item_not_found() {
-        let result = update_workflow_status(FLAT_FORMAT_YAML, "missing", "done");
+    fn test_update_field_errors() {
         assert!(matches!(
-            result,
-            Err(WorkflowError::ItemNotFound(ref id)) if id == "missing"
+            update_workflow_field(NEW_FORMAT_YAML, "missing", WorkflowField::Notes, "x"),
+            Err(WorkflowError::ItemNotFound(_))
         ));
-    }
-
-    #[test]
-    fn test_update_old_format_item_not_found() {
-        let result = update_workflow_status(OLD_FORMAT_YAML, "missing", "done");
         assert!(matches!(
-            result,
-            Err(WorkflowError::ItemNotFound(ref id)) if id == "missing"
+            update_workflow_field(FLAT_FORMAT_YAML, "prd", WorkflowField::Notes, "x"),
+            Err(WorkflowError::UpdateError(_))
+        ));
+        assert!(matches!(
+            update_workflow_field(FLAT_FORMAT_YAML, "missing", WorkflowField::Notes, "x"),
+            Err(WorkflowError::ItemNotFound(_))
+        ));
+        assert!(matches!(
+            update_workflow_field(NEW_FORMAT_YAML, "prd", WorkflowField::Notes, "a\nb: c"),
+            Err(WorkflowError::UpdateError(_))
+        ));
+        assert!(matches!(
+            update_workflow_field(
+                NEW_FORMAT_YAML,
+                "prd",
+                WorkflowField::Custom(" ".to_string()),
+                "x"
+            ),
+            Err(WorkflowError::UpdateError(_))
         ));
     }
 
     #[test]
-    fn test_update_preserves_structure() {
-        let updated =
-            update_workflow_status(NEW_FORMAT_YAML, "prd", "complete").expect("Should update");
-        // Verify other items are unchanged
-        assert!(updated.contains("brainstorm:"));
-        assert!(updated.contains("architecture:"));
-        // Verify metadata preserved
-        assert!(updated.contains("project: Demo Project"));
-        assert!(updated.contains("last_updated: 2025-12-01"));
+    fn test_update_field_replaces_block_scalar_notes() {
+        let yaml =
+            "workflows:\n  prd:\n    notes: |\n      line1\n      line2\n    status: required\n";
+        let updated = update_workflow_field(yaml, "prd", WorkflowField::Notes, "new note").unwrap();
+        assert_eq!(
+            updated,
+            "workflows:\n  prd:\n    notes: new note\n    status: required\n"
+        );
+        let data = parse_workflow_status(&updated).unwrap();
+        assert_eq!(data.items[0].note.as_deref(), Some("new note"));
     }
 
     #[test]
-    fn test_update_flat_format_quoting() {
-        let yaml = r#"
-project: Quote Test
-workflow_status:
-  item1: required
-"#;
-        // Status with / should be quoted
-        let updated = update_workflow_status(yaml, "item1", "docs/file.md").expect("Should update");
-        assert!(updated.contains("\"docs/file.md\"") || updated.contains("'docs/file.md'"));
-
-        // Status with : should be quoted
-        let updated = update_workflow_status(yaml, "item1", "status:done").expect("Should update");
-        assert!(updated.contains("\"status:done\"") || updated.contains("'status:done'"));
+    fn test_update_field_custom_status_is_a_status_update() {
+        let status = WorkflowField::Custom("status".to_string());
+        assert_eq!(
+            update_workflow_field(NEW_FORMAT_YAML, "prd", status.clone(), "docs/prd.md").unwrap(),
+            update_workflow_status(NEW_FORMAT_YAML, "prd", "docs/prd.md").unwrap()
+        );
+        assert!(matches!(
+            update_workflow_field(FLAT_FORMAT_YAML, "prd", status, "done\nevil: true"),
+            Err(WorkflowError::InvalidStatus(_))
+        ));
     }
 
     // =========================================================================
@@ -745,40 +3082,6 @@ workflow_status:
         assert!(!is_file_path("in-progress"));
     }
 
-    // =========================================================================
-    // Escape Regex Tests
-    // =========================================================================
-
-    #[test]
-    fn test_escape_regex_workflow() {
-        let escaped = escape_regex("test.item");
-        assert!(escaped.contains("\\.")); // Dot escaped
-
-        let escaped = escape_regex("item[0]");
-        assert!(escaped.contains("\\[")); // Bracket escaped
-        assert!(escaped.contains("\\]")); // Bracket escaped
-    }
-
-    #[test]
-    fn test_escape_regex_all_special() {
-        let input = "a.b*c+d?e^f$g{h}i(j)k|l[m]n\\o";
-        let escaped = escape_regex(input);
-        assert!(escaped.contains("\\."));
-        assert!(escaped.contains("\\*"));
-        assert!(escaped.contains("\\+"));
-        assert!(escaped.contains("\\?"));
-        assert!(escaped.contains("\\^"));
-        assert!(escaped.contains("\\$"));
-        assert!(escaped.contains("\\{"));
-        assert!(escaped.contains("\\}"));
-        assert!(escaped.contains("\\("));
-        assert!(escaped.contains("\\)"));
-        assert!(escaped.contains("\\|"));
-        assert!(escaped.contains("\\["));
-        assert!(escaped.contains("\\]"));
-        assert!(escaped.contains("\\\\"));
-    }
-
     // =========================================================================
     // Error Handling Tests
     // =========================================================================
@@ -793,6 +3096,38 @@ workflow_status:
 
         let update_err = WorkflowError::UpdateError("update failed".to_string());
         assert_eq!(format!("{}", update_err), "Update failed: update failed");
+
+        let limit_err = WorkflowError::ResourceLimit("too big".to_string());
+        assert_eq!(format!("{}", limit_err), "Resource limit exceeded: too big");
+
+        let status_err = WorkflowError::InvalidStatus("a\\nb".to_string());
+        assert_eq!(format!("{}", status_err), "Invalid status: a\\nb");
+    }
+
+    #[test]
+    fn test_parse_with_limits() {
+        let yaml = "project: Demo\nshared: &s [a, b]\nworkflow_status:\n  prd: required\nextra: [*s, *s]\n";
+        assert!(parse_workflow_status(yaml).is_ok());
+
+        let limits = ParseLimits {
+            max_aliases: 1,
+            ..ParseLimits::default()
+        };
+        let result = parse_workflow_status_with_limits(yaml, &limits);
+        assert!(matches!(result, Err(WorkflowError::ResourceLimit(_))));
+
+        let limits = ParseLimits {
+            max_items: 0,
+            ..ParseLimits::default()
+        };
+        let result = parse_workflow_status_with_limits(yaml, &limits);
+        assert!(matches!(result, Err(WorkflowError::ResourceLimit(m)) if m.contains("entries")));
+
+        let (data, issues) =
+            parse_workflow_status_lenient(&format!("a: {}{}", "[".repeat(100), "]".repeat(100)));
+        assert!(data.items.is_empty());
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.starts_with("Resource limit exceeded"));
     }
 
     #[test]
@@ -926,4 +3261,82 @@ workflows:
         assert_eq!(map.get("sprint-planning"), Some(&"sm"));
         assert_eq!(map.get("test-design"), Some(&"tea"));
     }
+
+    // =========================================================================
+    // Lenient Parsing Tests
+    // =========================================================================
+
+    #[test]
+    fn test_lenient_valid_yaml_has_no_issues() {
+        let (data, issues) = parse_workflow_status_lenient(NEW_FORMAT_YAML);
+        assert!(issues.is_empty());
+        assert_eq!(data, parse_workflow_status(NEW_FORMAT_YAML).unwrap());
+    }
+
+    #[test]
+    fn test_lenient_skips_stray_tab_entry() {
+        let yaml = "project: Demo\nworkflow_status:\n  prd: required\n\tarchitecture: required\n  sprint-planning: required\n";
+        assert!(parse_workflow_status(yaml).is_err());
+
+        let (data, issues) = parse_workflow_status_lenient(yaml);
+        assert_eq!(data.project, "Demo");
+        let ids: Vec<&str> = data.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["prd", "sprint-planning"]);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 4);
+        assert_eq!(issues[0].skipped, "\tarchitecture: required");
+    }
+
+    #[test]
+    fn test_lenient_skips_nested_block() {
+        let yaml = r#"
+project: Demo
+workflows:
+  brainstorm:
+    status: complete
+    output_file: docs/brainstorm.md
+  prd:
+    status: [unclosed
+    notes: broken
+  architecture:
+    status: not_started
+"#;
+        let (data, issues) = parse_workflow_status_lenient(yaml);
+        let ids: Vec<&str> = data.items.iter().map(|i| i.id.as_str()).collect();
+        assert!(ids.contains(&"brainstorm"));
+        assert!(ids.contains(&"architecture"));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 8);
+        assert_eq!(issues[0].skipped, "    status: [unclosed");
+    }
+
+    #[test]
+    fn test_lenient_reports_multiple_issues() {
+        let yaml = "project: Demo\nworkflow_status:\n\tprd: required\n  architecture: required\n\tsprint-planning: required\n";
+        let (data, issues) = parse_workflow_status_lenient(yaml);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(data.items.len(), 1);
+        assert_eq!(data.items[0].id, "architecture");
+    }
+
+    #[test]
+    fn test_lenient_empty_input() {
+        let (data, issues) = parse_workflow_status_lenient("");
+        assert!(issues.is_empty());
+        assert!(data.items.is_empty());
+    }
+
+    #[test]
+    fn test_bad_block_range() {
+        let lines: Vec<String> = ["a:", "  b: 1", "  c:", "    d: 2", "", "  e: 3"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(bad_block(&lines, 3), Some(2..4));
+        assert_eq!(bad_block(&lines, 5), Some(3..4));
+        assert_eq!(bad_block(&lines, 100), Some(5..6));
+        assert_eq!(bad_block(&lines, 1), Some(0..6));
+        assert_eq!(bad_block(&[], 1), None);
+    }
 }
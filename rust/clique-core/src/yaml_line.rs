@@ -0,0 +1,1028 @@
+// clique-core/src/yaml_line.rs
+//! Line-level YAML scanning for in-place edits.
+//!
+//! Updates rewrite a single scalar and must leave every other byte of the
+//! document untouched, so rather than re-serializing the parsed tree they
+//! locate the value's byte range by walking block-style `key: value` lines.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// A physical line of the document
+pub(crate) struct Line<'a> {
+    /// Byte offset of the line start within the document
+    pub start: usize,
+    /// Line content without its line ending
+    pub text: &'a str,
+}
+
+/// Iterate over lines with their byte offsets, stripping `\n` / `\r\n`.
+pub(crate) fn lines(content: &str) -> impl Iterator<Item = Line<'_>> {
+    let mut offset = 0;
+    content.split_inclusive('\n').map(move |raw| {
+        let start = offset;
+        offset += raw.len();
+        Line {
+            start,
+            text: raw.trim_end_matches(['\n', '\r']),
+        }
+    })
+}
+
+/// Width of the leading whitespace of a line
+pub(crate) fn indent(text: &str) -> usize {
+    text.len() - text.trim_start_matches([' ', '\t']).len()
+}
+
+/// Whether a line carries no content (blank or comment-only)
+pub(crate) fn is_blank(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+/// Line numbers (1-based) and text of the document's comments, `#`
+/// included. Lines inside block scalars are content, not comments.
+pub(crate) fn comments(content: &str) -> Vec<(usize, &str)> {
+    let mut found = Vec::new();
+    let mut block_scalar = None;
+    for (number, line) in lines(content).enumerate() {
+        let trimmed = line.text.trim_start();
+        if let Some(owner) = block_scalar {
+            if trimmed.is_empty() || indent(line.text) > owner {
+                continue;
+            }
+            block_scalar = None;
+        }
+        if trimmed.starts_with('#') {
+            found.push((number + 1, trimmed));
+            continue;
+        }
+        let Some(entry) = parse_entry(line.text) else {
+            continue;
+        };
+        if line.text[entry.value.clone()].starts_with(['|', '>']) {
+            block_scalar = Some(entry.indent);
+        }
+        let rest = line.text[entry.value.end..].trim_start();
+        if rest.starts_with('#') {
+            found.push((number + 1, rest));
+        }
+    }
+    found
+}
+
+/// A block-style `key: value` entry parsed from one line
+#[derive(Debug, PartialEq)]
+pub(crate) struct Entry<'a> {
+    /// Column of the key (after any `- ` sequence marker)
+    pub indent: usize,
+    /// Whether the entry opens a sequence item (`- key: value`)
+    pub is_item: bool,
+    /// The key with quotes and escapes resolved
+    pub key: Cow<'a, str>,
+    /// Byte range of the raw key token within the line
+    pub key_range: Range<usize>,
+    /// Byte range of the raw value token within the line, quotes included
+    /// and trailing comment excluded; empty when the key opens a block
+    pub value: Range<usize>,
+}
+
+/// Length of a quoted scalar starting at `s[0]`, including both quotes.
+fn quoted_len(s: &str) -> Option<usize> {
+    let quote = s.chars().next()?;
+    let bytes = s.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if quote == '"' => i += 2,
+            b'\'' if quote == '\'' && bytes.get(i + 1) == Some(&b'\'') => i += 2,
+            b if b == quote as u8 => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Resolve the escapes of a quoted scalar token (quotes included).
+fn unquote(token: &str) -> Cow<'_, str> {
+    let inner = &token[1..token.len() - 1];
+    if token.starts_with('\'') {
+        if inner.contains("''") {
+            return Cow::Owned(inner.replace("''", "'"));
+        }
+        return Cow::Borrowed(inner);
+    }
+    if !inner.contains('\\') {
+        return Cow::Borrowed(inner);
+    }
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Byte length of the raw value token at the start of `s` (no comment, no
+/// trailing whitespace).
+fn value_len(s: &str) -> usize {
+    // Values start after whitespace, so a leading '#' always opens a comment
+    if s.starts_with('#') {
+        return 0;
+    }
+    if s.starts_with(['"', '\''])
+        && let Some(len) = quoted_len(s)
+    {
+        return len;
+    }
+    let bytes = s.as_bytes();
+    let end = (1..bytes.len())
+        .find(|&i| bytes[i] == b'#' && matches!(bytes[i - 1], b' ' | b'\t'))
+        .unwrap_or(bytes.len());
+    s[..end].trim_end().len()
+}
+
+/// Parse a `key: value` line. Returns `None` for blank lines, comments,
+/// bare sequence scalars and anything else that is not a mapping entry.
+pub(crate) fn parse_entry(text: &str) -> Option<Entry<'_>> {
+    let mut pos = indent(text);
+    let mut is_item = false;
+    if let Some(rest) = text[pos..].strip_prefix('-')
+        && (rest.is_empty() || rest.starts_with([' ', '\t']))
+    {
+        is_item = true;
+        pos += 1;
+        pos += indent(&text[pos..]);
+    }
+    let key_start = pos;
+    let rest = &text[pos..];
+    if rest.is_empty() || rest.starts_with('#') {
+        return None;
+    }
+
+    // Locate the key token and the ':' that terminates it
+    let (key, key_len, colon) = if rest.starts_with(['"', '\'']) {
+        let len = quoted_len(rest)?;
+        let after = &rest[len..];
+        let gap = indent(after);
+        if !after[gap..].starts_with(':') {
+            return None;
+        }
+        (unquote(&rest[..len]), len, key_start + len + gap)
+    } else {
+        let bytes = rest.as_bytes();
+        let colon = (0..bytes.len()).find(|&i| {
+            bytes[i] == b':' && matches!(bytes.get(i + 1), None | Some(b' ') | Some(b'\t'))
+        })?;
+        let key = rest[..colon].trim_end();
+        if key.is_empty() || key.contains(" #") {
+            return None;
+        }
+        (Cow::Borrowed(key), key.len(), key_start + colon)
+    };
+
+    let after_colon = colon + 1;
+    let value_start = after_colon + indent(&text[after_colon..]);
+    let value_end = value_start + value_len(&text[value_start..]);
+
+    Some(Entry {
+        indent: key_start,
+        is_item,
+        key,
+        key_range: key_start..key_start + key_len,
+        value: value_start..value_end,
+    })
+}
+
+/// Find the entry for a nested mapping key, e.g. `["workflows", "prd", "status"]`.
+///
+/// Only direct children are considered at each level, so a key with the same
+/// name elsewhere in the document never matches.
+fn find_entry<'a>(content: &'a str, path: &[&str]) -> Option<(Line<'a>, Entry<'a>)> {
+    let mut depth = 0;
+    let mut parent_indent: Option<usize> = None;
+    let mut child_indent: Option<usize> = None;
+
+    for line in lines(content) {
+        if is_blank(line.text) {
+            continue;
+        }
+        if parent_indent.is_some_and(|p| indent(line.text) <= p) {
+            return None;
+        }
+        let Some(entry) = parse_entry(line.text) else {
+            continue;
+        };
+        if entry.is_item {
+            continue;
+        }
+        let level = *child_indent.get_or_insert(entry.indent);
+        if entry.indent != level || entry.key != path[depth] {
+            continue;
+        }
+
+        depth += 1;
+        if depth == path.len() {
+            return Some((line, entry));
+        }
+        parent_indent = Some(entry.indent);
+        child_indent = None;
+    }
+
+    None
+}
+
+/// Absolute byte range of an entry's value token
+fn value_range(line: &Line, entry: &Entry) -> Range<usize> {
+    line.start + entry.value.start..line.start + entry.value.end
+}
+
+/// Find the value of a nested mapping key; see [`find_entry`].
+pub(crate) fn find_value(content: &str, path: &[&str]) -> Option<Range<usize>> {
+    find_entry(content, path).map(|(line, entry)| value_range(&line, &entry))
+}
+
+/// Find the raw key token of a nested mapping key; see [`find_entry`].
+pub(crate) fn find_key(content: &str, path: &[&str]) -> Option<Range<usize>> {
+    find_entry(content, path)
+        .map(|(line, entry)| line.start + entry.key_range.start..line.start + entry.key_range.end)
+}
+
+/// A sequence item located by [`find_item`]
+struct ItemBlock {
+    /// Column of the item's keys (after the `- ` marker)
+    indent: usize,
+    /// End of the item's last non-blank line, before its line ending
+    end: usize,
+    /// The item's direct fields with their absolute value ranges
+    fields: Vec<(String, Range<usize>)>,
+}
+
+/// Byte range of the entry at `path` and everything nested under it, from
+/// the start of its line to the end of its last child (line ending excluded).
+pub(crate) fn find_block(content: &str, path: &[&str]) -> Option<Range<usize>> {
+    let (line, entry) = find_entry(content, path)?;
+    let (end, _) = block_end(content, &line, entry.indent);
+    Some(line.start..end)
+}
+
+/// Find the sequence item under `sequence` whose `id_field` equals `id`,
+/// e.g. the item with `id: prd` under `workflow_status:`.
+fn find_item(content: &str, sequence: &[&str], id_field: &str, id: &str) -> Option<ItemBlock> {
+    let (block, opener) = find_entry(content, sequence)?;
+    if !opener.value.is_empty() {
+        return None;
+    }
+
+    let mut found: Option<ItemBlock> = None;
+    for line in lines(content).filter(|line| line.start > block.start) {
+        if is_blank(line.text) {
+            continue;
+        }
+        // The sequence ends at the next line that is not nested under it
+        // (items may sit at the same indent as the key)
+        let line_indent = indent(line.text);
+        if line_indent < opener.indent
+            || (line_indent == opener.indent && !line.text[line_indent..].starts_with('-'))
+        {
+            break;
+        }
+        let entry = parse_entry(line.text);
+
+        if let Some(item) = found.as_mut() {
+            let starts_next_item = entry.as_ref().is_some_and(|e| e.is_item);
+            if starts_next_item || line_indent < item.indent {
+                break;
+            }
+            item.end = line.start + line.text.len();
+            if let Some(entry) = entry
+                && entry.indent == item.indent
+            {
+                let range = value_range(&line, &entry);
+                item.fields.push((entry.key.into_owned(), range));
+            }
+        } else if let Some(entry) = entry
+            && entry.is_item
+            && entry.key == id_field
+            && scalar(&line.text[entry.value.clone()]) == id
+        {
+            let range = value_range(&line, &entry);
+            found = Some(ItemBlock {
+                indent: entry.indent,
+                end: line.start + line.text.len(),
+                fields: vec![(entry.key.into_owned(), range)],
+            });
+        }
+    }
+
+    found
+}
+
+/// Byte range of a whole sequence item, from the start of its `- ` line to
+/// the end of its last line (line ending excluded); see [`find_item`].
+pub(crate) fn find_item_block(
+    content: &str,
+    sequence: &[&str],
+    id_field: &str,
+    id: &str,
+) -> Option<Range<usize>> {
+    let item = find_item(content, sequence, id_field, id)?;
+    let (_, id_value) = item.fields.first()?;
+    let start = content[..id_value.start].rfind('\n').map_or(0, |i| i + 1);
+    Some(start..item.end)
+}
+
+/// Find a field of a sequence item identified by one of its fields; see
+/// [`find_item`].
+pub(crate) fn find_item_value(
+    content: &str,
+    sequence: &[&str],
+    id_field: &str,
+    id: &str,
+    field: &str,
+) -> Option<Range<usize>> {
+    find_item(content, sequence, id_field, id)?
+        .fields
+        .into_iter()
+        .find(|(key, _)| key == field)
+        .map(|(_, range)| range)
+}
+
+/// Set a field of a sequence item to the already-formatted `value`,
+/// replacing it (block scalar content included) or appending a
+/// `field: value` line to the item.
+pub(crate) fn set_item_field(
+    content: &str,
+    sequence: &[&str],
+    id_field: &str,
+    id: &str,
+    field: &str,
+    value: &str,
+) -> Option<String> {
+    let item = find_item(content, sequence, id_field, id)?;
+    if let Some((_, span)) = item.fields.iter().find(|(key, _)| key == field) {
+        return Some(replace_value(content, span.clone(), item.indent, value));
+    }
+
+    let key = format_scalar("", field, needs_quotes(field));
+    let inserted = format!(
+        "{}{}{}: {}",
+        line_ending(content),
+        " ".repeat(item.indent),
+        key,
+        value
+    );
+    Some(splice(content, item.end..item.end, &inserted))
+}
+
+/// Remove a field of a sequence item, together with anything nested under
+/// it. Returns `None` if the item or field does not exist; the identifying
+/// field itself cannot be removed.
+pub(crate) fn remove_item_field(
+    content: &str,
+    sequence: &[&str],
+    id_field: &str,
+    id: &str,
+    field: &str,
+) -> Option<String> {
+    if field == id_field {
+        return None;
+    }
+    let item = find_item(content, sequence, id_field, id)?;
+    let (_, span) = item.fields.iter().find(|(key, _)| key == field)?;
+    let start = content[..span.start].rfind('\n').map_or(0, |i| i + 1);
+
+    let mut end = span.end;
+    for line in lines(content).filter(|line| line.start > start) {
+        if is_blank(line.text) {
+            continue;
+        }
+        if indent(line.text) <= item.indent {
+            break;
+        }
+        end = line.start + line.text.len();
+    }
+    let rest = &content[end..];
+    let eol_len = if rest.starts_with("\r\n") {
+        2
+    } else {
+        usize::from(rest.starts_with('\n'))
+    };
+    if eol_len == 0 {
+        // Last line without a final newline: drop the preceding line break
+        let break_len = if content[..start].ends_with("\r\n") {
+            2
+        } else {
+            1
+        };
+        return Some(splice(content, start - break_len..end, ""));
+    }
+    Some(splice(content, start..end + eol_len, ""))
+}
+
+/// The string content of a raw scalar token, with quotes resolved
+pub(crate) fn scalar(token: &str) -> Cow<'_, str> {
+    if token.len() >= 2 && token.starts_with(['"', '\'']) && quoted_len(token) == Some(token.len())
+    {
+        unquote(token)
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Whether a plain scalar would be misread (or not read back as a string)
+pub(crate) fn needs_quotes(value: &str) -> bool {
+    value.is_empty()
+        || value.trim() != value
+        || value.contains(": ")
+        || value.ends_with(':')
+        || value.contains(" #")
+        || value.starts_with([
+            '"', '\'', '&', '*', '!', '|', '>', '%', '@', '`', '[', ']', '{', '}', '#', ',', '?',
+            '-',
+        ])
+}
+
+/// Format `value` as a replacement for the raw scalar token `original`,
+/// keeping its quoting style. Plain originals stay plain unless `quote` says
+/// the new value needs quoting, in which case double quotes are used.
+pub(crate) fn format_scalar(original: &str, value: &str, quote: bool) -> String {
+    if original.starts_with('\'') {
+        format!("'{}'", value.replace('\'', "''"))
+    } else if original.starts_with('"') || quote {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Replace a byte range of `content`
+pub(crate) fn splice(content: &str, span: Range<usize>, replacement: &str) -> String {
+    let mut updated = String::with_capacity(content.len() + replacement.len());
+    updated.push_str(&content[..span.start]);
+    updated.push_str(replacement);
+    updated.push_str(&content[span.end..]);
+    updated
+}
+
+/// The document's dominant line ending, used for inserted lines
+pub(crate) fn line_ending(content: &str) -> &'static str {
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count() - crlf;
+    if crlf > lf { "\r\n" } else { "\n" }
+}
+
+/// Byte offset of the end of the last non-blank line nested under the entry
+/// on `line` (its own end if it has no children), before any line ending.
+fn block_end(content: &str, line: &Line, entry_indent: usize) -> (usize, Option<usize>) {
+    let mut end = line.start + line.text.len();
+    let mut child_indent = None;
+    for child in lines(content).filter(|l| l.start > line.start) {
+        if is_blank(child.text) {
+            continue;
+        }
+        let child_level = indent(child.text);
+        if child_level <= entry_indent {
+            break;
+        }
+        child_indent.get_or_insert(child_level);
+        end = child.start + child.text.len();
+    }
+    (end, child_indent)
+}
+
+/// End of the value at `span` of an entry whose key sits at column
+/// `entry_indent`: past the content lines of a block scalar (`|`, `>`) or
+/// the children of a block opener, else the end of the token itself
+fn value_end(content: &str, span: &Range<usize>, entry_indent: usize) -> usize {
+    let block_scalar = content[span.clone()].starts_with(['|', '>']);
+    if !block_scalar && !span.is_empty() {
+        return span.end;
+    }
+    let mut end = span.end;
+    for line in lines(content).filter(|line| line.start > span.start) {
+        // Inside a block scalar a '#' line is content, not a comment
+        let empty = match block_scalar {
+            true => line.text.trim().is_empty(),
+            false => is_blank(line.text),
+        };
+        if empty {
+            continue;
+        }
+        if indent(line.text) <= entry_indent {
+            break;
+        }
+        end = line.start + line.text.len();
+    }
+    end
+}
+
+/// Replace the value at `span` of an entry whose key sits at column
+/// `entry_indent` with the already-formatted `value`, block scalar content
+/// and nested children included
+fn replace_value(content: &str, span: Range<usize>, entry_indent: usize, value: &str) -> String {
+    let end = value_end(content, &span, entry_indent);
+    if span.is_empty() {
+        // "field:" with nothing after it; keep a space after the colon
+        return splice(content, span.start..end, &format!(" {}", value));
+    }
+    splice(content, span.start..end, value)
+}
+
+/// Set the existing entry at `path` to the already-formatted `value`; see
+/// [`set_field`]. Returns `None` if there is no such entry.
+pub(crate) fn replace_field(content: &str, path: &[&str], value: &str) -> Option<String> {
+    let (line, entry) = find_entry(content, path)?;
+    Some(replace_value(
+        content,
+        value_range(&line, &entry),
+        entry.indent,
+        value,
+    ))
+}
+
+/// Set `field` of the mapping at `parent` to the already-formatted `value`,
+/// replacing the existing value or inserting a `field: value` line after the
+/// mapping's last child. A block scalar or nested block being replaced is
+/// removed whole. Returns `None` if `parent` is not a block mapping.
+pub(crate) fn set_field(
+    content: &str,
+    parent: &[&str],
+    field: &str,
+    value: &str,
+) -> Option<String> {
+    let mut path = parent.to_vec();
+    path.push(field);
+    if let Some(updated) = replace_field(content, &path, value) {
+        return Some(updated);
+    }
+
+    let (line, entry) = find_entry(content, parent)?;
+    if !entry.value.is_empty() {
+        return None;
+    }
+    let (end, child_indent) = block_end(content, &line, entry.indent);
+    let indent = " ".repeat(child_indent.unwrap_or(entry.indent + 2));
+    let key = format_scalar("", field, needs_quotes(field));
+    let inserted = format!("{}{}{}: {}", line_ending(content), indent, key, value);
+    Some(splice(content, end..end, &inserted))
+}
+
+/// Remove the entry at `path`, together with anything nested under it.
+/// Returns `None` if the entry does not exist.
+pub(crate) fn remove_field(content: &str, path: &[&str]) -> Option<String> {
+    let (line, entry) = find_entry(content, path)?;
+    let (end, _) = block_end(content, &line, entry.indent);
+
+    let rest = &content[end..];
+    let eol_len = if rest.starts_with("\r\n") {
+        2
+    } else {
+        usize::from(rest.starts_with('\n'))
+    };
+    if eol_len == 0 && line.start > 0 {
+        // Last line without a final newline: drop the preceding line break
+        // instead, so the document still ends without one
+        let break_len = if content[..line.start].ends_with("\r\n") {
+            2
+        } else {
+            1
+        };
+        return Some(splice(content, line.start - break_len..end, ""));
+    }
+    Some(splice(content, line.start..end + eol_len, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slice(content: &str, range: Option<Range<usize>>) -> Option<&str> {
+        range.map(|r| &content[r])
+    }
+
+    // =========================================================================
+    // Entry Parsing Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_plain_entry() {
+        let entry = parse_entry("  prd: required").expect("Should parse");
+        assert_eq!(entry.indent, 2);
+        assert!(!entry.is_item);
+        assert_eq!(entry.key, "prd");
+        assert_eq!(&"  prd: required"[entry.value], "required");
+    }
+
+    #[test]
+    fn test_parse_block_opener() {
+        let entry = parse_entry("workflows:").expect("Should parse");
+        assert_eq!(entry.key, "workflows");
+        assert!(entry.value.is_empty());
+
+        let entry = parse_entry("workflows:   # comment").expect("Should parse");
+        assert!(entry.value.is_empty());
+    }
+
+    #[test]
+    fn test_parse_quoted_keys() {
+        let entry = parse_entry(r#"  "my item": required"#).expect("Should parse");
+        assert_eq!(entry.key, "my item");
+
+        let entry = parse_entry("  'it''s': done").expect("Should parse");
+        assert_eq!(entry.key, "it's");
+
+        let entry = parse_entry(r#"  "a: b" : done"#).expect("Should parse");
+        assert_eq!(entry.key, "a: b");
+        assert_eq!(entry.key_range, 2..8);
+
+        let entry = parse_entry(r#"  "esc\"aped": done"#).expect("Should parse");
+        assert_eq!(entry.key, "esc\"aped");
+    }
+
+    #[test]
+    fn test_parse_plain_keys_with_spaces_and_colons() {
+        let entry = parse_entry("  my item: required").expect("Should parse");
+        assert_eq!(entry.key, "my item");
+
+        let entry = parse_entry("  a:b: required").expect("Should parse");
+        assert_eq!(entry.key, "a:b");
+        assert_eq!(&"  a:b: required"[entry.value], "required");
+    }
+
+    #[test]
+    fn test_parse_value_excludes_comment_and_keeps_quotes() {
+        let text = "  prd: docs/prd.md # latest";
+        let entry = parse_entry(text).expect("Should parse");
+        assert_eq!(&text[entry.value], "docs/prd.md");
+
+        let text = r#"  prd: "a # b" # note"#;
+        let entry = parse_entry(text).expect("Should parse");
+        assert_eq!(&text[entry.value], r#""a # b""#);
+
+        let text = "  url: http://x#frag  ";
+        let entry = parse_entry(text).expect("Should parse");
+        assert_eq!(&text[entry.value], "http://x#frag");
+    }
+
+    #[test]
+    fn test_parse_sequence_items() {
+        let entry = parse_entry("  - id: prd").expect("Should parse");
+        assert!(entry.is_item);
+        assert_eq!(entry.indent, 4);
+        assert_eq!(entry.key, "id");
+
+        assert!(parse_entry("  - plain scalar").is_none());
+        assert!(parse_entry("  -").is_none());
+    }
+
+    #[test]
+    fn test_parse_non_entries() {
+        assert!(parse_entry("").is_none());
+        assert!(parse_entry("   ").is_none());
+        assert!(parse_entry("# key: value").is_none());
+        assert!(parse_entry("just text").is_none());
+        assert!(parse_entry("http://example.com").is_none());
+    }
+
+    #[test]
+    fn test_comments() {
+        let content = "# header\nproject: Demo # inline\nnote: \"a # b\"\nnotes: |\n  # kept\n\n  text\n\
+                       url: http://x/#anchor\n  # indented\n";
+        assert_eq!(
+            comments(content),
+            [(1, "# header"), (2, "# inline"), (9, "# indented")]
+        );
+        assert!(comments("a: 1\n").is_empty());
+    }
+
+    #[test]
+    fn test_lines_offsets_and_endings() {
+        let content = "a: 1\r\nb: 2\nc: 3";
+        let collected: Vec<(usize, &str)> = lines(content).map(|l| (l.start, l.text)).collect();
+        assert_eq!(collected, vec![(0, "a: 1"), (6, "b: 2"), (11, "c: 3")]);
+    }
+
+    #[test]
+    fn test_format_scalar_keeps_quote_style() {
+        assert_eq!(format_scalar("required", "done", false), "done");
+        assert_eq!(format_scalar("required", "a/b", true), "\"a/b\"");
+        assert_eq!(format_scalar("'required'", "it's", false), "'it''s'");
+        assert_eq!(
+            format_scalar("\"x\"", "say \"hi\"", false),
+            r#""say \"hi\"""#
+        );
+        assert_eq!(
+            format_scalar("\"x\"", "back\\slash", false),
+            r#""back\\slash""#
+        );
+    }
+
+    #[test]
+    fn test_needs_quotes() {
+        assert!(!needs_quotes("required"));
+        assert!(!needs_quotes("docs/prd.md"));
+        assert!(!needs_quotes("http://x"));
+        assert!(needs_quotes("a: b"));
+        assert!(needs_quotes("a #b"));
+        assert!(needs_quotes("*alias"));
+        assert!(needs_quotes("- item"));
+        assert!(needs_quotes(" padded"));
+        assert!(needs_quotes(""));
+    }
+
+    // =========================================================================
+    // Path Lookup Tests
+    // =========================================================================
+
+    const DOC: &str = r#"
+project: Demo
+workflows:
+  prd:
+    output_file: docs/prd.md
+    status: not_started  # todo
+  "my item":
+    status: complete
+other:
+  prd:
+    status: untouched
+"#;
+
+    #[test]
+    fn test_find_value_nested() {
+        assert_eq!(
+            slice(DOC, find_value(DOC, &["workflows", "prd", "status"])),
+            Some("not_started")
+        );
+        assert_eq!(
+            slice(DOC, find_value(DOC, &["workflows", "my item", "status"])),
+            Some("complete")
+        );
+        assert_eq!(
+            slice(DOC, find_value(DOC, &["other", "prd", "status"])),
+            Some("untouched")
+        );
+        assert_eq!(slice(DOC, find_value(DOC, &["project"])), Some("Demo"));
+    }
+
+    #[test]
+    fn test_find_key() {
+        assert_eq!(
+            slice(DOC, find_key(DOC, &["workflows", "my item"])),
+            Some("\"my item\"")
+        );
+        assert_eq!(slice(DOC, find_key(DOC, &["other", "prd"])), Some("prd"));
+        assert_eq!(find_key(DOC, &["missing"]), None);
+    }
+
+    #[test]
+    fn test_find_value_respects_scope() {
+        assert_eq!(find_value(DOC, &["workflows", "missing", "status"]), None);
+        assert_eq!(find_value(DOC, &["prd"]), None);
+        assert_eq!(find_value(DOC, &["workflows", "status"]), None);
+    }
+
+    // =========================================================================
+    // Field Editing Tests
+    // =========================================================================
+
+    #[test]
+    fn test_line_ending() {
+        assert_eq!(line_ending("a\r\nb\r\n"), "\r\n");
+        assert_eq!(line_ending("a\nb\n"), "\n");
+        assert_eq!(line_ending("a\r\nb\nc\n"), "\n");
+        assert_eq!(line_ending("single line"), "\n");
+    }
+
+    #[test]
+    fn test_set_field_replaces_existing() {
+        let content = "items:\n  prd:\n    status: todo\n    note: old # keep\n";
+        let updated = set_field(content, &["items", "prd"], "note", "new").unwrap();
+        assert_eq!(
+            updated,
+            "items:\n  prd:\n    status: todo\n    note: new # keep\n"
+        );
+
+        let content = "items:\n  prd:\n    note:\n";
+        let updated = set_field(content, &["items", "prd"], "note", "new").unwrap();
+        assert_eq!(updated, "items:\n  prd:\n    note: new\n");
+    }
+
+    #[test]
+    fn test_set_field_replaces_block_scalars() {
+        for indicator in ["|", ">", "|-"] {
+            let content = format!(
+                "workflows:\n  prd:\n    notes: {}\n      line1\n\n      # line2\n    status: required\n",
+                indicator
+            );
+            let updated = set_field(&content, &["workflows", "prd"], "notes", "new note").unwrap();
+            assert_eq!(
+                updated, "workflows:\n  prd:\n    notes: new note\n    status: required\n",
+                "{}",
+                indicator
+            );
+        }
+
+        let nested = "a:\n  b:\n    - x\n    - y\n  c: 1\n";
+        assert_eq!(
+            set_field(nested, &["a"], "b", "z").unwrap(),
+            "a:\n  b: z\n  c: 1\n"
+        );
+        let last = "a:\n  b: >\n    folded\n";
+        assert_eq!(set_field(last, &["a"], "b", "z").unwrap(), "a:\n  b: z\n");
+    }
+
+    #[test]
+    fn test_set_field_inserts_after_last_child() {
+        let content = "items:\n  prd:\n    status: todo\n\n  other:\n    status: done\n";
+        let updated = set_field(content, &["items", "prd"], "note", "hi").unwrap();
+        assert_eq!(
+            updated,
+            "items:\n  prd:\n    status: todo\n    note: hi\n\n  other:\n    status: done\n"
+        );
+    }
+
+    #[test]
+    fn test_set_field_inserts_with_document_conventions() {
+        let content = "items:\r\n  prd:\r\n      status: todo";
+        let updated = set_field(content, &["items", "prd"], "my note", "hi").unwrap();
+        assert_eq!(
+            updated,
+            "items:\r\n  prd:\r\n      status: todo\r\n      my note: hi"
+        );
+
+        let content = "items:\n  prd:\n";
+        let updated = set_field(content, &["items", "prd"], "status", "todo").unwrap();
+        assert_eq!(updated, "items:\n  prd:\n    status: todo\n");
+    }
+
+    #[test]
+    fn test_set_field_rejects_scalar_parent() {
+        let content = "items:\n  prd: required\n";
+        assert_eq!(set_field(content, &["items", "prd"], "note", "x"), None);
+        assert_eq!(set_field(content, &["missing"], "note", "x"), None);
+    }
+
+    #[test]
+    fn test_remove_field() {
+        let content = "items:\n  prd:\n    status: done\n    output_file: a.md\n  other: x\n";
+        let updated = remove_field(content, &["items", "prd", "output_file"]).unwrap();
+        assert_eq!(updated, "items:\n  prd:\n    status: done\n  other: x\n");
+
+        let updated = remove_field(content, &["items", "prd"]).unwrap();
+        assert_eq!(updated, "items:\n  other: x\n");
+
+        assert_eq!(remove_field(content, &["items", "missing"]), None);
+    }
+
+    #[test]
+    fn test_remove_last_line_keeps_missing_final_newline() {
+        let content = "items:\r\n  prd:\r\n    status: done\r\n    output_file: a.md";
+        let updated = remove_field(content, &["items", "prd", "output_file"]).unwrap();
+        assert_eq!(updated, "items:\r\n  prd:\r\n    status: done");
+    }
+
+    #[test]
+    fn test_find_item_value() {
+        let content = r#"
+workflow_status:
+  - id: brainstorm
+    phase: 0
+    status: "complete"
+  - id: "prd"
+    phase: 1
+    status: required
+notes:
+  - id: prd
+    status: elsewhere
+"#;
+        assert_eq!(
+            slice(
+                content,
+                find_item_value(content, &["workflow_status"], "id", "prd", "status")
+            ),
+            Some("required")
+        );
+        assert_eq!(
+            slice(
+                content,
+                find_item_value(content, &["workflow_status"], "id", "brainstorm", "status")
+            ),
+            Some("\"complete\"")
+        );
+        assert_eq!(
+            find_item_value(content, &["workflow_status"], "id", "missing", "status"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_set_item_field() {
+        let content = "workflow_status:\n- id: prd\n  status: required\n- id: other\n  status: optional\nproject: x\n";
+        let updated =
+            set_item_field(content, &["workflow_status"], "id", "prd", "note", "hi").unwrap();
+        assert_eq!(
+            updated,
+            "workflow_status:\n- id: prd\n  status: required\n  note: hi\n- id: other\n  status: optional\nproject: x\n"
+        );
+
+        let updated = set_item_field(
+            content,
+            &["workflow_status"],
+            "id",
+            "other",
+            "status",
+            "done",
+        )
+        .unwrap();
+        assert!(updated.contains("- id: other\n  status: done\nproject: x"));
+
+        let updated =
+            set_item_field(content, &["workflow_status"], "id", "other", "note", "last").unwrap();
+        assert!(updated.contains("  status: optional\n  note: last\nproject: x\n"));
+
+        assert_eq!(
+            set_item_field(content, &["workflow_status"], "id", "missing", "note", "x"),
+            None
+        );
+
+        let block =
+            "workflow_status:\n- id: prd\n  note: |-\n    one\n    two\n  status: required\n";
+        assert_eq!(
+            set_item_field(block, &["workflow_status"], "id", "prd", "note", "hi").unwrap(),
+            "workflow_status:\n- id: prd\n  note: hi\n  status: required\n"
+        );
+    }
+
+    #[test]
+    fn test_find_blocks() {
+        let content = "workflows:\n  prd:\n    status: done\n\n    notes: x\n  other: y\n";
+        assert_eq!(
+            slice(content, find_block(content, &["workflows", "prd"])),
+            Some("  prd:\n    status: done\n\n    notes: x")
+        );
+        assert_eq!(
+            slice(content, find_block(content, &["workflows", "other"])),
+            Some("  other: y")
+        );
+        assert_eq!(find_block(content, &["workflows", "missing"]), None);
+
+        let seq = "workflow_status:\n  - id: prd\n    status: required\n  - id: other\n";
+        assert_eq!(
+            slice(seq, find_item_block(seq, &["workflow_status"], "id", "prd")),
+            Some("  - id: prd\n    status: required")
+        );
+        assert_eq!(
+            slice(
+                seq,
+                find_item_block(seq, &["workflow_status"], "id", "other")
+            ),
+            Some("  - id: other")
+        );
+        assert_eq!(find_item_block(seq, &["workflow_status"], "id", "x"), None);
+    }
+
+    #[test]
+    fn test_remove_item_field() {
+        let content = "workflow_status:\n- id: prd\n  note: hi\n  depends_on:\n    - brief\n  status: required\n- id: other\n  status: optional";
+        let seq = ["workflow_status"];
+        let updated = remove_item_field(content, &seq, "id", "prd", "note").unwrap();
+        assert!(updated.starts_with("workflow_status:\n- id: prd\n  depends_on:\n"));
+
+        let updated = remove_item_field(content, &seq, "id", "prd", "depends_on").unwrap();
+        assert!(updated.contains("- id: prd\n  note: hi\n  status: required\n- id: other"));
+
+        // Last line without a final newline
+        let updated = remove_item_field(content, &seq, "id", "other", "status").unwrap();
+        assert!(updated.ends_with("  status: required\n- id: other"));
+
+        assert_eq!(remove_item_field(content, &seq, "id", "prd", "id"), None);
+        assert_eq!(
+            remove_item_field(content, &seq, "id", "prd", "missing"),
+            None
+        );
+        assert_eq!(
+            remove_item_field(content, &seq, "id", "missing", "note"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_item_value_field_must_belong_to_item() {
+        let content =
+            "workflow_status:\n  - id: prd\n    phase: 1\n  - id: other\n    status: required\n";
+        assert_eq!(
+            find_item_value(content, &["workflow_status"], "id", "prd", "status"),
+            None
+        );
+    }
+}
@@ -0,0 +1,392 @@
+//! Clique Native Bindings
+//!
+//! N-API bindings for Node hosts that can load a native module (CLI
+//! wrappers, CI bots). The exports mirror `clique-wasm` name for name, so
+//! a loader can require either module; only `wasm_size_wasm`, which
+//! describes the .wasm itself, has no native counterpart. Objects cross the
+//! boundary as JSON values instead of `JsValue`s.
+
+use clique_core::settings::{self, InitOptions, ParserConfig};
+use clique_core::{SprintDocument, WorkflowDocument, aggregate::Project};
+use napi::{Error, Result};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A core error as a JS `Error` with the same message clique-wasm gives
+fn reason(error: impl std::fmt::Display) -> Error {
+    Error::from_reason(error.to_string())
+}
+
+fn to_json(value: &impl Serialize) -> Result<Value> {
+    serde_json::to_value(value).map_err(reason)
+}
+
+/// Deserialize an optional options object; `undefined` and `null` give the
+/// defaults
+fn options<T: Default + for<'de> Deserialize<'de>>(value: Option<Value>) -> Result<T> {
+    match value {
+        None | Some(Value::Null) => Ok(T::default()),
+        Some(value) => serde_json::from_value(value).map_err(reason),
+    }
+}
+
+/// Apply parser settings that persist for subsequent calls.
+/// `options` is `{ limits?, phaseMap?, agentMap? }`; omitted fields, or no
+/// options at all, restore the defaults.
+#[napi(js_name = "init_wasm")]
+pub fn init(options_js: Option<Value>) -> Result<()> {
+    let options: InitOptions = options(options_js)?;
+    settings::set(settings::Settings::from_init(options));
+    Ok(())
+}
+
+/// Apply the extension's parser settings to subsequent calls.
+/// `config` is `{ statusAliases?, phaseMap?, sortOrder? }`; limits and the
+/// agent map set by `init_wasm` are kept.
+#[napi(js_name = "set_parser_config_wasm")]
+pub fn set_parser_config(config_js: Option<Value>) -> Result<()> {
+    let config: ParserConfig = options(config_js)?;
+    settings::configure(config);
+    Ok(())
+}
+
+/// Apply the status aliases, phases and agents of a `.clique.toml`
+/// document to subsequent calls, keeping the limits set by `init_wasm`.
+#[napi(js_name = "set_config_toml_wasm")]
+pub fn set_config_toml(content: String) -> Result<()> {
+    let config = clique_core::config::parse(&content).map_err(reason)?;
+    settings::configure_from(&config);
+    Ok(())
+}
+
+/// Parse workflow status from YAML content.
+#[napi(js_name = "parse_workflow_status_wasm")]
+pub fn parse_workflow_status(yaml_content: String) -> Result<Value> {
+    to_json(&settings::parse_workflow(&yaml_content).map_err(reason)?)
+}
+
+/// Parse workflow status, skipping over malformed blocks.
+/// Returns a `[WorkflowData, ParseIssue[]]` pair.
+#[napi(js_name = "parse_workflow_status_lenient_wasm")]
+pub fn parse_workflow_status_lenient(yaml_content: String) -> Result<Value> {
+    to_json(&clique_core::parse_workflow_status_lenient(&yaml_content))
+}
+
+/// Parse workflow status from YAML content into a JSON string.
+#[napi(js_name = "parse_workflow_status_json_wasm")]
+pub fn parse_workflow_status_json(yaml_content: String) -> Result<String> {
+    let result = settings::parse_workflow(&yaml_content).map_err(reason)?;
+    serde_json::to_string(&result).map_err(reason)
+}
+
+/// Parse workflow status and collect editor diagnostics.
+#[napi(js_name = "parse_workflow_with_diagnostics_wasm")]
+pub fn parse_workflow_with_diagnostics(yaml_content: String) -> Result<Value> {
+    to_json(&clique_core::workflow_diagnostics(&yaml_content))
+}
+
+/// Parse sprint status from YAML content.
+#[napi(js_name = "parse_sprint_status_wasm")]
+pub fn parse_sprint_status(yaml_content: String) -> Result<Value> {
+    to_json(&settings::parse_sprint(&yaml_content).map_err(reason)?)
+}
+
+/// Parse sprint status from YAML content into a JSON string.
+#[napi(js_name = "parse_sprint_status_json_wasm")]
+pub fn parse_sprint_status_json(yaml_content: String) -> Result<String> {
+    let result = settings::parse_sprint(&yaml_content).map_err(reason)?;
+    serde_json::to_string(&result).map_err(reason)
+}
+
+/// Parse sprint status and collect editor diagnostics.
+#[napi(js_name = "validate_sprint_wasm")]
+pub fn validate_sprint(yaml_content: String) -> Result<Value> {
+    to_json(&clique_core::sprint_diagnostics(&yaml_content))
+}
+
+/// Update workflow item status in YAML content.
+#[napi(js_name = "update_workflow_status_wasm")]
+pub fn update_workflow_status(
+    content: String,
+    item_id: String,
+    new_status: String,
+) -> Result<String> {
+    clique_core::update_workflow_status(&content, &item_id, &new_status).map_err(reason)
+}
+
+/// Mark a workflow item complete, optionally recording its output file.
+#[napi(js_name = "complete_item_wasm")]
+pub fn complete_item(
+    content: String,
+    item_id: String,
+    output_file: Option<String>,
+) -> Result<String> {
+    clique_core::complete_item(&content, &item_id, output_file.as_deref()).map_err(reason)
+}
+
+/// Reopen a completed workflow item.
+#[napi(js_name = "reopen_item_wasm")]
+pub fn reopen_item(content: String, item_id: String) -> Result<String> {
+    clique_core::reopen_item(&content, &item_id).map_err(reason)
+}
+
+/// Check that workflow YAML survives a parse/serialize round trip.
+/// Returns null when lossless, otherwise the RoundtripReport.
+#[napi(js_name = "roundtrip_check_wasm")]
+pub fn roundtrip_check(content: String) -> Result<Value> {
+    match clique_core::roundtrip_check(&content) {
+        Ok(()) => Ok(Value::Null),
+        Err(report) => to_json(&report),
+    }
+}
+
+/// Update story status in YAML content.
+#[napi(js_name = "update_story_status_wasm")]
+pub fn update_story_status(
+    content: String,
+    story_id: String,
+    new_status: String,
+) -> Result<String> {
+    clique_core::update_story_status(&content, &story_id, &new_status).map_err(reason)
+}
+
+/// One `{ id, status }` entry of a batch status update
+#[derive(Deserialize)]
+struct StatusUpdate {
+    id: String,
+    status: String,
+}
+
+/// `(id, status)` pairs from an array of `{ id, status }` objects
+fn status_updates(updates_js: Value) -> Result<Vec<(String, String)>> {
+    let updates: Vec<StatusUpdate> = serde_json::from_value(updates_js).map_err(reason)?;
+    Ok(updates.into_iter().map(|u| (u.id, u.status)).collect())
+}
+
+fn as_pairs(updates: &[(String, String)]) -> Vec<(&str, &str)> {
+    updates
+        .iter()
+        .map(|(id, status)| (id.as_str(), status.as_str()))
+        .collect()
+}
+
+/// Update several workflow item statuses in one call.
+#[napi(js_name = "update_workflow_statuses_wasm")]
+pub fn update_workflow_statuses(content: String, updates_js: Value) -> Result<String> {
+    let updates = status_updates(updates_js)?;
+    clique_core::update_workflow_statuses(&content, &as_pairs(&updates)).map_err(reason)
+}
+
+/// Update several story statuses in one call.
+#[napi(js_name = "update_story_statuses_wasm")]
+pub fn update_story_statuses(content: String, updates_js: Value) -> Result<String> {
+    let updates = status_updates(updates_js)?;
+    clique_core::update_story_statuses(&content, &as_pairs(&updates)).map_err(reason)
+}
+
+/// Compare two versions of a workflow file item by item.
+#[napi(js_name = "diff_workflow_wasm")]
+pub fn diff_workflow(old_content: String, new_content: String) -> Result<Value> {
+    let old = settings::parse_workflow(&old_content).map_err(reason)?;
+    let new = settings::parse_workflow(&new_content).map_err(reason)?;
+    to_json(&clique_core::diff_workflow(&old, &new))
+}
+
+/// Three-way merge of sprint status files.
+#[napi(js_name = "merge_sprint_wasm")]
+pub fn merge_sprint(base: String, ours: String, theirs: String) -> Result<Value> {
+    to_json(&clique_core::merge_sprint(&base, &ours, &theirs).map_err(reason)?)
+}
+
+/// Story counts for a sprint status file.
+#[napi(js_name = "sprint_stats_wasm")]
+pub fn sprint_stats(content: String) -> Result<Value> {
+    let project = Project {
+        workflow: None,
+        sprint: Some(settings::parse_sprint(&content).map_err(reason)?),
+    };
+    to_json(&project.stats())
+}
+
+/// The parsed document held by a [`CliqueDocument`]
+enum OpenDocument {
+    Workflow(WorkflowDocument),
+    Sprint(SprintDocument),
+}
+
+/// A status file kept parsed between calls.
+#[napi]
+pub struct CliqueDocument {
+    inner: OpenDocument,
+}
+
+#[napi]
+impl CliqueDocument {
+    /// Open a workflow status file.
+    #[napi(factory, js_name = "fromWorkflow")]
+    pub fn from_workflow(content: String) -> Result<Self> {
+        Ok(Self {
+            inner: OpenDocument::Workflow(WorkflowDocument::parse(&content).map_err(reason)?),
+        })
+    }
+
+    /// Open a sprint status file.
+    #[napi(factory, js_name = "fromSprint")]
+    pub fn from_sprint(content: String) -> Result<Self> {
+        Ok(Self {
+            inner: OpenDocument::Sprint(SprintDocument::parse(&content).map_err(reason)?),
+        })
+    }
+
+    /// The parsed WorkflowData or SprintData.
+    #[napi(js_name = "getData")]
+    pub fn get_data(&self) -> Result<Value> {
+        match &self.inner {
+            OpenDocument::Workflow(doc) => to_json(doc.data()),
+            OpenDocument::Sprint(doc) => to_json(doc.data()),
+        }
+    }
+
+    /// Set an item's or story's status; the document is unchanged on error.
+    #[napi(js_name = "updateStatus")]
+    pub fn update_status(&mut self, id: String, status: String) -> Result<()> {
+        match &mut self.inner {
+            OpenDocument::Workflow(doc) => doc.update_status(&id, &status).map_err(reason),
+            OpenDocument::Sprint(doc) => doc.update_status(&id, &status).map_err(reason),
+        }
+    }
+
+    /// The document's current YAML content.
+    #[napi]
+    pub fn serialize(&self) -> String {
+        match &self.inner {
+            OpenDocument::Workflow(doc) => doc.content().to_string(),
+            OpenDocument::Sprint(doc) => doc.content().to_string(),
+        }
+    }
+}
+
+/// Check if a file path is inside the workspace root.
+#[napi(js_name = "is_inside_workspace_wasm")]
+pub fn is_inside_workspace(file_path: String, workspace_root: String) -> bool {
+    clique_core::is_inside_workspace(&file_path, &workspace_root)
+}
+
+/// Get the file path if it is inside the workspace root.
+#[napi(js_name = "get_validated_path_wasm")]
+pub fn get_validated_path(file_path: String, workspace_root: String) -> Option<String> {
+    clique_core::get_validated_path(&file_path, &workspace_root)
+}
+
+/// Validate that a file path is inside the workspace root.
+#[napi(js_name = "validate_path_wasm")]
+pub fn validate_path(file_path: String, workspace_root: String) -> Result<String> {
+    clique_core::validate_path(&file_path, &workspace_root)
+        .map(|path| path.into_string())
+        .map_err(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const WORKFLOW: &str = "workflow_status:\n  brainstorm: required\n  prd: required\n";
+    const SPRINT: &str = "development_status:\n  epic-1: backlog\n  1-a: backlog\n";
+
+    // =========================================================================
+    // Parse Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_exports() {
+        let data = parse_workflow_status(WORKFLOW.to_string()).unwrap();
+        assert_eq!(data["items"][0]["id"], "brainstorm");
+        let json = parse_sprint_status_json(SPRINT.to_string()).unwrap();
+        assert!(json.contains("\"id\":\"1-a\""));
+
+        let error = parse_workflow_status("[oops".to_string()).unwrap_err();
+        assert!(!error.reason.is_empty());
+        assert!(validate_sprint("[oops".to_string()).unwrap()["data"].is_null());
+    }
+
+    #[test]
+    fn test_settings_exports() {
+        init(Some(json!({"agentMap": {"prd": "po"}}))).unwrap();
+        set_parser_config(Some(json!({"sortOrder": "id"}))).unwrap();
+        let data = parse_workflow_status(WORKFLOW.to_string()).unwrap();
+        assert_eq!(data["items"][1]["agent"], "po");
+        assert!(init(Some(json!(3))).is_err());
+        assert!(set_parser_config(Some(json!({"sortOrder": "random"}))).is_err());
+
+        init(None).unwrap();
+        set_parser_config(Some(Value::Null)).unwrap();
+        assert_eq!(settings::current(), settings::Settings::default());
+    }
+
+    #[test]
+    fn test_set_config_toml() {
+        set_config_toml("[status-aliases]\nshipped = \"done\"\n".to_string()).unwrap();
+        let data = parse_sprint_status(
+            "development_status:\n  epic-1: backlog\n  1-a: shipped\n".to_string(),
+        )
+        .unwrap();
+        assert_eq!(data["epics"][0]["stories"][0]["status"], "done");
+        assert!(set_config_toml("[status-aliases\n".to_string()).is_err());
+
+        set_config_toml(String::new()).unwrap();
+        assert_eq!(settings::current(), settings::Settings::default());
+    }
+
+    // =========================================================================
+    // Update Tests
+    // =========================================================================
+
+    #[test]
+    fn test_update_exports() {
+        let updated =
+            update_story_status(SPRINT.to_string(), "1-a".to_string(), "done".to_string()).unwrap();
+        assert!(updated.contains("1-a: done"));
+
+        let updates = json!([{"id": "prd", "status": "docs/prd.md"}, {"id": "brainstorm", "status": "skipped"}]);
+        let updated = update_workflow_statuses(WORKFLOW.to_string(), updates).unwrap();
+        assert!(updated.contains("brainstorm: skipped"));
+        assert!(update_story_statuses(SPRINT.to_string(), json!({})).is_err());
+    }
+
+    #[test]
+    fn test_clique_document() {
+        let mut doc = CliqueDocument::from_sprint(SPRINT.to_string()).unwrap();
+        doc.update_status("1-a".to_string(), "review".to_string())
+            .unwrap();
+        assert_eq!(
+            doc.get_data().unwrap()["epics"][0]["stories"][0]["status"],
+            "review"
+        );
+        assert!(doc.serialize().contains("1-a: review"));
+        assert!(
+            doc.update_status("9-x".to_string(), "done".to_string())
+                .is_err()
+        );
+        assert!(CliqueDocument::from_workflow("[oops".to_string()).is_err());
+    }
+
+    // =========================================================================
+    // Path Tests
+    // =========================================================================
+
+    #[test]
+    fn test_path_exports() {
+        let root = "/workspace".to_string();
+        assert!(is_inside_workspace(
+            "/workspace/a.yaml".to_string(),
+            root.clone()
+        ));
+        assert_eq!(
+            get_validated_path("/etc/passwd".to_string(), root.clone()),
+            None
+        );
+        assert!(validate_path("../x".to_string(), root).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Clique WASI Commands
+//!
+//! A stdin/stdout front end to the core library for sandboxed hosts:
+//! serverless functions and CI runners that can run a `wasm32-wasip1`
+//! module but have no Node.js. Each command reads one status file from
+//! stdin and writes JSON or the updated YAML to stdout.
+
+use clique_core::diagnostics::{Diagnosed, Severity};
+use clique_core::{
+    SprintError, WorkflowError, parse_sprint_status, parse_workflow_status, sprint_diagnostics,
+    update_story_status, update_workflow_status, workflow_diagnostics,
+};
+use serde::Serialize;
+use thiserror::Error;
+
+/// Usage summary printed for `help` and after usage errors
+pub const USAGE: &str = "\
+usage: clique-wasi <command> [args] < status.yaml
+
+commands:
+  parse-workflow                 print the workflow status as JSON
+  parse-sprint                   print the sprint status as JSON
+  update-workflow <id> <status>  print the workflow file with one status changed
+  update-story <id> <status>     print the sprint file with one status changed
+  check-workflow                 print diagnostics as JSON; fails on errors
+  check-sprint                   print diagnostics as JSON; fails on errors
+  help                           print this message";
+
+/// A command line, parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    ParseWorkflow,
+    ParseSprint,
+    UpdateWorkflow { id: String, status: String },
+    UpdateStory { id: String, status: String },
+    CheckWorkflow,
+    CheckSprint,
+    Help,
+}
+
+#[derive(Error, Debug)]
+pub enum CommandError {
+    #[error("{0}")]
+    Usage(String),
+    #[error(transparent)]
+    Workflow(#[from] WorkflowError),
+    #[error(transparent)]
+    Sprint(#[from] SprintError),
+    #[error("Failed to read input: {0}")]
+    Input(#[from] std::io::Error),
+    #[error("Failed to serialize output: {0}")]
+    Serialize(#[from] serde_json::Error),
+    /// The check ran; its diagnostics are the output and include errors
+    #[error("{0}")]
+    CheckFailed(String),
+}
+
+impl CommandError {
+    /// Process exit code: 2 for usage errors, 1 for everything else
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CommandError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl Command {
+    /// Parse the arguments after the program name.
+    pub fn from_args<I, S>(args: I) -> Result<Self, CommandError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let args: Vec<String> = args.into_iter().map(Into::into).collect();
+        let update = |args: &[String]| match args {
+            [id, status] => Ok((id.clone(), status.clone())),
+            _ => Err(CommandError::Usage(
+                "expected an ID and a status".to_string(),
+            )),
+        };
+        let no_args = |command: Command, rest: &[String]| match rest {
+            [] => Ok(command),
+            _ => Err(CommandError::Usage(format!(
+                "unexpected argument: {}",
+                rest[0]
+            ))),
+        };
+        let Some((name, rest)) = args.split_first() else {
+            return Err(CommandError::Usage("missing command".to_string()));
+        };
+        match name.as_str() {
+            "parse-workflow" => no_args(Command::ParseWorkflow, rest),
+            "parse-sprint" => no_args(Command::ParseSprint, rest),
+            "check-workflow" => no_args(Command::CheckWorkflow, rest),
+            "check-sprint" => no_args(Command::CheckSprint, rest),
+            "update-workflow" => {
+                let (id, status) = update(rest)?;
+                Ok(Command::UpdateWorkflow { id, status })
+            }
+            "update-story" => {
+                let (id, status) = update(rest)?;
+                Ok(Command::UpdateStory { id, status })
+            }
+            "help" | "--help" | "-h" => Ok(Command::Help),
+            other => Err(CommandError::Usage(format!("unknown command: {}", other))),
+        }
+    }
+
+    /// Run against the contents of stdin, returning what to print.
+    pub fn run(&self, input: &str) -> Result<String, CommandError> {
+        match self {
+            Command::ParseWorkflow => Ok(serde_json::to_string(&parse_workflow_status(input)?)?),
+            Command::ParseSprint => Ok(serde_json::to_string(&parse_sprint_status(input)?)?),
+            Command::UpdateWorkflow { id, status } => {
+                Ok(update_workflow_status(input, id, status)?)
+            }
+            Command::UpdateStory { id, status } => Ok(update_story_status(input, id, status)?),
+            Command::CheckWorkflow => check_output(&workflow_diagnostics(input)),
+            Command::CheckSprint => check_output(&sprint_diagnostics(input)),
+            Command::Help => Ok(USAGE.to_string()),
+        }
+    }
+}
+
+/// Diagnostics as JSON, failing when any of them is an error
+fn check_output<T: Serialize>(result: &Diagnosed<T>) -> Result<String, CommandError> {
+    let json = serde_json::to_string(result)?;
+    if result
+        .diagnostics
+        .iter()
+        .any(|d| d.severity == Severity::Error)
+    {
+        Err(CommandError::CheckFailed(json))
+    } else {
+        Ok(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKFLOW: &str = "workflow_status:\n  prd: required\n";
+    const SPRINT: &str = "development_status:\n  epic-1: backlog\n  1-a: backlog\n";
+
+    // =========================================================================
+    // Argument Tests
+    // =========================================================================
+
+    #[test]
+    fn test_from_args() {
+        assert_eq!(
+            Command::from_args(["parse-workflow"]).unwrap(),
+            Command::ParseWorkflow
+        );
+        assert_eq!(
+            Command::from_args(["update-story", "1-a", "done"]).unwrap(),
+            Command::UpdateStory {
+                id: "1-a".to_string(),
+                status: "done".to_string(),
+            }
+        );
+        assert_eq!(Command::from_args(["--help"]).unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        for args in [
+            vec![],
+            vec!["bogus"],
+            vec!["update-workflow", "prd"],
+            vec!["parse-sprint", "extra"],
+        ] {
+            let error = Command::from_args(args).unwrap_err();
+            assert!(matches!(error, CommandError::Usage(_)));
+            assert_eq!(error.exit_code(), 2);
+        }
+    }
+
+    // =========================================================================
+    // Command Tests
+    // =========================================================================
+
+    #[test]
+    fn test_parse_commands() {
+        let json = Command::ParseWorkflow.run(WORKFLOW).unwrap();
+        assert!(json.contains("\"id\":\"prd\""));
+        let json = Command::ParseSprint.run(SPRINT).unwrap();
+        assert!(json.contains("\"id\":\"1-a\""));
+
+        let error = Command::ParseWorkflow.run("[oops").unwrap_err();
+        assert!(matches!(error, CommandError::Workflow(_)));
+        assert_eq!(error.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_update_commands() {
+        let command = Command::UpdateWorkflow {
+            id: "prd".to_string(),
+            status: "docs/prd.md".to_string(),
+        };
+        assert_eq!(
+            command.run(WORKFLOW).unwrap(),
+            "workflow_status:\n  prd: \"docs/prd.md\"\n"
+        );
+
+        let command = Command::UpdateStory {
+            id: "9-missing".to_string(),
+            status: "done".to_string(),
+        };
+        assert!(matches!(
+            command.run(SPRINT),
+            Err(CommandError::Sprint(SprintError::StoryNotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn test_check_commands() {
+        let json = Command::CheckSprint.run(SPRINT).unwrap();
+        assert!(json.contains("\"diagnostics\":[]"));
+
+        match Command::CheckWorkflow.run("workflows:\n  prd: [oops\n") {
+            Err(CommandError::CheckFailed(json)) => {
+                assert!(json.contains("\"severity\":\"error\""))
+            }
+            other => panic!("expected a failed check, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! `clique-wasi` entry point: arguments in, stdin parsed, result on stdout.
+
+use clique_wasi::{Command, CommandError, USAGE};
+use std::io::Read;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let result = Command::from_args(std::env::args().skip(1)).and_then(|command| {
+        let mut input = String::new();
+        if command != Command::Help {
+            std::io::stdin().read_to_string(&mut input)?;
+        }
+        command.run(&input)
+    });
+    match result {
+        Ok(output) => {
+            println!("{}", output.trim_end_matches('\n'));
+            ExitCode::SUCCESS
+        }
+        Err(CommandError::CheckFailed(output)) => {
+            println!("{}", output);
+            ExitCode::from(1)
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            if let CommandError::Usage(_) = error {
+                eprintln!("\n{}", USAGE);
+            }
+            ExitCode::from(error.exit_code() as u8)
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! How the module was built, and the size its .wasm must stay under.
+//!
+//! The .wasm ships inside the VS Code extension, so CI builds it with the
+//! `min-size` profile (`opt-level = "z"`, then `wasm-opt -Oz`; see
+//! `scripts/build-wasm-min.sh`) and fails when the file outgrows the budget
+//! (`scripts/check-wasm-size.ts`). The size work needs no feature: every
+//! build uses the hand-written ID matchers instead of a regex engine, so the
+//! profile alone decides how small the module gets. The budget lives here
+//! so the check reads it from the module it is measuring.
+
+use serde::{Deserialize, Serialize};
+
+/// Size budget in bytes for the `min-size` profile build, after wasm-opt -Oz
+pub const SIZE_BUDGET: usize = 896 * 1024;
+
+/// Build facts reported by `wasm_size_wasm`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasmSize {
+    /// Largest acceptable .wasm, in bytes
+    pub budget: usize,
+}
+
+impl WasmSize {
+    /// Bytes by which a .wasm of `actual` bytes exceeds the budget, if it does
+    pub fn overage(&self, actual: usize) -> Option<usize> {
+        actual.checked_sub(self.budget).filter(|&over| over > 0)
+    }
+}
+
+/// Build facts for this module
+pub fn wasm_size() -> WasmSize {
+    WasmSize {
+        budget: SIZE_BUDGET,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // Size Budget Tests
+    // =========================================================================
+
+    #[test]
+    fn test_wasm_size_reports_budget() {
+        assert_eq!(wasm_size().budget, SIZE_BUDGET);
+    }
+
+    #[test]
+    fn test_overage() {
+        let size = WasmSize { budget: 100 };
+        assert_eq!(size.overage(99), None);
+        assert_eq!(size.overage(100), None);
+        assert_eq!(size.overage(130), Some(30));
+
+        let json = serde_json::to_string(&size).unwrap();
+        assert_eq!(json, r#"{"budget":100}"#);
+    }
+}
@@ -3,21 +3,115 @@
 //! WebAssembly bindings for the Clique core library,
 //! exposing workflow and sprint parsing functions to JavaScript.
 
-use clique_core::is_inside_workspace;
+#[cfg(target_arch = "wasm32")]
+use clique_core::settings;
 #[cfg(target_arch = "wasm32")]
 use clique_core::{
-    parse_sprint_status, parse_workflow_status, update_story_status, update_workflow_status,
+    SprintDocument, WorkflowDocument, aggregate::Project, complete_item, diff_workflow,
+    merge_sprint, parse_workflow_status_lenient, reopen_item, roundtrip_check, sprint_diagnostics,
+    update_story_status, update_story_statuses, update_workflow_status, update_workflow_statuses,
+    validate_path, workflow_diagnostics,
 };
+use clique_core::{get_validated_path, is_inside_workspace};
+#[cfg(target_arch = "wasm32")]
+use serde::Deserialize;
 #[cfg(target_arch = "wasm32")]
 use serde_wasm_bindgen;
 use wasm_bindgen::prelude::*;
 
+pub mod build_info;
+
+/// Set up the module: install a panic hook that reports Rust panics on the
+/// console, and apply parser settings that persist for subsequent calls.
+/// `options` is `{ limits?, phaseMap?, agentMap? }`; omitted fields, or no
+/// options at all, restore the defaults.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn init_wasm(options: JsValue) -> Result<(), JsError> {
+    console_error_panic_hook::set_once();
+
+    let options: settings::InitOptions = if options.is_undefined() || options.is_null() {
+        settings::InitOptions::default()
+    } else {
+        serde_wasm_bindgen::from_value(options).map_err(|e| JsError::new(&e.to_string()))?
+    };
+    settings::set(settings::Settings::from_init(options));
+    Ok(())
+}
+
+/// Apply the extension's parser settings to subsequent calls.
+/// `config` is `{ statusAliases?, phaseMap?, sortOrder? }` with `sortOrder`
+/// one of `"phase"`, `"id"` or `"status"`; omitted fields restore their
+/// defaults. Limits and the agent map set by `init_wasm` are kept.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_parser_config_wasm(config_js: JsValue) -> Result<(), JsError> {
+    let config: settings::ParserConfig = if config_js.is_undefined() || config_js.is_null() {
+        settings::ParserConfig::default()
+    } else {
+        serde_wasm_bindgen::from_value(config_js).map_err(|e| JsError::new(&e.to_string()))?
+    };
+    settings::configure(config);
+    Ok(())
+}
+
+/// Apply the status aliases, phases and agents of a `.clique.toml`
+/// document to subsequent calls, keeping the limits set by `init_wasm`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_config_toml_wasm(content: &str) -> Result<(), JsError> {
+    let config = clique_core::config::parse(content).map_err(|e| JsError::new(&e.to_string()))?;
+    settings::configure_from(&config);
+    Ok(())
+}
+
+/// The size budget the module's .wasm is held to: `{ budget }`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn wasm_size_wasm() -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(&build_info::wasm_size()).map_err(|e| JsError::new(&e.to_string()))
+}
+
 /// Parse workflow status from YAML content.
 /// Returns WorkflowData as a JS value or error.
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub fn parse_workflow_status_wasm(yaml_content: &str) -> Result<JsValue, JsError> {
-    let result = parse_workflow_status(yaml_content).map_err(|e| JsError::new(&e.to_string()))?;
+    let result =
+        settings::parse_workflow(yaml_content).map_err(|e| JsError::new(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Parse workflow status, skipping over malformed blocks.
+/// Returns a `[WorkflowData, ParseIssue[]]` pair as a JS value.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn parse_workflow_status_lenient_wasm(yaml_content: &str) -> Result<JsValue, JsError> {
+    let result = parse_workflow_status_lenient(yaml_content);
+
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Parse workflow status from YAML content.
+/// Returns WorkflowData serialized as a JSON string, for callers that find
+/// `JSON.parse` faster than converting a large JS value.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn parse_workflow_status_json_wasm(yaml_content: &str) -> Result<String, JsError> {
+    let result =
+        settings::parse_workflow(yaml_content).map_err(|e| JsError::new(&e.to_string()))?;
+
+    serde_json::to_string(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Parse workflow status and collect editor diagnostics.
+/// Returns `{ data, diagnostics }` as a JS value: YAML errors the parser
+/// recovered from, or schema warnings for a cleanly parsed document.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn parse_workflow_with_diagnostics_wasm(yaml_content: &str) -> Result<JsValue, JsError> {
+    let result = workflow_diagnostics(yaml_content);
 
     serde_wasm_bindgen::to_value(&result).map_err(|e| JsError::new(&e.to_string()))
 }
@@ -27,7 +121,29 @@ pub fn parse_workflow_status_wasm(yaml_content: &str) -> Result<JsValue, JsError
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub fn parse_sprint_status_wasm(yaml_content: &str) -> Result<JsValue, JsError> {
-    let result = parse_sprint_status(yaml_content).map_err(|e| JsError::new(&e.to_string()))?;
+    let result = settings::parse_sprint(yaml_content).map_err(|e| JsError::new(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Parse sprint status from YAML content.
+/// Returns SprintData serialized as a JSON string; see
+/// `parse_workflow_status_json_wasm`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn parse_sprint_status_json_wasm(yaml_content: &str) -> Result<String, JsError> {
+    let result = settings::parse_sprint(yaml_content).map_err(|e| JsError::new(&e.to_string()))?;
+
+    serde_json::to_string(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Parse sprint status and collect editor diagnostics.
+/// Returns `{ data, diagnostics }` as a JS value; `data` is null when the
+/// document could not be parsed.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn validate_sprint_wasm(yaml_content: &str) -> Result<JsValue, JsError> {
+    let result = sprint_diagnostics(yaml_content);
 
     serde_wasm_bindgen::to_value(&result).map_err(|e| JsError::new(&e.to_string()))
 }
@@ -44,6 +160,40 @@ pub fn update_workflow_status_wasm(
     update_workflow_status(content, item_id, new_status).map_err(|e| JsError::new(&e.to_string()))
 }
 
+/// Mark a workflow item complete, optionally recording its output file.
+/// Returns updated YAML content or error.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn complete_item_wasm(
+    content: &str,
+    item_id: &str,
+    output_file: Option<String>,
+) -> Result<String, JsError> {
+    complete_item(content, item_id, output_file.as_deref())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Reopen a completed workflow item.
+/// Returns updated YAML content or error.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn reopen_item_wasm(content: &str, item_id: &str) -> Result<String, JsError> {
+    reopen_item(content, item_id).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Check that workflow YAML survives a parse/serialize round trip.
+/// Returns null when lossless, otherwise the RoundtripReport as a JS value.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn roundtrip_check_wasm(content: &str) -> Result<JsValue, JsError> {
+    match roundtrip_check(content) {
+        Ok(()) => Ok(JsValue::NULL),
+        Err(report) => {
+            serde_wasm_bindgen::to_value(&report).map_err(|e| JsError::new(&e.to_string()))
+        }
+    }
+}
+
 /// Update story status in YAML content.
 /// Returns updated YAML content or error.
 #[cfg(target_arch = "wasm32")]
@@ -56,12 +206,179 @@ pub fn update_story_status_wasm(
     update_story_status(content, story_id, new_status).map_err(|e| JsError::new(&e.to_string()))
 }
 
+/// One `{ id, status }` entry of a batch status update.
+#[cfg(target_arch = "wasm32")]
+#[derive(Deserialize)]
+struct StatusUpdate {
+    id: String,
+    status: String,
+}
+
+/// Read an array of `{ id, status }` objects from JS.
+#[cfg(target_arch = "wasm32")]
+fn status_updates(updates_js: JsValue) -> Result<Vec<StatusUpdate>, JsError> {
+    serde_wasm_bindgen::from_value(updates_js).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Update several workflow item statuses in one call.
+/// Takes an array of `{ id, status }` objects; returns updated YAML content
+/// or the error of the first update that failed.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn update_workflow_statuses_wasm(
+    content: &str,
+    updates_js: JsValue,
+) -> Result<String, JsError> {
+    let updates = status_updates(updates_js)?;
+    let pairs: Vec<(&str, &str)> = updates
+        .iter()
+        .map(|u| (u.id.as_str(), u.status.as_str()))
+        .collect();
+    update_workflow_statuses(content, &pairs).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Update several story statuses in one call.
+/// Takes an array of `{ id, status }` objects; returns updated YAML content
+/// or the error of the first update that failed.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn update_story_statuses_wasm(content: &str, updates_js: JsValue) -> Result<String, JsError> {
+    let updates = status_updates(updates_js)?;
+    let pairs: Vec<(&str, &str)> = updates
+        .iter()
+        .map(|u| (u.id.as_str(), u.status.as_str()))
+        .collect();
+    update_story_statuses(content, &pairs).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Compare two versions of a workflow file item by item.
+/// Returns the list of ItemChange values as a JS value or error.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn diff_workflow_wasm(old_content: &str, new_content: &str) -> Result<JsValue, JsError> {
+    let old = settings::parse_workflow(old_content).map_err(|e| JsError::new(&e.to_string()))?;
+    let new = settings::parse_workflow(new_content).map_err(|e| JsError::new(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&diff_workflow(&old, &new))
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Three-way merge of sprint status files.
+/// Returns `{ content, conflicts }` as a JS value or error.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn merge_sprint_wasm(base: &str, ours: &str, theirs: &str) -> Result<JsValue, JsError> {
+    let result = merge_sprint(base, ours, theirs).map_err(|e| JsError::new(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Story counts for a sprint status file.
+/// Returns ProjectStats as a JS value or error.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn sprint_stats_wasm(content: &str) -> Result<JsValue, JsError> {
+    let data = settings::parse_sprint(content).map_err(|e| JsError::new(&e.to_string()))?;
+    let project = Project {
+        workflow: None,
+        sprint: Some(data),
+    };
+
+    serde_wasm_bindgen::to_value(&project.stats()).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// The parsed document held by a [`CliqueDocument`]
+#[cfg(target_arch = "wasm32")]
+enum OpenDocument {
+    Workflow(WorkflowDocument),
+    Sprint(SprintDocument),
+}
+
+/// A status file kept parsed between calls.
+/// Updates rewrite the text in place and re-read only the changed entry, so
+/// interactive editing does not re-parse the whole document on every change.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub struct CliqueDocument {
+    inner: OpenDocument,
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl CliqueDocument {
+    /// Open a workflow status file.
+    #[wasm_bindgen(js_name = fromWorkflow)]
+    pub fn from_workflow(content: &str) -> Result<CliqueDocument, JsError> {
+        let doc = WorkflowDocument::parse(content).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(CliqueDocument {
+            inner: OpenDocument::Workflow(doc),
+        })
+    }
+
+    /// Open a sprint status file.
+    #[wasm_bindgen(js_name = fromSprint)]
+    pub fn from_sprint(content: &str) -> Result<CliqueDocument, JsError> {
+        let doc = SprintDocument::parse(content).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(CliqueDocument {
+            inner: OpenDocument::Sprint(doc),
+        })
+    }
+
+    /// The parsed WorkflowData or SprintData as a JS value.
+    #[wasm_bindgen(js_name = getData)]
+    pub fn get_data(&self) -> Result<JsValue, JsError> {
+        match &self.inner {
+            OpenDocument::Workflow(doc) => serde_wasm_bindgen::to_value(doc.data()),
+            OpenDocument::Sprint(doc) => serde_wasm_bindgen::to_value(doc.data()),
+        }
+        .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Set an item's or story's status; the document is unchanged on error.
+    #[wasm_bindgen(js_name = updateStatus)]
+    pub fn update_status(&mut self, id: &str, status: &str) -> Result<(), JsError> {
+        match &mut self.inner {
+            OpenDocument::Workflow(doc) => doc
+                .update_status(id, status)
+                .map_err(|e| JsError::new(&e.to_string())),
+            OpenDocument::Sprint(doc) => doc
+                .update_status(id, status)
+                .map_err(|e| JsError::new(&e.to_string())),
+        }
+    }
+
+    /// The document's current YAML content.
+    pub fn serialize(&self) -> String {
+        match &self.inner {
+            OpenDocument::Workflow(doc) => doc.content().to_string(),
+            OpenDocument::Sprint(doc) => doc.content().to_string(),
+        }
+    }
+}
+
 /// Check if a file path is inside the workspace root.
 #[wasm_bindgen]
 pub fn is_inside_workspace_wasm(file_path: &str, workspace_root: &str) -> bool {
     is_inside_workspace(file_path, workspace_root)
 }
 
+/// Get the file path if it is inside the workspace root.
+/// Returns the path unchanged, or undefined when it is outside.
+#[wasm_bindgen]
+pub fn get_validated_path_wasm(file_path: &str, workspace_root: &str) -> Option<String> {
+    get_validated_path(file_path, workspace_root)
+}
+
+/// Validate that a file path is inside the workspace root.
+/// Returns the path, or an error explaining why it was refused.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn validate_path_wasm(file_path: &str, workspace_root: &str) -> Result<String, JsError> {
+    validate_path(file_path, workspace_root)
+        .map(|path| path.into_string())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,6 +481,179 @@ development_status:
         assert!(result.is_err());
     }
 
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_diagnostics_wasm() {
+        #[derive(serde::Deserialize)]
+        struct Diagnostic {
+            line: usize,
+            severity: String,
+        }
+        #[derive(serde::Deserialize)]
+        struct Diagnosed<T> {
+            data: T,
+            diagnostics: Vec<Diagnostic>,
+        }
+
+        let workflow = "workflows:\n  prd:\n    status: required\n    colour: red\n";
+        let result = parse_workflow_with_diagnostics_wasm(workflow).unwrap();
+        let result: Diagnosed<WorkflowData> = serde_wasm_bindgen::from_value(result).unwrap();
+        assert_eq!(result.data.items.len(), 1);
+        assert_eq!(result.diagnostics[0].line, 4);
+        assert_eq!(result.diagnostics[0].severity, "warning");
+
+        let sprint = "development_status:\n  epic-1: [oops\n";
+        let result = validate_sprint_wasm(sprint).unwrap();
+        let result: Diagnosed<Option<SprintData>> = serde_wasm_bindgen::from_value(result).unwrap();
+        assert!(result.data.is_none());
+        assert_eq!(result.diagnostics[0].severity, "error");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_diff_merge_stats_wasm() {
+        let old = "workflow_status:\n  prd: required\n";
+        let new = "workflow_status:\n  prd: docs/prd.md\n";
+        assert!(diff_workflow_wasm(old, new).unwrap().is_array());
+        assert!(diff_workflow_wasm(old, "[oops").is_err());
+
+        let base = "development_status:\n  epic-1: in-progress\n  1-a: backlog\n";
+        let theirs = "development_status:\n  epic-1: in-progress\n  1-a: done\n";
+        assert!(merge_sprint_wasm(base, base, theirs).unwrap().is_object());
+
+        assert!(sprint_stats_wasm(theirs).unwrap().is_object());
+        assert!(sprint_stats_wasm("[oops").is_err());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_clique_document() {
+        let mut doc = CliqueDocument::from_workflow("workflow_status:\n  prd: required\n").unwrap();
+        doc.update_status("prd", "skipped").unwrap();
+        assert!(doc.update_status("missing", "done").is_err());
+        assert_eq!(doc.serialize(), "workflow_status:\n  prd: skipped\n");
+        let data: WorkflowData = serde_wasm_bindgen::from_value(doc.get_data().unwrap()).unwrap();
+        assert_eq!(data.items[0].status, "skipped");
+
+        let mut doc =
+            CliqueDocument::from_sprint("development_status:\n  epic-1: backlog\n  1-a: backlog\n")
+                .unwrap();
+        doc.update_status("1-a", "done").unwrap();
+        let data: SprintData = serde_wasm_bindgen::from_value(doc.get_data().unwrap()).unwrap();
+        assert_eq!(data.epics[0].stories[0].status, "done");
+        assert!(CliqueDocument::from_sprint("[oops").is_err());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_parse_json_wasm() {
+        let workflow = "project: Test\nworkflow_status:\n  prd: required\n";
+        let json = parse_workflow_status_json_wasm(workflow).unwrap();
+        let data: WorkflowData = serde_json::from_str(&json).unwrap();
+        assert_eq!(data.project, "Test");
+        assert!(parse_workflow_status_json_wasm("[oops").is_err());
+
+        let sprint = "development_status:\n  epic-1: backlog\n  1-a: done\n";
+        let json = parse_sprint_status_json_wasm(sprint).unwrap();
+        let data: SprintData = serde_json::from_str(&json).unwrap();
+        assert_eq!(data.epics[0].stories[0].status, "done");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_init_wasm() {
+        init_wasm(JsValue::UNDEFINED).unwrap();
+        let yaml = "workflow_status:\n  prd: required\n";
+        let result = parse_workflow_status_json_wasm(yaml).unwrap();
+        assert!(result.contains("\"agent\":\"pm\""));
+
+        let options = serde_wasm_bindgen::to_value(&std::collections::HashMap::from([(
+            "agentMap",
+            std::collections::HashMap::from([("prd", "po")]),
+        )]))
+        .unwrap();
+        init_wasm(options).unwrap();
+        let result = parse_workflow_status_json_wasm(yaml).unwrap();
+        assert!(result.contains("\"agent\":\"po\""));
+        assert!(init_wasm(JsValue::from(3)).is_err());
+        init_wasm(JsValue::NULL).unwrap();
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_set_parser_config_wasm() {
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Config {
+            status_aliases: std::collections::HashMap<&'static str, &'static str>,
+            sort_order: &'static str,
+        }
+
+        let config = serde_wasm_bindgen::to_value(&Config {
+            status_aliases: std::collections::HashMap::from([("shipped", "done")]),
+            sort_order: "id",
+        })
+        .unwrap();
+        set_parser_config_wasm(config).unwrap();
+        let sprint = "development_status:\n  epic-1: backlog\n  1-a: shipped\n";
+        let json = parse_sprint_status_json_wasm(sprint).unwrap();
+        assert!(json.contains("\"status\":\"done\""));
+
+        assert!(set_parser_config_wasm(JsValue::from(3)).is_err());
+        set_parser_config_wasm(JsValue::UNDEFINED).unwrap();
+        let json = parse_sprint_status_json_wasm(sprint).unwrap();
+        assert!(json.contains("\"status\":\"shipped\""));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_wasm_size_wasm() {
+        let size: build_info::WasmSize =
+            serde_wasm_bindgen::from_value(wasm_size_wasm().unwrap()).unwrap();
+        assert_eq!(size, build_info::wasm_size());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_update_statuses_wasm() {
+        #[derive(serde::Serialize)]
+        struct Update<'a> {
+            id: &'a str,
+            status: &'a str,
+        }
+        let updates = |pairs: &[(&'static str, &'static str)]| {
+            let updates: Vec<Update> = pairs
+                .iter()
+                .map(|&(id, status)| Update { id, status })
+                .collect();
+            serde_wasm_bindgen::to_value(&updates).unwrap()
+        };
+
+        let workflow = "workflow_status:\n  prd: required\n  ux: optional\n";
+        let result =
+            update_workflow_statuses_wasm(workflow, updates(&[("prd", "done"), ("ux", "skipped")]));
+        assert_eq!(
+            result.unwrap(),
+            "workflow_status:\n  prd: done\n  ux: skipped\n"
+        );
+
+        let sprint = "development_status:\n  epic-1: in-progress\n  1-a: backlog\n";
+        let result = update_story_statuses_wasm(sprint, updates(&[("1-a", "done")]));
+        assert_eq!(
+            result.unwrap(),
+            "development_status:\n  epic-1: in-progress\n  1-a: done\n"
+        );
+        assert!(update_story_statuses_wasm(sprint, updates(&[("9-x", "done")])).is_err());
+        assert!(update_story_statuses_wasm(sprint, JsValue::from(3)).is_err());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn test_validate_path_wasm() {
+        assert_eq!(validate_path_wasm("/ws/a.md", "/ws").unwrap(), "/ws/a.md");
+        assert!(validate_path_wasm("/ws/../etc/passwd", "/ws").is_err());
+    }
+
     #[cfg(target_arch = "wasm32")]
     #[test]
     fn test_parse_workflow_status_wasm_error() {
@@ -229,6 +719,16 @@ development_status:
         assert!(!is_inside_workspace_wasm("/ws/../../../etc/passwd", "/ws"));
     }
 
+    #[test]
+    fn test_get_validated_path_wasm() {
+        assert_eq!(
+            get_validated_path_wasm("/ws/docs/file.md", "/ws").as_deref(),
+            Some("/ws/docs/file.md")
+        );
+        assert_eq!(get_validated_path_wasm("/ws/../etc/passwd", "/ws"), None);
+        assert_eq!(get_validated_path_wasm("", "/ws"), None);
+    }
+
     #[test]
     fn test_validation_wasm_similar_prefix() {
         // Paths with similar prefixes should not match
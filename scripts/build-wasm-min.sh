@@ -0,0 +1,27 @@
+#!/bin/bash
+# Build the size-optimized WASM module into dist/wasm-min.
+#
+# Uses the `min-size` profile (opt-level "z"), then shrinks the
+# result with wasm-opt -Oz when it is installed.
+# Requires: the wasm32-unknown-unknown target and wasm-bindgen-cli.
+set -euo pipefail
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+cd "$SCRIPT_DIR/../rust"
+
+OUT_DIR="../dist/wasm-min"
+
+echo "Build Rust/WASM (min-size)"
+
+cargo build -p clique-wasm --target wasm32-unknown-unknown --profile min-size
+wasm-bindgen target/wasm32-unknown-unknown/min-size/clique_wasm.wasm \
+    --target nodejs --out-dir "$OUT_DIR"
+
+if command -v wasm-opt >/dev/null 2>&1; then
+    wasm-opt -Oz --strip-debug --strip-producers \
+        "$OUT_DIR/clique_wasm_bg.wasm" -o "$OUT_DIR/clique_wasm_bg.wasm"
+else
+    echo "wasm-opt not found; skipping (install binaryen for the smallest build)"
+fi
+
+ls -l "$OUT_DIR/clique_wasm_bg.wasm"
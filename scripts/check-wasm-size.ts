@@ -0,0 +1,35 @@
+// scripts/check-wasm-size.ts
+/**
+ * WASM Size Budget Check
+ *
+ * Compares a built .wasm against the budget the module reports
+ * (`wasm_size_wasm()`), and fails when it is over.
+ * Run with: npx ts-node scripts/check-wasm-size.ts [dist/wasm-min]
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+
+interface WasmSize {
+    budget: number;
+}
+
+const outDir = path.resolve(__dirname, '..', process.argv[2] ?? 'dist/wasm');
+const wasmFile = path.join(outDir, 'clique_wasm_bg.wasm');
+
+if (!fs.existsSync(wasmFile)) {
+    console.error(`No WASM module at ${wasmFile}; build it first`);
+    process.exit(1);
+}
+
+// eslint-disable-next-line @typescript-eslint/no-var-requires
+const wasm = require(path.join(outDir, 'clique_wasm.js'));
+const size: WasmSize = wasm.wasm_size_wasm();
+const actual = fs.statSync(wasmFile).size;
+const kib = (bytes: number) => `${(bytes / 1024).toFixed(1)} KiB`;
+
+console.log(`${path.basename(outDir)}: ${kib(actual)} of ${kib(size.budget)} budget`);
+if (actual > size.budget) {
+    console.error(`Over budget by ${kib(actual - size.budget)}`);
+    process.exit(1);
+}